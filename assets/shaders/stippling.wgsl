@@ -4,15 +4,14 @@ struct StipplingMaterial {
     color: vec4<f32>,
     // Spacing between dots in world units
     dot_spacing: f32,
+    // Map width in world units (tiles * 64), for world-to-UV conversion
+    map_size_world: f32,
 }
 
 @group(2) @binding(0) var<uniform> material: StipplingMaterial;
 @group(2) @binding(1) var depth_texture: texture_2d<f32>;
 @group(2) @binding(2) var depth_sampler: sampler;
 
-// Map dimensions for UV offset calculation (512 tiles * 64 units = 32768 world units)
-const MAP_SIZE_WORLD: f32 = 32768.0;
-
 // Hash for randomizing dot positions
 fn hash21(p: vec2<f32>) -> f32 {
     var p3 = fract(vec3<f32>(p.xyx) * 0.1031);
@@ -55,11 +54,11 @@ fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
     // Convert ring edge offset from world units to UV units
     // outer_radius is in cell units, dot_spacing converts to world, then to UV
     let edge_world = outer_radius * material.dot_spacing;
-    let edge_uv = edge_world / MAP_SIZE_WORLD;
+    let edge_uv = edge_world / material.map_size_world;
     
     // Calculate ring center in UV space
     let ring_center_world_offset = ring_center_offset * material.dot_spacing;
-    let ring_center_uv_offset = ring_center_world_offset / MAP_SIZE_WORLD;
+    let ring_center_uv_offset = ring_center_world_offset / material.map_size_world;
     let ring_center_uv = mesh.uv + vec2<f32>(ring_center_uv_offset.x, -ring_center_uv_offset.y);
     
     // Sample density at 4 cardinal points on ring edge
@@ -1,9 +1,20 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::components::combat::AmmoType;
+
+/// Specialty shot a ship puts to sea with. Round shot is never short.
+pub const STARTING_AMMO: [(AmmoType, u32); 4] = [
+    (AmmoType::ChainShot, 12),
+    (AmmoType::BarShot, 9),
+    (AmmoType::GrapeShot, 9),
+    (AmmoType::HeatedShot, 6),
+];
+
 /// Types of goods that can be traded in the game.
 /// Each good has different economic properties (see `GoodsTrait`).
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect, Serialize, Deserialize)]
 pub enum GoodType {
     #[default]
     Rum,
@@ -12,6 +23,8 @@ pub enum GoodType {
     Timber,
     Cloth,
     Weapons,
+    Fish,
+    Fruit,
     // Future goods can be added here
 }
 
@@ -27,6 +40,17 @@ pub enum GoodsTrait {
 }
 
 impl GoodType {
+    pub const ALL: [GoodType; 8] = [
+        GoodType::Rum,
+        GoodType::Sugar,
+        GoodType::Spices,
+        GoodType::Timber,
+        GoodType::Cloth,
+        GoodType::Weapons,
+        GoodType::Fish,
+        GoodType::Fruit,
+    ];
+
     /// Returns the traits associated with this good type.
     pub fn traits(&self) -> Vec<GoodsTrait> {
         match self {
@@ -36,6 +60,18 @@ impl GoodType {
             GoodType::Timber => vec![GoodsTrait::Heavy],
             GoodType::Cloth => vec![],
             GoodType::Weapons => vec![GoodsTrait::Illegal, GoodsTrait::Heavy],
+            GoodType::Fish => vec![GoodsTrait::Perishable],
+            GoodType::Fruit => vec![GoodsTrait::Perishable],
+        }
+    }
+
+    /// Share of a ship's stock that rots each day at sea without an icehouse.
+    /// Rum and sugar keep in the hold; only fresh food spoils.
+    pub fn spoilage_per_day(&self) -> f32 {
+        match self {
+            GoodType::Fish => 0.25,
+            GoodType::Fruit => 0.15,
+            _ => 0.0,
         }
     }
 }
@@ -49,6 +85,10 @@ pub struct Cargo {
     pub goods: HashMap<GoodType, u32>,
     /// Maximum cargo capacity (total units all goods combined).
     pub capacity: u32,
+    /// Specialty shot in the magazine, in rounds. Kept apart from the
+    /// goods, so it does not count against capacity.
+    #[reflect(default)]
+    pub ammo: HashMap<AmmoType, u32>,
 }
 
 impl Cargo {
@@ -57,7 +97,17 @@ impl Cargo {
         Self {
             goods: HashMap::new(),
             capacity,
+            ammo: HashMap::new(),
+        }
+    }
+
+    /// A hold of the given capacity with the magazine stocked with `STARTING_AMMO`.
+    pub fn armed(capacity: u32) -> Self {
+        let mut cargo = Self::new(capacity);
+        for (ammo, rounds) in STARTING_AMMO {
+            cargo.stow_ammo(ammo, rounds);
         }
+        cargo
     }
 
     /// Returns the total number of units currently in the cargo hold.
@@ -107,6 +157,64 @@ impl Cargo {
     pub fn get(&self, good: GoodType) -> u32 {
         *self.goods.get(&good).unwrap_or(&0)
     }
+
+    /// Moves up to `amount` of a good into another hold, limited by what is
+    /// aboard and the other hold's free space. Returns how many were moved.
+    pub fn transfer_to(&mut self, other: &mut Cargo, good: GoodType, amount: u32) -> u32 {
+        let to_move = amount.min(self.get(good)).min(other.available_capacity());
+        self.remove(good, to_move);
+        other.add(good, to_move)
+    }
+
+    /// Lets `days` of spoilage rot the perishable goods aboard. Every
+    /// spoiling stock loses at least a unit a day. Returns what was lost.
+    pub fn spoil(&mut self, days: u32) -> Vec<(GoodType, u32)> {
+        let mut lost: Vec<(GoodType, u32)> = self
+            .goods
+            .iter()
+            .filter(|(good, _)| good.spoilage_per_day() > 0.0)
+            .map(|(good, &quantity)| {
+                let rotted = (quantity as f32 * good.spoilage_per_day() * days as f32).ceil() as u32;
+                (*good, rotted.min(quantity))
+            })
+            .filter(|(_, rotted)| *rotted > 0)
+            .collect();
+        lost.sort_by_key(|(good, _)| format!("{:?}", good));
+        for (good, rotted) in &lost {
+            self.remove(*good, *rotted);
+        }
+        lost
+    }
+
+    /// Rounds of `ammo` in the magazine; `None` if it never runs short.
+    pub fn ammo_count(&self, ammo: AmmoType) -> Option<u32> {
+        ammo.is_limited().then(|| *self.ammo.get(&ammo).unwrap_or(&0))
+    }
+
+    /// Adds rounds of `ammo` to the magazine.
+    pub fn stow_ammo(&mut self, ammo: AmmoType, rounds: u32) {
+        if ammo.is_limited() && rounds > 0 {
+            *self.ammo.entry(ammo).or_insert(0) += rounds;
+        }
+    }
+
+    /// Takes `rounds` of `ammo` for a broadside. Returns false, taking
+    /// nothing, if the magazine holds fewer.
+    pub fn take_ammo(&mut self, ammo: AmmoType, rounds: u32) -> bool {
+        if !ammo.is_limited() {
+            return true;
+        }
+        match self.ammo.get_mut(&ammo) {
+            Some(current) if *current >= rounds => {
+                *current -= rounds;
+                if *current == 0 {
+                    self.ammo.remove(&ammo);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Default for Cargo {
@@ -136,3 +244,64 @@ impl Gold {
         self.0 = self.0.saturating_add(amount);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_magazine_runs_short_but_round_shot_never_does() {
+        let mut cargo = Cargo::new(10);
+        assert_eq!(cargo.ammo_count(AmmoType::RoundShot), None);
+        assert!(cargo.take_ammo(AmmoType::RoundShot, 3));
+
+        cargo.stow_ammo(AmmoType::GrapeShot, 4);
+        assert!(cargo.take_ammo(AmmoType::GrapeShot, 3));
+        assert!(!cargo.take_ammo(AmmoType::GrapeShot, 3));
+        assert_eq!(cargo.ammo_count(AmmoType::GrapeShot), Some(1));
+        // Shot doesn't take up hold space
+        assert_eq!(cargo.available_capacity(), 10);
+    }
+
+    #[test]
+    fn test_hold_saved_before_the_magazine_loads_without_shot() {
+        use bevy::reflect::serde::TypedReflectDeserializer;
+        use bevy::reflect::{FromReflect, TypeRegistry};
+        use serde::de::DeserializeSeed;
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Cargo>();
+        let registration = registry.get(std::any::TypeId::of::<Cargo>()).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str("(goods: {}, capacity: 50)").unwrap();
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let cargo = Cargo::from_reflect(value.as_ref()).unwrap();
+        assert_eq!(cargo.capacity, 50);
+        assert!(cargo.ammo.is_empty());
+    }
+
+    #[test]
+    fn test_fresh_food_spoils_but_rum_keeps() {
+        let mut cargo = Cargo::new(100);
+        cargo.add(GoodType::Fish, 40);
+        cargo.add(GoodType::Fruit, 2);
+        cargo.add(GoodType::Rum, 20);
+        let lost = cargo.spoil(1);
+        assert_eq!(lost, vec![(GoodType::Fish, 10), (GoodType::Fruit, 1)]);
+        assert_eq!(cargo.get(GoodType::Fish), 30);
+        assert_eq!(cargo.get(GoodType::Rum), 20);
+        // A long voyage rots the lot
+        cargo.spoil(10);
+        assert_eq!(cargo.get(GoodType::Fish), 0);
+        assert_eq!(cargo.get(GoodType::Fruit), 0);
+    }
+
+    #[test]
+    fn test_armed_hold_carries_starting_ammo() {
+        let cargo = Cargo::armed(100);
+        for (ammo, rounds) in STARTING_AMMO {
+            assert_eq!(cargo.ammo_count(ammo), Some(rounds));
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+/// Something floating on the High Seas that the player picks up by sailing close.
+///
+/// Pickup sends a `CollectiblePickedUpEvent`; the item's own components
+/// (e.g. `Bottle`) decide what it does.
+#[derive(Component, Debug, Clone)]
+pub struct Collectible {
+    /// Distance from the player's ship at which the item is picked up.
+    pub pickup_radius: f32,
+}
+
+impl Default for Collectible {
+    fn default() -> Self {
+        Self { pickup_radius: 40.0 }
+    }
+}
+
+/// What a message in a bottle turns out to hold.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BottleMessage {
+    /// A scrap of chart revealing the tiles within `radius` of `center`.
+    MapFragment { center: IVec2, radius: i32 },
+    /// A page of lore, unlocking the codex entry with this id.
+    Lore(String),
+    /// Directions to buried treasure at a tile.
+    TreasureHint(IVec2),
+}
+
+/// A message in a bottle, drifting on the current.
+#[derive(Component, Debug, Clone)]
+pub struct Bottle {
+    pub message: BottleMessage,
+    /// Drift in world units per second.
+    pub drift: Vec2,
+}
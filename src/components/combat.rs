@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::ship::ShipType;
 
 /// Enum representing targetable ship components.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
@@ -14,6 +17,137 @@ impl Default for TargetComponent {
     }
 }
 
+/// Shot a broadside can be loaded with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
+pub enum AmmoType {
+    /// Solid iron balls for holing the hull. Never runs short.
+    #[default]
+    RoundShot,
+    /// Two balls chained together, to tear through rigging.
+    ChainShot,
+    /// A split bar, to smash the steering gear.
+    BarShot,
+    /// Bags of musket balls that sweep the decks of hands.
+    GrapeShot,
+    /// Shot heated red in the galley fire, to set the enemy alight.
+    HeatedShot,
+}
+
+/// What a round of each shot does on a hit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmmoProfile {
+    /// Part of the ship the shot damages.
+    pub target: TargetComponent,
+    /// Damage to that part.
+    pub damage: f32,
+    /// Hands killed or wounded.
+    pub crew_casualties: u32,
+    /// Chance the hit starts a fire, 0.0 to 1.0.
+    pub fire_chance: f32,
+}
+
+impl AmmoType {
+    pub const ALL: [AmmoType; 5] = [
+        AmmoType::RoundShot,
+        AmmoType::ChainShot,
+        AmmoType::BarShot,
+        AmmoType::GrapeShot,
+        AmmoType::HeatedShot,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RoundShot => "Round shot",
+            Self::ChainShot => "Chain shot",
+            Self::BarShot => "Bar shot",
+            Self::GrapeShot => "Grape shot",
+            Self::HeatedShot => "Heated shot",
+        }
+    }
+
+    /// The next ammunition in the loading rotation.
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|ammo| ammo == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Whether the magazine can run out of this shot (round shot is ballast and never does).
+    pub fn is_limited(&self) -> bool {
+        *self != Self::RoundShot
+    }
+
+    pub fn profile(&self) -> AmmoProfile {
+        let (target, damage, crew_casualties, fire_chance) = match self {
+            Self::RoundShot => (TargetComponent::Hull, 10.0, 0, 0.0),
+            Self::ChainShot => (TargetComponent::Sails, 10.0, 0, 0.0),
+            Self::BarShot => (TargetComponent::Rudder, 10.0, 0, 0.0),
+            Self::GrapeShot => (TargetComponent::Hull, 3.0, 1, 0.0),
+            Self::HeatedShot => (TargetComponent::Hull, 7.0, 0, 0.35),
+        };
+        AmmoProfile { target, damage, crew_casualties, fire_chance }
+    }
+}
+
+/// Seconds two ships stay fouled together after a ram.
+pub const ENTANGLE_SECONDS: f32 = 4.0;
+
+/// A ship fouled with another after a ram. The two drift as one until they
+/// work free, and may be boarded however fast they are going.
+#[derive(Component, Debug, Clone)]
+pub struct Entangled {
+    /// The ship she is fouled with.
+    pub with: Entity,
+    /// Time until the two work free.
+    pub timer: Timer,
+}
+
+impl Entangled {
+    pub fn new(with: Entity) -> Self {
+        Self { with, timer: Timer::from_seconds(ENTANGLE_SECONDS, TimerMode::Once) }
+    }
+}
+
+/// Land in a battle arena: a stretch of the coast the battle is fought off.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Coast;
+
+/// A ship run aground. She is held fast, though she may swing, until the
+/// sea lifts her off.
+#[derive(Component, Debug, Clone)]
+pub struct Aground {
+    /// Time until she floats free.
+    pub timer: Timer,
+}
+
+impl Aground {
+    pub fn new(seconds: f32) -> Self {
+        Self { timer: Timer::from_seconds(seconds, TimerMode::Once) }
+    }
+}
+
+/// Something in a battle arena that ships cannot see through, taken as a
+/// circle (see `utils::line_of_sight`).
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BlocksSight {
+    pub radius: f32,
+}
+
+/// A cloud of gun smoke left by a broadside, drifting downwind until it clears.
+#[derive(Component, Debug, Clone)]
+pub struct GunSmoke {
+    /// Time until it has cleared.
+    pub timer: Timer,
+}
+
+/// What an AI ship can see of her foes.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Lookout {
+    /// Where the foe she is after is, or was when last seen.
+    pub foe: Option<Vec2>,
+    /// Whether that foe is in sight now.
+    pub in_sight: bool,
+}
+
 /// Component applied to cannonball projectiles.
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
@@ -21,4 +155,305 @@ pub struct Projectile {
     pub damage: f32,
     pub target: TargetComponent,
     pub source: Entity,
+    /// Shot the cannon was loaded with.
+    pub ammo: AmmoType,
+}
+
+impl Projectile {
+    /// A round of `ammo` fired by `source`.
+    pub fn new(ammo: AmmoType, source: Entity) -> Self {
+        let profile = ammo.profile();
+        Self {
+            damage: profile.damage,
+            target: profile.target,
+            source,
+            ammo,
+        }
+    }
+
+    /// The same round, hitting `multiplier` times as hard.
+    pub fn scaled(mut self, multiplier: f32) -> Self {
+        self.damage *= multiplier;
+        self
+    }
+}
+
+/// Height of a gun port above the water (world units).
+pub const MUZZLE_HEIGHT: f32 = 3.0;
+/// Upward speed of a round shot leaving the gun: broadsides are laid with a
+/// little elevation, so shot climbs, then drops into the sea after about a second.
+pub const MUZZLE_CLIMB: f32 = 24.0;
+/// Pull of gravity on a shot's height (world units per second squared).
+pub const SHOT_GRAVITY: f32 = 48.0;
+/// Height at which a shot's sprite is drawn twice its size.
+pub const SHOT_SCALE_HEIGHT: f32 = 80.0;
+
+/// Height of a shot above the water. A shot only strikes a ship whose side
+/// stands higher than the shot (see `Freeboard`): close in, shot is low and
+/// hits anything; further out it flies over low hulls such as rafts.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ShotHeight {
+    pub height: f32,
+    /// Upward speed (world units per second).
+    pub climb: f32,
+}
+
+impl Default for ShotHeight {
+    /// Round shot just out of the gun.
+    fn default() -> Self {
+        Self { height: MUZZLE_HEIGHT, climb: MUZZLE_CLIMB }
+    }
+}
+
+impl ShotHeight {
+    /// Flies the shot on for `delta` seconds.
+    pub fn step(&mut self, delta: f32) {
+        self.climb -= SHOT_GRAVITY * delta;
+        self.height += self.climb * delta;
+    }
+
+    pub fn has_splashed(&self) -> bool {
+        self.height <= 0.0
+    }
+
+    /// Whether the shot flies over a side of this freeboard.
+    pub fn clears(&self, freeboard: Freeboard) -> bool {
+        self.height > freeboard.0
+    }
+
+    /// Sprite scale for a shot at this height, as seen from above.
+    pub fn sprite_scale(&self) -> f32 {
+        1.0 + self.height.max(0.0) / SHOT_SCALE_HEIGHT
+    }
+}
+
+/// How high a ship's side stands above the water (world units).
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Freeboard(pub f32);
+
+impl Default for Freeboard {
+    fn default() -> Self {
+        Self::for_ship_type(ShipType::Sloop)
+    }
+}
+
+impl Freeboard {
+    pub fn for_ship_type(ship_type: ShipType) -> Self {
+        Self(match ship_type {
+            ShipType::Raft => 5.0,
+            ShipType::Sloop => 11.0,
+            ShipType::Schooner => 12.0,
+            ShipType::Frigate => 15.0,
+        })
+    }
+}
+
+/// How far a ship is heeled over in battle (radians): positive with her
+/// starboard side down, negative to port.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Heel(pub f32);
+
+/// The shadow a shot casts on the water, a child of the shot.
+#[derive(Component, Debug, Default)]
+pub struct ShotShadow;
+
+/// Half-width of the arc a chaser gun can train through, either side of dead
+/// ahead (bow chaser) or dead astern (stern chaser), in radians.
+pub const CHASER_ARC: f32 = 0.44;
+/// Seconds to reload the chasers: long guns, slow to serve.
+pub const CHASER_RELOAD_SECONDS: f32 = 6.0;
+/// Furthest a chaser can reach, further than a broadside.
+pub const CHASER_RANGE: f32 = 380.0;
+/// Seconds to reload a mortar.
+pub const MORTAR_RELOAD_SECONDS: f32 = 10.0;
+/// Closest a mortar can drop a shell; nearer than this it would land on deck.
+pub const MORTAR_MIN_RANGE: f32 = 150.0;
+/// Furthest a mortar can throw a shell.
+pub const MORTAR_MAX_RANGE: f32 = 600.0;
+/// Hull damage to every ship and fort within `MORTAR_SPLASH_RADIUS` of a shell's fall.
+pub const MORTAR_DAMAGE: f32 = 25.0;
+pub const MORTAR_SPLASH_RADIUS: f32 = 45.0;
+/// Chance a shell bursting on a ship's deck sets her afire.
+pub const MORTAR_FIRE_CHANCE: f32 = 0.3;
+/// Highest point of a shell's arc at its longest range (world units).
+pub const MORTAR_PEAK_HEIGHT: f32 = 160.0;
+/// Share of `MORTAR_PEAK_HEIGHT` the shortest throws still reach.
+pub const MORTAR_MIN_PEAK_SHARE: f32 = 0.3;
+/// Seconds a shell spends climbing and falling, however short the throw.
+pub const MORTAR_BASE_FLIGHT_SECONDS: f32 = 1.2;
+/// Speed a shell covers ground at (world units per second).
+pub const MORTAR_SHELL_GROUND_SPEED: f32 = 400.0;
+
+/// The end of a ship a chaser gun is mounted at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaserMount {
+    Bow,
+    Stern,
+}
+
+impl ChaserMount {
+    /// The chaser that bears on `to_target` for a ship heading along
+    /// `forward`, if either does.
+    pub fn bearing(forward: Vec2, to_target: Vec2) -> Option<Self> {
+        let angle = forward.angle_to(to_target).abs();
+        if angle <= CHASER_ARC {
+            Some(Self::Bow)
+        } else if angle >= std::f32::consts::PI - CHASER_ARC {
+            Some(Self::Stern)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Bow => "Bow chaser",
+            Self::Stern => "Stern chaser",
+        }
+    }
+}
+
+/// A ship's bow and stern chasers: a long gun at each end, firing fore and
+/// aft where the broadside cannot. They share one slow reload.
+#[derive(Component, Debug, Clone)]
+pub struct Chasers {
+    pub reload: Timer,
+}
+
+impl Default for Chasers {
+    /// Loaded and ready.
+    fn default() -> Self {
+        Self { reload: loaded_timer(CHASER_RELOAD_SECONDS) }
+    }
+}
+
+/// A mortar fitted to the flagship (the `Mortar` upgrade), lobbing shells
+/// in a high arc over anything between it and where they fall.
+#[derive(Component, Debug, Clone)]
+pub struct Mortar {
+    pub reload: Timer,
+}
+
+impl Default for Mortar {
+    /// Loaded and ready.
+    fn default() -> Self {
+        Self { reload: loaded_timer(MORTAR_RELOAD_SECONDS) }
+    }
+}
+
+fn loaded_timer(seconds: f32) -> Timer {
+    let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+    timer.tick(timer.duration());
+    timer
+}
+
+/// A mortar shell in flight. Shells have no collider: they fly over ships
+/// and strike only where they fall. Their `ShotHeight` follows the arc.
+#[derive(Component, Debug, Clone)]
+pub struct MortarShell {
+    pub source: Entity,
+    pub from: Vec2,
+    pub to: Vec2,
+    /// Seconds since the shell was fired.
+    pub elapsed: f32,
+    /// Seconds from firing to the fall.
+    pub flight_time: f32,
+}
+
+impl MortarShell {
+    /// A shell fired by `source` from `from`, to fall at `to`.
+    pub fn new(source: Entity, from: Vec2, to: Vec2) -> Self {
+        let flight_time = MORTAR_BASE_FLIGHT_SECONDS + from.distance(to) / MORTAR_SHELL_GROUND_SPEED;
+        Self { source, from, to, elapsed: 0.0, flight_time }
+    }
+
+    /// How far along its flight the shell is, 0.0 to 1.0.
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / self.flight_time).clamp(0.0, 1.0)
+    }
+
+    /// Where the shell is over the water.
+    pub fn ground_position(&self) -> Vec2 {
+        self.from.lerp(self.to, self.progress())
+    }
+
+    /// Height above the water: a parabola peaking halfway, higher for longer throws.
+    pub fn height(&self) -> f32 {
+        let t = self.progress();
+        let peak = MORTAR_PEAK_HEIGHT * (self.from.distance(self.to) / MORTAR_MAX_RANGE).clamp(MORTAR_MIN_PEAK_SHARE, 1.0);
+        4.0 * peak * t * (1.0 - t)
+    }
+
+    pub fn has_fallen(&self) -> bool {
+        self.elapsed >= self.flight_time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ammo_rotation_covers_every_shot() {
+        let mut ammo = AmmoType::RoundShot;
+        for _ in 0..AmmoType::ALL.len() {
+            ammo = ammo.next();
+        }
+        assert_eq!(ammo, AmmoType::RoundShot);
+        assert_eq!(AmmoType::RoundShot.next(), AmmoType::ChainShot);
+    }
+
+    #[test]
+    fn test_ammo_profiles() {
+        assert_eq!(AmmoType::ChainShot.profile().target, TargetComponent::Sails);
+        assert_eq!(AmmoType::BarShot.profile().target, TargetComponent::Rudder);
+        assert!(AmmoType::GrapeShot.profile().crew_casualties > 0);
+        assert!(AmmoType::GrapeShot.profile().damage < AmmoType::RoundShot.profile().damage);
+        assert!(AmmoType::HeatedShot.profile().fire_chance > 0.0);
+        assert_eq!(AmmoType::RoundShot.profile().fire_chance, 0.0);
+        assert!(!AmmoType::RoundShot.is_limited());
+        assert!(AmmoType::HeatedShot.is_limited());
+    }
+
+    #[test]
+    fn test_shot_flies_over_rafts_at_range() {
+        let raft = Freeboard::for_ship_type(ShipType::Raft);
+        let mut shot = ShotHeight::default();
+        shot.step(0.05);
+        assert!(!shot.clears(raft), "point-blank shot is low enough to hit a raft");
+
+        let mut peak: f32 = 0.0;
+        let mut flight = 0.05;
+        while !shot.has_splashed() {
+            shot.step(0.05);
+            flight += 0.05;
+            peak = peak.max(shot.height);
+            // A broadside never flies over a proper ship's side
+            assert!(!shot.clears(Freeboard::default()));
+        }
+        assert!(peak > raft.0);
+        assert!((0.9..1.4).contains(&flight), "shot splashed after {flight}s");
+    }
+
+    #[test]
+    fn test_chaser_bearing() {
+        let forward = Vec2::Y;
+        assert_eq!(ChaserMount::bearing(forward, Vec2::new(0.2, 1.0)), Some(ChaserMount::Bow));
+        assert_eq!(ChaserMount::bearing(forward, Vec2::new(-0.2, -1.0)), Some(ChaserMount::Stern));
+        // Abeam is broadside work
+        assert_eq!(ChaserMount::bearing(forward, Vec2::X), None);
+        assert_eq!(ChaserMount::bearing(forward, Vec2::new(1.0, 1.0)), None);
+    }
+
+    #[test]
+    fn test_mortar_shell_arcs_to_its_mark() {
+        let mut shell = MortarShell::new(Entity::PLACEHOLDER, Vec2::ZERO, Vec2::new(MORTAR_MAX_RANGE, 0.0));
+        assert_eq!(shell.height(), 0.0);
+        shell.elapsed = shell.flight_time / 2.0;
+        assert!((shell.height() - MORTAR_PEAK_HEIGHT).abs() < 0.01);
+        assert_eq!(shell.ground_position(), Vec2::new(MORTAR_MAX_RANGE / 2.0, 0.0));
+        shell.elapsed = shell.flight_time;
+        assert!(shell.has_fallen());
+        assert!(shell.height().abs() < 0.01);
+    }
 }
@@ -1,11 +1,12 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Marker component for a Companion entity.
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct Companion;
 
 /// Role of a companion, determining their special ability.
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
 pub enum CompanionRole {
     /// Quartermaster: Auto-trades based on market intel.
     Quartermaster,
@@ -30,6 +31,17 @@ impl CompanionRole {
         }
     }
 
+    /// What a stranger in this role asks to sign on, before haggling.
+    pub fn base_hire_cost(&self) -> u32 {
+        match self {
+            CompanionRole::Quartermaster => 600,
+            CompanionRole::Navigator => 500,
+            CompanionRole::Lookout => 400,
+            CompanionRole::Gunner => 550,
+            CompanionRole::Mystic => 1000,
+        }
+    }
+
     pub fn description(&self) -> &'static str {
         match self {
             CompanionRole::Quartermaster => "Auto-trades heavily for profit.",
@@ -48,3 +60,87 @@ pub struct CompanionName(pub String);
 /// Links a companion to a specific ship entity.
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AssignedTo(pub Entity);
+
+/// Former companions ask this share of a stranger's price: they know the ship and skip the haggling.
+pub const REHIRE_COST_MULTIPLIER: f32 = 0.6;
+/// Regard lost each time a companion is dismissed.
+pub const DISMISSAL_GRUDGE: i32 = 20;
+/// Regard gained per day of service, up to `MAX_SERVICE_LOYALTY` per stint.
+pub const LOYALTY_PER_DAY: i32 = 2;
+pub const MAX_SERVICE_LOYALTY: i32 = 30;
+/// A former companion with this little regard refuses to sail with the player again.
+pub const REFUSAL_REGARD: i32 = -40;
+
+/// What a companion remembers of serving under the player.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub struct CompanionMemory {
+    /// Above zero they are loyal; below, they hold a grudge.
+    pub regard: i32,
+    pub times_dismissed: u32,
+}
+
+impl CompanionMemory {
+    /// Memory after being let go following `days_served` days aboard.
+    pub fn after_dismissal(self, days_served: u32) -> Self {
+        let loyalty = (days_served as i32 * LOYALTY_PER_DAY).min(MAX_SERVICE_LOYALTY);
+        Self {
+            regard: self.regard + loyalty - DISMISSAL_GRUDGE,
+            times_dismissed: self.times_dismissed + 1,
+        }
+    }
+
+    /// Asking price to rehire: below a stranger's, more with a grudge and less for a loyal hand.
+    pub fn rehire_cost(&self, role: CompanionRole) -> u32 {
+        let mood = (1.0 - self.regard as f32 / 100.0).clamp(0.5, 1.5);
+        (role.base_hire_cost() as f32 * REHIRE_COST_MULTIPLIER * mood) as u32
+    }
+
+    pub fn refuses_rehire(&self) -> bool {
+        self.regard <= REFUSAL_REGARD
+    }
+
+    pub fn mood(&self) -> &'static str {
+        match self.regard {
+            r if r <= REFUSAL_REGARD => "Won't sail with you again",
+            r if r < 0 => "Holds a grudge",
+            0 => "Indifferent",
+            _ => "Loyal",
+        }
+    }
+}
+
+/// A companion's time aboard, carried into the tavern if they are dismissed.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+pub struct CompanionService {
+    /// Day they signed on (`WorldClock::day`).
+    pub hired_on: u32,
+    /// What they remember of earlier stints, if they sailed with the player before.
+    pub memory: CompanionMemory,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quick_dismissal_breeds_a_grudge() {
+        let memory = CompanionMemory::default().after_dismissal(1);
+        assert_eq!(memory.regard, LOYALTY_PER_DAY - DISMISSAL_GRUDGE);
+        assert_eq!(memory.mood(), "Holds a grudge");
+        // Still cheaper than a stranger, but dearer than a friend
+        let stranger = CompanionRole::Gunner.base_hire_cost();
+        assert!(memory.rehire_cost(CompanionRole::Gunner) < stranger);
+        assert!(memory.rehire_cost(CompanionRole::Gunner) > CompanionMemory::default().rehire_cost(CompanionRole::Gunner));
+
+        let spurned = memory.after_dismissal(0).after_dismissal(0);
+        assert!(spurned.refuses_rehire());
+    }
+
+    #[test]
+    fn test_long_service_earns_loyalty() {
+        let memory = CompanionMemory::default().after_dismissal(30);
+        assert_eq!(memory.regard, MAX_SERVICE_LOYALTY - DISMISSAL_GRUDGE);
+        assert_eq!(memory.mood(), "Loyal");
+        assert_eq!(memory.times_dismissed, 1);
+    }
+}
@@ -1,13 +1,15 @@
 use bevy::prelude::*;
 
 use super::cargo::GoodType;
+use super::ship::FactionId;
+use crate::resources::TICKS_PER_HOUR;
 
 /// Marker component identifying an entity as a contract.
 #[derive(Component, Debug, Default)]
 pub struct Contract;
 
 /// Types of contracts available in the game.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Reflect)]
 pub enum ContractType {
     /// Deliver specific goods from origin port to destination port.
     #[default]
@@ -18,6 +20,10 @@ pub enum ContractType {
     Escort,
     /// Hunt down and destroy a specific enemy ship.
     Hunt,
+    /// Patrol an area and intercept smugglers.
+    Patrol,
+    /// Sink a rival nation's ships under a letter of marque.
+    Privateer,
 }
 
 impl ContractType {
@@ -28,8 +34,16 @@ impl ContractType {
             ContractType::Explore => "Explore area",
             ContractType::Escort => "Escort ship",
             ContractType::Hunt => "Hunt target",
+            ContractType::Patrol => "Patrol waters",
+            ContractType::Privateer => "Letter of marque",
         }
     }
+
+    /// Whether running out of time counts as failure. A letter of marque
+    /// simply lapses; prizes already taken have been paid for.
+    pub fn fails_on_expiry(&self) -> bool {
+        *self != ContractType::Privateer
+    }
 }
 
 /// Details of a specific contract.
@@ -49,11 +63,19 @@ pub struct ContractDetails {
     pub description: String,
     /// World tick at which this contract expires (None = never expires).
     pub expiry_tick: Option<u32>,
+    /// Faction whose port offered the contract, which takes offense if it fails.
+    pub issuer: Option<FactionId>,
 }
 
 impl ContractDetails {
     /// Default contract duration in ticks (2 in-game days = 2 * 24 * 60 ticks).
     pub const DEFAULT_DURATION_TICKS: u32 = 2 * 24 * 60;
+    /// Reputation lost with the issuer when an accepted contract runs out of time.
+    pub const FAILURE_REPUTATION_PENALTY: i32 = 10;
+    /// Fraction of the reward forfeited when an accepted contract runs out of time.
+    pub const FAILURE_GOLD_FRACTION: f32 = 0.25;
+    /// Below this many ticks remaining a contract is flagged as urgent (12 hours).
+    pub const URGENT_TICKS: u32 = 12 * TICKS_PER_HOUR;
 
     /// Creates a new Transport contract.
     pub fn transport(
@@ -71,6 +93,7 @@ impl ContractDetails {
             cargo_required: Some((good, quantity)),
             description: format!("Deliver {} {:?} to destination", quantity, good),
             expiry_tick: None, // Set by system when created with WorldClock
+            issuer: None,
         }
     }
 
@@ -98,6 +121,7 @@ impl ContractDetails {
             cargo_required: None,
             description: "Visit the marked location".to_string(),
             expiry_tick: None, // Set by system when created with WorldClock
+            issuer: None,
         }
     }
 
@@ -113,6 +137,58 @@ impl ContractDetails {
         contract
     }
 
+    /// Creates a new Patrol contract for intercepting `required` smugglers.
+    /// Its area is the `PatrolArea` component spawned alongside.
+    pub fn patrol(origin: Entity, port_name: &str, required: u32, reward: u32, current_tick: u32) -> Self {
+        Self {
+            contract_type: ContractType::Patrol,
+            origin_port: origin,
+            destination: None,
+            reward_gold: reward,
+            cargo_required: None,
+            description: format!("Patrol the waters off {} and intercept {} smugglers", port_name, required),
+            expiry_tick: Some(current_tick + crate::resources::PATROL_DURATION_TICKS),
+            issuer: None,
+        }
+    }
+
+    /// Creates a letter of marque against `rival`, asking for `required`
+    /// prizes at `prize_money` each. Its terms are the `Commission` component
+    /// spawned alongside.
+    pub fn privateer(origin: Entity, rival: FactionId, required: u32, prize_money: u32, current_tick: u32) -> Self {
+        Self {
+            contract_type: ContractType::Privateer,
+            origin_port: origin,
+            destination: None,
+            reward_gold: required * prize_money,
+            cargo_required: None,
+            description: format!(
+                "Letter of marque: sink {} ships of {}, {} gold a prize",
+                required,
+                rival.display_name(),
+                prize_money
+            ),
+            expiry_tick: Some(current_tick + crate::resources::COMMISSION_DURATION_TICKS),
+            issuer: None,
+        }
+    }
+
+    /// Sets the faction that offered this contract.
+    pub fn with_issuer(mut self, issuer: FactionId) -> Self {
+        self.issuer = Some(issuer);
+        self
+    }
+
+    /// Ticks left before expiry (None = never expires).
+    pub fn ticks_remaining(&self, current_tick: u32) -> Option<u32> {
+        self.expiry_tick.map(|expiry| expiry.saturating_sub(current_tick))
+    }
+
+    /// Gold forfeited if this contract is accepted and then fails.
+    pub fn failure_gold_penalty(&self) -> u32 {
+        (self.reward_gold as f32 * Self::FAILURE_GOLD_FRACTION).round() as u32
+    }
+
     /// Returns true if this contract has expired.
     pub fn is_expired(&self, current_tick: u32) -> bool {
         if let Some(expiry) = self.expiry_tick {
@@ -123,17 +199,37 @@ impl ContractDetails {
     }
 }
 
+/// Formats a tick count as days and hours for contract deadlines, e.g. "1d 6h".
+pub fn format_time_remaining(ticks: u32) -> String {
+    let hours = ticks / TICKS_PER_HOUR;
+    match (hours / 24, hours % 24) {
+        (0, 0) => "<1h".to_string(),
+        (0, h) => format!("{}h", h),
+        (d, 0) => format!("{}d", d),
+        (d, h) => format!("{}d {}h", d, h),
+    }
+}
+
 /// Component marking a contract as accepted by the player.
 #[derive(Component, Debug, Default)]
 pub struct AcceptedContract;
 
 /// Component for tracking contract progress.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 pub struct ContractProgress {
     /// For Transport: cargo delivered so far.
     pub cargo_delivered: u32,
     /// Whether the destination has been reached.
     pub destination_reached: bool,
+    /// For Patrol: smugglers captured or sunk in the area.
+    #[reflect(default)]
+    pub intercepted: u32,
+    /// For Patrol: honest merchants attacked by mistake.
+    #[reflect(default)]
+    pub false_positives: u32,
+    /// For Privateer: rival ships sunk under the commission.
+    #[reflect(default)]
+    pub prizes: u32,
 }
 
 impl Default for ContractProgress {
@@ -141,10 +237,48 @@ impl Default for ContractProgress {
         Self {
             cargo_delivered: 0,
             destination_reached: false,
+            intercepted: 0,
+            false_positives: 0,
+            prizes: 0,
         }
     }
 }
 
+/// Waters a Patrol contract covers, and how many smugglers it asks for.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+pub struct PatrolArea {
+    pub center: Vec2,
+    pub radius: f32,
+    pub required: u32,
+}
+
+impl PatrolArea {
+    pub fn contains(&self, position: Vec2) -> bool {
+        position.distance(self.center) <= self.radius
+    }
+
+    /// Whether `progress` satisfies the patrol.
+    pub fn is_complete(&self, progress: &ContractProgress) -> bool {
+        progress.intercepted >= self.required
+    }
+}
+
+/// Terms of a letter of marque: whose ships it licenses the player to take,
+/// and how many it asks for.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct Commission {
+    pub rival: FactionId,
+    pub required: u32,
+    pub prize_money: u32,
+}
+
+impl Commission {
+    /// Whether `progress` satisfies the commission.
+    pub fn is_complete(&self, progress: &ContractProgress) -> bool {
+        progress.prizes >= self.required
+    }
+}
+
 /// Component marking a contract as delegated to a fleet ship.
 /// The assigned ship will autonomously fulfill the contract.
 #[derive(Component, Debug)]
@@ -168,3 +302,27 @@ impl AssignedShip {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_saved_before_patrols_loads_with_none_intercepted() {
+        use bevy::reflect::serde::TypedReflectDeserializer;
+        use bevy::reflect::{FromReflect, TypeRegistry};
+        use serde::de::DeserializeSeed;
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<ContractProgress>();
+        let registration = registry.get(std::any::TypeId::of::<ContractProgress>()).unwrap();
+        let mut deserializer =
+            ron::de::Deserializer::from_str("(cargo_delivered: 12, destination_reached: true)").unwrap();
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let progress = ContractProgress::from_reflect(value.as_ref()).unwrap();
+        assert_eq!((progress.cargo_delivered, progress.destination_reached), (12, true));
+        assert_eq!((progress.intercepted, progress.false_positives, progress.prizes), (0, 0, 0));
+    }
+}
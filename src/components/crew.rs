@@ -0,0 +1,207 @@
+use bevy::prelude::*;
+
+use super::ship::ShipType;
+
+/// Wages owed each sailor per day, in gold.
+pub const SAILOR_WAGE: u32 = 2;
+/// Signing bounty paid to each sailor hired at a tavern.
+pub const HIRE_COST: u32 = 15;
+/// Share of her berths a ship needs filled to be worked at full efficiency.
+pub const FULL_CREW_SHARE: f32 = 0.75;
+/// Efficiency of a ship with nobody aboard to work her.
+const SKELETON_EFFICIENCY: f32 = 0.4;
+/// Hull damage that, on average, costs one hand.
+pub const HULL_DAMAGE_PER_CASUALTY: f32 = 12.0;
+/// Morale a new crew starts with.
+const STARTING_MORALE: f32 = 0.7;
+/// Morale gained on a payday the wages are met.
+const PAID_MORALE: f32 = 0.05;
+/// Morale lost on a payday the wages are not met.
+const UNPAID_MORALE: f32 = 0.2;
+/// Morale lost for each shipmate killed or lost.
+const LOSS_MORALE: f32 = 0.01;
+/// Below this morale, unpaid sailors start to desert.
+pub const DESERTION_MORALE: f32 = 0.3;
+/// Share of the hands who desert on each unpaid day below `DESERTION_MORALE`.
+const DESERTION_SHARE: f32 = 0.15;
+
+/// The sailors aboard a ship: how many, how many she has room for, and their spirits.
+///
+/// Too few hands slows her reloads and her turning (see `efficiency`). The
+/// player's crew draw daily wages; combat damage and boarding kill them, and
+/// unpaid crews lose heart and desert.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Crew {
+    pub hands: u32,
+    pub berths: u32,
+    /// Spirits, 0.0 (mutinous) to 1.0 (eager).
+    pub morale: f32,
+}
+
+impl Default for Crew {
+    fn default() -> Self {
+        Self::for_ship(ShipType::Sloop)
+    }
+}
+
+impl Crew {
+    /// Berths aboard a ship of `ship_type`.
+    pub fn berths_for(ship_type: ShipType) -> u32 {
+        match ship_type {
+            ShipType::Raft => 4,
+            ShipType::Sloop => 16,
+            ShipType::Schooner => 24,
+            ShipType::Frigate => 40,
+        }
+    }
+
+    /// A ship of `ship_type` manned to full efficiency and no more.
+    pub fn for_ship(ship_type: ShipType) -> Self {
+        let berths = Self::berths_for(ship_type);
+        Self {
+            hands: (berths as f32 * FULL_CREW_SHARE).ceil() as u32,
+            berths,
+            morale: STARTING_MORALE,
+        }
+    }
+
+    /// Berths with nobody in them.
+    pub fn vacancies(&self) -> u32 {
+        self.berths.saturating_sub(self.hands)
+    }
+
+    /// Signs on up to `count` sailors, as many as there are berths for.
+    /// Returns how many came aboard.
+    pub fn hire(&mut self, count: u32) -> u32 {
+        let hired = count.min(self.vacancies());
+        self.hands += hired;
+        hired
+    }
+
+    /// Loses up to `count` hands to shot, steel or desertion; the rest take
+    /// it badly. Returns how many were lost.
+    pub fn lose(&mut self, count: u32) -> u32 {
+        let lost = count.min(self.hands);
+        self.hands -= lost;
+        self.morale = (self.morale - LOSS_MORALE * lost as f32).max(0.0);
+        lost
+    }
+
+    /// Share of a full working crew aboard, 0.0 to 1.0.
+    pub fn manning(&self) -> f32 {
+        let needed = self.berths as f32 * FULL_CREW_SHARE;
+        if needed <= 0.0 {
+            return 1.0;
+        }
+        (self.hands as f32 / needed).min(1.0)
+    }
+
+    /// How well she can be worked: 1.0 fully manned, down to
+    /// `SKELETON_EFFICIENCY` with nobody aboard.
+    pub fn efficiency(&self) -> f32 {
+        SKELETON_EFFICIENCY + (1.0 - SKELETON_EFFICIENCY) * self.manning()
+    }
+
+    /// Multiplier on cannon reload time; above 1.0 when short-handed.
+    pub fn reload_multiplier(&self) -> f32 {
+        1.0 / self.efficiency()
+    }
+
+    /// Multiplier on turning; below 1.0 when short-handed.
+    pub fn handling_multiplier(&self) -> f32 {
+        self.efficiency()
+    }
+
+    /// Wages the crew are owed for a day.
+    pub fn daily_wages(&self) -> u32 {
+        self.hands * SAILOR_WAGE
+    }
+
+    /// Settles a payday. A paid crew's spirits rise; an unpaid one's fall,
+    /// and once they are low enough some desert. Returns how many deserted.
+    pub fn payday(&mut self, paid: bool) -> u32 {
+        if paid {
+            self.morale = (self.morale + PAID_MORALE).min(1.0);
+            return 0;
+        }
+        self.morale = (self.morale - UNPAID_MORALE).max(0.0);
+        if self.morale >= DESERTION_MORALE {
+            return 0;
+        }
+        let deserters = (self.hands as f32 * DESERTION_SHARE).ceil() as u32;
+        let lost = deserters.min(self.hands);
+        self.hands -= lost;
+        lost
+    }
+
+    /// Morale as shown to the player.
+    pub fn mood(&self) -> &'static str {
+        match self.morale {
+            m if m >= 0.8 => "Eager",
+            m if m >= 0.55 => "Content",
+            m if m >= DESERTION_MORALE => "Grumbling",
+            _ => "Mutinous",
+        }
+    }
+}
+
+/// Hands killed by a hit for `damage` to the hull, given a uniform `roll` in [0, 1)
+/// for the fraction of a casualty.
+pub fn hull_casualties(damage: f32, roll: f32) -> u32 {
+    let expected = damage.max(0.0) / HULL_DAMAGE_PER_CASUALTY;
+    let whole = expected.floor();
+    whole as u32 + u32::from(roll < expected - whole)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_crew_slows_reload_and_turning() {
+        let mut crew = Crew::for_ship(ShipType::Schooner);
+        assert_eq!(crew.berths, 24);
+        assert_eq!(crew.hands, 18);
+        assert!((crew.efficiency() - 1.0).abs() < 1e-5);
+
+        crew.lose(9);
+        assert!(crew.reload_multiplier() > 1.0);
+        assert!(crew.handling_multiplier() < 1.0);
+        assert!(crew.morale < STARTING_MORALE);
+
+        crew.lose(100);
+        assert_eq!(crew.hands, 0);
+        assert!((crew.efficiency() - SKELETON_EFFICIENCY).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_hiring_is_limited_by_berths() {
+        let mut crew = Crew::for_ship(ShipType::Sloop);
+        assert_eq!(crew.hire(100), 4);
+        assert_eq!(crew.vacancies(), 0);
+        assert_eq!(crew.daily_wages(), 16 * SAILOR_WAGE);
+    }
+
+    #[test]
+    fn test_unpaid_crews_desert() {
+        let mut crew = Crew::for_ship(ShipType::Frigate);
+        assert_eq!(crew.hands, 30);
+        // One missed payday is grumbled at; a run of them empties the berths
+        assert_eq!(crew.payday(false), 0);
+        let deserted: u32 = (0..2).map(|_| crew.payday(false)).sum();
+        assert!(deserted > 0);
+        assert_eq!(crew.hands, 30 - deserted);
+
+        crew.payday(true);
+        assert!(crew.morale > 0.0);
+    }
+
+    #[test]
+    fn test_hull_casualties_scale_with_damage() {
+        assert_eq!(hull_casualties(0.0, 0.0), 0);
+        assert_eq!(hull_casualties(HULL_DAMAGE_PER_CASUALTY * 2.0, 0.99), 2);
+        assert_eq!(hull_casualties(HULL_DAMAGE_PER_CASUALTY * 0.5, 0.2), 1);
+        assert_eq!(hull_casualties(HULL_DAMAGE_PER_CASUALTY * 0.5, 0.8), 0);
+    }
+}
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Share of a ship's damage an attacker must deal to earn an assist.
+pub const ASSIST_SHARE: f32 = 0.25;
+
+/// Who has damaged this ship, and by how much.
+///
+/// Filled from `ShipHitEvent`s with the damage each hit actually did, and by
+/// fire and flooding on behalf of the last attacker. When the ship is lost
+/// the ledger decides who gets the kill, who gets an assist and how rewards
+/// are split.
+#[derive(Component, Debug, Clone, Default)]
+pub struct DamageLedger {
+    pub contributions: HashMap<Entity, f32>,
+    /// The ship whose shot struck last: the final blow if this one sinks.
+    pub last_attacker: Option<Entity>,
+}
+
+impl DamageLedger {
+    pub fn record(&mut self, attacker: Entity, damage: f32) {
+        *self.contributions.entry(attacker).or_default() += damage.max(0.0);
+        self.last_attacker = Some(attacker);
+    }
+
+    /// Credits damage from fire or flooding to the last attacker, who set it
+    /// going. Damage before anyone has struck her is nobody's.
+    pub fn record_lingering(&mut self, damage: f32) {
+        if let Some(attacker) = self.last_attacker {
+            *self.contributions.entry(attacker).or_default() += damage.max(0.0);
+        }
+    }
+
+    pub fn total(&self) -> f32 {
+        self.contributions.values().sum()
+    }
+
+    /// Fraction of all recorded damage dealt by `attacker`.
+    pub fn share(&self, attacker: Entity) -> f32 {
+        let total = self.total();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        self.contributions.get(&attacker).copied().unwrap_or(0.0) / total
+    }
+
+    pub fn final_blow(&self) -> Option<Entity> {
+        self.last_attacker
+    }
+
+    /// Whether `attacker` landed the final blow or dealt at least `ASSIST_SHARE`.
+    pub fn is_credited(&self, attacker: Entity) -> bool {
+        self.last_attacker == Some(attacker) || self.share(attacker) >= ASSIST_SHARE
+    }
+
+    /// Every attacker's share of the damage, largest first.
+    pub fn shares(&self) -> Vec<(Entity, f32)> {
+        let mut shares: Vec<_> = self.contributions.keys().map(|e| (*e, self.share(*e))).collect();
+        shares.sort_by(|a, b| b.1.total_cmp(&a.1));
+        shares
+    }
+
+    /// Splits `pool` between attackers by damage share, rounding down.
+    /// Whatever rounding leaves over goes to the final blow.
+    pub fn split(&self, pool: u32) -> Vec<(Entity, u32)> {
+        let mut split: Vec<(Entity, u32)> = self
+            .shares()
+            .into_iter()
+            .map(|(attacker, share)| (attacker, (pool as f32 * share).floor() as u32))
+            .collect();
+        let remainder = pool.saturating_sub(split.iter().map(|(_, amount)| amount).sum());
+        if let Some(entry) = split.iter_mut().find(|(attacker, _)| Some(*attacker) == self.last_attacker) {
+            entry.1 += remainder;
+        }
+        split
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_final_blow_and_assists() {
+        let player = Entity::from_raw(1);
+        let ally = Entity::from_raw(2);
+        let bystander = Entity::from_raw(3);
+        let mut ledger = DamageLedger::default();
+        ledger.record(player, 60.0);
+        ledger.record(bystander, 10.0);
+        ledger.record(ally, 30.0);
+
+        assert_eq!(ledger.final_blow(), Some(ally));
+        assert!((ledger.share(player) - 0.6).abs() < 1e-5);
+        assert!(ledger.is_credited(player));
+        assert!(ledger.is_credited(ally));
+        assert!(!ledger.is_credited(bystander));
+        assert_eq!(ledger.shares()[0].0, player);
+    }
+
+    #[test]
+    fn test_split_hands_remainder_to_final_blow() {
+        let a = Entity::from_raw(1);
+        let b = Entity::from_raw(2);
+        let mut ledger = DamageLedger::default();
+        ledger.record(a, 1.0);
+        ledger.record(b, 2.0);
+
+        let split = ledger.split(100);
+        assert_eq!(split.iter().map(|(_, amount)| amount).sum::<u32>(), 100);
+        assert_eq!(split.iter().find(|(e, _)| *e == b).unwrap().1, 67);
+    }
+
+    #[test]
+    fn test_fire_and_flooding_go_to_the_last_attacker() {
+        let a = Entity::from_raw(1);
+        let b = Entity::from_raw(2);
+        let mut ledger = DamageLedger::default();
+        ledger.record_lingering(50.0);
+        assert_eq!(ledger.total(), 0.0);
+
+        ledger.record(a, 10.0);
+        ledger.record(b, 10.0);
+        ledger.record_lingering(20.0);
+        assert!((ledger.share(b) - 0.75).abs() < 1e-5);
+        assert_eq!(ledger.final_blow(), Some(b));
+    }
+}
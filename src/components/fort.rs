@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::components::FactionId;
+
+/// A shore battery covering a battle fought under a hostile port's guns.
+///
+/// Forts stand at the edge of the arena and throw plunging volleys at the
+/// player's ships. Each takes shot into its `Health` hull until silenced.
+#[derive(Component, Debug, Clone)]
+pub struct Fort {
+    /// Name shown in the log and on the loot screen.
+    pub name: String,
+    /// Flag of the port it defends.
+    pub faction: FactionId,
+    /// Time until the next volley.
+    pub reload: Timer,
+}
+
+/// Radius of a fort's walls (world units).
+pub const FORT_RADIUS: f32 = 40.0;
+
+/// A fort whose guns have been knocked out. It fires no more and takes no
+/// more shot.
+#[derive(Component, Debug, Default)]
+pub struct Silenced;
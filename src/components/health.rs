@@ -1,10 +1,36 @@
 use bevy::prelude::*;
 
+/// Drive left with the sails shot to rags; spars and scraps still catch some wind.
+pub const MIN_SAIL_POWER: f32 = 0.15;
+/// Steering left with the rudder shot away; a ship can still be nudged round by its sails.
+pub const MIN_STEERING: f32 = 0.1;
+/// Below this rudder ratio steering is impaired.
+pub const RUDDER_IMPAIRED_RATIO: f32 = 0.75;
+/// Below this rudder ratio the rudder is crippled and can be jury-rigged.
+pub const RUDDER_CRIPPLED_RATIO: f32 = 0.3;
+/// Rudder ratio a jury-rig brings a crippled rudder back up to.
+pub const JURY_RIG_RATIO: f32 = 0.45;
+/// Seconds the crew spends rigging a makeshift rudder, unable to steer.
+pub const JURY_RIG_SECONDS: f32 = 6.0;
+/// Hull points per second flood water strains away for each unit aboard.
+pub const FLOOD_HULL_STRAIN: f32 = 0.02;
+/// Flood water at which the lower deck is awash and any fire there drowns.
+pub const FLOOD_SWAMP_LEVEL: f32 = 20.0;
+/// Intensity of a fire when it first takes hold (out of 1.0).
+pub const FIRE_IGNITION: f32 = 0.4;
+/// Intensity a fire gains each second it is left to spread.
+pub const FIRE_SPREAD_RATE: f32 = 0.03;
+/// Hull and sail points per second a fire burns away at full intensity.
+pub const FIRE_HULL_DAMAGE: f32 = 2.0;
+pub const FIRE_SAIL_DAMAGE: f32 = 3.0;
+
 /// Represents the health state of a ship's three primary components.
 /// Damage to each component leads to different debuffs:
 /// - **Sails**: Reduce `MaxSpeed` proportionally.
 /// - **Rudder**: Reduce `TurnRate` proportionally.
 /// - **Hull**: Reduce both slightly, and add `WaterIntake` component.
+///
+/// Heated shot and bursting shells can also set a ship on `Fire`.
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
 pub struct Health {
@@ -36,6 +62,18 @@ impl Health {
         }
     }
 
+    /// The same health with every current and maximum value times `multiplier`.
+    pub fn scaled(&self, multiplier: f32) -> Self {
+        Self {
+            sails: self.sails * multiplier,
+            sails_max: self.sails_max * multiplier,
+            rudder: self.rudder * multiplier,
+            rudder_max: self.rudder_max * multiplier,
+            hull: self.hull * multiplier,
+            hull_max: self.hull_max * multiplier,
+        }
+    }
+
     /// Returns the ratio of current sails to max sails (0.0 to 1.0).
     pub fn sails_ratio(&self) -> f32 {
         if self.sails_max > 0.0 {
@@ -67,6 +105,60 @@ impl Health {
     pub fn is_destroyed(&self) -> bool {
         self.hull <= 0.0
     }
+
+    /// Fraction of top speed the sails can drive, never below `MIN_SAIL_POWER`.
+    pub fn sail_power(&self) -> f32 {
+        self.sails_ratio().max(MIN_SAIL_POWER)
+    }
+
+    /// Fraction of full turn rate the rudder allows, never below `MIN_STEERING`.
+    pub fn steering(&self) -> f32 {
+        self.rudder_ratio().max(MIN_STEERING)
+    }
+
+    /// How badly the rudder damage affects steering.
+    pub fn steering_state(&self) -> SteeringState {
+        let ratio = self.rudder_ratio();
+        if ratio < RUDDER_CRIPPLED_RATIO {
+            SteeringState::Crippled
+        } else if ratio < RUDDER_IMPAIRED_RATIO {
+            SteeringState::Impaired
+        } else {
+            SteeringState::Sound
+        }
+    }
+
+    /// Patches a crippled rudder up to `JURY_RIG_RATIO`. Returns false if the
+    /// rudder was not crippled.
+    pub fn jury_rig_rudder(&mut self) -> bool {
+        if self.steering_state() != SteeringState::Crippled {
+            return false;
+        }
+        self.rudder = self.rudder.max(self.rudder_max * JURY_RIG_RATIO);
+        true
+    }
+}
+
+/// Rudder condition as it affects handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SteeringState {
+    Sound,
+    /// Turning noticeably slower.
+    Impaired,
+    /// Barely answering the helm; a jury-rig is possible.
+    Crippled,
+}
+
+/// The crew is rigging a makeshift rudder. The ship cannot steer until done.
+#[derive(Component, Debug, Clone)]
+pub struct JuryRig {
+    pub timer: Timer,
+}
+
+impl Default for JuryRig {
+    fn default() -> Self {
+        Self { timer: Timer::from_seconds(JURY_RIG_SECONDS, TimerMode::Once) }
+    }
 }
 
 impl Default for Health {
@@ -77,7 +169,8 @@ impl Default for Health {
 
 /// Represents water flooding into a damaged ship hull.
 /// Water accumulates over time based on `rate`, and `current` tracks
-/// total water taken on. High water levels could affect ship performance.
+/// total water taken on. Water aboard strains the hull, and once the deck
+/// is swamped it puts out any fire.
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
 pub struct WaterIntake {
@@ -102,6 +195,16 @@ impl WaterIntake {
     pub fn tick(&mut self, delta_seconds: f32) {
         self.current += self.rate * delta_seconds;
     }
+
+    /// Hull points per second the water aboard strains away.
+    pub fn hull_strain(&self) -> f32 {
+        self.current * FLOOD_HULL_STRAIN
+    }
+
+    /// Whether the lower deck is awash.
+    pub fn is_swamped(&self) -> bool {
+        self.current >= FLOOD_SWAMP_LEVEL
+    }
 }
 
 impl Default for WaterIntake {
@@ -109,3 +212,109 @@ impl Default for WaterIntake {
         Self::new(1.0) // 1 unit of water per second by default
     }
 }
+
+/// Represents a fire burning aboard a ship, the counterpart of
+/// `WaterIntake`. Left alone it spreads, burning the hull and climbing into
+/// the rigging; hands at the repair station beat it down, and it drowns
+/// when the deck swamps.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Fire {
+    /// How fierce the fire is, up to 1.0. Out at 0.
+    pub intensity: f32,
+}
+
+impl Fire {
+    /// Creates a new Fire component at the given intensity.
+    pub fn new(intensity: f32) -> Self {
+        Self { intensity: intensity.clamp(0.0, 1.0) }
+    }
+
+    /// Feeds the fire, as when more heated shot lands in it.
+    pub fn stoke(&mut self, intensity: f32) {
+        self.intensity = (self.intensity + intensity).min(1.0);
+    }
+
+    /// Spreads the fire over `delta_seconds` while `hands` fight it, each
+    /// putting out `put_out_per_hand` intensity a second.
+    pub fn tick(&mut self, delta_seconds: f32, hands: f32, put_out_per_hand: f32) {
+        let change = FIRE_SPREAD_RATE - put_out_per_hand * hands;
+        self.intensity = (self.intensity + change * delta_seconds).clamp(0.0, 1.0);
+    }
+
+    /// Hull points per second the fire burns away.
+    pub fn hull_damage(&self) -> f32 {
+        self.intensity * FIRE_HULL_DAMAGE
+    }
+
+    /// Sail points per second the fire burns away.
+    pub fn sail_damage(&self) -> f32 {
+        self.intensity * FIRE_SAIL_DAMAGE
+    }
+
+    pub fn is_out(&self) -> bool {
+        self.intensity <= 0.0
+    }
+}
+
+impl Default for Fire {
+    fn default() -> Self {
+        Self::new(FIRE_IGNITION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steering_tracks_rudder_with_floor() {
+        let mut health = Health::default();
+        assert_eq!(health.steering(), 1.0);
+        health.rudder = 50.0;
+        assert_eq!(health.steering(), 0.5);
+        assert_eq!(health.steering_state(), SteeringState::Impaired);
+        health.rudder = 0.0;
+        assert_eq!(health.steering(), MIN_STEERING);
+        assert_eq!(health.steering_state(), SteeringState::Crippled);
+    }
+
+    #[test]
+    fn test_jury_rig_only_fixes_crippled_rudder() {
+        let mut health = Health::default();
+        health.rudder = 50.0;
+        assert!(!health.jury_rig_rudder());
+        assert_eq!(health.rudder, 50.0);
+
+        health.rudder = 10.0;
+        assert!(health.jury_rig_rudder());
+        assert_eq!(health.rudder, 100.0 * JURY_RIG_RATIO);
+        assert_ne!(health.steering_state(), SteeringState::Crippled);
+    }
+
+    #[test]
+    fn test_fire_spreads_unless_fought() {
+        let mut fire = Fire::default();
+        fire.tick(10.0, 0.0, 0.05);
+        assert!(fire.intensity > FIRE_IGNITION);
+        fire.stoke(1.0);
+        assert_eq!(fire.intensity, 1.0);
+        assert_eq!(fire.hull_damage(), FIRE_HULL_DAMAGE);
+
+        // Four hands at the buckets beat it down
+        for _ in 0..60 {
+            fire.tick(1.0, 4.0, 0.05);
+        }
+        assert!(fire.is_out());
+    }
+
+    #[test]
+    fn test_flood_water_strains_hull_and_swamps_deck() {
+        let mut intake = WaterIntake::new(2.0);
+        intake.tick(5.0);
+        assert!(!intake.is_swamped());
+        assert!(intake.hull_strain() > 0.0);
+        intake.tick(5.0);
+        assert!(intake.is_swamped());
+    }
+}
@@ -4,12 +4,27 @@
 
 use bevy::prelude::*;
 
+/// Animation duration of a single tile, and the shortest of any blob (seconds).
+pub const INK_BASE_DURATION: f32 = 0.5;
+/// Extra animation time for each tile of a blob's radius (seconds).
+pub const INK_SECONDS_PER_TILE: f32 = 0.05;
+/// Longest a blob takes to reveal, however large (seconds).
+pub const INK_MAX_DURATION: f32 = 1.5;
+/// Share of the animation over which the ink spreads from the blob's middle
+/// to its edge; each tile then fades over the rest.
+pub const INK_SPREAD_SHARE: f32 = 0.6;
+
 /// Component that tracks the progress of an ink reveal animation.
-/// Attached to tile entities when they are first explored.
+/// One entity animates a whole blob of contiguous tiles revealed together,
+/// the ink spreading outward from the middle of the blob.
 #[derive(Component, Debug, Clone)]
 pub struct InkReveal {
-    /// The tile position being revealed.
-    pub tile_pos: IVec2,
+    /// The tile positions being revealed.
+    pub tiles: Vec<IVec2>,
+    /// Middle of the blob, where the ink starts to spread.
+    pub origin: Vec2,
+    /// Distance from the origin to the furthest tile.
+    pub radius: f32,
     /// Time when the reveal animation started.
     pub start_time: f32,
     /// Animation duration in seconds.
@@ -17,17 +32,35 @@ pub struct InkReveal {
 }
 
 impl InkReveal {
-    /// Creates a new ink reveal animation for a tile.
-    pub fn new(tile_pos: IVec2, start_time: f32) -> Self {
+    /// Creates a new ink reveal animation for a blob of tiles.
+    pub fn new(tiles: Vec<IVec2>, start_time: f32) -> Self {
+        let origin = match tiles.len() {
+            0 => Vec2::ZERO,
+            count => tiles.iter().map(|tile| tile.as_vec2()).sum::<Vec2>() / count as f32,
+        };
+        let radius = tiles.iter().map(|tile| tile.as_vec2().distance(origin)).fold(0.0, f32::max);
         Self {
-            tile_pos,
+            tiles,
+            origin,
+            radius,
             start_time,
-            duration: 0.5, // 0.5 second animation
+            duration: (INK_BASE_DURATION + radius * INK_SECONDS_PER_TILE).min(INK_MAX_DURATION),
+        }
+    }
+
+    /// Reveals the tiles at once, with no animation to speak of.
+    pub fn instant(tiles: Vec<IVec2>, start_time: f32) -> Self {
+        Self {
+            duration: 0.0,
+            ..Self::new(tiles, start_time)
         }
     }
 
     /// Returns the animation progress (0.0 = start, 1.0 = complete).
     pub fn progress(&self, current_time: f32) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
         let elapsed = current_time - self.start_time;
         (elapsed / self.duration).clamp(0.0, 1.0)
     }
@@ -37,9 +70,16 @@ impl InkReveal {
         self.progress(current_time) >= 1.0
     }
 
-    /// Returns an eased progress value (ease-out for fast start, slow finish).
-    pub fn eased_progress(&self, current_time: f32) -> f32 {
-        let t = self.progress(current_time);
+    /// Returns an eased progress value for one tile of the blob (ease-out for
+    /// fast start, slow finish). Tiles further from the origin start later.
+    pub fn eased_progress(&self, tile: IVec2, current_time: f32) -> f32 {
+        // A lone tile fades over the whole animation
+        let (delay, fade) = if self.radius > 0.0 {
+            (INK_SPREAD_SHARE * tile.as_vec2().distance(self.origin) / self.radius, 1.0 - INK_SPREAD_SHARE)
+        } else {
+            (0.0, 1.0)
+        };
+        let t = ((self.progress(current_time) - delay) / fade).clamp(0.0, 1.0);
         // Ease-out cubic: 1 - (1-t)^3
         1.0 - (1.0 - t).powi(3)
     }
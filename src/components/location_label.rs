@@ -26,6 +26,15 @@ impl LabelImportance {
             LabelImportance::Minor => 14.0,
         }
     }
+
+    /// Layout priority; lower ranks are placed first and win collisions.
+    pub fn rank(&self) -> u8 {
+        match self {
+            LabelImportance::Major => 0,
+            LabelImportance::Standard => 1,
+            LabelImportance::Minor => 2,
+        }
+    }
 }
 
 /// Marks an entity as having a location label rendered on the nautical chart.
@@ -49,6 +58,56 @@ impl LocationLabel {
             angle,
         }
     }
+
+    /// Approximate world-space size of the rendered text (unrotated).
+    /// The Quintessential glyphs average a little over half an em wide.
+    pub fn estimated_size(&self) -> Vec2 {
+        let font_size = self.importance.font_size();
+        Vec2::new(
+            self.name.chars().count() as f32 * font_size * 0.55,
+            font_size * 1.2,
+        )
+    }
+}
+
+/// A label lettered along a curved path, such as a coast or sea name.
+/// Glyph children are built from this by the curved label plugin.
+#[derive(Component, Debug, Clone)]
+pub struct CurvedLabel {
+    pub text: String,
+    /// Baseline path in world coordinates, in any direction.
+    pub path: Vec<Vec2>,
+    pub font_size: f32,
+    /// Extra spacing between letters, in world units.
+    pub letter_spacing: f32,
+}
+
+/// Marks a single character entity belonging to a `CurvedLabel`.
+#[derive(Component, Debug)]
+pub struct CurvedLabelGlyph;
+
+/// Layout state for a location label, maintained by the label layout pass.
+#[derive(Component, Debug, Clone)]
+pub struct LabelPlacement {
+    /// Preferred world position of the label centre.
+    pub anchor: Vec2,
+    /// Current offset from the anchor chosen to avoid other labels.
+    pub nudge: Vec2,
+    /// False when the label could not be placed without overlapping.
+    pub placed: bool,
+    /// Current opacity, eased towards the zoom and placement target.
+    pub alpha: f32,
+}
+
+impl LabelPlacement {
+    pub fn new(anchor: Vec2) -> Self {
+        Self {
+            anchor,
+            nudge: Vec2::ZERO,
+            placed: true,
+            alpha: 0.0,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -69,4 +128,13 @@ mod tests {
         assert_eq!(label.importance, LabelImportance::Major);
         assert!((label.angle - 1.57).abs() < 0.001);
     }
+
+    #[test]
+    fn test_estimated_size_scales_with_text() {
+        let short = LocationLabel::new("Cove", LabelImportance::Standard, 0.0);
+        let long = LocationLabel::new("Port Royal Harbor", LabelImportance::Standard, 0.0);
+        let big = LocationLabel::new("Cove", LabelImportance::Major, 0.0);
+        assert!(long.estimated_size().x > short.estimated_size().x);
+        assert!(big.estimated_size().y > short.estimated_size().y);
+    }
 }
@@ -18,6 +18,18 @@ pub mod location_label;
 pub mod fade_controller;
 pub mod region;
 pub mod scene;
+pub mod sails;
+pub mod upgrade;
+pub mod collectible;
+pub mod stations;
+pub mod damage_ledger;
+pub mod picking;
+pub mod patrol;
+pub mod supernatural;
+pub mod weather;
+pub mod crew;
+pub mod fort;
+pub mod sea_monster;
 
 pub use ship::*;
 pub use health::*;
@@ -33,4 +45,5 @@ pub use intel::*;
 pub use ink_reveal::*;
 pub use typewriter::*;
 pub use scene::*;
+pub use crew::*;
 
@@ -34,4 +34,21 @@ impl NavigationPath {
     pub fn is_empty(&self) -> bool {
         self.waypoints.is_empty()
     }
+
+    /// Moves `distance` along the path from `from`, dropping waypoints as they
+    /// are passed. Returns the new position.
+    pub fn advance(&mut self, from: Vec2, distance: f32) -> Vec2 {
+        let mut position = from;
+        let mut remaining = distance;
+        while let Some(next) = self.next_waypoint() {
+            let leg = position.distance(next);
+            if leg > remaining {
+                return position.move_towards(next, remaining);
+            }
+            remaining -= leg;
+            position = next;
+            self.pop_waypoint();
+        }
+        position
+    }
 }
@@ -1,7 +1,8 @@
 //! Order types for AI ship behavior.
 //!
 //! Orders define what an AI-controlled ship should do. Ships execute orders
-//! from their OrderQueue to perform trade, patrol, escort, and scouting tasks.
+//! from their OrderQueue to perform trade, patrol, escort, scouting and
+//! scavenging tasks.
 
 use bevy::prelude::*;
 use std::collections::VecDeque;
@@ -51,6 +52,14 @@ pub enum Order {
         progress: f32,
     },
 
+    /// Sail to a charted wreck and strip her of what is left aboard.
+    Scavenge {
+        /// Map marker of the wreck (`WreckSiteMarker` or `LegacyWreckMarker`).
+        wreck: Entity,
+        /// Where she lies in world coordinates.
+        position: Vec2,
+    },
+
     /// Idle at current position, awaiting further orders.
     Idle,
 }
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+/// Marker for a merchant running contraband under an honest flag.
+/// Hidden from the player until the ship is hailed.
+#[derive(Component, Debug, Default)]
+pub struct Smuggler;
+
+/// A ship the player has hailed and inspected through the spyglass.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Hailed {
+    /// What the inspection found; may be wrong (see `inspection_suspicious`).
+    pub suspicious: bool,
+}
+
+/// Merchant traffic spawned in a patrol area for the player to inspect.
+#[derive(Component, Debug)]
+pub struct PatrolTraffic;
+
+/// A merchant that heaved to when chased and gave up her cargo. She is
+/// left to sail on with an empty hold.
+#[derive(Component, Debug, Default)]
+pub struct Plundered;
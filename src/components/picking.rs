@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+/// What kind of map object a `Pickable` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickKind {
+    Ship,
+    Port,
+    Wreck,
+}
+
+impl PickKind {
+    /// Click radius around the object's centre (world units), roughly its sprite size.
+    pub fn radius(self) -> f32 {
+        match self {
+            PickKind::Ship => 32.0,
+            PickKind::Port => 48.0,
+            PickKind::Wreck => 28.0,
+        }
+    }
+}
+
+/// A High Seas object that can be clicked, with the radius it answers to.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Pickable {
+    pub kind: PickKind,
+    pub radius: f32,
+}
+
+impl Pickable {
+    pub fn new(kind: PickKind) -> Self {
+        Self { kind, radius: kind.radius() }
+    }
+}
@@ -2,12 +2,28 @@ use bevy::prelude::*;
 use std::collections::HashMap;
 
 use super::cargo::GoodType;
+use super::ship::FactionId;
 
 /// Marker component that identifies an entity as a port.
 /// Ports are docking locations where players can trade, repair, and recruit.
 #[derive(Component, Debug, Default)]
 pub struct Port;
 
+/// Key identifying a port across High Seas visits: its rounded world position.
+///
+/// Port entities are respawned every time the High Seas are entered, but stay
+/// on the same tile, so anything remembered per port is keyed by this.
+pub fn port_key(port_position: Vec2) -> IVec2 {
+    port_position.round().as_ivec2()
+}
+
+/// The `port_key` of the port whose board or tavern carries this offer.
+///
+/// Offers outlive the port entity that posted them, so they are matched to
+/// their port by this rather than by entity.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OfferedAt(pub IVec2);
+
 /// The display name of a port.
 #[derive(Component, Debug, Clone)]
 pub struct PortName(pub String);
@@ -19,7 +35,7 @@ impl Default for PortName {
 }
 
 /// Represents a single item in a port's inventory.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Reflect)]
 pub struct InventoryItem {
     /// Current quantity in stock.
     pub quantity: u32,
@@ -35,7 +51,7 @@ impl InventoryItem {
 
 /// Port inventory containing goods available for trade.
 /// Each good has a quantity and price that can fluctuate.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 pub struct Inventory {
     /// Map of goods to their quantity and price.
     pub goods: HashMap<GoodType, InventoryItem>,
@@ -88,3 +104,64 @@ impl Inventory {
         revenue
     }
 }
+
+/// How developed a port is, by how many goods its market trades.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortSize {
+    Cove,
+    Town,
+    City,
+}
+
+impl PortSize {
+    pub fn from_goods(goods: usize) -> Self {
+        match goods {
+            0..=3 => PortSize::Cove,
+            4 => PortSize::Town,
+            _ => PortSize::City,
+        }
+    }
+
+    /// Whether the port is a town or a city.
+    pub fn is_major(&self) -> bool {
+        *self != PortSize::Cove
+    }
+
+    /// Houses along the waterfront.
+    pub fn buildings(&self) -> usize {
+        match self {
+            PortSize::Cove => 3,
+            PortSize::Town => 7,
+            PortSize::City => 12,
+        }
+    }
+
+    /// Whether a fort guards the harbour mouth.
+    pub fn has_fort(&self) -> bool {
+        *self == PortSize::City
+    }
+}
+
+/// What is going on at a port, as shown on the chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortStatus {
+    Open,
+    Festival,
+    /// The dockworkers are on strike.
+    Strike,
+    /// Its flag has closed the harbour to the player.
+    Closed,
+}
+
+/// What a port's composite sprite on the chart currently shows. The port
+/// visual sync rebuilds the sprite's parts whenever this falls out of date.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortVisual {
+    pub faction: FactionId,
+    pub size: PortSize,
+    pub status: PortStatus,
+}
+
+/// Marker for a child sprite making up part of a port's `PortVisual`.
+#[derive(Component, Debug)]
+pub struct PortVisualPart;
@@ -0,0 +1,199 @@
+//! Sail overlay state for ship sprites.
+//!
+//! Sails are child sprites placed at each `ShipType::sail_anchors` mast. The
+//! parent's `SailRig` says how much canvas is set and how damaged it is.
+
+use bevy::prelude::*;
+
+/// How much sail a ship has set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SailSet {
+    /// Stowed on the yards: anchored or docked.
+    #[default]
+    Furled,
+    /// Shortened for heavy weather.
+    Reefed,
+    /// Braced round for a reach or beating to windward.
+    Trimmed,
+    /// Everything set, running before the wind.
+    Full,
+}
+
+impl SailSet {
+    /// Drawn depth of the sail (along the hull) in 64px sprite units.
+    pub fn depth(&self) -> f32 {
+        match self {
+            SailSet::Furled => 3.0,
+            SailSet::Reefed => 6.0,
+            SailSet::Trimmed => 9.0,
+            SailSet::Full => 13.0,
+        }
+    }
+}
+
+/// Sail plan ordered by the captain: speed against a steadier gun deck and
+/// less canvas aloft to be shot through.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SailTrim {
+    /// Every sail set.
+    #[default]
+    Full,
+    /// Topsails only: the usual plan for a fight.
+    Battle,
+    /// Sails stowed; the ship drifts and turns on what little is left.
+    Furled,
+}
+
+impl SailTrim {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SailTrim::Full => "Full sail",
+            SailTrim::Battle => "Battle sail",
+            SailTrim::Furled => "Furled",
+        }
+    }
+
+    /// Fraction of top speed the trim allows.
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            SailTrim::Full => 1.0,
+            SailTrim::Battle => 0.6,
+            SailTrim::Furled => 0.15,
+        }
+    }
+
+    /// Random spread of each cannonball either side of the aim line (radians).
+    /// A ship heeling under full sail is a poor gun platform.
+    pub fn gun_spread(&self) -> f32 {
+        match self {
+            SailTrim::Full => 0.12,
+            SailTrim::Battle => 0.04,
+            SailTrim::Furled => 0.02,
+        }
+    }
+
+    /// Share of sail damage (shot or fire) that lands on canvas that is set.
+    pub fn canvas_exposure(&self) -> f32 {
+        match self {
+            SailTrim::Full => 1.0,
+            SailTrim::Battle => 0.6,
+            SailTrim::Furled => 0.3,
+        }
+    }
+
+    /// One step more canvas.
+    pub fn more(&self) -> Self {
+        match self {
+            SailTrim::Furled => SailTrim::Battle,
+            _ => SailTrim::Full,
+        }
+    }
+
+    /// One step less canvas.
+    pub fn less(&self) -> Self {
+        match self {
+            SailTrim::Full => SailTrim::Battle,
+            _ => SailTrim::Furled,
+        }
+    }
+
+    /// The most sail this trim shows on the rig.
+    pub fn limit_set(&self, set: SailSet) -> SailSet {
+        match (self, set) {
+            (SailTrim::Furled, _) => SailSet::Furled,
+            (SailTrim::Battle, SailSet::Full | SailSet::Trimmed) => SailSet::Reefed,
+            _ => set,
+        }
+    }
+}
+
+/// Visible sail damage, from `Health::sails_ratio`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SailDamage {
+    #[default]
+    Intact,
+    Torn,
+    Shredded,
+}
+
+impl SailDamage {
+    pub fn from_ratio(ratio: f32) -> Self {
+        if ratio > 0.66 {
+            SailDamage::Intact
+        } else if ratio > 0.33 {
+            SailDamage::Torn
+        } else {
+            SailDamage::Shredded
+        }
+    }
+
+    /// Fraction of the yard still carrying canvas.
+    pub fn width_factor(&self) -> f32 {
+        match self {
+            SailDamage::Intact => 1.0,
+            SailDamage::Torn => 0.8,
+            SailDamage::Shredded => 0.55,
+        }
+    }
+
+    /// Canvas color, darkening with scorch and grime as damage mounts.
+    pub fn color(&self) -> Color {
+        match self {
+            SailDamage::Intact => Color::srgb(0.95, 0.91, 0.80),
+            SailDamage::Torn => Color::srgb(0.82, 0.76, 0.64),
+            SailDamage::Shredded => Color::srgb(0.60, 0.54, 0.45),
+        }
+    }
+}
+
+/// Sail state of a ship, driving its sail child sprites.
+#[derive(Component, Debug, Clone, Default)]
+pub struct SailRig {
+    pub set: SailSet,
+    pub damage: SailDamage,
+    /// Position last frame, for measuring speed.
+    pub last_position: Option<Vec2>,
+    /// Ship sprite size relative to the 64px size sail anchors are defined for.
+    pub scale: f32,
+}
+
+/// A sail child sprite of a ship.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SailSprite {
+    /// Yard width in the ship's sprite space.
+    pub width: f32,
+    /// Current drawn depth, eased towards the rig's `SailSet::depth`.
+    pub depth: f32,
+    /// Billow animation phase offset so masts do not flap in unison.
+    pub phase: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage_from_ratio() {
+        assert_eq!(SailDamage::from_ratio(1.0), SailDamage::Intact);
+        assert_eq!(SailDamage::from_ratio(0.5), SailDamage::Torn);
+        assert_eq!(SailDamage::from_ratio(0.1), SailDamage::Shredded);
+    }
+
+    #[test]
+    fn test_trim_trades_speed_for_steadiness() {
+        assert!(SailTrim::Full.speed_factor() > SailTrim::Battle.speed_factor());
+        assert!(SailTrim::Full.gun_spread() > SailTrim::Battle.gun_spread());
+        assert!(SailTrim::Battle.canvas_exposure() > SailTrim::Furled.canvas_exposure());
+        assert_eq!(SailTrim::Full.less().less(), SailTrim::Furled);
+        assert_eq!(SailTrim::Furled.more().more(), SailTrim::Full);
+        assert_eq!(SailTrim::Battle.limit_set(SailSet::Full), SailSet::Reefed);
+        assert_eq!(SailTrim::Full.limit_set(SailSet::Trimmed), SailSet::Trimmed);
+    }
+
+    #[test]
+    fn test_more_sail_draws_deeper() {
+        assert!(SailSet::Furled.depth() < SailSet::Reefed.depth());
+        assert!(SailSet::Reefed.depth() < SailSet::Trimmed.depth());
+        assert!(SailSet::Trimmed.depth() < SailSet::Full.depth());
+    }
+}
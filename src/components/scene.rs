@@ -20,3 +20,8 @@ pub struct CombatEntity;
 /// Despawned automatically on `GameState::Port` exit.
 #[derive(Component, Default)]
 pub struct PortEntity;
+
+/// Marker for entities that belong to the MainMenu scene.
+/// Despawned automatically on `GameState::MainMenu` exit.
+#[derive(Component, Default)]
+pub struct MainMenuEntity;
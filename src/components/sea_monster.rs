@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use crate::resources::MonsterKind;
+
+/// A sea monster: roaming the High Seas, or its body in battle.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SeaMonster {
+    pub kind: MonsterKind,
+}
+
+/// One of a sea monster's tentacles or coils in battle.
+///
+/// It lies coiled beside the body until a ship comes within reach, then
+/// reaches for her and holds her fast until its grip gives or it is shot away.
+#[derive(Component, Debug, Clone)]
+pub struct Tentacle {
+    /// The body it belongs to.
+    pub monster: Entity,
+    /// Bearing of its resting place off the body (radians).
+    pub bearing: f32,
+    /// The ship in its grip.
+    pub holding: Option<Entity>,
+    /// Time until its grip gives.
+    pub grip: Timer,
+    /// Time between its crushing blows on the ship in its grip.
+    pub crush: Timer,
+    /// Time until it may seize a ship again.
+    pub recover: Timer,
+}
+
+/// Seconds a tentacle holds a ship before its grip gives.
+pub const GRIP_SECONDS: f32 = 5.0;
+/// Seconds a tentacle takes to recover after letting go.
+pub const RECOVER_SECONDS: f32 = 4.0;
+/// Seconds between a tentacle's crushing blows on the ship in its grip.
+pub const CRUSH_SECONDS: f32 = 1.0;
+
+impl Tentacle {
+    pub fn new(monster: Entity, bearing: f32) -> Self {
+        let mut recover = Timer::from_seconds(RECOVER_SECONDS, TimerMode::Once);
+        // Ready to seize the first ship that comes within reach
+        recover.tick(recover.duration());
+        Self {
+            monster,
+            bearing,
+            holding: None,
+            grip: Timer::from_seconds(GRIP_SECONDS, TimerMode::Once),
+            crush: Timer::from_seconds(CRUSH_SECONDS, TimerMode::Repeating),
+            recover,
+        }
+    }
+
+    /// Whether it may seize a ship.
+    pub fn is_ready(&self) -> bool {
+        self.holding.is_none() && self.recover.finished()
+    }
+
+    /// Seizes `ship`.
+    pub fn seize(&mut self, ship: Entity) {
+        self.holding = Some(ship);
+        self.grip.reset();
+        self.crush.reset();
+    }
+
+    /// Lets go of whatever it holds; returns the ship let go.
+    pub fn release(&mut self) -> Option<Entity> {
+        self.recover.reset();
+        self.holding.take()
+    }
+}
+
+/// A ship in a sea monster's grip, held fast until let go.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Grabbed {
+    /// The tentacle holding her.
+    pub by: Entity,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tentacle_recovers_before_seizing_again() {
+        let mut tentacle = Tentacle::new(Entity::from_raw(0), 0.0);
+        assert!(tentacle.is_ready());
+
+        let ship = Entity::from_raw(1);
+        tentacle.seize(ship);
+        assert!(!tentacle.is_ready());
+        assert_eq!(tentacle.release(), Some(ship));
+        // Let go, but not yet ready to seize another
+        assert!(!tentacle.is_ready());
+        tentacle.recover.tick(std::time::Duration::from_secs_f32(RECOVER_SECONDS));
+        assert!(tentacle.is_ready());
+        assert_eq!(tentacle.release(), None);
+    }
+}
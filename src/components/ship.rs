@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Marker component that identifies an entity as a ship.
 /// This is the primary marker for all vessels in the game, both player and AI-controlled.
@@ -26,8 +27,27 @@ pub struct PlayerOwned;
 #[reflect(Component)]
 pub struct Surrendered;
 
+/// Marker component for the enemy ship sighted on the High Seas that brought
+/// on a battle; the rest of her squadron sails with her.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct EncounteredShip;
+
+/// Pirate ship sent to prowl a busy trade lane.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct LaneRaider {
+    pub lane: crate::resources::LaneKey,
+}
+
+/// Ship sent by a nation to collect the bounty on the player. Hostile
+/// whatever the nation's standing with the player.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BountyHunter;
+
 /// Faction identifier for ships and ports.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Reflect)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
 pub enum FactionId {
     #[default]
     Pirates,
@@ -36,14 +56,37 @@ pub enum FactionId {
     NationC,
 }
 
+impl FactionId {
+    /// Name used in player-facing text.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            FactionId::Pirates => "The Brethren",
+            FactionId::NationA => "The Crown",
+            FactionId::NationB => "The Merchant League",
+            FactionId::NationC => "The Republic",
+        }
+    }
+
+    /// Colour of the faction's flag (RGB).
+    pub fn flag_rgb(&self) -> [u8; 3] {
+        match self {
+            FactionId::Pirates => [30, 30, 30],
+            FactionId::NationA => [170, 40, 40],
+            FactionId::NationB => [40, 80, 160],
+            FactionId::NationC => [40, 130, 70],
+        }
+    }
+}
+
 /// Component that assigns a faction to an entity.
 #[derive(Component, Debug, Default, Reflect)]
 #[reflect(Component)]
 pub struct Faction(pub FactionId);
 
-/// Ship class determines base stats and visual appearance.
+/// Hull form: how a ship handles, and the stats of the plain class built on
+/// it (see `ShipClass` for the class a ship is of).
 /// Also used as a component to identify ship type for movement/turn rate calculations.
-#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Reflect)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
 #[reflect(Component)]
 pub enum ShipType {
     /// Small, fast vessel. Low cargo, low firepower, high maneuverability.
@@ -69,13 +112,124 @@ impl ShipType {
         }
     }
 
-    /// Returns the base speed for this ship type.
+    /// Share of her speed a ship carries while coming about, so she sails
+    /// round her turning circle instead of pivoting on the spot. Light hulls
+    /// hold their way through a turn better than heavy ones.
+    pub fn turning_way(&self) -> f32 {
+        match self {
+            ShipType::Sloop => 0.45,
+            ShipType::Raft => 0.3,
+            ShipType::Schooner => 0.4,
+            ShipType::Frigate => 0.3,
+        }
+    }
+
+    /// Radius of the circle she sails turning hard at full speed: the
+    /// tightest corner her course can round.
+    pub fn turning_radius(&self) -> f32 {
+        self.base_speed() * self.turning_way() / self.turn_rate()
+    }
+
+    /// Furthest she lays over in battle, turning hard at speed or with a gale
+    /// on the beam (radians). Tall, narrow rigs heel further than beamy hulls.
+    pub fn heel_limit(&self) -> f32 {
+        match self {
+            ShipType::Sloop => 0.35,
+            ShipType::Raft => 0.12,
+            ShipType::Schooner => 0.4,
+            ShipType::Frigate => 0.22,
+        }
+    }
+
+    /// Share of her way lost each second in battle with the helm hard over.
+    pub fn rudder_drag(&self) -> f32 {
+        match self {
+            ShipType::Sloop => 0.25,
+            ShipType::Raft => 0.5,
+            ShipType::Schooner => 0.3,
+            ShipType::Frigate => 0.45,
+        }
+    }
+
+    /// How hard her keel grips the water against sliding sideways in battle,
+    /// as a share of the full lateral drag. Heavy hulls carry their way and
+    /// slide through a turn.
+    pub fn keel_grip(&self) -> f32 {
+        match self {
+            ShipType::Sloop => 0.8,
+            ShipType::Raft => 0.5,
+            ShipType::Schooner => 0.9,
+            ShipType::Frigate => 0.55,
+        }
+    }
+
+    /// Length of her hull on the world map, bow to stern (world units).
+    pub fn hull_length(&self) -> f32 {
+        match self {
+            ShipType::Raft => 28.0,
+            ShipType::Sloop => 40.0,
+            ShipType::Schooner => 52.0,
+            ShipType::Frigate => 68.0,
+        }
+    }
+
+    /// Returns the base speed for this ship type: the speed of its plain
+    /// class, which a ship of another class (see `ShipClass`) replaces.
     pub fn base_speed(&self) -> f32 {
+        crate::resources::ShipDefinition::builtin(*self).speed
+    }
+
+    /// Mast positions and yard widths for the sail overlay, in the local space
+    /// of a 64px ship sprite (bow towards +Y).
+    pub fn sail_anchors(&self) -> &'static [SailAnchor] {
+        const SLOOP: &[SailAnchor] = &[SailAnchor { offset: Vec2::new(0.0, 4.0), width: 34.0 }];
+        const RAFT: &[SailAnchor] = &[SailAnchor { offset: Vec2::new(0.0, 0.0), width: 18.0 }];
+        const SCHOONER: &[SailAnchor] = &[
+            SailAnchor { offset: Vec2::new(0.0, 12.0), width: 28.0 },
+            SailAnchor { offset: Vec2::new(0.0, -8.0), width: 30.0 },
+        ];
+        const FRIGATE: &[SailAnchor] = &[
+            SailAnchor { offset: Vec2::new(0.0, 18.0), width: 30.0 },
+            SailAnchor { offset: Vec2::new(0.0, 2.0), width: 36.0 },
+            SailAnchor { offset: Vec2::new(0.0, -14.0), width: 32.0 },
+        ];
         match self {
-            ShipType::Sloop => 300.0,
-            ShipType::Raft => 150.0,
-            ShipType::Schooner => 350.0,
-            ShipType::Frigate => 200.0,
+            ShipType::Sloop => SLOOP,
+            ShipType::Raft => RAFT,
+            ShipType::Schooner => SCHOONER,
+            ShipType::Frigate => FRIGATE,
         }
     }
+
+    /// Id of the plain ship class built on this hull in `ShipDefinitions`.
+    pub fn class_id(&self) -> &'static str {
+        match self {
+            ShipType::Sloop => "sloop",
+            ShipType::Frigate => "frigate",
+            ShipType::Schooner => "schooner",
+            ShipType::Raft => "raft",
+        }
+    }
+}
+
+/// Ship class of a ship, by its id in `ShipDefinitions`: where her speed,
+/// hull and hold come from. Her `ShipType` is the hull form she handles as.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct ShipClass(pub String);
+
+impl ShipClass {
+    /// The plain class built on `ship_type`.
+    pub fn of(ship_type: ShipType) -> Self {
+        Self(ship_type.class_id().to_string())
+    }
+}
+
+/// Where a sail is drawn on a ship sprite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SailAnchor {
+    /// Mast position relative to the sprite centre.
+    pub offset: Vec2,
+    /// Length of the yard (sail width across the hull).
+    pub width: f32,
 }
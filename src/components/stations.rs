@@ -0,0 +1,185 @@
+use bevy::prelude::*;
+
+/// Hands aboard a ship for station assignment.
+pub const DEFAULT_HANDS: u32 = 12;
+/// Hull points per second patched by each hand at the repair station.
+pub const HULL_REPAIR_PER_HAND: f32 = 0.15;
+/// Share of maximum hull the repair crew can bring a ship back to in battle.
+pub const BATTLE_REPAIR_LIMIT: f32 = 0.8;
+/// Water intake rate plugged per second by each hand at the repair station.
+pub const LEAK_PLUG_PER_HAND: f32 = 0.02;
+/// Flood water pumped out per second by each hand at the repair station.
+pub const PUMP_PER_HAND: f32 = 0.25;
+/// Fire intensity put out per second by each hand at the repair station.
+pub const FIRE_FIGHTING_PER_HAND: f32 = 0.025;
+
+/// A combat station crew can be assigned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Station {
+    Gunnery,
+    Sailing,
+    Repairs,
+}
+
+impl Station {
+    pub const ALL: [Station; 3] = [Station::Gunnery, Station::Sailing, Station::Repairs];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Station::Gunnery => "Gunnery",
+            Station::Sailing => "Sailing",
+            Station::Repairs => "Repairs",
+        }
+    }
+}
+
+/// How a ship's hands are split between combat stations.
+///
+/// An even split is the baseline: every multiplier is 1.0 there, and moving
+/// hands to one station buys its effect at the others' expense.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct CrewStations {
+    pub gunnery: u32,
+    pub sailing: u32,
+    pub repairs: u32,
+}
+
+impl Default for CrewStations {
+    fn default() -> Self {
+        Self::even(DEFAULT_HANDS)
+    }
+}
+
+impl CrewStations {
+    /// Splits `hands` as evenly as possible, any remainder to the guns.
+    pub fn even(hands: u32) -> Self {
+        let third = hands / 3;
+        Self {
+            gunnery: hands - third * 2,
+            sailing: third,
+            repairs: third,
+        }
+    }
+
+    /// Splits `hands` by weights (gunnery, sailing, repairs), keeping the total.
+    pub fn weighted(hands: u32, weights: [u32; 3]) -> Self {
+        let total: u32 = weights.iter().sum::<u32>().max(1);
+        let sailing = hands * weights[1] / total;
+        let repairs = hands * weights[2] / total;
+        Self {
+            gunnery: hands - sailing - repairs,
+            sailing,
+            repairs,
+        }
+    }
+
+    pub fn hands(&self) -> u32 {
+        self.gunnery + self.sailing + self.repairs
+    }
+
+    pub fn get(&self, station: Station) -> u32 {
+        match station {
+            Station::Gunnery => self.gunnery,
+            Station::Sailing => self.sailing,
+            Station::Repairs => self.repairs,
+        }
+    }
+
+    fn get_mut(&mut self, station: Station) -> &mut u32 {
+        match station {
+            Station::Gunnery => &mut self.gunnery,
+            Station::Sailing => &mut self.sailing,
+            Station::Repairs => &mut self.repairs,
+        }
+    }
+
+    /// Moves one hand from `from` to `to`. Returns false if `from` is empty.
+    pub fn move_hand(&mut self, from: Station, to: Station) -> bool {
+        if from == to || self.get(from) == 0 {
+            return false;
+        }
+        *self.get_mut(from) -= 1;
+        *self.get_mut(to) += 1;
+        true
+    }
+
+    /// Takes up to `count` hands out of action, each from the busiest
+    /// station. Returns how many were lost.
+    pub fn lose_hands(&mut self, count: u32) -> u32 {
+        let mut lost = 0;
+        while lost < count {
+            let Some(busiest) = Station::ALL.into_iter().max_by_key(|s| self.get(*s)) else {
+                break;
+            };
+            if self.get(busiest) == 0 {
+                break;
+            }
+            *self.get_mut(busiest) -= 1;
+            lost += 1;
+        }
+        lost
+    }
+
+    /// Share of the hands at `station`, 0.0 to 1.0.
+    pub fn share(&self, station: Station) -> f32 {
+        match self.hands() {
+            0 => 0.0,
+            hands => self.get(station) as f32 / hands as f32,
+        }
+    }
+
+    /// Multiplier on cannon reload time: 1.0 with a third of the hands on the
+    /// guns, down to 0.5 with everyone, up to 2.0 with nobody.
+    pub fn reload_multiplier(&self) -> f32 {
+        1.0 / (0.5 + 1.5 * self.share(Station::Gunnery))
+    }
+
+    /// Multiplier on thrust and turning: 1.0 with a third of the hands on
+    /// sheets and braces, 0.7 with none, 1.6 with everyone.
+    pub fn maneuver_multiplier(&self) -> f32 {
+        0.7 + 0.9 * self.share(Station::Sailing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_even_split_is_baseline() {
+        let stations = CrewStations::even(12);
+        assert_eq!(stations.hands(), 12);
+        assert!((stations.reload_multiplier() - 1.0).abs() < 1e-5);
+        assert!((stations.maneuver_multiplier() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_moving_hands_keeps_total_and_shifts_effects() {
+        let mut stations = CrewStations::even(12);
+        assert!(stations.move_hand(Station::Sailing, Station::Gunnery));
+        assert_eq!(stations.hands(), 12);
+        assert!(stations.reload_multiplier() < 1.0);
+        assert!(stations.maneuver_multiplier() < 1.0);
+
+        stations.sailing = 0;
+        assert!(!stations.move_hand(Station::Sailing, Station::Repairs));
+    }
+
+    #[test]
+    fn test_weighted_split_keeps_all_hands() {
+        let stations = CrewStations::weighted(10, [1, 3, 1]);
+        assert_eq!(stations.hands(), 10);
+        assert_eq!(stations.sailing, 6);
+        assert_eq!(stations.repairs, 2);
+        assert_eq!(stations.gunnery, 2);
+    }
+
+    #[test]
+    fn test_casualties_come_from_busiest_station() {
+        let mut stations = CrewStations { gunnery: 5, sailing: 2, repairs: 1 };
+        assert_eq!(stations.lose_hands(2), 2);
+        assert_eq!(stations.gunnery, 3);
+        assert_eq!(stations.lose_hands(10), 6);
+        assert_eq!(stations.hands(), 0);
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+/// A ghost ship sailing the High Seas at night.
+#[derive(Component, Debug, Default)]
+pub struct GhostShip;
+
+/// A cursed fog bank drifting on the High Seas.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CursedFogBank {
+    pub radius: f32,
+    /// Whether the derelict at its heart has been found.
+    pub plundered: bool,
+}
+
+/// A ship that shot cannot harm while its lanterns burn.
+///
+/// Hull hits pass straight through; shot into the rigging puts out a lantern.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Spectral {
+    /// Lanterns still lit.
+    pub lanterns: u32,
+}
+
+impl Spectral {
+    pub fn new(lanterns: u32) -> Self {
+        Self { lanterns }
+    }
+
+    /// Whether the hull still shrugs off shot.
+    pub fn is_lit(&self) -> bool {
+        self.lanterns > 0
+    }
+
+    /// Puts out a lantern. Returns true if it was the last.
+    pub fn shoot_lantern(&mut self) -> bool {
+        if self.lanterns == 0 {
+            return false;
+        }
+        self.lanterns -= 1;
+        self.lanterns == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lanterns_go_out_one_at_a_time() {
+        let mut spectral = Spectral::new(2);
+        assert!(spectral.is_lit());
+        assert!(!spectral.shoot_lantern());
+        assert!(spectral.shoot_lantern());
+        assert!(!spectral.is_lit());
+        // Nothing left to shoot out
+        assert!(!spectral.shoot_lantern());
+    }
+}
@@ -0,0 +1,207 @@
+//! Ship upgrades.
+//!
+//! Upgrades are items: bought at a shipyard, kept in the `UpgradeInventory`
+//! hold when not fitted, and installed into a hull's limited slots. Moving an
+//! upgrade between hulls is a refit at port.
+
+use bevy::prelude::*;
+
+/// Upgrade slots on every hull.
+pub const UPGRADE_SLOTS: usize = 3;
+
+/// Kinds of ship upgrade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum UpgradeKind {
+    /// Extra planking on the hull.
+    ReinforcedHull,
+    /// Copper plates below the waterline keep the hull sound for longer.
+    CopperSheathing,
+    /// Rearranged decks for more cargo.
+    ExpandedHold,
+    /// Oak knees and iron bracing under the gun deck.
+    IronBracing,
+    /// An insulated hold packed with ice and sawdust.
+    Icehouse,
+    /// A bomb vessel's mortar, bedded on the foredeck.
+    Mortar,
+    /// A fourth gun crew, so the broadside is served faster.
+    ExtraGuns,
+    /// A new suit of flax canvas, cut flatter to hold the wind.
+    ImprovedSails,
+}
+
+impl UpgradeKind {
+    /// All upgrades in shipyard display order.
+    pub fn all() -> &'static [UpgradeKind] {
+        &[
+            UpgradeKind::ReinforcedHull,
+            UpgradeKind::CopperSheathing,
+            UpgradeKind::ExpandedHold,
+            UpgradeKind::IronBracing,
+            UpgradeKind::Icehouse,
+            UpgradeKind::Mortar,
+            UpgradeKind::ExtraGuns,
+            UpgradeKind::ImprovedSails,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            UpgradeKind::ReinforcedHull => "Reinforced Hull",
+            UpgradeKind::CopperSheathing => "Copper Sheathing",
+            UpgradeKind::ExpandedHold => "Expanded Hold",
+            UpgradeKind::IronBracing => "Iron Bracing",
+            UpgradeKind::Icehouse => "Icehouse",
+            UpgradeKind::Mortar => "Mortar",
+            UpgradeKind::ExtraGuns => "Extra Guns",
+            UpgradeKind::ImprovedSails => "Improved Sails",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            UpgradeKind::ReinforcedHull => "+25 maximum hull.",
+            UpgradeKind::CopperSheathing => "+10 maximum hull.",
+            UpgradeKind::ExpandedHold => "+30 cargo capacity.",
+            UpgradeKind::IronBracing => "+15 maximum hull, +10 cargo capacity.",
+            UpgradeKind::Icehouse => "Fish and fruit keep fresh at sea.",
+            UpgradeKind::Mortar => "Lobs shells over ships and walls in battle (flagship only, M).",
+            UpgradeKind::ExtraGuns => "Broadsides reload 15% faster.",
+            UpgradeKind::ImprovedSails => "+10% speed under sail.",
+        }
+    }
+
+    /// Shipyard price in gold.
+    pub fn price(&self) -> u32 {
+        match self {
+            UpgradeKind::ReinforcedHull => 400,
+            UpgradeKind::CopperSheathing => 250,
+            UpgradeKind::ExpandedHold => 350,
+            UpgradeKind::IronBracing => 450,
+            UpgradeKind::Icehouse => 300,
+            UpgradeKind::Mortar => 600,
+            UpgradeKind::ExtraGuns => 500,
+            UpgradeKind::ImprovedSails => 450,
+        }
+    }
+
+    /// Added maximum hull points.
+    pub fn hull_bonus(&self) -> f32 {
+        match self {
+            UpgradeKind::ReinforcedHull => 25.0,
+            UpgradeKind::CopperSheathing => 10.0,
+            UpgradeKind::ExpandedHold => 0.0,
+            UpgradeKind::IronBracing => 15.0,
+            UpgradeKind::Icehouse | UpgradeKind::Mortar | UpgradeKind::ExtraGuns | UpgradeKind::ImprovedSails => 0.0,
+        }
+    }
+
+    /// Added cargo capacity.
+    pub fn cargo_bonus(&self) -> u32 {
+        match self {
+            UpgradeKind::ExpandedHold => 30,
+            UpgradeKind::IronBracing => 10,
+            _ => 0,
+        }
+    }
+
+    /// Multiplier on cannon reload time.
+    pub fn reload_multiplier(&self) -> f32 {
+        match self {
+            UpgradeKind::ExtraGuns => 0.85,
+            _ => 1.0,
+        }
+    }
+
+    /// Multiplier on speed under sail.
+    pub fn speed_multiplier(&self) -> f32 {
+        match self {
+            UpgradeKind::ImprovedSails => 1.1,
+            _ => 1.0,
+        }
+    }
+}
+
+/// What a ship's fitted upgrades do for her gunnery and sailing, carried on
+/// the ship entity. Hull and cargo bonuses go straight into `Health` and
+/// `Cargo`; these are applied where reload times and speeds are worked out.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Fittings {
+    pub reload_multiplier: f32,
+    pub speed_multiplier: f32,
+}
+
+impl Default for Fittings {
+    fn default() -> Self {
+        Self {
+            reload_multiplier: 1.0,
+            speed_multiplier: 1.0,
+        }
+    }
+}
+
+impl Fittings {
+    /// The combined effect of a set of fitted upgrades.
+    pub fn of(upgrades: &[UpgradeKind]) -> Self {
+        Self {
+            reload_multiplier: upgrades.iter().map(UpgradeKind::reload_multiplier).product(),
+            speed_multiplier: upgrades.iter().map(UpgradeKind::speed_multiplier).product(),
+        }
+    }
+}
+
+/// Total hull bonus of a set of fitted upgrades.
+pub fn upgrades_hull_bonus(upgrades: &[UpgradeKind]) -> f32 {
+    upgrades.iter().map(UpgradeKind::hull_bonus).sum()
+}
+
+/// Total cargo bonus of a set of fitted upgrades.
+pub fn upgrades_cargo_bonus(upgrades: &[UpgradeKind]) -> u32 {
+    upgrades.iter().map(UpgradeKind::cargo_bonus).sum()
+}
+
+/// Whether a hull with these upgrades keeps perishable cargo from spoiling.
+pub fn keeps_cargo_fresh(upgrades: &[UpgradeKind]) -> bool {
+    upgrades.contains(&UpgradeKind::Icehouse)
+}
+
+/// Whether a hull with these upgrades carries a mortar.
+pub fn has_mortar(upgrades: &[UpgradeKind]) -> bool {
+    upgrades.contains(&UpgradeKind::Mortar)
+}
+
+/// Comma-separated names of fitted upgrades, or "None".
+pub fn upgrade_summary(upgrades: &[UpgradeKind]) -> String {
+    if upgrades.is_empty() {
+        return "None".to_string();
+    }
+    upgrades.iter().map(UpgradeKind::name).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bonuses_add_up() {
+        let fitted = [UpgradeKind::ReinforcedHull, UpgradeKind::IronBracing, UpgradeKind::ExpandedHold];
+        assert_eq!(upgrades_hull_bonus(&fitted), 40.0);
+        assert_eq!(upgrades_cargo_bonus(&fitted), 40);
+        assert_eq!(upgrades_hull_bonus(&[]), 0.0);
+        assert_eq!(upgrade_summary(&fitted[..2]), "Reinforced Hull, Iron Bracing");
+        assert_eq!(upgrade_summary(&[]), "None");
+        assert!(!keeps_cargo_fresh(&fitted));
+        assert!(keeps_cargo_fresh(&[UpgradeKind::Icehouse]));
+        assert!(has_mortar(&[UpgradeKind::Icehouse, UpgradeKind::Mortar]));
+        assert!(!has_mortar(&fitted));
+    }
+
+    #[test]
+    fn test_fittings_combine() {
+        assert_eq!(Fittings::of(&[]), Fittings::default());
+        let fittings = Fittings::of(&[UpgradeKind::ExtraGuns, UpgradeKind::ExtraGuns, UpgradeKind::ImprovedSails]);
+        assert!((fittings.reload_multiplier - 0.85 * 0.85).abs() < 1e-5);
+        assert!((fittings.speed_multiplier - 1.1).abs() < 1e-5);
+        assert_eq!(UpgradeKind::ImprovedSails.hull_bonus(), 0.0);
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+/// A squall drifting with the wind across the High Seas.
+///
+/// Unlike the named hurricanes in `StormTracker`, squalls are entities, small
+/// and short-lived, and outlast visits to port.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct WeatherCell {
+    pub radius: f32,
+    /// In-game hours before it blows over.
+    pub hours_left: u32,
+    /// Starting angle of its ink swirl on the chart, so neighbours don't turn in step.
+    pub swirl_phase: f32,
+}
+
+impl WeatherCell {
+    /// Whether `point` is inside the cell centred on `center`.
+    pub fn contains(&self, center: Vec2, point: Vec2) -> bool {
+        center.distance(point) <= self.radius
+    }
+
+    /// Ages the cell by an in-game hour; returns whether it has blown over.
+    pub fn age_hour(&mut self) -> bool {
+        self.hours_left = self.hours_left.saturating_sub(1);
+        self.hours_left == 0
+    }
+}
+
+/// A ship caught in a squall, remembering how far she could see in clear weather.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct InWeather {
+    pub clear_radius: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_squall_covers_its_radius_and_blows_over() {
+        let mut cell = WeatherCell { radius: 200.0, hours_left: 2, swirl_phase: 0.0 };
+        let center = Vec2::new(100.0, -50.0);
+        assert!(cell.contains(center, center + Vec2::new(200.0, 0.0)));
+        assert!(!cell.contains(center, center + Vec2::new(150.0, 150.0)));
+
+        assert!(!cell.age_hour());
+        assert!(cell.age_hour());
+        assert!(cell.age_hour());
+        assert_eq!(cell.hours_left, 0);
+    }
+}
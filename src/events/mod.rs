@@ -7,6 +7,18 @@ pub struct ShipDestroyedEvent {
     pub entity: Entity,
     /// Whether this was the player's ship.
     pub was_player: bool,
+    /// Whether this was one of the player's fleet ships fighting alongside her.
+    pub was_fleet_ship: bool,
+    /// Name of the lost ship.
+    pub name: String,
+    /// Flag the ship sailed under, if any.
+    pub faction: Option<crate::components::FactionId>,
+    /// The ship whose shot sank it.
+    pub final_blow: Option<Entity>,
+    /// Each attacker's share of the damage it took, largest first.
+    pub shares: Vec<(Entity, f32)>,
+    /// Whether the player landed the final blow or an assist (see `DamageLedger`).
+    pub player_credited: bool,
 }
 
 /// Event emitted when combat ends (all enemies destroyed or player flees).
@@ -28,8 +40,8 @@ pub struct CombatTriggeredEvent {
 /// Event emitted when a trade is executed at a port.
 #[derive(Event, Debug)]
 pub struct TradeExecutedEvent {
-    /// The port entity where the trade occurred.
-    pub port_entity: Entity,
+    /// `port_key` of the port where the trade occurred.
+    pub port: IVec2,
     /// The type of good being traded.
     pub good_type: crate::components::cargo::GoodType,
     /// The quantity of goods traded.
@@ -38,6 +50,29 @@ pub struct TradeExecutedEvent {
     pub is_buy: bool,
 }
 
+/// Event emitted once a trade has gone through, with the units that
+/// actually changed hands. A `TradeExecutedEvent` may be refused or cut short.
+#[derive(Event, Debug)]
+pub struct TradeSettledEvent {
+    /// The type of good traded.
+    pub good_type: crate::components::cargo::GoodType,
+    /// The quantity of goods that changed hands.
+    pub quantity: u32,
+    /// True if bought from the port, false if sold to it.
+    pub is_buy: bool,
+}
+
+/// Event emitted when the player confirms a whole trade ticket at a port.
+///
+/// Expanded into one `TradeExecutedEvent` per good, sales before purchases.
+#[derive(Event, Debug)]
+pub struct BulkTradeEvent {
+    /// `port_key` of the port where the trade occurs.
+    pub port: IVec2,
+    /// Units of each good: positive to buy, negative to sell.
+    pub orders: Vec<(crate::components::cargo::GoodType, i32)>,
+}
+
 /// Event emitted when a player accepts a contract.
 #[derive(Event, Debug)]
 pub struct ContractAcceptedEvent {
@@ -61,10 +96,12 @@ pub struct ContractExpiredEvent {
     pub contract_entity: Entity,
     /// Whether this was an accepted contract (affects player negatively).
     pub was_accepted: bool,
+    /// The contract's terms, since the entity is despawned by the time this is read.
+    pub details: crate::components::ContractDetails,
 }
 
 /// Type of ship component to repair.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 pub enum RepairType {
     Sails,
     Rudder,
@@ -76,6 +113,82 @@ pub enum RepairType {
 pub struct RepairRequestEvent {
     /// Which component to repair.
     pub repair_type: RepairType,
+    /// Done at once for a surcharge, instead of queued with the shipwrights.
+    pub emergency: bool,
+    /// Most gold to spend; the repair stops short at what it pays for.
+    /// `None` repairs the component fully.
+    pub budget: Option<u32>,
+}
+
+/// Event emitted when the player asks to rest, in port or at anchor.
+#[derive(Event, Debug)]
+pub struct RestRequestEvent {
+    pub until: crate::resources::RestUntil,
+}
+
+/// Event emitted when the player pays to reclaim a ship scattered by a previous defeat.
+#[derive(Event, Debug)]
+pub struct ReclaimShipEvent {
+    /// Index into `MetaProfile::scattered_ships`.
+    pub index: usize,
+}
+
+/// Event emitted when the player moves goods between two holds in their fleet.
+#[derive(Event, Debug)]
+pub struct CargoTransferEvent {
+    pub from: crate::resources::CargoHold,
+    pub to: crate::resources::CargoHold,
+    pub good: crate::components::cargo::GoodType,
+    pub quantity: u32,
+}
+
+/// A shipyard job requested from the port UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefitAction {
+    /// Buy a new upgrade into the hold.
+    Buy(crate::components::upgrade::UpgradeKind),
+    /// Fit a stowed upgrade (index into `UpgradeInventory::stowed`) to a hull.
+    Install { stowed_index: usize, target: crate::resources::RefitTarget },
+    /// Take the upgrade in `slot` out of a hull and stow it.
+    Remove { target: crate::resources::RefitTarget, slot: usize },
+    /// Have the shipwright build an upgrade straight into the flagship.
+    Commission(crate::components::upgrade::UpgradeKind),
+}
+
+/// Event emitted when the player orders a shipyard job at port.
+#[derive(Event, Debug)]
+pub struct RefitEvent {
+    pub action: RefitAction,
+}
+
+/// A mooring job requested from the port UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MooringAction {
+    /// Leave a fleet ship (index into `PlayerFleet::ships`) at this port.
+    Moor(usize),
+    /// Take back a ship moored here (index into `MooredShips::at`).
+    Retrieve(usize),
+}
+
+/// Event emitted when the player moors or retrieves a ship at port.
+#[derive(Event, Debug)]
+pub struct MooringEvent {
+    pub action: MooringAction,
+}
+
+/// A deal at a port's ship market.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShipMarketAction {
+    /// Buy a new ship of this class (its id in `ShipDefinitions`) into the fleet.
+    Buy(String),
+    /// Sell a fleet ship (index into `PlayerFleet::ships`).
+    Sell(usize),
+}
+
+/// Event emitted when the player buys or sells a ship at port.
+#[derive(Event, Debug)]
+pub struct ShipMarketEvent {
+    pub action: ShipMarketAction,
 }
 
 /// Event emitted when intel is acquired by the player.
@@ -89,15 +202,28 @@ pub struct IntelAcquiredEvent {
     pub source_port: Option<Entity>,
 }
 
-/// Event emitted when a ship is hit by a projectile.
+/// Event emitted once a frame with every fog tile charted that frame.
+/// Read by the ink-reveal animation.
+#[derive(Event, Debug, Clone)]
+pub struct FogRevealedEvent {
+    /// Tiles newly explored.
+    pub tiles: Vec<IVec2>,
+}
+
+/// Event emitted when a ship is hit: by shot, a mortar shell, a ram, the
+/// rocks or a sea monster. Damage ledgers are kept from these.
 #[derive(Event, Debug)]
 pub struct ShipHitEvent {
     /// The ship entity that was hit.
     pub ship_entity: Entity,
     /// World position of the hit.
     pub hit_position: Vec2,
-    /// Damage amount dealt.
+    /// Damage the hit actually did.
     pub damage: f32,
+    /// Ship that fired the shot.
+    pub attacker: Entity,
+    /// Part of the ship the shot was aimed at.
+    pub component: crate::components::TargetComponent,
 }
 
 /// Event emitted when the player fires cannons.
@@ -105,6 +231,79 @@ pub struct ShipHitEvent {
 pub struct CannonFiredEvent {
     /// World position of the firing ship.
     pub position: Vec2,
-    /// Which side the broadside was fired from (positive = starboard, negative = port).
+    /// Which side the broadside was fired from (positive = starboard, negative = port;
+    /// zero for chasers and mortars).
     pub side: f32,
 }
+
+/// Event emitted when a first-time situation occurs that may warrant a hint.
+#[derive(Event, Debug)]
+pub struct HintTriggeredEvent {
+    /// The hint describing the situation.
+    pub hint: crate::resources::HintId,
+}
+
+/// Event emitted when the player encounters something with a codex entry.
+#[derive(Event, Debug)]
+pub struct CodexUnlockEvent {
+    /// Identifier of the codex entry to unlock.
+    pub entry_id: String,
+}
+
+/// Event emitted to open the codex, optionally at a specific entry.
+#[derive(Event, Debug)]
+pub struct CodexOpenEvent {
+    /// Entry to display, or `None` to open at the last viewed page.
+    pub entry_id: Option<String>,
+}
+
+/// Event emitted when something newsworthy happens in the world simulation.
+#[derive(Event, Debug, Clone)]
+pub struct WorldNewsEvent {
+    /// What the news is about.
+    pub category: crate::resources::NewsCategory,
+    /// One-line headline for the port news ticker.
+    pub headline: String,
+}
+
+/// Event emitted when the player sails over a `Collectible`.
+#[derive(Event, Debug)]
+pub struct CollectiblePickedUpEvent {
+    /// The collectible picked up; despawned by whatever handles its kind.
+    pub entity: Entity,
+}
+
+/// Event emitted when the player clicks the High Seas map, saying what was clicked.
+#[derive(Event, Debug, Clone)]
+pub struct PickingResult {
+    pub button: MouseButton,
+    /// Cursor position in world space.
+    pub world_pos: Vec2,
+    pub target: crate::resources::PickTarget,
+}
+
+/// Event emitted when the player's crew grapples an enemy and goes over her side.
+#[derive(Event, Debug)]
+pub struct BoardingStartedEvent {
+    pub attacker: Entity,
+    pub defender: Entity,
+}
+
+/// Event emitted for each surrendered ship when the loot screen closes,
+/// carrying the fate the player chose for her.
+#[derive(Event, Debug, Clone)]
+pub struct PrizeSettledEvent {
+    pub prize: crate::resources::Prize,
+}
+
+/// Event emitted when a boarding action is settled on deck.
+#[derive(Event, Debug)]
+pub struct BoardingResolvedEvent {
+    pub attacker: Entity,
+    pub defender: Entity,
+    pub outcome: crate::resources::BoardingOutcome,
+    /// Hands lost by the boarders.
+    pub attacker_losses: u32,
+    /// Hands lost by the defenders.
+    pub defender_losses: u32,
+}
@@ -4,6 +4,7 @@ use crate::components::ship::Ship;
 use crate::features::water::quadtree::OceanQuadtree;
 use crate::features::water::morton::{morton_decode, morton_encode};
 use crate::plugins::core::GameState;
+use crate::resources::BalanceConfig;
 
 #[derive(Default)]
 pub struct OceanPhysicsCouplingPlugin;
@@ -22,6 +23,7 @@ fn apply_ship_displacement(
     mut ocean: ResMut<OceanQuadtree>,
     ships: Query<(&GlobalTransform, &LinearVelocity, &Collider), With<Ship>>,
     time: Res<Time<Fixed>>,
+    balance: Res<BalanceConfig>,
 ) {
     let dt = time.delta_secs();
     if dt == 0.0 { return; }
@@ -50,7 +52,7 @@ fn apply_ship_displacement(
         let segment_vec = bow_pos - stern_pos;
         let segment_len_sq = segment_vec.length_squared();
         
-        let interaction_strength = 1.0; // Doubled from 0.5
+        let interaction_strength = balance.wake.displacement_force;
 
         for (&(depth, code), cell) in ocean.nodes.iter_mut() {
              let (gx, gy) = morton_decode(code);
@@ -109,6 +111,7 @@ fn apply_ship_displacement(
 fn apply_water_boudary_forces(
     ocean: Res<OceanQuadtree>,
     mut ships: Query<(&GlobalTransform, &LinearVelocity, &mut ExternalForce), With<Ship>>,
+    balance: Res<BalanceConfig>,
 ) {
     for (transform, velocity, mut force) in ships.iter_mut() {
         let ship_pos = transform.translation().truncate();
@@ -138,8 +141,7 @@ fn apply_water_boudary_forces(
         }
         
         if found {
-            // Increased from 1.0 to 5.0 for stronger wave influence
-            let drag_coeff = 5.0; 
+            let drag_coeff = balance.wake.drag_force;
             let rel_vel = sample_flow - velocity.0;
             let drag_force = rel_vel * drag_coeff;
             
@@ -20,19 +20,92 @@ use pirates::plugins::scale_bar::ScaleBarPlugin;
 use pirates::plugins::overlay_ui::OverlayUiPlugin;
 use pirates::plugins::cartouche::CartouchePlugin;
 use pirates::plugins::fade_controller::FadeControllerPlugin;
+use pirates::plugins::hints::HintsPlugin;
+use pirates::plugins::codex::CodexPlugin;
+use pirates::plugins::menu_background::MenuBackgroundPlugin;
+use pirates::plugins::map_lod::MapLodPlugin;
+use pirates::plugins::label_layout::LabelLayoutPlugin;
+use pirates::plugins::curved_labels::CurvedLabelPlugin;
+use pirates::plugins::wake::WakePlugin;
+use pirates::plugins::sails::SailsPlugin;
+use pirates::plugins::wildlife::WildlifePlugin;
+use pirates::plugins::news::NewsPlugin;
+use pirates::plugins::loot_screen::LootScreenPlugin;
+use pirates::plugins::game_over::GameOverPlugin;
+use pirates::plugins::refit::RefitPlugin;
+use pirates::plugins::cargo_ui::CargoUiPlugin;
+use pirates::plugins::storms::StormPlugin;
+use pirates::plugins::collectibles::CollectiblesPlugin;
+#[cfg(feature = "audio")]
+use pirates::plugins::music::MusicPlugin;
+#[cfg(feature = "audio")]
+use pirates::plugins::spatial_audio::SpatialAudioPlugin;
+use pirates::plugins::tactical::TacticalPlugin;
+use pirates::plugins::steering::SteeringPlugin;
+use pirates::plugins::sail_trim::SailTrimPlugin;
+use pirates::plugins::crew_stations::CrewStationsPlugin;
+use pirates::plugins::bounties::BountiesPlugin;
+use pirates::plugins::battle_summary::BattleSummaryPlugin;
+use pirates::plugins::wreck_sites::WreckSitesPlugin;
+use pirates::plugins::ai_activation::AiActivationPlugin;
+use pirates::plugins::picking::PickingPlugin;
+use pirates::plugins::save_compat::SaveCompatPlugin;
+use pirates::plugins::ui_layout::UiLayoutPlugin;
+use pirates::plugins::patrols::PatrolsPlugin;
+use pirates::plugins::supernatural::SupernaturalPlugin;
+use pirates::plugins::boarding::BoardingPlugin;
+use pirates::plugins::rest::RestPlugin;
+use pirates::plugins::wind_chart::WindChartPlugin;
+use pirates::plugins::mooring::MooringPlugin;
+use pirates::plugins::weather::WeatherPlugin;
+use pirates::plugins::agent_debug::AgentDebugPlugin;
+use pirates::plugins::privateering::PrivateeringPlugin;
+use pirates::plugins::crew::CrewPlugin;
+use pirates::plugins::ship_market::ShipMarketPlugin;
+use pirates::plugins::balance::BalancePlugin;
+use pirates::plugins::fleet_combat::FleetCombatPlugin;
+use pirates::plugins::port_events::PortEventsPlugin;
+use pirates::plugins::forts::FortsPlugin;
+use pirates::plugins::pursuit::PursuitPlugin;
+use pirates::plugins::ramming::RammingPlugin;
+use pirates::plugins::day_night::DayNightPlugin;
+use pirates::plugins::harbor_approach::HarborApproachPlugin;
+use pirates::plugins::port_visuals::PortVisualsPlugin;
+use pirates::plugins::battle_replay::BattleReplayPlugin;
+use pirates::plugins::sail_ahead::SailAheadPlugin;
+use pirates::plugins::journal::JournalPlugin;
+use pirates::plugins::heel::HeelPlugin;
+use pirates::plugins::dig_sites::DigSitesPlugin;
+use pirates::plugins::grounding::GroundingPlugin;
+use pirates::plugins::sea_monsters::SeaMonstersPlugin;
+use pirates::plugins::sea_events::SeaEventsPlugin;
+use pirates::plugins::line_of_sight::LineOfSightPlugin;
+use pirates::plugins::content::ContentPlugin;
+use pirates::plugins::pacing::PacingPlugin;
+use pirates::plugins::settings::SettingsPlugin;
 use pirates::systems::damage_effects::{
     setup_splatter_effects, spawn_damage_splatter,
 };
 use pirates::plugins::core::GameState;
-use pirates::resources::CliArgs;
+use pirates::resources::{CliArgs, RunSettings};
 
 fn main() {
     // Parse CLI arguments before building the app
     let cli_args = CliArgs::parse();
+    let run_settings = RunSettings::from_cli(&cli_args);
 
-    App::new()
-        .insert_resource(cli_args)
-        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+    let mut app = App::new();
+    app.insert_resource(cli_args)
+        .insert_resource(run_settings)
+        .add_plugins(
+            DefaultPlugins
+                .set(ImagePlugin::default_nearest())
+                // Content tables under assets/data hot-reload in dev builds
+                .set(AssetPlugin {
+                    watch_for_changes_override: Some(cfg!(debug_assertions)),
+                    ..default()
+                }),
+        )
         .add_plugins(FrameTimeDiagnosticsPlugin::default())
         .add_plugins(LogDiagnosticsPlugin::default())
         .add_plugins(TilemapPlugin)
@@ -45,24 +118,87 @@ fn main() {
         .add_plugins(PhysicsPlugin)
         .add_plugins(CombatPlugin)
         .add_plugins(WorldMapPlugin)
+        .add_plugins(MapLodPlugin)
+        .add_plugins(LabelLayoutPlugin)
+        .add_plugins(CurvedLabelPlugin)
+        .add_plugins(WakePlugin)
+        .add_plugins(SailsPlugin)
+        .add_plugins(WildlifePlugin)
+        .add_plugins(NewsPlugin)
+        .add_plugins(LootScreenPlugin)
+        .add_plugins(GameOverPlugin)
+        .add_plugins(RefitPlugin)
+        .add_plugins(CargoUiPlugin)
+        .add_plugins(StormPlugin)
+        .add_plugins(CollectiblesPlugin)
+        .add_plugins(TacticalPlugin)
+        .add_plugins(SteeringPlugin)
+        .add_plugins(SailTrimPlugin)
+        .add_plugins(CrewStationsPlugin)
+        .add_plugins(BountiesPlugin)
+        .add_plugins(BattleSummaryPlugin)
+        .add_plugins(WreckSitesPlugin)
+        .add_plugins(AiActivationPlugin)
+        .add_plugins(PickingPlugin)
+        .add_plugins(SaveCompatPlugin)
+        .add_plugins(UiLayoutPlugin)
+        .add_plugins(PatrolsPlugin)
+        .add_plugins(SupernaturalPlugin)
+        .add_plugins(BoardingPlugin)
+        .add_plugins(RestPlugin)
+        .add_plugins(WindChartPlugin)
+        .add_plugins(MooringPlugin)
+        .add_plugins(WeatherPlugin)
+        .add_plugins(AgentDebugPlugin)
+        .add_plugins(PrivateeringPlugin)
+        .add_plugins(CrewPlugin)
+        .add_plugins(ShipMarketPlugin)
+        .add_plugins(BalancePlugin)
+        .add_plugins(FleetCombatPlugin)
+        .add_plugins(PortEventsPlugin)
+        .add_plugins(FortsPlugin)
+        .add_plugins(PursuitPlugin)
+        .add_plugins(RammingPlugin)
+        .add_plugins(DayNightPlugin)
+        .add_plugins(HarborApproachPlugin)
+        .add_plugins(PortVisualsPlugin)
+        .add_plugins(BattleReplayPlugin)
+        .add_plugins(SailAheadPlugin)
+        .add_plugins(JournalPlugin)
+        .add_plugins(HeelPlugin)
+        .add_plugins(DigSitesPlugin)
+        .add_plugins(GroundingPlugin)
+        .add_plugins(SeaMonstersPlugin)
+        .add_plugins(SeaEventsPlugin)
+        .add_plugins(LineOfSightPlugin)
+        .add_plugins(ContentPlugin)
+        .add_plugins(PacingPlugin)
+        .add_plugins(SettingsPlugin)
         .add_plugins(PortPlugin)
         .add_plugins(PortUiPlugin)
         .add_plugins(FleetUiPlugin)
         .add_plugins(CompanionPlugin)
         .add_plugins(MainMenuPlugin)
+        .add_plugins(MenuBackgroundPlugin)
         .add_plugins(PersistencePlugin)
         .add_plugins(OverlayUiPlugin)
         .add_plugins(CompassRosePlugin)
         .add_plugins(ScaleBarPlugin)
         .add_plugins(CartouchePlugin)
         .add_plugins(FadeControllerPlugin)
+        .add_plugins(HintsPlugin)
+        .add_plugins(CodexPlugin)
         .add_plugins(pirates::plugins::graphics::GraphicsPlugin)
         // Particle effect systems (8.5) - Damage splatter remains, wake effects removed (now fluid sim)
         .add_systems(Startup, setup_splatter_effects)
         .add_systems(
             Update,
             spawn_damage_splatter.run_if(in_state(GameState::HighSeas).or(in_state(GameState::Combat))),
-        )
-        .run();
+        );
+
+    #[cfg(feature = "audio")]
+    app.add_plugins((MusicPlugin, SpatialAudioPlugin));
+
+    app.run();
 }
 
@@ -0,0 +1,312 @@
+//! Per-agent overlay for the F3 navmesh debug view.
+//!
+//! While landmass debug drawing is on, clicking a ship selects her as the
+//! debug agent. The overlay then draws her path corridor (her waypoints, or
+//! the straight leg to her target), the target point, and her desired and
+//! actual velocities, and a panel lists her agent state and any trouble
+//! flags: stalled, off the navmesh, target off the navmesh, or no path.
+//! Every other agent gets a faint ring so it can be found and clicked. The
+//! panel also counts the stuck-ship recoveries made (see `stuck_recovery`).
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use bevy_landmass::debug::EnableLandmassDebug;
+use bevy_landmass::prelude::*;
+
+use crate::components::{Destination, NavigationPath, Ship};
+use crate::events::PickingResult;
+use crate::plugins::core::GameState;
+use crate::resources::PickTarget;
+use crate::systems::{StuckStats, StuckWatch};
+
+/// Speed below which an agent that wants to move counts as not moving.
+const STALL_SPEED: f32 = 4.0;
+/// Desired speed above which an agent is trying to move.
+const MOVING_SPEED: f32 = 1.0;
+/// Seconds without progress before an agent is flagged as stalled.
+const STALL_SECONDS: f32 = 2.0;
+/// Seconds of travel a velocity arrow covers.
+const ARROW_SECONDS: f32 = 0.75;
+
+const DESIRED_COLOR: Color = Color::srgb(0.1, 0.7, 0.2);
+const ACTUAL_COLOR: Color = Color::srgb(0.15, 0.35, 0.9);
+const CORRIDOR_COLOR: Color = Color::srgba(0.9, 0.6, 0.1, 0.9);
+const TARGET_COLOR: Color = Color::srgb(0.85, 0.15, 0.1);
+const AGENT_RING_COLOR: Color = Color::srgba(0.3, 0.3, 0.3, 0.5);
+
+/// The agent selected for the overlay, and what has been measured of her.
+#[derive(Resource, Debug, Default)]
+pub struct AgentDebug {
+    pub selected: Option<Entity>,
+    last_position: Option<Vec2>,
+    /// Velocity measured from her movement over the last frame.
+    pub actual_velocity: Vec2,
+    /// Seconds she has wanted to move without getting anywhere.
+    pub stalled_for: f32,
+}
+
+impl AgentDebug {
+    /// Selects `entity`, or clears the selection if she was already selected.
+    pub fn toggle(&mut self, entity: Entity) {
+        let selected = (self.selected != Some(entity)).then_some(entity);
+        *self = Self { selected, ..default() };
+    }
+
+    /// Measures the selected agent's movement this frame.
+    pub fn track(&mut self, position: Vec2, desired_velocity: Vec2, delta_secs: f32) {
+        if delta_secs <= 0.0 {
+            return;
+        }
+        if let Some(last) = self.last_position {
+            self.actual_velocity = (position - last) / delta_secs;
+        }
+        self.last_position = Some(position);
+
+        let wants_to_move = desired_velocity.length() > MOVING_SPEED;
+        if wants_to_move && self.actual_velocity.length() < STALL_SPEED {
+            self.stalled_for += delta_secs;
+        } else {
+            self.stalled_for = 0.0;
+        }
+    }
+
+    pub fn is_stalled(&self) -> bool {
+        self.stalled_for >= STALL_SECONDS
+    }
+}
+
+/// Trouble flags for an agent, for the panel.
+pub fn agent_flags(state: Option<&AgentState>, stalled: bool) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+    if stalled {
+        flags.push("Stalled");
+    }
+    match state {
+        Some(AgentState::AgentNotOnNavMesh) => flags.push("Off navmesh"),
+        Some(AgentState::TargetNotOnNavMesh) => flags.push("Target off navmesh"),
+        Some(AgentState::NoPath) => flags.push("No path"),
+        Some(_) => {}
+        None => flags.push("No agent"),
+    }
+    flags
+}
+
+/// An agent as the overlay sees her.
+type DebugAgents<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        Option<&'static Name>,
+        Option<&'static AgentDesiredVelocity2d>,
+        Option<&'static AgentState>,
+        Option<&'static AgentTarget2d>,
+        Option<&'static Destination>,
+        Option<&'static NavigationPath>,
+    ),
+    With<Ship>,
+>;
+
+/// Plugin for the per-agent debug overlay.
+pub struct AgentDebugPlugin;
+
+impl Plugin for AgentDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AgentDebug>()
+            .add_systems(
+                Update,
+                (
+                    select_debug_agent.after(crate::plugins::picking::emit_picking_results),
+                    track_debug_agent,
+                    draw_agent_overlay.after(track_debug_agent),
+                    agent_debug_panel.after(track_debug_agent).after(EguiSet::InitContexts),
+                )
+                    .run_if(in_state(GameState::HighSeas))
+                    .run_if(agent_debug_enabled),
+            )
+            .add_systems(OnExit(GameState::HighSeas), clear_debug_agent);
+    }
+}
+
+/// Run condition: the F3 navmesh debug view is on.
+pub fn agent_debug_enabled(debug: Option<Res<EnableLandmassDebug>>) -> bool {
+    debug.is_some_and(|debug| debug.0)
+}
+
+/// Selects the clicked ship, or deselects her on a second click.
+fn select_debug_agent(mut picks: EventReader<PickingResult>, mut agent_debug: ResMut<AgentDebug>) {
+    for pick in picks.read() {
+        if let (MouseButton::Left, PickTarget::Ship(entity)) = (pick.button, pick.target) {
+            agent_debug.toggle(entity);
+        }
+    }
+}
+
+fn track_debug_agent(mut agent_debug: ResMut<AgentDebug>, agents: DebugAgents, time: Res<Time>) {
+    let Some(selected) = agent_debug.selected else {
+        return;
+    };
+    let Ok((_, transform, _, desired, ..)) = agents.get(selected) else {
+        // Sunk, despawned or gone dormant out of the world
+        *agent_debug = AgentDebug::default();
+        return;
+    };
+    let desired = desired.map_or(Vec2::ZERO, AgentDesiredVelocity2d::velocity);
+    agent_debug.track(transform.translation.truncate(), desired, time.delta_secs());
+}
+
+/// Draws the selected agent's corridor, target and velocities, and a ring round every other agent.
+fn draw_agent_overlay(agent_debug: Res<AgentDebug>, agents: DebugAgents, mut gizmos: Gizmos) {
+    for (entity, transform, _, desired, state, target, destination, path) in &agents {
+        let position = transform.translation.truncate();
+        if agent_debug.selected != Some(entity) {
+            if desired.is_some() {
+                gizmos.circle_2d(Isometry2d::from_translation(position), 28.0, AGENT_RING_COLOR);
+            }
+            continue;
+        }
+
+        let flagged = !agent_flags(state, agent_debug.is_stalled()).is_empty();
+        let ring = if flagged { TARGET_COLOR } else { DESIRED_COLOR };
+        gizmos.circle_2d(Isometry2d::from_translation(position), 34.0, ring);
+
+        let target_point = match target {
+            Some(AgentTarget2d::Point(point)) => Some(*point),
+            _ => destination.map(|destination| destination.target),
+        };
+        let mut corridor = vec![position];
+        if let Some(path) = path {
+            corridor.extend(path.waypoints.iter().copied());
+        }
+        corridor.extend(target_point);
+        gizmos.linestrip_2d(corridor, CORRIDOR_COLOR);
+
+        if let Some(point) = target_point {
+            let size = 14.0;
+            gizmos.line_2d(point + Vec2::new(-size, -size), point + Vec2::new(size, size), TARGET_COLOR);
+            gizmos.line_2d(point + Vec2::new(-size, size), point + Vec2::new(size, -size), TARGET_COLOR);
+        }
+
+        if let Some(desired) = desired {
+            let velocity = desired.velocity();
+            if velocity != Vec2::ZERO {
+                gizmos.arrow_2d(position, position + velocity * ARROW_SECONDS, DESIRED_COLOR);
+            }
+        }
+        if agent_debug.actual_velocity != Vec2::ZERO {
+            gizmos.arrow_2d(position, position + agent_debug.actual_velocity * ARROW_SECONDS, ACTUAL_COLOR);
+        }
+    }
+}
+
+/// Lists the selected agent's state and trouble flags.
+fn agent_debug_panel(
+    mut contexts: EguiContexts,
+    agent_debug: Res<AgentDebug>,
+    agents: DebugAgents,
+    watches: Query<&StuckWatch>,
+    stuck_stats: Res<StuckStats>,
+) {
+    egui::Window::new("Agent Debug")
+        .default_pos(egui::pos2(10.0, 300.0))
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!(
+                "Stuck recoveries: {} nudges, {} repaths, {} teleports",
+                stuck_stats.nudges, stuck_stats.repaths, stuck_stats.teleports
+            ));
+            ui.separator();
+
+            let Some((entity, transform, name, desired, state, target, destination, path)) =
+                agent_debug.selected.and_then(|selected| agents.get(selected).ok())
+            else {
+                ui.label("Click a ship to inspect her agent.");
+                return;
+            };
+
+            let position = transform.translation.truncate();
+            ui.strong(name.map_or_else(|| format!("{:?}", entity), |name| format!("{} ({:?})", name, entity)));
+            ui.label(format!("Position: ({:.0}, {:.0})", position.x, position.y));
+            ui.label(format!("Agent state: {}", state.map_or_else(|| "-".to_string(), |state| format!("{:?}", state))));
+            match target {
+                Some(AgentTarget2d::Point(point)) => ui.label(format!("Target: ({:.0}, {:.0})", point.x, point.y)),
+                Some(other) => ui.label(format!("Target: {:?}", other)),
+                None => ui.label("Target: none"),
+            };
+            if let Some(destination) = destination {
+                ui.label(format!(
+                    "Destination: {:.0} away",
+                    position.distance(destination.target)
+                ));
+            }
+            if let Some(path) = path {
+                ui.label(format!("Waypoints: {}", path.waypoints.len()));
+            }
+
+            let desired = desired.map_or(Vec2::ZERO, AgentDesiredVelocity2d::velocity);
+            ui.colored_label(egui::Color32::from_rgb(25, 180, 50), format!("Desired speed: {:.1}", desired.length()));
+            ui.colored_label(
+                egui::Color32::from_rgb(40, 90, 230),
+                format!("Actual speed: {:.1}", agent_debug.actual_velocity.length()),
+            );
+
+            let flags = agent_flags(state, agent_debug.is_stalled());
+            if flags.is_empty() {
+                ui.label("Flags: none");
+            } else {
+                ui.colored_label(egui::Color32::from_rgb(200, 40, 30), format!("Flags: {}", flags.join(", ")));
+            }
+            if let Some(watch) = watches.get(entity).ok().filter(|watch| watch.attempts > 0) {
+                ui.label(format!("Recovery attempts: {}", watch.attempts));
+            }
+        });
+}
+
+fn clear_debug_agent(mut agent_debug: ResMut<AgentDebug>) {
+    *agent_debug = AgentDebug::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_stalls_when_it_cannot_make_way() {
+        let mut debug = AgentDebug::default();
+        let desired = Vec2::new(40.0, 0.0);
+        let mut position = Vec2::ZERO;
+        for _ in 0..30 {
+            position.x += 0.01;
+            debug.track(position, desired, 0.1);
+        }
+        assert!(debug.is_stalled());
+        assert_eq!(agent_flags(Some(&AgentState::Moving), debug.is_stalled()), vec!["Stalled"]);
+
+        // Under way again
+        debug.track(position + Vec2::new(4.0, 0.0), desired, 0.1);
+        assert!(!debug.is_stalled());
+        assert!((debug.actual_velocity.x - 40.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_idle_agent_is_not_stalled() {
+        let mut debug = AgentDebug::default();
+        for _ in 0..30 {
+            debug.track(Vec2::ZERO, Vec2::ZERO, 0.1);
+        }
+        assert!(!debug.is_stalled());
+        assert_eq!(agent_flags(Some(&AgentState::NoPath), false), vec!["No path"]);
+        assert_eq!(agent_flags(None, false), vec!["No agent"]);
+    }
+
+    #[test]
+    fn test_second_click_deselects() {
+        let mut debug = AgentDebug::default();
+        let ship = Entity::from_raw(7);
+        debug.toggle(ship);
+        assert_eq!(debug.selected, Some(ship));
+        debug.toggle(ship);
+        assert_eq!(debug.selected, None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Chunk-style AI activation on the High Seas.
+//!
+//! Only AI ships within `ACTIVE_RADIUS` of the player keep their landmass
+//! agent, coastline avoidance and sprite. Past `DORMANT_RADIUS` a ship is
+//! marked `Dormant`: its agent is removed, it is hidden, and every
+//! `ABSTRACT_TICK_SECONDS` it is moved along a cached tile route to its
+//! `Destination`, as fast as the wind would carry it awake. Orders, faction AI and encounter detection work on both,
+//! since they only read `Transform`, `OrderQueue` and `Destination`.
+
+use bevy::prelude::*;
+use bevy_landmass::prelude::*;
+
+use crate::components::{Destination, NavigationPath, Player, Ship, ShipClass, ShipType};
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::{HighSeasAI, HighSeasPlayer};
+use crate::resources::landmass::{LandmassArchipelagos, ShoreBufferTier};
+use crate::resources::{AiActivation, BalanceConfig, Fidelity, MapData, RouteCache, ShipDefinitions, Wind, WindShadow};
+use crate::systems::cached_route;
+use crate::utils::path_smoothing::smooth_course;
+
+/// Plugin for promoting and demoting High Seas AI between full and abstract simulation.
+pub struct AiActivationPlugin;
+
+impl Plugin for AiActivationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AiActivation>()
+            .add_systems(Update, (
+                update_ai_fidelity,
+                plan_dormant_routes.after(update_ai_fidelity),
+                advance_dormant_ai.after(plan_dormant_routes),
+            ).run_if(in_state(GameState::HighSeas)));
+    }
+}
+
+/// A distant AI ship simulated abstractly.
+#[derive(Component, Debug)]
+pub struct Dormant {
+    /// Whether the ship had a landmass agent to give back when it wakes.
+    pub had_agent: bool,
+}
+
+type AiFidelityQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static ShipType,
+        Option<&'static ShipClass>,
+        Option<&'static Dormant>,
+        Has<AgentDesiredVelocity2d>,
+        Option<&'static Destination>,
+    ),
+    (With<HighSeasAI>, With<Ship>),
+>;
+
+/// Demotes ships that have drifted far from the player and wakes those it approaches.
+fn update_ai_fidelity(
+    mut commands: Commands,
+    mut activation: ResMut<AiActivation>,
+    player_query: Query<&Transform, (With<Player>, With<HighSeasPlayer>)>,
+    ai_query: AiFidelityQuery,
+    archipelagos: Option<Res<LandmassArchipelagos>>,
+    balance: Res<BalanceConfig>,
+    ships: Res<ShipDefinitions>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    let (mut active, mut dormant) = (0, 0);
+    for (entity, transform, ship_type, class, sleeping, has_agent, destination) in &ai_query {
+        let current = if sleeping.is_some() { Fidelity::Abstract } else { Fidelity::Full };
+        let wanted = current.at_distance(transform.translation.truncate().distance(player_pos));
+
+        match (current, wanted) {
+            (Fidelity::Full, Fidelity::Abstract) => {
+                commands
+                    .entity(entity)
+                    .remove::<(Agent2dBundle, AgentTarget2d, AgentDesiredVelocity2d)>()
+                    .insert((Dormant { had_agent: has_agent }, Visibility::Hidden));
+                // Dormant ships route over tiles; a fresh Destination triggers planning
+                if let Some(destination) = destination {
+                    commands.entity(entity).insert(destination.clone());
+                }
+            }
+            (Fidelity::Abstract, Fidelity::Full) => {
+                let mut entity_commands = commands.entity(entity);
+                entity_commands.remove::<(Dormant, NavigationPath)>();
+                if let (Some(archipelagos), Some(true)) = (&archipelagos, sleeping.map(|d| d.had_agent)) {
+                    let tier = ShoreBufferTier::from_ship_type(*ship_type);
+                    entity_commands.insert(Agent2dBundle {
+                        agent: Default::default(),
+                        settings: balance.ai_speed.agent_settings(*ship_type, ships.speed(class, *ship_type)), // AI slower than player
+                        archipelago_ref: ArchipelagoRef2d::new(archipelagos.get(tier)),
+                    });
+                    if let Some(destination) = destination {
+                        entity_commands.insert(AgentTarget2d::Point(destination.target));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        match wanted {
+            Fidelity::Full => active += 1,
+            Fidelity::Abstract => dormant += 1,
+        }
+    }
+    activation.active = active;
+    activation.dormant = dormant;
+}
+
+/// Routes dormant ships to each new destination over cached tile paths,
+/// smoothed to their turning circles.
+fn plan_dormant_routes(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform, &Destination, &ShipType), (With<Dormant>, Changed<Destination>)>,
+    map_data: Res<MapData>,
+    wind: Res<Wind>,
+    mut route_cache: ResMut<RouteCache>,
+) {
+    for (entity, transform, destination, ship_type) in &query {
+        let start = transform.translation.truncate();
+        let route = cached_route(start, destination.target, &map_data, &wind, &mut route_cache);
+        let waypoints = smooth_course(start, &route, ship_type.turning_radius(), &map_data);
+        commands.entity(entity).insert(NavigationPath { waypoints });
+    }
+}
+
+type DormantShips<'w, 's> =
+    Query<'w, 's, (Entity, &'static mut Transform, &'static mut NavigationPath, &'static ShipType, Option<&'static ShipClass>), With<Dormant>>;
+
+/// Moves dormant ships along their routes in coarse steps, feeling the wind
+/// on the leg they start each step on.
+fn advance_dormant_ai(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut activation: ResMut<AiActivation>,
+    mut query: DormantShips,
+    balance: Res<BalanceConfig>,
+    ships: Res<ShipDefinitions>,
+    wind: Res<Wind>,
+    wind_shadow: Res<WindShadow>,
+) {
+    if !activation.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let elapsed = activation.timer.duration().as_secs_f32();
+
+    for (entity, mut transform, mut path, ship_type, class) in &mut query {
+        let from = transform.translation.truncate();
+        let leg = path.next_waypoint().map_or(Vec2::ZERO, |waypoint| (waypoint - from).normalize_or_zero());
+        // Same cruising speed and wind as an active AI ship
+        let speed = balance.ai_speed.cruise_speed(ships.speed(class, *ship_type))
+            * wind.sailing_factor(leg)
+            * wind_shadow.lee_factor(from, wind.strength);
+        let to = path.advance(from, speed * elapsed);
+        let heading = to - from;
+        if heading.length_squared() > 0.0 {
+            // Ships face +Y in local space
+            transform.rotation = Quat::from_rotation_z(heading.to_angle() - std::f32::consts::FRAC_PI_2);
+        }
+        transform.translation.x = to.x;
+        transform.translation.y = to.y;
+
+        if path.is_empty() {
+            commands.entity(entity).remove::<(NavigationPath, Destination)>();
+        }
+    }
+}
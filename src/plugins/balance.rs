@@ -0,0 +1,37 @@
+//! Balance plugin: keeps AI ships' agents in step with `BalanceConfig`.
+//!
+//! `BalanceConfig` is a content table, loaded and hot-reloaded by
+//! `ContentPlugin`. Values read each frame take effect at once. AI ships'
+//! landmass agents are set up when they spawn, so their speeds are
+//! re-applied on every change to the balance, and on every change to the
+//! ship classes.
+
+use bevy::prelude::*;
+use bevy_landmass::prelude::AgentSettings;
+
+use crate::components::ship::{ShipClass, ShipType};
+use crate::plugins::worldmap::HighSeasAI;
+use crate::resources::{BalanceConfig, ShipDefinitions};
+
+/// Plugin for the tunable balance numbers.
+pub struct BalancePlugin;
+
+impl Plugin for BalancePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            apply_ai_speed_balance.run_if(resource_changed::<BalanceConfig>.or(resource_changed::<ShipDefinitions>)),
+        );
+    }
+}
+
+/// Brings AI ships' landmass agents up to the current speeds.
+fn apply_ai_speed_balance(
+    balance: Res<BalanceConfig>,
+    ships: Res<ShipDefinitions>,
+    mut agents: Query<(&mut AgentSettings, &ShipType, Option<&ShipClass>), With<HighSeasAI>>,
+) {
+    for (mut settings, ship_type, class) in &mut agents {
+        *settings = balance.ai_speed.agent_settings(*ship_type, ships.speed(class, *ship_type));
+    }
+}
@@ -0,0 +1,240 @@
+//! Bird's-eye replay of the last battle.
+//!
+//! `record_battle_trace` samples the battle into `BattleTrace` until the
+//! summary screen opens. From there the player can watch it back on a plain
+//! chart: ships as arrows with their recent wakes, cannonballs in flight and
+//! hits flaring where they struck, with play/pause, scrubbing and speed
+//! controls, or export it as JSON to share.
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::{Player, PlayerOwned, Projectile, Ship};
+use crate::events::ShipHitEvent;
+use crate::plugins::core::GameState;
+use crate::plugins::ui_theme::INK_COLOR;
+use crate::resources::{
+    BattleStats, BattleTrace, ReplayFrame, ReplayHit, ReplayShip, ReplaySide, ReplayViewer, REPLAY_SAMPLE_HZ,
+};
+
+/// Size of the replay chart in the viewer (points).
+const CHART_SIZE: egui::Vec2 = egui::vec2(520.0, 380.0);
+/// Sea room left round the ships' tracks (world units).
+const CHART_MARGIN: f32 = 80.0;
+/// Seconds of wake drawn behind each ship.
+const WAKE_SECONDS: f32 = 4.0;
+/// Seconds a hit stays marked on the chart.
+const HIT_FLARE_SECONDS: f32 = 0.6;
+/// Playback speeds offered.
+const SPEEDS: [f32; 4] = [0.5, 1.0, 2.0, 4.0];
+
+const SEA_COLOR: egui::Color32 = egui::Color32::from_rgb(226, 216, 188);
+/// Hits the player's side dealt, and took.
+const HIT_DEALT_COLOR: egui::Color32 = egui::Color32::from_rgb(220, 120, 30);
+const HIT_TAKEN_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 30, 30);
+
+/// Plugin for the battle trace and its replay viewer.
+pub struct BattleReplayPlugin;
+
+impl Plugin for BattleReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BattleTrace>()
+            .init_resource::<ReplayViewer>()
+            .add_systems(OnEnter(GameState::Combat), begin_battle_trace)
+            .add_systems(
+                Update,
+                (
+                    record_battle_trace.run_if(in_state(GameState::Combat)),
+                    battle_replay_ui
+                        .after(EguiSet::InitContexts)
+                        .run_if(in_state(GameState::Combat).or(in_state(GameState::GameOver))),
+                ),
+            );
+    }
+}
+
+fn begin_battle_trace(mut trace: ResMut<BattleTrace>, mut viewer: ResMut<ReplayViewer>) {
+    *trace = BattleTrace::default();
+    viewer.open = false;
+}
+
+/// Samples the ships and shot at `REPLAY_SAMPLE_HZ`, and every hit, until the
+/// battle is won.
+fn record_battle_trace(
+    mut trace: ResMut<BattleTrace>,
+    time: Res<Time>,
+    stats: Res<BattleStats>,
+    ships: Query<(Entity, &Transform, Has<Player>, Has<PlayerOwned>), With<Ship>>,
+    shots: Query<&Transform, With<Projectile>>,
+    mut hit_events: EventReader<ShipHitEvent>,
+    mut clock: Local<f32>,
+) {
+    if stats.open {
+        hit_events.clear();
+        return;
+    }
+    if trace.is_empty() {
+        *clock = 0.0;
+    } else {
+        *clock += time.delta_secs();
+    }
+
+    for hit in hit_events.read() {
+        let on_player_side = ships
+            .get(hit.ship_entity)
+            .is_ok_and(|(_, _, player, owned)| player || owned);
+        trace.record_hit(ReplayHit { time: *clock, position: hit.hit_position, on_player_side });
+    }
+
+    if *clock < trace.frames.len() as f32 / REPLAY_SAMPLE_HZ {
+        return;
+    }
+    let ships = ships
+        .iter()
+        .map(|(entity, transform, player, owned)| {
+            let bow = (transform.rotation * Vec3::Y).truncate();
+            ReplayShip {
+                id: entity.index(),
+                side: match (player, owned) {
+                    (true, _) => ReplaySide::Player,
+                    (false, true) => ReplaySide::Fleet,
+                    (false, false) => ReplaySide::Enemy,
+                },
+                position: transform.translation.truncate(),
+                heading: bow.y.atan2(bow.x),
+            }
+        })
+        .collect();
+    let shots = shots.iter().map(|transform| transform.translation.truncate()).collect();
+    trace.record(ReplayFrame { time: *clock, ships, shots });
+}
+
+/// The replay window, opened from the battle summary, after a battle won or
+/// from the game over screen after one lost.
+fn battle_replay_ui(
+    mut contexts: EguiContexts,
+    trace: Res<BattleTrace>,
+    mut viewer: ResMut<ReplayViewer>,
+    time: Res<Time<Real>>,
+) {
+    if !viewer.open {
+        return;
+    }
+    let duration = trace.duration();
+    viewer.advance(time.delta_secs(), duration);
+
+    let mut open = true;
+    egui::Window::new("Battle Replay")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            if trace.is_empty() {
+                ui.label("Nothing of this battle was recorded.");
+                return;
+            }
+
+            let (response, painter) = ui.allocate_painter(CHART_SIZE, egui::Sense::hover());
+            paint_replay(&painter, response.rect, &trace, viewer.time);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                let label = if viewer.playing { "⏸" } else { "▶" };
+                if ui.button(label).clicked() {
+                    if !viewer.playing && viewer.time >= duration {
+                        viewer.time = 0.0;
+                    }
+                    viewer.playing = !viewer.playing;
+                }
+                let scrub = ui.add(egui::Slider::new(&mut viewer.time, 0.0..=duration).show_value(false));
+                if scrub.dragged() {
+                    viewer.playing = false;
+                }
+                ui.label(format!("{} / {}", clock_text(viewer.time), clock_text(duration)));
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Speed:");
+                for speed in SPEEDS {
+                    ui.selectable_value(&mut viewer.speed, speed, format!("{}×", speed));
+                }
+                ui.separator();
+                if ui.button("💾 Export").on_hover_text("Write the replay out as JSON to share").clicked() {
+                    viewer.export_note = Some(match trace.export() {
+                        Ok(path) => format!("Saved to {}", path.display()),
+                        Err(e) => e,
+                    });
+                }
+            });
+            if let Some(note) = &viewer.export_note {
+                ui.small(note.as_str());
+            }
+        });
+    if !open {
+        viewer.open = false;
+    }
+}
+
+/// Draws the battle as it stood at `time` into `rect`.
+fn paint_replay(painter: &egui::Painter, rect: egui::Rect, trace: &BattleTrace, time: f32) {
+    painter.rect(rect, 2.0, SEA_COLOR, egui::Stroke::new(1.0, INK_COLOR));
+    let (Some(index), Some(bounds)) = (trace.index_at(time), trace.bounds()) else {
+        return;
+    };
+
+    // World to chart, north up, the whole battle in view
+    let bounds = bounds.inflate(CHART_MARGIN);
+    let scale = (rect.width() / bounds.width()).min(rect.height() / bounds.height());
+    let centre = bounds.center();
+    let to_chart = |position: Vec2| {
+        let offset = (position - centre) * scale;
+        rect.center() + egui::vec2(offset.x, -offset.y)
+    };
+
+    // Wakes over the last few seconds
+    let mut wakes: HashMap<u32, (ReplaySide, Vec<egui::Pos2>)> = HashMap::new();
+    for frame in trace.frames[..=index].iter().filter(|frame| time - frame.time <= WAKE_SECONDS) {
+        for ship in &frame.ships {
+            wakes.entry(ship.id).or_insert((ship.side, Vec::new())).1.push(to_chart(ship.position));
+        }
+    }
+    for (side, points) in wakes.into_values() {
+        painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, side_colour(side).gamma_multiply(0.4))));
+    }
+
+    let frame = &trace.frames[index];
+    for shot in &frame.shots {
+        painter.circle_filled(to_chart(*shot), 1.5, INK_COLOR);
+    }
+    for (hit, age) in trace.recent_hits(time, HIT_FLARE_SECONDS) {
+        let fade = 1.0 - age / HIT_FLARE_SECONDS;
+        let colour = if hit.on_player_side { HIT_TAKEN_COLOR } else { HIT_DEALT_COLOR };
+        let radius = 3.0 + 6.0 * (1.0 - fade);
+        painter.circle_stroke(to_chart(hit.position), radius, egui::Stroke::new(1.5, colour.gamma_multiply(fade)));
+    }
+    for ship in &frame.ships {
+        let at = to_chart(ship.position);
+        let bow = egui::vec2(ship.heading.cos(), -ship.heading.sin());
+        let beam = egui::vec2(-bow.y, bow.x);
+        painter.add(egui::Shape::convex_polygon(
+            vec![at + bow * 8.0, at - bow * 5.0 + beam * 4.0, at - bow * 5.0 - beam * 4.0],
+            side_colour(ship.side),
+            egui::Stroke::new(1.0, INK_COLOR),
+        ));
+    }
+}
+
+fn side_colour(side: ReplaySide) -> egui::Color32 {
+    match side {
+        ReplaySide::Player => egui::Color32::from_rgb(40, 80, 160),
+        ReplaySide::Fleet => egui::Color32::from_rgb(40, 130, 70),
+        ReplaySide::Enemy => egui::Color32::from_rgb(170, 40, 40),
+    }
+}
+
+/// Battle time as minutes and seconds.
+fn clock_text(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
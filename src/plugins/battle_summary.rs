@@ -0,0 +1,219 @@
+//! Battle summary screen.
+//!
+//! `BattleStats` is reset as each battle is joined and tallied from shots,
+//! hits and sinkings while it lasts. Once the battle is won (after the loot
+//! screen, if there was anything to loot), or the enemy has got away, the
+//! summary shows damage dealt and taken per component, gunnery accuracy,
+//! loot, prizes and XP, offers a replay of the battle (see `battle_replay`),
+//! and closing it returns to the High Seas. A battle lost can be looked over
+//! the same way from the game over screen.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::{Cargo, Gold, Player, Projectile, Ship};
+use crate::events::{ShipDestroyedEvent, ShipHitEvent};
+use crate::plugins::core::GameState;
+use crate::resources::{BattleOutcome, BattleStats, Journal, JournalCategory, ReplayViewer, WorldClock};
+
+/// Plugin for the per-battle tally and the summary screen.
+pub struct BattleSummaryPlugin;
+
+impl Plugin for BattleSummaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Combat), begin_battle_stats)
+            .add_systems(Update, (
+                tally_battle_events,
+                battle_summary_ui.after(EguiSet::InitContexts),
+            ).run_if(in_state(GameState::Combat)))
+            .add_systems(OnEnter(GameState::GameOver), close_battle_summary)
+            .add_systems(
+                Update,
+                lost_battle_summary_ui
+                    .after(EguiSet::InitContexts)
+                    .run_if(in_state(GameState::GameOver)),
+            );
+    }
+}
+
+fn begin_battle_stats(
+    mut stats: ResMut<BattleStats>,
+    player_query: Query<(Option<&Gold>, Option<&Cargo>), (With<Player>, With<Ship>)>,
+) {
+    let (gold, cargo) = player_query.get_single().unwrap_or((None, None));
+    *stats = BattleStats::begin(gold.map_or(0, |g| g.0), cargo.map_or(0, Cargo::total_units));
+}
+
+/// Counts the player's shots, hits both ways and enemies sunk.
+fn tally_battle_events(
+    mut stats: ResMut<BattleStats>,
+    mut hit_events: EventReader<ShipHitEvent>,
+    mut destroyed_events: EventReader<ShipDestroyedEvent>,
+    new_projectiles: Query<&Projectile, Added<Projectile>>,
+    player_query: Query<Entity, (With<Player>, With<Ship>)>,
+) {
+    let player = player_query.get_single().ok();
+
+    stats.shots_fired += new_projectiles
+        .iter()
+        .filter(|projectile| Some(projectile.source) == player)
+        .count() as u32;
+
+    for hit in hit_events.read() {
+        if Some(hit.attacker) == player {
+            stats.dealt.add(hit.component, hit.damage);
+            stats.shots_hit += 1;
+        }
+        if Some(hit.ship_entity) == player {
+            stats.taken.add(hit.component, hit.damage);
+        }
+    }
+
+    for event in destroyed_events.read() {
+        if event.was_player {
+            stats.outcome = BattleOutcome::Lost;
+            continue;
+        }
+        if event.was_fleet_ship {
+            stats.record_lost(event.name.clone());
+            continue;
+        }
+        let player_share = event
+            .shares
+            .iter()
+            .filter(|(attacker, _)| Some(*attacker) == player)
+            .map(|(_, share)| *share)
+            .sum();
+        stats.record_sunk(event.name.clone(), player_share);
+    }
+}
+
+/// The summary window; its button returns to the High Seas.
+fn battle_summary_ui(
+    mut contexts: EguiContexts,
+    stats: Res<BattleStats>,
+    player_query: Query<(Option<&Gold>, Option<&Cargo>), (With<Player>, With<Ship>)>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+    mut replay: ResMut<ReplayViewer>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !stats.open {
+        return;
+    }
+    let (gold, cargo) = player_query.get_single().unwrap_or((None, None));
+    let gold_gained = gold.map_or(0, |g| g.0).saturating_sub(stats.gold_at_start);
+    let cargo_gained = cargo.map_or(0, Cargo::total_units).saturating_sub(stats.cargo_at_start);
+
+    let mut dismissed = false;
+    egui::Window::new("Battle Summary")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(contexts.ctx_mut(), |ui| {
+            summary_body(ui, &stats, Some((gold_gained, cargo_gained)));
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.button("📽 Watch the replay").clicked() {
+                    replay.open();
+                }
+                if ui.button("⛵ Return to the High Seas").clicked() {
+                    dismissed = true;
+                }
+            });
+        });
+
+    // The summary stays open until the scene changes, so victory is not handled twice
+    if dismissed {
+        replay.open = false;
+        journal.record_as(&world_clock, JournalCategory::Combat, stats.journal_line());
+        next_state.set(GameState::HighSeas);
+    }
+}
+
+/// The summary of the battle that sank the flagship, opened from the game
+/// over screen. There is no loot to show.
+fn lost_battle_summary_ui(mut contexts: EguiContexts, mut stats: ResMut<BattleStats>, mut replay: ResMut<ReplayViewer>) {
+    if !stats.open {
+        return;
+    }
+
+    let mut open = true;
+    egui::Window::new("Battle Summary")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(contexts.ctx_mut(), |ui| {
+            summary_body(ui, &stats, None);
+            ui.separator();
+            if ui.button("📽 Watch the replay").clicked() {
+                replay.open();
+            }
+        });
+    if !open {
+        stats.open = false;
+        replay.open = false;
+    }
+}
+
+/// The summary is only opened from the game over screen on request.
+fn close_battle_summary(mut stats: ResMut<BattleStats>, mut replay: ResMut<ReplayViewer>) {
+    stats.open = false;
+    replay.open = false;
+}
+
+/// The damage table and the tally of the battle.
+fn summary_body(ui: &mut egui::Ui, stats: &BattleStats, loot: Option<(u32, u32)>) {
+    egui::Grid::new("battle_damage_grid")
+        .num_columns(3)
+        .striped(true)
+        .min_col_width(80.0)
+        .show(ui, |ui| {
+            ui.strong("Damage");
+            ui.strong("Dealt");
+            ui.strong("Taken");
+            ui.end_row();
+            damage_row(ui, "Hull", stats.dealt.hull, stats.taken.hull);
+            damage_row(ui, "Sails", stats.dealt.sails, stats.taken.sails);
+            damage_row(ui, "Rudder", stats.dealt.rudder, stats.taken.rudder);
+            damage_row(ui, "Total", stats.dealt.total(), stats.taken.total());
+        });
+    ui.separator();
+
+    ui.label(format!(
+        "🎯 Gunnery: {} of {} shots hit ({:.0}%)",
+        stats.shots_hit,
+        stats.shots_fired,
+        stats.accuracy() * 100.0
+    ));
+    if let Some((gold_gained, cargo_gained)) = loot {
+        ui.label(format!("💰 Loot: {} gold, {} units of cargo", gold_gained, cargo_gained));
+    }
+    ui.label(format!("☠ Sunk: {}", name_list(&stats.sunk)));
+    ui.label(format!("⚑ Captured: {}", name_list(&stats.captured)));
+    if !stats.lost.is_empty() {
+        ui.label(format!("⚓ Lost: {}", name_list(&stats.lost)));
+    }
+    if !stats.escaped.is_empty() {
+        ui.label(format!("🏳 Got away: {}", name_list(&stats.escaped)));
+    }
+    ui.label(format!("⭐ Experience: {} XP", stats.xp));
+}
+
+fn damage_row(ui: &mut egui::Ui, label: &str, dealt: f32, taken: f32) {
+    ui.label(label);
+    ui.label(format!("{:.0}", dealt));
+    ui.label(format!("{:.0}", taken));
+    ui.end_row();
+}
+
+fn name_list(names: &[String]) -> String {
+    if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
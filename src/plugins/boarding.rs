@@ -0,0 +1,316 @@
+//! Boarding actions in combat.
+//!
+//! With an enemy alongside and both ships nearly stopped, or fouled together
+//! after a ram, G (gamepad North) throws the grapnels across. The ships are held together while the melee
+//! runs, round by round: each round the captain calls a volley, a charge or a
+//! parley before the time runs out, or the boarders charge unbidden, and crew
+//! strength settles who wins it (see `resources::boarding`). A carried
+//! deck makes the enemy strike her colours: she becomes `Surrendered`, so the
+//! victory spoils plunder her hold and take her into the `PlayerFleet`. A
+//! repelled party loses hands and must regroup before trying again.
+
+use avian2d::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use leafwing_input_manager::prelude::*;
+use rand::Rng;
+
+use crate::components::companion::CompanionRole;
+use crate::components::stations::CrewStations;
+use crate::components::{Cargo, CombatEntity, Crew, GoodType, Entangled, Health, Player, PlayerOwned, Ship, Surrendered, AI};
+use crate::events::{BoardingResolvedEvent, BoardingStartedEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::input::PlayerAction;
+use crate::resources::{
+    boarding_casualties, can_board, crew_morale, musket_share, BoardingAction, BoardingOutcome, BoardingState,
+    BoardingTactic, Journal, JournalCategory, Melee, MeleeCrews, WorldClock, BOARDING_COOLDOWN, MELEE_ROUNDS,
+};
+
+/// Plugin for boarding actions.
+pub struct BoardingPlugin;
+
+impl Plugin for BoardingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BoardingState>()
+            .add_event::<BoardingStartedEvent>()
+            .add_event::<BoardingResolvedEvent>()
+            .add_systems(Update, (
+                start_boarding,
+                grapple_ships.after(start_boarding),
+                resolve_boarding.after(grapple_ships),
+                boarding_ui.after(EguiSet::InitContexts).before(resolve_boarding),
+            ).run_if(in_state(GameState::Combat)))
+            .add_systems(OnExit(GameState::Combat), reset_boarding);
+    }
+}
+
+/// Enemy ships that can still be boarded.
+pub type BoardableFilter = (With<Ship>, With<AI>, With<CombatEntity>, Without<Surrendered>, Without<Player>, Without<PlayerOwned>);
+
+/// The player's ship, as her crew squares up for a boarding.
+type BoardersQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Health, Option<&'static mut CrewStations>, Option<&'static Cargo>, Option<&'static Crew>),
+    (With<Ship>, With<Player>),
+>;
+
+/// Ships whose crews can be boarded.
+type DefendersQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Health, Option<&'static mut CrewStations>, Option<&'static Name>, Option<&'static Crew>),
+    (With<Ship>, Without<Player>),
+>;
+
+/// The two crews in a boarding action, the companions leading the player's,
+/// and the muskets in the player's hold.
+#[derive(SystemParam)]
+struct Crews<'w, 's> {
+    player: BoardersQuery<'w, 's>,
+    enemies: DefendersQuery<'w, 's>,
+    companions: Query<'w, 's, &'static CompanionRole>,
+}
+
+impl Crews<'_, '_> {
+    /// The player's crew squared up against `defender`'s. Each side fights
+    /// in the spirits of its `Crew`, or by the state of its hull if it has none.
+    fn square_up(&self, defender: Entity) -> Option<MeleeCrews> {
+        let (_, player_health, player_stations, cargo, player_crew) = self.player.get_single().ok()?;
+        let (enemy_health, enemy_stations, _, enemy_crew) = self.enemies.get(defender).ok()?;
+        let attacker_hands = player_stations.map_or(0, |s| s.hands());
+        let weapons = cargo.map_or(0, |cargo| cargo.get(GoodType::Weapons));
+        Some(MeleeCrews {
+            attacker_hands,
+            attacker_morale: player_crew.map_or_else(|| crew_morale(player_health.hull_ratio()), |crew| crew.morale),
+            defender_hands: enemy_stations.map_or(0, |s| s.hands()),
+            defender_morale: enemy_crew.map_or_else(|| crew_morale(enemy_health.hull_ratio()), |crew| crew.morale),
+            companions: self.companions.iter().copied().collect(),
+            muskets: musket_share(weapons, attacker_hands),
+        })
+    }
+}
+
+/// Enemy ships that can still be boarded, and whom each is fouled with.
+pub type Boardable<'w, 's> =
+    Query<'w, 's, (Entity, &'static Transform, &'static LinearVelocity, Option<&'static Entangled>), BoardableFilter>;
+
+/// The nearest enemy that can be grappled from the player's ship, if any.
+/// A ship fouled with the player's can always be boarded. Boarding is offered
+/// (and the first-boarding hint shown) whenever there is one.
+pub fn boarding_target(
+    player_query: &Query<(Entity, &Transform, &LinearVelocity), (With<Ship>, With<Player>)>,
+    enemy_query: &Boardable,
+) -> Option<(Entity, Entity)> {
+    let (player, player_transform, player_velocity) = player_query.get_single().ok()?;
+    let player_pos = player_transform.translation.truncate();
+    enemy_query
+        .iter()
+        .map(|(entity, transform, velocity, entangled)| {
+            let fouled = entangled.is_some_and(|entangled| entangled.with == player);
+            (entity, transform.translation.truncate().distance(player_pos), velocity, fouled)
+        })
+        .filter(|(_, distance, velocity, fouled)| {
+            *fouled || can_board(*distance, player_velocity.length(), velocity.length())
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(enemy, ..)| (player, enemy))
+}
+
+/// G throws the grapnels at the nearest enemy alongside.
+fn start_boarding(
+    action_query: Query<&ActionState<PlayerAction>>,
+    time: Res<Time>,
+    mut state: ResMut<BoardingState>,
+    player_query: Query<(Entity, &Transform, &LinearVelocity), (With<Ship>, With<Player>)>,
+    enemy_query: Boardable,
+    crews: Crews,
+    mut started: EventWriter<BoardingStartedEvent>,
+) {
+    state.cooldown = (state.cooldown - time.delta_secs()).max(0.0);
+    let Ok(action_state) = action_query.get_single() else {
+        return;
+    };
+    if !action_state.just_pressed(&PlayerAction::Board) || state.is_boarding() {
+        return;
+    }
+    if state.cooldown > 0.0 {
+        info!("The boarders are still regrouping ({:.0}s)", state.cooldown);
+        return;
+    }
+    let Some((attacker, defender)) = boarding_target(&player_query, &enemy_query) else {
+        info!("No enemy alongside and slow enough to board");
+        return;
+    };
+
+    let Some(melee_crews) = crews.square_up(defender) else {
+        return;
+    };
+
+    state.action = Some(BoardingAction { defender, melee: Melee::new(melee_crews) });
+    started.send(BoardingStartedEvent { attacker, defender });
+    info!("Grapnels away - boarders over the side!");
+}
+
+/// Grappled ships drift together and go nowhere while the melee lasts.
+fn grapple_ships(
+    state: Res<BoardingState>,
+    mut ship_query: Query<(Entity, &mut LinearVelocity, Has<Player>), With<Ship>>,
+) {
+    let Some(action) = &state.action else {
+        return;
+    };
+    for (entity, mut velocity, is_player) in &mut ship_query {
+        if is_player || entity == action.defender {
+            velocity.0 = Vec2::ZERO;
+        }
+    }
+}
+
+/// Fights each round of the melee as it is called, or when its time runs
+/// out, and settles the melee once it is won or lost.
+fn resolve_boarding(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut state: ResMut<BoardingState>,
+    mut crews: Crews,
+    mut resolved: EventWriter<BoardingResolvedEvent>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    let Some(action) = state.action.as_mut() else {
+        return;
+    };
+    let melee = &mut action.melee;
+    let called = melee.called;
+    if called.is_none() && !melee.timer.tick(time.delta()).finished() {
+        return;
+    }
+    // Left to themselves, the boarders charge
+    let tactic = called.unwrap_or(BoardingTactic::Charge);
+    let Some(outcome) = melee.fight_round(tactic, rand::thread_rng().gen::<f32>()) else {
+        return;
+    };
+    let ferocity = melee.ferocity();
+    let rounds = melee.round;
+    let defender = action.defender;
+    state.action = None;
+
+    let (Ok((attacker, _, player_stations, ..)), Ok((_, enemy_stations, enemy_name, _))) =
+        (crews.player.get_single_mut(), crews.enemies.get_mut(defender))
+    else {
+        // One side went down mid-melee
+        return;
+    };
+    let attacker_hands = player_stations.as_ref().map_or(0, |s| s.hands());
+    let defender_hands = enemy_stations.as_ref().map_or(0, |s| s.hands());
+    let (attacker_losses, defender_losses) = boarding_casualties(outcome, attacker_hands, defender_hands, ferocity);
+    if let Some(mut stations) = player_stations {
+        stations.lose_hands(attacker_losses);
+    }
+    if let Some(mut stations) = enemy_stations {
+        stations.lose_hands(defender_losses);
+    }
+
+    let enemy_name = enemy_name.map(|name| name.as_str()).unwrap_or("the enemy");
+    match outcome {
+        BoardingOutcome::Captured => {
+            // Her helm is ours: she stops dead and her captain's orders stop with her
+            commands.entity(defender).insert((Surrendered, ExternalForce::default(), ExternalTorque::default()));
+            let how = if tactic == BoardingTactic::Parley {
+                "called on her crew to strike"
+            } else {
+                "carried her deck"
+            };
+            let entry = format!(
+                "Boarded {} and {}. She struck her colours; we lost {} hands.",
+                enemy_name, how, attacker_losses
+            );
+            journal.record_as(&world_clock, JournalCategory::Combat, entry);
+        }
+        BoardingOutcome::Repelled => {
+            state.cooldown = BOARDING_COOLDOWN;
+            journal.record_as(
+                &world_clock,
+                JournalCategory::Combat,
+                format!("Our boarders were thrown back from {}'s deck with the loss of {} hands.", enemy_name, attacker_losses),
+            );
+        }
+    }
+    info!("Boarding {:?} after {} rounds", outcome, rounds);
+    resolved.send(BoardingResolvedEvent { attacker, defender, outcome, attacker_losses, defender_losses });
+}
+
+/// The melee in progress and the tactics to call, or a prompt when an enemy
+/// can be boarded.
+fn boarding_ui(
+    mut contexts: EguiContexts,
+    mut state: ResMut<BoardingState>,
+    player_query: Query<(Entity, &Transform, &LinearVelocity), (With<Ship>, With<Player>)>,
+    enemy_query: Boardable,
+) {
+    if let Some(action) = state.action.as_mut() {
+        let melee = &mut action.melee;
+        egui::Window::new("Boarding!")
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 120.0])
+            .resizable(false)
+            .collapsible(false)
+            .show(contexts.ctx_mut(), |ui| {
+                ui.label(format!(
+                    "Round {} of {}. {} of ours against {} of theirs.",
+                    melee.round + 1,
+                    MELEE_ROUNDS,
+                    melee.crews.attacker_hands,
+                    melee.crews.defender_hands
+                ));
+                ui.horizontal(|ui| {
+                    ui.label("Her deck");
+                    ui.add(egui::ProgressBar::new(melee.momentum).text(format!("{:.0}% ours", melee.momentum * 100.0)));
+                });
+                match melee.last {
+                    Some((tactic, true)) => ui.label(format!("Our {} gained ground.", tactic.name().to_lowercase())),
+                    Some((BoardingTactic::Parley, false)) => ui.label("They would not hear of striking, and rallied."),
+                    Some((tactic, false)) => ui.label(format!("Our {} was beaten back.", tactic.name().to_lowercase())),
+                    None => ui.label("Cutlasses out - the crews are fighting for her deck."),
+                };
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    for tactic in BoardingTactic::ALL {
+                        let odds = melee.crews.round_odds(tactic, melee.momentum);
+                        if ui
+                            .button(format!("{} ({:.0}%)", tactic.name(), odds * 100.0))
+                            .on_hover_text(tactic.description())
+                            .clicked()
+                        {
+                            melee.called = Some(tactic);
+                        }
+                    }
+                });
+                ui.add(
+                    egui::ProgressBar::new(1.0 - melee.timer.fraction())
+                        .text(format!("The boarders charge unbidden in {:.1}s", melee.timer.remaining_secs())),
+                );
+            });
+        return;
+    }
+
+    let text = if state.cooldown > 0.0 {
+        format!("Boarders regrouping ({:.0}s)", state.cooldown)
+    } else if boarding_target(&player_query, &enemy_query).is_some() {
+        "G - Board her!".to_string()
+    } else {
+        return;
+    };
+
+    egui::Area::new(egui::Id::new("boarding_prompt"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -96.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.colored_label(egui::Color32::from_rgb(235, 225, 200), text);
+        });
+}
+
+fn reset_boarding(mut state: ResMut<BoardingState>) {
+    *state = BoardingState::default();
+}
@@ -0,0 +1,201 @@
+//! Faction bounties for sinking outlaws.
+//!
+//! Every ship the player sinks or assists in sinking is checked against `bounty_for`;
+//! kills that carry a bounty become `PendingBounties` claims. Docking at a port
+//! that honours them pays the claims into the player's purse, and
+//! the Contracts tab lists what was paid and what is still owed.
+//!
+//! Nations also send bounty hunters after a wanted player (see
+//! `bounty_hunter_system`). They are kept in `PendingBounties` while the
+//! player is off the High Seas, and sail again on return, until one closes
+//! with the player and the two fight it out.
+
+use bevy::prelude::*;
+
+use crate::components::{BountyHunter, Faction, Gold, Player, Ship, ShipType};
+use crate::events::{CombatTriggeredEvent, ShipDestroyedEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::CurrentPort;
+use crate::plugins::run_state::dock_flagship;
+use crate::plugins::worldmap::{spawn_high_seas_player, HighSeasPlayer};
+use crate::resources::{HunterAtSea, Journal, PendingBounties, PendingRunRestore, ShipDefinitions, WorldClock};
+use crate::systems::spawn_bounty_hunter;
+
+/// Plugin for crediting and paying faction bounties.
+pub struct BountiesPlugin;
+
+impl Plugin for BountiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingBounties>()
+            .add_systems(Update, credit_bounties)
+            .add_systems(OnEnter(GameState::Port), pay_bounties.after(dock_flagship))
+            .add_systems(OnExit(GameState::Port), clear_paid_bounties)
+            .add_systems(Update, hunter_closes_in.run_if(in_state(GameState::HighSeas)))
+            // A quickload leaves the High Seas too; the loaded hunters stand
+            .add_systems(
+                OnExit(GameState::HighSeas),
+                chart_bounty_hunters.run_if(not(resource_exists::<PendingRunRestore>)),
+            )
+            .add_systems(OnEnter(GameState::HighSeas), return_bounty_hunters.after(spawn_high_seas_player))
+            .add_systems(OnEnter(GameState::MainMenu), reset_bounties);
+    }
+}
+
+/// Credits a claim for each bountied ship the player sinks.
+fn credit_bounties(
+    mut events: EventReader<ShipDestroyedEvent>,
+    world_clock: Res<WorldClock>,
+    mut bounties: ResMut<PendingBounties>,
+) {
+    for event in events.read() {
+        if !event.player_credited {
+            continue;
+        }
+        let Some(victim) = event.faction else {
+            continue;
+        };
+        if let Some(amount) = bounties.credit(event.name.clone(), victim, world_clock.day) {
+            info!("Bounty of {} gold owed for sinking {}", amount, event.name);
+        }
+    }
+}
+
+/// Pays every claim the port honours on docking.
+fn pay_bounties(
+    current_port: Res<CurrentPort>,
+    world_clock: Res<WorldClock>,
+    mut bounties: ResMut<PendingBounties>,
+    mut journal: ResMut<Journal>,
+    mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
+) {
+    let Some(port_faction) = current_port.faction else {
+        return;
+    };
+    let Ok(mut gold) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let total = bounties.settle(port_faction);
+    if total > 0 {
+        gold.add(total);
+        journal.record(
+            &world_clock,
+            format!(
+                "Collected {} gold from {} in bounties for {} ship(s).",
+                total,
+                port_faction.display_name(),
+                bounties.paid.len()
+            ),
+        );
+    }
+}
+
+/// A hunter that has found the player is off the books: she is fought in the
+/// battle that follows, and gone from the High Seas with it.
+fn hunter_closes_in(
+    mut commands: Commands,
+    mut combat_events: EventReader<CombatTriggeredEvent>,
+    hunters: Query<(), With<BountyHunter>>,
+) {
+    for event in combat_events.read() {
+        if hunters.contains(event.enemy_entity) {
+            commands.entity(event.enemy_entity).despawn_recursive();
+        }
+    }
+}
+
+/// Notes where each hunter still at sea is as the player leaves the High Seas.
+fn chart_bounty_hunters(
+    mut bounties: ResMut<PendingBounties>,
+    hunters: Query<(&Faction, &ShipType, &Transform), With<BountyHunter>>,
+) {
+    bounties.hunters = hunters
+        .iter()
+        .map(|(faction, ship_type, transform)| HunterAtSea {
+            faction: faction.0,
+            ship_type: *ship_type,
+            position: transform.translation.truncate(),
+        })
+        .collect();
+}
+
+/// Puts the hunters still after the player back to sea.
+fn return_bounty_hunters(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ships: Res<ShipDefinitions>,
+    bounties: Res<PendingBounties>,
+    player_query: Query<Entity, With<HighSeasPlayer>>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    for hunter in &bounties.hunters {
+        let texture_handle = asset_server.load(ships.enemy_sprite(hunter.ship_type));
+        spawn_bounty_hunter(&mut commands, texture_handle, hunter, player);
+    }
+}
+
+fn clear_paid_bounties(mut bounties: ResMut<PendingBounties>) {
+    bounties.paid.clear();
+}
+
+fn reset_bounties(mut bounties: ResMut<PendingBounties>) {
+    bounties.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Cargo, FactionId, Health, HighSeasEntity, PortEntity};
+    use crate::plugins::core::despawn_scene_entities;
+    use crate::plugins::run_state::RunStatePlugin;
+    use crate::resources::{FogOfWar, RunSnapshot, PIRATE_BOUNTY};
+
+    #[test]
+    fn test_docking_pays_bounties_into_the_flagships_purse() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::state::app::StatesPlugin))
+            .init_asset::<Image>()
+            .init_state::<GameState>()
+            .add_event::<ShipDestroyedEvent>()
+            .add_event::<CombatTriggeredEvent>()
+            .init_resource::<Journal>()
+            .init_resource::<WorldClock>()
+            .init_resource::<ShipDefinitions>()
+            .init_resource::<FogOfWar>()
+            .insert_resource(CurrentPort { faction: Some(FactionId::NationA), ..default() })
+            .add_plugins((RunStatePlugin, BountiesPlugin))
+            .add_systems(OnExit(GameState::HighSeas), despawn_scene_entities::<HighSeasEntity>)
+            .add_systems(OnExit(GameState::Port), despawn_scene_entities::<PortEntity>);
+
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::HighSeas);
+        app.update();
+        app.world_mut().spawn((
+            Player,
+            Ship,
+            HighSeasPlayer,
+            HighSeasEntity,
+            ShipType::Sloop,
+            Health::default(),
+            Cargo::armed(50),
+            Gold(500),
+            Transform::default(),
+        ));
+        app.world_mut().resource_mut::<PendingBounties>().credit("Black Gull", FactionId::Pirates, 0);
+
+        // Into port: the flagship is docked and paid
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Port);
+        app.update();
+        let mut player = app.world_mut().query_filtered::<&Gold, (With<Player>, With<Ship>)>();
+        let purses: Vec<u32> = player.iter(app.world()).map(|gold| gold.0).collect();
+        assert_eq!(purses, vec![500 + PIRATE_BOUNTY]);
+
+        // And back out to sea with the bounty aboard
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::HighSeas);
+        app.update();
+        let flagship = app.world().resource::<RunSnapshot>().flagship.clone().unwrap();
+        assert_eq!(flagship.gold, 500 + PIRATE_BOUNTY);
+        assert_eq!(player.iter(app.world()).count(), 0);
+    }
+}
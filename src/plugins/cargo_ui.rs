@@ -0,0 +1,297 @@
+//! Cargo manifest: every hold in the fleet side by side.
+//!
+//! Goods are dragged from one ship's column and dropped on another's. Fleet
+//! holds can be reached while docked, or at sea when the ship sails within
+//! `TRANSFER_RANGE` of the flagship. Fleet cargo lives in `ShipData::cargo`;
+//! the flagship's is its `Cargo` component.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::cargo::{Cargo, GoodType};
+use crate::components::ship::{Player, Ship};
+use crate::components::upgrade::keeps_cargo_fresh;
+use crate::events::CargoTransferEvent;
+use crate::plugins::core::GameState;
+use crate::resources::{CargoHold, FleetEntities, PlayerFleet, UpgradeInventory};
+
+/// Maximum distance between the flagship and a fleet ship for transfers at sea.
+pub const TRANSFER_RANGE: f32 = 150.0;
+
+/// Plugin for the cargo manifest window.
+pub struct CargoUiPlugin;
+
+impl Plugin for CargoUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CargoUiState>()
+            .add_event::<CargoTransferEvent>()
+            .add_systems(Update, (
+                toggle_cargo_ui_system,
+                cargo_ui_system.after(EguiSet::InitContexts),
+                cargo_transfer_system,
+            ).run_if(in_state(GameState::Port).or(in_state(GameState::HighSeas))));
+    }
+}
+
+/// UI state for the cargo manifest.
+#[derive(Resource)]
+pub struct CargoUiState {
+    pub is_open: bool,
+    /// Units moved by each drag and drop.
+    pub transfer_amount: u32,
+}
+
+impl Default for CargoUiState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            transfer_amount: 10,
+        }
+    }
+}
+
+/// Whether a fleet ship at `ship_pos` is close enough to the flagship to swap cargo at sea.
+pub fn in_transfer_range(player_pos: Vec2, ship_pos: Vec2) -> bool {
+    player_pos.distance(ship_pos) <= TRANSFER_RANGE
+}
+
+/// What decides which holds can be reached for a transfer.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct TransferReach<'w, 's> {
+    state: Res<'w, State<GameState>>,
+    fleet_entities: Res<'w, FleetEntities>,
+    transforms: Query<'w, 's, &'static Transform>,
+    player_query: Query<'w, 's, &'static Transform, (With<Player>, With<Ship>)>,
+}
+
+impl TransferReach<'_, '_> {
+    /// Whether goods can be moved in or out of `hold` right now.
+    pub fn reachable(&self, hold: CargoHold) -> bool {
+        match hold {
+            CargoHold::Flagship => true,
+            CargoHold::Fleet(_) if *self.state.get() == GameState::Port => true,
+            CargoHold::Fleet(index) => {
+                let ship = self.fleet_entities.entities.get(index).and_then(|e| self.transforms.get(*e).ok());
+                match (self.player_query.get_single(), ship) {
+                    (Ok(player), Some(ship)) => {
+                        in_transfer_range(player.translation.truncate(), ship.translation.truncate())
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+/// System to toggle the manifest with the 'H' key.
+fn toggle_cargo_ui_system(
+    mut ui_state: ResMut<CargoUiState>,
+    input: Res<ButtonInput<KeyCode>>,
+) {
+    if input.just_pressed(KeyCode::KeyH) {
+        ui_state.is_open = !ui_state.is_open;
+    }
+}
+
+/// Renders one column per hold; dropping a stack on a column requests a transfer.
+fn cargo_ui_system(
+    mut contexts: EguiContexts,
+    mut ui_state: ResMut<CargoUiState>,
+    player_query: Query<&Cargo, (With<Player>, With<Ship>)>,
+    player_fleet: Res<PlayerFleet>,
+    upgrades: Res<UpgradeInventory>,
+    reach: TransferReach,
+    mut transfer_events: EventWriter<CargoTransferEvent>,
+) {
+    if !ui_state.is_open {
+        return;
+    }
+
+    let mut holds = Vec::new();
+    if let Ok(cargo) = player_query.get_single() {
+        holds.push((CargoHold::Flagship, "Flagship".to_string(), cargo.clone(), keeps_cargo_fresh(&upgrades.flagship)));
+    }
+    for (index, ship) in player_fleet.ships.iter().enumerate() {
+        holds.push((CargoHold::Fleet(index), ship.name.clone(), ship.hold(), keeps_cargo_fresh(&ship.upgrades)));
+    }
+
+    let mut is_open = ui_state.is_open;
+    egui::Window::new("Cargo Manifest")
+        .open(&mut is_open)
+        .default_width(600.0)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Move per drag:");
+                ui.add(egui::Slider::new(&mut ui_state.transfer_amount, 1..=50));
+            });
+            ui.weak(format!(
+                "Drag goods between holds. At sea, ships must be within {:.0} of the flagship.",
+                TRANSFER_RANGE
+            ));
+            ui.separator();
+
+            egui::ScrollArea::horizontal().show(ui, |ui| {
+                ui.horizontal_top(|ui| {
+                    for (hold, name, cargo, fresh) in &holds {
+                        let reachable = reach.reachable(*hold);
+                        let frame = egui::Frame::group(ui.style());
+                        let (_, dropped) = ui.dnd_drop_zone::<(CargoHold, GoodType), _>(
+                            frame,
+                            |ui| render_hold_column(ui, *hold, name, cargo, *fresh, reachable),
+                        );
+                        let Some(payload) = dropped else {
+                            continue;
+                        };
+                        let (from, good) = *payload;
+                        if from != *hold && reachable {
+                            transfer_events.send(CargoTransferEvent {
+                                from,
+                                to: *hold,
+                                good,
+                                quantity: ui_state.transfer_amount,
+                            });
+                        }
+                    }
+                });
+            });
+        });
+    ui_state.is_open = is_open;
+}
+
+/// `fresh` holds have an icehouse, so nothing in them spoils.
+fn render_hold_column(ui: &mut egui::Ui, hold: CargoHold, name: &str, cargo: &Cargo, fresh: bool, reachable: bool) {
+    ui.set_min_width(140.0);
+    ui.vertical(|ui| {
+        ui.strong(name);
+        ui.label(format!("📦 {}/{}", cargo.total_units(), cargo.capacity));
+        if fresh {
+            ui.weak("❄ Icehouse");
+        }
+        if !reachable {
+            ui.weak("Out of reach");
+        }
+        ui.separator();
+
+        let mut goods: Vec<(GoodType, u32)> = cargo.goods.iter().map(|(g, q)| (*g, *q)).collect();
+        goods.sort_by_key(|(good, _)| format!("{:?}", good));
+        if goods.is_empty() {
+            ui.weak("Empty hold");
+        }
+        for (good, quantity) in goods {
+            let label = match spoilage_note(good, fresh) {
+                Some(note) => format!("{:?} ×{} ({})", good, quantity, note),
+                None => format!("{:?} ×{}", good, quantity),
+            };
+            if reachable {
+                ui.dnd_drag_source(egui::Id::new(("cargo_drag", hold, good)), (hold, good), |ui| {
+                    ui.label(label);
+                });
+            } else {
+                ui.weak(label);
+            }
+        }
+    });
+}
+
+/// How fast a good spoils in a hold, if it does at all.
+pub fn spoilage_note(good: GoodType, fresh: bool) -> Option<String> {
+    match good.spoilage_per_day() {
+        rate if rate <= 0.0 => None,
+        _ if fresh => Some("on ice".to_string()),
+        rate => Some(format!("-{:.0}%/day", rate * 100.0)),
+    }
+}
+
+/// Applies transfers between holds, respecting reach and capacity.
+fn cargo_transfer_system(
+    mut events: EventReader<CargoTransferEvent>,
+    reach: TransferReach,
+    mut player_query: Query<&mut Cargo, (With<Player>, With<Ship>)>,
+    mut player_fleet: ResMut<PlayerFleet>,
+) {
+    for event in events.read() {
+        if event.from == event.to || !reach.reachable(event.from) || !reach.reachable(event.to) {
+            warn!("Cargo transfer rejected: {:?} -> {:?}", event.from, event.to);
+            continue;
+        }
+        let flagship = player_query.get_single().ok().cloned();
+        let (Some(mut from), Some(mut to)) = (
+            read_hold(event.from, flagship.as_ref(), &player_fleet),
+            read_hold(event.to, flagship.as_ref(), &player_fleet),
+        ) else {
+            warn!("Cargo transfer failed: hold not found");
+            continue;
+        };
+
+        let moved = from.transfer_to(&mut to, event.good, event.quantity);
+        if moved == 0 {
+            continue;
+        }
+        for (hold, cargo) in [(event.from, from), (event.to, to)] {
+            match hold {
+                CargoHold::Flagship => {
+                    if let Ok(mut flagship) = player_query.get_single_mut() {
+                        *flagship = cargo;
+                    }
+                }
+                CargoHold::Fleet(index) => player_fleet.ships[index].set_hold(cargo),
+            }
+        }
+        info!("Moved {} {:?} from {:?} to {:?}", moved, event.good, event.from, event.to);
+    }
+}
+
+/// Copy of a hold's contents, with upgrade bonuses counted for fleet ships.
+fn read_hold(hold: CargoHold, flagship: Option<&Cargo>, player_fleet: &PlayerFleet) -> Option<Cargo> {
+    match hold {
+        CargoHold::Flagship => flagship.cloned(),
+        CargoHold::Fleet(index) => player_fleet.ships.get(index).map(|ship| ship.hold()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::upgrade::UpgradeKind;
+    use crate::resources::ShipData;
+
+    #[test]
+    fn test_transfer_respects_capacity() {
+        let mut from = Cargo::new(100);
+        from.add(GoodType::Rum, 30);
+        let mut to = Cargo::new(20);
+        to.add(GoodType::Sugar, 15);
+
+        assert_eq!(from.transfer_to(&mut to, GoodType::Rum, 10), 5);
+        assert_eq!((from.get(GoodType::Rum), to.get(GoodType::Rum)), (25, 5));
+        assert_eq!(from.transfer_to(&mut to, GoodType::Spices, 10), 0);
+    }
+
+    #[test]
+    fn test_fleet_hold_counts_upgrades() {
+        let mut ship = ShipData {
+            upgrades: vec![UpgradeKind::ExpandedHold],
+            ..default()
+        };
+        let mut hold = ship.hold();
+        assert_eq!(hold.capacity, 130);
+
+        hold.add(GoodType::Timber, 120);
+        ship.set_hold(hold);
+        let stored = ship.cargo.as_ref().unwrap();
+        assert_eq!((stored.capacity, stored.get(GoodType::Timber)), (100, 120));
+    }
+
+    #[test]
+    fn test_transfer_range() {
+        assert!(in_transfer_range(Vec2::ZERO, Vec2::new(100.0, 0.0)));
+        assert!(!in_transfer_range(Vec2::ZERO, Vec2::new(TRANSFER_RANGE + 1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_spoilage_note() {
+        assert_eq!(spoilage_note(GoodType::Fish, false), Some("-25%/day".to_string()));
+        assert_eq!(spoilage_note(GoodType::Fish, true), Some("on ice".to_string()));
+        assert_eq!(spoilage_note(GoodType::Rum, false), None);
+    }
+}
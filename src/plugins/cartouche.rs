@@ -1,17 +1,18 @@
 //! Cartouche UI component - a decorative baroque-style frame for the map title.
 //!
 //! Uses Lyon vector graphics rendered via the shared Overlay Camera (RenderLayer 1).
-//! Positioned at the top-center of the screen.
+//! Positioned at the top-center of the screen. Beneath the title it keeps the
+//! date and season of the `WorldClock`.
 
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
-use bevy::window::PrimaryWindow;
 use bevy_prototype_lyon::prelude::*;
 
 use crate::plugins::core::GameState;
-use crate::plugins::overlay_ui::{UI_LAYER, COLOR_INK, COLOR_PARCHMENT, COLOR_GOLD};
+use crate::plugins::overlay_ui::{ScreenAnchor, ScreenEdge, UI_LAYER, COLOR_INK, COLOR_PARCHMENT, COLOR_GOLD};
 use crate::components::fade_controller::FadeController;
 use crate::components::HighSeasEntity;
+use crate::resources::WorldClock;
 
 pub struct CartouchePlugin;
 
@@ -19,10 +20,7 @@ impl Plugin for CartouchePlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnEnter(GameState::HighSeas), spawn_cartouche)
-            .add_systems(Update, (
-                update_cartouche_position,
-                apply_cartouche_fade,
-            ).run_if(in_state(GameState::HighSeas)));
+            .add_systems(Update, (apply_cartouche_fade, update_cartouche_date).run_if(in_state(GameState::HighSeas)));
     }
 }
 
@@ -40,25 +38,23 @@ pub struct Cartouche;
 #[derive(Component)]
 pub struct CartoucheRoot;
 
+/// Marker for the cartouche's date line.
+#[derive(Component)]
+pub struct CartoucheDate;
+
 fn spawn_cartouche(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
     asset_server: Res<AssetServer>,
+    world_clock: Res<WorldClock>,
 ) {
-    // Calculate initial position (top-center)
-    let mut initial_pos = Vec3::new(0.0, 0.0, 0.0);
-    if let Ok(window) = window_query.get_single() {
-        let half_h = window.height() / 2.0;
-        initial_pos = Vec3::new(0.0, half_h - MARGIN_TOP, 0.0);
-    }
-
-    // Spawn Cartouche Root
+    // Spawn Cartouche Root, pinned to the top-center
     let root = commands.spawn((
         Name::new("Cartouche Root"),
         Cartouche,
         CartoucheRoot,
         FadeController::visible(),
-        Transform::from_translation(initial_pos),
+        ScreenAnchor::new(ScreenEdge::TopCenter, Vec2::new(0.0, MARGIN_TOP)),
+        Transform::default(),
         Visibility::Inherited,
         RenderLayers::layer(UI_LAYER),
         HighSeasEntity,
@@ -112,9 +108,9 @@ fn spawn_cartouche(
         RenderLayers::layer(UI_LAYER),
     )).set_parent(root);
 
-    // Subtitle
+    // Subtitle: today's date
     commands.spawn((
-        Text2d::new("A Nautical Chart"),
+        Text2d::new(date_line(&world_clock)),
         TextFont {
             font,
             font_size: 14.0,
@@ -123,12 +119,29 @@ fn spawn_cartouche(
         TextColor(COLOR_INK),
         Transform::from_xyz(0.0, -15.0, 0.5),
         Cartouche,
+        CartoucheDate,
         RenderLayers::layer(UI_LAYER),
     )).set_parent(root);
 
     info!("Spawned Map Title Cartouche");
 }
 
+/// The season and date, e.g. "Spring, 14 March 1715".
+fn date_line(world_clock: &WorldClock) -> String {
+    let date = world_clock.date();
+    format!("{}, {}", date.season().name(), date.formatted())
+}
+
+/// Keeps the date line current as the days go by.
+fn update_cartouche_date(world_clock: Res<WorldClock>, mut dates: Query<&mut Text2d, With<CartoucheDate>>) {
+    let line = date_line(&world_clock);
+    for mut text in &mut dates {
+        if text.0 != line {
+            text.0 = line.clone();
+        }
+    }
+}
+
 /// Draws the outer baroque-style frame with ornate border.
 fn spawn_baroque_frame(commands: &mut Commands, parent: Entity) {
     // Outer frame shape
@@ -224,19 +237,6 @@ enum Corner {
     BottomLeft,
 }
 
-/// Keeps the cartouche at the top-center when window is resized.
-fn update_cartouche_position(
-    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
-    mut root_query: Query<&mut Transform, With<CartoucheRoot>>,
-) {
-    let Ok(window) = window_query.get_single() else { return; };
-    let Ok(mut transform) = root_query.get_single_mut() else { return; };
-
-    let half_h = window.height() / 2.0;
-    transform.translation.x = 0.0;
-    transform.translation.y = half_h - MARGIN_TOP;
-}
-
 /// Applies the root's FadeController alpha to all child cartouche entities.
 /// Updates TextColor, Stroke, and Fill components.
 fn apply_cartouche_fade(
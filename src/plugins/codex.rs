@@ -0,0 +1,322 @@
+//! Codex plugin: an in-game encyclopedia of ships, goods, factions and mechanics.
+//!
+//! Entries come from `CodexDatabase` (a content table, see `ContentPlugin`) and unlock as the player
+//! encounters them. Open with C in-game, from the main menu, or by clicking a
+//! linked name elsewhere in the UI (which sends `CodexOpenEvent`).
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::cargo::Cargo;
+use crate::components::ship::{Player, Ship, ShipClass, ShipType};
+use crate::events::{
+    CodexOpenEvent, CodexUnlockEvent, CombatTriggeredEvent, HintTriggeredEvent,
+    IntelAcquiredEvent, TradeExecutedEvent,
+};
+use crate::plugins::companion::CompanionRecruitedEvent;
+use crate::plugins::core::GameState;
+use crate::resources::{
+    faction_codex_id, good_codex_id, ship_codex_id, CodexCategory, CodexDatabase, MetaProfile,
+};
+
+/// Plugin for the codex window and entry unlocking.
+pub struct CodexPlugin;
+
+impl Plugin for CodexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CodexUiState>()
+            .add_event::<CodexUnlockEvent>()
+            .add_event::<CodexOpenEvent>()
+            .add_systems(OnEnter(GameState::HighSeas), unlock_high_seas_entries)
+            .add_systems(OnEnter(GameState::Port), unlock_port_entries)
+            .add_systems(OnEnter(GameState::Combat), unlock_combat_entries)
+            .add_systems(
+                Update,
+                (
+                    toggle_codex_system.after(EguiSet::InitContexts),
+                    unlock_from_player_ship,
+                    unlock_from_gameplay_events,
+                    apply_codex_unlocks,
+                    handle_codex_open_events,
+                    codex_ui_system.after(EguiSet::InitContexts),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// UI state for the codex window.
+#[derive(Resource)]
+pub struct CodexUiState {
+    pub is_open: bool,
+    pub category: CodexCategory,
+    /// Identifier of the entry being displayed.
+    pub selected: Option<String>,
+}
+
+impl Default for CodexUiState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            category: CodexCategory::Mechanics,
+            selected: None,
+        }
+    }
+}
+
+/// Toggles the codex window with the C key, unless it is being typed into a
+/// text field.
+fn toggle_codex_system(
+    input: Res<ButtonInput<KeyCode>>,
+    mut contexts: EguiContexts,
+    mut ui_state: ResMut<CodexUiState>,
+) {
+    if input.just_pressed(KeyCode::KeyC) && !contexts.ctx_mut().wants_keyboard_input() {
+        ui_state.is_open = !ui_state.is_open;
+    }
+}
+
+/// Unlocks the wind and charting entries on first setting sail.
+fn unlock_high_seas_entries(mut events: EventWriter<CodexUnlockEvent>) {
+    for id in ["mechanic.wind", "mechanic.fog_of_war"] {
+        events.send(CodexUnlockEvent { entry_id: id.to_string() });
+    }
+}
+
+/// Unlocks port service entries on first docking.
+fn unlock_port_entries(mut events: EventWriter<CodexUnlockEvent>) {
+    for id in ["mechanic.trading", "mechanic.contracts", "mechanic.repairs"] {
+        events.send(CodexUnlockEvent { entry_id: id.to_string() });
+    }
+}
+
+/// Unlocks the combat entry on first battle.
+fn unlock_combat_entries(mut events: EventWriter<CodexUnlockEvent>) {
+    events.send(CodexUnlockEvent { entry_id: "mechanic.combat".to_string() });
+}
+
+/// Unlocks the player's ship class and any goods loaded into the hold.
+fn unlock_from_player_ship(
+    ship_query: Query<
+        (&ShipType, Option<&ShipClass>),
+        (With<Player>, With<Ship>, Or<(Changed<ShipType>, Changed<ShipClass>)>),
+    >,
+    cargo_query: Query<&Cargo, (With<Player>, With<Ship>, Changed<Cargo>)>,
+    mut events: EventWriter<CodexUnlockEvent>,
+) {
+    for (ship_type, class) in &ship_query {
+        let class = class.cloned().unwrap_or_else(|| ShipClass::of(*ship_type));
+        events.send(CodexUnlockEvent { entry_id: ship_codex_id(&class) });
+    }
+    for cargo in &cargo_query {
+        for (good, &qty) in &cargo.goods {
+            if qty > 0 {
+                events.send(CodexUnlockEvent { entry_id: good_codex_id(*good) });
+            }
+        }
+    }
+}
+
+/// Unlocks entries in response to gameplay events (encounters, trades, hints, ...).
+fn unlock_from_gameplay_events(
+    mut combat_events: EventReader<CombatTriggeredEvent>,
+    mut trade_events: EventReader<TradeExecutedEvent>,
+    mut hint_events: EventReader<HintTriggeredEvent>,
+    mut intel_events: EventReader<IntelAcquiredEvent>,
+    mut recruit_events: EventReader<CompanionRecruitedEvent>,
+    mut events: EventWriter<CodexUnlockEvent>,
+) {
+    for event in combat_events.read() {
+        events.send(CodexUnlockEvent { entry_id: faction_codex_id(event.enemy_faction) });
+    }
+    for event in trade_events.read() {
+        events.send(CodexUnlockEvent { entry_id: good_codex_id(event.good_type) });
+    }
+    for event in hint_events.read() {
+        events.send(CodexUnlockEvent { entry_id: event.hint.codex_id().to_string() });
+    }
+    if intel_events.read().count() > 0 {
+        events.send(CodexUnlockEvent { entry_id: "mechanic.intel".to_string() });
+    }
+    if recruit_events.read().count() > 0 {
+        events.send(CodexUnlockEvent { entry_id: "mechanic.companions".to_string() });
+    }
+}
+
+/// Records unlocked entries in the profile and saves when anything new is unlocked.
+fn apply_codex_unlocks(
+    mut events: EventReader<CodexUnlockEvent>,
+    database: Res<CodexDatabase>,
+    mut profile: ResMut<MetaProfile>,
+) {
+    let mut newly_unlocked = false;
+
+    for event in events.read() {
+        let Some(entry) = database.get(&event.entry_id) else {
+            debug!("Ignoring unlock for unknown codex entry '{}'", event.entry_id);
+            continue;
+        };
+        if profile.unlock_codex_entry(&entry.id) {
+            info!("Codex entry unlocked: {}", entry.title);
+            newly_unlocked = true;
+        }
+    }
+
+    if newly_unlocked {
+        if let Err(e) = profile.save_to_file() {
+            error!("Failed to save profile after codex unlock: {}", e);
+        }
+    }
+}
+
+/// Opens the codex window, jumping to the requested entry if it is unlocked.
+fn handle_codex_open_events(
+    mut events: EventReader<CodexOpenEvent>,
+    database: Res<CodexDatabase>,
+    profile: Res<MetaProfile>,
+    mut ui_state: ResMut<CodexUiState>,
+) {
+    for event in events.read() {
+        ui_state.is_open = true;
+        let Some(id) = &event.entry_id else {
+            continue;
+        };
+        if let Some(entry) = database.get(id) {
+            if profile.is_codex_unlocked(id) {
+                ui_state.category = entry.category;
+                ui_state.selected = Some(entry.id.clone());
+            }
+        }
+    }
+}
+
+/// Renders the codex window: category tabs, entry list and the selected page.
+fn codex_ui_system(
+    mut contexts: EguiContexts,
+    mut ui_state: ResMut<CodexUiState>,
+    database: Res<CodexDatabase>,
+    profile: Res<MetaProfile>,
+) {
+    if !ui_state.is_open {
+        return;
+    }
+
+    let mut is_open = ui_state.is_open;
+    let mut clicked_link: Option<String> = None;
+
+    egui::Window::new("Codex")
+        .open(&mut is_open)
+        .default_width(520.0)
+        .default_height(400.0)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                for &category in CodexCategory::all() {
+                    if ui.selectable_label(ui_state.category == category, category.name()).clicked() {
+                        ui_state.category = category;
+                    }
+                }
+            });
+            ui.separator();
+
+            ui.horizontal_top(|ui| {
+                // Entry list
+                ui.vertical(|ui| {
+                    ui.set_width(160.0);
+                    egui::ScrollArea::vertical().id_salt("codex_entry_list").show(ui, |ui| {
+                        for entry in database.by_category(ui_state.category) {
+                            if profile.is_codex_unlocked(&entry.id) {
+                                let is_selected = ui_state.selected.as_deref() == Some(entry.id.as_str());
+                                if ui.selectable_label(is_selected, &entry.title).clicked() {
+                                    ui_state.selected = Some(entry.id.clone());
+                                }
+                            } else {
+                                ui.weak("???");
+                            }
+                        }
+                    });
+                });
+
+                ui.separator();
+
+                // Selected page
+                ui.vertical(|ui| {
+                    let entry = ui_state
+                        .selected
+                        .as_deref()
+                        .and_then(|id| database.get(id))
+                        .filter(|e| e.category == ui_state.category);
+
+                    let Some(entry) = entry else {
+                        ui.label("Select an entry.");
+                        return;
+                    };
+
+                    ui.heading(&entry.title);
+                    ui.add_space(6.0);
+                    ui.label(&entry.body);
+
+                    if !entry.see_also.is_empty() {
+                        ui.add_space(10.0);
+                        ui.strong("See also");
+                        ui.horizontal_wrapped(|ui| {
+                            for link in &entry.see_also {
+                                let Some(target) = database.get(link) else {
+                                    continue;
+                                };
+                                if profile.is_codex_unlocked(link) {
+                                    if ui.link(&target.title).clicked() {
+                                        clicked_link = Some(link.clone());
+                                    }
+                                } else {
+                                    ui.weak("???");
+                                }
+                            }
+                        });
+                    }
+                });
+            });
+        });
+
+    if let Some(link) = clicked_link {
+        if let Some(entry) = database.get(&link) {
+            ui_state.category = entry.category;
+        }
+        ui_state.selected = Some(link);
+    }
+    ui_state.is_open = is_open;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::window::PrimaryWindow;
+    use bevy_egui::{EguiContext, EguiUserTextures};
+
+    #[test]
+    fn test_typing_c_into_a_text_field_leaves_the_codex_shut() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<EguiUserTextures>()
+            .init_resource::<CodexUiState>()
+            .add_systems(Update, toggle_codex_system);
+        app.world_mut().spawn((Window::default(), PrimaryWindow, EguiContext::default()));
+        let mut contexts = app.world_mut().query::<&mut EguiContext>();
+        let press_c = |app: &mut App| {
+            app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyC);
+            app.update();
+            app.update();
+            app.world_mut().resource_mut::<ButtonInput<KeyCode>>().reset_all();
+        };
+
+        let mut context = contexts.single_mut(app.world_mut());
+        context.get_mut().memory_mut(|memory| memory.request_focus(egui::Id::new("captain")));
+        press_c(&mut app);
+        assert!(!app.world().resource::<CodexUiState>().is_open);
+
+        let mut context = contexts.single_mut(app.world_mut());
+        context.get_mut().memory_mut(|memory| memory.surrender_focus(egui::Id::new("captain")));
+        press_c(&mut app);
+        assert!(app.world().resource::<CodexUiState>().is_open);
+    }
+}
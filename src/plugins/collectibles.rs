@@ -0,0 +1,221 @@
+//! Floating collectibles on the High Seas, starting with messages in bottles.
+//!
+//! `Collectible` items are picked up by sailing within their pickup radius.
+//! A few rare bottles are set adrift over deep water on every voyage; each
+//! holds a chart fragment (reveals fog), a page of lore (unlocks a codex
+//! entry) or a treasure hint (marks a dig spot like tavern treasure intel).
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::components::collectible::{Bottle, BottleMessage, Collectible};
+use crate::components::intel::{AcquiredIntel, Intel, IntelData, IntelType};
+use crate::components::HighSeasEntity;
+use crate::events::{CodexOpenEvent, CodexUnlockEvent, CollectiblePickedUpEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::HighSeasPlayer;
+use crate::resources::{FogOfWar, Journal, MapData, TileType, WorldClock};
+use crate::utils::pathfinding::tile_to_world;
+
+/// Bottles set adrift each time the player puts to sea.
+const BOTTLES_PER_VOYAGE: usize = 3;
+/// Tiles revealed around the center of a chart fragment.
+const MAP_FRAGMENT_RADIUS: i32 = 5;
+
+/// Codex entries that can wash up in a bottle.
+pub const BOTTLE_LORE_IDS: [&str; 5] = [
+    "lore.drowned_admiral",
+    "lore.black_tide",
+    "lore.sugar_war",
+    "lore.lighthouse_keeper",
+    "lore.silent_fleet",
+];
+
+/// Plugin for collectibles and messages in bottles.
+pub struct CollectiblesPlugin;
+
+impl Plugin for CollectiblesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CollectiblePickedUpEvent>()
+            .add_systems(OnEnter(GameState::HighSeas), spawn_bottles)
+            .add_systems(Update, (
+                drift_bottles,
+                collectible_pickup_system.after(drift_bottles),
+                open_bottles.after(collectible_pickup_system),
+            ).run_if(in_state(GameState::HighSeas)));
+    }
+}
+
+/// Rolls what a new bottle holds.
+fn roll_message(rng: &mut impl Rng, deep_water: &[IVec2], coast: &[IVec2]) -> BottleMessage {
+    let roll: f32 = rng.gen();
+    if roll < 0.4 {
+        if let Some(center) = deep_water.choose(rng) {
+            return BottleMessage::MapFragment { center: *center, radius: MAP_FRAGMENT_RADIUS };
+        }
+    }
+    if roll < 0.75 {
+        let id = BOTTLE_LORE_IDS.choose(rng).copied().unwrap_or(BOTTLE_LORE_IDS[0]);
+        return BottleMessage::Lore(id.to_string());
+    }
+    match coast.choose(rng) {
+        Some(tile) => BottleMessage::TreasureHint(*tile),
+        None => BottleMessage::Lore(BOTTLE_LORE_IDS[0].to_string()),
+    }
+}
+
+/// Sets a few bottles adrift on random deep-water tiles.
+fn spawn_bottles(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    map_data: Res<MapData>,
+    existing: Query<(), With<Bottle>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+
+    let mut deep_water = Vec::new();
+    let mut coast = Vec::new();
+    for (x, y, tile) in map_data.iter() {
+        match tile.tile_type {
+            TileType::DeepWater => deep_water.push(IVec2::new(x as i32, y as i32)),
+            TileType::Sand => coast.push(IVec2::new(x as i32, y as i32)),
+            _ => {}
+        }
+    }
+    if deep_water.is_empty() {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..BOTTLES_PER_VOYAGE {
+        let Some(tile) = deep_water.choose(&mut rng).copied() else {
+            break;
+        };
+        let position = tile_to_world(tile, map_data.width, map_data.height);
+        let message = roll_message(&mut rng, &deep_water, &coast);
+        let drift = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU)) * 4.0;
+
+        commands.spawn((
+            Name::new("Message in a Bottle"),
+            Bottle { message, drift },
+            Collectible::default(),
+            Sprite {
+                image: asset_server.load("sprites/loot/gold.png"),
+                color: Color::srgb(0.45, 0.75, 0.55), // Sea-glass green
+                custom_size: Some(Vec2::new(12.0, 20.0)),
+                ..default()
+            },
+            Transform::from_xyz(position.x, position.y, 1.5),
+            HighSeasEntity,
+        ));
+    }
+    debug!("Set {} bottles adrift", BOTTLES_PER_VOYAGE);
+}
+
+/// Bottles bob slowly along on the current.
+fn drift_bottles(time: Res<Time>, mut query: Query<(&Bottle, &mut Transform)>) {
+    for (bottle, mut transform) in &mut query {
+        transform.translation += (bottle.drift * time.delta_secs()).extend(0.0);
+    }
+}
+
+/// Picks up any collectible the player sails close to.
+fn collectible_pickup_system(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<HighSeasPlayer>>,
+    collectible_query: Query<(Entity, &Transform, &Collectible)>,
+    mut events: EventWriter<CollectiblePickedUpEvent>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player.translation.truncate();
+
+    for (entity, transform, collectible) in &collectible_query {
+        if transform.translation.truncate().distance(player_pos) <= collectible.pickup_radius {
+            // Stop further pickups; the item's handler despawns it
+            commands.entity(entity).remove::<Collectible>();
+            events.send(CollectiblePickedUpEvent { entity });
+        }
+    }
+}
+
+/// Codex event writers used when a bottle holds a page of lore.
+#[derive(SystemParam)]
+struct CodexWriters<'w> {
+    unlocks: EventWriter<'w, CodexUnlockEvent>,
+    open: EventWriter<'w, CodexOpenEvent>,
+}
+
+/// Reads picked-up bottles and applies their message.
+fn open_bottles(
+    mut commands: Commands,
+    mut events: EventReader<CollectiblePickedUpEvent>,
+    bottle_query: Query<&Bottle>,
+    mut fog_of_war: ResMut<FogOfWar>,
+    mut journal: ResMut<Journal>,
+    world_clock: Res<WorldClock>,
+    mut codex: CodexWriters,
+) {
+    for event in events.read() {
+        let Ok(bottle) = bottle_query.get(event.entity) else {
+            continue;
+        };
+        codex.unlocks.send(CodexUnlockEvent { entry_id: "mechanic.bottles".to_string() });
+
+        match &bottle.message {
+            BottleMessage::MapFragment { center, radius } => {
+                let revealed = fog_of_war.reveal_circle(*center, *radius);
+                info!("Bottle chart fragment revealed {} tiles", revealed);
+                journal.record(&world_clock, "Fished a bottle from the sea: a torn scrap of chart.");
+            }
+            BottleMessage::Lore(entry_id) => {
+                codex.unlocks.send(CodexUnlockEvent { entry_id: entry_id.clone() });
+                codex.open.send(CodexOpenEvent { entry_id: Some(entry_id.clone()) });
+                journal.record(&world_clock, "Fished a bottle from the sea: a water-stained letter.");
+            }
+            BottleMessage::TreasureHint(tile) => {
+                fog_of_war.reveal_tiles([*tile]);
+                commands.spawn((
+                    Intel,
+                    IntelData {
+                        intel_type: IntelType::TreasureLocation,
+                        revealed_positions: vec![*tile],
+                        description: "A dying sailor's directions to buried treasure".to_string(),
+                        ..default()
+                    },
+                    AcquiredIntel,
+                ));
+                journal.record(&world_clock, "Fished a bottle from the sea: an X on a crude map.");
+            }
+        }
+        commands.entity(event.entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{CodexDatabase, ContentTable};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bottle_lore_is_in_codex() {
+        let db = CodexDatabase::from_ron(include_str!("../../assets/data/codex.ron")).unwrap();
+        for id in BOTTLE_LORE_IDS {
+            assert!(db.get(id).is_some(), "missing codex entry {}", id);
+        }
+    }
+
+    #[test]
+    fn test_roll_message_falls_back_without_tiles() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        for _ in 0..50 {
+            assert!(matches!(roll_message(&mut rng, &[], &[]), BottleMessage::Lore(_)));
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use crate::components::{AmmoType, Cargo, Chasers, Mortar, Player, Ship};
 use crate::plugins::core::GameState;
 use crate::systems::{
     buffer_ship_input, 
@@ -8,9 +10,17 @@ use crate::systems::{
     consume_firing_input,
     projectile_system,
     projectile_collision_system,
+    shot_flight_system,
+    spawn_shot_shadows,
+    shot_height_render_system,
     target_cycling_system,
+    ammo_selection_system,
+    fire_damage_system,
+    flooding_system,
+    record_ship_hits,
     ship_destruction_system,
     handle_player_death_system,
+    journal_kill_system,
     loot_collection_system,
     loot_timer_system,
     combat_victory_system,
@@ -18,14 +28,23 @@ use crate::systems::{
     // AI systems
     combat_ai_system,
     ai_firing_system,
+    ai_sail_trim_system,
+    ai_crew_station_system,
     spawn_combat_enemies,
+    // Chasers and mortars
+    arm_combat_ships,
+    chaser_firing_system,
+    ai_chaser_system,
+    mortar_firing_system,
+    mortar_shell_system,
     AIPhysicsConfig,
     ShipInputBuffer,
     ShipPhysicsConfig,
+    BROADSIDE_ROUNDS,
 };
 use crate::systems::camera::{camera_shake_system, trigger_camera_shake_on_fire};
 use crate::systems::hit_flash::{trigger_hit_flash_system, update_hit_flash_system};
-use crate::resources::CannonState;
+use crate::resources::{BattleStats, CannonState, VictorySpoils, WreckSites};
 
 use crate::plugins::water::WaterPlugin;
 
@@ -47,12 +66,20 @@ impl Plugin for CombatPlugin {
         app.init_resource::<ShipInputBuffer>()
             .init_resource::<ShipPhysicsConfig>()
             .init_resource::<CannonState>()
-            .init_resource::<AIPhysicsConfig>();
+            .init_resource::<AIPhysicsConfig>()
+            .init_resource::<VictorySpoils>()
+            .init_resource::<BattleStats>()
+            .init_resource::<WreckSites>();
         
         // Buffer input in Update
         app.add_systems(
             Update,
-            buffer_ship_input.run_if(in_state(GameState::Combat)),
+            (
+                buffer_ship_input,
+                arm_combat_ships,
+                ammo_selection_system,
+                ammo_hud_system.after(EguiSet::InitContexts),
+            ).run_if(in_state(GameState::Combat)),
         );
         
         // Apply physics forces and firing in FixedUpdate
@@ -61,11 +88,19 @@ impl Plugin for CombatPlugin {
             (
                 ship_physics_system,
                 cannon_firing_system,
-                consume_firing_input.after(cannon_firing_system),
+                chaser_firing_system,
+                mortar_firing_system,
+                consume_firing_input
+                    .after(cannon_firing_system)
+                    .after(chaser_firing_system)
+                    .after(mortar_firing_system),
                 target_cycling_system,
                 // AI systems - run after player physics is processed
-                combat_ai_system.after(ship_physics_system),
+                ai_sail_trim_system,
+                ai_crew_station_system,
+                combat_ai_system.after(ship_physics_system).after(ai_sail_trim_system),
                 ai_firing_system.after(combat_ai_system),
+                ai_chaser_system.after(combat_ai_system),
             ).run_if(in_state(GameState::Combat)),
         );
         
@@ -75,11 +110,29 @@ impl Plugin for CombatPlugin {
             (
                 projectile_system,
                 projectile_collision_system,
+                mortar_shell_system,
+                shot_flight_system,
+                spawn_shot_shadows,
+                shot_height_render_system
+                    .after(shot_flight_system)
+                    .after(mortar_shell_system)
+                    .after(spawn_shot_shadows),
                 loot_collection_system.after(projectile_collision_system),
                 loot_timer_system,
                 debug_ship_physics,
-                ship_destruction_system.after(projectile_collision_system),
+                (
+                    fire_damage_system,
+                    flooding_system,
+                    record_ship_hits
+                        .after(projectile_collision_system)
+                        .after(mortar_shell_system),
+                ),
+                ship_destruction_system
+                    .after(record_ship_hits)
+                    .after(fire_damage_system)
+                    .after(flooding_system),
                 handle_player_death_system.after(ship_destruction_system),
+                journal_kill_system.after(ship_destruction_system),
                 combat_victory_system.after(ship_destruction_system),
                 handle_combat_victory_system.after(combat_victory_system),
                 // Camera shake visual effects
@@ -98,3 +151,55 @@ impl Plugin for CombatPlugin {
         );
     }
 }
+
+/// Where the ammunition selector sits, up from the bottom-left corner.
+const AMMO_HUD_OFFSET: [f32; 2] = [16.0, -104.0];
+
+/// Ammunition selector: shot in the magazine, click (or V) to load.
+/// Below it, the reload of the chasers and the mortar.
+fn ammo_hud_system(
+    mut contexts: EguiContexts,
+    mut cannon_state: ResMut<CannonState>,
+    player_query: Query<(&Cargo, Option<&Chasers>, Option<&Mortar>), (With<Player>, With<Ship>)>,
+) {
+    let Ok((cargo, chasers, mortar)) = player_query.get_single() else {
+        return;
+    };
+
+    egui::Window::new("Guns")
+        .anchor(egui::Align2::LEFT_BOTTOM, AMMO_HUD_OFFSET)
+        .resizable(false)
+        .collapsible(true)
+        .show(contexts.ctx_mut(), |ui| {
+            for ammo in AmmoType::ALL {
+                let rounds = cargo.ammo_count(ammo);
+                let count = rounds.map_or_else(|| "∞".to_string(), |rounds| rounds.to_string());
+                let loadable = rounds.is_none_or(|rounds| rounds >= BROADSIDE_ROUNDS);
+                let loaded = cannon_state.current_ammo == ammo;
+                let label = ui.add_enabled(
+                    loadable || loaded,
+                    egui::SelectableLabel::new(loaded, format!("{:<12}{:>3}", ammo.name(), count)),
+                );
+                if label.clicked() && !loaded {
+                    cannon_state.current_ammo = ammo;
+                    info!("Loading {}", ammo.name());
+                }
+            }
+            ui.small("V to change shot");
+
+            ui.separator();
+            let reload_label = |name: &str, key: &str, reload: &Timer| {
+                if reload.finished() {
+                    format!("{} ready ({})", name, key)
+                } else {
+                    format!("{} {:.0}s", name, reload.remaining_secs().ceil())
+                }
+            };
+            if let Some(chasers) = chasers {
+                ui.label(reload_label("Chasers", "T", &chasers.reload));
+            }
+            if let Some(mortar) = mortar {
+                ui.label(reload_label("Mortar", "M", &mortar.reload));
+            }
+        });
+}
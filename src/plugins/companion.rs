@@ -1,10 +1,13 @@
 use bevy::prelude::*;
-use crate::components::companion::{Companion, CompanionName, CompanionRole};
+use std::collections::HashMap;
+use crate::components::companion::{Companion, CompanionMemory, CompanionName, CompanionRole, CompanionService};
 use crate::plugins::core::GameState;
+use crate::plugins::port_ui::CurrentPort;
+use crate::resources::{event_price, PortEvents, PortMarkets, WorldClock};
 
 use crate::components::ship::{Player, Ship};
 use crate::components::cargo::Gold;
-use crate::components::Cargo;
+use crate::components::{port_key, Cargo};
 use crate::events::TradeExecutedEvent;
 use rand::Rng;
 
@@ -14,31 +17,67 @@ impl Plugin for CompanionPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<TavernCompanions>()
+            .init_resource::<TavernRosters>()
             .add_event::<CompanionRecruitedEvent>()
+            .add_event::<CompanionDismissedEvent>()
             .add_event::<AutoTradeEvent>()
             .add_systems(OnEnter(GameState::Port), generate_tavern_companions)
-            .add_systems(OnExit(GameState::Port), clear_tavern_companions)
+            .add_systems(OnExit(GameState::Port), store_tavern_companions)
+            .add_systems(OnEnter(GameState::MainMenu), reset_tavern_rosters)
             .add_systems(Update, (
                 companion_recruitment_system,
+                companion_dismissal_system,
                 auto_trade_system,
             ).run_if(in_state(GameState::Port)));
     }
 }
 
+/// Chance that a veteran from a previous run is in a tavern on a port visit.
+const VETERAN_APPEARANCE_CHANCE: f64 = 0.5;
+/// Veterans rejoin for less than a stranger would ask.
+const VETERAN_COST_MULTIPLIER: f32 = 0.5;
+/// In-game days before the strangers in a tavern have moved on and new ones drifted in.
+/// Former companions stay put until rehired.
+pub const TAVERN_ROSTER_DAYS: u32 = 5;
+
 /// Resource storing companions available for recruitment in the current port.
 #[derive(Resource, Default)]
 pub struct TavernCompanions {
     pub available: Vec<RecruitableCompanion>,
+    /// Day the strangers among them were generated.
+    pub generated_on: u32,
+}
+
+/// Who was drinking in each port's tavern when the player last left it.
+///
+/// Keyed by `port_key`, since port entities are respawned every High Seas visit.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct TavernRosters {
+    pub rosters: HashMap<IVec2, TavernRoster>,
+}
+
+#[derive(Clone, Debug, Default, Reflect)]
+pub struct TavernRoster {
+    pub companions: Vec<RecruitableCompanion>,
+    /// Day the strangers in it were generated.
+    pub generated_on: u32,
 }
 
 /// Data struct for a companion available in the tavern (not yet an entity).
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
 pub struct RecruitableCompanion {
     pub name: String,
     pub role: CompanionRole,
     pub cost: u32,
     /// Unique ID for UI tracking
     pub id: u64,
+    /// What they remember, if they sailed with the player before.
+    pub memory: Option<CompanionMemory>,
+    /// Whether they survived a previous captain's defeat. They stay among the
+    /// profile's veterans until hired.
+    #[reflect(default)]
+    pub veteran: bool,
 }
 
 /// Event triggered when a companion is recruited.
@@ -47,10 +86,17 @@ pub struct CompanionRecruitedEvent {
     pub companion_id: u64,
 }
 
+/// Event triggered when the player lets a companion go in port.
+#[derive(Event)]
+pub struct CompanionDismissedEvent {
+    pub companion: Entity,
+}
+
 /// Event triggered to execute an auto-trade.
 #[derive(Event)]
 pub struct AutoTradeEvent {
-    pub port_entity: Entity,
+    /// `port_key` of the port to trade at.
+    pub port: IVec2,
 }
 
 /// Helper function to spawn a companion entity.
@@ -59,18 +105,49 @@ pub fn spawn_companion(
     commands: &mut Commands,
     name: String,
     role: CompanionRole,
+    service: CompanionService,
 ) -> Entity {
     commands.spawn((
         Companion,
         CompanionName(name),
         role,
+        service,
     )).id()
 }
 
-/// System to generate random companions when entering a port.
-fn generate_tavern_companions(
+/// System to fill the tavern when entering a port.
+///
+/// A tavern visited in the last `TAVERN_ROSTER_DAYS` days has the same
+/// strangers in it; after that they are replaced. Former companions the
+/// player dismissed here are still waiting either way. A veteran drinks in
+/// one tavern at a time, and leaves every tavern once hired.
+pub fn generate_tavern_companions(
     mut tavern_comps: ResMut<TavernCompanions>,
+    mut rosters: ResMut<TavernRosters>,
+    profile: Res<crate::resources::MetaProfile>,
+    current_port: Res<CurrentPort>,
+    world_clock: Res<WorldClock>,
 ) {
+    let roster = current_port
+        .position
+        .and_then(|position| rosters.rosters.remove(&port_key(position)));
+    if let Some(roster) = &roster {
+        if world_clock.day < roster.generated_on + TAVERN_ROSTER_DAYS {
+            tavern_comps.available = roster
+                .companions
+                .iter()
+                .filter(|c| !c.veteran || profile.veteran_companions.iter().any(|v| v.name == c.name))
+                .cloned()
+                .collect();
+            tavern_comps.generated_on = roster.generated_on;
+            info!("{} companions still in the tavern", tavern_comps.available.len());
+            return;
+        }
+    }
+    let former: Vec<RecruitableCompanion> = roster
+        .map(|roster| roster.companions.into_iter().filter(|c| c.memory.is_some()).collect())
+        .unwrap_or_default();
+
     let mut rng = rand::thread_rng();
     let num_companions = rng.gen_range(1..=3);
     
@@ -93,16 +170,57 @@ fn generate_tavern_companions(
             role,
             cost,
             id: rng.gen::<u64>(), // Simple random ID
+            memory: None,
+            veteran: false,
         });
     }
     
+    // A companion who survived a previous captain's defeat may be drinking here
+    let elsewhere: Vec<&str> = rosters
+        .rosters
+        .values()
+        .flat_map(|roster| roster.companions.iter())
+        .filter(|c| c.veteran)
+        .map(|c| c.name.as_str())
+        .collect();
+    let veteran = profile.veteran_companions.iter().find(|v| !elsewhere.contains(&v.name.as_str()));
+    if let Some(veteran) = veteran.filter(|_| rng.gen_bool(VETERAN_APPEARANCE_CHANCE)) {
+        let cost = (calculate_recruitment_cost(veteran.role, &mut rng) as f32 * VETERAN_COST_MULTIPLIER) as u32;
+        info!("Veteran companion {} is in the tavern", veteran.name);
+        companions.push(RecruitableCompanion {
+            name: veteran.name.clone(),
+            role: veteran.role,
+            cost,
+            id: rng.gen::<u64>(),
+            memory: None,
+            veteran: true,
+        });
+    }
+
+    companions.extend(former);
     tavern_comps.available = companions;
+    tavern_comps.generated_on = world_clock.day;
     info!("Generated {} companions at tavern", tavern_comps.available.len());
 }
 
-/// Clears tavern companions when leaving port.
-fn clear_tavern_companions(mut tavern_comps: ResMut<TavernCompanions>) {
-    tavern_comps.available.clear();
+/// Leaves whoever is still in the tavern there for the next visit.
+pub fn store_tavern_companions(
+    mut tavern_comps: ResMut<TavernCompanions>,
+    mut rosters: ResMut<TavernRosters>,
+    current_port: Res<CurrentPort>,
+) {
+    let companions = std::mem::take(&mut tavern_comps.available);
+    let Some(position) = current_port.position else {
+        return;
+    };
+    rosters.rosters.insert(
+        port_key(position),
+        TavernRoster { companions, generated_on: tavern_comps.generated_on },
+    );
+}
+
+fn reset_tavern_rosters(mut rosters: ResMut<TavernRosters>) {
+    rosters.rosters.clear();
 }
 
 /// System to handle recruitment events.
@@ -110,24 +228,44 @@ fn companion_recruitment_system(
     mut commands: Commands,
     mut events: EventReader<CompanionRecruitedEvent>,
     mut tavern_comps: ResMut<TavernCompanions>,
+    mut profile: ResMut<crate::resources::MetaProfile>,
     mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
+    world_clock: Res<WorldClock>,
+    current_port: Res<CurrentPort>,
+    port_events: Res<PortEvents>,
 ) {
+    let tavern_rate = port_events.at_port(current_port.position).map_or(1.0, |event| event.kind.tavern_rate());
     for event in events.read() {
         // Find the companion in the available list
         if let Some(index) = tavern_comps.available.iter().position(|c| c.id == event.companion_id) {
             let companion_data = &tavern_comps.available[index];
+            if companion_data.memory.is_some_and(|memory| memory.refuses_rehire()) {
+                warn!("{} won't sail with you again", companion_data.name);
+                continue;
+            }
             
             // Check gold
             if let Ok(mut gold) = player_query.get_single_mut() {
-                if gold.spend(companion_data.cost) {
+                if gold.spend(event_price(companion_data.cost, tavern_rate)) {
                     // Spawn the entity
                     spawn_companion(
                         &mut commands,
                         companion_data.name.clone(),
                         companion_data.role,
+                        CompanionService {
+                            hired_on: world_clock.day,
+                            memory: companion_data.memory.unwrap_or_default(),
+                        },
                     );
                     
                     info!("Recruited companion: {} ({:?})", companion_data.name, companion_data.role);
+
+                    // A veteran signs on for good
+                    if companion_data.veteran {
+                        if let Some(veteran) = profile.veteran_companions.iter().position(|v| v.name == companion_data.name) {
+                            profile.veteran_companions.remove(veteran);
+                        }
+                    }
                     
                     // Remove from tavern list
                     tavern_comps.available.remove(index);
@@ -141,18 +279,38 @@ fn companion_recruitment_system(
     }
 }
 
+/// Lets a companion go: they take a room in this port's tavern, remembering how they were treated.
+fn companion_dismissal_system(
+    mut commands: Commands,
+    mut events: EventReader<CompanionDismissedEvent>,
+    mut tavern_comps: ResMut<TavernCompanions>,
+    companion_query: Query<(&CompanionName, &CompanionRole, Option<&CompanionService>), With<Companion>>,
+    world_clock: Res<WorldClock>,
+) {
+    let mut rng = rand::thread_rng();
+    for event in events.read() {
+        let Ok((name, role, service)) = companion_query.get(event.companion) else {
+            continue;
+        };
+        let service = service.copied().unwrap_or_default();
+        let memory = service.memory.after_dismissal(world_clock.day.saturating_sub(service.hired_on));
+        info!("Dismissed companion: {} ({}), now {}", name.0, role.name(), memory.mood());
+        tavern_comps.available.push(RecruitableCompanion {
+            name: name.0.clone(),
+            role: *role,
+            cost: memory.rehire_cost(*role),
+            id: rng.gen::<u64>(),
+            memory: Some(memory),
+            veteran: false,
+        });
+        commands.entity(event.companion).despawn_recursive();
+    }
+}
+
 fn calculate_recruitment_cost(role: CompanionRole, rng: &mut rand::rngs::ThreadRng) -> u32 {
-    let base_cost = match role {
-        CompanionRole::Quartermaster => 600,
-        CompanionRole::Navigator => 500,
-        CompanionRole::Lookout => 400,
-        CompanionRole::Gunner => 550,
-        CompanionRole::Mystic => 1000,
-    };
-    
     // Variance +/- 10%
     let variance = rng.gen_range(0.9..=1.1);
-    (base_cost as f32 * variance) as u32
+    (role.base_hire_cost() as f32 * variance) as u32
 }
 
 fn generate_companion_name(rng: &mut rand::rngs::ThreadRng) -> String {
@@ -176,12 +334,12 @@ fn auto_trade_system(
     _commands: Commands,
     mut events: EventReader<AutoTradeEvent>,
     mut player_query: Query<(Entity, &mut Gold, &mut Cargo), (With<Player>, With<Ship>)>,
-    port_query: Query<&crate::components::port::Inventory, With<crate::components::port::Port>>,
+    markets: Res<PortMarkets>,
     mut trade_events: EventWriter<TradeExecutedEvent>,
 ) {
     for event in events.read() {
         if let Ok((_, gold, cargo)) = player_query.get_single_mut() {
-            if let Ok(inventory) = port_query.get(event.port_entity) {
+            if let Some(inventory) = markets.get(event.port).map(|market| &market.inventory) {
                 // Find a good to buy.
                 // Pick a random good sold by the market.
                 if !inventory.goods.is_empty() {
@@ -204,7 +362,7 @@ fn auto_trade_system(
                                  // That avoids double transactions.
                                  
                                  trade_events.send(TradeExecutedEvent {
-                                     port_entity: event.port_entity,
+                                     port: event.port,
                                      good_type: *good_type,
                                      quantity: amount,
                                      is_buy: true,
@@ -6,19 +6,18 @@
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
 use bevy_prototype_lyon::prelude::*;
-use bevy::window::PrimaryWindow;
 
 use crate::plugins::core::GameState;
-use crate::plugins::overlay_ui::{UI_LAYER, COLOR_INK, COLOR_PARCHMENT, COLOR_GOLD, COLOR_GOLD_DARK, COLOR_GREEN, COLOR_RED};
+use crate::plugins::overlay_ui::{ScreenAnchor, ScreenEdge, UI_LAYER, COLOR_INK, COLOR_PARCHMENT, COLOR_GOLD, COLOR_GOLD_DARK, COLOR_GREEN, COLOR_RED};
 use crate::components::HighSeasEntity;
 
 pub struct CompassRosePlugin;
 
 impl Plugin for CompassRosePlugin {
     fn build(&self, app: &mut App) {
-        app
+        app.init_resource::<CompassDeviation>()
             .add_systems(OnEnter(GameState::HighSeas), spawn_compass_rose)
-            .add_systems(Update, update_compass_position.run_if(in_state(GameState::HighSeas)));
+            .add_systems(Update, turn_compass_rose.run_if(in_state(GameState::HighSeas)));
     }
 }
 
@@ -42,28 +41,34 @@ pub struct CompassRose;
 #[derive(Component)]
 pub struct CompassRoseRoot;
 
-fn spawn_compass_rose(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+/// How far the compass rose is turned off north, in radians.
+///
+/// Zero for a true compass; whatever throws the needle off sets it (see
+/// `plugins::supernatural`).
+#[derive(Resource, Debug, Default)]
+pub struct CompassDeviation(pub f32);
+
+/// Turns the compass rose by its deviation.
+fn turn_compass_rose(
+    deviation: Res<CompassDeviation>,
+    mut root_query: Query<&mut Transform, With<CompassRoseRoot>>,
 ) {
-    // Calculate initial position (bottom-right)
-    let mut initial_pos = Vec3::new(0.0, 0.0, 0.0);
-    if let Ok(window) = window_query.get_single() {
-        let half_w = window.width() / 2.0;
-        let half_h = window.height() / 2.0;
-        initial_pos = Vec3::new(
-            half_w - MARGIN.x,
-            -half_h + MARGIN.y,
-            0.0
-        );
+    let rotation = Quat::from_rotation_z(deviation.0);
+    for mut transform in &mut root_query {
+        if transform.rotation != rotation {
+            transform.rotation = rotation;
+        }
     }
+}
 
-    // Spawn Compass Root
+fn spawn_compass_rose(mut commands: Commands) {
+    // Spawn Compass Root, pinned to the bottom-right corner
     let root = commands.spawn((
         Name::new("Compass Rose Root"),
         CompassRose,
         CompassRoseRoot,
-        Transform::from_translation(initial_pos).with_scale(Vec3::splat(0.7)),
+        ScreenAnchor::new(ScreenEdge::BottomRight, MARGIN).with_scale(0.7),
+        Transform::from_scale(Vec3::splat(0.7)),
         Visibility::Inherited,
         RenderLayers::layer(UI_LAYER),
         HighSeasEntity,
@@ -193,19 +198,3 @@ fn spawn_cross(commands: &mut Commands, parent: Entity, position: Vec2, z: f32)
         RenderLayers::layer(UI_LAYER),
     )).set_parent(parent);
 }
-
-
-/// Keeps the compass in the bottom-right corner when window is resized.
-fn update_compass_position(
-    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
-    mut root_query: Query<&mut Transform, With<CompassRoseRoot>>,
-) {
-    let Ok(window) = window_query.get_single() else { return; };
-    let Ok(mut transform) = root_query.get_single_mut() else { return; };
-
-    let half_w = window.width() / 2.0;
-    let half_h = window.height() / 2.0;
-    
-    transform.translation.x = half_w - MARGIN.x;
-    transform.translation.y = -half_h + MARGIN.y;
-}
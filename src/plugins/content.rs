@@ -0,0 +1,69 @@
+//! Content plugin: loads the content tables and, in dev builds, hot-reloads them.
+//!
+//! See `resources::content`. Each table is read from disk while the app is
+//! built, then loaded again as an asset so the `AssetServer` watches its file.
+//! When the file is saved, the reloaded asset replaces the resource; systems
+//! reading the table pick the change up at once. A file that no longer parses
+//! is reported by the `AssetServer` and the table in play is kept.
+
+use bevy::prelude::*;
+
+use crate::resources::{
+    ArchetypeRegistry, BalanceConfig, CodexDatabase, ContentHandle, ContentTable, ContractRewards, EncounterTemplates, GoodsTable,
+    RonContentLoader, SeaEventDeck, ShipDefinitions,
+};
+
+/// Plugin for the data-driven content tables.
+pub struct ContentPlugin;
+
+impl Plugin for ContentPlugin {
+    fn build(&self, app: &mut App) {
+        add_content_table::<GoodsTable>(app);
+        add_content_table::<ArchetypeRegistry>(app);
+        add_content_table::<ContractRewards>(app);
+        add_content_table::<ShipDefinitions>(app);
+        add_content_table::<BalanceConfig>(app);
+        add_content_table::<EncounterTemplates>(app);
+        add_content_table::<SeaEventDeck>(app);
+        add_content_table::<CodexDatabase>(app);
+    }
+}
+
+/// Registers a table's asset type and loader, and inserts the table as read
+/// from disk.
+fn add_content_table<T: ContentTable>(app: &mut App) {
+    app.init_asset::<T>()
+        .register_asset_loader(RonContentLoader::<T>::default())
+        .insert_resource(T::load_from_file())
+        .add_systems(Startup, load_content_table::<T>)
+        .add_systems(Update, reload_content_table::<T>);
+}
+
+/// Loads a table through the `AssetServer`, keeping its handle so it stays loaded.
+fn load_content_table<T: ContentTable>(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ContentHandle::<T>(asset_server.load(T::ASSET_PATH)));
+}
+
+/// Copies a table's asset into its resource whenever it loads with changes.
+fn reload_content_table<T: ContentTable>(
+    mut events: EventReader<AssetEvent<T>>,
+    handle: Option<Res<ContentHandle<T>>>,
+    assets: Res<Assets<T>>,
+    mut table: ResMut<T>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+    for event in events.read() {
+        if !event.is_loaded_with_dependencies(&handle.0) && !event.is_modified(&handle.0) {
+            continue;
+        }
+        let Some(loaded) = assets.get(&handle.0) else {
+            continue;
+        };
+        if *loaded != *table {
+            *table = loaded.clone();
+            info!("Reloaded {} data from assets/{}", T::NAME, T::ASSET_PATH);
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiSet};
 use crate::plugins::input::{get_default_input_map, PlayerAction};
 use crate::plugins::graphics::AestheticSettings;
-use crate::components::{Player, Ship, HighSeasEntity, CombatEntity, PortEntity};
-use crate::resources::{Wind, WorldClock, FactionRegistry, ArchetypeRegistry, ArchetypeId, MetaProfile, PlayerDeathData};
-use crate::systems::{wind_system, world_tick_system, price_calculation_system, goods_decay_system, contract_expiry_system, intel_expiry_system, faction_ai_system, trade_route_generation_system, faction_ship_spawning_system, faction_threat_response_system, ThreatResponseCooldown, GlobalDemand};
-use crate::events::ContractExpiredEvent;
+use crate::components::{Player, Ship, HighSeasEntity, CombatEntity, PortEntity, MainMenuEntity};
+use crate::resources::{Wind, WindShadow, WorldClock, FactionRegistry, ArchetypeRegistry, ArchetypeId, MetaProfile, PlayerDeathData, RunSettings, DefeatOutcome, PlayerFleet, PortMarkets, TradeLaneTraffic, VeteranCompanion, scatter_fleet};
+use crate::components::companion::{Companion, CompanionName, CompanionRole};
+use crate::systems::{wind_system, wind_shadow_system, world_tick_system, price_calculation_system, goods_decay_system, cargo_spoilage_system, contract_expiry_system, contract_failure_system, intel_expiry_system, faction_fleet_census_system, faction_ai_system, trade_route_generation_system, faction_ship_spawning_system, faction_threat_response_system, bounty_hunter_system, trade_lane_traffic_system, pirate_lane_raiding_system, port_market_sync_system, market_day_system, ThreatResponseCooldown, GlobalDemand};
+use crate::events::{ContractExpiredEvent, WorldNewsEvent};
 use leafwing_input_manager::prelude::*;
 
 #[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
@@ -13,6 +15,8 @@ pub enum GameState {
     MainMenu,
     Port,
     HighSeas,
+    /// Standing in to a port before going ashore (see `plugins::harbor_approach`).
+    HarborApproach,
     Combat,
     GameOver,
 }
@@ -27,13 +31,18 @@ impl Plugin for CorePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
             .init_resource::<Wind>()
+            .init_resource::<WindShadow>()
             .init_resource::<WorldClock>()
             .init_resource::<GlobalDemand>()
+            .init_resource::<PortMarkets>()
             .init_resource::<ThreatResponseCooldown>()
-            .init_resource::<ArchetypeRegistry>()
             .init_resource::<PlayerDeathData>()
+            .init_resource::<RunSettings>()
+            .init_resource::<DefeatOutcome>()
+            .init_resource::<TradeLaneTraffic>()
             .insert_resource(FactionRegistry::new())
             .add_event::<ContractExpiredEvent>()
+            .add_event::<WorldNewsEvent>()
             .add_systems(Startup, (
                 spawn_camera,
                 init_meta_profile,
@@ -41,35 +50,50 @@ impl Plugin for CorePlugin {
             ))
             .insert_resource(ClearColor(Color::srgba(0.94, 0.9, 0.78, 1.0))) // Parchment background
             .add_systems(Update, (
-                debug_state_transitions,
+                debug_state_transitions.after(EguiSet::InitContexts).run_if(debug_hotkeys_allowed),
                 log_state_transitions,
                 camera_control,
                 camera_follow.run_if(in_state(GameState::Combat).or(in_state(GameState::HighSeas))),
                 draw_ocean_grid,
                 wind_system,
+                wind_shadow_system.after(wind_system).run_if(in_state(GameState::HighSeas)),
+                port_market_sync_system,
                 faction_threat_response_system.run_if(in_state(GameState::HighSeas)),
             ))
             .add_systems(FixedUpdate, (
                 world_tick_system,
-                price_calculation_system.after(world_tick_system),
+                market_day_system.after(world_tick_system),
+                price_calculation_system.after(market_day_system),
                 goods_decay_system.after(world_tick_system),
+                cargo_spoilage_system.after(world_tick_system),
                 contract_expiry_system.after(world_tick_system),
+                contract_failure_system.after(contract_expiry_system),
                 intel_expiry_system.after(world_tick_system),
-                faction_ai_system.after(world_tick_system),
+                faction_fleet_census_system.after(world_tick_system),
+                faction_ai_system.after(faction_fleet_census_system),
                 trade_route_generation_system.after(faction_ai_system),
                 faction_ship_spawning_system.after(trade_route_generation_system),
+                trade_lane_traffic_system.after(faction_ship_spawning_system),
+                pirate_lane_raiding_system
+                    .after(trade_lane_traffic_system)
+                    .run_if(in_state(GameState::HighSeas)),
+                bounty_hunter_system
+                    .after(faction_ai_system)
+                    .run_if(in_state(GameState::HighSeas)),
             ))
             // Scene cleanup: despawn all entities tagged with scene markers on state exit
             .add_systems(OnExit(GameState::HighSeas), despawn_scene_entities::<HighSeasEntity>)
             .add_systems(OnExit(GameState::Combat), despawn_scene_entities::<CombatEntity>)
             .add_systems(OnExit(GameState::Port), despawn_scene_entities::<PortEntity>)
-            .add_systems(OnEnter(GameState::GameOver), save_profile_on_death);
+            .add_systems(OnExit(GameState::MainMenu), despawn_scene_entities::<MainMenuEntity>)
+            .add_systems(OnEnter(GameState::GameOver), (resolve_defeat_losses, save_profile_on_death).chain())
+            .add_systems(OnEnter(GameState::MainMenu), (reset_trade_lanes, reset_port_markets));
     }
 }
 
 /// Generic system that despawns all entities with the specified marker component.
 /// Uses despawn_recursive to handle entity hierarchies (children are despawned with parents).
-fn despawn_scene_entities<T: Component>(
+pub fn despawn_scene_entities<T: Component>(
     mut commands: Commands,
     query: Query<Entity, With<T>>,
 ) {
@@ -145,11 +169,21 @@ fn camera_control(
     }
 }
 
+/// Whether the number keys switch states. In port they pick the tabs, and on
+/// the main menu they go into the new game's seed.
+fn debug_hotkeys_allowed(state: Res<State<GameState>>) -> bool {
+    !matches!(state.get(), GameState::Port | GameState::MainMenu)
+}
+
 fn debug_state_transitions(
     keys: Res<ButtonInput<KeyCode>>,
-    _state: Res<State<GameState>>,
+    mut contexts: EguiContexts,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
+    // Digits typed into a text field are not hotkeys
+    if contexts.ctx_mut().wants_keyboard_input() {
+        return;
+    }
     if keys.just_pressed(KeyCode::Digit1) {
         next_state.set(GameState::MainMenu);
     } else if keys.just_pressed(KeyCode::Digit2) {
@@ -184,9 +218,13 @@ fn draw_ocean_grid(mut gizmos: Gizmos) {
 }
 
 /// Loads the MetaProfile from disk on app start.
-/// Creates a fresh profile if no save file exists.
-fn init_meta_profile(mut commands: Commands) {
-    let profile = crate::resources::MetaProfile::load_from_file();
+/// Creates a fresh profile if no save file exists, or if it cannot be read
+/// (raising the save compatibility dialog to offer a migration).
+fn init_meta_profile(mut commands: Commands, dialog: Option<ResMut<crate::resources::SaveCompatDialog>>) {
+    let (profile, issue) = crate::resources::MetaProfile::load_from_file_checked();
+    if let (Some(mut dialog), Some(issue)) = (dialog, issue) {
+        dialog.open(issue);
+    }
     info!(
         "MetaProfile loaded: {} runs completed, {} deaths, {} wrecks",
         profile.runs_completed, profile.deaths, profile.legacy_wrecks.len()
@@ -194,14 +232,80 @@ fn init_meta_profile(mut commands: Commands) {
     commands.insert_resource(profile);
 }
 
+/// Applies the difficulty's defeat rules when the player dies.
+///
+/// Trims the gold and cargo recorded for the legacy wreck, sends companions to
+/// the tavern pool and scatters the fleet, recording the results in
+/// `DefeatOutcome` for the game over screen. Runs before `save_profile_on_death`.
+fn resolve_defeat_losses(
+    mut commands: Commands,
+    mut death_data: ResMut<PlayerDeathData>,
+    mut profile: ResMut<MetaProfile>,
+    mut player_fleet: ResMut<PlayerFleet>,
+    mut outcome: ResMut<DefeatOutcome>,
+    run_settings: Res<RunSettings>,
+    companion_query: Query<(Entity, &CompanionName, &CompanionRole), With<Companion>>,
+) {
+    let rules = run_settings.difficulty.defeat_rules();
+    let (wreck_gold, gold_lost) = rules.split_gold(death_data.gold);
+    death_data.gold = wreck_gold;
+    death_data.cargo = rules.salvage_cargo(&death_data.cargo);
+
+    let mut companions_to_taverns = Vec::new();
+    for (entity, name, role) in &companion_query {
+        profile.veteran_companions.push(VeteranCompanion {
+            name: name.0.clone(),
+            role: *role,
+        });
+        companions_to_taverns.push(name.0.clone());
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let (recoverable, ships_lost) = scatter_fleet(&player_fleet.ships, &rules, &mut rand::thread_rng());
+    let ships_recoverable: Vec<String> = recoverable.iter().map(|ship| ship.name.clone()).collect();
+    profile.scattered_ships.extend(recoverable);
+    player_fleet.ships.clear();
+
+    info!(
+        "Defeat: {} gold lost, {} left in the wreck, {} companions to taverns, {} ships scattered, {} lost",
+        gold_lost,
+        wreck_gold,
+        companions_to_taverns.len(),
+        ships_recoverable.len(),
+        ships_lost.len()
+    );
+
+    *outcome = DefeatOutcome {
+        ship_name: death_data.ship_name.clone(),
+        rules: rules.describe(),
+        gold_lost,
+        wreck_gold,
+        wreck_cargo: death_data.cargo.clone(),
+        companions_to_taverns,
+        ships_recoverable,
+        ships_lost,
+    };
+}
+
 /// Saves the MetaProfile to disk when the player dies.
 /// Creates a legacy wreck from death data and increments death counter.
 fn save_profile_on_death(
     mut profile: ResMut<MetaProfile>,
     mut death_data: ResMut<PlayerDeathData>,
+    world_clock: Res<WorldClock>,
+    selected_archetype: Option<Res<crate::plugins::main_menu::SelectedArchetype>>,
+    outcome: Res<DefeatOutcome>,
 ) {
     profile.deaths += 1;
 
+    // Record a summary of this run for the main menu profile card
+    profile.record_run_end(
+        selected_archetype.map(|a| a.0).unwrap_or(ArchetypeId::Default),
+        death_data.ship_name.clone(),
+        world_clock.day,
+        outcome.wreck_gold + outcome.gold_lost,
+    );
+
     // Create legacy wreck from death data
     let run_number = profile.deaths; // Use death count as run number
     const TILE_SIZE: f32 = 16.0; // Must match MapData tile size
@@ -232,6 +336,16 @@ fn save_profile_on_death(
     }
 }
 
+/// Forgets lane traffic from the previous run's map.
+fn reset_trade_lanes(mut lane_traffic: ResMut<TradeLaneTraffic>) {
+    lane_traffic.clear();
+}
+
+/// Forgets the previous run's port stock, so markets start from the content defaults.
+fn reset_port_markets(mut markets: ResMut<PortMarkets>) {
+    markets.clear();
+}
+
 /// Checks all archetypes and unlocks any that meet their unlock conditions.
 /// Runs after profile load to update unlocks based on lifetime stats.
 fn check_archetype_unlocks(
@@ -269,4 +383,50 @@ fn check_archetype_unlocks(
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::window::PrimaryWindow;
+    use bevy_egui::{egui, EguiContext, EguiUserTextures};
+
+    fn hotkey_app(state: GameState) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::state::app::StatesPlugin))
+            .insert_state(state)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<EguiUserTextures>()
+            .add_systems(Update, debug_state_transitions.run_if(debug_hotkeys_allowed));
+        app.world_mut().spawn((Window::default(), PrimaryWindow, EguiContext::default()));
+        app
+    }
+
+    fn type_digit(app: &mut App, key: KeyCode) {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(key);
+        app.update();
+        app.update();
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().reset_all();
+    }
+
+    #[test]
+    fn test_digits_typed_into_the_seed_field_stay_on_the_menu() {
+        let mut app = hotkey_app(GameState::MainMenu);
+        type_digit(&mut app, KeyCode::Digit3);
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::MainMenu);
+    }
+
+    #[test]
+    fn test_digits_typed_into_a_text_field_are_not_hotkeys() {
+        let mut app = hotkey_app(GameState::HighSeas);
+        let mut contexts = app.world_mut().query::<&mut EguiContext>();
+        let mut context = contexts.single_mut(app.world_mut());
+        context.get_mut().memory_mut(|memory| memory.request_focus(egui::Id::new("seed")));
+        type_digit(&mut app, KeyCode::Digit1);
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::HighSeas);
 
+        // Without a text field in focus the hotkey still works
+        let mut context = contexts.single_mut(app.world_mut());
+        context.get_mut().memory_mut(|memory| memory.surrender_focus(egui::Id::new("seed")));
+        type_digit(&mut app, KeyCode::Digit1);
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::MainMenu);
+    }
+}
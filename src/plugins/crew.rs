@@ -0,0 +1,155 @@
+//! The ship's company: mustering crews, paying wages, hiring and losses.
+//!
+//! See `components::crew`. Every flagship and combat ship is given a `Crew`
+//! when spawned: the player's from `PlayerCrew`, which carries it between
+//! scenes; enemies a full crew for their hull. In battle the crew are the
+//! hands split between `CrewStations`, so casualties taken at the stations
+//! come off the crew too. Wages fall due at midnight, and sailors are hired
+//! in the Tavern.
+
+use bevy::prelude::*;
+
+use crate::components::stations::CrewStations;
+use crate::components::{CombatEntity, Crew, Gold, Player, Ship, ShipType, HIRE_COST};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::CurrentPort;
+use crate::resources::{event_price, Journal, PlayerCrew, PortEvents, WorldClock};
+use crate::systems::world_tick_system;
+
+/// Plugin for crews, wages and hiring.
+pub struct CrewPlugin;
+
+impl Plugin for CrewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerCrew>()
+            .add_event::<HireSailorsEvent>()
+            .add_systems(
+                Update,
+                (
+                    muster_player_crew,
+                    muster_enemy_crews,
+                    count_station_losses,
+                    hire_sailors_system.run_if(in_state(GameState::Port)),
+                    store_player_crew
+                        .after(muster_player_crew)
+                        .after(count_station_losses)
+                        .after(hire_sailors_system),
+                ),
+            )
+            .add_systems(FixedUpdate, pay_wages_system.after(world_tick_system))
+            .add_systems(OnEnter(GameState::MainMenu), reset_player_crew);
+    }
+}
+
+/// Event sent from the Tavern to sign on sailors.
+#[derive(Event, Debug)]
+pub struct HireSailorsEvent {
+    pub count: u32,
+}
+
+/// Puts the player's crew aboard her flagship, wherever it is spawned.
+fn muster_player_crew(
+    mut commands: Commands,
+    mut player_crew: ResMut<PlayerCrew>,
+    query: Query<(Entity, Option<&ShipType>), (With<Player>, With<Ship>, Without<Crew>)>,
+) {
+    for (entity, ship_type) in &query {
+        let crew = player_crew.get_or_muster(ship_type.copied().unwrap_or_default());
+        commands.entity(entity).insert(crew);
+    }
+}
+
+/// Enemy ships come into battle fully manned.
+fn muster_enemy_crews(
+    mut commands: Commands,
+    query: Query<(Entity, Option<&ShipType>), (With<Ship>, With<CombatEntity>, Without<Player>, Without<Crew>)>,
+) {
+    for (entity, ship_type) in &query {
+        commands.entity(entity).insert(Crew::for_ship(ship_type.copied().unwrap_or_default()));
+    }
+}
+
+/// Hands lost at their stations in battle are lost to the crew.
+fn count_station_losses(mut query: Query<(&CrewStations, &mut Crew), Changed<CrewStations>>) {
+    for (stations, mut crew) in &mut query {
+        let hands = stations.hands();
+        if hands < crew.hands {
+            let lost = crew.hands - hands;
+            crew.lose(lost);
+        }
+    }
+}
+
+/// Keeps `PlayerCrew` up to date with the crew aboard the flagship.
+fn store_player_crew(
+    mut player_crew: ResMut<PlayerCrew>,
+    query: Query<&Crew, (With<Player>, With<Ship>, Changed<Crew>)>,
+) {
+    if let Ok(crew) = query.get_single() {
+        player_crew.set_if_neq(PlayerCrew { crew: Some(*crew) });
+    }
+}
+
+/// Signs on sailors at `HIRE_COST` each (less during a festival), as many as
+/// there are berths and gold for.
+fn hire_sailors_system(
+    mut events: EventReader<HireSailorsEvent>,
+    mut player_query: Query<(&mut Gold, &mut Crew), (With<Player>, With<Ship>)>,
+    current_port: Res<CurrentPort>,
+    port_events: Res<PortEvents>,
+) {
+    let tavern_rate = port_events.at_port(current_port.position).map_or(1.0, |event| event.kind.tavern_rate());
+    let bounty = event_price(HIRE_COST, tavern_rate).max(1);
+    for event in events.read() {
+        let Ok((mut gold, mut crew)) = player_query.get_single_mut() else {
+            warn!("Failed to hire sailors: Player not found");
+            continue;
+        };
+        let affordable = gold.0 / bounty;
+        let count = event.count.min(affordable).min(crew.vacancies());
+        if count == 0 {
+            warn!("Failed to hire sailors: no gold or no berths");
+            continue;
+        }
+        gold.0 -= count * bounty;
+        crew.hire(count);
+        info!("Signed on {} sailors for {}g", count, count * bounty);
+    }
+}
+
+/// Pays the crew's wages at midnight. Crews that go unpaid lose heart, and
+/// mutinous ones desert.
+fn pay_wages_system(
+    world_clock: Res<WorldClock>,
+    mut player_query: Query<(&mut Gold, &mut Crew), (With<Player>, With<Ship>)>,
+    mut journal: ResMut<Journal>,
+) {
+    if world_clock.tick != 0 || world_clock.hour != 0 {
+        return;
+    }
+    let Ok((mut gold, mut crew)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let wages = crew.daily_wages();
+    if gold.spend(wages) {
+        crew.payday(true);
+        debug!("Paid {} gold in wages", wages);
+        return;
+    }
+
+    let deserted = crew.payday(false);
+    let text = if deserted > 0 {
+        format!(
+            "Could not meet the {} gold owed in wages. {} hands went over the side in the night.",
+            wages, deserted
+        )
+    } else {
+        format!("Could not meet the {} gold owed in wages. The crew are {}.", wages, crew.mood().to_lowercase())
+    };
+    journal.record(&world_clock, text);
+}
+
+fn reset_player_crew(mut player_crew: ResMut<PlayerCrew>) {
+    player_crew.crew = None;
+}
@@ -0,0 +1,153 @@
+//! Crew station assignment in combat.
+//!
+//! Every combat ship splits its crew (`Crew::hands`) between gunnery, sailing
+//! and repairs (`CrewStations`). Gunnery shortens reloads and sailing
+//! strengthens thrust and turning; both are read by the combat physics,
+//! firing and AI systems.
+//! Repairs are applied here: the repair crew slowly patches the hull and
+//! works the pumps, or fights the fire while one burns. The player sets the
+//! split from a HUD widget, which also shows any fire and flooding; enemy
+//! captains use `ai_crew_station_system`.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::stations::{
+    CrewStations, Station, BATTLE_REPAIR_LIMIT, FIRE_FIGHTING_PER_HAND, HULL_REPAIR_PER_HAND, LEAK_PLUG_PER_HAND,
+    PUMP_PER_HAND,
+};
+use crate::components::{CombatEntity, Crew, Fire, Health, Player, Ship, WaterIntake, FLOOD_SWAMP_LEVEL};
+use crate::plugins::core::GameState;
+
+/// Plugin for crew stations in combat.
+pub struct CrewStationsPlugin;
+
+impl Plugin for CrewStationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            attach_crew_stations,
+            repair_station_system,
+            crew_station_ui.after(EguiSet::InitContexts),
+        ).run_if(in_state(GameState::Combat)));
+    }
+}
+
+/// Combat ships start with their crew split evenly between the stations.
+fn attach_crew_stations(
+    mut commands: Commands,
+    ship_query: Query<(Entity, &Crew), (With<Ship>, With<CombatEntity>, Without<CrewStations>)>,
+) {
+    for (entity, crew) in &ship_query {
+        commands.entity(entity).insert(CrewStations::even(crew.hands));
+    }
+}
+
+/// The repair crew patches the hull (up to `BATTLE_REPAIR_LIMIT`), plugs leaks and pumps.
+/// While a fire burns, every hand at the station fights it instead.
+fn repair_station_system(
+    time: Res<Time>,
+    mut query: Query<(&CrewStations, &mut Health, Option<&mut WaterIntake>, Option<&mut Fire>)>,
+) {
+    let delta = time.delta_secs();
+    for (stations, mut health, water_intake, fire) in &mut query {
+        let hands = stations.repairs as f32;
+        if health.is_destroyed() {
+            continue;
+        }
+        if let Some(mut fire) = fire {
+            fire.tick(delta, hands, FIRE_FIGHTING_PER_HAND);
+            continue;
+        }
+        if hands == 0.0 {
+            continue;
+        }
+
+        let limit = health.hull_max * BATTLE_REPAIR_LIMIT;
+        if health.hull < limit {
+            health.hull = (health.hull + HULL_REPAIR_PER_HAND * hands * delta).min(limit);
+        }
+
+        if let Some(mut intake) = water_intake {
+            intake.rate = (intake.rate - LEAK_PLUG_PER_HAND * hands * delta).max(0.0);
+            intake.current = (intake.current - PUMP_PER_HAND * hands * delta).max(0.0);
+        }
+    }
+}
+
+/// Station widget: hands at each station with buttons to move them.
+fn crew_station_ui(
+    mut contexts: EguiContexts,
+    mut player_query: Query<(&mut CrewStations, Option<&Fire>, Option<&WaterIntake>), (With<Player>, With<Ship>)>,
+) {
+    let Ok((mut stations, fire, water_intake)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    egui::Window::new("Stations")
+        .anchor(egui::Align2::RIGHT_BOTTOM, [-16.0, -16.0])
+        .resizable(false)
+        .collapsible(true)
+        .show(contexts.ctx_mut(), |ui| {
+            for station in Station::ALL {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{:<8}", station.name()));
+                    if ui.small_button("-").clicked() {
+                        // Give a hand to the station that has fewest
+                        if let Some(to) = Station::ALL
+                            .into_iter()
+                            .filter(|s| *s != station)
+                            .min_by_key(|s| stations.get(*s))
+                        {
+                            stations.move_hand(station, to);
+                        }
+                    }
+                    ui.strong(format!("{:>2}", stations.get(station)));
+                    if ui.small_button("+").clicked() {
+                        // Take a hand from the station that has most
+                        if let Some(from) = Station::ALL
+                            .into_iter()
+                            .filter(|s| *s != station)
+                            .max_by_key(|s| stations.get(*s))
+                        {
+                            stations.move_hand(from, station);
+                        }
+                    }
+                    ui.small(station_effect(&stations, station, fire.is_some()));
+                });
+            }
+
+            // Damage control: what the repair crew is up against
+            if fire.is_none() && water_intake.is_none() {
+                return;
+            }
+            ui.separator();
+            if let Some(fire) = fire {
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::from_rgb(230, 120, 40), "🔥 Fire");
+                    ui.add(egui::ProgressBar::new(fire.intensity).desired_width(100.0));
+                });
+            }
+            if let Some(intake) = water_intake {
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::from_rgb(90, 140, 220), "🌊 Water");
+                    ui.add(
+                        egui::ProgressBar::new(intake.current / FLOOD_SWAMP_LEVEL)
+                            .desired_width(100.0)
+                            .text(format!("+{:.1}/s", intake.rate)),
+                    );
+                });
+                if intake.is_swamped() {
+                    ui.small("Lower deck awash");
+                }
+            }
+        });
+}
+
+fn station_effect(stations: &CrewStations, station: Station, burning: bool) -> String {
+    match station {
+        Station::Gunnery => format!("reload ×{:.2}", stations.reload_multiplier()),
+        Station::Sailing => format!("handling ×{:.2}", stations.maneuver_multiplier()),
+        Station::Repairs if burning => "fighting the fire".to_string(),
+        Station::Repairs => format!("+{:.1} hull/s", HULL_REPAIR_PER_HAND * stations.repairs as f32),
+    }
+}
@@ -0,0 +1,229 @@
+//! Curved lettering on the world map.
+//!
+//! `CurvedLabel` entities get one `Text2d` child per character laid out along
+//! their path. The largest landmasses are named this way, with the name arcing
+//! over the northern shore just offshore of the waterlines.
+
+use bevy::prelude::*;
+
+use crate::components::location_label::{CurvedLabel, CurvedLabelGlyph};
+use crate::components::HighSeasEntity;
+use crate::plugins::core::GameState;
+use crate::plugins::map_lod::{decoration_visibility, LodLevel, MapDetail, MapLod};
+use crate::plugins::worldmap::CoastlineData;
+use crate::resources::RunSettings;
+use crate::utils::curved_text::{glyph_advance, layout_along_path, polyline_length, smooth_path};
+use crate::utils::geometry::offset_polygon;
+
+/// Number of landmasses given a coast name.
+const COAST_NAME_COUNT: usize = 6;
+const COAST_NAME_FONT_SIZE: f32 = 30.0;
+const COAST_NAME_LETTER_SPACING: f32 = 6.0;
+/// Distance seaward of the coastline for the name baseline, clear of the waterlines.
+const COAST_NAME_OFFSET: f32 = 70.0;
+/// Path length as a multiple of the text length, leaving room either side.
+const COAST_NAME_PATH_RATIO: f32 = 1.3;
+
+/// Plugin that builds curved labels and names the major coasts.
+pub struct CurvedLabelPlugin;
+
+impl Plugin for CurvedLabelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::HighSeas), spawn_coast_names)
+            .add_systems(
+                Update,
+                (build_curved_label_glyphs, apply_curved_label_lod)
+                    .run_if(in_state(GameState::HighSeas)),
+            );
+    }
+}
+
+/// Deterministic coast name for the `index`th largest landmass of a world.
+/// Names are distinct for the first ten landmasses.
+fn coast_name(seed: u32, index: usize) -> String {
+    const FIRST: [&str; 10] = [
+        "Windward", "Leeward", "Mosquito", "Pearl", "Ivory",
+        "Smugglers'", "Golden", "Spanish", "Cutlass", "Turtle",
+    ];
+    const SECOND: [&str; 4] = ["Coast", "Shore", "Strand", "Banks"];
+
+    // Stepping by 7 (coprime with 10) keeps the first word unique per world
+    let first = FIRST[(seed as usize + index * 7) % FIRST.len()];
+    let second = SECOND[(seed as usize / FIRST.len() + index) % SECOND.len()];
+    format!("The {} {}", first, second)
+}
+
+/// Extracts a stretch of a closed polygon of about `length`, centred on its
+/// northernmost point.
+fn northern_arc(points: &[Vec2], length: f32) -> Vec<Vec2> {
+    let n = points.len();
+    let Some(top) = (0..n).max_by(|&a, &b| points[a].y.total_cmp(&points[b].y)) else {
+        return Vec::new();
+    };
+
+    let mut start = top;
+    let mut walked = 0.0;
+    for _ in 0..n / 2 {
+        if walked >= length / 2.0 {
+            break;
+        }
+        let prev = (start + n - 1) % n;
+        walked += points[start].distance(points[prev]);
+        start = prev;
+    }
+
+    let mut arc = vec![points[start]];
+    let mut index = start;
+    let mut walked = 0.0;
+    for _ in 0..n - 1 {
+        if walked >= length {
+            break;
+        }
+        let next = (index + 1) % n;
+        walked += points[index].distance(points[next]);
+        arc.push(points[next]);
+        index = next;
+    }
+    arc
+}
+
+/// Names the largest landmasses with lettering along their northern shore.
+fn spawn_coast_names(
+    mut commands: Commands,
+    coastline_data: Res<CoastlineData>,
+    run_settings: Res<RunSettings>,
+    lod: Res<MapLod>,
+) {
+    let mut polygons: Vec<(f32, &Vec<Vec2>)> = coastline_data
+        .polygons
+        .iter()
+        .filter(|polygon| polygon.points.len() >= 3)
+        .map(|polygon| {
+            let mut perimeter = polyline_length(&polygon.points);
+            perimeter += polygon.points[0].distance(polygon.points[polygon.points.len() - 1]);
+            (perimeter, &polygon.points)
+        })
+        .collect();
+    polygons.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let detail = MapDetail {
+        min: LodLevel::Standard,
+        max: LodLevel::Overview,
+    };
+    let mut named = 0;
+
+    for (index, (perimeter, points)) in polygons.into_iter().take(COAST_NAME_COUNT).enumerate() {
+        let name = coast_name(run_settings.seed, index);
+        let text_length: f32 = name
+            .chars()
+            .map(|ch| glyph_advance(ch, COAST_NAME_FONT_SIZE) + COAST_NAME_LETTER_SPACING)
+            .sum();
+        let path_length = text_length * COAST_NAME_PATH_RATIO;
+        // Small islands would wrap the name around themselves
+        if perimeter < path_length * 3.0 {
+            continue;
+        }
+
+        let seaward = offset_polygon(points, COAST_NAME_OFFSET);
+        if seaward.len() < 3 {
+            continue;
+        }
+        let path = smooth_path(&northern_arc(&seaward, path_length), 4);
+
+        commands.spawn((
+            Name::new(format!("Coast Name: {}", name)),
+            CurvedLabel {
+                text: name,
+                path,
+                font_size: COAST_NAME_FONT_SIZE,
+                letter_spacing: COAST_NAME_LETTER_SPACING,
+            },
+            detail,
+            Transform::from_xyz(0.0, 0.0, 5.0),
+            decoration_visibility(true, &lod, Some(&detail)),
+            HighSeasEntity,
+        ));
+        named += 1;
+    }
+
+    info!("Named {} coasts", named);
+}
+
+/// Spawns the per-character text children of new curved labels.
+fn build_curved_label_glyphs(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    label_query: Query<(Entity, &CurvedLabel), Added<CurvedLabel>>,
+) {
+    if label_query.is_empty() {
+        return;
+    }
+    let font = asset_server.load("fonts/Quintessential-Regular.ttf");
+    let ink_color = Color::srgba(0.25, 0.18, 0.12, 0.75);
+
+    for (entity, label) in &label_query {
+        let glyphs = layout_along_path(&label.path, &label.text, label.font_size, label.letter_spacing);
+        if glyphs.is_empty() {
+            warn!("Curved label '{}' does not fit its path", label.text);
+            continue;
+        }
+
+        commands.entity(entity).with_children(|parent| {
+            for glyph in glyphs {
+                parent.spawn((
+                    Text2d::new(glyph.ch.to_string()),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: label.font_size,
+                        ..default()
+                    },
+                    TextColor(ink_color),
+                    Transform::from_translation(glyph.position.extend(0.0))
+                        .with_rotation(Quat::from_rotation_z(glyph.rotation)),
+                    CurvedLabelGlyph,
+                ));
+            }
+        });
+    }
+}
+
+/// Shows curved labels only at the zoom levels in their detail range.
+fn apply_curved_label_lod(
+    lod: Res<MapLod>,
+    mut label_query: Query<(&MapDetail, &mut Visibility), With<CurvedLabel>>,
+) {
+    if !lod.is_changed() {
+        return;
+    }
+    for (detail, mut visibility) in &mut label_query {
+        *visibility = decoration_visibility(true, &lod, Some(detail));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coast_name_is_deterministic() {
+        assert_eq!(coast_name(7, 0), coast_name(7, 0));
+        assert_ne!(coast_name(7, 0), coast_name(7, 1));
+        assert!(coast_name(7, 2).starts_with("The "));
+    }
+
+    #[test]
+    fn test_northern_arc_is_centred_on_top() {
+        // CCW square, 400 units a side
+        let square = [
+            Vec2::new(-200.0, -200.0),
+            Vec2::new(200.0, -200.0),
+            Vec2::new(200.0, 200.0),
+            Vec2::new(0.0, 201.0),
+            Vec2::new(-200.0, 200.0),
+        ];
+        let arc = northern_arc(&square, 400.0);
+        assert!(arc.contains(&Vec2::new(0.0, 201.0)));
+        assert!(polyline_length(&arc) >= 400.0);
+        assert!(arc.iter().all(|p| p.y >= 200.0));
+    }
+}
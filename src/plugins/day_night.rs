@@ -0,0 +1,36 @@
+//! The light coming and going over the sea.
+//!
+//! Shades the world map and the battle deeper blue as night falls, following
+//! the `WorldClock` (see `resources::day_night` for what else the dark does).
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::plugins::core::GameState;
+use crate::resources::{clock_darkness, WorldClock, NIGHT_SHADE_ALPHA};
+
+/// Plugin for the day/night shade.
+pub struct DayNightPlugin;
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            night_shade_ui
+                .after(EguiSet::InitContexts)
+                .run_if(in_state(GameState::HighSeas).or(in_state(GameState::Combat))),
+        );
+    }
+}
+
+/// Night blue over the world, beneath every window.
+fn night_shade_ui(mut contexts: EguiContexts, world_clock: Res<WorldClock>) {
+    let alpha = (clock_darkness(&world_clock) * NIGHT_SHADE_ALPHA) as u8;
+    if alpha == 0 {
+        return;
+    }
+    let ctx = contexts.ctx_mut();
+    let shade = egui::Color32::from_rgba_unmultiplied(10, 14, 40, alpha);
+    ctx.layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("night_shade")))
+        .rect_filled(ctx.screen_rect(), 0.0, shade);
+}
@@ -13,6 +13,8 @@ pub struct DebugToggles {
     pub show_debug_panel: bool,
     pub show_tilemap: bool,
     pub show_coastlines: bool,
+    /// Draw AI sightlines and what blocks them in battle.
+    pub show_sightlines: bool,
 }
 
 impl Default for DebugToggles {
@@ -21,6 +23,7 @@ impl Default for DebugToggles {
             show_debug_panel: false, // Hidden by default, toggle with F4
             show_tilemap: true,
             show_coastlines: true,
+            show_sightlines: false,
         }
     }
 }
@@ -38,7 +41,7 @@ impl Plugin for DebugUiPlugin {
             .add_systems(Update, (
                 toggle_debug_panel,
                 debug_panel.after(EguiSet::InitContexts),
-                apply_tilemap_visibility,
+                apply_tilemap_visibility.run_if(in_state(GameState::HighSeas)),
                 spawn_scale_test_ships.run_if(in_state(GameState::HighSeas)),
             ));
     }
@@ -140,6 +143,7 @@ fn debug_panel(
         ui.heading("Visibility");
         ui.checkbox(&mut toggles.show_tilemap, "Show Tilemap");
         ui.checkbox(&mut toggles.show_coastlines, "Show Coastlines");
+        ui.checkbox(&mut toggles.show_sightlines, "Show Sightlines (battle)");
 
         ui.separator();
         ui.heading("State Transitions");
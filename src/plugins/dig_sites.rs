@@ -0,0 +1,177 @@
+//! Treasure maps and the dig sites they mark on the High Seas.
+//!
+//! Every treasure location the player acquires, bought in a tavern or found
+//! in a bottle, is charted in `DigSites` and marked on the chart with a red X.
+//! Hove to within `DIG_RADIUS` of one, I (or the gamepad's east button) puts
+//! a boat ashore to dig it up; the gold comes aboard at once, but a guarded
+//! site brings the pirates watching it down on the ship.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use leafwing_input_manager::prelude::*;
+
+use crate::components::intel::{AcquiredIntel, IntelData, IntelType};
+use crate::components::{FactionId, Gold, HighSeasEntity, Player};
+use crate::events::CombatTriggeredEvent;
+use crate::plugins::core::GameState;
+use crate::plugins::input::PlayerAction;
+use crate::plugins::worldmap::HighSeasPlayer;
+use crate::resources::{DigSites, Journal, JournalCategory, MapData, WorldClock, DIG_RADIUS};
+use crate::utils::pathfinding::tile_to_world;
+
+/// Half the span of the X marking a dig site (world units).
+const MARK_SIZE: f32 = 14.0;
+const MARK_COLOR: Color = Color::srgba(0.7, 0.1, 0.1, 0.9);
+
+/// Plugin for treasure maps and digging up what they mark.
+pub struct DigSitesPlugin;
+
+impl Plugin for DigSitesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DigSites>()
+            .add_systems(OnEnter(GameState::MainMenu), reset_dig_sites)
+            .add_systems(Update, chart_treasure_maps.run_if(not(in_state(GameState::MainMenu))))
+            .add_systems(
+                Update,
+                (
+                    dig_for_treasure,
+                    sync_dig_site_markers,
+                    draw_dig_sites,
+                    dig_prompt_ui.after(EguiSet::InitContexts),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::HighSeas)),
+            );
+    }
+}
+
+/// Map marker for a charted dig site.
+#[derive(Component)]
+pub struct DigSiteMarker {
+    /// `DigSite::id` of the site shown.
+    pub id: u32,
+}
+
+fn reset_dig_sites(mut dig_sites: ResMut<DigSites>) {
+    dig_sites.clear();
+}
+
+/// Charts the treasure marked on each treasure map as it is acquired.
+fn chart_treasure_maps(
+    maps: Query<&IntelData, Added<AcquiredIntel>>,
+    map_data: Res<MapData>,
+    mut dig_sites: ResMut<DigSites>,
+) {
+    let mut rng = rand::thread_rng();
+    for intel in maps.iter().filter(|intel| intel.intel_type == IntelType::TreasureLocation) {
+        for marked in &intel.revealed_positions {
+            if let Some(id) = dig_sites.chart(&map_data, *marked, &mut rng) {
+                info!("Charted dig site {} for treasure marked at {:?}", id, marked);
+            }
+        }
+    }
+}
+
+/// Marks newly charted sites on the chart and clears the marks of sites dug up.
+fn sync_dig_site_markers(
+    mut commands: Commands,
+    dig_sites: Res<DigSites>,
+    markers: Query<(Entity, &DigSiteMarker)>,
+    map_data: Res<MapData>,
+) {
+    for (entity, marker) in &markers {
+        if dig_sites.get(marker.id).is_none() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    for site in &dig_sites.sites {
+        if markers.iter().any(|(_, marker)| marker.id == site.id) {
+            continue;
+        }
+        let position = tile_to_world(site.tile, map_data.width, map_data.height);
+        commands.spawn((
+            Name::new("Dig Site"),
+            DigSiteMarker { id: site.id },
+            Transform::from_translation(position.extend(1.5)),
+            HighSeasEntity,
+        ));
+    }
+}
+
+/// X marks the spot, ringed by how close the ship must come.
+fn draw_dig_sites(markers: Query<&Transform, With<DigSiteMarker>>, mut gizmos: Gizmos) {
+    for transform in &markers {
+        let at = transform.translation.truncate();
+        gizmos.line_2d(at + Vec2::new(-MARK_SIZE, -MARK_SIZE), at + Vec2::new(MARK_SIZE, MARK_SIZE), MARK_COLOR);
+        gizmos.line_2d(at + Vec2::new(-MARK_SIZE, MARK_SIZE), at + Vec2::new(MARK_SIZE, -MARK_SIZE), MARK_COLOR);
+        gizmos.circle_2d(Isometry2d::from_translation(at), DIG_RADIUS, MARK_COLOR.with_alpha(0.25));
+    }
+}
+
+/// The dig site within `DIG_RADIUS` of `position`, if any.
+fn site_in_reach<'a>(position: Vec2, markers: impl Iterator<Item = (&'a Transform, &'a DigSiteMarker)>) -> Option<u32> {
+    markers
+        .filter(|(transform, _)| transform.translation.truncate().distance(position) <= DIG_RADIUS)
+        .map(|(_, marker)| marker.id)
+        .next()
+}
+
+/// Digs up the site in reach when the player gives the word.
+fn dig_for_treasure(
+    action_query: Query<&ActionState<PlayerAction>>,
+    mut player_query: Query<(&Transform, &mut Gold), (With<HighSeasPlayer>, With<Player>)>,
+    markers: Query<(Entity, &Transform, &DigSiteMarker)>,
+    mut dig_sites: ResMut<DigSites>,
+    mut combat_events: EventWriter<CombatTriggeredEvent>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    let Ok(action_state) = action_query.get_single() else {
+        return;
+    };
+    if !action_state.just_pressed(&PlayerAction::Interact) {
+        return;
+    }
+    let Ok((player_transform, mut gold)) = player_query.get_single_mut() else {
+        return;
+    };
+    let in_reach = site_in_reach(
+        player_transform.translation.truncate(),
+        markers.iter().map(|(_, transform, marker)| (transform, marker)),
+    );
+    let Some(site) = in_reach.and_then(|id| dig_sites.dig(id)) else {
+        return;
+    };
+
+    gold.add(site.gold);
+    let mut entry = format!("Put a boat ashore where the map marked an X and dug up {} gold.", site.gold);
+    if site.guarded {
+        entry.push_str(" Pirates who had been watching the spot fell on us as the boat pulled back.");
+        if let Some((marker, _, _)) = markers.iter().find(|(_, _, marker)| marker.id == site.id) {
+            combat_events.send(CombatTriggeredEvent {
+                enemy_entity: marker,
+                enemy_faction: FactionId::Pirates,
+            });
+        }
+    }
+    journal.record_as(&world_clock, JournalCategory::Discovery, entry);
+}
+
+/// Offers to dig while a site is in reach.
+fn dig_prompt_ui(
+    mut contexts: EguiContexts,
+    player_query: Query<&Transform, (With<HighSeasPlayer>, With<Player>)>,
+    markers: Query<(&Transform, &DigSiteMarker)>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    if site_in_reach(player_transform.translation.truncate(), markers.iter()).is_none() {
+        return;
+    }
+    egui::Area::new(egui::Id::new("dig_prompt"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -140.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(egui::RichText::new("⛏ X marks the spot. Press I to dig.").strong());
+        });
+}
@@ -0,0 +1,192 @@
+//! Fleet combat: the player's escorts fight alongside her.
+//!
+//! Fleet ships escorting the flagship when a battle is joined
+//! (`EncounteredEnemy::escorts`) are spawned into the arena from their
+//! `ShipData` on her quarters and fight with the broadside AI. When the
+//! battle is left, won or not, the survivors' hulls and holds are written
+//! back to `PlayerFleet` and the ships sunk are struck from it.
+
+use bevy::prelude::*;
+
+use crate::components::upgrade::upgrades_hull_bonus;
+use crate::components::{Cargo, CombatEntity, Health, PlayerOwned, Ship};
+use crate::plugins::core::{despawn_scene_entities, GameState};
+use crate::plugins::worldmap::EncounteredEnemy;
+use crate::resources::PlayerFleet;
+use crate::systems::ai::{AICannonCooldown, AIState};
+use crate::systems::ship::spawn_fleet_ship;
+use crate::systems::{spawn_combat_enemies, Formation};
+
+/// Plugin for the player's fleet ships in battle.
+pub struct FleetCombatPlugin;
+
+impl Plugin for FleetCombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FleetInBattle>()
+            // The enemy musters against the escorts before they are taken
+            .add_systems(OnEnter(GameState::Combat), spawn_fleet_in_battle.after(spawn_combat_enemies))
+            // Read the ships before the arena is cleared away
+            .add_systems(
+                OnExit(GameState::Combat),
+                (settle_fleet_after_battle, reset_fleet_in_battle)
+                    .chain()
+                    .before(despawn_scene_entities::<CombatEntity>),
+            );
+    }
+}
+
+/// The fleet ships in the current battle.
+#[derive(Resource, Debug, Default)]
+pub struct FleetInBattle {
+    /// Index into `PlayerFleet::ships` and the ship's entity, for each one.
+    pub members: Vec<(usize, Entity)>,
+}
+
+/// How one fleet ship came out of a battle: her hull and hold if she is
+/// still afloat, `None` if she was sunk.
+pub type BattleOutcome = (usize, Option<(f32, Option<Cargo>)>);
+
+/// Brings the escorting fleet ships into the battle on the flagship's quarters.
+fn spawn_fleet_in_battle(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut encountered_enemy: ResMut<EncounteredEnemy>,
+    player_fleet: Res<PlayerFleet>,
+    mut fleet_in_battle: ResMut<FleetInBattle>,
+) {
+    let escorts: Vec<usize> = std::mem::take(&mut encountered_enemy.escorts)
+        .into_iter()
+        .filter(|index| *index < player_fleet.ships.len())
+        .collect();
+
+    // The flagship starts at the origin with her bow to the enemy
+    let count = escorts.len();
+    for (rank, index) in escorts.into_iter().enumerate() {
+        let position = Formation::Wedge.slot_offset(rank, count);
+        let entity = spawn_fleet_ship(&mut commands, &asset_server, position, &player_fleet.ships[index]);
+        commands.entity(entity).insert((AIState::default(), AICannonCooldown::default()));
+        fleet_in_battle.members.push((index, entity));
+    }
+
+    if !fleet_in_battle.members.is_empty() {
+        info!("{} fleet ships join the battle", fleet_in_battle.members.len());
+    }
+}
+
+/// Writes the fleet ships' damage back to `PlayerFleet` as the battle is
+/// left, whether it was won, fled or lost.
+fn settle_fleet_after_battle(
+    mut fleet_in_battle: ResMut<FleetInBattle>,
+    mut player_fleet: ResMut<PlayerFleet>,
+    ships: Query<(&Health, Option<&Cargo>), (With<Ship>, With<PlayerOwned>)>,
+) {
+    if fleet_in_battle.members.is_empty() {
+        return;
+    }
+
+    let outcomes: Vec<BattleOutcome> = std::mem::take(&mut fleet_in_battle.members)
+        .into_iter()
+        .map(|(index, entity)| {
+            let afloat = ships.get(entity).ok().map(|(health, cargo)| (health.hull, cargo.cloned()));
+            (index, afloat)
+        })
+        .collect();
+    settle_fleet(&mut player_fleet, outcomes);
+}
+
+/// Applies each fleet ship's battle outcome to `PlayerFleet`. Hull bonuses
+/// from upgrades are taken off again, and a ship that limped home keeps at
+/// least one point of hull.
+pub fn settle_fleet(player_fleet: &mut PlayerFleet, mut outcomes: Vec<BattleOutcome>) {
+    // Strike sunk ships from the back, so earlier indices stay put
+    outcomes.sort_unstable_by_key(|(index, _)| std::cmp::Reverse(*index));
+
+    for (index, afloat) in outcomes {
+        if index >= player_fleet.ships.len() {
+            continue;
+        }
+        match afloat {
+            Some((hull, cargo)) => {
+                let ship = &mut player_fleet.ships[index];
+                ship.hull_health = (hull - upgrades_hull_bonus(&ship.upgrades)).clamp(1.0, ship.max_hull_health);
+                if cargo.is_some() {
+                    ship.cargo = cargo;
+                }
+            }
+            None => {
+                let ship = player_fleet.ships.remove(index);
+                info!("{} was lost with all hands", ship.name);
+            }
+        }
+    }
+}
+
+fn reset_fleet_in_battle(mut fleet_in_battle: ResMut<FleetInBattle>) {
+    fleet_in_battle.members.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ShipData;
+
+    fn fleet(names: &[&str]) -> PlayerFleet {
+        PlayerFleet {
+            ships: names
+                .iter()
+                .map(|name| ShipData { name: name.to_string(), ..default() })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_damage_is_written_back_and_sunk_ships_struck() {
+        let mut player_fleet = fleet(&["Swift", "Gannet", "Petrel"]);
+        settle_fleet(
+            &mut player_fleet,
+            vec![(0, None), (1, Some((35.0, Some(Cargo::new(20))))), (2, None)],
+        );
+
+        assert_eq!(player_fleet.ships.len(), 1);
+        assert_eq!(player_fleet.ships[0].name, "Gannet");
+        assert_eq!(player_fleet.ships[0].hull_health, 35.0);
+        assert!(player_fleet.ships[0].cargo.is_some());
+    }
+
+    #[test]
+    fn test_hull_bonus_is_taken_off_again() {
+        let mut player_fleet = fleet(&["Swift"]);
+        player_fleet.ships[0].upgrades = vec![crate::components::upgrade::UpgradeKind::ReinforcedHull];
+        let bonus = upgrades_hull_bonus(&player_fleet.ships[0].upgrades);
+
+        settle_fleet(&mut player_fleet, vec![(0, Some((60.0 + bonus, None)))]);
+        assert_eq!(player_fleet.ships[0].hull_health, 60.0);
+
+        // A ship whose bonus planking took all the damage comes home barely afloat
+        settle_fleet(&mut player_fleet, vec![(0, Some((bonus * 0.5, None)))]);
+        assert_eq!(player_fleet.ships[0].hull_health, 1.0);
+    }
+
+    #[test]
+    fn test_escorts_keep_their_damage_when_the_battle_is_not_won() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.insert_resource(fleet(&["Swift", "Gannet"]));
+        let mut health = Health::new(100.0, 100.0, 100.0);
+        health.hull = 40.0;
+        let afloat = world.spawn((Ship, PlayerOwned, health)).id();
+        let sunk = world.spawn_empty().id();
+        world.despawn(sunk);
+        world.insert_resource(FleetInBattle { members: vec![(0, afloat), (1, sunk)] });
+
+        // No `CombatEndedEvent`: the player broke off the fight
+        world.run_system_once(settle_fleet_after_battle).unwrap();
+
+        let player_fleet = world.resource::<PlayerFleet>();
+        assert_eq!(player_fleet.ships.len(), 1);
+        assert_eq!(player_fleet.ships[0].name, "Swift");
+        assert_eq!(player_fleet.ships[0].hull_health, 40.0);
+        assert!(world.resource::<FleetInBattle>().members.is_empty());
+    }
+}
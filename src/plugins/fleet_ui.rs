@@ -4,6 +4,7 @@ use crate::resources::{PlayerFleet, FleetEntities};
 use crate::components::{OrderQueue, Order, PlayerOwned, Health, Cargo};
 use crate::components::contract::{Contract, ContractDetails, AcceptedContract, AssignedShip};
 use crate::systems::ai::AIState;
+use crate::systems::formation::{Formation, FleetFormation};
 use bevy::math::Vec2;
 
 /// Plugin for the Fleet Management UI.
@@ -13,6 +14,7 @@ impl Plugin for FleetUiPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<FleetUiState>()
+            .init_resource::<FleetFormation>()
             .add_event::<AssignOrderEvent>()
             .add_event::<AssignContractEvent>()
             .add_systems(Update, (
@@ -60,6 +62,7 @@ fn fleet_ui_system(
     mut commands: Commands,
     mut contexts: EguiContexts,
     mut ui_state: ResMut<FleetUiState>,
+    mut formation: ResMut<FleetFormation>,
     player_fleet: Res<PlayerFleet>,
     fleet_entities: Res<FleetEntities>,
     ship_query: Query<(Entity, Option<&Name>, &Health, Option<&Cargo>, Option<&OrderQueue>, Option<&AIState>)>,
@@ -91,6 +94,8 @@ fn fleet_ui_system(
             // Tab content
             match ui_state.selected_tab {
                 0 => {
+                    render_formation_picker(ui, &mut formation);
+                    ui.separator();
                     render_ship_list(ui, &mut commands, &player_fleet, &fleet_entities, &ship_query, &contract_query);
                 },
                 1 => {
@@ -102,6 +107,24 @@ fn fleet_ui_system(
 }
 
 
+/// Choice of the formation the fleet keeps around the flagship.
+fn render_formation_picker(ui: &mut egui::Ui, formation: &mut FleetFormation) {
+    ui.horizontal(|ui| {
+        ui.label("Formation:");
+        for choice in Formation::ALL {
+            if ui
+                .selectable_label(formation.formation == choice, choice.name())
+                .on_hover_text(choice.description())
+                .clicked()
+                && formation.formation != choice
+            {
+                formation.formation = choice;
+                info!("Fleet formation set to {}", choice.name());
+            }
+        }
+    });
+}
+
 fn render_ship_list(
     ui: &mut egui::Ui,
     commands: &mut Commands,
@@ -129,6 +152,10 @@ fn render_ship_list(
                             ui.strong(format!("{}. {}", i+1, if let Some(n) = name { n.as_str() } else { &ship_data.name }));
                             ui.label(format!("HP: {:.0}/{:.0}", health.hull, health.hull_max));
                         });
+                        ui.label(format!(
+                            "Upgrades: {}",
+                            crate::components::upgrade::upgrade_summary(&ship_data.upgrades)
+                        ));
                         
                         if let Some(cargo) = cargo {
                             ui.label(format!("Cargo: {}/{}", cargo.total_units(), cargo.capacity));
@@ -0,0 +1,377 @@
+//! Shore forts: the guns of a hostile port join battles fought under them.
+//!
+//! When a battle begins within `SHORE_BATTERY_RANGE` of a port whose flag is
+//! hostile to the player (by `Hostility`, so a letter of marque counts), that
+//! port's batteries are set at the edge of the arena in the direction the port
+//! lies. A fort throws a plunging volley at the nearest of the player's ships
+//! every few seconds, and stands until its walls are battered in. A silenced
+//! fort gives up its magazine on the loot screen, and the port's rivals think
+//! the better of the player for it.
+//! Forts are not ships: the battle is won when the enemy ships are beaten,
+//! whether or not the forts still fire.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+use crate::components::fort::{Fort, Silenced, FORT_RADIUS};
+use crate::components::{
+    AmmoType, Cargo, CombatEntity, Faction, FactionId, GoodType, Health, Player, PlayerOwned, Port, PortName, Projectile,
+    Ship, ShotHeight, TargetComponent, SHOT_GRAVITY,
+};
+use crate::events::WorldNewsEvent;
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::{EncounteredEnemy, Hostility};
+use crate::resources::{FactionRegistry, Journal, JournalCategory, NewsCategory, SpoilsSource, VictorySpoils, WorldClock};
+use crate::systems::combat::ProjectileTimer;
+
+/// How close to a hostile port a battle must begin for its forts to join it (world units; 8 tiles).
+pub const SHORE_BATTERY_RANGE: f32 = 512.0;
+/// How far from the flagship's starting position forts stand.
+const FORT_DISTANCE: f32 = 650.0;
+/// Distance between neighbouring forts.
+const FORT_SPACING: f32 = 220.0;
+/// Names of a port's forts, nearest the port's guns first.
+const FORT_NAMES: [&str; 2] = ["Citadel", "Water Battery"];
+/// Furthest a fort's guns reach.
+pub const FORT_RANGE: f32 = 900.0;
+/// Seconds a fort takes to reload between volleys.
+const FORT_RELOAD_SECONDS: f32 = 8.0;
+/// Bearing of each gun in a volley off the line to the target, in radians.
+const FORT_VOLLEY: [f32; 3] = [-0.05, 0.0, 0.05];
+/// Speed of a fort's shot over the water.
+const FORT_SHOT_SPEED: f32 = 420.0;
+/// Heavy shore guns hit harder than a ship's.
+const FORT_SHOT_DAMAGE: f32 = 1.5;
+/// Height of a fort's gun embrasures above the water.
+const FORT_GUN_HEIGHT: f32 = 30.0;
+/// Height a fort's shot has fallen to when it reaches the point it was laid on,
+/// below the side of any ship afloat.
+const FORT_SHOT_LANDING_HEIGHT: f32 = 2.0;
+/// Share of shot aimed at rigging or rudder that does any harm to stone.
+const FORT_GLANCING_SHARE: f32 = 0.25;
+/// Hull of a fort's walls.
+const FORT_HULL: f32 = 160.0;
+/// Gold in a silenced fort's strongroom.
+const FORT_PLUNDER_GOLD: u32 = 150;
+/// Small arms carried off from a silenced fort's armoury.
+const FORT_PLUNDER_WEAPONS: u32 = 8;
+/// Reputation lost with the port's flag for each fort silenced.
+const FORT_REPUTATION_LOSS: i32 = 5;
+/// Reputation gained with each of the port's rival nations for each fort silenced.
+const FORT_REPUTATION_GAIN: i32 = 3;
+
+const FORT_COLOR: Color = Color::srgb(0.55, 0.5, 0.42);
+const SILENCED_FORT_COLOR: Color = Color::srgb(0.25, 0.23, 0.2);
+
+/// Plugin for shore forts in battle.
+pub struct FortsPlugin;
+
+impl Plugin for FortsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShoreBattery>()
+            .add_systems(OnExit(GameState::HighSeas), chart_shore_battery)
+            .add_systems(OnEnter(GameState::Combat), spawn_forts)
+            .add_systems(FixedUpdate, fort_firing_system.run_if(in_state(GameState::Combat)))
+            .add_systems(
+                Update,
+                (fort_hit_system, fort_silenced_system.after(fort_hit_system)).run_if(in_state(GameState::Combat)),
+            )
+            .add_systems(OnExit(GameState::Combat), reset_shore_battery);
+    }
+}
+
+/// The hostile port whose guns cover the coming battle.
+#[derive(Resource, Debug, Default)]
+pub struct ShoreBattery {
+    pub port: Option<CoveringPort>,
+}
+
+/// A port close enough to a battle to bring its guns to bear.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoveringPort {
+    pub name: String,
+    pub faction: FactionId,
+    /// Direction from where the battle began to the port.
+    pub bearing: Vec2,
+    pub distance: f32,
+}
+
+impl CoveringPort {
+    /// How many forts cover the battle: both when it is fought close under
+    /// the port's walls, one further out.
+    pub fn forts(&self) -> usize {
+        if self.distance <= SHORE_BATTERY_RANGE / 2.0 {
+            FORT_NAMES.len()
+        } else {
+            1
+        }
+    }
+}
+
+/// Positions of `count` forts in the arena, in a line across `bearing`
+/// at `FORT_DISTANCE` from the flagship's starting position.
+pub fn fort_positions(bearing: Vec2, count: usize) -> Vec<Vec2> {
+    let bearing = bearing.normalize_or(Vec2::Y);
+    let across = bearing.perp();
+    let middle = (count as f32 - 1.0) / 2.0;
+    (0..count)
+        .map(|index| bearing * FORT_DISTANCE + across * (index as f32 - middle) * FORT_SPACING)
+        .collect()
+}
+
+/// Height and climb for a fort's shot to come down at
+/// `FORT_SHOT_LANDING_HEIGHT` after flying `distance`.
+pub fn plunging_shot(distance: f32) -> ShotHeight {
+    let flight = (distance / FORT_SHOT_SPEED).max(f32::EPSILON);
+    let climb = (FORT_SHOT_LANDING_HEIGHT - FORT_GUN_HEIGHT + 0.5 * SHOT_GRAVITY * flight * flight) / flight;
+    ShotHeight { height: FORT_GUN_HEIGHT, climb }
+}
+
+/// Damage a shot does to a fort's walls.
+pub fn fort_damage(projectile: &Projectile) -> f32 {
+    match projectile.target {
+        TargetComponent::Hull => projectile.damage,
+        TargetComponent::Sails | TargetComponent::Rudder => projectile.damage * FORT_GLANCING_SHARE,
+    }
+}
+
+/// Notes the nearest hostile port within range of a battle about to begin,
+/// while the ports are still on the chart.
+fn chart_shore_battery(
+    encountered_enemy: Res<EncounteredEnemy>,
+    hostility: Hostility,
+    ports: Query<(&Transform, &PortName, &Faction), With<Port>>,
+    mut shore_battery: ResMut<ShoreBattery>,
+) {
+    // The enemy's flag is only set when the High Seas are left for a battle
+    let Some(position) = encountered_enemy.position.filter(|_| encountered_enemy.faction.is_some()) else {
+        shore_battery.port = None;
+        return;
+    };
+
+    shore_battery.port = ports
+        .iter()
+        .filter(|(_, _, faction)| hostility.is_hostile(faction.0))
+        .map(|(transform, name, faction)| {
+            let offset = transform.translation.truncate() - position;
+            CoveringPort {
+                name: name.0.clone(),
+                faction: faction.0,
+                bearing: offset.normalize_or(Vec2::Y),
+                distance: offset.length(),
+            }
+        })
+        .filter(|port| port.distance <= SHORE_BATTERY_RANGE)
+        .min_by(|a, b| a.distance.total_cmp(&b.distance));
+}
+
+/// Raises the covering port's forts at the edge of the arena.
+fn spawn_forts(mut commands: Commands, shore_battery: Res<ShoreBattery>) {
+    let Some(port) = &shore_battery.port else {
+        return;
+    };
+
+    let walls = shapes::RegularPolygon {
+        sides: 5,
+        feature: shapes::RegularPolygonFeature::Radius(FORT_RADIUS),
+        ..default()
+    };
+    for (index, position) in fort_positions(port.bearing, port.forts()).into_iter().enumerate() {
+        let name = format!("{} {}", port.name, FORT_NAMES[index]);
+        let mut reload = Timer::from_seconds(FORT_RELOAD_SECONDS, TimerMode::Repeating);
+        // Neighbouring forts fire in turn rather than together
+        reload.set_elapsed(std::time::Duration::from_secs_f32(FORT_RELOAD_SECONDS * index as f32 / FORT_NAMES.len() as f32));
+        commands.spawn((
+            Name::new(name.clone()),
+            Fort { name, faction: port.faction, reload },
+            Health::new(0.0, 0.0, FORT_HULL),
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&walls),
+                transform: Transform::from_xyz(position.x, position.y, 1.0),
+                ..default()
+            },
+            Fill::color(FORT_COLOR),
+            Stroke::new(Color::BLACK, 3.0),
+            RigidBody::Static,
+            Collider::circle(FORT_RADIUS),
+            CombatEntity,
+        ));
+    }
+    info!("The guns of {} cover the battle ({} forts)", port.name, port.forts());
+}
+
+/// Fires each fort's volley at the nearest of the player's ships in range,
+/// laid where she will be when the shot comes down.
+fn fort_firing_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut forts: Query<(Entity, &Transform, &mut Fort), Without<Silenced>>,
+    targets: Query<(&Transform, &LinearVelocity), (With<Ship>, Or<(With<Player>, With<PlayerOwned>)>)>,
+) {
+    for (entity, transform, mut fort) in &mut forts {
+        fort.reload.tick(time.delta());
+        if !fort.reload.just_finished() {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let Some((target, velocity)) = targets
+            .iter()
+            .map(|(target, velocity)| (target.translation.truncate(), velocity.0))
+            .filter(|(target, _)| target.distance(position) <= FORT_RANGE)
+            .min_by(|a, b| a.0.distance_squared(position).total_cmp(&b.0.distance_squared(position)))
+        else {
+            continue;
+        };
+
+        let aim = target + velocity * (target.distance(position) / FORT_SHOT_SPEED);
+        let to_aim = aim - position;
+        let direction = to_aim.normalize_or(Vec2::Y);
+        for spread in FORT_VOLLEY {
+            commands.spawn((
+                Sprite {
+                    image: asset_server.load("sprites/projectile.png"),
+                    custom_size: Some(Vec2::new(16.0, 16.0)),
+                    ..default()
+                },
+                Transform::from_translation((position + direction * 48.0).extend(5.0)),
+                RigidBody::Dynamic,
+                Collider::circle(8.0),
+                Sensor,
+                LinearVelocity(Vec2::from_angle(spread).rotate(direction) * FORT_SHOT_SPEED),
+                Projectile::new(AmmoType::RoundShot, entity).scaled(FORT_SHOT_DAMAGE),
+                plunging_shot(to_aim.length()),
+                ProjectileTimer::default(),
+                CombatEntity,
+            ));
+        }
+        info!("{} fires a volley!", fort.name);
+    }
+}
+
+/// Batters forts with the player's shot. Shot from the enemy's ships and
+/// the forts themselves flies on.
+fn fort_hit_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<Collision>,
+    projectiles: Query<&Projectile>,
+    mut forts: Query<(&Fort, &mut Health), Without<Silenced>>,
+    player_side: Query<(), (With<Ship>, Or<(With<Player>, With<PlayerOwned>)>)>,
+) {
+    for Collision(contacts) in collision_events.read() {
+        let (shot, fort_entity) = if projectiles.contains(contacts.entity1) && forts.contains(contacts.entity2) {
+            (contacts.entity1, contacts.entity2)
+        } else if projectiles.contains(contacts.entity2) && forts.contains(contacts.entity1) {
+            (contacts.entity2, contacts.entity1)
+        } else {
+            continue;
+        };
+        let (Ok(projectile), Ok((fort, mut health))) = (projectiles.get(shot), forts.get_mut(fort_entity)) else {
+            continue;
+        };
+        if !player_side.contains(projectile.source) {
+            continue;
+        }
+
+        health.hull -= fort_damage(projectile);
+        info!("Hit! {} walls at {:.1}", fort.name, health.hull);
+        commands.entity(shot).despawn_recursive();
+    }
+}
+
+/// Silences forts battered in: their guns fall quiet, their strongroom is
+/// offered on the loot screen and word of it reaches the nations.
+fn fort_silenced_system(
+    mut commands: Commands,
+    mut forts: Query<(Entity, &Fort, &Health, &mut Fill), Without<Silenced>>,
+    mut factions: ResMut<FactionRegistry>,
+    mut spoils: ResMut<VictorySpoils>,
+    mut journal: ResMut<Journal>,
+    world_clock: Res<WorldClock>,
+    mut news: EventWriter<WorldNewsEvent>,
+) {
+    for (entity, fort, health, mut fill) in &mut forts {
+        if !health.is_destroyed() {
+            continue;
+        }
+        commands.entity(entity).insert(Silenced);
+        fill.color = SILENCED_FORT_COLOR;
+
+        let mut stores = Cargo::new(FORT_PLUNDER_WEAPONS);
+        stores.add(GoodType::Weapons, FORT_PLUNDER_WEAPONS);
+        spoils.sources.push(SpoilsSource {
+            name: fort.name.clone(),
+            surrendered: false,
+            gold: FORT_PLUNDER_GOLD,
+            hold: stores,
+            prize: None,
+        });
+
+        factions.adjust_reputation(fort.faction, -FORT_REPUTATION_LOSS);
+        for rival in [FactionId::NationA, FactionId::NationB, FactionId::NationC] {
+            if rival == fort.faction {
+                continue;
+            }
+            factions.adjust_reputation(rival, FORT_REPUTATION_GAIN);
+        }
+
+        journal.record_as(&world_clock, JournalCategory::Combat, format!("Silenced the guns of the {}.", fort.name));
+        news.send(WorldNewsEvent {
+            category: NewsCategory::Harbor,
+            headline: format!("The {} falls silent under a captain's guns", fort.name),
+        });
+        info!("{} is silenced!", fort.name);
+    }
+}
+
+fn reset_shore_battery(mut shore_battery: ResMut<ShoreBattery>) {
+    shore_battery.port = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forts_line_up_across_the_bearing() {
+        let port = CoveringPort { name: "Tortuga".into(), faction: FactionId::Pirates, bearing: Vec2::X, distance: 100.0 };
+        let positions = fort_positions(port.bearing, port.forts());
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[0].x, FORT_DISTANCE);
+        assert_eq!(positions[0].y, -positions[1].y);
+        assert_eq!(positions[0].distance(positions[1]), FORT_SPACING);
+
+        let distant = CoveringPort { distance: SHORE_BATTERY_RANGE, ..port };
+        assert_eq!(fort_positions(distant.bearing, distant.forts()), vec![Vec2::X * FORT_DISTANCE]);
+    }
+
+    #[test]
+    fn test_plunging_shot_comes_down_on_its_mark() {
+        for distance in [200.0, 500.0, FORT_RANGE] {
+            let mut shot = plunging_shot(distance);
+            let steps = 1000;
+            let delta = distance / FORT_SHOT_SPEED / steps as f32;
+            for _ in 0..steps {
+                shot.step(delta);
+            }
+            assert!((shot.height - FORT_SHOT_LANDING_HEIGHT).abs() < 0.5, "{} at {}", shot.height, distance);
+        }
+        // Halfway there it flies high over any deck
+        let mut shot = plunging_shot(FORT_RANGE);
+        for _ in 0..500 {
+            shot.step(FORT_RANGE / FORT_SHOT_SPEED / 1000.0);
+        }
+        assert!(shot.height > 15.0);
+    }
+
+    #[test]
+    fn test_chain_shot_glances_off_stone() {
+        let source = Entity::from_raw(1);
+        let round = Projectile::new(AmmoType::RoundShot, source);
+        assert_eq!(fort_damage(&round), round.damage);
+        let chain = Projectile::new(AmmoType::ChainShot, source);
+        assert!(fort_damage(&chain) < chain.damage);
+    }
+}
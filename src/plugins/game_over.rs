@@ -0,0 +1,102 @@
+//! Game over screen shown after the player's flagship is sunk.
+//!
+//! Explains the defeat rules for the run's difficulty and what became of the
+//! player's gold, cargo, companions and fleet (see `DefeatOutcome`), and
+//! opens the summary and replay of the battle that was lost.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::plugins::core::GameState;
+use crate::resources::ui_assets::UiAssets;
+use crate::resources::{BattleOutcome, BattleStats, DefeatOutcome};
+
+/// Plugin for the game over screen.
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            game_over_ui_system
+                .after(EguiSet::InitContexts)
+                .run_if(in_state(GameState::GameOver)),
+        );
+    }
+}
+
+/// Width of the parchment sheet the report is written on.
+const REPORT_WIDTH: f32 = 520.0;
+
+fn game_over_ui_system(
+    mut contexts: EguiContexts,
+    mut next_state: ResMut<NextState<GameState>>,
+    outcome: Res<DefeatOutcome>,
+    mut battle: ResMut<BattleStats>,
+    ui_assets: Res<UiAssets>,
+) {
+    let texture_id = contexts.add_image(ui_assets.parchment_texture.clone());
+
+    egui::CentralPanel::default().show(contexts.ctx_mut(), |ui| {
+        crate::plugins::ui_theme::draw_parchment_bg(ui, texture_id);
+        let panel_rect = ui.max_rect().shrink(20.0);
+        crate::plugins::ui_theme::draw_corner_flourishes(ui, panel_rect, 40.0);
+
+        ui.vertical_centered(|ui| {
+            ui.add_space(40.0);
+            ui.heading(egui::RichText::new("Lost at Sea").size(40.0).strong());
+            if !outcome.ship_name.is_empty() {
+                ui.label(egui::RichText::new(format!("The {} has gone down.", outcome.ship_name)).italics());
+            }
+            ui.add_space(10.0);
+            crate::plugins::ui_theme::draw_ornamental_divider(ui, ui.available_width().min(REPORT_WIDTH));
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(REPORT_WIDTH, ui.available_height()), |ui| {
+                ui.strong("Articles of Defeat");
+                for rule in &outcome.rules {
+                    ui.label(format!("• {}", rule));
+                }
+                ui.add_space(12.0);
+
+                ui.strong("The Reckoning");
+                ui.label(format!("💰 {} gold lost to the deep.", outcome.gold_lost));
+                ui.label(format!("⚓ {} gold left in your wreck for a future captain.", outcome.wreck_gold));
+                if outcome.wreck_cargo.is_empty() {
+                    ui.label("📦 No cargo survived.");
+                } else {
+                    let cargo: Vec<String> = outcome
+                        .wreck_cargo
+                        .iter()
+                        .map(|(good, quantity)| format!("{} {}", quantity, good))
+                        .collect();
+                    ui.label(format!("📦 Salvageable cargo: {}", cargo.join(", ")));
+                }
+                if !outcome.companions_to_taverns.is_empty() {
+                    ui.label(format!(
+                        "🍺 Gone ashore to the taverns: {}",
+                        outcome.companions_to_taverns.join(", ")
+                    ));
+                }
+                if !outcome.ships_recoverable.is_empty() {
+                    ui.label(format!(
+                        "⛵ Scattered, to be reclaimed at port: {}",
+                        outcome.ships_recoverable.join(", ")
+                    ));
+                }
+                if !outcome.ships_lost.is_empty() {
+                    ui.label(format!("☠ Never seen again: {}", outcome.ships_lost.join(", ")));
+                }
+            });
+
+            ui.add_space(24.0);
+            if battle.outcome == BattleOutcome::Lost && ui.button("📋 The last battle").clicked() {
+                battle.open = true;
+            }
+            ui.add_space(8.0);
+            if ui.button(egui::RichText::new("Return to Main Menu").size(20.0)).clicked() {
+                next_state.set(GameState::MainMenu);
+            }
+        });
+    });
+}
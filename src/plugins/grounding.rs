@@ -0,0 +1,258 @@
+//! Running aground: the coast in battles fought close inshore.
+//!
+//! When a battle begins near land, the stretch of coast around it is charted
+//! from `MapData` and raised at the edge of the arena, each land tile a rock
+//! ships cannot sail through. A ship that strikes the coast takes hull damage
+//! by how hard she struck, read from the contact impulse as for a collision
+//! between ships, and is held fast (`Aground`) for longer the harder she hit.
+//! AI ships keep a lookout ahead and bear away from the rocks, so a captain
+//! who knows the coast can pin a foe against a lee shore or lure a careless
+//! chaser onto it. The rocks block sight, so a ship can hide behind the land
+//! (see `plugins::line_of_sight`).
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::components::{Aground, BlocksSight, CombatEntity, Coast, Health, Ship, TargetComponent, WaterIntake};
+use crate::events::ShipHitEvent;
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::EncounteredEnemy;
+use crate::resources::MapData;
+use crate::systems::{combat_ai_system, record_ship_hits, ship_physics_system};
+use crate::utils::pathfinding::{tile_to_world, world_to_tile, TILE_SIZE};
+
+/// How far from where a battle begins the coast is charted (tiles).
+const COAST_REACH_TILES: i32 = 9;
+/// Arena units to a world unit: the arena is drawn a little larger than the chart.
+const ARENA_SCALE: f32 = 1.5;
+/// Side of a rock in the arena: one land tile, scaled.
+pub const ROCK_SIZE: f32 = TILE_SIZE * ARENA_SCALE;
+/// Closest the coast comes to the middle of the arena, so no ship is spawned on it.
+const COAST_CLEARANCE: f32 = 560.0;
+/// Impact speed below which a ship only touches bottom (world units per second).
+pub const GROUNDING_MIN_SPEED: f32 = 30.0;
+/// Hull damage for each unit of impact speed over `GROUNDING_MIN_SPEED`;
+/// rock gives less than another ship's side.
+pub const GROUNDING_DAMAGE_PER_SPEED: f32 = 0.2;
+/// Seconds held fast after the lightest grounding.
+const AGROUND_MIN_SECONDS: f32 = 1.5;
+/// Further seconds held fast per unit of impact speed.
+const AGROUND_SECONDS_PER_SPEED: f32 = 0.02;
+/// Longest a ship is held fast.
+const AGROUND_MAX_SECONDS: f32 = 6.0;
+/// Water intake rate opened per point of grounding damage.
+const LEAK_PER_DAMAGE: f32 = 0.15;
+/// How close to a rock an AI ship's lookout begins to bear away (arena units).
+pub const COAST_LOOKOUT_RANGE: f32 = 180.0;
+
+const COAST_COLOR: Color = Color::srgb(0.62, 0.55, 0.38);
+
+/// Plugin for the coast in battle and ships running aground on it.
+pub struct GroundingPlugin;
+
+impl Plugin for GroundingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BattleCoast>()
+            .add_systems(OnExit(GameState::HighSeas), chart_battle_coast)
+            .add_systems(OnEnter(GameState::Combat), spawn_coast)
+            .add_systems(
+                FixedUpdate,
+                hold_aground_ships
+                    .after(ship_physics_system)
+                    .after(combat_ai_system)
+                    .run_if(in_state(GameState::Combat)),
+            )
+            .add_systems(
+                Update,
+                (grounding_system.before(record_ship_hits), float_free_system.after(grounding_system)).run_if(in_state(GameState::Combat)),
+            )
+            .add_systems(OnExit(GameState::Combat), reset_battle_coast);
+    }
+}
+
+/// The land around the coming battle, as rock positions in the arena.
+#[derive(Resource, Debug, Default)]
+pub struct BattleCoast {
+    pub rocks: Vec<Vec2>,
+}
+
+/// Arena positions of the land within `COAST_REACH_TILES` of `position`,
+/// measured from the middle of the arena where the battle begins. Land
+/// closer than `COAST_CLEARANCE` is left out so the fleets have room to form.
+pub fn coast_rocks(map: &MapData, position: Vec2) -> Vec<Vec2> {
+    let centre = world_to_tile(position, map.width, map.height);
+    let mut rocks = Vec::new();
+    for dy in -COAST_REACH_TILES..=COAST_REACH_TILES {
+        for dx in -COAST_REACH_TILES..=COAST_REACH_TILES {
+            let tile = centre + IVec2::new(dx, dy);
+            if !map.in_bounds(tile.x, tile.y) || map.is_navigable(tile.x as u32, tile.y as u32) {
+                continue;
+            }
+            let rock = (tile_to_world(tile, map.width, map.height) - position) * ARENA_SCALE;
+            if rock.length() >= COAST_CLEARANCE {
+                rocks.push(rock);
+            }
+        }
+    }
+    rocks
+}
+
+/// Hull damage from striking the coast at `impact_speed`.
+pub fn grounding_damage(impact_speed: f32) -> f32 {
+    (impact_speed - GROUNDING_MIN_SPEED).max(0.0) * GROUNDING_DAMAGE_PER_SPEED
+}
+
+/// Seconds a ship striking the coast at `impact_speed` is held fast.
+pub fn aground_seconds(impact_speed: f32) -> f32 {
+    (AGROUND_MIN_SECONDS + impact_speed * AGROUND_SECONDS_PER_SPEED).min(AGROUND_MAX_SECONDS)
+}
+
+/// Direction and strength pushing a ship's lookout at `lookout` away from the
+/// rocks within `COAST_LOOKOUT_RANGE` of it; zero in open water.
+pub fn coast_avoidance(lookout: Vec2, rocks: impl IntoIterator<Item = Vec2>) -> Vec2 {
+    let reach = COAST_LOOKOUT_RANGE + ROCK_SIZE / 2.0;
+    rocks
+        .into_iter()
+        .map(|rock| lookout - rock)
+        .filter(|offset| offset.length() < reach)
+        .map(|offset| offset.normalize_or(Vec2::Y) * (1.0 - offset.length() / reach))
+        .sum()
+}
+
+/// Charts the coast around a battle about to begin, while the chart is still to hand.
+fn chart_battle_coast(encountered_enemy: Res<EncounteredEnemy>, map_data: Res<MapData>, mut coast: ResMut<BattleCoast>) {
+    // The enemy's flag is only set when the High Seas are left for a battle
+    coast.rocks = encountered_enemy
+        .position
+        .filter(|_| encountered_enemy.faction.is_some())
+        .map(|position| coast_rocks(&map_data, position))
+        .unwrap_or_default();
+}
+
+/// Raises the charted coast around the arena.
+fn spawn_coast(mut commands: Commands, coast: Res<BattleCoast>) {
+    for rock in &coast.rocks {
+        commands.spawn((
+            Name::new("Coast"),
+            Coast,
+            Sprite::from_color(COAST_COLOR, Vec2::splat(ROCK_SIZE)),
+            Transform::from_xyz(rock.x, rock.y, 0.5),
+            RigidBody::Static,
+            Collider::rectangle(ROCK_SIZE, ROCK_SIZE),
+            BlocksSight { radius: ROCK_SIZE / 2.0 },
+            CombatEntity,
+        ));
+    }
+    if !coast.rocks.is_empty() {
+        info!("The battle is fought off a coast ({} rocks)", coast.rocks.len());
+    }
+}
+
+/// Ships that strike the coast are holed by how hard they struck and run aground.
+fn grounding_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<Collision>,
+    mut ships: Query<(&Transform, &Mass, &mut Health, Option<&mut WaterIntake>), With<Ship>>,
+    coast: Query<(), With<Coast>>,
+    mut ship_hit_events: EventWriter<ShipHitEvent>,
+) {
+    for Collision(contacts) in collision_events.read() {
+        // Only the blow as she strikes; a ship lying against the rocks takes no more harm
+        if contacts.during_previous_frame {
+            continue;
+        }
+        let (ship_entity, rock) = if coast.contains(contacts.entity2) {
+            (contacts.entity1, contacts.entity2)
+        } else if coast.contains(contacts.entity1) {
+            (contacts.entity2, contacts.entity1)
+        } else {
+            continue;
+        };
+        let Ok((transform, mass, mut health, water_intake)) = ships.get_mut(ship_entity) else {
+            continue;
+        };
+
+        // The rock does not give, so the ship takes the whole blow
+        let impact_speed = contacts.total_normal_impulse / mass.0.max(f32::EPSILON);
+        let damage = grounding_damage(impact_speed);
+        if damage <= 0.0 {
+            continue;
+        }
+
+        health.hull -= damage;
+        let leak = damage * LEAK_PER_DAMAGE;
+        if let Some(mut intake) = water_intake {
+            intake.increase_rate(leak);
+        } else {
+            commands.entity(ship_entity).try_insert(WaterIntake::new(leak));
+        }
+        commands.entity(ship_entity).try_insert(Aground::new(aground_seconds(impact_speed)));
+
+        ship_hit_events.send(ShipHitEvent {
+            ship_entity,
+            hit_position: transform.translation.truncate(),
+            damage,
+            attacker: rock,
+            component: TargetComponent::Hull,
+        });
+        info!("Run aground! {:.0} hull damage", damage);
+    }
+}
+
+/// Ships aground are held fast where they struck.
+fn hold_aground_ships(mut ships: Query<&mut LinearVelocity, (With<Ship>, With<Aground>)>) {
+    for mut velocity in &mut ships {
+        velocity.0 = Vec2::ZERO;
+    }
+}
+
+/// Ships float free once their time is up.
+fn float_free_system(mut commands: Commands, time: Res<Time>, mut ships: Query<(Entity, &mut Aground)>) {
+    for (entity, mut aground) in &mut ships {
+        if aground.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Aground>();
+        }
+    }
+}
+
+fn reset_battle_coast(mut coast: ResMut<BattleCoast>) {
+    coast.rocks.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::TileType;
+
+    #[test]
+    fn test_coast_charted_clear_of_the_fleets() {
+        let mut map = MapData::new(64, 64);
+        // A coast running north-south seven tiles east of the battle
+        for x in 39..64 {
+            for y in 0..64 {
+                map.set_type(x, y, TileType::Land);
+            }
+        }
+        let position = tile_to_world(IVec2::new(32, 32), map.width, map.height);
+        let rocks = coast_rocks(&map, position);
+
+        assert!(!rocks.is_empty());
+        assert!(rocks.iter().all(|rock| rock.x > 0.0 && rock.length() >= COAST_CLEARANCE));
+        assert!(coast_rocks(&MapData::new(64, 64), position).is_empty());
+    }
+
+    #[test]
+    fn test_harder_groundings_hurt_more_and_hold_longer() {
+        assert_eq!(grounding_damage(GROUNDING_MIN_SPEED), 0.0);
+        assert!(grounding_damage(150.0) > grounding_damage(80.0));
+        assert!(aground_seconds(150.0) > aground_seconds(80.0));
+        assert_eq!(aground_seconds(10_000.0), AGROUND_MAX_SECONDS);
+    }
+
+    #[test]
+    fn test_lookout_bears_away_from_rocks() {
+        let rock = Vec2::new(100.0, 0.0);
+        assert!(coast_avoidance(Vec2::ZERO, [rock]).x < 0.0);
+        assert_eq!(coast_avoidance(Vec2::new(-500.0, 0.0), [rock]), Vec2::ZERO);
+    }
+}
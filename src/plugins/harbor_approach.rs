@@ -0,0 +1,391 @@
+//! The harbour approach scene between the High Seas and the port screen.
+//!
+//! Arriving at a port enters `GameState::HarborApproach`. For a town or city,
+//! with the scene turned on in the settings, a side-on view of the harbour is
+//! painted in ink on parchment: hills, the waterfront and the sea slide past
+//! at their own pace while the player's ship stands in. A click, Space or
+//! Escape skips it. Anywhere else the port screen opens at once. See
+//! `resources::harbor_approach` for how the scene is chosen.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::weather::InWeather;
+use crate::components::{port_key, Player, PortSize, Ship};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::CurrentPort;
+use crate::plugins::ui_theme::{draw_parchment_bg, INK_COLOR};
+use crate::resources::ui_assets::UiAssets;
+use crate::resources::{
+    clock_darkness, parallax_offset, Approach, GameSettings, HarborApproach, HarborWeather,
+    PortMarkets, Wind, WorldClock,
+};
+
+/// Share of the scene's width the layers slide across over the approach.
+const PAN_SHARE: f32 = 0.25;
+/// Height of the horizon down the scene, as a share of its height.
+const HORIZON_SHARE: f32 = 0.6;
+/// Width of the ink outlines.
+const INK_WIDTH: f32 = 2.0;
+
+/// Sky wash in a squall, and its opacity.
+const SQUALL_SKY: [u8; 3] = [90, 90, 95];
+const SQUALL_SKY_ALPHA: u8 = 120;
+/// Sky wash at night, and its opacity at full dark.
+const NIGHT_SKY: [u8; 3] = [10, 14, 40];
+const NIGHT_SKY_ALPHA: f32 = 150.0;
+
+/// How fast the hills slide past, against the sea.
+const HILLS_PARALLAX: f32 = 0.15;
+const HILL_COUNT: usize = 5;
+/// Centre of the first hill and the gap to the next, as shares of the width.
+const FIRST_HILL_SHARE: f32 = 0.1;
+const HILL_GAP_SHARE: f32 = 0.25;
+/// Radius of a low hill, and how much more every other hill has, as shares
+/// of the height.
+const HILL_RADIUS_SHARE: f32 = 0.16;
+const TALL_HILL_EXTRA_SHARE: f32 = 0.04;
+/// Points along a hill's ridge, and how flat it is against a half circle.
+const HILL_RIDGE_STEPS: usize = 12;
+const HILL_FLATTENING: f32 = 0.6;
+const HILL_FILL: egui::Color32 = egui::Color32::from_rgb(205, 190, 150);
+
+/// How fast the town slides past, against the sea.
+const TOWN_PARALLAX: f32 = 0.4;
+/// Left end of the waterfront, as a share of the width.
+const TOWN_LEFT_SHARE: f32 = 0.45;
+/// Width of a house as a share of the scene's width; the rest of the town is
+/// measured in house widths.
+const HOUSE_WIDTH_SHARE: f32 = 0.035;
+/// Distance from one house to the next.
+const HOUSE_PITCH: f32 = 1.1;
+/// Extra height each storey step adds to a house; houses vary over
+/// `HOUSE_STOREY_STEPS` steps.
+const HOUSE_STOREY: f32 = 0.35;
+const HOUSE_STOREY_STEPS: usize = 5;
+/// Rise of a roof's peak, and how far its eaves overhang (pixels).
+const ROOF_RISE: f32 = 0.6;
+const EAVES_OVERHANG: f32 = 3.0;
+const HOUSE_FILL: egui::Color32 = egui::Color32::from_rgb(225, 210, 175);
+const ROOF_FILL: egui::Color32 = egui::Color32::from_rgb(150, 80, 60);
+/// Darkness from which lamps are lit in the windows, and a window's size.
+const LAMPLIGHT_DARKNESS: f32 = 0.5;
+const WINDOW_SIZE: f32 = 0.3;
+const LAMP_FILL: egui::Color32 = egui::Color32::from_rgb(240, 200, 90);
+
+/// A city's fort: its height and width, and its battlements.
+const FORT_HEIGHT: f32 = 2.0;
+const FORT_WIDTH: f32 = 3.0;
+const MERLON_COUNT: usize = 4;
+const MERLON_PITCH: f32 = 0.8;
+const MERLON_SIZE: f32 = 0.4;
+const FORT_FILL: egui::Color32 = egui::Color32::from_rgb(180, 170, 150);
+
+/// Height of the flagpole and size of the flag.
+const FLAGPOLE_HEIGHT: f32 = 1.2;
+const FLAG_SIZE: egui::Vec2 = egui::vec2(0.8, 0.5);
+/// Flag shown when the port flies no nation's colours.
+const UNCLAIMED_FLAG: [u8; 3] = [230, 225, 210];
+
+const SEA_WASH: [u8; 4] = [70, 110, 140, 90];
+/// Wave height and rows of waves in fair weather and when it blows.
+const FAIR_SWELL: (f32, usize) = (4.0, 4);
+const ROUGH_SWELL: (f32, usize) = (9.0, 7);
+/// Gap between waves along a row, drop from the horizon to the first row,
+/// and how far each row is staggered from the last (pixels).
+const WAVE_SPACING: f32 = 60.0;
+const FIRST_WAVE_ROW: f32 = 20.0;
+const WAVE_ROW_STAGGER: f32 = 17.0;
+/// Where a wave's crest and far foot fall, as shares of the spacing.
+const WAVE_CREST: f32 = 0.3;
+const WAVE_LENGTH: f32 = 0.6;
+const WAVE_INK_WIDTH: f32 = 1.5;
+
+const RAIN: [u8; 4] = [60, 60, 70, 140];
+/// Speed of the rain across the scene, the gap between streaks and how far
+/// each slants on its way down (pixels).
+const RAIN_SPEED: f32 = 300.0;
+const RAIN_SPACING: f32 = 40.0;
+const RAIN_SLANT: f32 = 30.0;
+
+/// Where the ship starts and how far she stands in, as shares of the width.
+const SHIP_START_SHARE: f32 = 0.08;
+const SHIP_RUN_SHARE: f32 = 0.3;
+/// Waterline below the horizon as a share of the height, and how fast and
+/// far (against the swell) she rolls.
+const WATERLINE_SHARE: f32 = 0.12;
+const ROLL_RATE: f32 = 2.5;
+const ROLL_SHARE: f32 = 0.5;
+/// Length of the ship as a share of the width; the rest of her is measured
+/// in lengths.
+const SHIP_LENGTH_SHARE: f32 = 0.14;
+/// Height of her deck above the waterline, and where her keel meets the
+/// stern and the bow.
+const FREEBOARD: f32 = 0.12;
+const STERN_FOOT: f32 = 0.85;
+const BOW_FOOT: f32 = 0.12;
+/// Each mast's place along her and height.
+const MASTS: [(f32, f32); 2] = [(0.35, 0.9), (0.65, 1.0)];
+/// A sail's width, and its height against its mast.
+const SAIL_WIDTH: f32 = 0.22;
+const SAIL_DROP: f32 = 0.6;
+const SAIL_ROUNDING: f32 = 2.0;
+const HULL_FILL: egui::Color32 = egui::Color32::from_rgb(110, 75, 45);
+const SAIL_FILL: egui::Color32 = egui::Color32::from_rgb(245, 240, 225);
+
+/// Plugin for the harbour approach scene.
+pub struct HarborApproachPlugin;
+
+impl Plugin for HarborApproachPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HarborApproach>()
+            .add_systems(OnEnter(GameState::HarborApproach), begin_harbor_approach)
+            .add_systems(
+                Update,
+                harbor_approach_ui
+                    .after(EguiSet::InitContexts)
+                    .run_if(in_state(GameState::HarborApproach)),
+            )
+            .add_systems(OnExit(GameState::HarborApproach), end_harbor_approach);
+    }
+}
+
+/// The wind, the hour and whether the player's ship came in out of a squall.
+#[derive(SystemParam)]
+struct HarborConditions<'w, 's> {
+    wind: Res<'w, Wind>,
+    world_clock: Res<'w, WorldClock>,
+    player: Query<'w, 's, Has<InWeather>, (With<Player>, With<Ship>)>,
+}
+
+impl HarborConditions<'_, '_> {
+    fn weather(&self) -> HarborWeather {
+        let in_squall = self.player.get_single().unwrap_or(false);
+        HarborWeather::from_conditions(self.wind.strength, in_squall)
+    }
+}
+
+/// Sets the scene for the port just reached, or goes straight ashore.
+fn begin_harbor_approach(
+    current_port: Res<CurrentPort>,
+    markets: Res<PortMarkets>,
+    settings: Res<GameSettings>,
+    conditions: HarborConditions,
+    mut harbor: ResMut<HarborApproach>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // The port entities are gone with the High Seas; their markets are kept
+    let goods = current_port
+        .position
+        .and_then(|position| markets.get(port_key(position)))
+        .map_or(0, |market| market.inventory.goods.len());
+    let size = PortSize::from_goods(goods);
+    if !settings.harbor_approach || !size.is_major() {
+        next_state.set(GameState::Port);
+        return;
+    }
+
+    harbor.approach = Some(Approach {
+        port_name: current_port.name.clone().unwrap_or_else(|| "Unknown Port".to_string()),
+        faction: current_port.faction,
+        size,
+        weather: conditions.weather(),
+        darkness: clock_darkness(&conditions.world_clock),
+        elapsed: 0.0,
+    });
+}
+
+/// Plays the approach, then opens the port screen.
+fn harbor_approach_ui(
+    mut contexts: EguiContexts,
+    time: Res<Time<Real>>,
+    ui_assets: Res<UiAssets>,
+    mut harbor: ResMut<HarborApproach>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(approach) = harbor.approach.as_mut() else {
+        next_state.set(GameState::Port);
+        return;
+    };
+    approach.elapsed += time.delta_secs();
+
+    let texture_id = contexts.add_image(ui_assets.parchment_texture.clone());
+    let ctx = contexts.ctx_mut();
+    let skipped = ctx.input(|i| {
+        i.key_pressed(egui::Key::Escape) || i.key_pressed(egui::Key::Space) || i.pointer.any_click()
+    });
+
+    egui::CentralPanel::default().show(ctx, |ui| {
+        draw_parchment_bg(ui, texture_id);
+        paint_harbor(ui.painter(), ui.max_rect(), approach);
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.heading(egui::RichText::new(&approach.port_name).size(32.0).color(INK_COLOR));
+            if let Some(faction) = approach.faction {
+                ui.label(egui::RichText::new(faction.display_name()).color(INK_COLOR));
+            }
+        });
+        ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
+            ui.add_space(10.0);
+            ui.weak("Click or press Space to go ashore");
+        });
+    });
+
+    if skipped || approach.is_done() {
+        next_state.set(GameState::Port);
+    }
+}
+
+fn end_harbor_approach(mut harbor: ResMut<HarborApproach>) {
+    harbor.approach = None;
+}
+
+/// Paints the harbour from back to front: sky, hills, waterfront, sea and ship.
+fn paint_harbor(painter: &egui::Painter, rect: egui::Rect, approach: &Approach) {
+    let progress = approach.progress();
+    let pan = rect.width() * PAN_SHARE;
+    let horizon = rect.top() + rect.height() * HORIZON_SHARE;
+    let ink = INK_COLOR;
+    let stroke = egui::Stroke::new(INK_WIDTH, ink);
+
+    // Sky: washed out in a squall, deep blue at night
+    let ([r, g, b], sky_alpha) = match approach.weather {
+        HarborWeather::Squall => (SQUALL_SKY, SQUALL_SKY_ALPHA),
+        _ => (NIGHT_SKY, (approach.darkness * NIGHT_SKY_ALPHA) as u8),
+    };
+    let sky_tint = egui::Color32::from_rgba_unmultiplied(r, g, b, sky_alpha);
+    painter.rect_filled(egui::Rect::from_min_max(rect.min, egui::pos2(rect.right(), horizon)), 0.0, sky_tint);
+
+    // Hills behind the town, slow to move
+    let hills_x = parallax_offset(progress, HILLS_PARALLAX, pan);
+    for i in 0..HILL_COUNT {
+        let center_x = rect.left() + hills_x + rect.width() * (FIRST_HILL_SHARE + HILL_GAP_SHARE * i as f32);
+        let radius = rect.height() * (HILL_RADIUS_SHARE + TALL_HILL_EXTRA_SHARE * (i % 2) as f32);
+        let ridge = (0..=HILL_RIDGE_STEPS)
+            .map(|step| {
+                let angle = std::f32::consts::PI * step as f32 / HILL_RIDGE_STEPS as f32;
+                egui::pos2(center_x + radius * angle.cos(), horizon - radius * HILL_FLATTENING * angle.sin())
+            })
+            .collect();
+        painter.add(egui::Shape::convex_polygon(ridge, HILL_FILL, stroke));
+    }
+
+    // The waterfront, houses and a fort for a city
+    let town_x = parallax_offset(progress, TOWN_PARALLAX, pan);
+    let town_left = rect.left() + rect.width() * TOWN_LEFT_SHARE + town_x;
+    let house_width = rect.width() * HOUSE_WIDTH_SHARE;
+    let count = approach.size.buildings();
+    let mut tallest = egui::pos2(town_left, horizon);
+    for i in 0..count {
+        let height = house_width * (1.0 + ((i * 37) % HOUSE_STOREY_STEPS) as f32 * HOUSE_STOREY);
+        let left = town_left + i as f32 * house_width * HOUSE_PITCH;
+        let house = egui::Rect::from_min_max(egui::pos2(left, horizon - height), egui::pos2(left + house_width, horizon));
+        painter.rect(house, 0.0, HOUSE_FILL, stroke);
+        let peak = egui::pos2(left + house_width / 2.0, horizon - height - house_width * ROOF_RISE);
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                egui::pos2(left - EAVES_OVERHANG, horizon - height),
+                peak,
+                egui::pos2(left + house_width + EAVES_OVERHANG, horizon - height),
+            ],
+            ROOF_FILL,
+            stroke,
+        ));
+        // Lamps in the windows after dark
+        if approach.darkness > LAMPLIGHT_DARKNESS {
+            let window = egui::Rect::from_center_size(house.center(), egui::Vec2::splat(house_width * WINDOW_SIZE));
+            painter.rect_filled(window, 0.0, LAMP_FILL);
+        }
+        if peak.y < tallest.y {
+            tallest = peak;
+        }
+    }
+    if approach.size.has_fort() {
+        let left = town_left + count as f32 * house_width * HOUSE_PITCH + house_width;
+        let fort = egui::Rect::from_min_max(
+            egui::pos2(left, horizon - house_width * FORT_HEIGHT),
+            egui::pos2(left + house_width * FORT_WIDTH, horizon),
+        );
+        painter.rect(fort, 0.0, FORT_FILL, stroke);
+        let merlon = house_width * MERLON_SIZE;
+        for i in 0..MERLON_COUNT {
+            let x = left + i as f32 * house_width * MERLON_PITCH;
+            painter.rect(
+                egui::Rect::from_min_size(egui::pos2(x, fort.top() - merlon), egui::Vec2::splat(merlon)),
+                0.0,
+                FORT_FILL,
+                stroke,
+            );
+        }
+        tallest = egui::pos2(fort.center().x, fort.top());
+    }
+
+    // The port's flag over its tallest building
+    let [r, g, b] = approach.faction.map_or(UNCLAIMED_FLAG, |faction| faction.flag_rgb());
+    let pole_top = tallest - egui::vec2(0.0, house_width * FLAGPOLE_HEIGHT);
+    painter.line_segment([tallest, pole_top], stroke);
+    painter.rect(
+        egui::Rect::from_min_size(pole_top, FLAG_SIZE * house_width),
+        0.0,
+        egui::Color32::from_rgb(r, g, b),
+        egui::Stroke::new(1.0, ink),
+    );
+
+    // The sea, quickest of all; higher waves when it blows
+    let sea = egui::Rect::from_min_max(egui::pos2(rect.left(), horizon), rect.max);
+    let [r, g, b, a] = SEA_WASH;
+    painter.rect_filled(sea, 0.0, egui::Color32::from_rgba_unmultiplied(r, g, b, a));
+    let (swell, rows) = match approach.weather {
+        HarborWeather::Fair => FAIR_SWELL,
+        HarborWeather::Blowing | HarborWeather::Squall => ROUGH_SWELL,
+    };
+    let sea_x = parallax_offset(progress, 1.0, pan);
+    let wave_stroke = egui::Stroke::new(WAVE_INK_WIDTH, ink);
+    for row in 0..rows {
+        let y = horizon + FIRST_WAVE_ROW + row as f32 * (sea.height() - FIRST_WAVE_ROW) / rows as f32;
+        let mut x = rect.left() + sea_x.rem_euclid(WAVE_SPACING) - WAVE_SPACING + row as f32 * WAVE_ROW_STAGGER;
+        while x < rect.right() {
+            let crest = egui::pos2(x + WAVE_SPACING * WAVE_CREST, y - swell);
+            painter.line_segment([egui::pos2(x, y), crest], wave_stroke);
+            painter.line_segment([crest, egui::pos2(x + WAVE_SPACING * WAVE_LENGTH, y)], wave_stroke);
+            x += WAVE_SPACING;
+        }
+    }
+    if approach.weather == HarborWeather::Squall {
+        let [r, g, b, a] = RAIN;
+        let rain = egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(r, g, b, a));
+        let mut x = rect.left() + (approach.elapsed * RAIN_SPEED).rem_euclid(RAIN_SPACING);
+        while x < rect.right() {
+            painter.line_segment([egui::pos2(x, rect.top()), egui::pos2(x - RAIN_SLANT, horizon)], rain);
+            x += RAIN_SPACING;
+        }
+    }
+
+    // The player's ship standing in from the left, rolling with the swell
+    let eased = 1.0 - (1.0 - progress).powi(2);
+    let ship_x = rect.left() + rect.width() * (SHIP_START_SHARE + SHIP_RUN_SHARE * eased);
+    let waterline = horizon + rect.height() * WATERLINE_SHARE + (approach.elapsed * ROLL_RATE).sin() * swell * ROLL_SHARE;
+    let length = rect.width() * SHIP_LENGTH_SHARE;
+    let deck = waterline - length * FREEBOARD;
+    painter.add(egui::Shape::convex_polygon(
+        vec![
+            egui::pos2(ship_x, deck),
+            egui::pos2(ship_x + length, deck),
+            egui::pos2(ship_x + length * STERN_FOOT, waterline),
+            egui::pos2(ship_x + length * BOW_FOOT, waterline),
+        ],
+        HULL_FILL,
+        stroke,
+    ));
+    for (mast, height) in MASTS {
+        let foot = egui::pos2(ship_x + length * mast, deck);
+        let head = foot - egui::vec2(0.0, length * height);
+        painter.line_segment([foot, head], stroke);
+        let sail = egui::Rect::from_center_size(
+            egui::pos2(foot.x, (foot.y + head.y) / 2.0),
+            egui::vec2(length * SAIL_WIDTH, length * height * SAIL_DROP),
+        );
+        painter.rect(sail, SAIL_ROUNDING, SAIL_FILL, stroke);
+    }
+}
@@ -0,0 +1,123 @@
+//! Ships heeling over in battle.
+//!
+//! A ship lays over to leeward with the wind on her beam, and outward as she
+//! turns hard at speed, up to her `ShipType::heel_limit`. A top-down sprite
+//! cannot roll, so the heel is drawn by sliding the hull a little toward her
+//! low side and leaning the sails, carried high on the masts, further still.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use crate::components::sails::SailSprite;
+use crate::components::{Heel, Ship, ShipType};
+use crate::plugins::core::GameState;
+use crate::resources::Wind;
+
+/// Speed times rate of turn that lays a ship over as far as she goes
+/// (world units per second times radians per second).
+const FULL_TURN_HEEL: f32 = 175.0;
+/// Share of her heel limit a full gale square on the beam lays her over.
+const BEAM_WIND_HEEL: f32 = 0.6;
+/// Rate at which a ship rolls to her new heel (per second, exponential).
+const HEEL_EASE_RATE: f32 = 2.5;
+/// How far the hull slides toward her low side per radian of heel (world units).
+const HULL_ROLL_SHIFT: f32 = 10.0;
+/// Height of the sails above the deck, for how far they lean (world units).
+const SAIL_HEIGHT: f32 = 30.0;
+
+/// Plugin that heels ships over in battle.
+pub struct HeelPlugin;
+
+impl Plugin for HeelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (roll_ships, draw_heel).chain().run_if(in_state(GameState::Combat)),
+        );
+    }
+}
+
+/// The heel a ship is pressed toward, positive with her starboard side down.
+///
+/// Turning to port (positive angular velocity) throws her out to starboard,
+/// the harder the faster she is going; the wind on her beam presses her down
+/// to leeward.
+pub fn target_heel(ship_type: ShipType, forward: Vec2, velocity: Vec2, angular_velocity: f32, wind: &Wind) -> f32 {
+    let right = -forward.perp();
+    let headway = velocity.dot(forward).max(0.0);
+    let turning = headway * angular_velocity / FULL_TURN_HEEL;
+    let beam_wind = wind.direction_vec().dot(right) * wind.strength.clamp(0.0, 1.0) * BEAM_WIND_HEEL;
+    (turning + beam_wind).clamp(-1.0, 1.0) * ship_type.heel_limit()
+}
+
+/// Eases each ship's `Heel` toward what her way, her helm and the wind press her to.
+fn roll_ships(
+    time: Res<Time>,
+    wind: Res<Wind>,
+    mut ships: Query<(&Transform, &LinearVelocity, &AngularVelocity, Option<&ShipType>, &mut Heel), With<Ship>>,
+) {
+    let ease = 1.0 - (-HEEL_EASE_RATE * time.delta_secs()).exp();
+    for (transform, velocity, angular_velocity, ship_type, mut heel) in &mut ships {
+        // Combat ships without a ShipType are drawn as sloops
+        let ship_type = ship_type.copied().unwrap_or_default();
+        let forward = (transform.rotation * Vec3::Y).truncate();
+        let target = target_heel(ship_type, forward, velocity.0, angular_velocity.0, &wind);
+        heel.0 += (target - heel.0) * ease;
+    }
+}
+
+/// Slides each hull toward her low side and leans her sails further.
+fn draw_heel(
+    mut ships: Query<(&Heel, &mut Sprite, Option<&Children>), With<Ship>>,
+    mut sails: Query<&mut Sprite, (With<SailSprite>, Without<Ship>)>,
+) {
+    for (heel, mut sprite, children) in &mut ships {
+        // Starboard is +X in the ship's local space; the anchor moves the
+        // sprite the opposite way to its own offset
+        let roll = heel.0.sin();
+        sprite.anchor = lean(&sprite, roll * HULL_ROLL_SHIFT);
+        for child in children.into_iter().flatten() {
+            if let Ok(mut sail) = sails.get_mut(*child) {
+                sail.anchor = lean(&sail, roll * SAIL_HEIGHT);
+            }
+        }
+    }
+}
+
+/// Anchor drawing `sprite` `shift` world units to starboard of where it stands.
+fn lean(sprite: &Sprite, shift: f32) -> Anchor {
+    let width = sprite.custom_size.map_or(0.0, |size| size.x);
+    if width <= 0.0 {
+        return Anchor::Center;
+    }
+    Anchor::Custom(Vec2::new(-shift / width, 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heels_out_of_a_turn_and_to_leeward() {
+        let calm = Wind { direction: 0.0, strength: 0.0 };
+        let ahead = Vec2::new(0.0, 200.0);
+
+        // Turning hard to port at speed throws her out to starboard
+        let turning = target_heel(ShipType::Sloop, Vec2::Y, ahead, 0.8, &calm);
+        assert!(turning > 0.0);
+        assert!(target_heel(ShipType::Sloop, Vec2::Y, ahead, -0.8, &calm) < 0.0);
+        // Dead in the water she does not heel however the helm is put
+        assert_eq!(target_heel(ShipType::Sloop, Vec2::Y, Vec2::ZERO, 0.8, &calm), 0.0);
+
+        // A gale blowing toward her starboard side lays her down to starboard
+        let gale = Wind { direction: 0.0, strength: 1.0 };
+        let pressed = target_heel(ShipType::Sloop, Vec2::Y, Vec2::ZERO, 0.0, &gale);
+        assert!(pressed > 0.0 && pressed <= ShipType::Sloop.heel_limit());
+        // A beamy frigate heels less than a schooner in the same wind
+        assert!(
+            target_heel(ShipType::Frigate, Vec2::Y, ahead, 0.0, &gale)
+                < target_heel(ShipType::Schooner, Vec2::Y, ahead, 0.0, &gale)
+        );
+    }
+}
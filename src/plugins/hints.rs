@@ -0,0 +1,189 @@
+//! Contextual hints plugin.
+//!
+//! Watches for first-time situations (storms, contraband, a ship to board)
+//! and shows a one-time tip. Seen hints are recorded in `MetaProfile` and
+//! saved immediately so they never repeat, even across runs.
+
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::cargo::{Cargo, GoodsTrait};
+use crate::components::ship::{Player, Ship};
+use crate::events::HintTriggeredEvent;
+use crate::plugins::boarding::{boarding_target, Boardable};
+use crate::plugins::core::GameState;
+use crate::resources::{HintId, HintQueue, MetaProfile, Wind};
+
+/// Plugin that detects first-time mechanics and displays hints.
+pub struct HintsPlugin;
+
+impl Plugin for HintsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HintQueue>()
+            .add_event::<HintTriggeredEvent>()
+            .add_systems(
+                Update,
+                (
+                    detect_storm_hint
+                        .run_if(in_state(GameState::HighSeas).or(in_state(GameState::Combat))),
+                    detect_contraband_hint,
+                    detect_boarding_hint.run_if(in_state(GameState::Combat)),
+                    queue_hints_system,
+                    hint_display_system.after(EguiSet::InitContexts),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Fires the storm hint when the wind first reaches storm strength.
+fn detect_storm_hint(
+    wind: Res<Wind>,
+    profile: Res<MetaProfile>,
+    mut events: EventWriter<HintTriggeredEvent>,
+) {
+    if wind.is_storm() && profile.should_show_hint(HintId::FirstStorm) {
+        events.send(HintTriggeredEvent { hint: HintId::FirstStorm });
+    }
+}
+
+/// Fires the contraband hint when illegal goods first enter the player's hold.
+fn detect_contraband_hint(
+    cargo_query: Query<&Cargo, (With<Player>, With<Ship>, Changed<Cargo>)>,
+    profile: Res<MetaProfile>,
+    mut events: EventWriter<HintTriggeredEvent>,
+) {
+    if !profile.should_show_hint(HintId::FirstContraband) {
+        return;
+    }
+
+    let carrying_contraband = cargo_query.iter().any(|cargo| {
+        cargo
+            .goods
+            .iter()
+            .any(|(good, &qty)| qty > 0 && good.traits().contains(&GoodsTrait::Illegal))
+    });
+
+    if carrying_contraband {
+        events.send(HintTriggeredEvent { hint: HintId::FirstContraband });
+    }
+}
+
+/// Fires the boarding hint the first time an enemy can be boarded, by the
+/// same test that offers the boarding (`boarding_target`).
+fn detect_boarding_hint(
+    player_query: Query<(Entity, &Transform, &LinearVelocity), (With<Ship>, With<Player>)>,
+    enemy_query: Boardable,
+    profile: Res<MetaProfile>,
+    mut events: EventWriter<HintTriggeredEvent>,
+) {
+    if profile.should_show_hint(HintId::FirstBoarding) && boarding_target(&player_query, &enemy_query).is_some() {
+        events.send(HintTriggeredEvent { hint: HintId::FirstBoarding });
+    }
+}
+
+/// Records triggered hints as seen and queues them for display.
+fn queue_hints_system(
+    mut events: EventReader<HintTriggeredEvent>,
+    mut profile: ResMut<MetaProfile>,
+    mut queue: ResMut<HintQueue>,
+) {
+    let mut newly_seen = false;
+
+    for event in events.read() {
+        if !profile.should_show_hint(event.hint) {
+            continue;
+        }
+        if profile.mark_hint_seen(event.hint) {
+            info!("Showing hint: {:?}", event.hint);
+            queue.push(event.hint);
+            newly_seen = true;
+        }
+    }
+
+    if newly_seen {
+        if let Err(e) = profile.save_to_file() {
+            error!("Failed to save profile after hint: {}", e);
+        }
+    }
+}
+
+/// Renders the current hint in a small window with dismiss and disable controls.
+fn hint_display_system(
+    mut contexts: EguiContexts,
+    mut queue: ResMut<HintQueue>,
+    mut profile: ResMut<MetaProfile>,
+) {
+    let Some(hint) = queue.current() else {
+        return;
+    };
+
+    let mut dismissed = false;
+    let mut disable_hints = false;
+
+    egui::Window::new(format!("Tip: {}", hint.title()))
+        .id(egui::Id::new("contextual_hint"))
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 40.0])
+        .resizable(false)
+        .collapsible(false)
+        .default_width(320.0)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(hint.text());
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button("Got it").clicked() {
+                    dismissed = true;
+                }
+                if ui.button("Don't show tips").clicked() {
+                    disable_hints = true;
+                }
+            });
+        });
+
+    if disable_hints {
+        profile.hints_enabled = false;
+        queue.clear();
+        info!("Contextual hints disabled");
+        if let Err(e) = profile.save_to_file() {
+            error!("Failed to save profile after disabling hints: {}", e);
+        }
+    } else if dismissed {
+        queue.dismiss();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::ship::AI;
+    use crate::components::CombatEntity;
+    use crate::resources::BOARDING_RANGE;
+
+    #[test]
+    fn test_boarding_hint_waits_for_an_enemy_that_can_be_boarded() {
+        let mut app = App::new();
+        app.init_resource::<MetaProfile>()
+            .add_event::<HintTriggeredEvent>()
+            .add_systems(Update, detect_boarding_hint);
+        app.world_mut().spawn((Ship, Player, Transform::default(), LinearVelocity::ZERO));
+        let enemy = app
+            .world_mut()
+            .spawn((Ship, AI, CombatEntity, Transform::from_xyz(BOARDING_RANGE * 2.0, 0.0, 0.0), LinearVelocity::ZERO))
+            .id();
+        app.update();
+        assert!(app.world().resource::<Events<HintTriggeredEvent>>().is_empty());
+
+        // Alongside but under way: still no boarding
+        app.world_mut()
+            .entity_mut(enemy)
+            .insert((Transform::from_xyz(BOARDING_RANGE / 2.0, 0.0, 0.0), LinearVelocity(Vec2::X * 200.0)));
+        app.update();
+        assert!(app.world().resource::<Events<HintTriggeredEvent>>().is_empty());
+
+        app.world_mut().entity_mut(enemy).insert(LinearVelocity::ZERO);
+        app.update();
+        let mut events = app.world_mut().resource_mut::<Events<HintTriggeredEvent>>();
+        assert_eq!(events.drain().map(|event| event.hint).collect::<Vec<_>>(), vec![HintId::FirstBoarding]);
+    }
+}
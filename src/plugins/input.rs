@@ -9,7 +9,21 @@ pub enum PlayerAction {
     TurnRight,
     FirePort,
     FireStarboard,
+    FireChaser,
+    FireMortar,
     Anchor,
+    MoreSail,
+    LessSail,
+    CycleAmmo,
+    Board,
+    SpeedUp,
+    SlowDown,
+    Interact,
+    TacticalPause,
+    TacticalTarget,
+    TacticalAmmo,
+    JuryRig,
+    Journal,
     #[actionlike(DualAxis)]
     CameraMove,
     #[actionlike(Axis)]
@@ -37,13 +51,47 @@ pub fn get_default_input_map() -> InputMap<PlayerAction> {
     // Actions
     input_map.insert(PlayerAction::FirePort, KeyCode::KeyQ);
     input_map.insert(PlayerAction::FireStarboard, KeyCode::KeyE);
+    input_map.insert(PlayerAction::FireChaser, KeyCode::KeyT);
+    input_map.insert(PlayerAction::FireMortar, KeyCode::KeyM);
     input_map.insert(PlayerAction::Anchor, KeyCode::ShiftLeft);
+    input_map.insert(PlayerAction::MoreSail, KeyCode::KeyX);
+    input_map.insert(PlayerAction::LessSail, KeyCode::KeyZ);
+    input_map.insert(PlayerAction::CycleAmmo, KeyCode::KeyV);
+    input_map.insert(PlayerAction::Board, KeyCode::KeyG);
+    input_map.insert(PlayerAction::SpeedUp, KeyCode::Period);
+    input_map.insert(PlayerAction::SlowDown, KeyCode::Comma);
+    input_map.insert(PlayerAction::Interact, KeyCode::KeyI);
+    input_map.insert(PlayerAction::TacticalPause, KeyCode::Space);
+    input_map.insert(PlayerAction::TacticalTarget, KeyCode::Tab);
+    input_map.insert(PlayerAction::TacticalAmmo, KeyCode::KeyR);
+    input_map.insert(PlayerAction::JuryRig, KeyCode::KeyJ);
+    input_map.insert(PlayerAction::Journal, KeyCode::KeyL);
     
     // Camera (arrow keys for pan, scroll for zoom)
     // Note: MouseMove removed - was causing camera to fly away on any mouse movement
     // TODO: Implement proper mouse drag with CameraDrag action + modifier button
     input_map.insert_dual_axis(PlayerAction::CameraMove, VirtualDPad::arrow_keys());
     input_map.insert_axis(PlayerAction::CameraZoom, MouseScrollAxis::Y);
+
+    // Gamepad (Steam Deck and controllers)
+    input_map.insert(PlayerAction::Thrust, GamepadButton::RightTrigger2);
+    input_map.insert(PlayerAction::Reverse, GamepadButton::LeftTrigger2);
+    input_map.insert(PlayerAction::TurnLeft, GamepadControlDirection::LEFT_LEFT);
+    input_map.insert(PlayerAction::TurnRight, GamepadControlDirection::LEFT_RIGHT);
+    input_map.insert(PlayerAction::FirePort, GamepadButton::LeftTrigger);
+    input_map.insert(PlayerAction::FireStarboard, GamepadButton::RightTrigger);
+    input_map.insert(PlayerAction::FireChaser, GamepadButton::RightThumb);
+    input_map.insert(PlayerAction::FireMortar, GamepadButton::LeftThumb);
+    input_map.insert(PlayerAction::Anchor, GamepadButton::South);
+    input_map.insert(PlayerAction::MoreSail, GamepadButton::DPadUp);
+    input_map.insert(PlayerAction::LessSail, GamepadButton::DPadDown);
+    input_map.insert(PlayerAction::CycleAmmo, GamepadButton::West);
+    input_map.insert(PlayerAction::Board, GamepadButton::North);
+    input_map.insert(PlayerAction::SpeedUp, GamepadButton::DPadRight);
+    input_map.insert(PlayerAction::SlowDown, GamepadButton::DPadLeft);
+    input_map.insert(PlayerAction::Interact, GamepadButton::East);
+    input_map.insert(PlayerAction::TacticalPause, GamepadButton::Select);
+    input_map.insert_dual_axis(PlayerAction::CameraMove, GamepadStick::RIGHT);
     
     input_map
 }
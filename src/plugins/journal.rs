@@ -0,0 +1,182 @@
+//! The captain's journal: what goes in it and the window to read it.
+//!
+//! Besides the entries other systems write as things happen, the journal
+//! notes every contract accepted, every piece of intel bought, every port
+//! sighted for the first time and every battle joined. L
+//! (`PlayerAction::Journal`) opens the journal window, where entries can be
+//! browsed newest first by category. The `Journal` is saved with the run and
+//! cleared on returning to the main menu.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use leafwing_input_manager::prelude::*;
+
+use crate::components::intel::IntelData;
+use crate::components::port::PortName;
+use crate::components::{ContractDetails, Faction};
+use crate::events::{ContractAcceptedEvent, IntelAcquiredEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::input::PlayerAction;
+use crate::plugins::worldmap::{EncounteredEnemy, HighSeasPort};
+use crate::resources::{CalendarDate, FogOfWar, Journal, JournalCategory, MapData, SeaMonsterState, WorldClock};
+use crate::utils::pathfinding::world_to_tile;
+
+/// Plugin for the captain's journal.
+pub struct JournalPlugin;
+
+impl Plugin for JournalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Journal>()
+            .init_resource::<JournalUiState>()
+            .add_systems(OnEnter(GameState::MainMenu), clear_journal)
+            .add_systems(OnEnter(GameState::Combat), journal_battle_joined)
+            .add_systems(
+                Update,
+                (
+                    journal_accepted_contracts,
+                    journal_bought_intel,
+                    journal_sighted_ports.run_if(in_state(GameState::HighSeas)),
+                    toggle_journal_window,
+                    journal_window_ui.after(EguiSet::InitContexts),
+                )
+                    .run_if(not(in_state(GameState::MainMenu))),
+            );
+    }
+}
+
+/// UI state for the journal window.
+#[derive(Resource, Debug, Default)]
+pub struct JournalUiState {
+    pub is_open: bool,
+    /// Category shown, or every entry for `None`.
+    pub category: Option<JournalCategory>,
+}
+
+/// A new run starts with a clean page.
+fn clear_journal(mut journal: ResMut<Journal>) {
+    journal.clear();
+}
+
+fn journal_accepted_contracts(
+    mut events: EventReader<ContractAcceptedEvent>,
+    contracts: Query<&ContractDetails>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    for event in events.read() {
+        let Ok(details) = contracts.get(event.contract_entity) else {
+            continue;
+        };
+        let due = details
+            .expiry_tick
+            .map_or_else(String::new, |tick| format!(", due by {}", CalendarDate::at_tick(tick).formatted()));
+        journal.record_as(
+            &world_clock,
+            JournalCategory::Contract,
+            format!("Accepted a contract: {} ({} gold{}).", details.description, details.reward_gold, due),
+        );
+    }
+}
+
+fn journal_bought_intel(
+    mut events: EventReader<IntelAcquiredEvent>,
+    intel: Query<&IntelData>,
+    ports: Query<&PortName>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    for event in events.read() {
+        let Ok(data) = intel.get(event.intel_entity) else {
+            continue;
+        };
+        let text = match event.source_port.and_then(|port| ports.get(port).ok()) {
+            Some(port) => format!("Bought intel in {}: {}", port.0, data.description),
+            None => format!("Came by intel: {}", data.description),
+        };
+        journal.record_as(&world_clock, JournalCategory::Intel, text);
+    }
+}
+
+/// Notes each port the first time its tile is charted.
+fn journal_sighted_ports(
+    ports: Query<(&Transform, &PortName, &Faction), With<HighSeasPort>>,
+    fog_of_war: Res<FogOfWar>,
+    map_data: Res<MapData>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    for (transform, name, faction) in &ports {
+        let tile = world_to_tile(transform.translation.truncate(), map_data.width, map_data.height);
+        if fog_of_war.is_explored(tile) && journal.sight_port(tile) {
+            journal.record_as(
+                &world_clock,
+                JournalCategory::Discovery,
+                format!("Sighted {}, flying the colours of {}.", name.0, faction.0.display_name()),
+            );
+        }
+    }
+}
+
+/// Notes the ships that brought on a battle. A sea monster's rising is
+/// journaled by `plugins::sea_monsters` instead.
+fn journal_battle_joined(
+    encountered: Res<EncounteredEnemy>,
+    monster: Option<Res<SeaMonsterState>>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    let Some(faction) = encountered.faction else {
+        return;
+    };
+    if monster.is_some_and(|monster| monster.engaged.is_some()) {
+        return;
+    }
+    let text = match encountered.squadron.len() {
+        0 | 1 => format!("Brought to battle by a ship of {}.", faction.display_name()),
+        ships => format!("Brought to battle by {} ships of {}.", ships, faction.display_name()),
+    };
+    journal.record_as(&world_clock, JournalCategory::Combat, text);
+}
+
+fn toggle_journal_window(action_query: Query<&ActionState<PlayerAction>>, mut ui_state: ResMut<JournalUiState>) {
+    if action_query.get_single().is_ok_and(|action_state| action_state.just_pressed(&PlayerAction::Journal)) {
+        ui_state.is_open = !ui_state.is_open;
+    }
+}
+
+/// The journal, newest entries first, filtered by category.
+fn journal_window_ui(mut contexts: EguiContexts, mut ui_state: ResMut<JournalUiState>, journal: Res<Journal>) {
+    if !ui_state.is_open {
+        return;
+    }
+
+    let mut is_open = ui_state.is_open;
+    egui::Window::new("📖 Captain's Journal")
+        .open(&mut is_open)
+        .default_width(460.0)
+        .default_height(420.0)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.selectable_value(&mut ui_state.category, None, "All");
+                for category in JournalCategory::ALL {
+                    let label = format!("{} {}", category.icon(), category.name());
+                    ui.selectable_value(&mut ui_state.category, Some(category), label);
+                }
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical().id_salt("journal_entries").show(ui, |ui| {
+                let mut entries = journal.newest_first(ui_state.category).peekable();
+                if entries.peek().is_none() {
+                    ui.weak("Nothing written here yet.");
+                }
+                for entry in entries {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.weak(entry.stamp());
+                        ui.label(format!("{} {}", entry.category.icon(), entry.text));
+                    });
+                }
+            });
+        });
+    ui_state.is_open = is_open;
+}
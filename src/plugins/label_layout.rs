@@ -0,0 +1,253 @@
+//! Collision-free layout and zoom fading for port labels.
+//!
+//! Labels are laid out in screen space whenever the zoom changes: major labels
+//! are placed first, and each following label tries a few positions stepped
+//! inland or seaward from its anchor. A label that still overlaps is hidden.
+//! Independently, each importance fades out over a zoom range so the chart
+//! thins out gradually, as on a printed chart at smaller scale.
+
+use bevy::prelude::*;
+
+use crate::components::location_label::{LabelImportance, LabelPlacement, LocationLabel};
+use crate::plugins::core::{GameState, MainCamera};
+
+/// Minimum screen-space gap between labels, in pixels.
+const LABEL_PADDING: f32 = 6.0;
+/// Label-height steps tried along the label normal, in order of preference.
+const NUDGE_STEPS: [f32; 5] = [0.0, 1.0, -1.0, 2.0, -2.0];
+/// Opacity change per second while fading.
+const FADE_SPEED: f32 = 4.0;
+
+/// Plugin that lays out port labels and fades them with zoom.
+pub struct LabelLayoutPlugin;
+
+impl Plugin for LabelLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (layout_location_labels, fade_location_labels)
+                .chain()
+                .run_if(in_state(GameState::HighSeas)),
+        );
+    }
+}
+
+/// Camera scales over which labels of an importance fade from fully shown to
+/// hidden. `None` means always shown.
+pub fn label_fade_range(importance: LabelImportance) -> Option<(f32, f32)> {
+    match importance {
+        LabelImportance::Major => None,
+        LabelImportance::Standard => Some((2.0, 3.0)),
+        LabelImportance::Minor => Some((1.0, 1.5)),
+    }
+}
+
+/// Opacity of a label of the given importance at a camera scale, before collisions.
+pub fn label_zoom_alpha(importance: LabelImportance, scale: f32) -> f32 {
+    match label_fade_range(importance) {
+        Some((full, hidden)) => ((hidden - scale) / (hidden - full)).clamp(0.0, 1.0),
+        None => 1.0,
+    }
+}
+
+/// A label to be placed, in screen space.
+#[derive(Debug, Clone, Copy)]
+pub struct LabelCandidate {
+    pub center: Vec2,
+    /// Half size of the axis-aligned box around the rotated label.
+    pub half_extents: Vec2,
+    /// Unit direction the label may be nudged along.
+    pub normal: Vec2,
+    /// Distance of one nudge step.
+    pub step: f32,
+    /// Layout priority from `LabelImportance::rank`.
+    pub rank: u8,
+}
+
+/// Places labels greedily in rank order.
+///
+/// Returns, per candidate, the screen-space offset from its centre at which it
+/// fits without overlapping an earlier label, or `None` if no nudge fits.
+pub fn layout_labels(candidates: &[LabelCandidate], padding: f32) -> Vec<Option<Vec2>> {
+    let mut order: Vec<usize> = (0..candidates.len()).collect();
+    order.sort_by_key(|&i| candidates[i].rank);
+
+    let mut occupied: Vec<Rect> = Vec::with_capacity(candidates.len());
+    let mut offsets = vec![None; candidates.len()];
+
+    for i in order {
+        let candidate = &candidates[i];
+        let half_size = candidate.half_extents + Vec2::splat(padding / 2.0);
+        for k in NUDGE_STEPS {
+            let offset = candidate.normal * k * (candidate.step + padding);
+            let rect = Rect::from_center_half_size(candidate.center + offset, half_size);
+            if occupied.iter().all(|other| other.intersect(rect).is_empty()) {
+                occupied.push(rect);
+                offsets[i] = Some(offset);
+                break;
+            }
+        }
+    }
+
+    offsets
+}
+
+/// Half extents of the axis-aligned box around a `size` rectangle rotated by `rotation`.
+fn rotated_half_extents(size: Vec2, rotation: f32) -> Vec2 {
+    let (sin, cos) = rotation.sin_cos();
+    let half = size / 2.0;
+    Vec2::new(
+        cos.abs() * half.x + sin.abs() * half.y,
+        sin.abs() * half.x + cos.abs() * half.y,
+    )
+}
+
+/// Re-runs the label layout when the zoom changes or labels are spawned.
+///
+/// Labels are world-space text, so panning never changes overlaps; only the
+/// scale (which sets the world size of the screen padding) does.
+fn layout_location_labels(
+    camera_query: Query<Ref<OrthographicProjection>, With<MainCamera>>,
+    added_labels: Query<(), Added<LabelPlacement>>,
+    mut label_query: Query<(&LocationLabel, &mut LabelPlacement, &mut Transform)>,
+) {
+    let Ok(projection) = camera_query.get_single() else {
+        return;
+    };
+    if !projection.is_changed() && added_labels.is_empty() {
+        return;
+    }
+    let scale = projection.scale.max(f32::EPSILON);
+
+    let mut candidates = Vec::new();
+    for (label, placement, _) in &label_query {
+        if label_zoom_alpha(label.importance, scale) <= 0.0 {
+            continue;
+        }
+        let size = label.estimated_size() / scale;
+        candidates.push(LabelCandidate {
+            center: placement.anchor / scale,
+            half_extents: rotated_half_extents(size, label.angle - std::f32::consts::FRAC_PI_2),
+            normal: Vec2::from_angle(label.angle),
+            step: size.y,
+            rank: label.importance.rank(),
+        });
+    }
+
+    // Query order is stable between the two passes, so offsets line up with labels
+    let mut offsets = layout_labels(&candidates, LABEL_PADDING).into_iter();
+    let mut hidden = 0;
+    for (label, mut placement, mut transform) in &mut label_query {
+        if label_zoom_alpha(label.importance, scale) <= 0.0 {
+            // Faded out by zoom; keep the last position so it fades in smoothly
+            continue;
+        }
+        let Some(offset) = offsets.next() else { break };
+        placement.placed = offset.is_some();
+        if let Some(offset) = offset {
+            placement.nudge = offset * scale;
+        } else {
+            hidden += 1;
+        }
+        let position = placement.anchor + placement.nudge;
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+
+    trace!("Label layout at scale {:.2}: {} hidden by collisions", scale, hidden);
+}
+
+/// Eases each label's opacity towards its zoom and placement target.
+fn fade_location_labels(
+    time: Res<Time>,
+    camera_query: Query<&OrthographicProjection, With<MainCamera>>,
+    mut label_query: Query<(&LocationLabel, &mut LabelPlacement, &mut TextColor, &mut Visibility)>,
+) {
+    let Ok(projection) = camera_query.get_single() else {
+        return;
+    };
+    let max_step = FADE_SPEED * time.delta_secs();
+
+    for (label, mut placement, mut text_color, mut visibility) in &mut label_query {
+        let target = if placement.placed {
+            label_zoom_alpha(label.importance, projection.scale)
+        } else {
+            0.0
+        };
+        if (placement.alpha - target).abs() < 1e-3 {
+            continue;
+        }
+
+        placement.alpha += (target - placement.alpha).clamp(-max_step, max_step);
+        text_color.0.set_alpha(placement.alpha);
+        *visibility = if placement.alpha > 0.01 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(center: Vec2, rank: u8) -> LabelCandidate {
+        LabelCandidate {
+            center,
+            half_extents: Vec2::new(50.0, 10.0),
+            normal: Vec2::Y,
+            step: 20.0,
+            rank,
+        }
+    }
+
+    #[test]
+    fn test_zoom_alpha_fades_minor_first() {
+        assert_eq!(label_zoom_alpha(LabelImportance::Minor, 0.5), 1.0);
+        assert_eq!(label_zoom_alpha(LabelImportance::Minor, 2.0), 0.0);
+        assert!((label_zoom_alpha(LabelImportance::Minor, 1.25) - 0.5).abs() < 1e-5);
+        assert_eq!(label_zoom_alpha(LabelImportance::Standard, 2.0), 1.0);
+        assert_eq!(label_zoom_alpha(LabelImportance::Major, 5.0), 1.0);
+    }
+
+    #[test]
+    fn test_separate_labels_stay_put() {
+        let offsets = layout_labels(
+            &[candidate(Vec2::ZERO, 1), candidate(Vec2::new(200.0, 0.0), 1)],
+            LABEL_PADDING,
+        );
+        assert_eq!(offsets, vec![Some(Vec2::ZERO), Some(Vec2::ZERO)]);
+    }
+
+    #[test]
+    fn test_overlapping_label_is_nudged() {
+        let offsets = layout_labels(
+            &[candidate(Vec2::ZERO, 1), candidate(Vec2::new(30.0, 5.0), 1)],
+            LABEL_PADDING,
+        );
+        assert_eq!(offsets[0], Some(Vec2::ZERO));
+        let nudge = offsets[1].expect("second label should fit after a nudge");
+        assert!(nudge.y.abs() >= 20.0);
+    }
+
+    #[test]
+    fn test_lower_importance_loses_collision() {
+        // Minor label listed first still yields to the major label
+        let mut crowd = vec![candidate(Vec2::ZERO, 2)];
+        for k in NUDGE_STEPS {
+            crowd.push(candidate(Vec2::new(0.0, k * (20.0 + LABEL_PADDING)), 0));
+        }
+        let offsets = layout_labels(&crowd, LABEL_PADDING);
+        assert_eq!(offsets[0], None);
+        assert_eq!(offsets[1], Some(Vec2::ZERO));
+    }
+
+    #[test]
+    fn test_rotated_half_extents() {
+        let upright = rotated_half_extents(Vec2::new(100.0, 20.0), 0.0);
+        assert!((upright - Vec2::new(50.0, 10.0)).length() < 1e-4);
+        let vertical = rotated_half_extents(Vec2::new(100.0, 20.0), std::f32::consts::FRAC_PI_2);
+        assert!((vertical - Vec2::new(10.0, 50.0)).length() < 1e-4);
+    }
+}
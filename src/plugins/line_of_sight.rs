@@ -0,0 +1,224 @@
+//! Line of sight in battle: gun smoke, the coast, and what AI ships can see.
+//!
+//! Every broadside or chaser shot leaves a cloud of `GunSmoke` at the guns,
+//! which swells as it drifts downwind and clears after `SMOKE_SECONDS`. Smoke
+//! and the rocks of the coast both `BlocksSight`. Before each AI ship steers
+//! or fires, her `Lookout` is taken: the nearest foe she can see (see
+//! `utils::line_of_sight`), or, with none in sight, where the one she was
+//! after was last seen. She steers for that spot and holds her fire until a
+//! foe is in sight again, so a captain can slip away behind an island or the
+//! smoke of their own guns. Once she reaches the spot without finding anyone
+//! she makes for the sound of the guns: the nearest foe, seen or not.
+//! The sightlines and everything blocking them can be drawn from the debug
+//! panel (F4).
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+use crate::components::{BlocksSight, CombatEntity, GunSmoke, Lookout, Projectile, Ship, Surrendered, AI};
+use crate::plugins::core::GameState;
+use crate::plugins::debug_ui::DebugToggles;
+use crate::resources::Wind;
+use crate::systems::{ai_firing_system, battle_sides, combat_ai_system, side_of, Combatants, Side};
+use crate::utils::line_of_sight::{first_obstruction, line_of_sight};
+
+/// Seconds a cloud of gun smoke takes to clear.
+pub const SMOKE_SECONDS: f32 = 5.0;
+/// Radius of a cloud of gun smoke as it leaves the guns.
+const SMOKE_START_RADIUS: f32 = 35.0;
+/// Radius it has swelled to by the time it clears.
+const SMOKE_END_RADIUS: f32 = 80.0;
+/// How fast smoke drifts in a full gale (world units per second).
+const SMOKE_DRIFT_SPEED: f32 = 40.0;
+/// How close an AI ship comes to where her foe was last seen before giving
+/// up the search.
+const SEARCH_RADIUS: f32 = 60.0;
+
+const SMOKE_COLOR: Color = Color::srgba(0.85, 0.85, 0.82, 0.7);
+const CLEAR_SIGHT_COLOR: Color = Color::srgb(0.2, 0.8, 0.3);
+const BLOCKED_SIGHT_COLOR: Color = Color::srgb(0.9, 0.2, 0.15);
+const BLOCK_OUTLINE_COLOR: Color = Color::srgba(0.5, 0.5, 0.5, 0.8);
+
+/// Plugin for gun smoke and AI line of sight in battle.
+pub struct LineOfSightPlugin;
+
+impl Plugin for LineOfSightPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            ai_lookout_system
+                .before(combat_ai_system)
+                .before(ai_firing_system)
+                .run_if(in_state(GameState::Combat)),
+        )
+        .add_systems(
+            Update,
+            (
+                raise_gun_smoke,
+                drift_gun_smoke,
+                draw_sightlines.run_if(|toggles: Res<DebugToggles>| toggles.show_sightlines),
+            )
+                .run_if(in_state(GameState::Combat)),
+        );
+    }
+}
+
+/// What a ship at `position` makes of her foes at `foes`, given what she saw
+/// before (`last`) and what blocks her sight (`blocks`, centre and radius).
+pub fn look_out(position: Vec2, last: Lookout, foes: &[Vec2], blocks: &[(Vec2, f32)]) -> Lookout {
+    let nearest_first = |a: &&Vec2, b: &&Vec2| a.distance_squared(position).total_cmp(&b.distance_squared(position));
+    if let Some(seen) = foes
+        .iter()
+        .filter(|foe| line_of_sight(position, **foe, blocks.iter().copied()))
+        .min_by(nearest_first)
+    {
+        return Lookout { foe: Some(*seen), in_sight: true };
+    }
+    // Search where she was last seen, then make for the sound of the guns
+    let foe = last
+        .foe
+        .filter(|spot| spot.distance(position) > SEARCH_RADIUS)
+        .or_else(|| foes.iter().min_by(nearest_first).copied());
+    Lookout { foe, in_sight: false }
+}
+
+/// Takes every AI ship's lookout before she steers or fires.
+fn ai_lookout_system(
+    mut commands: Commands,
+    combatants: Combatants,
+    mut ai_query: Query<(Entity, &Transform, Option<&mut Lookout>), (With<Ship>, With<AI>, Without<Surrendered>)>,
+    blocks: Query<(&Transform, &BlocksSight)>,
+) {
+    let sides = battle_sides(&combatants);
+    let blocks: Vec<(Vec2, f32)> =
+        blocks.iter().map(|(transform, block)| (transform.translation.truncate(), block.radius)).collect();
+
+    for (entity, transform, lookout) in &mut ai_query {
+        let Some(side) = side_of(entity, &sides) else {
+            continue;
+        };
+        let foes: Vec<Vec2> = sides
+            .iter()
+            .filter(|(_, _, other)| other.is_some_and(|other| (other == Side::Player) != (side == Side::Player)))
+            .map(|(_, position, _)| *position)
+            .collect();
+        let position = transform.translation.truncate();
+        match lookout {
+            Some(mut lookout) => {
+                let seen = look_out(position, *lookout, &foes, &blocks);
+                if *lookout != seen {
+                    *lookout = seen;
+                }
+            }
+            None => {
+                commands.entity(entity).try_insert(look_out(position, Lookout::default(), &foes, &blocks));
+            }
+        }
+    }
+}
+
+/// Each volley leaves a cloud of smoke where its shot left the guns.
+fn raise_gun_smoke(mut commands: Commands, shots: Query<(&Projectile, &Transform), Added<Projectile>>) {
+    let mut broadsides: HashMap<Entity, (Vec2, f32)> = HashMap::new();
+    for (projectile, transform) in &shots {
+        let (sum, count) = broadsides.entry(projectile.source).or_default();
+        *sum += transform.translation.truncate();
+        *count += 1.0;
+    }
+
+    let cloud = shapes::Circle { radius: SMOKE_START_RADIUS, center: Vec2::ZERO };
+    for (sum, count) in broadsides.into_values() {
+        let at = sum / count;
+        commands.spawn((
+            Name::new("Gun Smoke"),
+            GunSmoke { timer: Timer::from_seconds(SMOKE_SECONDS, TimerMode::Once) },
+            BlocksSight { radius: SMOKE_START_RADIUS },
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&cloud),
+                // Over the ships, so it hides them from the player too
+                transform: Transform::from_translation(at.extend(6.0)),
+                ..default()
+            },
+            Fill::color(SMOKE_COLOR),
+            CombatEntity,
+        ));
+    }
+}
+
+/// Smoke drifts downwind, swelling and thinning until it clears.
+fn drift_gun_smoke(
+    mut commands: Commands,
+    time: Res<Time>,
+    wind: Res<Wind>,
+    mut clouds: Query<(Entity, &mut GunSmoke, &mut BlocksSight, &mut Transform, &mut Fill)>,
+) {
+    let drift = wind.velocity() * SMOKE_DRIFT_SPEED * time.delta_secs();
+    for (entity, mut smoke, mut block, mut transform, mut fill) in &mut clouds {
+        if smoke.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let age = smoke.timer.fraction();
+        block.radius = SMOKE_START_RADIUS + (SMOKE_END_RADIUS - SMOKE_START_RADIUS) * age;
+        transform.translation += drift.extend(0.0);
+        transform.scale = Vec3::splat(block.radius / SMOKE_START_RADIUS);
+        fill.color = SMOKE_COLOR.with_alpha(SMOKE_COLOR.alpha() * (1.0 - age));
+    }
+}
+
+/// Debug view: each AI ship's sightline to her foe, green if clear and red
+/// if blocked, and the outline of everything that blocks sight.
+fn draw_sightlines(
+    ai_query: Query<(&Transform, &Lookout), (With<Ship>, With<AI>)>,
+    blocks: Query<(&Transform, &BlocksSight)>,
+    mut gizmos: Gizmos,
+) {
+    let blocks: Vec<(Vec2, f32)> =
+        blocks.iter().map(|(transform, block)| (transform.translation.truncate(), block.radius)).collect();
+    for (centre, radius) in &blocks {
+        gizmos.circle_2d(Isometry2d::from_translation(*centre), *radius, BLOCK_OUTLINE_COLOR);
+    }
+
+    for (transform, lookout) in &ai_query {
+        let Some(foe) = lookout.foe else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+        if lookout.in_sight {
+            gizmos.line_2d(position, foe, CLEAR_SIGHT_COLOR);
+            continue;
+        }
+        // Red as far as what blocks her view, if it is still there
+        let blocked_at = first_obstruction(position, foe, blocks.iter().copied())
+            .map_or(foe, |index| blocks[index].0);
+        gizmos.line_2d(position, blocked_at, BLOCKED_SIGHT_COLOR);
+        gizmos.circle_2d(Isometry2d::from_translation(foe), 10.0, BLOCKED_SIGHT_COLOR);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookout_searches_where_the_foe_was_last_seen() {
+        // Her foe has slipped into a cloud of smoke
+        let hidden = Vec2::new(300.0, 0.0);
+        let smoke = [(hidden, 60.0)];
+        let last = Lookout { foe: Some(Vec2::new(200.0, 50.0)), in_sight: true };
+
+        let lookout = look_out(Vec2::ZERO, last, &[hidden], &smoke);
+        assert_eq!(lookout, Lookout { foe: last.foe, in_sight: false });
+
+        // Reaching the spot, she gives up the search and makes for the guns
+        let lookout = look_out(Vec2::new(190.0, 50.0), last, &[hidden], &smoke);
+        assert_eq!(lookout, Lookout { foe: Some(hidden), in_sight: false });
+
+        // A foe in plain sight is taken over one hidden nearer
+        let open = Vec2::new(0.0, 400.0);
+        let lookout = look_out(Vec2::ZERO, last, &[hidden, open], &smoke);
+        assert_eq!(lookout, Lookout { foe: Some(open), in_sight: true });
+    }
+}
@@ -0,0 +1,208 @@
+//! Loot screen shown after winning a battle.
+//!
+//! Lists the gold and cargo of every sunk or surrendered enemy from
+//! `VictorySpoils` and lets the player take what fits in their hold, and
+//! decide the fate of each surrendered ship. Setting sail hands over to the
+//! battle summary, and each prize is settled by a `PrizeSettledEvent`: taken
+//! ships join the fleet with whatever was left aboard.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::cargo::{Cargo, Gold, GoodType};
+use crate::components::ship::{Player, Ship};
+use crate::components::Crew;
+use crate::events::PrizeSettledEvent;
+use crate::plugins::core::GameState;
+use crate::resources::{BattleStats, FactionRegistry, Journal, JournalCategory, PlayerFleet, PrizeChoice, VictorySpoils, WorldClock};
+
+/// Plugin for the post-battle loot screen.
+pub struct LootScreenPlugin;
+
+impl Plugin for LootScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PrizeSettledEvent>()
+            .add_systems(OnEnter(GameState::Combat), clear_spoils)
+            .add_systems(
+                Update,
+                (
+                    loot_screen_system.after(EguiSet::InitContexts),
+                    settle_prizes.after(loot_screen_system),
+                )
+                    .run_if(in_state(GameState::Combat)),
+            );
+    }
+}
+
+/// A choice made on the loot screen, applied after the UI is drawn.
+enum LootAction {
+    TakeGold(usize),
+    TakeGood(usize, GoodType),
+    TakeAll(usize),
+    ChoosePrize(usize, PrizeChoice),
+    SetSail,
+}
+
+fn clear_spoils(mut spoils: ResMut<VictorySpoils>) {
+    spoils.clear();
+}
+
+fn loot_screen_system(
+    mut contexts: EguiContexts,
+    mut spoils: ResMut<VictorySpoils>,
+    mut player_query: Query<(&mut Cargo, &mut Gold), (With<Player>, With<Ship>)>,
+    mut settled: EventWriter<PrizeSettledEvent>,
+    mut battle_stats: ResMut<BattleStats>,
+) {
+    if !spoils.open {
+        return;
+    }
+    let Ok((mut cargo, mut gold)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let mut action = None;
+    let hold_full = cargo.is_full();
+
+    egui::Window::new("Spoils of Victory")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("📦 Hold: {}/{}", cargo.total_units(), cargo.capacity));
+                ui.separator();
+                ui.label(format!("💰 Purse: {}", gold.0));
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                for (index, source) in spoils.sources.iter().enumerate() {
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.strong(&source.name);
+                            ui.weak(if source.surrendered { "(surrendered)" } else { "(sunk, salvage only)" });
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.add_enabled(!source.is_empty(), egui::Button::new("Take all")).clicked() {
+                                    action = Some(LootAction::TakeAll(index));
+                                }
+                            });
+                        });
+
+                        if let Some(prize) = &source.prize {
+                            ui.horizontal(|ui| {
+                                for choice in PrizeChoice::ALL {
+                                    if ui.selectable_label(prize.choice == choice, choice.name()).clicked() {
+                                        action = Some(LootAction::ChoosePrize(index, choice));
+                                    }
+                                }
+                            });
+                            ui.weak(prize.outcome());
+                        }
+
+                        if source.is_empty() {
+                            ui.weak("Nothing left aboard.");
+                            return;
+                        }
+
+                        egui::Grid::new(("loot_grid", index))
+                            .num_columns(3)
+                            .striped(true)
+                            .min_col_width(80.0)
+                            .show(ui, |ui| {
+                                if source.gold > 0 {
+                                    ui.label("Gold");
+                                    ui.label(format!("💰{}", source.gold));
+                                    if ui.button("Take").clicked() {
+                                        action = Some(LootAction::TakeGold(index));
+                                    }
+                                    ui.end_row();
+                                }
+                                for (good, quantity) in source.goods() {
+                                    ui.label(format!("{:?}", good));
+                                    ui.label(quantity.to_string());
+                                    let take = ui
+                                        .add_enabled(!hold_full, egui::Button::new("Take"))
+                                        .on_disabled_hover_text("Your hold is full");
+                                    if take.clicked() {
+                                        action = Some(LootAction::TakeGood(index, good));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                }
+            });
+
+            ui.separator();
+            if spoils.sources.iter().any(|s| s.prize.as_ref().is_some_and(|p| p.choice == PrizeChoice::TakeShip)) {
+                ui.weak("Cargo left on captured ships sails with them in your fleet.");
+            }
+            if ui.button("⛵ Set Sail").clicked() {
+                action = Some(LootAction::SetSail);
+            }
+        });
+
+    match action {
+        Some(LootAction::TakeGold(index)) => {
+            let taken = spoils.take_gold(index, &mut gold);
+            info!("Looted {} gold", taken);
+        }
+        Some(LootAction::TakeGood(index, good)) => {
+            let taken = spoils.take_good(index, good, &mut cargo);
+            info!("Looted {} {:?}", taken, good);
+        }
+        Some(LootAction::TakeAll(index)) => {
+            spoils.take_all(index, &mut cargo, &mut gold);
+        }
+        Some(LootAction::ChoosePrize(index, choice)) => {
+            spoils.choose(index, choice);
+        }
+        Some(LootAction::SetSail) => {
+            for prize in spoils.finish() {
+                settled.send(PrizeSettledEvent { prize });
+            }
+            info!("Loot screen closed. Opening battle summary.");
+            battle_stats.open = true;
+        }
+        None => {}
+    }
+}
+
+/// Carries out the fate chosen for each prize.
+fn settle_prizes(
+    mut settled: EventReader<PrizeSettledEvent>,
+    mut player_query: Query<(&mut Gold, &mut Crew), (With<Player>, With<Ship>)>,
+    mut player_fleet: ResMut<PlayerFleet>,
+    mut factions: ResMut<FactionRegistry>,
+    mut journal: ResMut<Journal>,
+    world_clock: Res<WorldClock>,
+) {
+    for PrizeSettledEvent { prize } in settled.read() {
+        let name = prize.ship.name.clone();
+        let entry = match prize.choice {
+            PrizeChoice::TakeShip => {
+                info!("Captured ship: {}", name);
+                player_fleet.ships.push(prize.ship.clone());
+                format!("Put a prize crew aboard the {} and took her into the fleet.", name)
+            }
+            PrizeChoice::Release => format!("Let the {} go with her hold emptied.", name),
+            PrizeChoice::PressCrew => {
+                let pressed = player_query
+                    .get_single_mut()
+                    .map_or(0, |(_, mut crew)| crew.hire(prize.pressed_hands()));
+                format!("Pressed {} hands from the {} and scuttled her.", pressed, name)
+            }
+            PrizeChoice::Ransom => {
+                if let Ok((mut gold, _)) = player_query.get_single_mut() {
+                    gold.add(prize.ransom());
+                }
+                format!("Ransomed the officers of the {} for {} gold and let her go.", name, prize.ransom())
+            }
+        };
+        if let Some(faction) = prize.faction {
+            factions.adjust_reputation(faction, prize.choice.reputation());
+        }
+        journal.record_as(&world_clock, JournalCategory::Combat, entry);
+    }
+}
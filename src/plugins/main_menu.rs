@@ -1,13 +1,16 @@
 //! Main Menu UI plugin.
 //!
-//! Displays archetype selection and game start options.
+//! Displays the title page, the New Game setup screen (captain, seed, map size,
+//! difficulty, modifiers) and a profile summary card over the animated chart
+//! rendered by `MenuBackgroundPlugin`.
 
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts, EguiSet};
-use bevy_save::prelude::*;
 
 use crate::plugins::core::GameState;
-use crate::resources::{ArchetypeId, ArchetypeRegistry, MetaProfile, UnlockCondition};
+use crate::plugins::save::request_load;
+use crate::plugins::settings::SettingsWindow;
+use crate::resources::{ArchetypeId, ArchetypeRegistry, Difficulty, MapSizePreset, MetaProfile, PendingWorldGeneration, RunSettings, ShipDefinitions, UnlockCondition};
 use crate::resources::ui_assets::UiAssets;
 
 /// Plugin for the Main Menu UI.
@@ -17,8 +20,11 @@ impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SelectedArchetype>()
             .init_resource::<SaveFileExists>()
+            .init_resource::<MainMenuScreen>()
+            .init_resource::<NewGameUiState>()
             .add_event::<LoadGameEvent>()
             .add_systems(Startup, check_save_file_exists)
+            .add_systems(OnEnter(GameState::MainMenu), reset_main_menu_screen)
             .add_systems(
                 Update,
                 (
@@ -38,7 +44,8 @@ pub struct LoadGameEvent;
 pub struct SaveFileExists(pub bool);
 
 /// Resource storing the player's selected starting archetype.
-#[derive(Resource, Debug)]
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
 pub struct SelectedArchetype(pub ArchetypeId);
 
 impl Default for SelectedArchetype {
@@ -59,7 +66,7 @@ fn check_save_file_exists(mut save_exists: ResMut<SaveFileExists>) {
     }
 }
 
-/// Handles the LoadGameEvent by loading the autosave.
+/// Handles the LoadGameEvent by loading the autosave (see `request_load`).
 fn handle_load_game_event(world: &mut World) {
     // Check if there are any load events
     let has_event = world
@@ -70,29 +77,46 @@ fn handle_load_game_event(world: &mut World) {
 
     if has_event {
         info!("Loading autosave from main menu...");
-
-        match world.load("autosave") {
-            Ok(_) => {
-                info!("Autosave loaded successfully");
-                if let Some(mut next_state) = world.get_resource_mut::<NextState<GameState>>() {
-                    next_state.set(GameState::HighSeas);
-                }
-            }
-            Err(e) => {
-                error!("Failed to load autosave: {:?}", e);
-            }
-        }
+        // Checks the save against this build; mismatches open the compatibility dialog
+        request_load(world, "autosave");
     }
 }
 
-/// Renders the main menu with archetype selection.
+/// Which page of the main menu is shown.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MainMenuScreen {
+    /// Title page with Continue / New Voyage / Codex.
+    #[default]
+    Title,
+    /// Run setup: captain, seed, map size, difficulty and modifiers.
+    NewGame,
+}
+
+/// Editable text buffers for the New Game screen.
+#[derive(Resource, Debug, Default)]
+pub struct NewGameUiState {
+    /// Seed as typed by the player (numeric or free text).
+    pub seed_text: String,
+}
+
+/// Returns to the title page whenever the main menu is entered.
+fn reset_main_menu_screen(mut screen: ResMut<MainMenuScreen>) {
+    *screen = MainMenuScreen::Title;
+}
+
+/// Renders the main menu: the title page or the New Game setup screen.
 fn main_menu_ui_system(
+    mut commands: Commands,
     mut contexts: EguiContexts,
     mut next_state: ResMut<NextState<GameState>>,
     mut selected: ResMut<SelectedArchetype>,
-    mut load_events: EventWriter<LoadGameEvent>,
+    mut screen: ResMut<MainMenuScreen>,
+    mut run_settings: ResMut<RunSettings>,
+    mut new_game_ui: ResMut<NewGameUiState>,
+    mut events: MainMenuEvents,
     registry: Res<ArchetypeRegistry>,
-    profile: Res<MetaProfile>,
+    ships: Res<ShipDefinitions>,
+    mut profile: ResMut<MetaProfile>,
     save_exists: Res<SaveFileExists>,
     ui_assets: Res<UiAssets>,
     time: Res<Time>,
@@ -102,13 +126,26 @@ fn main_menu_ui_system(
     typewriter.tick_all(time.delta_secs());
     
     let texture_id = contexts.add_image(ui_assets.parchment_texture.clone());
+    let ctx = contexts.ctx_mut().clone();
+
+    render_profile_summary(&ctx, texture_id, &profile, &registry);
+
+    egui::CentralPanel::default().show(&ctx, |ui| {
+        // Parchment sheet over the central column; the chart shows around it
+        let full_rect = ui.max_rect();
+        let sheet_rect = egui::Rect::from_center_size(
+            full_rect.center(),
+            egui::vec2(MENU_SHEET_WIDTH.min(full_rect.width()), full_rect.height()),
+        );
+        ui.painter().image(
+            texture_id,
+            sheet_rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::from_white_alpha(235),
+        );
 
-    egui::CentralPanel::default().show(contexts.ctx_mut(), |ui| {
-        // Draw parchment background
-        crate::plugins::ui_theme::draw_parchment_bg(ui, texture_id);
-        
         // Draw decorative corner flourishes
-        let panel_rect = ui.max_rect().shrink(20.0);
+        let panel_rect = sheet_rect.shrink(20.0);
         crate::plugins::ui_theme::draw_corner_flourishes(ui, panel_rect, 50.0);
 
         ui.vertical_centered(|ui| {
@@ -146,148 +183,341 @@ fn main_menu_ui_system(
             crate::plugins::ui_theme::draw_ornamental_divider(ui, ui.available_width().min(400.0));
             ui.add_space(20.0);
 
-            // Archetype Selection Header
-            ui.heading("Choose Your Captain");
-            ui.add_space(10.0);
-            ui.label(format!(
-                "Runs completed: {} | Deaths: {} | Lifetime gold: {}",
-                profile.runs_completed, profile.deaths, profile.lifetime_gold
-            ));
-            ui.add_space(20.0);
-
-            // Archetype Grid
-            egui::Grid::new("archetype_grid")
-                .num_columns(2)
-                .spacing([20.0, 15.0])
-                .show(ui, |ui| {
-                    for &archetype_id in ArchetypeId::all() {
-                        let Some(config) = registry.get(archetype_id) else {
-                            continue;
-                        };
-
-                        let is_unlocked = profile.unlocked_archetypes.contains(&archetype_id);
-                        let is_selected = selected.0 == archetype_id;
-
-                        // Archetype card
-                        let card_response = ui.add_enabled(
-                            is_unlocked,
+            match *screen {
+                MainMenuScreen::Title => {
+                    // Continue Button (only shown if save exists)
+                    if save_exists.0 {
+                        let continue_button = ui.add(
                             egui::Button::new(
-                                egui::RichText::new(config.name)
-                                    .size(18.0)
+                                egui::RichText::new("▶ Continue")
+                                    .size(24.0)
                                     .strong()
-                                    .color(if is_selected {
-                                        egui::Color32::GOLD
-                                    } else if is_unlocked {
-                                        egui::Color32::WHITE
-                                    } else {
-                                        egui::Color32::DARK_GRAY
-                                    }),
+                                    .color(egui::Color32::WHITE),
                             )
-                            .min_size(egui::vec2(180.0, 40.0))
-                            .fill(if is_selected {
-                                egui::Color32::from_rgb(60, 40, 20)
-                            } else {
-                                egui::Color32::from_rgb(30, 30, 40)
-                            }),
+                            .min_size(egui::vec2(200.0, 50.0))
+                            .fill(egui::Color32::from_rgb(60, 100, 60)),
                         );
 
-                        if card_response.clicked() && is_unlocked {
-                            selected.0 = archetype_id;
+                        if continue_button.clicked() {
+                            info!("Loading saved game...");
+                            events.load.send(LoadGameEvent);
                         }
 
-                        // Description column
-                        ui.vertical(|ui| {
-                            if is_unlocked {
-                                ui.label(config.description);
-                                ui.label(format!(
-                                    "Start: {} gold, {}",
-                                    config.starting_gold,
-                                    format_ship_type(config.ship_type)
-                                ));
-                            } else {
-                                ui.label(
-                                    egui::RichText::new("🔒 Locked")
-                                        .color(egui::Color32::DARK_GRAY),
-                                );
-                                ui.label(
-                                    egui::RichText::new(format_unlock_condition(
-                                        &config.unlock_condition,
-                                    ))
-                                    .color(egui::Color32::GRAY)
-                                    .small(),
+                        ui.add_space(10.0);
+                    }
+
+                    // New Voyage opens the run setup screen
+                    let new_game_button = ui.add(
+                        egui::Button::new(
+                            egui::RichText::new("⛵ New Voyage")
+                                .size(24.0)
+                                .strong()
+                                .color(egui::Color32::WHITE),
+                        )
+                        .min_size(egui::vec2(200.0, 50.0))
+                        .fill(egui::Color32::from_rgb(40, 80, 120)),
+                    );
+
+                    if new_game_button.clicked() {
+                        new_game_ui.seed_text = rand::random::<u32>().to_string();
+                        *screen = MainMenuScreen::NewGame;
+                    }
+
+                    ui.add_space(10.0);
+
+                    if ui.button("📖 Codex").clicked() {
+                        events.codex.send(crate::events::CodexOpenEvent { entry_id: None });
+                    }
+                    if ui.button("⚙ Settings").clicked() {
+                        events.settings_window.open = true;
+                    }
+
+                    ui.add_space(20.0);
+                    let mut hints_enabled = profile.hints_enabled;
+                    if ui.checkbox(&mut hints_enabled, "Show tips for new mechanics").changed() {
+                        profile.hints_enabled = hints_enabled;
+                        info!("Contextual hints {}", if hints_enabled { "enabled" } else { "disabled" });
+                        if let Err(e) = profile.save_to_file() {
+                            error!("Failed to save profile: {}", e);
+                        }
+                    }
+                }
+                MainMenuScreen::NewGame => {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        render_archetype_picker(ui, &mut selected, &registry, &ships, &profile);
+                        ui.add_space(20.0);
+                        crate::plugins::ui_theme::draw_rope_divider(ui, ui.available_width().min(400.0));
+                        ui.add_space(10.0);
+                        render_run_options(ui, &mut run_settings, &mut new_game_ui);
+                        ui.add_space(30.0);
+
+                        ui.horizontal(|ui| {
+                            if ui.button("◀ Back").clicked() {
+                                *screen = MainMenuScreen::Title;
+                            }
+
+                            let start_button = ui.add(
+                                egui::Button::new(
+                                    egui::RichText::new("⛵ Set Sail")
+                                        .size(24.0)
+                                        .strong()
+                                        .color(egui::Color32::WHITE),
+                                )
+                                .min_size(egui::vec2(200.0, 50.0))
+                                .fill(egui::Color32::from_rgb(40, 80, 120)),
+                            );
+
+                            if start_button.clicked() {
+                                run_settings.seed = RunSettings::seed_from_text(&new_game_ui.seed_text);
+                                info!(
+                                    "Starting new game with archetype {:?}: seed {}, {:?} map, {:?} difficulty, modifiers {:?}",
+                                    selected.0,
+                                    run_settings.seed,
+                                    run_settings.map_size,
+                                    run_settings.difficulty,
+                                    run_settings.modifiers
                                 );
+                                commands.insert_resource(PendingWorldGeneration);
+                                next_state.set(GameState::HighSeas);
                             }
                         });
+                    });
+                }
+            }
+        });
+    });
+}
 
-                        ui.end_row();
-                    }
-                });
-
-            ui.add_space(40.0);
+/// Event writers used by the main menu, and the settings window it opens.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct MainMenuEvents<'w> {
+    pub load: EventWriter<'w, LoadGameEvent>,
+    pub codex: EventWriter<'w, crate::events::CodexOpenEvent>,
+    pub settings_window: ResMut<'w, SettingsWindow>,
+}
 
-            // Continue Button (only shown if save exists)
-            if save_exists.0 {
-                let continue_button = ui.add(
+/// Renders the archetype selection grid.
+fn render_archetype_picker(
+    ui: &mut egui::Ui,
+    selected: &mut SelectedArchetype,
+    registry: &ArchetypeRegistry,
+    ships: &ShipDefinitions,
+    profile: &MetaProfile,
+) {
+    ui.heading("Choose Your Captain");
+    ui.add_space(20.0);
+
+    egui::Grid::new("archetype_grid")
+        .num_columns(2)
+        .spacing([20.0, 15.0])
+        .show(ui, |ui| {
+            for &archetype_id in ArchetypeId::all() {
+                let Some(config) = registry.get(archetype_id) else {
+                    continue;
+                };
+
+                let is_unlocked = profile.unlocked_archetypes.contains(&archetype_id);
+                let is_selected = selected.0 == archetype_id;
+
+                // Archetype card
+                let card_response = ui.add_enabled(
+                    is_unlocked,
                     egui::Button::new(
-                        egui::RichText::new("▶ Continue")
-                            .size(24.0)
+                        egui::RichText::new(config.name.as_str())
+                            .size(18.0)
                             .strong()
-                            .color(egui::Color32::WHITE),
+                            .color(if is_selected {
+                                egui::Color32::GOLD
+                            } else if is_unlocked {
+                                egui::Color32::WHITE
+                            } else {
+                                egui::Color32::DARK_GRAY
+                            }),
                     )
-                    .min_size(egui::vec2(200.0, 50.0))
-                    .fill(egui::Color32::from_rgb(60, 100, 60)),
+                    .min_size(egui::vec2(180.0, 40.0))
+                    .fill(if is_selected {
+                        egui::Color32::from_rgb(60, 40, 20)
+                    } else {
+                        egui::Color32::from_rgb(30, 30, 40)
+                    }),
                 );
 
-                if continue_button.clicked() {
-                    info!("Loading saved game...");
-                    load_events.send(LoadGameEvent);
+                if card_response.clicked() && is_unlocked {
+                    selected.0 = archetype_id;
                 }
 
-                ui.add_space(10.0);
-            }
-
-            // Start Button (New Game)
-            let start_button = ui.add(
-                egui::Button::new(
-                    egui::RichText::new("⛵ New Voyage")
-                        .size(24.0)
-                        .strong()
-                        .color(egui::Color32::WHITE),
-                )
-                .min_size(egui::vec2(200.0, 50.0))
-                .fill(egui::Color32::from_rgb(40, 80, 120)),
-            );
+                // Description column
+                ui.vertical(|ui| {
+                    if is_unlocked {
+                        ui.label(config.description.as_str());
+                        ui.label(format!(
+                            "Start: {} gold, {}",
+                            config.starting_gold,
+                            ships.get(&config.ship).map_or(config.ship.as_str(), |ship| ship.name.as_str())
+                        ));
+                    } else {
+                        ui.label(
+                            egui::RichText::new("🔒 Locked")
+                                .color(egui::Color32::DARK_GRAY),
+                        );
+                        ui.label(
+                            egui::RichText::new(format_unlock_condition(
+                                &config.unlock_condition,
+                            ))
+                            .color(egui::Color32::GRAY)
+                            .small(),
+                        );
+                    }
+                });
 
-            if start_button.clicked() {
-                info!("Starting new game with archetype: {:?}", selected.0);
-                next_state.set(GameState::HighSeas);
+                ui.end_row();
             }
+        });
+}
 
-            ui.add_space(20.0);
+/// Renders seed, map size, difficulty and modifier controls.
+fn render_run_options(
+    ui: &mut egui::Ui,
+    run_settings: &mut RunSettings,
+    new_game_ui: &mut NewGameUiState,
+) {
+    egui::Grid::new("run_options_grid")
+        .num_columns(2)
+        .spacing([20.0, 10.0])
+        .show(ui, |ui| {
+            ui.strong("Seed");
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut new_game_ui.seed_text).desired_width(140.0));
+                if ui.button("🎲").on_hover_text("Random seed").clicked() {
+                    new_game_ui.seed_text = rand::random::<u32>().to_string();
+                }
+            });
+            ui.end_row();
 
-            // Selected archetype info
-            if let Some(config) = registry.get(selected.0) {
-                crate::plugins::ui_theme::draw_rope_divider(ui, ui.available_width().min(300.0));
-                ui.add_space(10.0);
-                ui.label(
-                    egui::RichText::new(format!("Selected: {}", config.name))
-                        .size(14.0)
-                        .color(egui::Color32::GOLD),
-                );
-            }
+            ui.strong("Map Size");
+            ui.horizontal(|ui| {
+                for &preset in MapSizePreset::all() {
+                    ui.radio_value(&mut run_settings.map_size, preset, preset.name());
+                }
+            });
+            ui.end_row();
+
+            ui.strong("Difficulty");
+            ui.horizontal(|ui| {
+                for &difficulty in Difficulty::all() {
+                    ui.radio_value(&mut run_settings.difficulty, difficulty, difficulty.name());
+                }
+            });
+            ui.end_row();
+
+            ui.strong("Modifiers");
+            ui.vertical(|ui| {
+                ui.checkbox(&mut run_settings.modifiers.no_legacy_wrecks, "No legacy wrecks")
+                    .on_hover_text("Wrecks from previous captains are not placed on the map.");
+                ui.checkbox(&mut run_settings.modifiers.double_pirates, "Double pirates")
+                    .on_hover_text("Twice as many pirate ships roam the seas.");
+                ui.checkbox(&mut run_settings.modifiers.merchant_boom, "Merchant boom")
+                    .on_hover_text("Ports start with far larger stocks of goods.");
+                ui.checkbox(&mut run_settings.modifiers.historical_waters, "Historical waters")
+                    .on_hover_text("No ghost ships or cursed fog; the sea holds nothing stranger than pirates.");
+            });
+            ui.end_row();
         });
-    });
 }
 
-/// Formats a ShipType for display.
-fn format_ship_type(ship_type: crate::components::ship::ShipType) -> &'static str {
-    use crate::components::ship::ShipType;
-    match ship_type {
-        ShipType::Sloop => "Sloop",
-        ShipType::Frigate => "Frigate",
-        ShipType::Schooner => "Schooner",
-        ShipType::Raft => "Raft",
-    }
+/// Width of the parchment sheet behind the central menu column.
+const MENU_SHEET_WIDTH: f32 = 640.0;
+
+/// Renders the profile summary card: lifetime stats, unlocked archetypes and the last run.
+fn render_profile_summary(
+    ctx: &egui::Context,
+    texture_id: egui::TextureId,
+    profile: &MetaProfile,
+    registry: &ArchetypeRegistry,
+) {
+    egui::Window::new("Captain's Log")
+        .id(egui::Id::new("profile_summary"))
+        .anchor(egui::Align2::RIGHT_TOP, [-24.0, 24.0])
+        .resizable(false)
+        .collapsible(false)
+        .default_width(240.0)
+        .show(ctx, |ui| {
+            crate::plugins::ui_theme::draw_parchment_bg(ui, texture_id);
+
+            ui.strong("Lifetime");
+            egui::Grid::new("profile_lifetime_grid")
+                .num_columns(2)
+                .spacing([12.0, 2.0])
+                .show(ui, |ui| {
+                    ui.label("Runs completed");
+                    ui.label(profile.runs_completed.to_string());
+                    ui.end_row();
+                    ui.label("Deaths");
+                    ui.label(profile.deaths.to_string());
+                    ui.end_row();
+                    ui.label("Gold earned");
+                    ui.label(profile.lifetime_gold.to_string());
+                    ui.end_row();
+                    ui.label("Ships captured");
+                    ui.label(profile.lifetime_captures.to_string());
+                    ui.end_row();
+                    if !profile.slain_monsters.is_empty() {
+                        ui.label("Monsters slain");
+                        ui.label(
+                            profile
+                                .slain_monsters
+                                .iter()
+                                .map(|kind| kind.name())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        );
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(6.0);
+            ui.label(format!(
+                "Charisma {} · Navigation {} · Logistics {}",
+                profile.stats.charisma, profile.stats.navigation, profile.stats.logistics
+            ));
+
+            crate::plugins::ui_theme::draw_rope_divider(ui, ui.available_width());
+            ui.strong(format!(
+                "Captains ({}/{})",
+                profile.unlocked_archetypes.len(),
+                ArchetypeId::all().len()
+            ));
+            for &archetype_id in ArchetypeId::all() {
+                let Some(config) = registry.get(archetype_id) else {
+                    continue;
+                };
+                if profile.unlocked_archetypes.contains(&archetype_id) {
+                    ui.label(format!("⚓ {}", config.name));
+                } else {
+                    ui.weak("🔒 ???");
+                }
+            }
+
+            crate::plugins::ui_theme::draw_rope_divider(ui, ui.available_width());
+            ui.strong("Last Voyage");
+            match &profile.last_run {
+                Some(run) => {
+                    let captain = registry
+                        .get(run.archetype)
+                        .map_or("Unknown", |c| c.name.as_str());
+                    let ship = if run.ship_name.is_empty() { "an unnamed ship" } else { run.ship_name.as_str() };
+                    ui.label(format!("{} aboard {}", captain, ship));
+                    ui.label(format!(
+                        "Lost at sea on day {} with {} gold",
+                        run.days_survived,
+                        run.final_gold
+                    ));
+                }
+                None => {
+                    ui.weak("No voyages logged yet.");
+                }
+            }
+        });
 }
 
 /// Formats an UnlockCondition for display.
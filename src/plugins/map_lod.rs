@@ -0,0 +1,267 @@
+//! Zoom-based level of detail for world map decorations.
+//!
+//! Camera zoom selects a `LodLevel`. Decoration entities carry a `MapDetail`
+//! range saying at which levels they are drawn: fine strokes (hachures,
+//! waterlines) drop out when zoomed out, and coarse overview glyphs marking
+//! mountain ranges take their place. Port labels fade separately, see
+//! `label_layout`.
+
+use bevy::prelude::*;
+
+use crate::plugins::core::{GameState, MainCamera};
+use crate::plugins::worldmap::{spawn_coastline_shapes, CoastlineShape};
+use crate::resources::{MapData, TileType};
+use crate::utils::line_mesh::LineMeshBuilder;
+use crate::utils::pathfinding::{map_half_extents, TILE_SIZE};
+
+/// Camera scale below which full detail is drawn.
+const DETAILED_MAX_SCALE: f32 = 1.25;
+/// Camera scale above which only the overview is drawn.
+const OVERVIEW_MIN_SCALE: f32 = 2.5;
+/// Scale margin past a threshold before the level switches, to avoid flicker.
+const LOD_HYSTERESIS: f32 = 0.1;
+
+/// Size in tiles of the blocks summarised by one overview glyph.
+const GLYPH_BLOCK_TILES: u32 = 16;
+/// Elevated tiles a block needs before it gets a glyph.
+const GLYPH_MIN_ELEVATED: usize = 40;
+/// Mountain tiles a block needs for a peak glyph rather than a hill glyph.
+const GLYPH_MIN_MOUNTAINS: usize = 12;
+
+/// Plugin that tracks map zoom level and toggles decorations to match.
+pub struct MapLodPlugin;
+
+impl Plugin for MapLodPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapLod>()
+            .add_systems(OnEnter(GameState::HighSeas), spawn_overview_glyphs.after(spawn_coastline_shapes))
+            .add_systems(
+                Update,
+                update_map_lod.run_if(in_state(GameState::HighSeas)),
+            );
+    }
+}
+
+/// Level of detail for map decorations, from closest to farthest zoom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum LodLevel {
+    #[default]
+    Detailed,
+    Standard,
+    Overview,
+}
+
+impl LodLevel {
+    /// Level for a camera scale, ignoring hysteresis.
+    pub fn from_scale(scale: f32) -> Self {
+        if scale < DETAILED_MAX_SCALE {
+            LodLevel::Detailed
+        } else if scale < OVERVIEW_MIN_SCALE {
+            LodLevel::Standard
+        } else {
+            LodLevel::Overview
+        }
+    }
+
+    /// Level for a camera scale, switching away from `current` only once the
+    /// scale is past a threshold by `LOD_HYSTERESIS`.
+    pub fn for_scale(scale: f32, current: LodLevel) -> Self {
+        let coarser = Self::from_scale(scale - LOD_HYSTERESIS);
+        let finer = Self::from_scale(scale + LOD_HYSTERESIS);
+        if coarser > current {
+            coarser
+        } else if finer < current {
+            finer
+        } else {
+            current
+        }
+    }
+}
+
+/// Current map level of detail, derived from the main camera zoom.
+#[derive(Resource, Debug, Default)]
+pub struct MapLod {
+    pub level: LodLevel,
+}
+
+/// Range of detail levels at which a decoration entity is drawn.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct MapDetail {
+    pub min: LodLevel,
+    pub max: LodLevel,
+}
+
+impl MapDetail {
+    /// Drawn from full detail up to (and including) `max`.
+    pub fn up_to(max: LodLevel) -> Self {
+        Self { min: LodLevel::Detailed, max }
+    }
+
+    /// Drawn only at exactly `level`.
+    pub fn only(level: LodLevel) -> Self {
+        Self { min: level, max: level }
+    }
+
+    pub fn visible_at(&self, level: LodLevel) -> bool {
+        self.min <= level && level <= self.max
+    }
+}
+
+/// Visibility for a decoration given its toggle and optional detail range.
+pub fn decoration_visibility(shown: bool, lod: &MapLod, detail: Option<&MapDetail>) -> Visibility {
+    if shown && detail.is_none_or(|d| d.visible_at(lod.level)) {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// Marker for the overview glyph mesh.
+#[derive(Component)]
+pub struct OverviewGlyphs;
+
+/// Updates the LOD level from the main camera zoom.
+fn update_map_lod(
+    camera_query: Query<&OrthographicProjection, (With<MainCamera>, Changed<OrthographicProjection>)>,
+    mut lod: ResMut<MapLod>,
+) {
+    let Ok(projection) = camera_query.get_single() else {
+        return;
+    };
+    let level = LodLevel::for_scale(projection.scale, lod.level);
+    if level != lod.level {
+        debug!("Map LOD {:?} -> {:?} (scale {:.2})", lod.level, level, projection.scale);
+        lod.level = level;
+    }
+}
+
+/// Builds one coarse glyph per block of elevated terrain, drawn only at overview zoom.
+fn spawn_overview_glyphs(
+    mut commands: Commands,
+    map_data: Res<MapData>,
+    existing: Query<Entity, With<OverviewGlyphs>>,
+    lod: Res<MapLod>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+
+    let ink_color = Color::srgba(0.15, 0.12, 0.08, 0.8);
+    let half_map = map_half_extents(map_data.width, map_data.height);
+    let mut strokes = LineMeshBuilder::new();
+    let mut glyph_count = 0;
+
+    for block_y in (0..map_data.height).step_by(GLYPH_BLOCK_TILES as usize) {
+        for block_x in (0..map_data.width).step_by(GLYPH_BLOCK_TILES as usize) {
+            let mut elevated = 0;
+            let mut mountains = 0;
+            let mut sum = Vec2::ZERO;
+
+            for y in block_y..(block_y + GLYPH_BLOCK_TILES).min(map_data.height) {
+                for x in block_x..(block_x + GLYPH_BLOCK_TILES).min(map_data.width) {
+                    let Some(tile) = map_data.tile(x, y) else { continue };
+                    match tile.tile_type {
+                        TileType::Mountains => mountains += 1,
+                        TileType::Hills => {}
+                        _ => continue,
+                    }
+                    elevated += 1;
+                    sum += Vec2::new(x as f32, y as f32);
+                }
+            }
+
+            if elevated < GLYPH_MIN_ELEVATED {
+                continue;
+            }
+
+            let center = sum / elevated as f32 * TILE_SIZE - half_map;
+            if mountains >= GLYPH_MIN_MOUNTAINS {
+                add_range_glyph(&mut strokes, center, ink_color);
+            } else {
+                add_hill_glyph(&mut strokes, center, ink_color);
+            }
+            glyph_count += 1;
+        }
+    }
+
+    let detail = MapDetail::only(LodLevel::Overview);
+    commands.spawn((
+        Name::new("Overview Glyphs"),
+        CoastlineShape,
+        OverviewGlyphs,
+        detail,
+        Mesh2d(meshes.add(strokes.build())),
+        MeshMaterial2d(materials.add(ColorMaterial::from(Color::WHITE))),
+        Transform::from_xyz(0.0, 0.0, -7.9),
+        decoration_visibility(true, &lod, Some(&detail)),
+    ));
+
+    info!("Built {} overview terrain glyphs", glyph_count);
+}
+
+/// Three overlapping peaks summarising a mountain range.
+fn add_range_glyph(strokes: &mut LineMeshBuilder, center: Vec2, color: Color) {
+    for (dx, height) in [(-90.0, 150.0), (0.0, 220.0), (90.0, 150.0)] {
+        let base = center + Vec2::new(dx, -80.0);
+        strokes.add_polyline(
+            &[
+                base + Vec2::new(-80.0, 0.0),
+                base + Vec2::new(0.0, height),
+                base + Vec2::new(80.0, 0.0),
+            ],
+            false,
+            10.0,
+            color,
+        );
+    }
+}
+
+/// A pair of rounded humps summarising hill country.
+fn add_hill_glyph(strokes: &mut LineMeshBuilder, center: Vec2, color: Color) {
+    for dx in [-70.0, 70.0] {
+        let points: Vec<Vec2> = (0..=8)
+            .map(|i| {
+                let t = i as f32 / 8.0;
+                center + Vec2::new(dx - 80.0 + 160.0 * t, (t * std::f32::consts::PI).sin() * 70.0)
+            })
+            .collect();
+        strokes.add_polyline(&points, false, 8.0, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_from_scale() {
+        assert_eq!(LodLevel::from_scale(1.0), LodLevel::Detailed);
+        assert_eq!(LodLevel::from_scale(2.0), LodLevel::Standard);
+        assert_eq!(LodLevel::from_scale(4.0), LodLevel::Overview);
+    }
+
+    #[test]
+    fn test_hysteresis_holds_level_near_threshold() {
+        // Just past the threshold: stays at the current level
+        assert_eq!(LodLevel::for_scale(1.3, LodLevel::Detailed), LodLevel::Detailed);
+        assert_eq!(LodLevel::for_scale(1.2, LodLevel::Standard), LodLevel::Standard);
+        // Well past the threshold: switches
+        assert_eq!(LodLevel::for_scale(1.4, LodLevel::Detailed), LodLevel::Standard);
+        assert_eq!(LodLevel::for_scale(1.1, LodLevel::Standard), LodLevel::Detailed);
+        // Large jumps skip levels
+        assert_eq!(LodLevel::for_scale(5.0, LodLevel::Detailed), LodLevel::Overview);
+    }
+
+    #[test]
+    fn test_map_detail_ranges() {
+        let fine = MapDetail::up_to(LodLevel::Standard);
+        assert!(fine.visible_at(LodLevel::Detailed));
+        assert!(!fine.visible_at(LodLevel::Overview));
+
+        let overview = MapDetail::only(LodLevel::Overview);
+        assert!(!overview.visible_at(LodLevel::Standard));
+        assert!(overview.visible_at(LodLevel::Overview));
+    }
+}
@@ -0,0 +1,215 @@
+//! Animated main menu background.
+//!
+//! Renders a small procedurally generated chart as a single texture, slowly
+//! pans it behind the menu, and drifts soft ink clouds across it. All entities
+//! are tagged `MainMenuEntity` and despawned when the menu closes.
+
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use rand::Rng;
+
+use crate::components::MainMenuEntity;
+use crate::plugins::core::{GameState, MainCamera};
+use crate::resources::TileType;
+use crate::utils::procgen::{generate_world_map, MapGenConfig};
+
+/// Chart resolution in tiles (one texel per tile).
+const CHART_TILES: u32 = 128;
+/// World units per chart texel when rendered.
+const CHART_TEXEL_SIZE: f32 = 32.0;
+/// Radius of the elliptical pan path in world units.
+const PAN_RADIUS: Vec2 = Vec2::new(600.0, 400.0);
+/// Angular speed of the pan in radians per second (one loop every ~5 minutes).
+const PAN_SPEED: f32 = 0.02;
+/// Number of drifting ink clouds.
+const INK_CLOUD_COUNT: usize = 8;
+/// Half-extent of the area clouds drift within before wrapping.
+const INK_CLOUD_BOUNDS: Vec2 = Vec2::new(1400.0, 900.0);
+/// Resolution of the generated ink cloud texture.
+const INK_CLOUD_TEXTURE_SIZE: u32 = 64;
+
+/// Plugin that spawns and animates the main menu background.
+pub struct MenuBackgroundPlugin;
+
+impl Plugin for MenuBackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::MainMenu), spawn_menu_background)
+            .add_systems(
+                Update,
+                (pan_menu_chart, drift_ink_clouds).run_if(in_state(GameState::MainMenu)),
+            );
+    }
+}
+
+/// The panning chart sprite.
+#[derive(Component)]
+pub struct MenuChart {
+    /// Current angle along the pan path.
+    pub phase: f32,
+}
+
+/// An ink cloud drifting over the chart, positioned relative to the camera.
+#[derive(Component)]
+pub struct InkCloud {
+    pub offset: Vec2,
+    pub velocity: Vec2,
+}
+
+/// Generates the chart and ink cloud textures and spawns the background entities.
+fn spawn_menu_background(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let seed: u32 = rand::thread_rng().gen();
+    let chart = images.add(create_chart_texture(seed));
+    let cloud = images.add(create_ink_cloud_texture());
+
+    let chart_size = Vec2::splat(CHART_TILES as f32 * CHART_TEXEL_SIZE);
+    commands.spawn((
+        Name::new("Menu Chart"),
+        Sprite {
+            image: chart,
+            custom_size: Some(chart_size),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, -50.0),
+        MenuChart { phase: 0.0 },
+        MainMenuEntity,
+    ));
+
+    let mut rng = rand::thread_rng();
+    for i in 0..INK_CLOUD_COUNT {
+        let offset = Vec2::new(
+            rng.gen_range(-INK_CLOUD_BOUNDS.x..INK_CLOUD_BOUNDS.x),
+            rng.gen_range(-INK_CLOUD_BOUNDS.y..INK_CLOUD_BOUNDS.y),
+        );
+        let velocity = Vec2::new(rng.gen_range(8.0..20.0), rng.gen_range(-4.0..4.0));
+        let size = rng.gen_range(300.0..700.0);
+        commands.spawn((
+            Name::new(format!("Ink Cloud {}", i)),
+            Sprite {
+                image: cloud.clone(),
+                custom_size: Some(Vec2::new(size * 1.6, size)),
+                color: Color::srgba(1.0, 1.0, 1.0, rng.gen_range(0.25..0.5)),
+                ..default()
+            },
+            Transform::from_xyz(offset.x, offset.y, -40.0),
+            InkCloud { offset, velocity },
+            MainMenuEntity,
+        ));
+    }
+
+    info!("Spawned main menu background (seed {})", seed);
+}
+
+/// Moves the chart along a slow elliptical path centred on the camera.
+fn pan_menu_chart(
+    time: Res<Time>,
+    camera_query: Query<&Transform, (With<MainCamera>, Without<MenuChart>)>,
+    mut chart_query: Query<(&mut Transform, &mut MenuChart)>,
+) {
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+    for (mut transform, mut chart) in &mut chart_query {
+        chart.phase += PAN_SPEED * time.delta_secs();
+        let offset = Vec2::new(chart.phase.cos(), chart.phase.sin()) * PAN_RADIUS;
+        transform.translation.x = camera.translation.x + offset.x;
+        transform.translation.y = camera.translation.y + offset.y;
+    }
+}
+
+/// Drifts ink clouds across the view, wrapping them at the edges.
+fn drift_ink_clouds(
+    time: Res<Time>,
+    camera_query: Query<&Transform, (With<MainCamera>, Without<InkCloud>)>,
+    mut cloud_query: Query<(&mut Transform, &mut InkCloud)>,
+) {
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+    let dt = time.delta_secs();
+    for (mut transform, mut cloud) in &mut cloud_query {
+        let drift = cloud.velocity * dt;
+        cloud.offset += drift;
+        if cloud.offset.x > INK_CLOUD_BOUNDS.x {
+            cloud.offset.x = -INK_CLOUD_BOUNDS.x;
+        }
+        if cloud.offset.y.abs() > INK_CLOUD_BOUNDS.y {
+            cloud.offset.y = -cloud.offset.y.signum() * INK_CLOUD_BOUNDS.y;
+        }
+        transform.translation.x = camera.translation.x + cloud.offset.x;
+        transform.translation.y = camera.translation.y + cloud.offset.y;
+    }
+}
+
+/// Renders a small generated map into an ink-on-parchment texture.
+fn create_chart_texture(seed: u32) -> Image {
+    let map = generate_world_map(MapGenConfig {
+        seed,
+        width: CHART_TILES,
+        height: CHART_TILES,
+        // Scale frequency with the smaller map so island sizes read similarly
+        frequency: 0.015 * 512.0 / CHART_TILES as f64,
+        ..Default::default()
+    });
+
+    let mut data = Vec::with_capacity((CHART_TILES * CHART_TILES * 4) as usize);
+    // Texture rows run top-down while map rows run bottom-up
+    for y in (0..CHART_TILES).rev() {
+        for x in 0..CHART_TILES {
+            let tile_type = map.tile(x, y).map(|t| t.tile_type).unwrap_or(TileType::DeepWater);
+            let rgba: [u8; 4] = match tile_type {
+                TileType::DeepWater => [214, 200, 166, 255],
+                TileType::ShallowWater => [200, 184, 148, 255],
+                TileType::Sand => [176, 150, 108, 255],
+                TileType::Land | TileType::Port => [150, 122, 84, 255],
+                TileType::Hills => [126, 100, 68, 255],
+                TileType::Mountains => [96, 74, 50, 255],
+            };
+            data.extend_from_slice(&rgba);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: CHART_TILES,
+            height: CHART_TILES,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Creates a soft radial ink blot with alpha falling off towards the edge.
+fn create_ink_cloud_texture() -> Image {
+    let size = INK_CLOUD_TEXTURE_SIZE;
+    let center = (size as f32 - 1.0) / 2.0;
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let dx = (x as f32 - center) / center;
+            let dy = (y as f32 - center) / center;
+            let dist = (dx * dx + dy * dy).sqrt();
+            let falloff = (1.0 - dist).clamp(0.0, 1.0);
+            let alpha = (falloff * falloff * 255.0) as u8;
+            data.extend_from_slice(&[60, 42, 26, alpha]);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    )
+}
@@ -19,4 +19,66 @@ pub mod scale_bar;
 pub mod overlay_ui;
 pub mod cartouche;
 pub mod fade_controller;
+pub mod hints;
+pub mod codex;
+pub mod menu_background;
+pub mod map_lod;
 
+pub mod label_layout;
+pub mod curved_labels;
+pub mod wake;
+pub mod sails;
+pub mod wildlife;
+pub mod news;
+pub mod loot_screen;
+pub mod game_over;
+pub mod refit;
+pub mod cargo_ui;
+pub mod storms;
+pub mod collectibles;
+pub mod music;
+pub mod spatial_audio;
+pub mod tactical;
+pub mod steering;
+pub mod sail_trim;
+pub mod crew_stations;
+pub mod bounties;
+pub mod battle_summary;
+pub mod wreck_sites;
+pub mod ai_activation;
+pub mod picking;
+pub mod save_compat;
+pub mod ui_layout;
+pub mod patrols;
+pub mod supernatural;
+pub mod boarding;
+pub mod rest;
+pub mod wind_chart;
+pub mod mooring;
+pub mod weather;
+pub mod run_state;
+pub mod agent_debug;
+pub mod privateering;
+pub mod crew;
+pub mod ship_market;
+pub mod balance;
+pub mod fleet_combat;
+pub mod port_events;
+pub mod forts;
+pub mod pursuit;
+pub mod ramming;
+pub mod day_night;
+pub mod harbor_approach;
+pub mod port_visuals;
+pub mod battle_replay;
+pub mod sail_ahead;
+pub mod journal;
+pub mod heel;
+pub mod dig_sites;
+pub mod grounding;
+pub mod sea_monsters;
+pub mod sea_events;
+pub mod line_of_sight;
+pub mod content;
+pub mod pacing;
+pub mod settings;
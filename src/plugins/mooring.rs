@@ -0,0 +1,91 @@
+//! Mooring fleet ships in port and taking them back.
+//!
+//! See `resources::mooring`. Ships are moored and retrieved from the port's
+//! Moorings tab; while moored they are out of `PlayerFleet`, so they are not
+//! spawned on the High Seas and take no part in battles.
+
+use bevy::prelude::*;
+
+use crate::components::cargo::Gold;
+use crate::components::port::port_key;
+use crate::components::ship::{Player, Ship};
+use crate::events::{MooringAction, MooringEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::CurrentPort;
+use crate::resources::{MooredShips, PlayerFleet, PortEvents, WorldClock, MOORING_FEE};
+use crate::systems::world_tick_system;
+
+/// Plugin for ship moorings.
+pub struct MooringPlugin;
+
+impl Plugin for MooringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MooredShips>()
+            .add_event::<MooringEvent>()
+            .add_systems(Update, mooring_system.run_if(in_state(GameState::Port)))
+            .add_systems(FixedUpdate, moored_repair_system.after(world_tick_system))
+            .add_systems(OnEnter(GameState::GameOver), clear_moorings);
+    }
+}
+
+/// Moors and retrieves ships at the current port, unless its dockworkers are on strike.
+fn mooring_system(
+    mut events: EventReader<MooringEvent>,
+    mut moored: ResMut<MooredShips>,
+    mut player_fleet: ResMut<PlayerFleet>,
+    current_port: Res<CurrentPort>,
+    world_clock: Res<WorldClock>,
+    port_events: Res<PortEvents>,
+    mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
+) {
+    let on_strike = port_events.docks_closed(current_port.position);
+    for event in events.read() {
+        if on_strike {
+            info!("Mooring refused: the dockworkers are on strike");
+            continue;
+        }
+        let Some(position) = current_port.position else {
+            warn!("Mooring failed: no port");
+            continue;
+        };
+        let key = port_key(position);
+
+        match event.action {
+            MooringAction::Moor(index) => {
+                if index >= player_fleet.ships.len() {
+                    continue;
+                }
+                let Ok(mut gold) = player_query.get_single_mut() else {
+                    warn!("Mooring failed: Player not found");
+                    continue;
+                };
+                if !gold.spend(MOORING_FEE) {
+                    warn!("Mooring failed: Insufficient gold");
+                    continue;
+                }
+                let ship = player_fleet.ships.remove(index);
+                info!("Moored {} for {}g", ship.name, MOORING_FEE);
+                let port_name = current_port.name.as_deref().unwrap_or("Unknown Port");
+                moored.moor(key, ship, port_name, world_clock.day);
+            }
+            MooringAction::Retrieve(index) => {
+                if let Some(ship) = moored.retrieve(key, index) {
+                    info!("Took {} off her mooring", ship.name);
+                    player_fleet.ships.push(ship);
+                }
+            }
+        }
+    }
+}
+
+/// The harbor's hourly work on moored hulls.
+fn moored_repair_system(world_clock: Res<WorldClock>, mut moored: ResMut<MooredShips>) {
+    if world_clock.tick == 0 && !moored.is_empty() {
+        moored.repair_hour();
+    }
+}
+
+/// Moorings are paid for by the run; they go with the flagship.
+fn clear_moorings(mut moored: ResMut<MooredShips>) {
+    moored.clear();
+}
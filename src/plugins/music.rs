@@ -0,0 +1,107 @@
+//! Adaptive music.
+//!
+//! Every `MusicStem` loops from startup, silent until needed. Each frame the
+//! game situation (scene, hostiles, player hull, nearby storms, time of day) is
+//! turned into target stem volumes by `MusicMix::evaluate`, and the playing
+//! stems are faded toward them, so the score shifts instead of cutting.
+//!
+//! The stems are not in the repository, so the plugin is only added with the
+//! `audio` feature.
+
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+use crate::components::{CombatEntity, Health, Player, PlayerOwned, Ship, Surrendered, AI};
+use crate::plugins::core::GameState;
+use crate::resources::{clock_darkness, GameSettings, MusicInputs, MusicMix, MusicScene, MusicStem, StormTracker, WorldClock};
+
+/// Distance beyond a storm's edge at which its music starts to creep in.
+const STORM_HEARING_RANGE: f32 = 800.0;
+/// Overall music volume, before the player's volume settings.
+const MUSIC_VOLUME: f32 = 0.6;
+
+/// A looping audio player for one stem.
+#[derive(Component, Debug)]
+pub struct MusicStemPlayer(pub MusicStem);
+
+/// Plugin for layered, intensity-driven music.
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicMix>()
+            .add_systems(Startup, spawn_music_stems)
+            .add_systems(Update, (
+                evaluate_music_intensity,
+                crossfade_music_stems.after(evaluate_music_intensity),
+            ));
+    }
+}
+
+/// Starts every stem looping at zero volume so they stay in step.
+fn spawn_music_stems(mut commands: Commands, asset_server: Res<AssetServer>) {
+    for stem in MusicStem::ALL {
+        commands.spawn((
+            Name::new(format!("Music Stem {:?}", stem)),
+            AudioPlayer::new(asset_server.load(stem.path())),
+            PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+            MusicStemPlayer(stem),
+        ));
+    }
+}
+
+/// Turns the current game situation into stem targets.
+fn evaluate_music_intensity(
+    state: Res<State<GameState>>,
+    world_clock: Res<WorldClock>,
+    storms: Res<StormTracker>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+    hostile_query: Query<(), (With<Ship>, With<AI>, With<CombatEntity>, Without<PlayerOwned>, Without<Surrendered>)>,
+    mut mix: ResMut<MusicMix>,
+) {
+    let player = player_query.get_single().ok();
+    let player_health = player.map_or(1.0, |(_, health)| health.hull_ratio());
+
+    let scene = match state.get() {
+        GameState::MainMenu | GameState::Port | GameState::HarborApproach => MusicScene::Port,
+        GameState::HighSeas => MusicScene::HighSeas,
+        GameState::Combat => MusicScene::Combat,
+        GameState::GameOver => MusicScene::Silent,
+    };
+
+    let storm = match (scene, player) {
+        (MusicScene::HighSeas, Some((transform, _))) => {
+            let position = transform.translation.truncate();
+            storms
+                .storms
+                .iter()
+                .map(|storm| {
+                    let past_edge = storm.position.distance(position) - storm.radius;
+                    1.0 - (past_edge / STORM_HEARING_RANGE).clamp(0.0, 1.0)
+                })
+                .fold(0.0, f32::max)
+        }
+        _ => 0.0,
+    };
+
+    mix.evaluate(&MusicInputs {
+        scene,
+        hostiles: hostile_query.iter().count(),
+        player_health,
+        storm,
+        night: clock_darkness(&world_clock),
+    });
+}
+
+/// Fades each playing stem toward its target volume.
+fn crossfade_music_stems(
+    time: Res<Time>,
+    mut mix: ResMut<MusicMix>,
+    settings: Res<GameSettings>,
+    sink_query: Query<(&MusicStemPlayer, &AudioSink)>,
+) {
+    mix.crossfade(time.delta_secs());
+    for (player, sink) in &sink_query {
+        sink.set_volume(mix.volume(player.0) * MUSIC_VOLUME * settings.music_gain());
+    }
+}
@@ -0,0 +1,277 @@
+//! Harbor gossip ticker shown along the bottom of the port screen.
+//!
+//! Headlines come from `WorldNewsEvent`s sent by the faction AI, plus two daily
+//! reports gathered here: the dearest and cheapest goods across all markets at
+//! noon, and a pirate captain sighted near a port in the evening.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use rand::seq::IteratorRandom;
+
+use crate::components::cargo::GoodType;
+use crate::components::port::{Inventory, Port, PortName};
+use crate::components::ship::{Faction, FactionId};
+use crate::events::WorldNewsEvent;
+use crate::plugins::core::GameState;
+use crate::plugins::ui_theme::INK_COLOR;
+use crate::plugins::worldmap::HighSeasAI;
+use crate::resources::{GoodsTable, NewsCategory, NewsFeed, WorldClock};
+use crate::systems::price_calculation_system;
+
+/// Hour of the day the market report is written.
+const MARKET_REPORT_HOUR: u32 = 12;
+/// Hour of the day pirate sightings come in.
+const SIGHTING_REPORT_HOUR: u32 = 18;
+/// Price to base price ratio at or above which a good makes the news as dear.
+const DEAR_RATIO: f32 = 1.5;
+/// Price to base price ratio at or below which a good makes the news as a glut.
+const GLUT_RATIO: f32 = 0.6;
+/// Ticker scroll speed in points per second.
+const TICKER_SPEED: f32 = 45.0;
+/// Space between repeats of the ticker text.
+const TICKER_GAP: f32 = 80.0;
+const TICKER_HEIGHT: f32 = 22.0;
+
+/// Plugin that gathers world news and shows it as a ticker in port.
+pub struct NewsPlugin;
+
+impl Plugin for NewsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NewsFeed>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    market_report_system.after(price_calculation_system),
+                    pirate_sighting_system,
+                ),
+            )
+            .add_systems(Update, record_world_news)
+            .add_systems(
+                Update,
+                news_ticker_system
+                    .after(EguiSet::InitContexts)
+                    .run_if(in_state(GameState::Port)),
+            );
+    }
+}
+
+/// One good on sale at one port.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketListing<'a> {
+    pub port: &'a str,
+    pub good: GoodType,
+    pub price: f32,
+    /// The good's base price (see `GoodsTable`).
+    pub base_price: f32,
+}
+
+impl MarketListing<'_> {
+    fn ratio(&self) -> f32 {
+        self.price / self.base_price
+    }
+}
+
+/// Headlines for the dearest and cheapest listings, if either is far enough
+/// from its base price to be worth gossiping about.
+pub fn market_headlines(listings: &[MarketListing]) -> Vec<String> {
+    let by_ratio = |a: &&MarketListing, b: &&MarketListing| {
+        a.ratio()
+            .partial_cmp(&b.ratio())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    };
+    let mut headlines = Vec::new();
+
+    if let Some(dear) = listings.iter().max_by(by_ratio).filter(|l| l.ratio() >= DEAR_RATIO) {
+        headlines.push(format!(
+            "{:?} fetches {:.0} gold in {}",
+            dear.good, dear.price, dear.port
+        ));
+    }
+    if let Some(glut) = listings.iter().min_by(by_ratio).filter(|l| l.ratio() <= GLUT_RATIO) {
+        headlines.push(format!(
+            "A glut of {:?} in {}, going for {:.0} gold",
+            glut.good, glut.port, glut.price
+        ));
+    }
+    headlines
+}
+
+/// A pirate captain's name, stable for a given seed.
+pub fn captain_name(seed: u32) -> String {
+    const FIRST: [&str; 8] = ["Black", "Red", "Mad", "Gentleman", "Calico", "Bloody", "One-Eyed", "Silver"];
+    const LAST: [&str; 8] = ["Morgan", "Vane", "Bonny", "Teague", "Rackham", "Flint", "Kidd", "Tew"];
+    let first = FIRST[seed as usize % FIRST.len()];
+    let last = LAST[(seed as usize / FIRST.len()) % LAST.len()];
+    format!("{} {}", first, last)
+}
+
+/// Files every `WorldNewsEvent` in the feed under the current day.
+fn record_world_news(
+    mut events: EventReader<WorldNewsEvent>,
+    world_clock: Res<WorldClock>,
+    mut feed: ResMut<NewsFeed>,
+) {
+    for event in events.read() {
+        feed.push(world_clock.day, event.category, event.headline.clone());
+    }
+}
+
+/// Reports the most unusual prices across all markets once a day.
+fn market_report_system(
+    world_clock: Res<WorldClock>,
+    port_query: Query<(&PortName, &Inventory), With<Port>>,
+    goods: Res<GoodsTable>,
+    mut news: EventWriter<WorldNewsEvent>,
+) {
+    if world_clock.tick != 0 || world_clock.hour != MARKET_REPORT_HOUR {
+        return;
+    }
+
+    let listings: Vec<MarketListing> = port_query
+        .iter()
+        .flat_map(|(name, inventory)| {
+            let goods = &goods;
+            inventory.goods.iter().map(move |(good, item)| MarketListing {
+                port: name.0.as_str(),
+                good: *good,
+                price: item.price,
+                base_price: goods.base_price(*good),
+            })
+        })
+        .collect();
+
+    for headline in market_headlines(&listings) {
+        news.send(WorldNewsEvent {
+            category: NewsCategory::Market,
+            headline,
+        });
+    }
+}
+
+/// Reports a pirate captain sighted near the closest port once a day.
+fn pirate_sighting_system(
+    world_clock: Res<WorldClock>,
+    pirate_query: Query<(Entity, &Transform, &Faction), With<HighSeasAI>>,
+    port_query: Query<(&Transform, &PortName), With<Port>>,
+    mut news: EventWriter<WorldNewsEvent>,
+) {
+    if world_clock.tick != 0 || world_clock.hour != SIGHTING_REPORT_HOUR {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let Some((pirate, pirate_transform, _)) = pirate_query
+        .iter()
+        .filter(|(_, _, faction)| faction.0 == FactionId::Pirates)
+        .choose(&mut rng)
+    else {
+        return;
+    };
+
+    let pirate_pos = pirate_transform.translation.truncate();
+    let nearest_port = port_query.iter().min_by(|(a, _), (b, _)| {
+        let da = a.translation.truncate().distance_squared(pirate_pos);
+        let db = b.translation.truncate().distance_squared(pirate_pos);
+        da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let Some((_, port_name)) = nearest_port else {
+        return;
+    };
+
+    news.send(WorldNewsEvent {
+        category: NewsCategory::Sighting,
+        headline: format!(
+            "Captain {} sighted off {}",
+            captain_name(pirate.index()),
+            port_name.0
+        ),
+    });
+}
+
+/// Scrolls the news feed along the bottom of the port screen.
+/// Hovering the ticker lists the recent headlines with their dates.
+fn news_ticker_system(mut contexts: EguiContexts, time: Res<Time>, feed: Res<NewsFeed>) {
+    if feed.is_empty() {
+        return;
+    }
+
+    let text = feed.ticker_text();
+    let ctx = contexts.ctx_mut();
+    let width = (ctx.screen_rect().width() * 0.6).max(320.0);
+
+    egui::Area::new(egui::Id::new("news_ticker"))
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -12.0))
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            let (rect, response) =
+                ui.allocate_exact_size(egui::vec2(width, TICKER_HEIGHT), egui::Sense::hover());
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 4.0, egui::Color32::from_rgba_unmultiplied(240, 228, 200, 230));
+            painter.rect_stroke(rect, 4.0, egui::Stroke::new(1.0, INK_COLOR));
+
+            let galley = ui.fonts(|fonts| {
+                fonts.layout_no_wrap(text, egui::FontId::proportional(14.0), INK_COLOR)
+            });
+            let loop_width = galley.size().x + TICKER_GAP;
+            let offset = (time.elapsed_secs() * TICKER_SPEED) % loop_width;
+            let y = rect.center().y - galley.size().y * 0.5;
+
+            // Repeat the text so the ticker never runs dry
+            let mut x = rect.left() - offset;
+            while x < rect.right() {
+                painter.galley(egui::pos2(x, y), galley.clone(), INK_COLOR);
+                x += loop_width;
+            }
+
+            response.on_hover_ui(|ui| {
+                ui.label(egui::RichText::new("Harbor Gossip").strong());
+                for item in feed.newest_first() {
+                    ui.label(format!("Day {}: {} {}", item.day, item.category.icon(), item.headline));
+                }
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(port: &str, good: GoodType, ratio: f32) -> MarketListing<'_> {
+        let base_price = GoodsTable::default().base_price(good);
+        MarketListing {
+            port,
+            good,
+            price: base_price * ratio,
+            base_price,
+        }
+    }
+
+    #[test]
+    fn test_ordinary_prices_are_not_news() {
+        let listings = [
+            listing("Tortuga", GoodType::Rum, 1.1),
+            listing("Nassau", GoodType::Sugar, 0.9),
+        ];
+        assert!(market_headlines(&listings).is_empty());
+    }
+
+    #[test]
+    fn test_extremes_are_reported_by_ratio() {
+        let listings = [
+            listing("Tortuga", GoodType::Rum, 1.6),
+            // Dearer relative to its base price despite a lower absolute price
+            listing("Nassau", GoodType::Timber, 2.5),
+            listing("Havana", GoodType::Spices, 0.55),
+        ];
+        let headlines = market_headlines(&listings);
+        assert_eq!(headlines.len(), 2);
+        assert!(headlines[0].contains("Timber") && headlines[0].contains("Nassau"));
+        assert!(headlines[1].contains("Spices") && headlines[1].contains("Havana"));
+    }
+
+    #[test]
+    fn test_captain_name_is_stable() {
+        assert_eq!(captain_name(42), captain_name(42));
+        assert_ne!(captain_name(0), captain_name(1));
+    }
+}
@@ -1,21 +1,27 @@
 //! Shared utilities for overlay UI elements rendered on RenderLayer 1.
 //!
 //! Provides a single overlay camera and common constants for cartography-style UI
-//! elements like the CompassRose and ScaleBar.
+//! elements like the CompassRose and ScaleBar, and the `ScreenAnchor` layout
+//! that keeps them pinned to the window's edges.
 
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
 use bevy::render::camera::ClearColorConfig;
+use bevy::window::PrimaryWindow;
 
 use crate::plugins::core::GameState;
 use crate::components::HighSeasEntity;
+use crate::resources::UiLayout;
 
 pub struct OverlayUiPlugin;
 
 impl Plugin for OverlayUiPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(OnEnter(GameState::HighSeas), spawn_overlay_camera);
+            .add_systems(OnEnter(GameState::HighSeas), spawn_overlay_camera)
+            .add_systems(PostUpdate, layout_screen_anchors
+                .before(TransformSystem::TransformPropagate)
+                .run_if(in_state(GameState::HighSeas)));
     }
 }
 
@@ -42,12 +48,77 @@ pub const COLOR_GREEN: Color = Color::srgba(0.18, 0.35, 0.24, 1.0);
 /// Red for quarter-wind markers.
 pub const COLOR_RED: Color = Color::srgba(0.69, 0.19, 0.19, 1.0);
 
+/// Widest aspect ratio map furniture spreads across. On ultrawide windows the
+/// corners are pulled in to a centred 16:9 area so they stay in view.
+pub const MAX_SAFE_ASPECT: f32 = 16.0 / 9.0;
+
 // --- Components ---
 
 /// Marker for the shared overlay camera.
 #[derive(Component)]
 pub struct OverlayCamera;
 
+/// Edge or corner of the screen an overlay element is pinned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenEdge {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl ScreenEdge {
+    /// Direction from the screen centre: x is -1 left, 0 centre, 1 right; y is 1 top, -1 bottom.
+    fn direction(self) -> Vec2 {
+        match self {
+            ScreenEdge::TopLeft => Vec2::new(-1.0, 1.0),
+            ScreenEdge::TopCenter => Vec2::new(0.0, 1.0),
+            ScreenEdge::TopRight => Vec2::new(1.0, 1.0),
+            ScreenEdge::BottomLeft => Vec2::new(-1.0, -1.0),
+            ScreenEdge::BottomCenter => Vec2::new(0.0, -1.0),
+            ScreenEdge::BottomRight => Vec2::new(1.0, -1.0),
+        }
+    }
+}
+
+/// Pins an overlay root to a screen edge, re-placed whenever the window or
+/// UI scale changes. Margins and scale are in UI units and grow with the
+/// UI scale, so furniture keeps its proportions in the compact layout.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ScreenAnchor {
+    pub edge: ScreenEdge,
+    /// Distance of the element's origin in from the edge(s).
+    pub margin: Vec2,
+    /// Scale at UI scale 1.0 (x and y separately, for elements that stretch).
+    pub base_scale: Vec2,
+}
+
+impl ScreenAnchor {
+    pub fn new(edge: ScreenEdge, margin: Vec2) -> Self {
+        Self { edge, margin, base_scale: Vec2::ONE }
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.base_scale = Vec2::splat(scale);
+        self
+    }
+
+    /// Position in overlay camera space (origin at the window centre, one
+    /// unit per logical pixel) for a window of `size` at `ui_scale`.
+    pub fn position(&self, size: Vec2, ui_scale: f32) -> Vec2 {
+        let half = safe_area(size) / 2.0;
+        let direction = self.edge.direction();
+        direction * (half - self.margin * ui_scale)
+    }
+}
+
+/// The part of a window of `size` that map furniture is laid out in.
+pub fn safe_area(size: Vec2) -> Vec2 {
+    Vec2::new(size.x.min(size.y * MAX_SAFE_ASPECT), size.y)
+}
+
 // --- Systems ---
 
 fn spawn_overlay_camera(mut commands: Commands) {
@@ -64,3 +135,49 @@ fn spawn_overlay_camera(mut commands: Commands) {
     ));
     info!("Spawned Overlay UI Camera");
 }
+
+/// Places every anchored overlay root for the current window and UI scale.
+fn layout_screen_anchors(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    layout: Res<UiLayout>,
+    mut anchors: Query<(&ScreenAnchor, &mut Transform)>,
+) {
+    let Ok(window) = window_query.get_single() else { return; };
+    let ui_scale = layout.zoom();
+
+    for (anchor, mut transform) in &mut anchors {
+        let position = anchor.position(window.size(), ui_scale);
+        let scale = (anchor.base_scale * ui_scale).extend(1.0);
+        // Only write on change, so transforms are not re-propagated every frame
+        if transform.translation.truncate() != position || transform.scale != scale {
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+            transform.scale = scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_follows_window_size() {
+        let anchor = ScreenAnchor::new(ScreenEdge::BottomRight, Vec2::new(90.0, 90.0));
+        assert_eq!(anchor.position(Vec2::new(1280.0, 720.0), 1.0), Vec2::new(550.0, -270.0));
+        assert_eq!(anchor.position(Vec2::new(1920.0, 1080.0), 1.0), Vec2::new(870.0, -450.0));
+        // Margins grow with the UI scale
+        assert_eq!(anchor.position(Vec2::new(1920.0, 1080.0), 2.0), Vec2::new(780.0, -360.0));
+    }
+
+    #[test]
+    fn test_ultrawide_keeps_furniture_in_safe_area() {
+        let anchor = ScreenAnchor::new(ScreenEdge::TopLeft, Vec2::new(100.0, 60.0));
+        let ultrawide = Vec2::new(3440.0, 1440.0);
+        assert!(safe_area(ultrawide).abs_diff_eq(Vec2::new(2560.0, 1440.0), 1e-2));
+        assert!(anchor.position(ultrawide, 1.0).abs_diff_eq(Vec2::new(-1180.0, 660.0), 1e-2));
+
+        let centred = ScreenAnchor::new(ScreenEdge::TopCenter, Vec2::new(0.0, 60.0));
+        assert_eq!(centred.position(ultrawide, 1.0).x, 0.0);
+    }
+}
@@ -0,0 +1,162 @@
+//! Encounter pacing: keeps heavy ships off a green captain.
+//!
+//! A layer over the encounter and population systems, steered by the
+//! `PacingDirector` (see `resources::pacing`). While the player sails the
+//! director is reassessed from the days at sea, the difficulty's
+//! `PacingCurve` and the player's strength. Any AI ship heavier than it allows
+//! `StandsOff`: she sails her course as ever, but forces no battle, joins no
+//! squadron the player runs into and is not chased down. Bounty hunters come
+//! regardless. In battle the enemy draws a template within the allowance and,
+//! early on, may turn tail at once (`TurnedTail`), coming about to fight if
+//! the threat rises while she runs. An enemy that runs far enough from every
+//! ship on the player's side gets clean away; if they all do, the battle ends
+//! without a victory.
+
+use bevy::prelude::*;
+
+use crate::components::{BountyHunter, Faction, Player, PlayerOwned, Ship, ShipType, Surrendered, AI};
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::{encounter_detection_system, HighSeasAI, HighSeasPlayer};
+use crate::resources::{
+    BalanceConfig, BattleStats, Journal, PacingDirector, PlayerFleet, PlayerStrength, RunSettings, UpgradeInventory,
+    WorldClock,
+};
+use crate::systems::AIState;
+
+/// How far a fleeing enemy must get from every ship on the player's side to escape (world units).
+const ESCAPE_DISTANCE: f32 = 1400.0;
+
+/// An AI ship too heavy for the player yet, by the `PacingDirector`.
+#[derive(Component, Debug)]
+pub struct StandsOff;
+
+/// An enemy that ran from the start of a battle on the director's
+/// `flee_chance`. She runs while her `roll` is still under it.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TurnedTail {
+    pub roll: f32,
+}
+
+/// Plugin for encounter pacing.
+pub struct PacingPlugin;
+
+impl Plugin for PacingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PacingDirector>()
+            // Days go by in battle too
+            .add_systems(
+                Update,
+                assess_pacing
+                    .before(encounter_detection_system)
+                    .run_if(in_state(GameState::HighSeas).or(in_state(GameState::Combat))),
+            )
+            .add_systems(
+                Update,
+                hold_off_outclassing_ships
+                    .after(assess_pacing)
+                    .before(encounter_detection_system)
+                    .run_if(in_state(GameState::HighSeas)),
+            )
+            .add_systems(
+                Update,
+                (rally_turned_tail.after(assess_pacing), fleeing_enemies_escape).run_if(in_state(GameState::Combat)),
+            );
+    }
+}
+
+/// Reassesses the director from the days at sea and the player's strength.
+fn assess_pacing(
+    world_clock: Res<WorldClock>,
+    balance: Res<BalanceConfig>,
+    run_settings: Res<RunSettings>,
+    fleet: Res<PlayerFleet>,
+    upgrades: Res<UpgradeInventory>,
+    player_query: Query<&ShipType, (With<Player>, Or<(With<HighSeasPlayer>, With<Ship>)>)>,
+    mut director: ResMut<PacingDirector>,
+) {
+    let strength = PlayerStrength {
+        fleet_ships: fleet.ships.len(),
+        flagship: player_query.get_single().copied().unwrap_or_default(),
+        upgrades: upgrades.flagship.len() + fleet.ships.iter().map(|ship| ship.upgrades.len()).sum::<usize>(),
+    };
+    let curve = balance.pacing.curve(run_settings.difficulty);
+    let days_at_sea = world_clock.day.saturating_sub(1) as f32;
+    let assessed = PacingDirector::assess(curve, days_at_sea, strength);
+    if *director != assessed {
+        if director.heaviest_rank() != assessed.heaviest_rank() {
+            info!("Pacing: threat {:.2}, ships up to class rank {} may engage", assessed.threat, assessed.heaviest_rank());
+        }
+        *director = assessed;
+    }
+}
+
+/// Marks the AI ships the director keeps away from the player, and clears
+/// the mark once they are allowed.
+fn hold_off_outclassing_ships(
+    mut commands: Commands,
+    director: Res<PacingDirector>,
+    ships: Query<(Entity, &ShipType, Has<StandsOff>), (With<HighSeasAI>, Without<BountyHunter>)>,
+) {
+    for (entity, ship_type, standing_off) in &ships {
+        let allowed = director.allows(*ship_type);
+        if standing_off && allowed {
+            commands.entity(entity).remove::<StandsOff>();
+        } else if !standing_off && !allowed {
+            commands.entity(entity).try_insert(StandsOff);
+        }
+    }
+}
+
+/// Brings about the enemies that turned tail once the director would no longer
+/// have them run.
+fn rally_turned_tail(
+    mut commands: Commands,
+    director: Res<PacingDirector>,
+    mut enemies: Query<(Entity, &TurnedTail, &mut AIState), Without<Surrendered>>,
+) {
+    for (entity, turned_tail, mut ai_state) in &mut enemies {
+        if turned_tail.roll < director.flee_chance {
+            continue;
+        }
+        commands.entity(entity).remove::<TurnedTail>();
+        if *ai_state == AIState::Fleeing {
+            *ai_state = AIState::default();
+            info!("A running enemy comes about to fight (threat {:.2})", director.threat);
+        }
+    }
+}
+
+/// A fleeing enemy that outruns every ship on the player's side escapes.
+fn fleeing_enemies_escape(
+    mut commands: Commands,
+    enemies: Query<
+        (Entity, &Transform, &AIState, &Name, Option<&ShipType>, Option<&Faction>),
+        (With<Ship>, With<AI>, Without<PlayerOwned>, Without<Surrendered>),
+    >,
+    player_side: Query<&Transform, (With<Ship>, Or<(With<Player>, With<PlayerOwned>)>)>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+    mut battle_stats: ResMut<BattleStats>,
+) {
+    for (entity, transform, ai_state, name, ship_type, faction) in &enemies {
+        if *ai_state != AIState::Fleeing {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let pursued = player_side
+            .iter()
+            .any(|pursuer| pursuer.translation.truncate().distance(position) <= ESCAPE_DISTANCE);
+        if pursued {
+            continue;
+        }
+        let ship_type = ship_type.copied().unwrap_or_default();
+        let flag = faction.map_or("no", |faction| faction.0.display_name());
+        info!("Fleeing {:?} under {} colours escaped", ship_type, flag);
+        journal.record(
+            &world_clock,
+            format!("The {:?} under {} colours showed us her heels and got clean away.", ship_type, flag),
+        );
+        battle_stats.record_escaped(name.as_str());
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,391 @@
+//! Border patrols: intercepting smugglers for a nation that trusts the player.
+//!
+//! Nations the player stands well with post a patrol contract at each of their
+//! ports. While one is accepted, merchant traffic (some of it smuggling) works
+//! the patrol waters whenever the player puts to sea. Right-clicking a ship in
+//! spyglass range hails and inspects it; right-clicking a hailed ship engages
+//! it. Beating a smuggler counts toward the contract, while attacking an
+//! honest merchant costs reputation and a fine, whatever the inspection said.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::contract::{AcceptedContract, Contract, ContractDetails, ContractProgress, ContractType, PatrolArea};
+use crate::components::patrol::{Hailed, PatrolTraffic, Smuggler};
+use crate::components::{
+    port_key, Faction, FactionId, Gold, HighSeasEntity, Health, NavigationPath, OfferedAt, Order, OrderQueue, Player, Port,
+    PortName, Ship, ShipType, AI,
+};
+use crate::events::{CombatEndedEvent, CombatTriggeredEvent, ContractCompletedEvent, PickingResult};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::PlayerContracts;
+use crate::plugins::worldmap::{HighSeasAI, HighSeasPlayer};
+use crate::resources::{
+    inspection_suspicious, offers_patrols, ContractRewards, FactionRegistry, Interception, Journal, MapData,
+    PatrolEngagement, PickTarget, ShipDefinitions, WorldClock, FALSE_POSITIVE_FINE, FALSE_POSITIVE_REPUTATION_PENALTY, HAIL_RANGE,
+    PATROL_RADIUS, PATROL_REPUTATION_REWARD, PATROL_SMUGGLERS, PATROL_TRAFFIC,
+};
+use crate::systems::world_tick_system;
+use crate::utils::pathfinding::{tile_to_world, world_to_tile};
+
+/// Plugin for patrol contracts, hailing and smuggler interception.
+pub struct PatrolsPlugin;
+
+impl Plugin for PatrolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PatrolEngagement>()
+            .add_systems(OnEnter(GameState::HighSeas), spawn_patrol_traffic)
+            .add_systems(
+                FixedUpdate,
+                post_patrol_contracts.after(world_tick_system).run_if(in_state(GameState::HighSeas)),
+            )
+            .add_systems(Update, (
+                hail_ships.after(crate::plugins::picking::emit_picking_results),
+                // Before hailing, so one click cannot both hail and engage
+                engage_hailed_ships.after(crate::plugins::picking::emit_picking_results).before(hail_ships),
+                smugglers_run.after(hail_ships),
+            ).run_if(in_state(GameState::HighSeas)))
+            .add_systems(Update, (resolve_interceptions, complete_patrols.after(resolve_interceptions)))
+            .add_systems(OnEnter(GameState::MainMenu), reset_patrol_engagement);
+    }
+}
+
+/// Names painted on the sterns of patrol-area merchantmen.
+const MERCHANT_NAMES: [&str; 8] = [
+    "Good Hope", "Providence", "Swallow", "Charity", "Dolphin", "Fortune", "Endeavour", "Mary Ann",
+];
+
+/// Tint of a ship whose inspection turned up contraband.
+const SUSPICIOUS_TINT: Color = Color::srgb(1.0, 0.55, 0.45);
+
+/// How far a smuggler runs once its cargo is found (world units).
+const SMUGGLER_RUN_DISTANCE: f32 = 1500.0;
+
+/// Journal entries stamped with the world clock.
+#[derive(SystemParam)]
+struct PatrolJournal<'w> {
+    clock: Res<'w, WorldClock>,
+    journal: ResMut<'w, Journal>,
+}
+
+impl PatrolJournal<'_> {
+    fn record(&mut self, text: impl Into<String>) {
+        self.journal.record(&self.clock, text);
+    }
+}
+
+/// Keeps a patrol contract on the board of every port whose nation trusts the player.
+/// Checked on the hour, so a withdrawn offer is replaced at the next one.
+fn post_patrol_contracts(
+    mut commands: Commands,
+    world_clock: Res<WorldClock>,
+    registry: Res<FactionRegistry>,
+    rewards: Res<ContractRewards>,
+    port_query: Query<(Entity, &Transform, &Faction, &PortName), With<Port>>,
+    contract_query: Query<(&ContractDetails, &OfferedAt), With<Contract>>,
+) {
+    if world_clock.tick != 0 {
+        return;
+    }
+    let mut rng = rand::thread_rng();
+    let current_tick = world_clock.total_ticks();
+
+    for (port_entity, transform, faction, name) in &port_query {
+        let reputation = registry.get(faction.0).map_or(0, |state| state.player_reputation);
+        if !offers_patrols(faction.0, reputation) {
+            continue;
+        }
+        let key = port_key(transform.translation.truncate());
+        let posted = contract_query
+            .iter()
+            .any(|(details, offered_at)| details.contract_type == ContractType::Patrol && offered_at.0 == key);
+        if posted {
+            continue;
+        }
+
+        let required = rng.gen_range(2..=3);
+        commands.spawn((
+            Contract,
+            ContractDetails::patrol(port_entity, &name.0, required, rewards.patrol_reward(required), current_tick)
+                .with_issuer(faction.0),
+            OfferedAt(key),
+            PatrolArea {
+                center: transform.translation.truncate(),
+                radius: PATROL_RADIUS,
+                required,
+            },
+        ));
+        debug!("Port {} posted a patrol for {} smugglers", name.0, required);
+    }
+}
+
+/// Puts merchant traffic, some of it smuggling, into each accepted patrol's waters.
+fn spawn_patrol_traffic(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ships: Res<ShipDefinitions>,
+    map_data: Res<MapData>,
+    contract_query: Query<(&ContractDetails, &PatrolArea), (With<Contract>, With<AcceptedContract>)>,
+) {
+    let mut rng = rand::thread_rng();
+    let texture_handle: Handle<Image> = asset_server.load(ships.enemy_sprite(ShipType::Schooner));
+
+    for (details, area) in &contract_query {
+        let flag = details.issuer.unwrap_or(FactionId::NationA);
+        for i in 0..PATROL_TRAFFIC {
+            let offset = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU)) * rng.gen_range(0.0..area.radius);
+            let tile = world_to_tile(area.center + offset, map_data.width, map_data.height);
+            let Some(water) = map_data.nearest_navigable(tile, 4) else {
+                continue;
+            };
+            let position = tile_to_world(water, map_data.width, map_data.height);
+            let name = MERCHANT_NAMES[rng.gen_range(0..MERCHANT_NAMES.len())];
+
+            let mut merchant = commands.spawn((
+                Name::new(name),
+                Ship,
+                ShipType::Schooner,
+                AI,
+                Faction(flag),
+                HighSeasAI,
+                PatrolTraffic,
+                Health::default(),
+                OrderQueue::with_order(Order::Patrol {
+                    center: area.center,
+                    radius: area.radius,
+                    waypoint_index: 0,
+                }),
+                NavigationPath::default(),
+                Sprite {
+                    image: texture_handle.clone(),
+                    custom_size: Some(Vec2::splat(48.0)),
+                    flip_y: true,
+                    ..default()
+                },
+                Transform::from_xyz(position.x, position.y, 1.0),
+                HighSeasEntity,
+            ));
+            if i < PATROL_SMUGGLERS {
+                merchant.insert(Smuggler);
+            }
+        }
+    }
+}
+
+type HailQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static Faction, &'static Name, Has<Smuggler>, &'static mut Sprite),
+    (With<HighSeasAI>, Without<Hailed>),
+>;
+
+/// Hails and inspects an unhailed ship the player right-clicks within spyglass range.
+fn hail_ships(
+    mut commands: Commands,
+    mut picks: EventReader<PickingResult>,
+    player_query: Query<&Transform, (With<Player>, With<HighSeasPlayer>)>,
+    mut ship_query: HailQuery,
+    mut journal: PatrolJournal,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+    let mut rng = rand::thread_rng();
+
+    for pick in picks.read() {
+        let (MouseButton::Right, PickTarget::Ship(entity)) = (pick.button, pick.target) else {
+            continue;
+        };
+        let Ok((transform, faction, name, smuggler, mut sprite)) = ship_query.get_mut(entity) else {
+            continue;
+        };
+        // Pirates fly their colours openly; there is nothing to inspect
+        if faction.0 == FactionId::Pirates {
+            continue;
+        }
+        if transform.translation.truncate().distance(player_pos) > HAIL_RANGE {
+            info!("{} is out of spyglass range", name);
+            continue;
+        }
+
+        let suspicious = inspection_suspicious(smuggler, rng.gen());
+        commands.entity(entity).insert(Hailed { suspicious });
+        if suspicious {
+            sprite.color = SUSPICIOUS_TINT;
+            journal.record(format!(
+                "Hailed the {} under {} colours: her papers do not match her hold. Smuggling, by the look of it.",
+                name,
+                faction.0.display_name()
+            ));
+        } else {
+            journal.record(format!(
+                "Hailed the {} under {} colours: papers in order, nothing amiss.",
+                name,
+                faction.0.display_name()
+            ));
+        }
+    }
+}
+
+/// Smugglers caught out make a run for it, away from the player.
+fn smugglers_run(
+    player_query: Query<&Transform, (With<Player>, With<HighSeasPlayer>)>,
+    mut query: Query<(&Transform, &Hailed, &mut OrderQueue), (With<Smuggler>, Added<Hailed>)>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    for (transform, hailed, mut orders) in &mut query {
+        if !hailed.suspicious {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let away = (position - player_pos).normalize_or(Vec2::Y);
+        *orders = OrderQueue::with_order(Order::Patrol {
+            center: position + away * SMUGGLER_RUN_DISTANCE,
+            radius: 200.0,
+            waypoint_index: 0,
+        });
+    }
+}
+
+/// Engages a hailed ship the player right-clicks within spyglass range.
+fn engage_hailed_ships(
+    mut picks: EventReader<PickingResult>,
+    player_query: Query<&Transform, (With<Player>, With<HighSeasPlayer>)>,
+    ship_query: Query<(&Transform, &Faction, Has<Smuggler>), (With<HighSeasAI>, With<Hailed>)>,
+    mut engagement: ResMut<PatrolEngagement>,
+    mut combat_events: EventWriter<CombatTriggeredEvent>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    for pick in picks.read() {
+        let (MouseButton::Right, PickTarget::Ship(entity)) = (pick.button, pick.target) else {
+            continue;
+        };
+        let Ok((transform, faction, smuggler)) = ship_query.get(entity) else {
+            continue;
+        };
+        if transform.translation.truncate().distance(player_pos) > HAIL_RANGE {
+            continue;
+        }
+
+        engagement.interception = Some(Interception {
+            ship: entity,
+            faction: faction.0,
+            smuggler,
+            position: player_pos,
+        });
+        combat_events.send(CombatTriggeredEvent {
+            enemy_entity: entity,
+            enemy_faction: faction.0,
+        });
+        return;
+    }
+}
+
+/// Settles an interception once its battle ends.
+///
+/// A smuggler beaten inside a patrol's waters counts toward that patrol. An
+/// honest merchant costs reputation with its nation and a fine as soon as it
+/// is attacked, won or not.
+fn resolve_interceptions(
+    mut commands: Commands,
+    mut combat_events: EventReader<CombatEndedEvent>,
+    mut engagement: ResMut<PatrolEngagement>,
+    mut contract_query: Query<(&PatrolArea, &mut ContractProgress), (With<Contract>, With<AcceptedContract>)>,
+    mut registry: ResMut<FactionRegistry>,
+    mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
+    mut journal: PatrolJournal,
+) {
+    let Some(event) = combat_events.read().last() else {
+        return;
+    };
+    let Some(interception) = engagement.interception.take() else {
+        return;
+    };
+
+    if interception.smuggler {
+        if !event.victory {
+            return;
+        }
+        if let Some(ship) = commands.get_entity(interception.ship) {
+            ship.despawn_recursive();
+        }
+        let mut counted = false;
+        for (area, mut progress) in &mut contract_query {
+            if area.contains(interception.position) {
+                progress.intercepted += 1;
+                counted = true;
+            }
+        }
+        if counted {
+            journal.record("Intercepted a smuggler in the patrol waters.");
+        }
+        return;
+    }
+
+    for (area, mut progress) in &mut contract_query {
+        if area.contains(interception.position) {
+            progress.false_positives += 1;
+        }
+    }
+    registry.adjust_reputation(interception.faction, -FALSE_POSITIVE_REPUTATION_PENALTY);
+    let mut fine = 0;
+    if let Ok(mut gold) = player_query.get_single_mut() {
+        fine = FALSE_POSITIVE_FINE.min(gold.0);
+        gold.0 -= fine;
+    }
+    journal.record(format!(
+        "Attacked an honest {} merchantman. {} reputation with them, fined {} gold.",
+        interception.faction.display_name(),
+        -FALSE_POSITIVE_REPUTATION_PENALTY,
+        fine
+    ));
+}
+
+/// Pays out patrols that have intercepted enough smugglers.
+fn complete_patrols(
+    mut commands: Commands,
+    contract_query: Query<(Entity, &ContractDetails, &PatrolArea, &ContractProgress), (With<AcceptedContract>, Changed<ContractProgress>)>,
+    mut registry: ResMut<FactionRegistry>,
+    mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
+    mut player_contracts: ResMut<PlayerContracts>,
+    mut completion_events: EventWriter<ContractCompletedEvent>,
+    mut journal: PatrolJournal,
+) {
+    for (entity, details, area, progress) in &contract_query {
+        if !area.is_complete(progress) {
+            continue;
+        }
+        if let Ok(mut gold) = player_query.get_single_mut() {
+            gold.add(details.reward_gold);
+        }
+        if let Some(issuer) = details.issuer {
+            registry.adjust_reputation(issuer, PATROL_REPUTATION_REWARD);
+        }
+        journal.record(format!(
+            "Patrol complete: {} smugglers intercepted for {}. Paid {} gold.",
+            progress.intercepted,
+            details.issuer.map_or("the issuer", |faction| faction.display_name()),
+            details.reward_gold
+        ));
+        completion_events.send(ContractCompletedEvent {
+            contract_entity: entity,
+            reward_gold: details.reward_gold,
+        });
+        player_contracts.active.retain(|&e| e != entity);
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn reset_patrol_engagement(mut engagement: ResMut<PatrolEngagement>) {
+    engagement.interception = None;
+}
@@ -0,0 +1,102 @@
+//! Click picking on the High Seas.
+//!
+//! Ships, ports and wrecks are tagged `Pickable` as they appear. Each frame
+//! the visible ones are indexed in `PickIndex`, and every left or right click
+//! is resolved to what lies under the cursor and sent as a `PickingResult`
+//! for navigation (and anything else that reacts to clicks) to consume.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::components::picking::{PickKind, Pickable};
+use crate::events::PickingResult;
+use crate::plugins::core::{GameState, MainCamera};
+use crate::plugins::worldmap::{HighSeasAI, HighSeasPort, LegacyWreckMarker};
+use crate::plugins::wreck_sites::WreckSiteMarker;
+use crate::resources::{MapData, PickCandidate, PickIndex, PickTarget};
+use crate::utils::pathfinding::world_to_tile;
+
+/// Plugin for resolving map clicks to ships, ports, wrecks or open water.
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PickIndex>()
+            .add_event::<PickingResult>()
+            .add_systems(Update, (
+                tag_pickables,
+                rebuild_pick_index.after(tag_pickables),
+                emit_picking_results.after(rebuild_pick_index),
+            ).run_if(in_state(GameState::HighSeas)));
+    }
+}
+
+/// Makes newly spawned High Seas ships, ports and wrecks clickable.
+fn tag_pickables(
+    mut commands: Commands,
+    ships: Query<Entity, Added<HighSeasAI>>,
+    ports: Query<Entity, Added<HighSeasPort>>,
+    wrecks: Query<Entity, Or<(Added<WreckSiteMarker>, Added<LegacyWreckMarker>)>>,
+) {
+    for entity in &ships {
+        commands.entity(entity).try_insert(Pickable::new(PickKind::Ship));
+    }
+    for entity in &ports {
+        commands.entity(entity).try_insert(Pickable::new(PickKind::Port));
+    }
+    for entity in &wrecks {
+        commands.entity(entity).try_insert(Pickable::new(PickKind::Wreck));
+    }
+}
+
+/// Indexes every visible pickable; ships hidden in fog or asleep cannot be clicked.
+fn rebuild_pick_index(
+    mut index: ResMut<PickIndex>,
+    query: Query<(Entity, &Pickable, &GlobalTransform, &InheritedVisibility)>,
+) {
+    index.clear();
+    for (entity, pickable, transform, visibility) in &query {
+        if !visibility.get() {
+            continue;
+        }
+        index.insert(PickCandidate {
+            entity,
+            kind: pickable.kind,
+            position: transform.translation().truncate(),
+            radius: pickable.radius,
+        });
+    }
+}
+
+/// Resolves this frame's clicks against the index.
+pub fn emit_picking_results(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    index: Res<PickIndex>,
+    map_data: Res<MapData>,
+    mut results: EventWriter<PickingResult>,
+) {
+    for button in [MouseButton::Left, MouseButton::Right] {
+        if !mouse_button.just_pressed(button) {
+            continue;
+        }
+        let Ok(window) = window_query.get_single() else { return };
+        let Ok((camera, camera_transform)) = camera_query.get_single() else { return };
+        let Some(cursor_pos) = window.cursor_position() else { return };
+        let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) else { return };
+
+        let target = match index.pick(world_pos) {
+            Some(candidate) => candidate.target(),
+            None => {
+                let tile = world_to_tile(world_pos, map_data.width, map_data.height);
+                if map_data.in_bounds(tile.x, tile.y) && map_data.is_navigable(tile.x as u32, tile.y as u32) {
+                    PickTarget::Water
+                } else {
+                    PickTarget::Land
+                }
+            }
+        };
+        results.send(PickingResult { button, world_pos, target });
+    }
+}
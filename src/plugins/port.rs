@@ -6,6 +6,7 @@ use crate::components::{
     port::{Inventory, Port, PortName},
     ship::Faction,
 };
+use crate::resources::GoodsTable;
 
 /// Plugin for managing port entities and their interactions.
 pub struct PortPlugin;
@@ -24,6 +25,8 @@ impl Plugin for PortPlugin {
 /// * `world_position` - Position in world coordinates
 /// * `name` - Display name of the port
 /// * `faction` - The faction controlling this port
+/// * `goods` - Base prices and quantities for the starting stock
+/// * `stock_multiplier` - Scales the starting quantity of every good
 /// 
 /// Returns the spawned port entity.
 pub fn spawn_port(
@@ -31,8 +34,10 @@ pub fn spawn_port(
     world_position: Vec2,
     name: String,
     faction: Faction,
+    goods: &GoodsTable,
+    stock_multiplier: f32,
 ) -> Entity {
-    let inventory = generate_random_inventory();
+    let inventory = generate_random_inventory(goods, stock_multiplier);
     
     let entity = commands.spawn((
         Port,
@@ -40,6 +45,8 @@ pub fn spawn_port(
         faction,
         inventory,
         Transform::from_xyz(world_position.x, world_position.y, 0.0),
+        // Drawn by its `PortVisual` parts
+        Visibility::default(),
     )).id();
     
     info!("Spawned port '{}' at ({}, {})", name, world_position.x, world_position.y);
@@ -47,25 +54,20 @@ pub fn spawn_port(
     entity
 }
 
+/// Starting stock falls within this fraction of a good's base quantity.
+const STARTING_STOCK_RANGE: std::ops::RangeInclusive<f32> = 0.5..=1.5;
+
 /// Generates a random starting inventory for a port.
-/// Each port has a randomized selection of goods with varied quantities and prices.
-pub fn generate_random_inventory() -> Inventory {
+/// Each port has a randomized selection of goods with varied quantities and prices,
+/// drawn around the base price and quantity in `goods`.
+/// Quantities are scaled by `stock_multiplier` (1.0 for normal stock).
+pub fn generate_random_inventory(goods: &GoodsTable, stock_multiplier: f32) -> Inventory {
     let mut rng = rand::thread_rng();
     let mut inventory = Inventory::new();
     
-    // Base prices for each good type
-    let goods_config = [
-        (GoodType::Rum, 15.0, 50, 150),      // (type, base_price, min_qty, max_qty)
-        (GoodType::Sugar, 8.0, 80, 200),
-        (GoodType::Spices, 25.0, 20, 80),
-        (GoodType::Timber, 5.0, 100, 300),
-        (GoodType::Cloth, 12.0, 40, 120),
-        (GoodType::Weapons, 40.0, 10, 50),
-    ];
-    
     // Each port has 3-5 goods initially available
     let num_goods = rng.gen_range(3..=5);
-    let mut available_goods: Vec<_> = goods_config.iter().collect();
+    let mut available_goods: Vec<GoodType> = GoodType::ALL.to_vec();
     
     for _ in 0..num_goods {
         if available_goods.is_empty() {
@@ -73,16 +75,17 @@ pub fn generate_random_inventory() -> Inventory {
         }
         
         let idx = rng.gen_range(0..available_goods.len());
-        let (good, base_price, min_qty, max_qty) = available_goods.remove(idx);
+        let good = available_goods.remove(idx);
+        let stats = goods.stats(good);
         
-        // Randomize quantity within range
-        let quantity = rng.gen_range(*min_qty..=*max_qty);
+        // Randomize quantity around the base quantity
+        let quantity = (stats.base_quantity as f32 * rng.gen_range(STARTING_STOCK_RANGE) * stock_multiplier).round() as u32;
         
         // Randomize price within ±30% of base
         let price_variance = rng.gen_range(0.7..1.3);
-        let price = base_price * price_variance;
+        let price = stats.base_price * price_variance;
         
-        inventory.set_good(*good, quantity, price);
+        inventory.set_good(good, quantity, price);
     }
     
     inventory
@@ -112,3 +115,25 @@ pub fn generate_port_name() -> String {
         suffixes[rng.gen_range(0..suffixes.len())]
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::GoodStats;
+
+    #[test]
+    fn test_starting_stock_follows_the_goods_table() {
+        let mut goods = GoodsTable::default();
+        for stats in goods.goods.values_mut() {
+            *stats = GoodStats { base_price: 100.0, base_quantity: 1000 };
+        }
+
+        let inventory = generate_random_inventory(&goods, 1.0);
+
+        assert!(inventory.goods.len() >= 3);
+        for item in inventory.goods.values() {
+            assert!((500..=1500).contains(&item.quantity), "quantity {}", item.quantity);
+            assert!((70.0..=130.0).contains(&item.price), "price {}", item.price);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Festivals and dockworkers' strikes, declared each morning.
+//!
+//! At `PORT_EVENT_HOUR` the events that have run their course end, and with
+//! `PORT_EVENT_CHANCE` a port without one declares a festival or has its docks
+//! walk out. Both make the news. A festival's guest is seated in the tavern
+//! whenever the player puts in during it, and leaves with the player so
+//! they are never kept in the tavern's roster.
+
+use bevy::prelude::*;
+use rand::seq::IteratorRandom;
+use rand::Rng;
+
+use crate::components::companion::{Companion, CompanionName};
+use crate::components::port::{Port, PortName};
+use crate::events::WorldNewsEvent;
+use crate::plugins::companion::{generate_tavern_companions, store_tavern_companions, RecruitableCompanion, TavernCompanions};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::CurrentPort;
+use crate::resources::{NewsCategory, PortEventKind, PortEvents, WorldClock, FESTIVAL_GUESTS};
+
+/// Hour of the day festivals and strikes begin and end.
+const PORT_EVENT_HOUR: u32 = 7;
+/// Chance each morning that some port declares a festival or strike.
+const PORT_EVENT_CHANCE: f64 = 0.35;
+/// Share of new port events that are festivals rather than strikes.
+const FESTIVAL_SHARE: f64 = 0.6;
+
+/// Plugin for port festivals and strikes.
+pub struct PortEventsPlugin;
+
+impl Plugin for PortEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PortEvents>()
+            .add_systems(FixedUpdate, port_event_scheduler)
+            .add_systems(OnEnter(GameState::Port), seat_festival_guest.after(generate_tavern_companions))
+            .add_systems(OnExit(GameState::Port), send_festival_guest_home.before(store_tavern_companions))
+            .add_systems(OnEnter(GameState::MainMenu), reset_port_events);
+    }
+}
+
+/// Ends the festivals and strikes that are over, and may start a new one at
+/// a port with nothing under way.
+fn port_event_scheduler(
+    world_clock: Res<WorldClock>,
+    mut port_events: ResMut<PortEvents>,
+    port_query: Query<(&Transform, &PortName), With<Port>>,
+    mut news: EventWriter<WorldNewsEvent>,
+) {
+    if world_clock.tick != 0 || world_clock.hour != PORT_EVENT_HOUR {
+        return;
+    }
+
+    for ended in port_events.expire(world_clock.day) {
+        let headline = match ended.kind {
+            PortEventKind::Festival => format!("The festival in {} is over", ended.port_name),
+            PortEventKind::Strike => format!("The dockworkers of {} are back at work", ended.port_name),
+        };
+        news.send(WorldNewsEvent { category: NewsCategory::Harbor, headline });
+    }
+
+    let mut rng = rand::thread_rng();
+    if !rng.gen_bool(PORT_EVENT_CHANCE) {
+        return;
+    }
+    // Ports only stand on the chart while the High Seas are loaded
+    let Some((transform, name)) = port_query
+        .iter()
+        .filter(|(transform, _)| port_events.at(transform.translation.truncate()).is_none())
+        .choose(&mut rng)
+    else {
+        return;
+    };
+
+    let kind = if rng.gen_bool(FESTIVAL_SHARE) { PortEventKind::Festival } else { PortEventKind::Strike };
+    let guest = rng.gen_range(0..FESTIVAL_GUESTS.len());
+    if port_events.start(transform.translation.truncate(), name.0.clone(), kind, world_clock.day, guest) {
+        let headline = match kind {
+            PortEventKind::Festival => format!("{} declares a festival; the taverns are full", name.0),
+            PortEventKind::Strike => format!("Dockworkers strike in {}; the harbor is shut to trade", name.0),
+        };
+        news.send(WorldNewsEvent { category: NewsCategory::Harbor, headline });
+    }
+}
+
+/// Seats the festival's guest in the tavern, unless they have already
+/// signed on.
+fn seat_festival_guest(
+    current_port: Res<CurrentPort>,
+    port_events: Res<PortEvents>,
+    mut tavern_comps: ResMut<TavernCompanions>,
+    companions: Query<&CompanionName, With<Companion>>,
+) {
+    let Some((name, role)) = port_events.at_port(current_port.position).and_then(|event| event.festival_guest()) else {
+        return;
+    };
+    let aboard = companions.iter().any(|companion| companion.0 == name);
+    if aboard || tavern_comps.available.iter().any(|companion| companion.name == name) {
+        return;
+    }
+
+    tavern_comps.available.push(RecruitableCompanion {
+        name: name.to_string(),
+        role,
+        cost: role.base_hire_cost(),
+        id: rand::thread_rng().gen::<u64>(),
+        memory: None,
+        veteran: false,
+    });
+    info!("{} is in town for the festival", name);
+}
+
+/// Takes a festival guest who was not recruited out of the tavern.
+fn send_festival_guest_home(mut tavern_comps: ResMut<TavernCompanions>) {
+    tavern_comps
+        .available
+        .retain(|companion| companion.memory.is_some() || !FESTIVAL_GUESTS.iter().any(|(name, _)| *name == companion.name));
+}
+
+fn reset_port_events(mut port_events: ResMut<PortEvents>) {
+    port_events.clear();
+}
@@ -1,18 +1,25 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts, EguiSet};
+use std::collections::HashMap;
 use crate::resources::ui_assets::UiAssets;
 
 use crate::components::{
     cargo::{Cargo, Gold},
-    contract::{AcceptedContract, Contract, ContractDetails, ContractProgress},
+    crew::{Crew, HIRE_COST, SAILOR_WAGE},
+    contract::{format_time_remaining, AcceptedContract, Contract, ContractDetails, ContractProgress},
     health::Health,
     intel::{Intel, IntelData, IntelType, IntelExpiry, TavernIntel, AcquiredIntel},
-    port::{Inventory, Port, PortName},
-    ship::{Player, Ship},
+    port::{port_key, Inventory, OfferedAt, Port},
+    ship::{Faction, FactionId, Player, Ship},
 };
-use crate::events::{ContractAcceptedEvent, ContractCompletedEvent, TradeExecutedEvent, RepairRequestEvent, RepairType, IntelAcquiredEvent};
+use crate::plugins::ship_market::{current_ship_terms, has_cargo_aboard};
+use crate::events::{BulkTradeEvent, CodexOpenEvent, RestRequestEvent, ContractAcceptedEvent, ContractCompletedEvent, ContractExpiredEvent, TradeExecutedEvent, TradeSettledEvent, RepairRequestEvent, RepairType, IntelAcquiredEvent, ReclaimShipEvent, RefitAction, RefitEvent, MooringAction, MooringEvent, ShipMarketAction, ShipMarketEvent};
+use crate::resources::{DockQueue, FactionRegistry, MetaProfile, MooredShips, PlayerCrew, MOORING_FEE, RestUntil, PendingBounties, PlayerFleet, RefitTarget, ScatteredShip, TradeTicket, UiLayout, UpgradeInventory, COMPACT_TARGET_HEIGHT, EMERGENCY_REPAIR_MULTIPLIER, REFIT_FEE, REFIT_HOURS, TradeTerms, TICKS_PER_HOUR, ShipDefinitions, ShipMarketTerms, BalanceConfig, RepairBalance, PortEvents, event_price, markets_open, clock_darkness, CalendarDate, Season, GoodsTable, ContractRewards, PortMarket, PortMarkets};
+use crate::components::upgrade::{upgrade_summary, upgrades_cargo_bonus, upgrades_hull_bonus, Fittings, UpgradeKind, UPGRADE_SLOTS};
+use crate::resources::good_codex_id;
 use crate::plugins::core::GameState;
-use crate::systems::repair::{repair_execution_system, calculate_repair_cost};
+use crate::systems::repair::{calculate_repair_cost, calculate_repair_hours, dock_jobs_system, plan_repair_all, repair_damage, repair_execution_system, RepairPriority};
+use crate::systems::economy::{price_at_stock, GlobalDemand};
 
 /// Plugin for the Port View UI.
 /// Displays when the player is docked at a port.
@@ -24,18 +31,29 @@ impl Plugin for PortUiPlugin {
             .init_resource::<CurrentPort>()
             .init_resource::<PortUiState>()
             .init_resource::<PlayerContracts>()
+            .init_resource::<DockQueue>()
             .add_event::<TradeExecutedEvent>()
+            .add_event::<TradeSettledEvent>()
+            .add_event::<BulkTradeEvent>()
             .add_event::<ContractAcceptedEvent>()
             .add_event::<ContractCompletedEvent>()
             .add_event::<RepairRequestEvent>()
             .add_event::<IntelAcquiredEvent>()
-            .add_systems(OnEnter(GameState::Port), (generate_port_contracts, generate_tavern_intel))
+            .add_event::<ReclaimShipEvent>()
+            .init_resource::<PortBoardSchedule>()
+            .add_systems(FixedUpdate, refresh_port_boards)
+            .add_systems(OnEnter(GameState::MainMenu), reset_port_board_schedule)
+            .add_systems(OnExit(GameState::Port), (clear_trade_ticket, clear_dock_queue))
             .add_systems(Update, (
                 port_ui_system.after(EguiSet::InitContexts),
+                bulk_trade_system.before(trade_execution_system),
                 trade_execution_system,
+                crate::systems::trade_demand_system.after(trade_execution_system),
                 contract_acceptance_system,
                 repair_execution_system,
+                dock_jobs_system.after(repair_execution_system),
                 intel_purchase_system,
+                ship_reclaim_system,
                 crate::systems::intel_acquisition_system,
             ).run_if(in_state(GameState::Port)));
     }
@@ -44,14 +62,136 @@ impl Plugin for PortUiPlugin {
 /// Resource tracking which port the player is currently visiting.
 #[derive(Resource, Default)]
 pub struct CurrentPort {
-    pub entity: Option<Entity>,
+    /// Flag flown by the port, kept after its High Seas entity is despawned.
+    pub faction: Option<FactionId>,
+    /// Name and world position of the port, kept likewise.
+    pub name: Option<String>,
+    pub position: Option<Vec2>,
 }
 
 /// UI state for the port interface.
 #[derive(Resource, Default)]
 pub struct PortUiState {
-    /// Currently selected tab (0=Market, 1=Tavern, 2=Docks, 3=Contracts)
+    /// Currently selected tab (0=Market, 1=Tavern, 2=Docks, 3=Contracts, 4=Shipyard, 5=Moorings)
     pub selected_tab: usize,
+    /// Trade being put together on the Market tab.
+    pub trade_ticket: TradeTicket,
+    /// Market row the arrow keys are on.
+    pub market_row: usize,
+    /// What the Docks' "repair all" sees to first when gold is short.
+    pub repair_priority: RepairPriority,
+}
+
+/// Keys pressed on the port screen this frame. Number keys pick a tab; on
+/// the Market, Up and Down move between goods, Left and Right sell or buy
+/// one more (ten with Shift) and Enter makes the trade; on the Docks, R books
+/// every repair the purse runs to.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PortKeys {
+    pub tab: Option<usize>,
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub shift: bool,
+    pub enter: bool,
+    pub repair_all: bool,
+}
+
+impl PortKeys {
+    /// Reads the keys from egui's input.
+    pub fn read(input: &egui::InputState) -> Self {
+        const TAB_KEYS: [egui::Key; 6] =
+            [egui::Key::Num1, egui::Key::Num2, egui::Key::Num3, egui::Key::Num4, egui::Key::Num5, egui::Key::Num6];
+        Self {
+            tab: TAB_KEYS.iter().position(|key| input.key_pressed(*key)),
+            up: input.key_pressed(egui::Key::ArrowUp),
+            down: input.key_pressed(egui::Key::ArrowDown),
+            left: input.key_pressed(egui::Key::ArrowLeft),
+            right: input.key_pressed(egui::Key::ArrowRight),
+            shift: input.modifiers.shift,
+            enter: input.key_pressed(egui::Key::Enter),
+            repair_all: input.key_pressed(egui::Key::R),
+        }
+    }
+
+    /// Whether the focused row moved this frame.
+    pub fn moved(&self) -> bool {
+        self.up || self.down
+    }
+
+    /// The focused row after this frame's Up and Down, among `rows` rows.
+    pub fn move_focus(&self, row: usize, rows: usize) -> usize {
+        if rows == 0 {
+            return 0;
+        }
+        let row = row.min(rows - 1);
+        match (self.up, self.down) {
+            (true, false) => row.saturating_sub(1),
+            (false, true) => (row + 1).min(rows - 1),
+            _ => row,
+        }
+    }
+
+    /// Units to add to the focused good's trade: negative to sell, positive to buy.
+    pub fn nudge(&self) -> i32 {
+        let step = if self.shift { 10 } else { 1 };
+        match (self.left, self.right) {
+            (true, false) => -step,
+            (false, true) => step,
+            _ => 0,
+        }
+    }
+}
+
+/// Port tab names, in `PortUiState::selected_tab` order.
+pub const PORT_TABS: [&str; 6] = ["Market", "Tavern", "Docks", "Contracts", "Shipyard", "Moorings"];
+/// Width of a port tab in the compact layout (points).
+const COMPACT_TAB_WIDTH: f32 = 140.0;
+
+/// In-game days between refreshes of a port's contract board.
+/// Matches `ContractDetails::DEFAULT_DURATION_TICKS` so boards are never empty.
+pub const CONTRACT_REFRESH_DAYS: u32 = 2;
+/// In-game days between refreshes of a port's tavern intel.
+/// Matches `IntelExpiry::DEFAULT_DURATION_TICKS`.
+pub const INTEL_REFRESH_DAYS: u32 = 1;
+
+/// Day each port last refreshed its contract board and tavern intel.
+///
+/// Keyed by `port_key` rather than the port's entity.
+#[derive(Resource, Default, Debug)]
+pub struct PortBoardSchedule {
+    contracts: HashMap<IVec2, u32>,
+    intel: HashMap<IVec2, u32>,
+}
+
+impl PortBoardSchedule {
+    /// Whether the port's contract board is due a refresh on `day`.
+    pub fn contracts_due(&self, key: IVec2, day: u32) -> bool {
+        Self::is_due(self.contracts.get(&key), day, CONTRACT_REFRESH_DAYS)
+    }
+
+    /// Whether the port's tavern intel is due a refresh on `day`.
+    pub fn intel_due(&self, key: IVec2, day: u32) -> bool {
+        Self::is_due(self.intel.get(&key), day, INTEL_REFRESH_DAYS)
+    }
+
+    pub fn mark_contracts(&mut self, key: IVec2, day: u32) {
+        self.contracts.insert(key, day);
+    }
+
+    pub fn mark_intel(&mut self, key: IVec2, day: u32) {
+        self.intel.insert(key, day);
+    }
+
+    pub fn clear(&mut self) {
+        self.contracts.clear();
+        self.intel.clear();
+    }
+
+    fn is_due(last: Option<&u32>, day: u32, cooldown: u32) -> bool {
+        last.is_none_or(|last| day >= last + cooldown)
+    }
 }
 
 /// Resource tracking player's active contracts.
@@ -63,11 +203,72 @@ pub struct PlayerContracts {
 #[derive(bevy::ecs::system::SystemParam)]
 pub struct PortUiEvents<'w> {
     pub trade: EventWriter<'w, TradeExecutedEvent>,
+    pub bulk_trade: EventWriter<'w, BulkTradeEvent>,
     pub contract: EventWriter<'w, ContractAcceptedEvent>,
     pub repair: EventWriter<'w, RepairRequestEvent>,
     pub intel: EventWriter<'w, IntelAcquiredEvent>,
     pub companion: EventWriter<'w, crate::plugins::companion::CompanionRecruitedEvent>,
+    pub dismiss: EventWriter<'w, crate::plugins::companion::CompanionDismissedEvent>,
     pub auto_trade: EventWriter<'w, crate::plugins::companion::AutoTradeEvent>,
+    pub codex: EventWriter<'w, CodexOpenEvent>,
+    pub reclaim: EventWriter<'w, ReclaimShipEvent>,
+    pub refit: EventWriter<'w, RefitEvent>,
+    pub rest: EventWriter<'w, RestRequestEvent>,
+    pub mooring: EventWriter<'w, MooringEvent>,
+    pub hire: EventWriter<'w, crate::plugins::crew::HireSailorsEvent>,
+    pub ship_market: EventWriter<'w, ShipMarketEvent>,
+}
+
+/// The player's companions.
+type CrewCompanions<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static crate::components::companion::CompanionName, &'static crate::components::companion::CompanionRole),
+    With<crate::components::companion::Companion>,
+>;
+
+/// Read-only campaign state shown across the port tabs.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct PortUiData<'w> {
+    pub world_clock: Res<'w, crate::resources::WorldClock>,
+    pub profile: Res<'w, MetaProfile>,
+    pub upgrades: Res<'w, UpgradeInventory>,
+    pub player_fleet: Res<'w, PlayerFleet>,
+    pub bounties: Res<'w, PendingBounties>,
+    pub layout: Res<'w, UiLayout>,
+    pub global_demand: Res<'w, GlobalDemand>,
+    pub dock_queue: Res<'w, DockQueue>,
+    pub moored: Res<'w, MooredShips>,
+    pub factions: Res<'w, FactionRegistry>,
+    pub crew: Res<'w, PlayerCrew>,
+    pub balance: Res<'w, BalanceConfig>,
+    pub port_events: Res<'w, PortEvents>,
+    pub goods: Res<'w, GoodsTable>,
+    pub ships: Res<'w, ShipDefinitions>,
+    pub markets: Res<'w, PortMarkets>,
+}
+
+impl CurrentPort {
+    /// The terms the port trades on, by the player's standing with its flag.
+    pub fn trade_terms(&self, factions: &FactionRegistry) -> TradeTerms {
+        self.faction
+            .map_or_else(TradeTerms::default, |faction| TradeTerms::for_standing(factions.standing(faction)))
+    }
+
+    /// The terms its market trades goods on: `trade_terms`, marked down
+    /// during a festival.
+    pub fn market_terms(&self, factions: &FactionRegistry, port_events: &PortEvents) -> TradeTerms {
+        let mut terms = self.trade_terms(factions);
+        if let Some(event) = port_events.at_port(self.position) {
+            terms.buy *= event.kind.goods_rate();
+        }
+        terms
+    }
+
+    /// The port's market, as `PortMarkets` keeps it between visits.
+    pub fn market<'a>(&self, markets: &'a PortMarkets) -> Option<&'a PortMarket> {
+        self.position.and_then(|position| markets.get(port_key(position)))
+    }
 }
 
 /// Main system to render the Port UI.
@@ -78,21 +279,28 @@ fn port_ui_system(
     current_port: Res<CurrentPort>,
     mut events: PortUiEvents,
     // Queries
-    port_query: Query<(Entity, &PortName, &Inventory), With<Port>>,
     player_query: Query<(&Health, Option<&Cargo>, Option<&Gold>), (With<Player>, With<Ship>)>,
-    contract_query: Query<(Entity, &ContractDetails), (With<Contract>, Without<AcceptedContract>)>,
+    contract_query: Query<(Entity, &ContractDetails, &OfferedAt), (With<Contract>, Without<AcceptedContract>)>,
     active_contract_query: Query<(Entity, &ContractDetails), (With<Contract>, With<AcceptedContract>)>,
-    intel_query: Query<(Entity, &IntelData), (With<Intel>, With<TavernIntel>, Without<AcquiredIntel>)>,
+    intel_query: Query<(Entity, &IntelData, &OfferedAt), (With<Intel>, With<TavernIntel>, Without<AcquiredIntel>)>,
     player_contracts: Res<PlayerContracts>,
     tavern_companions: Res<crate::plugins::companion::TavernCompanions>,
-    companion_query: Query<&crate::components::companion::CompanionRole, With<crate::components::companion::Companion>>,
+    companion_query: CrewCompanions,
     ui_assets: Res<UiAssets>,
+    data: PortUiData,
 ) {
-    // Check key input to close port view
-    if contexts.ctx_mut().input(|i| i.key_pressed(egui::Key::Escape)) {
+    // Check key input to close port view (not while the ship is laid up)
+    let laid_up = data.dock_queue.is_laid_up();
+    if !laid_up && contexts.ctx_mut().input(|i| i.key_pressed(egui::Key::Escape)) {
         next_state.set(GameState::HighSeas);
         return;
     }
+    // Hotkeys stand down while a text field has the keyboard
+    let ctx = contexts.ctx_mut();
+    let keys = if ctx.wants_keyboard_input() { PortKeys::default() } else { ctx.input(PortKeys::read) };
+    if let Some(tab) = keys.tab {
+        ui_state.selected_tab = tab;
+    }
 
     // Get player data
     let player_data = player_query.get_single().ok();
@@ -100,185 +308,428 @@ fn port_ui_system(
     let player_cargo = player_data.and_then(|(_, c, _)| c);
     
     // Check for Quartermaster
-    let has_quartermaster = companion_query.iter().any(|r| matches!(r, crate::components::companion::CompanionRole::Quartermaster));
+    let has_quartermaster = companion_query.iter().any(|(_, _, r)| matches!(r, crate::components::companion::CompanionRole::Quartermaster));
 
     let texture_id = contexts.add_image(ui_assets.parchment_texture.clone());
+    ui_state.trade_ticket.set_terms(current_port.market_terms(&data.factions, &data.port_events));
+    let port_event = data.port_events.at_port(current_port.position);
+    let docks_closed = data.port_events.docks_closed(current_port.position);
+    let market_closed = !markets_open(clock_darkness(&data.world_clock));
+    let tavern_rate = port_event.map_or(1.0, |event| event.kind.tavern_rate());
 
     egui::CentralPanel::default().show(contexts.ctx_mut(), |ui| {
         // Draw parchment background
         crate::plugins::ui_theme::draw_parchment_bg(ui, texture_id);
         
-        // Draw decorative corner flourishes
-        let panel_rect = ui.max_rect().shrink(20.0);
-        crate::plugins::ui_theme::draw_corner_flourishes(ui, panel_rect, 40.0);
+        // Draw decorative corner flourishes (tighter in the compact layout)
+        let compact = data.layout.is_compact();
+        let (margin, flourish) = if compact { (8.0, 24.0) } else { (20.0, 40.0) };
+        let panel_rect = ui.max_rect().shrink(margin);
+        crate::plugins::ui_theme::draw_corner_flourishes(ui, panel_rect, flourish);
 
-        let port_name = current_port.entity
-            .and_then(|e| port_query.get(e).ok())
-            .map(|(_, name, _)| name.0.as_str())
-            .unwrap_or("Unknown Port");
+        let port_name = current_port.name.as_deref().unwrap_or("Unknown Port");
 
         ui.horizontal(|ui| {
             ui.heading(port_name);
+            let season = data.world_clock.season();
+            ui.label(format!("{} {}", season.icon(), data.world_clock.date().formatted()))
+                .on_hover_text(format!("{}: {}", season.name(), season.description()));
+            if let Some(faction) = current_port.faction {
+                let standing = data.factions.standing(faction);
+                ui.label(format!("{} ({})", faction.display_name(), standing.name()))
+                    .on_hover_text("Your standing here sets what the market charges and pays");
+            }
+            if let Some(event) = port_event {
+                let remaining = match event.days_left(data.world_clock.day) {
+                    0 => "ends today".to_string(),
+                    1 => "1 day left".to_string(),
+                    days => format!("{} days left", days),
+                };
+                ui.strong(format!("{} {} ({})", event.kind.icon(), event.kind.name(), remaining))
+                    .on_hover_text(event.kind.description());
+            }
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if ui.button("⛵ Depart").clicked() {
+                if ui.add_enabled(!laid_up, egui::Button::new("⛵ Depart")).clicked() {
                     info!("Departing from port...");
                     next_state.set(GameState::HighSeas);
                 }
+                if laid_up {
+                    let hours = data.dock_queue.hours_remaining(data.world_clock.total_ticks());
+                    if ui.button("⏳ Wait until ready").on_hover_text("Let the days pass in port").clicked() {
+                        if let Some(ready_at) = data.dock_queue.ready_at() {
+                            events.rest.send(RestRequestEvent { until: RestUntil::Tick(ready_at) });
+                        }
+                    }
+                    ui.label(format!("⚒ Laid up: ready in {}h", hours));
+                }
             });
         });
         
         // Ornamental anchor divider instead of plain separator
         crate::plugins::ui_theme::draw_ornamental_divider(ui, ui.available_width());
         
-        ui.horizontal(|ui| {
-            if ui.selectable_label(ui_state.selected_tab == 0, "Market").clicked() { ui_state.selected_tab = 0; }
-            if ui.selectable_label(ui_state.selected_tab == 1, "Tavern").clicked() { ui_state.selected_tab = 1; }
-            if ui.selectable_label(ui_state.selected_tab == 2, "Docks").clicked() { ui_state.selected_tab = 2; }
-            if ui.selectable_label(ui_state.selected_tab == 3, "Contracts").clicked() { ui_state.selected_tab = 3; }
-        });
+        if compact {
+            // Large, evenly sized tabs that wrap onto a second row when narrow
+            ui.horizontal_wrapped(|ui| {
+                for (index, name) in PORT_TABS.iter().enumerate() {
+                    let tab = egui::SelectableLabel::new(ui_state.selected_tab == index, *name);
+                    let tab = ui.add_sized([COMPACT_TAB_WIDTH, COMPACT_TARGET_HEIGHT], tab);
+                    if tab.on_hover_text(format!("Key {}", index + 1)).clicked() {
+                        ui_state.selected_tab = index;
+                    }
+                }
+            });
+        } else {
+            ui.horizontal(|ui| {
+                for (index, name) in PORT_TABS.iter().enumerate() {
+                    let tab = ui.selectable_label(ui_state.selected_tab == index, *name);
+                    if tab.on_hover_text(format!("Key {}", index + 1)).clicked() {
+                        ui_state.selected_tab = index;
+                    }
+                }
+            });
+        }
         
         // Rope divider for tab section
         crate::plugins::ui_theme::draw_rope_divider(ui, ui.available_width());
 
         egui::ScrollArea::vertical().show(ui, |ui| {
-            match ui_state.selected_tab {
-                0 => render_market_panel(
-                    ui, 
-                    current_port.entity, 
-                    current_port.entity.and_then(|e| port_query.get(e).ok()).map(|p| p.2), // p.2 is &Inventory
-                    player_gold, 
-                    player_cargo, 
-                    &mut events.trade,
-                    has_quartermaster,
-                    &mut events.auto_trade,
-                ),
-                1 => render_tavern_panel(
-                    ui,
-                    current_port.entity,
-                    player_gold,
-                    &intel_query,
-                    &mut events.intel,
-                    &tavern_companions,
-                    &mut events.companion,
-                ),
-                2 => render_docks_panel(ui, player_data.map(|(h, _, _)| h), player_gold, &mut events.repair),
-                3 => render_contracts_panel(
-                    ui,
-                    current_port.entity,
-                    &contract_query,
-                    &active_contract_query,
-                    &player_contracts,
-                    &mut events.contract,
-                ),
+            let selected_tab = ui_state.selected_tab;
+            match selected_tab {
+                0 => {
+                    render_strike_notice(ui, docks_closed);
+                    render_market_hours_notice(ui, market_closed, &mut events);
+                    ui.add_enabled_ui(!docks_closed && !market_closed, |ui| {
+                        render_market_panel(
+                            ui,
+                            current_port.position.map(port_key),
+                            current_port.market(&data.markets).map(|market| &market.inventory),
+                            player_gold,
+                            player_cargo,
+                            &mut ui_state,
+                            &keys,
+                            &mut events,
+                            has_quartermaster,
+                            &data.global_demand,
+                            data.world_clock.season(),
+                            &data.goods,
+                        );
+                    });
+                }
+                1 => {
+                    render_tavern_panel(
+                        ui,
+                        current_port.position.map(port_key),
+                        player_gold,
+                        tavern_rate,
+                        &intel_query,
+                        &mut events.intel,
+                    );
+                    render_hire_sailors_section(ui, player_gold, tavern_rate, data.crew.crew.as_ref(), &mut events.hire);
+                    render_recruitment_section(
+                        ui,
+                        player_gold,
+                        tavern_rate,
+                        &tavern_companions,
+                        &companion_query,
+                        &mut events.companion,
+                        &mut events.dismiss,
+                    );
+                }
+                2 => {
+                    render_strike_notice(ui, docks_closed);
+                    ui.add_enabled_ui(!docks_closed, |ui| {
+                        render_docks_panel(
+                            ui,
+                            player_data.map(|(h, _, _)| h),
+                            player_gold,
+                            &mut events.repair,
+                            &data.profile.scattered_ships,
+                            &mut events.reclaim,
+                            &data.dock_queue,
+                            data.world_clock.total_ticks(),
+                            &data.balance.repair,
+                            keys.repair_all,
+                            &mut ui_state.repair_priority,
+                        );
+                        ui.add_space(10.0);
+                        render_shipwright_section(ui, &data.upgrades.flagship, player_gold, &mut events.refit);
+                    });
+                }
+                3 => {
+                    render_contracts_panel(
+                        ui,
+                        current_port.position.map(port_key),
+                        &contract_query,
+                        &active_contract_query,
+                        &player_contracts,
+                        &mut events.contract,
+                        data.world_clock.total_ticks(),
+                    );
+                    render_bounty_claims(ui, &data.bounties);
+                }
+                4 => {
+                    render_strike_notice(ui, docks_closed);
+                    ui.add_enabled_ui(!docks_closed, |ui| {
+                        render_shipyard_panel(
+                            ui,
+                            &data.upgrades,
+                            &data.player_fleet,
+                            player_gold,
+                            &mut events.refit,
+                        );
+                        let terms = current_ship_terms(&current_port, &data.factions, &data.markets, &data.goods);
+                        ui.add_space(10.0);
+                        render_ship_market_section(
+                            ui,
+                            &terms,
+                            &data.ships,
+                            &data.player_fleet,
+                            player_gold,
+                            &mut events.ship_market,
+                        );
+                    });
+                }
+                5 => {
+                    render_strike_notice(ui, docks_closed);
+                    ui.add_enabled_ui(!docks_closed, |ui| {
+                        render_moorings_panel(
+                            ui,
+                            &data.player_fleet,
+                            &data.moored,
+                            current_port.position.map(port_key),
+                            player_gold,
+                            &mut events.mooring,
+                        );
+                    });
+                }
                 _ => {}
             }
         });
     });
 }
 
-/// Renders the Market panel with goods for buying/selling.
+/// Explains why the market and the yard are shut during a strike.
+fn render_strike_notice(ui: &mut egui::Ui, docks_closed: bool) {
+    if docks_closed {
+        ui.colored_label(egui::Color32::from_rgb(160, 60, 40), "✊ The dockworkers are on strike. Nothing is loaded, landed or mended until it is settled.");
+        ui.add_space(5.0);
+    }
+}
+
+/// Renders a notice that the market has shut for the night, with a way to wait for it to open.
+fn render_market_hours_notice(ui: &mut egui::Ui, market_closed: bool, events: &mut PortUiEvents) {
+    if market_closed {
+        ui.horizontal(|ui| {
+            ui.colored_label(egui::Color32::from_rgb(60, 60, 120), "🌙 The market stalls are shuttered for the night.");
+            if ui.button("Wait until morning").clicked() {
+                events.rest.send(RestRequestEvent { until: RestUntil::Morning });
+            }
+        });
+        ui.add_space(5.0);
+    }
+}
+
+/// Renders the Market panel: a quantity slider per good and a preview of the whole trade.
 fn render_market_panel(
-    ui: &mut egui::Ui, 
-    port_entity: Option<Entity>,
+    ui: &mut egui::Ui,
+    port: Option<IVec2>,
     inventory: Option<&Inventory>,
     player_gold: u32,
     player_cargo: Option<&Cargo>,
-    trade_events: &mut EventWriter<TradeExecutedEvent>,
+    ui_state: &mut PortUiState,
+    keys: &PortKeys,
+    events: &mut PortUiEvents,
     has_quartermaster: bool,
-    auto_trade_events: &mut EventWriter<crate::plugins::companion::AutoTradeEvent>,
+    global_demand: &GlobalDemand,
+    season: Season,
+    goods: &GoodsTable,
 ) {
     ui.horizontal(|ui| {
         ui.heading("Market");
         if has_quartermaster {
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.button("⚡ Auto-Trade").on_hover_text("Quartermaster: Automatically buy low and sell high.").clicked() {
-                     if let Some(port) = port_entity {
-                         auto_trade_events.send(crate::plugins::companion::AutoTradeEvent { port_entity: port });
+                     if let Some(port) = port {
+                         events.auto_trade.send(crate::plugins::companion::AutoTradeEvent { port });
                      }
                 }
             });
         }
     });
-    ui.label("Buy and sell goods at this port.");
+    ui.label("Set how much of each good to buy (right) or sell (left), then trade it all at once.");
+    ui.weak("↑/↓ choose a good, ←/→ sell or buy one (Shift for ten), Enter to trade.");
     ui.add_space(10.0);
     
-    if let (Some(port_entity), Some(inventory)) = (port_entity, inventory) {
-        if inventory.goods.is_empty() {
-            ui.label("No goods available at this market.");
-        } else {
-            // Table header
-            egui::Grid::new("market_grid")
-                .num_columns(5)
-                .striped(true)
-                .min_col_width(60.0)
-                .show(ui, |ui| {
-                    ui.strong("Good");
-                    ui.strong("Stock");
-                    ui.strong("Price");
-                    ui.strong("You Have");
-                    ui.strong("Actions");
-                    ui.end_row();
-                    
-                    // Sort goods for consistent display
-                    let mut goods: Vec<_> = inventory.goods.iter().collect();
-                    goods.sort_by(|a, b| format!("{:?}", a.0).cmp(&format!("{:?}", b.0)));
-                    
-                    for (good_type, item) in goods {
-                        ui.label(format!("{:?}", good_type));
-                        ui.label(format!("{}", item.quantity));
-                        ui.label(format!("{:.0}g", item.price));
-                        
-                        // Show player's quantity of this good
-                        let player_qty = player_cargo
-                            .map(|c| c.get(*good_type))
-                            .unwrap_or(0);
-                        ui.label(format!("{}", player_qty));
-                        
-                        // Buy/Sell buttons
-                        ui.horizontal(|ui| {
-                            let price = item.price as u32;
-                            let can_buy = item.quantity > 0 
-                                && player_gold >= price
-                                && player_cargo.map(|c| !c.is_full()).unwrap_or(false);
-                            
-                            if ui.add_enabled(can_buy, egui::Button::new("Buy")).clicked() {
-                                trade_events.send(TradeExecutedEvent {
-                                    port_entity,
-                                    good_type: *good_type,
-                                    quantity: 1,
-                                    is_buy: true,
-                                });
-                            }
-                            
-                            let can_sell = player_qty > 0;
-                            if ui.add_enabled(can_sell, egui::Button::new("Sell")).clicked() {
-                                trade_events.send(TradeExecutedEvent {
-                                    port_entity,
-                                    good_type: *good_type,
-                                    quantity: 1,
-                                    is_buy: false,
-                                });
-                            }
-                        });
-                        ui.end_row();
+    let (Some(port), Some(inventory)) = (port, inventory) else {
+        ui.label("⚠ No port data available");
+        ui.weak("(Enter port from High Seas to trade)");
+        return;
+    };
+    if inventory.goods.is_empty() {
+        ui.label("No goods available at this market.");
+        return;
+    }
+    let Some(cargo) = player_cargo else {
+        ui.label("Your ship has no hold to trade from.");
+        return;
+    };
+    let PortUiState { trade_ticket: ticket, market_row, .. } = ui_state;
+    // No trading from the keyboard while the market is shut
+    let keys = if ui.is_enabled() { *keys } else { PortKeys::default() };
+    *market_row = keys.move_focus(*market_row, inventory.goods.len());
+
+    // Table header
+    egui::Grid::new("market_grid")
+        .num_columns(7)
+        .striped(true)
+        .min_col_width(60.0)
+        .show(ui, |ui| {
+            ui.strong("Good");
+            ui.strong("Stock");
+            ui.strong("Price");
+            ui.strong("You Have");
+            ui.strong("Sell ← → Buy");
+            ui.strong("");
+            ui.strong("Price After");
+            ui.end_row();
+            
+            // Sort goods for consistent display
+            let mut rows: Vec<_> = inventory.goods.iter().collect();
+            rows.sort_by(|a, b| format!("{:?}", a.0).cmp(&format!("{:?}", b.0)));
+            
+            for (row, (good_type, item)) in rows.into_iter().enumerate() {
+                // Good name links to its codex page; the arrow keys' row is marked
+                let focused = row == *market_row;
+                let name = if focused {
+                    egui::RichText::new(format!("▶ {:?}", good_type)).strong()
+                } else {
+                    egui::RichText::new(format!("{:?}", good_type))
+                };
+                let link = ui.link(name);
+                if focused && keys.moved() {
+                    link.scroll_to_me(None);
+                }
+                if link.on_hover_text("Open in codex").clicked() {
+                    events.codex.send(CodexOpenEvent {
+                        entry_id: Some(good_codex_id(*good_type)),
+                    });
+                }
+                ui.label(format!("{}", item.quantity));
+                let terms = ticket.terms();
+                ui.label(format!("{}g / {:.0}g", terms.buy_price(item.price), terms.sell_price(item.price)))
+                    .on_hover_text("Buying / selling price");
+                
+                // Show player's quantity of this good
+                let player_qty = ticket.max_sell(*good_type, cargo);
+                ui.label(format!("{}", player_qty));
+                
+                // Quantity to trade, kept within what the rest of the ticket leaves room for
+                let max_buy = ticket.max_buy(*good_type, inventory, cargo, player_gold);
+                let mut quantity = ticket.get(*good_type).clamp(-(player_qty as i32), max_buy as i32);
+                if focused {
+                    quantity = (quantity + keys.nudge()).clamp(-(player_qty as i32), max_buy as i32);
+                }
+                ui.add(egui::Slider::new(&mut quantity, -(player_qty as i32)..=max_buy as i32));
+                
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(max_buy > 0, egui::Button::new("Max")).on_hover_text("Buy as much as you can").clicked() {
+                        quantity = max_buy as i32;
+                    }
+                    if ui.add_enabled(player_qty > 0, egui::Button::new("All")).on_hover_text("Sell all you have").clicked() {
+                        quantity = -(player_qty as i32);
                     }
                 });
+                ticket.set(*good_type, quantity);
+
+                // Where the port's price settles once the trade moves its stock
+                if quantity == 0 {
+                    ui.label("");
+                } else {
+                    let stock_after = (item.quantity as i64 - quantity as i64).max(0) as u32;
+                    let price_after = price_at_stock(goods, good_type, stock_after, global_demand.get(good_type), season);
+                    let arrow = if price_after > item.price { "▲" } else { "▼" };
+                    ui.label(format!("{} {:.0}g", arrow, price_after));
+                }
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(10.0);
+    crate::plugins::ui_theme::draw_rope_divider(ui, ui.available_width());
+
+    let preview = ticket.preview(inventory, cargo, player_gold);
+    ui.label(format!("Buying: {}g    Selling: {}g", preview.cost, preview.revenue));
+    ui.label(format!("Gold after: {}g", preview.gold_after));
+    ui.label(format!("Hold after: {}/{}", preview.cargo_after, preview.capacity));
+    if !preview.is_feasible() {
+        ui.colored_label(egui::Color32::from_rgb(200, 50, 50), "You can't pay for this trade or stow it.");
+    }
+
+    ui.horizontal(|ui| {
+        let can_trade = !ticket.is_empty() && preview.is_feasible();
+        if ui.add_enabled(can_trade, egui::Button::new("⚖ Trade")).clicked() || (can_trade && keys.enter) {
+            events.bulk_trade.send(BulkTradeEvent {
+                port,
+                orders: ticket.orders(),
+            });
+            ticket.clear();
+        }
+        if ui.add_enabled(!ticket.is_empty(), egui::Button::new("Clear")).clicked() {
+            ticket.clear();
+        }
+    });
+}
+
+/// Expands a confirmed trade ticket into individual trades, sales first so
+/// their proceeds and freed hold space go towards the purchases.
+fn bulk_trade_system(
+    mut bulk_events: EventReader<BulkTradeEvent>,
+    mut trade_events: EventWriter<TradeExecutedEvent>,
+) {
+    for event in bulk_events.read() {
+        let mut orders = event.orders.clone();
+        orders.sort_by_key(|(_, quantity)| *quantity > 0);
+        for (good_type, quantity) in orders {
+            if quantity == 0 {
+                continue;
+            }
+            trade_events.send(TradeExecutedEvent {
+                port: event.port,
+                good_type,
+                quantity: quantity.unsigned_abs(),
+                is_buy: quantity > 0,
+            });
         }
-    } else {
-        ui.label("⚠ No port data available");
-        ui.weak("(Enter port from High Seas to trade)");
     }
 }
 
 /// System that executes trades based on TradeExecutedEvent.
 fn trade_execution_system(
     mut trade_events: EventReader<TradeExecutedEvent>,
-    mut port_query: Query<&mut Inventory, With<Port>>,
+    current_port: Res<CurrentPort>,
+    factions: Res<FactionRegistry>,
+    port_events: Res<PortEvents>,
+    world_clock: Res<crate::resources::WorldClock>,
+    mut markets: ResMut<PortMarkets>,
     mut player_query: Query<(&mut Cargo, &mut Gold), (With<Player>, With<Ship>)>,
+    mut settled_events: EventWriter<TradeSettledEvent>,
 ) {
+    let terms = current_port.market_terms(&factions, &port_events);
+    let on_strike = port_events.docks_closed(current_port.position);
+    let market_closed = !markets_open(clock_darkness(&world_clock));
     for event in trade_events.read() {
-        let Ok(mut inventory) = port_query.get_mut(event.port_entity) else {
-            warn!("Trade failed: Port entity {:?} not found", event.port_entity);
+        if on_strike {
+            info!("Trade failed: the dockworkers are on strike");
+            continue;
+        }
+        if market_closed {
+            info!("Trade failed: the market is closed for the night");
+            continue;
+        }
+
+        let Some(inventory) = markets.get_mut(event.port).map(|market| &mut market.inventory) else {
+            warn!("Trade failed: No market charted at {:?}", event.port);
             continue;
         };
         
@@ -294,7 +745,7 @@ fn trade_execution_system(
                 continue;
             };
             
-            let price = item.price as u32;
+            let price = terms.buy_price(item.price);
             let available = item.quantity;
             let qty = event.quantity.min(available);
             
@@ -321,6 +772,7 @@ fn trade_execution_system(
             let _ = inventory.buy(&event.good_type, added);
             
             info!("Bought {} {:?} for {} gold", added, event.good_type, added * price);
+            settled_events.send(TradeSettledEvent { good_type: event.good_type, quantity: added, is_buy: true });
         } else {
             // Selling: Player gives goods, receives gold
             let removed = cargo.remove(event.good_type, event.quantity);
@@ -329,90 +781,148 @@ fn trade_execution_system(
                 continue;
             }
             
-            // Sell at 80% of port's buy price
-            let sell_modifier = 0.8;
-            let revenue = inventory.sell(event.good_type, removed, sell_modifier) as u32;
+            // Sell below the port's buying price
+            let revenue = inventory.sell(event.good_type, removed, terms.sell) as u32;
             gold.add(revenue);
             
             info!("Sold {} {:?} for {} gold", removed, event.good_type, revenue);
+            settled_events.send(TradeSettledEvent { good_type: event.good_type, quantity: removed, is_buy: false });
         }
     }
 }
 
-/// Generates contracts for ports when entering port state.
-/// Each contract receives an expiry time based on the current WorldClock.
-fn generate_port_contracts(
+fn clear_trade_ticket(mut ui_state: ResMut<PortUiState>) {
+    ui_state.trade_ticket.clear();
+}
+
+fn clear_dock_queue(mut dock_queue: ResMut<DockQueue>) {
+    dock_queue.jobs.clear();
+}
+
+/// When each port's boards turn over, and what their contracts pay.
+#[derive(bevy::ecs::system::SystemParam)]
+struct PortBoards<'w> {
+    schedule: ResMut<'w, PortBoardSchedule>,
+    rewards: Res<'w, ContractRewards>,
+}
+
+/// Refreshes each port's contract board and tavern intel on its own cooldown.
+///
+/// Checked on the hour. A port due a refresh withdraws its unaccepted contracts
+/// and unsold intel before posting new ones, so boards turn over instead of
+/// piling up. Ports only exist on the High Seas, so boards refresh while sailing.
+fn refresh_port_boards(
     mut commands: Commands,
-    port_query: Query<Entity, With<Port>>,
-    existing_contracts: Query<Entity, With<Contract>>,
     world_clock: Res<crate::resources::WorldClock>,
+    mut boards: PortBoards,
+    port_query: Query<(Entity, &Transform, &Faction), With<Port>>,
+    contract_query: Query<(Entity, &ContractDetails, &OfferedAt), (With<Contract>, Without<AcceptedContract>)>,
+    intel_query: Query<(Entity, &IntelExpiry, &OfferedAt), (With<TavernIntel>, Without<AcquiredIntel>)>,
+    mut expire_events: EventWriter<ContractExpiredEvent>,
 ) {
-    use crate::components::cargo::GoodType;
-    use rand::Rng;
-    
-    // Don't regenerate if contracts exist
-    if existing_contracts.iter().count() > 0 {
+    if world_clock.tick != 0 {
         return;
     }
-    
+
+    let day = world_clock.day;
     let current_tick = world_clock.total_ticks();
+    let ports: Vec<Entity> = port_query.iter().map(|(entity, _, _)| entity).collect();
     let mut rng = rand::thread_rng();
-    let ports: Vec<Entity> = port_query.iter().collect();
-    
-    if ports.len() < 2 {
-        warn!("Not enough ports to generate contracts");
-        return;
-    }
-    
-    // Generate 2-4 contracts per port
-    for &origin_port in &ports {
-        let num_contracts = rng.gen_range(2..=4);
-        
-        for _ in 0..num_contracts {
-            // Pick a random destination different from origin
-            let dest_port = loop {
-                let idx = rng.gen_range(0..ports.len());
-                if ports[idx] != origin_port {
-                    break ports[idx];
+
+    for (port_entity, transform, faction) in &port_query {
+        let key = port_key(transform.translation.truncate());
+
+        if boards.schedule.contracts_due(key, day) && ports.len() >= 2 {
+            for (contract_entity, details, offered_at) in &contract_query {
+                // Offers past their expiry are left to contract_expiry_system
+                if offered_at.0 == key && !details.is_expired(current_tick) {
+                    expire_events.send(ContractExpiredEvent {
+                        contract_entity,
+                        was_accepted: false,
+                        details: details.clone(),
+                    });
+                    commands.entity(contract_entity).despawn_recursive();
                 }
-            };
-            
-            // Random good type
-            let good = match rng.gen_range(0..6) {
-                0 => GoodType::Rum,
-                1 => GoodType::Sugar,
-                2 => GoodType::Spices,
-                3 => GoodType::Timber,
-                4 => GoodType::Cloth,
-                _ => GoodType::Weapons,
-            };
-            
-            let quantity = rng.gen_range(5..=20);
-            let reward = quantity * rng.gen_range(15..=30);
-            
-            commands.spawn((
-                Contract,
-                ContractDetails::transport_with_expiry(
-                    origin_port, dest_port, good, quantity, reward, current_tick
-                ),
-            ));
+            }
+            let count = spawn_port_contracts(&mut commands, &mut rng, &boards.rewards, port_entity, key, faction.0, &ports, current_tick);
+            boards.schedule.mark_contracts(key, day);
+            debug!("Port {:?} posted {} contracts on day {}", port_entity, count, day);
+        }
+
+        if boards.schedule.intel_due(key, day) {
+            for (intel_entity, expiry, offered_at) in &intel_query {
+                if offered_at.0 == key && !expiry.is_expired(current_tick) {
+                    commands.entity(intel_entity).despawn_recursive();
+                }
+            }
+            let count = spawn_tavern_intel(&mut commands, &mut rng, port_entity, key, current_tick);
+            boards.schedule.mark_intel(key, day);
+            debug!("Port {:?} tavern has {} new intel on day {}", port_entity, count, day);
         }
     }
-    
-    info!("Generated {} contracts with expiry for {} ports", 
-        ports.len() * 3, // approximate count
-        ports.len()
-    );
+}
+
+/// Forgets board refresh days so a new run starts with fresh boards everywhere.
+fn reset_port_board_schedule(mut schedule: ResMut<PortBoardSchedule>) {
+    schedule.clear();
+}
+
+/// Spawns fresh contracts offered at `origin_port` (keyed `offered_at`), as
+/// many and paying as much as `ContractRewards` says.
+/// Each contract receives an expiry time based on the current WorldClock.
+fn spawn_port_contracts(
+    commands: &mut Commands,
+    rng: &mut impl rand::Rng,
+    rewards: &ContractRewards,
+    origin_port: Entity,
+    offered_at: IVec2,
+    issuer: FactionId,
+    ports: &[Entity],
+    current_tick: u32,
+) -> usize {
+    use crate::components::cargo::GoodType;
+    use rand::seq::SliceRandom;
+
+    if ports.len() < 2 {
+        return 0;
+    }
+
+    let num_contracts = rewards.transport_contracts.roll(rng) as usize;
+    for _ in 0..num_contracts {
+        // Pick a random destination different from origin
+        let dest_port = loop {
+            let idx = rng.gen_range(0..ports.len());
+            if ports[idx] != origin_port {
+                break ports[idx];
+            }
+        };
+
+        // Random good type
+        let good = *GoodType::ALL.choose(rng).unwrap_or(&GoodType::Rum);
+
+        let quantity = rewards.transport_quantity.roll(rng);
+        let reward = quantity * rewards.transport_gold_per_unit.roll(rng);
+
+        commands.spawn((
+            Contract,
+            ContractDetails::transport_with_expiry(
+                origin_port, dest_port, good, quantity, reward, current_tick
+            ).with_issuer(issuer),
+            OfferedAt(offered_at),
+        ));
+    }
+    num_contracts
 }
 
 /// Renders the Contracts panel.
 fn render_contracts_panel(
     ui: &mut egui::Ui,
-    current_port: Option<Entity>,
-    available_query: &Query<(Entity, &ContractDetails), (With<Contract>, Without<AcceptedContract>)>,
+    current_port: Option<IVec2>,
+    available_query: &Query<(Entity, &ContractDetails, &OfferedAt), (With<Contract>, Without<AcceptedContract>)>,
     active_query: &Query<(Entity, &ContractDetails), (With<Contract>, With<AcceptedContract>)>,
     player_contracts: &PlayerContracts,
     contract_events: &mut EventWriter<ContractAcceptedEvent>,
+    current_tick: u32,
 ) {
     ui.heading("Contracts");
     ui.label("Accept jobs for gold and reputation.");
@@ -427,8 +937,18 @@ fn render_contracts_panel(
             for (entity, details) in active_query.iter() {
                 if player_contracts.active.contains(&entity) {
                     ui.horizontal(|ui| {
+                        deadline_badge(ui, details, current_tick);
                         ui.label(format!("• {} - 💰{}", details.description, details.reward_gold));
                     });
+                    if details.contract_type.fails_on_expiry() {
+                        ui.weak(format!(
+                            "   Failing forfeits 💰{} and {} reputation",
+                            details.failure_gold_penalty(),
+                            ContractDetails::FAILURE_REPUTATION_PENALTY
+                        ));
+                    } else {
+                        ui.weak("   Lapses when its time runs out; prizes are paid as they are taken");
+                    }
                 }
             }
         });
@@ -439,27 +959,29 @@ fn render_contracts_panel(
     ui.strong("📜 Available Contracts");
     ui.add_space(5.0);
     
-    let Some(port_entity) = current_port else {
+    let Some(port) = current_port else {
         ui.label("No port selected.");
         return;
     };
     
     let mut contracts_at_port = 0;
     egui::Grid::new("contracts_grid")
-        .num_columns(3)
+        .num_columns(4)
         .striped(true)
         .min_col_width(100.0)
         .show(ui, |ui| {
             ui.strong("Description");
             ui.strong("Reward");
+            ui.strong("Time Left");
             ui.strong("Action");
             ui.end_row();
             
-            for (entity, details) in available_query.iter() {
-                if details.origin_port == port_entity {
+            for (entity, details, offered_at) in available_query.iter() {
+                if offered_at.0 == port {
                     contracts_at_port += 1;
                     ui.label(&details.description);
                     ui.label(format!("💰{}", details.reward_gold));
+                    deadline_badge(ui, details, current_tick);
                     if ui.button("Accept").clicked() {
                         contract_events.send(ContractAcceptedEvent {
                             contract_entity: entity,
@@ -475,6 +997,49 @@ fn render_contracts_panel(
     }
 }
 
+/// Lists bounties paid on docking here and those still owed elsewhere.
+fn render_bounty_claims(ui: &mut egui::Ui, bounties: &PendingBounties) {
+    if bounties.paid.is_empty() && bounties.claims.is_empty() {
+        return;
+    }
+    ui.add_space(10.0);
+    ui.group(|ui| {
+        ui.strong("⚓ Bounty Claims");
+        ui.add_space(5.0);
+        for claim in &bounties.paid {
+            ui.label(format!("✔ {} ({}, day {}) - 💰{} paid", claim.ship_name, claim.victim.display_name(), claim.day, claim.amount));
+        }
+        for claim in &bounties.claims {
+            ui.weak(format!("• {} ({}, day {}) - 💰{} owed", claim.ship_name, claim.victim.display_name(), claim.day, claim.amount));
+        }
+        if !bounties.claims.is_empty() {
+            ui.weak("Outstanding claims are not honoured at this port.");
+        }
+    });
+}
+
+/// Shows how long a contract has left, in red once it is urgent.
+fn deadline_badge(ui: &mut egui::Ui, details: &ContractDetails, current_tick: u32) {
+    let (Some(remaining), Some(expiry)) = (details.ticks_remaining(current_tick), details.expiry_tick) else {
+        ui.label("⏳ No deadline");
+        return;
+    };
+    let color = if remaining < ContractDetails::URGENT_TICKS {
+        egui::Color32::from_rgb(170, 30, 20)
+    } else {
+        crate::plugins::ui_theme::INK_COLOR
+    };
+    ui.label(
+        egui::RichText::new(format!("⏳ {}", format_time_remaining(remaining)))
+            .strong()
+            .color(color),
+    )
+    .on_hover_text(format!(
+        "Due by {}. Accepted contracts that run out of time cost gold and reputation.",
+        CalendarDate::at_tick(expiry).formatted()
+    ));
+}
+
 /// System that handles contract acceptance.
 fn contract_acceptance_system(
     mut commands: Commands,
@@ -497,18 +1062,17 @@ fn contract_acceptance_system(
 /// Renders the Tavern panel with intel for purchase.
 fn render_tavern_panel(
     ui: &mut egui::Ui,
-    current_port: Option<Entity>,
+    current_key: Option<IVec2>,
     player_gold: u32,
-    intel_query: &Query<(Entity, &IntelData), (With<Intel>, With<TavernIntel>, Without<AcquiredIntel>)>,
+    tavern_rate: f32,
+    intel_query: &Query<(Entity, &IntelData, &OfferedAt), (With<Intel>, With<TavernIntel>, Without<AcquiredIntel>)>,
     intel_events: &mut EventWriter<IntelAcquiredEvent>,
-    tavern_companions: &crate::plugins::companion::TavernCompanions,
-    recruit_events: &mut EventWriter<crate::plugins::companion::CompanionRecruitedEvent>,
 ) {
     ui.heading("Tavern");
     ui.label("Gather intelligence and recruit crew.");
     ui.add_space(10.0);
     
-    let Some(port_entity) = current_port else {
+    let Some(current_key) = current_key else {
         ui.label("No port selected.");
         return;
     };
@@ -528,9 +1092,9 @@ fn render_tavern_panel(
                 ui.strong("Action");
                 ui.end_row();
                 
-                for (entity, intel_data) in intel_query.iter() {
+                for (entity, intel_data, offered_at) in intel_query.iter() {
                     // Only show intel from this port
-                    if intel_data.source_port != Some(port_entity) {
+                    if offered_at.0 != current_key {
                         continue;
                     }
                     intel_count += 1;
@@ -545,14 +1109,15 @@ fn render_tavern_panel(
                         IntelType::MapReveal => "🗺️",
                     };
                     ui.label(format!("{} {}", icon, intel_data.description));
-                    ui.label(format!("💰{}", intel_data.purchase_cost));
+                    let cost = event_price(intel_data.purchase_cost, tavern_rate);
+                    ui.label(format!("💰{}", cost));
                     
-                    let can_afford = player_gold >= intel_data.purchase_cost;
+                    let can_afford = player_gold >= cost;
                     if ui.add_enabled(can_afford, egui::Button::new("Buy")).clicked() {
                         intel_events.send(IntelAcquiredEvent {
                             intel_entity: entity,
                             intel_type: intel_data.intel_type,
-                            source_port: Some(port_entity),
+                            source_port: intel_data.source_port,
                         });
                     }
                     ui.end_row();
@@ -565,16 +1130,61 @@ fn render_tavern_panel(
             ui.weak("(Try another port)");
         }
     });
-    
-    render_recruitment_section(ui, player_gold, tavern_companions, recruit_events);
+}
+
+/// Renders the Hire Sailors section within the Tavern panel.
+fn render_hire_sailors_section(
+    ui: &mut egui::Ui,
+    player_gold: u32,
+    tavern_rate: f32,
+    crew: Option<&Crew>,
+    hire_events: &mut EventWriter<crate::plugins::crew::HireSailorsEvent>,
+) {
+    ui.add_space(20.0);
+    ui.group(|ui| {
+        ui.strong("⚓ Hire Sailors");
+        ui.add_space(5.0);
+
+        let Some(crew) = crew else {
+            ui.label("You have no ship to crew.");
+            return;
+        };
+        ui.label(format!("Crew: {} / {} berths ({})", crew.hands, crew.berths, crew.mood()));
+        ui.weak(format!(
+            "Wages: 💰{} a day. Fewer than {:.0}% of berths filled slows reloading and turning.",
+            crew.daily_wages(),
+            crate::components::crew::FULL_CREW_SHARE * 100.0
+        ));
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            for count in [1, 5, 10] {
+                let bounty = event_price(HIRE_COST, tavern_rate);
+                let cost = count * bounty;
+                let enabled = count <= crew.vacancies() && player_gold >= cost;
+                let button = ui
+                    .add_enabled(enabled, egui::Button::new(format!("Hire {} (💰{})", count, cost)))
+                    .on_hover_text(format!("💰{} signing bounty each, then 💰{} a day", bounty, SAILOR_WAGE));
+                if button.clicked() {
+                    hire_events.send(crate::plugins::crew::HireSailorsEvent { count });
+                }
+            }
+        });
+        if crew.vacancies() == 0 {
+            ui.weak("Every berth is filled.");
+        }
+    });
 }
 
 /// Renders the Recruitment section within the Tavern panel.
 fn render_recruitment_section(
     ui: &mut egui::Ui,
     player_gold: u32,
+    tavern_rate: f32,
     tavern_companions: &crate::plugins::companion::TavernCompanions,
+    crew: &CrewCompanions,
     recruit_events: &mut EventWriter<crate::plugins::companion::CompanionRecruitedEvent>,
+    dismiss_events: &mut EventWriter<crate::plugins::companion::CompanionDismissedEvent>,
 ) {
     ui.add_space(20.0);
     ui.group(|ui| {
@@ -598,25 +1208,55 @@ fn render_recruitment_section(
                 ui.end_row();
 
                 for companion in &tavern_companions.available {
-                    ui.label(&companion.name);
+                    // Former companions show how they remember you
+                    match companion.memory {
+                        Some(memory) => ui.label(&companion.name).on_hover_text(memory.mood()),
+                        None => ui.label(&companion.name),
+                    };
                     
                     // Show role with description tooltip
                     let role_name = companion.role.name();
                     let role_desc = companion.role.description();
                     ui.label(role_name).on_hover_text(role_desc);
                     
-                    ui.label(format!("💰{}", companion.cost));
+                    let cost = event_price(companion.cost, tavern_rate);
+                    ui.label(format!("💰{}", cost));
                     
-                    let can_afford = player_gold >= companion.cost;
-                    if ui.add_enabled(can_afford, egui::Button::new("Recruit")).clicked() {
-                        recruit_events.send(crate::plugins::companion::CompanionRecruitedEvent {
-                            companion_id: companion.id,
-                        });
+                    let can_afford = player_gold >= cost;
+                    if let Some(memory) = companion.memory.filter(|memory| memory.refuses_rehire()) {
+                        ui.weak(memory.mood());
+                    } else {
+                        let action = if companion.memory.is_some() { "Rehire" } else { "Recruit" };
+                        if ui.add_enabled(can_afford, egui::Button::new(action)).clicked() {
+                            recruit_events.send(crate::plugins::companion::CompanionRecruitedEvent {
+                                companion_id: companion.id,
+                            });
+                        }
                     }
                     ui.end_row();
                 }
             });
     });
+
+    // Companions aboard, who can be left ashore here
+    if crew.is_empty() {
+        return;
+    }
+    ui.add_space(10.0);
+    ui.group(|ui| {
+        ui.strong("⚓ Your Companions");
+        ui.weak("A dismissed companion waits in this tavern and remembers how long they served.");
+        ui.add_space(5.0);
+        for (entity, name, role) in crew.iter() {
+            ui.horizontal(|ui| {
+                ui.label(&name.0);
+                ui.label(role.name());
+                if ui.small_button("Dismiss").clicked() {
+                    dismiss_events.send(crate::plugins::companion::CompanionDismissedEvent { companion: entity });
+                }
+            });
+        }
+    });
 }
 
 /// Renders the Docks panel with ship repair options.
@@ -625,9 +1265,16 @@ fn render_docks_panel(
     health: Option<&Health>,
     player_gold: u32,
     repair_events: &mut EventWriter<RepairRequestEvent>,
+    scattered_ships: &[ScatteredShip],
+    reclaim_events: &mut EventWriter<ReclaimShipEvent>,
+    dock_queue: &DockQueue,
+    now: u32,
+    repair_prices: &RepairBalance,
+    repair_all: bool,
+    priority: &mut RepairPriority,
 ) {
     ui.heading("Docks");
-    ui.label("Repair and upgrade your ship.");
+    ui.label("Repair and upgrade your ship. The shipwrights take their time; she can't sail until they are done.");
     ui.add_space(10.0);
     
     if let Some(health) = health {
@@ -635,176 +1282,488 @@ fn render_docks_panel(
             ui.label("Ship Status:");
             ui.add_space(5.0);
             
-            // Sails
-            let sails_damage = health.sails_max - health.sails;
-            let sails_cost = calculate_repair_cost(RepairType::Sails, sails_damage);
-            let sails_pct = health.sails / health.sails_max;
-            ui.horizontal(|ui| {
-                ui.label("Sails:");
-                ui.add(egui::ProgressBar::new(sails_pct)
-                    .text(format!("{:.0}/{:.0}", health.sails, health.sails_max))
-                    .fill(if sails_pct > 0.5 { egui::Color32::from_rgb(100, 180, 100) } else { egui::Color32::from_rgb(200, 150, 50) })
-                );
-                if sails_pct < 1.0 {
-                    let can_afford = player_gold >= sails_cost;
-                    let button_text = format!("Repair ({}g)", sails_cost);
-                    if ui.add_enabled(can_afford, egui::Button::new(button_text).small()).clicked() {
-                        repair_events.send(RepairRequestEvent { repair_type: RepairType::Sails });
+            let components = [
+                ("Sails:", RepairType::Sails, health.sails, health.sails_max, egui::Color32::from_rgb(200, 150, 50)),
+                ("Rudder:", RepairType::Rudder, health.rudder, health.rudder_max, egui::Color32::from_rgb(200, 150, 50)),
+                ("Hull:", RepairType::Hull, health.hull, health.hull_max, egui::Color32::from_rgb(180, 80, 80)),
+            ];
+            for (label, repair_type, current, max, low_color) in components {
+                let damage = repair_damage(health, repair_type);
+                let pct = current / max;
+                ui.horizontal(|ui| {
+                    ui.label(label);
+                    ui.add(egui::ProgressBar::new(pct)
+                        .text(format!("{:.0}/{:.0}", current, max))
+                        .fill(if pct > 0.5 { egui::Color32::from_rgb(100, 180, 100) } else { low_color })
+                    );
+                    if dock_queue.has_repair(repair_type) {
+                        ui.weak("⚒ Shipwrights at work");
+                    } else if pct < 1.0 {
+                        let cost = calculate_repair_cost(repair_type, damage, repair_prices);
+                        let hours = calculate_repair_hours(repair_type, damage);
+                        let button = egui::Button::new(format!("Repair ({}g, {}h)", cost, hours)).small();
+                        if ui.add_enabled(player_gold >= cost, button).clicked() {
+                            repair_events.send(RepairRequestEvent { repair_type, emergency: false, budget: None });
+                        }
+
+                        // Done at once, for a price
+                        let rush_cost = cost * EMERGENCY_REPAIR_MULTIPLIER;
+                        let rush = egui::Button::new(format!("Rush ({}g)", rush_cost)).small();
+                        if ui.add_enabled(player_gold >= rush_cost, rush).on_hover_text("Emergency repair, done at once").clicked() {
+                            repair_events.send(RepairRequestEvent { repair_type, emergency: true, budget: None });
+                        }
                     }
-                }
-            });
-            
-            // Rudder
-            let rudder_damage = health.rudder_max - health.rudder;
-            let rudder_cost = calculate_repair_cost(RepairType::Rudder, rudder_damage);
-            let rudder_pct = health.rudder / health.rudder_max;
-            ui.horizontal(|ui| {
-                ui.label("Rudder:");
-                ui.add(egui::ProgressBar::new(rudder_pct)
-                    .text(format!("{:.0}/{:.0}", health.rudder, health.rudder_max))
-                    .fill(if rudder_pct > 0.5 { egui::Color32::from_rgb(100, 180, 100) } else { egui::Color32::from_rgb(200, 150, 50) })
-                );
-                if rudder_pct < 1.0 {
-                    let can_afford = player_gold >= rudder_cost;
-                    let button_text = format!("Repair ({}g)", rudder_cost);
-                    if ui.add_enabled(can_afford, egui::Button::new(button_text).small()).clicked() {
-                        repair_events.send(RepairRequestEvent { repair_type: RepairType::Rudder });
+                });
+            }
+
+            // Everything at once, as far as the purse runs
+            let plan = plan_repair_all(health, dock_queue, player_gold, repair_prices, *priority);
+            if plan.full_cost > 0 {
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    let text = if plan.is_complete() {
+                        format!("⚒ Repair all ({}g)", plan.cost)
+                    } else {
+                        format!("⚒ Repair what we can ({}g of {}g)", plan.cost, plan.full_cost)
+                    };
+                    let button = ui
+                        .add_enabled(!plan.requests.is_empty(), egui::Button::new(text))
+                        .on_hover_text("Book the shipwrights for all the damage (R). Short of gold, the parts are mended in priority order as far as it goes.");
+                    // R is ignored during a strike, when the panel is disabled
+                    if button.clicked() || (repair_all && ui.is_enabled()) {
+                        repair_events.send_batch(plan.requests);
                     }
-                }
-            });
-            
-            // Hull
-            let hull_damage = health.hull_max - health.hull;
-            let hull_cost = calculate_repair_cost(RepairType::Hull, hull_damage);
-            let hull_pct = health.hull / health.hull_max;
-            ui.horizontal(|ui| {
-                ui.label("Hull:");
-                ui.add(egui::ProgressBar::new(hull_pct)
-                    .text(format!("{:.0}/{:.0}", health.hull, health.hull_max))
-                    .fill(if hull_pct > 0.5 { egui::Color32::from_rgb(100, 180, 100) } else { egui::Color32::from_rgb(180, 80, 80) })
-                );
-                if hull_pct < 1.0 {
-                    let can_afford = player_gold >= hull_cost;
-                    let button_text = format!("Repair ({}g)", hull_cost);
-                    if ui.add_enabled(can_afford, egui::Button::new(button_text).small()).clicked() {
-                        repair_events.send(RepairRequestEvent { repair_type: RepairType::Hull });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Short of gold:");
+                    for option in RepairPriority::ALL {
+                        if ui.selectable_label(*priority == option, option.name()).clicked() {
+                            *priority = option;
+                        }
                     }
+                });
+            }
+        });
+
+        // Work in hand, in the order the shipwrights will finish it
+        if dock_queue.is_laid_up() {
+            ui.add_space(10.0);
+            ui.group(|ui| {
+                ui.label("In the yard:");
+                for job in &dock_queue.jobs {
+                    let hours = job.ready_at.saturating_sub(now).div_ceil(TICKS_PER_HOUR);
+                    ui.label(format!("⚒ {} - ready in {}h", job.service.label(), hours));
                 }
             });
-        });
+        }
     } else {
         ui.label("⚠ No ship data available");
         ui.weak("(Player ship not found)");
     }
+
+    // Ships scattered when a previous flagship went down
+    if !scattered_ships.is_empty() {
+        ui.add_space(10.0);
+        ui.group(|ui| {
+            ui.label("Familiar hulls in the harbor:");
+            ui.add_space(5.0);
+            for (index, ship) in scattered_ships.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("⛵ {} ({:.0}/{:.0} hull)", ship.name, ship.hull_health, ship.max_hull_health));
+                    let cost = ship.reclaim_cost();
+                    let button = egui::Button::new(format!("Reclaim ({}g)", cost)).small();
+                    if ui.add_enabled(player_gold >= cost, button).clicked() {
+                        reclaim_events.send(ReclaimShipEvent { index });
+                    }
+                });
+            }
+        });
+    }
 }
 
-/// Generates intel available for purchase at taverns when entering port state.
-fn generate_tavern_intel(
-    mut commands: Commands,
-    port_query: Query<Entity, With<Port>>,
-    existing_intel: Query<Entity, With<TavernIntel>>,
-    world_clock: Res<crate::resources::WorldClock>,
+/// Upgrades the Docks' shipwright builds straight into the flagship.
+const SHIPWRIGHT_WORK: [UpgradeKind; 4] = [
+    UpgradeKind::ReinforcedHull,
+    UpgradeKind::ExtraGuns,
+    UpgradeKind::ExpandedHold,
+    UpgradeKind::ImprovedSails,
+];
+
+/// Renders the shipwright under the Docks panel: the flagship's fittings and
+/// what they do, and upgrades to buy and fit to her in one job.
+fn render_shipwright_section(
+    ui: &mut egui::Ui,
+    fitted: &[UpgradeKind],
+    player_gold: u32,
+    refit_events: &mut EventWriter<RefitEvent>,
 ) {
-    use rand::Rng;
-    
-    // Don't regenerate if intel exists
-    if existing_intel.iter().count() > 0 {
-        return;
+    ui.group(|ui| {
+        ui.label("Shipwright:");
+        ui.add_space(5.0);
+        ui.label(format!("Fitted ({}/{} slots): {}", fitted.len(), UPGRADE_SLOTS, upgrade_summary(fitted)));
+
+        let fittings = Fittings::of(fitted);
+        let hull = upgrades_hull_bonus(fitted);
+        let cargo = upgrades_cargo_bonus(fitted);
+        ui.weak(format!(
+            "+{:.0} hull, +{} cargo, reload x{:.2}, speed x{:.2}",
+            hull, cargo, fittings.reload_multiplier, fittings.speed_multiplier
+        ));
+        ui.add_space(5.0);
+
+        let slot_free = fitted.len() < UPGRADE_SLOTS;
+        for upgrade in SHIPWRIGHT_WORK {
+            ui.horizontal(|ui| {
+                ui.label(upgrade.name());
+                ui.weak(upgrade.description());
+                let cost = upgrade.price() + REFIT_FEE;
+                let button = egui::Button::new(format!("Build in ({}g, {}h)", cost, REFIT_HOURS)).small();
+                let enabled = slot_free && player_gold >= cost;
+                if ui.add_enabled(enabled, button).clicked() {
+                    refit_events.send(RefitEvent { action: RefitAction::Commission(upgrade) });
+                }
+            });
+        }
+        if !slot_free {
+            ui.weak("Every slot is taken; have something removed at the Shipyard first.");
+        }
+    });
+}
+
+/// System that returns scattered ships to the fleet for the harbor master's fee.
+fn ship_reclaim_system(
+    mut events: EventReader<ReclaimShipEvent>,
+    mut profile: ResMut<MetaProfile>,
+    mut player_fleet: ResMut<PlayerFleet>,
+    mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
+) {
+    for event in events.read() {
+        let Some(ship) = profile.scattered_ships.get(event.index) else {
+            continue;
+        };
+        let Ok(mut gold) = player_query.get_single_mut() else {
+            warn!("Ship reclaim failed: Player not found");
+            continue;
+        };
+        if !gold.spend(ship.reclaim_cost()) {
+            warn!("Ship reclaim failed: Insufficient gold");
+            continue;
+        }
+
+        let ship = profile.scattered_ships.remove(event.index);
+        info!("Reclaimed scattered ship: {}", ship.name);
+        player_fleet.ships.push(ship.to_ship_data());
     }
-    
-    let current_tick = world_clock.total_ticks();
-    let mut rng = rand::thread_rng();
-    let ports: Vec<Entity> = port_query.iter().collect();
-    
-    if ports.is_empty() {
-        warn!("No ports to generate intel for");
-        return;
+}
+
+/// Renders the Shipyard panel: buy upgrades and move them between hulls.
+fn render_shipyard_panel(
+    ui: &mut egui::Ui,
+    upgrades: &UpgradeInventory,
+    player_fleet: &PlayerFleet,
+    player_gold: u32,
+    refit_events: &mut EventWriter<RefitEvent>,
+) {
+    ui.heading("Shipyard");
+    ui.label(format!(
+        "Each fitting or removal costs {}g and keeps you in port for {} hours.",
+        REFIT_FEE, REFIT_HOURS
+    ));
+    ui.add_space(10.0);
+
+    let can_afford_refit = player_gold >= REFIT_FEE;
+    let mut hulls = vec![(RefitTarget::Flagship, "Flagship".to_string(), upgrades.flagship.as_slice())];
+    hulls.extend(
+        player_fleet
+            .ships
+            .iter()
+            .enumerate()
+            .map(|(index, ship)| (RefitTarget::Fleet(index), ship.name.clone(), ship.upgrades.as_slice())),
+    );
+
+    // Fitted upgrades per hull
+    for (target, name, fitted) in &hulls {
+        ui.group(|ui| {
+            ui.strong(format!("⛵ {} ({}/{} slots)", name, fitted.len(), UPGRADE_SLOTS));
+            for (slot, upgrade) in fitted.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(upgrade.name()).on_hover_text(upgrade.description());
+                    let button = egui::Button::new(format!("Remove ({}g)", REFIT_FEE)).small();
+                    if ui.add_enabled(can_afford_refit, button).clicked() {
+                        refit_events.send(RefitEvent { action: RefitAction::Remove { target: *target, slot } });
+                    }
+                });
+            }
+            if fitted.is_empty() {
+                ui.weak("No upgrades fitted.");
+            }
+        });
     }
-    
-    // Generate 2-4 intel items per port
-    for &port_entity in &ports {
-        let num_intel = rng.gen_range(2..=4);
-        
-        for _ in 0..num_intel {
-            // Random intel type with weighted distribution
-            let intel_type = match rng.gen_range(0..10) {
-                0..=3 => IntelType::Rumor,           // 40% rumors
-                4..=5 => IntelType::MapReveal,       // 20% map reveals
-                6..=7 => IntelType::ShipRoute,       // 20% ship routes
-                8 => IntelType::TreasureLocation,    // 10% treasure
-                _ => IntelType::FleetPosition,       // 10% fleet positions
-            };
-            
-            // Generate description and cost based on type
-            let (description, cost, positions) = match intel_type {
-                IntelType::Rumor => {
-                    let rumors = [
-                        "A merchant fleet was spotted heading north",
-                        "Pirates have been raiding the eastern waters",
-                        "A storm sank a treasure ship last week",
-                        "The navy is patrolling near the southern islands",
-                    ];
-                    let desc = rumors[rng.gen_range(0..rumors.len())].to_string();
-                    (desc, rng.gen_range(10..=30), Vec::new())
-                }
-                IntelType::MapReveal => {
-                    // Reveal a random area of the map
-                    let center_x = rng.gen_range(50..450);
-                    let center_y = rng.gen_range(50..450);
-                    let radius = rng.gen_range(5..=15);
-                    let mut positions = Vec::new();
-                    for dx in -radius..=radius {
-                        for dy in -radius..=radius {
-                            if dx * dx + dy * dy <= radius * radius {
-                                positions.push(IVec2::new(center_x + dx, center_y + dy));
-                            }
-                        }
+
+    // Stowed upgrades waiting for a hull
+    ui.add_space(10.0);
+    ui.group(|ui| {
+        ui.label("In the hold:");
+        if upgrades.stowed.is_empty() {
+            ui.weak("No spare upgrades.");
+        }
+        for (stowed_index, upgrade) in upgrades.stowed.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(upgrade.name()).on_hover_text(upgrade.description());
+                for (target, name, fitted) in &hulls {
+                    let button = egui::Button::new(format!("Fit to {}", name)).small();
+                    let enabled = can_afford_refit && fitted.len() < UPGRADE_SLOTS;
+                    if ui.add_enabled(enabled, button).clicked() {
+                        refit_events.send(RefitEvent { action: RefitAction::Install { stowed_index, target: *target } });
                     }
-                    let desc = format!("Map of a region ({} tiles)", positions.len());
-                    (desc, rng.gen_range(30..=80), positions)
                 }
-                IntelType::ShipRoute => {
-                    let desc = "Trade route between nearby ports".to_string();
-                    (desc, rng.gen_range(40..=100), Vec::new())
+            });
+        }
+    });
+
+    // New upgrades for sale
+    ui.add_space(10.0);
+    ui.group(|ui| {
+        ui.label("For sale:");
+        for upgrade in UpgradeKind::all() {
+            ui.horizontal(|ui| {
+                ui.label(upgrade.name());
+                ui.weak(upgrade.description());
+                let price = upgrade.price();
+                let button = egui::Button::new(format!("Buy ({}g)", price)).small();
+                if ui.add_enabled(player_gold >= price, button).clicked() {
+                    refit_events.send(RefitEvent { action: RefitAction::Buy(*upgrade) });
                 }
-                IntelType::TreasureLocation => {
-                    let x = rng.gen_range(50..450);
-                    let y = rng.gen_range(50..450);
-                    let desc = "Location of hidden treasure".to_string();
-                    (desc, rng.gen_range(80..=200), vec![IVec2::new(x, y)])
+            });
+        }
+    });
+}
+
+/// Renders the ship market under the Shipyard panel: new hulls for sale, and
+/// what the yard offers for each fleet ship.
+fn render_ship_market_section(
+    ui: &mut egui::Ui,
+    terms: &ShipMarketTerms,
+    ships: &ShipDefinitions,
+    player_fleet: &PlayerFleet,
+    player_gold: u32,
+    ship_market_events: &mut EventWriter<ShipMarketEvent>,
+) {
+    ui.group(|ui| {
+        ui.label("New hulls:");
+        for ship in ships.for_sale() {
+            ui.horizontal(|ui| {
+                ui.strong(&ship.name);
+                ui.weak(format!("{:.0} hull, {} cargo, speed {:.0}", ship.hull_strength, ship.cargo, ship.speed));
+                let price = terms.hull_price(ship);
+                let button = egui::Button::new(format!("Buy ({}g)", price)).small();
+                if ui.add_enabled(player_gold >= price, button).clicked() {
+                    ship_market_events.send(ShipMarketEvent { action: ShipMarketAction::Buy(ship.id.clone()) });
                 }
-                IntelType::FleetPosition => {
-                    let x = rng.gen_range(50..450);
-                    let y = rng.gen_range(50..450);
-                    let desc = "Last known position of a fleet".to_string();
-                    (desc, rng.gen_range(50..=120), vec![IVec2::new(x, y)])
+            });
+        }
+    });
+
+    ui.add_space(10.0);
+    ui.group(|ui| {
+        ui.label("Sell a ship:");
+        if player_fleet.ships.is_empty() {
+            ui.weak("No ships besides the flagship.");
+        }
+        for (index, ship) in player_fleet.ships.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.strong(&ship.name);
+                ui.label(format!("Hull {:.0}/{:.0}", ship.hull_health, ship.max_hull_health));
+                let button = egui::Button::new(format!("Sell ({}g)", terms.sale_price(ship, ships))).small();
+                let laden = has_cargo_aboard(ship);
+                let response = ui.add_enabled(!laden, button);
+                if laden {
+                    response.on_disabled_hover_text("Unload her hold first");
+                } else if response.clicked() {
+                    ship_market_events.send(ShipMarketEvent { action: ShipMarketAction::Sell(index) });
                 }
-                IntelType::PortInventory => {
-                    let desc = "Port market prices".to_string();
-                    (desc, rng.gen_range(20..=50), Vec::new())
+            });
+        }
+    });
+}
+
+/// Renders the Moorings panel: leaving fleet ships here, taking them back, and the registry of every mooring.
+fn render_moorings_panel(
+    ui: &mut egui::Ui,
+    player_fleet: &PlayerFleet,
+    moored: &MooredShips,
+    here: Option<IVec2>,
+    player_gold: u32,
+    mooring_events: &mut EventWriter<MooringEvent>,
+) {
+    ui.heading("Moorings");
+    ui.label(format!(
+        "Leave a ship here for {}g and she stays behind when you sail. The harbor keeps her in slow repair until you come back for her.",
+        MOORING_FEE
+    ));
+    ui.add_space(10.0);
+
+    let Some(here) = here else {
+        ui.label("⚠ No port data available");
+        return;
+    };
+
+    ui.group(|ui| {
+        ui.label("Your fleet:");
+        if player_fleet.ships.is_empty() {
+            ui.weak("No ships besides the flagship.");
+        }
+        for (index, ship) in player_fleet.ships.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.strong(&ship.name);
+                ui.label(format!("Hull {:.0}/{:.0}", ship.hull_health, ship.max_hull_health));
+                let button = egui::Button::new(format!("Moor ({}g)", MOORING_FEE)).small();
+                if ui.add_enabled(player_gold >= MOORING_FEE, button).clicked() {
+                    mooring_events.send(MooringEvent { action: MooringAction::Moor(index) });
                 }
-            };
-            
-            let intel_data = IntelData {
-                intel_type,
-                source_port: Some(port_entity),
-                target_entity: None,
-                revealed_positions: positions,
-                route_waypoints: Vec::new(),
-                description,
-                purchase_cost: cost,
-            };
-            
-            commands.spawn((
-                Intel,
-                intel_data,
-                TavernIntel,
-                IntelExpiry::new(current_tick),
-            ));
+            });
         }
-    }
+    });
+
+    ui.add_space(10.0);
+    ui.group(|ui| {
+        ui.label("Moored here:");
+        let moored_here = moored.at(here);
+        if moored_here.is_empty() {
+            ui.weak("None of your ships lie here.");
+        }
+        for (index, mooring) in moored_here.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.strong(&mooring.ship.name);
+                ui.label(format!("Hull {:.0}/{:.0}", mooring.ship.hull_health, mooring.ship.max_hull_health));
+                ui.weak(format!("since Day {}", mooring.moored_on));
+                if ui.small_button("Retrieve").clicked() {
+                    mooring_events.send(MooringEvent { action: MooringAction::Retrieve(index) });
+                }
+            });
+        }
+    });
+
+    // Registry of every mooring, this port's included
+    ui.add_space(10.0);
+    ui.collapsing(format!("Registry ({} moored)", moored.len()), |ui| {
+        if moored.is_empty() {
+            ui.weak("No ships moored anywhere.");
+            return;
+        }
+        egui::Grid::new("mooring_registry")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Ship");
+                ui.strong("Port");
+                ui.strong("Hull");
+                ui.strong("Since");
+                ui.end_row();
+                for (key, mooring) in moored.all() {
+                    ui.label(&mooring.ship.name);
+                    if key == here {
+                        ui.label(format!("{} (here)", mooring.port_name));
+                    } else {
+                        ui.label(&mooring.port_name);
+                    }
+                    ui.label(format!("{:.0}/{:.0}", mooring.ship.hull_health, mooring.ship.max_hull_health));
+                    ui.label(format!("Day {}", mooring.moored_on));
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+/// Spawns 2-4 fresh intel items for sale in the tavern at `port_entity` (keyed `offered_at`).
+fn spawn_tavern_intel(
+    commands: &mut Commands,
+    rng: &mut impl rand::Rng,
+    port_entity: Entity,
+    offered_at: IVec2,
+    current_tick: u32,
+) -> usize {
+    let num_intel = rng.gen_range(2..=4);
     
-    info!("Generated tavern intel for {} ports", ports.len());
+    for _ in 0..num_intel {
+        // Random intel type with weighted distribution
+        let intel_type = match rng.gen_range(0..10) {
+            0..=3 => IntelType::Rumor,           // 40% rumors
+            4..=5 => IntelType::MapReveal,       // 20% map reveals
+            6..=7 => IntelType::ShipRoute,       // 20% ship routes
+            8 => IntelType::TreasureLocation,    // 10% treasure
+            _ => IntelType::FleetPosition,       // 10% fleet positions
+        };
+        
+        // Generate description and cost based on type
+        let (description, cost, positions) = match intel_type {
+            IntelType::Rumor => {
+                let rumors = [
+                    "A merchant fleet was spotted heading north",
+                    "Pirates have been raiding the eastern waters",
+                    "A storm sank a treasure ship last week",
+                    "The navy is patrolling near the southern islands",
+                ];
+                let desc = rumors[rng.gen_range(0..rumors.len())].to_string();
+                (desc, rng.gen_range(10..=30), Vec::new())
+            }
+            IntelType::MapReveal => {
+                // Reveal a random area of the map
+                let center_x = rng.gen_range(50..450);
+                let center_y = rng.gen_range(50..450);
+                let radius = rng.gen_range(5..=15);
+                let mut positions = Vec::new();
+                for dx in -radius..=radius {
+                    for dy in -radius..=radius {
+                        if dx * dx + dy * dy <= radius * radius {
+                            positions.push(IVec2::new(center_x + dx, center_y + dy));
+                        }
+                    }
+                }
+                let desc = format!("Map of a region ({} tiles)", positions.len());
+                (desc, rng.gen_range(30..=80), positions)
+            }
+            IntelType::ShipRoute => {
+                let desc = "Trade route between nearby ports".to_string();
+                (desc, rng.gen_range(40..=100), Vec::new())
+            }
+            IntelType::TreasureLocation => {
+                let x = rng.gen_range(50..450);
+                let y = rng.gen_range(50..450);
+                let desc = "Location of hidden treasure".to_string();
+                (desc, rng.gen_range(80..=200), vec![IVec2::new(x, y)])
+            }
+            IntelType::FleetPosition => {
+                let x = rng.gen_range(50..450);
+                let y = rng.gen_range(50..450);
+                let desc = "Last known position of a fleet".to_string();
+                (desc, rng.gen_range(50..=120), vec![IVec2::new(x, y)])
+            }
+            IntelType::PortInventory => {
+                let desc = "Port market prices".to_string();
+                (desc, rng.gen_range(20..=50), Vec::new())
+            }
+        };
+        
+        let intel_data = IntelData {
+            intel_type,
+            source_port: Some(port_entity),
+            target_entity: None,
+            revealed_positions: positions,
+            route_waypoints: Vec::new(),
+            description,
+            purchase_cost: cost,
+        };
+        
+        commands.spawn((
+            Intel,
+            intel_data,
+            TavernIntel,
+            IntelExpiry::new(current_tick),
+            OfferedAt(offered_at),
+        ));
+    }
+    num_intel
 }
 
 /// System that processes intel purchases from the tavern.
@@ -812,7 +1771,10 @@ fn intel_purchase_system(
     mut events: EventReader<IntelAcquiredEvent>,
     mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
     intel_query: Query<&IntelData, With<Intel>>,
+    current_port: Res<CurrentPort>,
+    port_events: Res<PortEvents>,
 ) {
+    let tavern_rate = port_events.at_port(current_port.position).map_or(1.0, |event| event.kind.tavern_rate());
     for event in events.read() {
         // Get intel data to check cost
         let Ok(intel_data) = intel_query.get(event.intel_entity) else {
@@ -825,16 +1787,102 @@ fn intel_purchase_system(
             continue;
         };
         
-        if gold.spend(intel_data.purchase_cost) {
+        let cost = event_price(intel_data.purchase_cost, tavern_rate);
+        if gold.spend(cost) {
             info!(
                 "Purchased intel for {} gold: {}",
-                intel_data.purchase_cost, intel_data.description
+                cost, intel_data.description
             );
         } else {
             warn!(
                 "Intel purchase failed: Insufficient gold ({} < {})",
-                gold.0, intel_data.purchase_cost
+                gold.0, cost
             );
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrow_keys_move_through_market_rows() {
+        let down = PortKeys { down: true, ..default() };
+        let up = PortKeys { up: true, ..default() };
+        assert_eq!(down.move_focus(0, 3), 1);
+        assert_eq!(down.move_focus(2, 3), 2);
+        assert_eq!(up.move_focus(0, 3), 0);
+        // A row left over from a bigger market is pulled back in
+        assert_eq!(PortKeys::default().move_focus(7, 3), 2);
+        assert_eq!(down.move_focus(4, 0), 0);
+
+        assert_eq!(PortKeys { left: true, ..default() }.nudge(), -1);
+        assert_eq!(PortKeys { right: true, shift: true, ..default() }.nudge(), 10);
+        assert_eq!(PortKeys { left: true, right: true, ..default() }.nudge(), 0);
+    }
+
+    #[test]
+    fn test_unseen_port_is_due() {
+        let schedule = PortBoardSchedule::default();
+        let key = port_key(Vec2::new(64.0, -128.0));
+        assert!(schedule.contracts_due(key, 1));
+        assert!(schedule.intel_due(key, 1));
+    }
+
+    #[test]
+    fn test_boards_refresh_on_their_own_cooldowns() {
+        let mut schedule = PortBoardSchedule::default();
+        let key = port_key(Vec2::new(64.0, -128.0));
+        schedule.mark_contracts(key, 5);
+        schedule.mark_intel(key, 5);
+
+        assert!(!schedule.contracts_due(key, 5));
+        assert!(!schedule.intel_due(key, 5));
+        assert!(schedule.intel_due(key, 5 + INTEL_REFRESH_DAYS));
+        assert!(!schedule.contracts_due(key, 5 + CONTRACT_REFRESH_DAYS - 1));
+        assert!(schedule.contracts_due(key, 5 + CONTRACT_REFRESH_DAYS));
+
+        // Other ports keep their own schedule
+        let other = port_key(Vec2::new(640.0, 0.0));
+        assert!(schedule.contracts_due(other, 5));
+    }
+
+    #[test]
+    fn test_a_ticket_buys_from_the_ports_kept_market() {
+        use crate::components::cargo::GoodType;
+        use crate::resources::WorldClock;
+
+        let position = Vec2::new(640.0, -320.0);
+        let key = port_key(position);
+        let mut inventory = Inventory::new();
+        inventory.set_good(GoodType::Rum, 100, 12.0);
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::state::app::StatesPlugin))
+            .insert_state(GameState::Port)
+            .add_event::<BulkTradeEvent>()
+            .add_event::<TradeExecutedEvent>()
+            .add_event::<TradeSettledEvent>()
+            .insert_resource(CurrentPort { position: Some(position), ..default() })
+            .init_resource::<FactionRegistry>()
+            .init_resource::<PortEvents>()
+            .insert_resource(WorldClock { day: 1, hour: 12, tick: 0 })
+            .insert_resource(PortMarkets {
+                markets: [(key, PortMarket { inventory, produces: None, consumes: None })].into(),
+            })
+            .add_systems(
+                Update,
+                (bulk_trade_system, trade_execution_system).chain().run_if(in_state(GameState::Port)),
+            );
+        let player = app.world_mut().spawn((Player, Ship, Cargo::new(50), Gold(1000))).id();
+
+        app.world_mut().send_event(BulkTradeEvent { port: key, orders: vec![(GoodType::Rum, 30)] });
+        app.update();
+
+        let cost = 30 * TradeTerms::default().buy_price(12.0);
+        assert_eq!(app.world().get::<Cargo>(player).unwrap().get(GoodType::Rum), 30);
+        assert_eq!(app.world().get::<Gold>(player).unwrap().0, 1000 - cost);
+        let market = app.world().resource::<PortMarkets>().get(key).unwrap();
+        assert_eq!(market.inventory.get_good(&GoodType::Rum).map(|item| item.quantity), Some(70));
+    }
+}
@@ -0,0 +1,227 @@
+//! Composite port sprites on the world map.
+//!
+//! Each port is drawn from a handful of child sprites rather than one baked
+//! icon: waterfront houses in number by its `PortSize`, a fort for a city, a
+//! flag in its faction's colours, bunting while it holds a festival, grey
+//! idle quays while its dockworkers strike, and a boom across the harbour
+//! mouth while its flag has closed it to the player. The sync rebuilds a
+//! port's parts only when its `PortVisual` changes.
+
+use bevy::prelude::*;
+
+use crate::components::port::{Inventory, PortSize, PortStatus, PortVisual, PortVisualPart};
+use crate::components::{Faction, FactionId};
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::HighSeasPort;
+use crate::resources::{FactionRegistry, PortEventKind, PortEvents};
+
+/// Depth of port parts, over the coastline ink and under the fog of war.
+const PORT_PART_Z: f32 = -6.0;
+/// Houses to a row along the waterfront.
+const HOUSES_PER_ROW: usize = 4;
+/// Where the first house of the bottom row stands on the quay (world units,
+/// relative to the port, as are all the offsets below).
+const QUAY_CORNER: Vec2 = Vec2::new(-21.0, -12.0);
+/// A house's walls.
+const HOUSE_SIZE: Vec2 = Vec2::new(10.0, 7.0);
+/// Gap between neighbouring houses in a row.
+const HOUSE_GAP: f32 = 4.0;
+/// How far every other row is shifted along, so the rows stagger.
+const ROW_STAGGER: f32 = 5.0;
+/// Spacing between rows of houses.
+const ROW_SPACING: f32 = 10.0;
+/// A roof, overhanging its walls, and how far above the foot of the walls it sits.
+const ROOF_SIZE: Vec2 = Vec2::new(12.0, 4.0);
+const ROOF_RISE: f32 = 5.0;
+/// A city's fort and the dark line of its battlements.
+const FORT_OFFSET: Vec2 = Vec2::new(-22.0, -24.0);
+const FORT_SIZE: Vec2 = Vec2::new(18.0, 10.0);
+const BATTLEMENTS_OFFSET: Vec2 = Vec2::new(-22.0, -18.0);
+const BATTLEMENTS_SIZE: Vec2 = Vec2::new(20.0, 2.0);
+/// Festival bunting strung above the roofs: how many pennants, where the
+/// first hangs, their spacing, size, and height over the highest roof.
+const BUNTING_PENNANTS: usize = 7;
+const BUNTING_LEFT: f32 = -24.0;
+const BUNTING_SPACING: f32 = 8.0;
+const BUNTING_SIZE: Vec2 = Vec2::splat(3.0);
+const BUNTING_RISE: f32 = 3.0;
+/// The flagpole over the highest roof, and its flag, raised above it by the given heights.
+const FLAGPOLE_SIZE: Vec2 = Vec2::new(1.5, 16.0);
+const FLAGPOLE_RISE: f32 = 8.0;
+const FLAG_SIZE: Vec2 = Vec2::new(10.0, 6.0);
+const FLAG_OFFSET_X: f32 = 5.5;
+const FLAG_RISE: f32 = 13.0;
+/// The boom across the harbour mouth of a closed port.
+const BOOM_OFFSET: Vec2 = Vec2::new(10.0, -26.0);
+const BOOM_SIZE: Vec2 = Vec2::new(36.0, 3.0);
+
+const WALL_COLOR: [u8; 3] = [222, 206, 170];
+const ROOF_COLOR: [u8; 3] = [150, 72, 50];
+const IDLE_WALL_COLOR: [u8; 3] = [160, 156, 148];
+const IDLE_ROOF_COLOR: [u8; 3] = [104, 100, 96];
+const STONE_COLOR: [u8; 3] = [122, 112, 96];
+const INK_COLOR: [u8; 3] = [60, 40, 30];
+const BUNTING_COLORS: [[u8; 3]; 3] = [[200, 50, 40], [230, 190, 60], [50, 90, 180]];
+
+/// Plugin that composes port sprites from their faction, size and status.
+pub struct PortVisualsPlugin;
+
+impl Plugin for PortVisualsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, sync_port_visuals.run_if(in_state(GameState::HighSeas)));
+    }
+}
+
+/// One sprite of a port's composition, relative to the port.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PortPart {
+    pub offset: Vec2,
+    pub size: Vec2,
+    pub color: [u8; 3],
+}
+
+impl PortPart {
+    fn new(offset: Vec2, size: Vec2, color: [u8; 3]) -> Self {
+        Self { offset, size, color }
+    }
+}
+
+/// What a port shows, given whether it admits the player and any festival or
+/// strike under way. A closed harbour outranks a strike, a strike a festival.
+pub fn port_status(admits_player: bool, event: Option<PortEventKind>) -> PortStatus {
+    match (admits_player, event) {
+        (false, _) => PortStatus::Closed,
+        (true, Some(PortEventKind::Strike)) => PortStatus::Strike,
+        (true, Some(PortEventKind::Festival)) => PortStatus::Festival,
+        (true, None) => PortStatus::Open,
+    }
+}
+
+/// The sprites making up a port, back to front.
+pub fn port_parts(visual: &PortVisual) -> Vec<PortPart> {
+    let mut parts = Vec::new();
+    let (wall, roof) = if visual.status == PortStatus::Strike {
+        (IDLE_WALL_COLOR, IDLE_ROOF_COLOR)
+    } else {
+        (WALL_COLOR, ROOF_COLOR)
+    };
+
+    // Houses in staggered rows climbing from the quay
+    let houses = visual.size.buildings();
+    let mut top = QUAY_CORNER.y;
+    for i in 0..houses {
+        let row = i / HOUSES_PER_ROW;
+        let column = i % HOUSES_PER_ROW;
+        let x = QUAY_CORNER.x + column as f32 * (HOUSE_SIZE.x + HOUSE_GAP) + (row % 2) as f32 * ROW_STAGGER;
+        let y = QUAY_CORNER.y + row as f32 * ROW_SPACING;
+        parts.push(PortPart::new(Vec2::new(x, y), HOUSE_SIZE, wall));
+        parts.push(PortPart::new(Vec2::new(x, y + ROOF_RISE), ROOF_SIZE, roof));
+        top = top.max(y + HOUSE_SIZE.y);
+    }
+
+    if visual.size.has_fort() {
+        parts.push(PortPart::new(FORT_OFFSET, FORT_SIZE, STONE_COLOR));
+        parts.push(PortPart::new(BATTLEMENTS_OFFSET, BATTLEMENTS_SIZE, INK_COLOR));
+    }
+
+    if visual.status == PortStatus::Festival {
+        for (k, color) in BUNTING_COLORS.iter().cycle().take(BUNTING_PENNANTS).enumerate() {
+            let offset = Vec2::new(BUNTING_LEFT + k as f32 * BUNTING_SPACING, top + BUNTING_RISE);
+            parts.push(PortPart::new(offset, BUNTING_SIZE, *color));
+        }
+    }
+
+    // The flag over the highest roofs
+    parts.push(PortPart::new(Vec2::new(0.0, top + FLAGPOLE_RISE), FLAGPOLE_SIZE, INK_COLOR));
+    parts.push(PortPart::new(Vec2::new(FLAG_OFFSET_X, top + FLAG_RISE), FLAG_SIZE, visual.faction.flag_rgb()));
+
+    if visual.status == PortStatus::Closed {
+        parts.push(PortPart::new(BOOM_OFFSET, BOOM_SIZE, INK_COLOR));
+    }
+    parts
+}
+
+/// Rebuilds the sprites of every port whose faction, size or status has
+/// changed since they were last composed.
+fn sync_port_visuals(
+    mut commands: Commands,
+    ports: Query<(Entity, &Transform, &Faction, &Inventory, Option<&PortVisual>, Option<&Children>), With<HighSeasPort>>,
+    parts: Query<(), With<PortVisualPart>>,
+    port_events: Res<PortEvents>,
+    factions: Res<FactionRegistry>,
+) {
+    for (port, transform, faction, inventory, current, children) in &ports {
+        let visual = port_visual(faction.0, inventory, transform.translation.truncate(), &port_events, &factions);
+        if current == Some(&visual) {
+            continue;
+        }
+
+        for &child in children.into_iter().flatten() {
+            if parts.contains(child) {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+        commands.entity(port).insert(visual).with_children(|parent| {
+            for (i, part) in port_parts(&visual).iter().enumerate() {
+                let [r, g, b] = part.color;
+                parent.spawn((
+                    Sprite::from_color(Color::srgb_u8(r, g, b), part.size),
+                    // Later parts sit a hair above earlier ones
+                    Transform::from_translation(part.offset.extend(PORT_PART_Z + i as f32 * 0.001)),
+                    PortVisualPart,
+                ));
+            }
+        });
+    }
+}
+
+/// What the port flying `faction` at `position` should look like now.
+fn port_visual(
+    faction: FactionId,
+    inventory: &Inventory,
+    position: Vec2,
+    port_events: &PortEvents,
+    factions: &FactionRegistry,
+) -> PortVisual {
+    let admits_player = factions.standing(faction).allows_port_entry();
+    let event = port_events.at(position).map(|event| event.kind);
+    PortVisual {
+        faction,
+        size: PortSize::from_goods(inventory.goods.len()),
+        status: port_status(admits_player, event),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visual(size: PortSize, status: PortStatus) -> PortVisual {
+        PortVisual { faction: FactionId::NationA, size, status }
+    }
+
+    #[test]
+    fn test_closed_harbour_outranks_strike_and_festival() {
+        assert_eq!(port_status(false, Some(PortEventKind::Festival)), PortStatus::Closed);
+        assert_eq!(port_status(true, Some(PortEventKind::Strike)), PortStatus::Strike);
+        assert_eq!(port_status(true, Some(PortEventKind::Festival)), PortStatus::Festival);
+        assert_eq!(port_status(true, None), PortStatus::Open);
+    }
+
+    #[test]
+    fn test_parts_follow_size_faction_and_status() {
+        let cove = port_parts(&visual(PortSize::Cove, PortStatus::Open));
+        let city = port_parts(&visual(PortSize::City, PortStatus::Open));
+        assert!(city.len() > cove.len());
+
+        let flag = FactionId::NationA.flag_rgb();
+        assert!(cove.iter().any(|part| part.color == flag));
+        let league = PortVisual { faction: FactionId::NationB, ..visual(PortSize::Cove, PortStatus::Open) };
+        assert!(!port_parts(&league).iter().any(|part| part.color == flag));
+
+        let strike = port_parts(&visual(PortSize::Cove, PortStatus::Strike));
+        assert!(!strike.iter().any(|part| part.color == WALL_COLOR));
+        assert!(port_parts(&visual(PortSize::Cove, PortStatus::Festival)).len() > cove.len());
+        assert!(port_parts(&visual(PortSize::Cove, PortStatus::Closed)).len() > cove.len());
+    }
+}
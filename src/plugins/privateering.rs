@@ -0,0 +1,166 @@
+//! Letters of marque: privateering commissions posted at nations' ports.
+//!
+//! See `resources::privateering`. A nation that thinks well enough of the
+//! player keeps a commission against one of its rivals on each of its
+//! contract boards. Once accepted, the rival's ships are treated as hostile
+//! on the High Seas (`encounter_detection_system`), and each one the player
+//! is credited with sinking pays prize money and shifts reputation with both
+//! nations.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::contract::{AcceptedContract, Commission, Contract, ContractDetails, ContractProgress, ContractType};
+use crate::components::{port_key, Faction, Gold, OfferedAt, Player, Port, PortName, Ship};
+use crate::events::{ContractCompletedEvent, ShipDestroyedEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::PlayerContracts;
+use crate::resources::{
+    commission_rival, offers_commissions, ContractRewards, FactionRegistry, Journal, WorldClock,
+    COMMISSION_REPUTATION_REWARD, PRIZE_REPUTATION_GAIN, PRIZE_REPUTATION_LOSS,
+};
+use crate::systems::world_tick_system;
+
+/// Plugin for letters of marque.
+pub struct PrivateeringPlugin;
+
+impl Plugin for PrivateeringPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            post_commissions.after(world_tick_system).run_if(in_state(GameState::HighSeas)),
+        )
+            .add_systems(Update, (credit_prizes, complete_commissions.after(credit_prizes)));
+    }
+}
+
+type AcceptedCommissions<'w, 's> = Query<
+    'w,
+    's,
+    (&'static ContractDetails, &'static Commission, &'static mut ContractProgress),
+    (With<Contract>, With<AcceptedContract>),
+>;
+
+/// Keeps a letter of marque on the board of every port whose nation will issue one.
+/// Checked on the hour, so a withdrawn offer is replaced at the next one.
+fn post_commissions(
+    mut commands: Commands,
+    world_clock: Res<WorldClock>,
+    registry: Res<FactionRegistry>,
+    rewards: Res<ContractRewards>,
+    port_query: Query<(Entity, &Transform, &Faction, &PortName), With<Port>>,
+    contract_query: Query<(&ContractDetails, &OfferedAt), With<Contract>>,
+) {
+    if world_clock.tick != 0 {
+        return;
+    }
+    let mut rng = rand::thread_rng();
+    let current_tick = world_clock.total_ticks();
+
+    for (port_entity, transform, faction, name) in &port_query {
+        if !offers_commissions(faction.0, registry.reputation(faction.0)) {
+            continue;
+        }
+        let key = port_key(transform.translation.truncate());
+        let posted = contract_query
+            .iter()
+            .any(|(details, offered_at)| details.contract_type == ContractType::Privateer && offered_at.0 == key);
+        if posted {
+            continue;
+        }
+
+        let rival = commission_rival(faction.0, rng.gen());
+        let required = rng.gen_range(2..=4);
+        commands.spawn((
+            Contract,
+            ContractDetails::privateer(port_entity, rival, required, rewards.prize_money, current_tick).with_issuer(faction.0),
+            OfferedAt(key),
+            Commission {
+                rival,
+                required,
+                prize_money: rewards.prize_money,
+            },
+        ));
+        debug!("Port {} posted a letter of marque against {:?}", name.0, rival);
+    }
+}
+
+/// Pays prize money for each rival ship the player is credited with sinking,
+/// under the first accepted commission against her flag.
+fn credit_prizes(
+    mut events: EventReader<ShipDestroyedEvent>,
+    mut commissions: AcceptedCommissions,
+    mut registry: ResMut<FactionRegistry>,
+    mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    for event in events.read() {
+        if event.was_player || !event.player_credited {
+            continue;
+        }
+        let Some(victim) = event.faction else {
+            continue;
+        };
+        let Some((details, commission, mut progress)) =
+            commissions.iter_mut().find(|(_, commission, _)| commission.rival == victim)
+        else {
+            continue;
+        };
+
+        progress.prizes += 1;
+        if let Ok(mut gold) = player_query.get_single_mut() {
+            gold.add(commission.prize_money);
+        }
+        if let Some(issuer) = details.issuer {
+            registry.adjust_reputation(issuer, PRIZE_REPUTATION_GAIN);
+        }
+        registry.adjust_reputation(victim, -PRIZE_REPUTATION_LOSS);
+        journal.record(
+            &world_clock,
+            format!(
+                "Took the {} as a prize under letter of marque ({} of {}). Paid {} gold; {} will not forget it.",
+                event.name,
+                progress.prizes,
+                commission.required,
+                commission.prize_money,
+                victim.display_name()
+            ),
+        );
+    }
+}
+
+/// Closes commissions that have taken all the prizes they asked for.
+fn complete_commissions(
+    mut commands: Commands,
+    contract_query: Query<(Entity, &ContractDetails, &Commission, &ContractProgress), (With<AcceptedContract>, Changed<ContractProgress>)>,
+    mut registry: ResMut<FactionRegistry>,
+    mut player_contracts: ResMut<PlayerContracts>,
+    mut completion_events: EventWriter<ContractCompletedEvent>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    for (entity, details, commission, progress) in &contract_query {
+        if !commission.is_complete(progress) {
+            continue;
+        }
+        if let Some(issuer) = details.issuer {
+            registry.adjust_reputation(issuer, COMMISSION_REPUTATION_REWARD);
+        }
+        journal.record(
+            &world_clock,
+            format!(
+                "Letter of marque fulfilled: {} ships of {} taken for {}.",
+                progress.prizes,
+                commission.rival.display_name(),
+                details.issuer.map_or("the issuer", |issuer| issuer.display_name())
+            ),
+        );
+        completion_events.send(ContractCompletedEvent {
+            contract_entity: entity,
+            reward_gold: progress.prizes * commission.prize_money,
+        });
+        player_contracts.active.retain(|&e| e != entity);
+        commands.entity(entity).despawn_recursive();
+    }
+}
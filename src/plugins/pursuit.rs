@@ -0,0 +1,364 @@
+//! Chasing fleeing merchants on the High Seas.
+//!
+//! A hostile merchant on a trade route that comes within encounter range
+//! runs for it. If the player's ship is the faster, the encounter becomes a
+//! chase (see `resources::pursuit`) instead of a battle: the High Seas go
+//! into `SeaPursuit::Chasing`, and the world holds still while the player
+//! steers through the wind shifts and fires warning shots. A merchant whose
+//! nerve breaks heaves to and her own hold and purse are taken aboard, as
+//! far as there is room; she is `Plundered` and left to sail on. One that holds out turns
+//! to fight, and the battle is joined as for any other encounter. A merchant
+//! faster than the player is met as ever.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::patrol::{Plundered, Smuggler};
+use crate::components::weather::InWeather;
+use crate::components::{BountyHunter, Cargo, Faction, Gold, Health, Order, OrderQueue, Player, ShipClass, ShipType};
+use crate::events::CombatTriggeredEvent;
+use crate::plugins::core::GameState;
+use crate::plugins::pacing::StandsOff;
+use crate::plugins::settings::PauseMenu;
+use crate::plugins::worldmap::{
+    encounter_detection_system, EncounterCooldown, EncounterRange, HighSeasAI, HighSeasPlayer, Hostility,
+};
+use crate::resources::{
+    outsails, wind_on_line, Chase, ChaseCourse, Journal, PursuitOutcome, PursuitState, Quarry, ShipDefinitions, Wind,
+    WorldClock, CHASE_MAX_GAP, WARNING_SHOT_RANGE,
+};
+
+/// Plugin for chasing down fleeing merchants.
+pub struct PursuitPlugin;
+
+impl Plugin for PursuitPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PursuitState>()
+            .add_sub_state::<SeaPursuit>()
+            .add_systems(
+                Update,
+                sight_fleeing_merchant
+                    .before(encounter_detection_system)
+                    .run_if(in_state(SeaPursuit::Sailing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    run_pursuit,
+                    pursuit_ui.after(EguiSet::InitContexts).after(run_pursuit),
+                    heave_to.after(pursuit_ui),
+                    turn_to_fight.after(pursuit_ui),
+                )
+                    .run_if(in_state(SeaPursuit::Chasing)),
+            )
+            .add_systems(OnEnter(SeaPursuit::Chasing), hold_the_world)
+            .add_systems(OnExit(SeaPursuit::Chasing), end_pursuit);
+    }
+}
+
+/// Whether the player is chasing a merchant. Only exists on the High Seas,
+/// so leaving them mid-chase (a quickload) calls the chase off.
+#[derive(SubStates, Default, Clone, Eq, PartialEq, Debug, Hash)]
+#[source(GameState = GameState::HighSeas)]
+pub enum SeaPursuit {
+    #[default]
+    Sailing,
+    Chasing,
+}
+
+/// Merchants on the High Seas that could run from the player.
+type Merchants<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static Faction,
+        Option<&'static Name>,
+        Option<&'static ShipType>,
+        Option<&'static ShipClass>,
+        &'static OrderQueue,
+        Has<Smuggler>,
+    ),
+    (With<HighSeasAI>, Without<Plundered>, Without<BountyHunter>, Without<StandsOff>),
+>;
+
+/// What a chase is run in: the wind, and the ship classes that set how fast
+/// each side sails.
+#[derive(SystemParam)]
+struct ChaseConditions<'w> {
+    wind: Res<'w, Wind>,
+    ships: Res<'w, ShipDefinitions>,
+}
+
+/// Gives chase to the nearest hostile merchant in encounter range, if the
+/// player's ship can outsail her. Runs before the encounter check, which
+/// stands down while the chase is on.
+fn sight_fleeing_merchant(
+    mut pursuit: ResMut<PursuitState>,
+    mut next_state: ResMut<NextState<SeaPursuit>>,
+    mut encounter_cooldown: ResMut<EncounterCooldown>,
+    hostility: Hostility,
+    encounter_range: EncounterRange,
+    conditions: ChaseConditions,
+    player_query: Query<(&Transform, &ShipType, Option<&ShipClass>, &Health, Has<InWeather>), (With<Player>, With<HighSeasPlayer>)>,
+    merchants: Merchants,
+) {
+    if encounter_cooldown.active {
+        return;
+    }
+    // Nobody finds a ship hidden in a squall
+    let Ok((player_transform, ship_type, class, health, false)) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    let Some((entity, position, faction, name, her_speed, smuggler)) = merchants
+        .iter()
+        .filter(|(_, _, _, _, _, _, orders, _)| matches!(orders.current(), Some(Order::TradeRoute { .. })))
+        .filter(|(_, _, faction, ..)| hostility.is_hostile(faction.0))
+        .map(|(entity, transform, faction, name, her_type, her_class, _, smuggler)| {
+            let her_speed = conditions.ships.speed(her_class, her_type.copied().unwrap_or_default());
+            (entity, transform.translation.truncate(), faction.0, name, her_speed, smuggler)
+        })
+        .filter(|(_, position, ..)| position.distance(player_pos) <= encounter_range.radius())
+        .min_by(|a, b| a.1.distance_squared(player_pos).total_cmp(&b.1.distance_squared(player_pos)))
+    else {
+        return;
+    };
+
+    let player_speed = conditions.ships.speed(class, *ship_type) * health.sail_power();
+    let quarry_speed = her_speed * encounter_range.balance.ai_speed.max_share;
+    if !outsails(player_speed, quarry_speed) {
+        return;
+    }
+
+    let quarry = Quarry {
+        entity,
+        name: name.map_or_else(|| "merchantman".to_string(), |name| name.to_string()),
+        faction,
+        speed: quarry_speed,
+        smuggler,
+    };
+    info!("{} runs for it - giving chase", quarry.name);
+    pursuit.chase = Some(Chase::new(quarry, player_speed, position - player_pos, *conditions.wind, &mut rand::thread_rng()));
+    next_state.set(SeaPursuit::Chasing);
+    encounter_cooldown.active = true;
+}
+
+/// Stops the world for the chase, unless something else already has.
+fn hold_the_world(mut pursuit: ResMut<PursuitState>, mut virtual_time: ResMut<Time<Virtual>>) {
+    pursuit.stopped_world = !virtual_time.is_paused();
+    if pursuit.stopped_world {
+        virtual_time.pause();
+    }
+}
+
+/// Starts the world again once the chase is over, however it ended, if the
+/// chase stopped it. With the pause menu open, the menu starts it on resuming.
+fn end_pursuit(
+    mut pursuit: ResMut<PursuitState>,
+    mut pause_menu: ResMut<PauseMenu>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    pursuit.chase = None;
+    if std::mem::take(&mut pursuit.stopped_world) {
+        if pause_menu.open {
+            pause_menu.hold_world();
+        } else {
+            virtual_time.unpause();
+        }
+    }
+}
+
+/// Runs the chase on real time while the world holds still. The chase waits
+/// while the pause menu is open.
+fn run_pursuit(real_time: Res<Time<Real>>, pause_menu: Res<PauseMenu>, mut pursuit: ResMut<PursuitState>) {
+    if pause_menu.open {
+        return;
+    }
+    let Some(chase) = pursuit.chase.as_mut() else {
+        return;
+    };
+    chase.step(real_time.delta_secs(), &mut rand::thread_rng());
+}
+
+/// The chase: her lead, the wind, the helm and the bow gun.
+fn pursuit_ui(mut contexts: EguiContexts, mut pursuit: ResMut<PursuitState>) {
+    let Some(chase) = pursuit.chase.as_mut() else {
+        return;
+    };
+
+    egui::Window::new("⛵ Chase!")
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 120.0])
+        .resizable(false)
+        .collapsible(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.heading(format!("{} ({})", chase.quarry.name, chase.quarry.faction.display_name()));
+            ui.label(format!("She leads by {:.1} cables. Wind {}.", chase.gap, wind_on_line(&chase.wind, chase.line)));
+            ui.add(egui::ProgressBar::new(1.0 - chase.gap / CHASE_MAX_GAP).text(format!(
+                "{} {:.1} cables a minute",
+                if chase.closing_rate() >= 0.0 { "Gaining" } else { "Losing" },
+                chase.closing_rate().abs() * 60.0
+            )));
+
+            ui.separator();
+            ui.label("Steer:");
+            ui.horizontal(|ui| {
+                for course in ChaseCourse::ALL {
+                    if ui.selectable_label(chase.course == course, course.name()).clicked() {
+                        chase.course = course;
+                    }
+                }
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Her nerve");
+                ui.add(egui::ProgressBar::new(chase.resolve.clamp(0.0, 1.0)));
+            });
+            if ui
+                .add_enabled(chase.can_fire_warning_shot(), egui::Button::new("💥 Warning shot across her bows"))
+                .clicked()
+            {
+                chase.warning_shot();
+            }
+            if chase.gap > WARNING_SHOT_RANGE {
+                ui.weak(format!("Close to within {:.0} cables to fire a warning shot.", WARNING_SHOT_RANGE));
+            } else if chase.reload > 0.0 {
+                ui.weak(format!("Reloading the bow gun ({:.0}s)", chase.reload));
+            }
+            ui.weak(format!("She will turn to fight in {:.0}s.", chase.seconds_left()));
+        });
+}
+
+/// Takes the hold of a merchant that has heaved to, and lets her go.
+fn heave_to(
+    mut commands: Commands,
+    mut pursuit: ResMut<PursuitState>,
+    mut next_state: ResMut<NextState<SeaPursuit>>,
+    mut encounter_cooldown: ResMut<EncounterCooldown>,
+    player_query: Query<Entity, (With<Player>, With<HighSeasPlayer>)>,
+    mut holds: Query<(&mut Cargo, &mut Gold)>,
+    mut journal: ResMut<Journal>,
+    world_clock: Res<WorldClock>,
+) {
+    let Some(chase) = pursuit.chase.take_if(|chase| chase.outcome == Some(PursuitOutcome::HeaveTo)) else {
+        return;
+    };
+    next_state.set(SeaPursuit::Sailing);
+    encounter_cooldown.active = false;
+    commands.entity(chase.quarry.entity).try_insert(Plundered);
+
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let Ok([(mut cargo, mut gold), (mut her_cargo, mut her_gold)]) = holds.get_many_mut([player, chase.quarry.entity]) else {
+        journal.record(&world_clock, format!("The {} heaved to after a chase, but had nothing aboard worth taking.", chase.quarry.name));
+        return;
+    };
+    let purse = std::mem::take(&mut her_gold.0);
+    gold.add(purse);
+    let mut taken = 0;
+    for (good, quantity) in her_cargo.goods.clone() {
+        let stowed = cargo.add(good, quantity);
+        her_cargo.remove(good, stowed);
+        taken += stowed;
+    }
+    let left = her_cargo.total_units();
+
+    let mut entry = format!(
+        "The {} heaved to after a chase and gave up her hold: {} gold and {} tons of cargo.",
+        chase.quarry.name, purse, taken
+    );
+    if left > 0 {
+        entry.push_str(&format!(" {} tons were left aboard her for want of room.", left));
+    }
+    journal.record(&world_clock, entry);
+}
+
+/// A merchant that holds out to the end turns to fight.
+fn turn_to_fight(
+    mut pursuit: ResMut<PursuitState>,
+    mut next_state: ResMut<NextState<SeaPursuit>>,
+    mut combat_events: EventWriter<CombatTriggeredEvent>,
+    mut journal: ResMut<Journal>,
+    world_clock: Res<WorldClock>,
+) {
+    let Some(chase) = pursuit.chase.take_if(|chase| chase.outcome == Some(PursuitOutcome::Fight)) else {
+        return;
+    };
+    next_state.set(SeaPursuit::Sailing);
+    journal.record(&world_clock, format!("The {} would not heave to, and turned to fight.", chase.quarry.name));
+    combat_events.send(CombatTriggeredEvent {
+        enemy_entity: chase.quarry.entity,
+        enemy_faction: chase.quarry.faction,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{FactionId, GoodType};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_a_merchant_heaving_to_gives_up_her_own_hold() {
+        let mut app = App::new();
+        app.init_resource::<PursuitState>()
+            .init_resource::<NextState<SeaPursuit>>()
+            .init_resource::<EncounterCooldown>()
+            .init_resource::<Journal>()
+            .init_resource::<WorldClock>()
+            .add_systems(Update, heave_to);
+
+        let mut hold = Cargo::new(40);
+        hold.add(GoodType::Rum, 10);
+        app.world_mut().spawn((Player, HighSeasPlayer, hold, Gold(5)));
+        let mut her_hold = Cargo::new(100);
+        her_hold.add(GoodType::Sugar, 45);
+        let quarry = app.world_mut().spawn((her_hold, Gold(70))).id();
+
+        let mut chase = Chase::new(
+            Quarry { entity: quarry, name: "Dorothea".into(), faction: FactionId::NationA, speed: 200.0, smuggler: false },
+            300.0,
+            Vec2::Y,
+            Wind::default(),
+            &mut rand::rngs::StdRng::seed_from_u64(1),
+        );
+        chase.outcome = Some(PursuitOutcome::HeaveTo);
+        app.world_mut().resource_mut::<PursuitState>().chase = Some(chase);
+        app.update();
+
+        // Her purse and as much sugar as there is room for; the rest stays aboard her
+        let (cargo, gold) = app.world_mut().query_filtered::<(&Cargo, &Gold), With<Player>>().single(app.world());
+        assert_eq!((cargo.goods.get(&GoodType::Sugar), gold.0), (Some(&30), 75));
+        let her = app.world().entity(quarry);
+        assert_eq!((her.get::<Cargo>().unwrap().total_units(), her.get::<Gold>().unwrap().0), (15, 0));
+        assert!(her.contains::<Plundered>());
+        assert!(!app.world().resource::<PursuitState>().is_chasing());
+        assert!(matches!(app.world().resource::<NextState<SeaPursuit>>(), NextState::Pending(SeaPursuit::Sailing)));
+    }
+
+    #[test]
+    fn test_a_chase_only_starts_the_world_it_stopped() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<PursuitState>();
+        world.init_resource::<PauseMenu>();
+        world.init_resource::<Time<Virtual>>();
+
+        world.run_system_once(hold_the_world).unwrap();
+        assert!(world.resource::<Time<Virtual>>().is_paused());
+        world.run_system_once(end_pursuit).unwrap();
+        assert!(!world.resource::<Time<Virtual>>().is_paused());
+
+        // Already stopped by something else, say a sea event: that keeps it
+        world.resource_mut::<Time<Virtual>>().pause();
+        world.run_system_once(hold_the_world).unwrap();
+        world.run_system_once(end_pursuit).unwrap();
+        assert!(world.resource::<Time<Virtual>>().is_paused());
+    }
+}
@@ -0,0 +1,201 @@
+//! Collisions and rams between ships in combat.
+//!
+//! Ships that run into each other both take hull damage by how hard they
+//! met: the closing speed is read from the contact impulse, and anything
+//! under `COLLISION_MIN_SPEED` is no more than a scrape. A ship that strikes
+//! another with her bow rams her. The rammed ship takes `RAM_BONUS` times the
+//! damage, while the rammer's stout stem takes only `RAMMER_SHARE` of it.
+//! A ram fouls the two ships together: they drift as one until they work
+//! free (`Entangled`), and the player may board a ship fouled with hers
+//! whatever their speed (see `plugins::boarding`).
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::components::{Entangled, Health, Ship, TargetComponent, WaterIntake};
+use crate::events::ShipHitEvent;
+use crate::plugins::core::GameState;
+use crate::systems::record_ship_hits;
+
+/// Closing speed below which ships only scrape (world units per second).
+pub const COLLISION_MIN_SPEED: f32 = 40.0;
+/// Hull damage for each unit of closing speed over `COLLISION_MIN_SPEED`.
+pub const COLLISION_DAMAGE_PER_SPEED: f32 = 0.1;
+/// Half-width of the arc off the bow a ship rams within (radians).
+pub const RAM_ARC: f32 = 0.45;
+/// Damage multiplier on the ship taking a ram.
+pub const RAM_BONUS: f32 = 2.5;
+/// Share of the damage the rammer's own stem takes.
+pub const RAMMER_SHARE: f32 = 0.5;
+/// Water intake rate opened per point of collision damage, as for shot.
+const LEAK_PER_DAMAGE: f32 = 0.1;
+
+/// Plugin for ship-to-ship collision damage and rams.
+pub struct RammingPlugin;
+
+impl Plugin for RammingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                ship_collision_system.before(record_ship_hits),
+                drift_entangled_ships.after(ship_collision_system),
+                work_free_system.after(drift_entangled_ships),
+            )
+                .run_if(in_state(GameState::Combat)),
+        );
+    }
+}
+
+/// Mass that takes the blow when two bodies meet (the reduced mass).
+pub fn reduced_mass(first: f32, second: f32) -> f32 {
+    if first + second <= 0.0 {
+        return 0.0;
+    }
+    first * second / (first + second)
+}
+
+/// Hull damage from meeting at `closing_speed`, before any ram.
+pub fn collision_damage(closing_speed: f32) -> f32 {
+    (closing_speed - COLLISION_MIN_SPEED).max(0.0) * COLLISION_DAMAGE_PER_SPEED
+}
+
+/// Whether a ship heading along `forward` strikes with her bow at a ship
+/// lying `to_other` from her.
+pub fn strikes_bow_on(forward: Vec2, to_other: Vec2) -> bool {
+    forward.angle_to(to_other).abs() <= RAM_ARC
+}
+
+/// Damage to each of two ships meeting for `damage`, given which of them
+/// struck bow-on. Stem to stem, neither has the better of it.
+pub fn impact_damage(damage: f32, first_rams: bool, second_rams: bool) -> (f32, f32) {
+    match (first_rams, second_rams) {
+        (true, false) => (damage * RAMMER_SHARE, damage * RAM_BONUS),
+        (false, true) => (damage * RAM_BONUS, damage * RAMMER_SHARE),
+        _ => (damage, damage),
+    }
+}
+
+/// Ships that meet take hull damage on both sides, and a ram fouls them together.
+fn ship_collision_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<Collision>,
+    mut ships: Query<
+        (&Transform, &Mass, &mut Health, Option<&mut WaterIntake>),
+        With<Ship>,
+    >,
+    mut ship_hit_events: EventWriter<ShipHitEvent>,
+) {
+    for Collision(contacts) in collision_events.read() {
+        // Only the blow as they meet; ships lying against each other do no more harm
+        if contacts.during_previous_frame {
+            continue;
+        }
+        let (first, second) = (contacts.entity1, contacts.entity2);
+        let Ok([mut a, mut b]) = ships.get_many_mut([first, second]) else {
+            continue;
+        };
+
+        let closing_speed = contacts.total_normal_impulse / reduced_mass(a.1 .0, b.1 .0).max(f32::EPSILON);
+        let damage = collision_damage(closing_speed);
+        if damage <= 0.0 {
+            continue;
+        }
+
+        let first_pos = a.0.translation.truncate();
+        let second_pos = b.0.translation.truncate();
+        let first_rams = strikes_bow_on((a.0.rotation * Vec3::Y).truncate(), second_pos - first_pos);
+        let second_rams = strikes_bow_on((b.0.rotation * Vec3::Y).truncate(), first_pos - second_pos);
+        let (first_damage, second_damage) = impact_damage(damage, first_rams, second_rams);
+        let hit_position = (first_pos + second_pos) / 2.0;
+
+        for (entity, other, damage, ship) in [(first, second, first_damage, &mut a), (second, first, second_damage, &mut b)] {
+            let (_, _, health, water_intake) = ship;
+            health.hull -= damage;
+
+            // Started planks let the sea in
+            let leak = damage * LEAK_PER_DAMAGE;
+            if let Some(intake) = water_intake {
+                intake.increase_rate(leak);
+            } else {
+                commands.entity(entity).try_insert(WaterIntake::new(leak));
+            }
+
+            ship_hit_events.send(ShipHitEvent {
+                ship_entity: entity,
+                hit_position,
+                damage,
+                attacker: other,
+                component: TargetComponent::Hull,
+            });
+        }
+
+        if first_rams || second_rams {
+            commands.entity(first).try_insert(Entangled::new(second));
+            commands.entity(second).try_insert(Entangled::new(first));
+            info!(
+                "Rammed! {:.0} and {:.0} hull damage - the ships are fouled together",
+                first_damage, second_damage
+            );
+        } else {
+            info!("Ships collide: {:.0} and {:.0} hull damage", first_damage, second_damage);
+        }
+    }
+}
+
+/// Fouled ships drift as one.
+fn drift_entangled_ships(mut ships: Query<(Entity, &mut LinearVelocity, &Entangled)>) {
+    let pairs: Vec<(Entity, Entity)> = ships
+        .iter()
+        .filter(|(entity, _, entangled)| *entity < entangled.with)
+        .map(|(entity, _, entangled)| (entity, entangled.with))
+        .collect();
+    for (first, second) in pairs {
+        let Ok([(_, mut first_velocity, _), (_, mut second_velocity, _)]) = ships.get_many_mut([first, second]) else {
+            continue;
+        };
+        let drift = (first_velocity.0 + second_velocity.0) / 2.0;
+        first_velocity.0 = drift;
+        second_velocity.0 = drift;
+    }
+}
+
+/// Fouled ships work free once their time is up, or when the other is gone.
+fn work_free_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut ships: Query<(Entity, &mut Entangled)>,
+    others: Query<(), With<Ship>>,
+) {
+    for (entity, mut entangled) in &mut ships {
+        if entangled.timer.tick(time.delta()).finished() || !others.contains(entangled.with) {
+            commands.entity(entity).remove::<Entangled>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrapes_do_no_harm() {
+        assert_eq!(collision_damage(COLLISION_MIN_SPEED), 0.0);
+        assert!(collision_damage(COLLISION_MIN_SPEED + 100.0) > 0.0);
+        // Two equal ships meeting head on share the blow
+        assert_eq!(reduced_mass(1000.0, 1000.0), 500.0);
+    }
+
+    #[test]
+    fn test_ram_hurts_the_rammed_ship_more() {
+        // Bow pointed straight at her
+        assert!(strikes_bow_on(Vec2::Y, Vec2::new(0.1, 1.0)));
+        // Struck broadside-to
+        assert!(!strikes_bow_on(Vec2::X, Vec2::Y));
+
+        let (rammer, rammed) = impact_damage(10.0, true, false);
+        assert!(rammed > 10.0 && rammer < 10.0);
+        assert_eq!(impact_damage(10.0, false, true), (rammed, rammer));
+        assert_eq!(impact_damage(10.0, true, true), (10.0, 10.0));
+    }
+}
@@ -0,0 +1,182 @@
+//! Shipyard refits: buying upgrades and moving them between hulls at port.
+//!
+//! Upgrades are items in `UpgradeInventory` rather than permanent changes to a
+//! ship. Their bonuses are applied to the flagship when she is first spawned
+//! (afterwards she is spawned from `RunSnapshot` with them already in), and
+//! to fleet ships from `ShipData::upgrades` in `spawn_player_fleet`. Hull and
+//! cargo bonuses go into `Health` and `Cargo`; reload and speed bonuses ride
+//! on the ship as `Fittings`. The Docks' shipwright buys and fits an upgrade
+//! to the flagship in one job (`RefitAction::Commission`).
+
+use bevy::prelude::*;
+
+use crate::components::cargo::{Cargo, Gold};
+use crate::components::health::Health;
+use crate::components::ship::{Player, Ship};
+use crate::components::upgrade::{upgrades_cargo_bonus, upgrades_hull_bonus, Fittings, UpgradeKind, UPGRADE_SLOTS};
+use crate::events::{RefitAction, RefitEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::CurrentPort;
+use crate::resources::{DockQueue, DockService, PlayerFleet, PortEvents, RefitTarget, RunSnapshot, UpgradeInventory, WorldClock, REFIT_FEE, REFIT_HOURS};
+
+/// Plugin for shipyard refits.
+pub struct RefitPlugin;
+
+impl Plugin for RefitPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UpgradeInventory>()
+            .add_event::<RefitEvent>()
+            .add_systems(Update, (
+                apply_flagship_upgrades,
+                refit_system.run_if(in_state(GameState::Port)),
+            ))
+            .add_systems(OnEnter(GameState::GameOver), clear_upgrades);
+    }
+}
+
+/// Applies the bonuses of fitted upgrades to a freshly spawned flagship.
+/// One spawned from `RunSnapshot` already has her hull and cargo bonuses.
+fn apply_flagship_upgrades(
+    mut commands: Commands,
+    upgrades: Res<UpgradeInventory>,
+    snapshot: Res<RunSnapshot>,
+    mut player_query: Query<(Entity, &mut Health, Option<&mut Cargo>), (Added<Player>, With<Ship>)>,
+) {
+    for (entity, mut health, cargo) in &mut player_query {
+        commands.entity(entity).insert(Fittings::of(&upgrades.flagship));
+        if snapshot.flagship.is_some() {
+            continue;
+        }
+        let hull_bonus = upgrades_hull_bonus(&upgrades.flagship);
+        health.hull += hull_bonus;
+        health.hull_max += hull_bonus;
+        if let Some(mut cargo) = cargo {
+            cargo.capacity += upgrades_cargo_bonus(&upgrades.flagship);
+        }
+    }
+}
+
+/// Carries out shipyard jobs ordered from the port UI.
+///
+/// Fitting and removal cost `REFIT_FEE` each and lay the ship up in the
+/// `DockQueue` for `REFIT_HOURS`. Changes to the flagship also update the
+/// docked flagship's stats. A commission buys an upgrade and fits it to the
+/// flagship as one job, for its price and the fee. Nothing is done while the
+/// port's dockworkers are on strike.
+fn refit_system(
+    mut events: EventReader<RefitEvent>,
+    mut upgrades: ResMut<UpgradeInventory>,
+    mut player_fleet: ResMut<PlayerFleet>,
+    mut dock_queue: ResMut<DockQueue>,
+    world_clock: Res<WorldClock>,
+    current_port: Res<CurrentPort>,
+    port_events: Res<PortEvents>,
+    mut player_query: Query<(&mut Gold, &mut Health, Option<&mut Cargo>, Option<&mut Fittings>), (With<Player>, With<Ship>)>,
+) {
+    let on_strike = port_events.docks_closed(current_port.position);
+    for event in events.read() {
+        if on_strike {
+            info!("Refit refused: the dockworkers are on strike");
+            continue;
+        }
+        let Ok((mut gold, mut health, mut cargo, fittings)) = player_query.get_single_mut() else {
+            warn!("Refit failed: Player not found");
+            continue;
+        };
+
+        let (cost, target, result) = match event.action {
+            RefitAction::Buy(upgrade) => {
+                if !gold.spend(upgrade.price()) {
+                    warn!("Upgrade purchase failed: Insufficient gold");
+                    continue;
+                }
+                upgrades.stowed.push(upgrade);
+                info!("Bought upgrade: {}", upgrade.name());
+                continue;
+            }
+            RefitAction::Commission(upgrade) => {
+                if upgrades.flagship.len() >= UPGRADE_SLOTS {
+                    warn!("Commission failed: No free slot on the flagship");
+                    continue;
+                }
+                if gold.0 < upgrade.price() + REFIT_FEE {
+                    warn!("Commission failed: Insufficient gold");
+                    continue;
+                }
+                gold.spend(upgrade.price());
+                upgrades.stowed.push(upgrade);
+                let stowed_index = upgrades.stowed.len() - 1;
+                let target = RefitTarget::Flagship;
+                (REFIT_FEE, target, upgrades.install(&mut player_fleet, stowed_index, target).map(|u| (u, 1.0)))
+            }
+            RefitAction::Install { stowed_index, target } => {
+                if gold.0 < REFIT_FEE {
+                    warn!("Refit failed: Insufficient gold");
+                    continue;
+                }
+                (REFIT_FEE, target, upgrades.install(&mut player_fleet, stowed_index, target).map(|u| (u, 1.0)))
+            }
+            RefitAction::Remove { target, slot } => {
+                if gold.0 < REFIT_FEE {
+                    warn!("Refit failed: Insufficient gold");
+                    continue;
+                }
+                (REFIT_FEE, target, upgrades.remove(&mut player_fleet, target, slot).map(|u| (u, -1.0)))
+            }
+        };
+
+        let (upgrade, sign) = match result {
+            Ok(done) => done,
+            Err(error) => {
+                warn!("Refit failed: {:?}", error);
+                continue;
+            }
+        };
+        gold.spend(cost);
+        dock_queue.schedule(DockService::Refit(upgrade), REFIT_HOURS, world_clock.total_ticks());
+
+        if target == RefitTarget::Flagship {
+            adjust_flagship(&mut health, cargo.as_deref_mut(), upgrade, sign);
+            if let Some(mut fittings) = fittings {
+                *fittings = Fittings::of(&upgrades.flagship);
+            }
+        }
+        info!("Refit underway: {} ({:?}), ready in {} hours", upgrade.name(), target, dock_queue.hours_remaining(world_clock.total_ticks()));
+    }
+}
+
+/// Adds (`sign` 1.0) or takes away (`sign` -1.0) one upgrade's bonuses on the live flagship.
+fn adjust_flagship(health: &mut Health, cargo: Option<&mut Cargo>, upgrade: UpgradeKind, sign: f32) {
+    let hull_bonus = upgrade.hull_bonus() * sign;
+    health.hull_max += hull_bonus;
+    health.hull = (health.hull + hull_bonus.max(0.0)).min(health.hull_max);
+    if let Some(cargo) = cargo {
+        if sign > 0.0 {
+            cargo.capacity += upgrade.cargo_bonus();
+        } else {
+            cargo.capacity = cargo.capacity.saturating_sub(upgrade.cargo_bonus());
+        }
+    }
+}
+
+/// Upgrades go down with the flagship.
+fn clear_upgrades(mut upgrades: ResMut<UpgradeInventory>) {
+    upgrades.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjust_flagship_round_trip() {
+        let mut health = Health { hull: 90.0, hull_max: 100.0, ..default() };
+        let mut cargo = Cargo::new(100);
+
+        adjust_flagship(&mut health, Some(&mut cargo), UpgradeKind::IronBracing, 1.0);
+        assert_eq!((health.hull, health.hull_max, cargo.capacity), (105.0, 115.0, 110));
+
+        adjust_flagship(&mut health, Some(&mut cargo), UpgradeKind::IronBracing, -1.0);
+        assert_eq!((health.hull, health.hull_max, cargo.capacity), (100.0, 100.0, 100));
+    }
+}
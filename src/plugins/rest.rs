@@ -0,0 +1,215 @@
+//! Resting in port or at anchor: "wait until morning" and "wait N hours".
+//!
+//! A rest runs `Time<Virtual>` at `REST_TIME_SCALE`, the same lever the
+//! tactical pause uses to slow combat, so the world simulation plays out at
+//! speed instead of being skipped. The screen darkens and lightens with the
+//! hours as they fly by. See `resources::rest` for where it is safe to rest
+//! and what cuts a rest short.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::components::{AcceptedContract, ContractDetails, Destination, Faction, FactionId, Player};
+use crate::events::RestRequestEvent;
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::{HighSeasAI, HighSeasPlayer};
+use crate::resources::{
+    anchorage_hazard, clock_darkness, AnchorageHazard, Journal, RestState, RestUntil, StormTracker, WorldClock,
+    MAX_REST_HOURS, REST_TIME_SCALE,
+};
+use crate::systems::world_tick_system;
+
+/// Plugin for resting in port and at anchor.
+pub struct RestPlugin;
+
+impl Plugin for RestPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(RestState { hours_choice: 4, ..default() })
+            .add_event::<RestRequestEvent>()
+            .add_systems(Update, (
+                start_rest,
+                rest_controls_ui.after(EguiSet::InitContexts),
+                rest_overlay_ui.after(EguiSet::InitContexts),
+            ).run_if(in_state(GameState::Port).or(in_state(GameState::HighSeas))))
+            .add_systems(FixedUpdate, watch_rest
+                .after(world_tick_system)
+                .run_if(in_state(GameState::Port).or(in_state(GameState::HighSeas))))
+            .add_systems(OnExit(GameState::Port), stop_rest)
+            .add_systems(OnExit(GameState::HighSeas), stop_rest);
+    }
+}
+
+/// Accepted contracts with a deadline.
+type AcceptedContracts<'w, 's> = Query<'w, 's, (Entity, &'static ContractDetails), With<AcceptedContract>>;
+
+/// Whatever could make it unsafe to lie at anchor.
+#[derive(SystemParam)]
+struct Surroundings<'w, 's> {
+    state: Res<'w, State<GameState>>,
+    player: Query<'w, 's, (&'static Transform, Has<Destination>), (With<Player>, With<HighSeasPlayer>)>,
+    ships: Query<'w, 's, (&'static Transform, &'static Faction), With<HighSeasAI>>,
+    storms: Res<'w, StormTracker>,
+}
+
+impl Surroundings<'_, '_> {
+    /// Why the player can't rest here, if anything. Ports are always safe.
+    fn hazard(&self) -> Option<AnchorageHazard> {
+        if *self.state.get() == GameState::Port {
+            return None;
+        }
+        let Ok((transform, under_way)) = self.player.get_single() else {
+            return Some(AnchorageHazard::UnderWay);
+        };
+        let pirates = self
+            .ships
+            .iter()
+            .filter(|(_, faction)| faction.0 == FactionId::Pirates)
+            .map(|(transform, _)| transform.translation.truncate());
+        anchorage_hazard(transform.translation.truncate(), under_way, pirates, &self.storms)
+    }
+}
+
+/// Contracts within `ContractDetails::URGENT_TICKS` of their deadline.
+fn urgent_contracts(contracts: &AcceptedContracts, now: u32) -> Vec<Entity> {
+    contracts
+        .iter()
+        .filter(|(_, details)| details.ticks_remaining(now).is_some_and(|left| left <= ContractDetails::URGENT_TICKS))
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+/// Begins a rest if it is safe to.
+fn start_rest(
+    mut requests: EventReader<RestRequestEvent>,
+    mut rest: ResMut<RestState>,
+    mut time: ResMut<Time<Virtual>>,
+    world_clock: Res<WorldClock>,
+    surroundings: Surroundings,
+    contracts: AcceptedContracts,
+) {
+    let Some(request) = requests.read().last() else {
+        return;
+    };
+    if let Some(hazard) = surroundings.hazard() {
+        info!("Cannot rest: {}", hazard.describe());
+        return;
+    }
+    let now = world_clock.total_ticks();
+    rest.started_at = now;
+    rest.until = Some(request.until.target_tick(&world_clock));
+    rest.urgent_contracts = urgent_contracts(&contracts, now);
+    time.set_relative_speed(REST_TIME_SCALE);
+    info!("Resting {:?}", request.until);
+}
+
+/// Ends the rest when its time is up, or when something needs the captain.
+fn watch_rest(
+    mut rest: ResMut<RestState>,
+    mut time: ResMut<Time<Virtual>>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+    surroundings: Surroundings,
+    contracts: AcceptedContracts,
+) {
+    let Some(until) = rest.until else {
+        return;
+    };
+    let now = world_clock.total_ticks();
+
+    let interruption = if let Some(hazard) = surroundings.hazard() {
+        Some(hazard.describe())
+    } else {
+        contracts
+            .iter()
+            .find(|(entity, details)| {
+                !rest.urgent_contracts.contains(entity)
+                    && details.ticks_remaining(now).is_some_and(|left| left <= ContractDetails::URGENT_TICKS)
+            })
+            .map(|(_, details)| format!("Woken early: \"{}\" is nearly due.", details.description))
+    };
+
+    if let Some(reason) = interruption {
+        journal.record(&world_clock, reason);
+    } else if now < until {
+        return;
+    }
+    rest.until = None;
+    time.set_relative_speed(1.0);
+    info!("Rest over at {}", world_clock.formatted_time());
+}
+
+/// Rest buttons: always in port, at sea only when safely at anchor.
+fn rest_controls_ui(
+    mut contexts: EguiContexts,
+    mut rest: ResMut<RestState>,
+    mut requests: EventWriter<RestRequestEvent>,
+    surroundings: Surroundings,
+) {
+    if rest.is_resting() {
+        return;
+    }
+    let at_anchor = surroundings.hazard().is_none();
+    if !at_anchor {
+        return;
+    }
+
+    egui::Window::new("⚓ Rest")
+        .anchor(egui::Align2::RIGHT_TOP, [-16.0, 96.0])
+        .resizable(false)
+        .collapsible(true)
+        .show(contexts.ctx_mut(), |ui| {
+            if ui.button("Wait until morning").clicked() {
+                requests.send(RestRequestEvent { until: RestUntil::Morning });
+            }
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut rest.hours_choice).range(1..=MAX_REST_HOURS).suffix("h"));
+                if ui.button("Wait").clicked() {
+                    requests.send(RestRequestEvent { until: RestUntil::Hours(rest.hours_choice) });
+                }
+            });
+        });
+}
+
+/// The hours flying by: the clock, a way to stop, and the light coming and going.
+fn rest_overlay_ui(
+    mut contexts: EguiContexts,
+    mut rest: ResMut<RestState>,
+    mut time: ResMut<Time<Virtual>>,
+    world_clock: Res<WorldClock>,
+    state: Res<State<GameState>>,
+) {
+    if !rest.is_resting() {
+        return;
+    }
+    let ctx = contexts.ctx_mut();
+
+    // The High Seas have their own night shade (see `plugins::day_night`)
+    if *state.get() == GameState::Port {
+        let darkness = clock_darkness(&world_clock);
+        let shade = egui::Color32::from_rgba_unmultiplied(10, 14, 40, (darkness * 140.0) as u8);
+        ctx.layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("rest_shade")))
+            .rect_filled(ctx.screen_rect(), 0.0, shade);
+    }
+
+    egui::Window::new("Resting")
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.label(world_clock.formatted_time());
+            ui.add(egui::ProgressBar::new(rest.progress(world_clock.total_ticks())));
+            if ui.button("Stop").clicked() {
+                rest.until = None;
+                time.set_relative_speed(1.0);
+            }
+        });
+}
+
+/// Leaving port or the High Seas always wakes the crew and restores full speed.
+fn stop_rest(mut rest: ResMut<RestState>, mut time: ResMut<Time<Virtual>>) {
+    if rest.is_resting() {
+        rest.until = None;
+        time.set_relative_speed(1.0);
+    }
+}
@@ -0,0 +1,340 @@
+//! Recording the run into `RunSnapshot` for saves, and rebuilding the High
+//! Seas from it after a load.
+//!
+//! See `resources::run_state`. The snapshot is recorded whenever the game is
+//! saved and on leaving the High Seas, so a save made in port still has the
+//! flagship and the ships at sea as they were when the player sailed in.
+//! The flagship's damage, hold and purse are recorded again on leaving a
+//! battle or a port, and she carries them into each battle and back out to
+//! sea. In port she is docked as a bare `Player` ship (no sprite, no
+//! physics) for the port's trades to pay from and repairs to mend.
+//! After a load the High Seas are entered afresh: the usual spawn systems run
+//! (random AI ships are skipped when the save has its own), and the restore
+//! systems here then put everything back where it was.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::components::companion::{Companion, CompanionName, CompanionRole, CompanionService};
+use crate::components::contract::{AcceptedContract, Commission, Contract, ContractDetails, ContractProgress, PatrolArea};
+use crate::components::{port_key, Cargo, Faction, Gold, Health, OfferedAt, Player, PortEntity, Ship, ShipClass, ShipType};
+use crate::plugins::core::GameState;
+use crate::plugins::companion::spawn_companion;
+use crate::plugins::port_ui::PlayerContracts;
+use crate::plugins::worldmap::{
+    spawn_high_seas_ai_ship, spawn_high_seas_player, spawn_port_entities, HighSeasAI, HighSeasPlayer, HighSeasPort,
+};
+use crate::resources::{
+    BalanceConfig, FogOfWar, LandmassArchipelagos, PendingRunRestore, RunSnapshot, SavedAiShip, SavedCompanion, SavedContract,
+    SavedFlagship, ShipDefinitions,
+};
+
+type FlagshipQuery<'w, 's, Q> = Query<'w, 's, Q, With<HighSeasPlayer>>;
+
+/// Everything on the High Seas that goes into a `RunSnapshot`.
+#[derive(SystemParam)]
+pub struct RunStateQueries<'w, 's> {
+    player: FlagshipQuery<
+        'w,
+        's,
+        (
+            &'static Transform,
+            &'static Health,
+            &'static Cargo,
+            &'static Gold,
+            Option<&'static ShipType>,
+            Option<&'static ShipClass>,
+        ),
+    >,
+    ai_ships: Query<
+        'w,
+        's,
+        (&'static Transform, Option<&'static ShipType>, Option<&'static ShipClass>, &'static Faction, Option<&'static Health>),
+        With<HighSeasAI>,
+    >,
+    contracts: Query<
+        'w,
+        's,
+        (
+            &'static ContractDetails,
+            Option<&'static ContractProgress>,
+            Option<&'static PatrolArea>,
+            Option<&'static Commission>,
+        ),
+        (With<Contract>, With<AcceptedContract>),
+    >,
+    docked: Query<'w, 's, (&'static Health, &'static Cargo, &'static Gold), (With<Player>, With<Ship>, With<PortEntity>)>,
+    ports: Query<'w, 's, &'static Transform, With<HighSeasPort>>,
+    companions: Query<'w, 's, (&'static CompanionName, &'static CompanionRole, &'static CompanionService), With<Companion>>,
+}
+
+/// Plugin recording and restoring run state.
+pub struct RunStatePlugin;
+
+impl Plugin for RunStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunSnapshot>()
+            // A quickload back into the High Seas leaves them too; the loaded
+            // snapshot must not be overwritten on the way
+            .add_systems(
+                OnExit(GameState::HighSeas),
+                record_run_state.run_if(not(resource_exists::<PendingRunRestore>)),
+            )
+            .add_systems(
+                OnEnter(GameState::HighSeas),
+                (
+                    (restore_flagship, restore_ai_ships, restore_contracts, restore_exploration, restore_companions)
+                        .after(spawn_high_seas_player)
+                        .after(spawn_port_entities),
+                    finish_run_restore,
+                )
+                    .chain()
+                    .run_if(resource_exists::<PendingRunRestore>),
+            )
+            .add_systems(OnEnter(GameState::Port), dock_flagship)
+            .add_systems(OnExit(GameState::Combat), record_flagship)
+            .add_systems(OnExit(GameState::Port), record_flagship)
+            .add_systems(OnEnter(GameState::MainMenu), reset_run_snapshot);
+    }
+}
+
+/// Run condition: the High Seas are being rebuilt from a save that has its own AI ships.
+pub fn restoring_ai_ships(pending: Option<Res<PendingRunRestore>>, snapshot: Res<RunSnapshot>) -> bool {
+    pending.is_some() && !snapshot.ai_ships.is_empty()
+}
+
+/// Records the run into `RunSnapshot`. Away from the High Seas there are no
+/// ships at sea to record, and the last ones recorded are kept; in port the
+/// docked flagship's damage, hold and purse are.
+pub fn record_run_state(mut snapshot: ResMut<RunSnapshot>, fog_of_war: Res<FogOfWar>, run: RunStateQueries) {
+    if let Ok((transform, health, cargo, gold, ship_type, class)) = run.player.get_single() {
+        snapshot.flagship = Some(SavedFlagship {
+            position: transform.translation.truncate(),
+            rotation: transform.rotation,
+            health: health.clone(),
+            cargo: cargo.clone(),
+            gold: gold.0,
+            ship_type: ship_type.copied().unwrap_or_default(),
+            class: class.map_or_else(String::new, |class| class.0.clone()),
+        });
+        snapshot.ai_ships = run
+            .ai_ships
+            .iter()
+            .map(|(transform, ship_type, class, faction, health)| SavedAiShip {
+                position: transform.translation.truncate(),
+                ship_type: ship_type.copied().unwrap_or_default(),
+                class: class.map_or_else(String::new, |class| class.0.clone()),
+                faction: faction.0,
+                health: health.cloned().unwrap_or_default(),
+            })
+            .collect();
+    } else if let (Some(flagship), Ok((health, cargo, gold))) = (snapshot.flagship.as_mut(), run.docked.get_single()) {
+        flagship.keep(health, cargo, gold);
+    }
+
+    let port_position = |entity: Entity| run.ports.get(entity).ok().map(|t| t.translation.truncate());
+    snapshot.contracts = run
+        .contracts
+        .iter()
+        .map(|(details, progress, area, commission)| {
+            SavedContract::new(
+                details,
+                port_position(details.origin_port),
+                details.destination.and_then(port_position),
+                progress.cloned().unwrap_or_default(),
+                area.copied(),
+                commission.copied(),
+            )
+        })
+        .collect();
+
+    snapshot.companions = run
+        .companions
+        .iter()
+        .map(|(name, role, service)| SavedCompanion {
+            name: name.0.clone(),
+            role: *role,
+            service: *service,
+        })
+        .collect();
+
+    let mut explored: Vec<IVec2> = fog_of_war.explored_tiles().collect();
+    explored.sort_by_key(|tile| (tile.y, tile.x));
+    snapshot.explored_tiles = explored;
+}
+
+/// Docks the flagship at the port being entered, as she came in.
+pub fn dock_flagship(mut commands: Commands, snapshot: Res<RunSnapshot>) {
+    let Some(flagship) = &snapshot.flagship else {
+        return;
+    };
+    commands.spawn((
+        Name::new("Docked Flagship"),
+        Player,
+        Ship,
+        flagship.ship_type,
+        ShipClass(flagship.class.clone()),
+        flagship.health.clone(),
+        flagship.cargo.clone(),
+        Gold(flagship.gold),
+        Transform::from_translation(flagship.position.extend(0.0)).with_rotation(flagship.rotation),
+        PortEntity,
+    ));
+}
+
+/// Records the flagship as a battle or a stay in port left her: damage taken
+/// or mended, shot spent, goods and gold won or traded. She sails back out
+/// with it.
+fn record_flagship(
+    mut snapshot: ResMut<RunSnapshot>,
+    player_query: Query<(&Health, &Cargo, &Gold), (With<Player>, With<Ship>, Without<HighSeasPlayer>)>,
+) {
+    let (Some(flagship), Ok((health, cargo, gold))) = (snapshot.flagship.as_mut(), player_query.get_single()) else {
+        return;
+    };
+    flagship.keep(health, cargo, gold);
+}
+
+/// Puts the flagship back where she was, as she was.
+fn restore_flagship(
+    snapshot: Res<RunSnapshot>,
+    mut player_query: FlagshipQuery<(&mut Transform, &mut Health, &mut Cargo, &mut Gold)>,
+) {
+    let Some(saved) = &snapshot.flagship else {
+        return;
+    };
+    let Ok((mut transform, mut health, mut cargo, mut gold)) = player_query.get_single_mut() else {
+        warn!("Run restore: no flagship on the High Seas");
+        return;
+    };
+    transform.translation = saved.position.extend(transform.translation.z);
+    transform.rotation = saved.rotation;
+    *health = saved.health.clone();
+    *cargo = saved.cargo.clone();
+    gold.0 = saved.gold;
+}
+
+/// Spawns the saved ships at sea in place of the usual random ones.
+fn restore_ai_ships(
+    mut commands: Commands,
+    snapshot: Res<RunSnapshot>,
+    asset_server: Res<AssetServer>,
+    archipelagos: Option<Res<LandmassArchipelagos>>,
+    balance: Res<BalanceConfig>,
+    ships: Res<ShipDefinitions>,
+) {
+    for (i, saved) in snapshot.ai_ships.iter().enumerate() {
+        let entity = spawn_high_seas_ai_ship(
+            &mut commands,
+            &asset_server,
+            archipelagos.as_deref(),
+            format!("High Seas AI Ship {}", i),
+            &ships.definition(&saved.class, saved.ship_type),
+            saved.faction,
+            saved.position,
+            &balance.ai_speed,
+        );
+        commands.entity(entity).insert(saved.health.clone());
+    }
+    info!("Run restore: {} ships at sea", snapshot.ai_ships.len());
+}
+
+/// Re-creates the accepted contracts, bound to the respawned ports.
+fn restore_contracts(
+    mut commands: Commands,
+    snapshot: Res<RunSnapshot>,
+    mut player_contracts: ResMut<PlayerContracts>,
+    ports: Query<(Entity, &Transform), With<HighSeasPort>>,
+) {
+    let find_port = |position: Option<Vec2>| {
+        position.and_then(|position| {
+            RunSnapshot::find_port(position, ports.iter().map(|(entity, t)| (entity, t.translation.truncate())))
+        })
+    };
+
+    for saved in &snapshot.contracts {
+        let details = saved.details(find_port(saved.origin_port), find_port(saved.destination));
+        let mut entity_commands = commands.spawn((Contract, AcceptedContract, details, saved.progress.clone()));
+        if let Some(origin) = saved.origin_port {
+            entity_commands.insert(OfferedAt(port_key(origin)));
+        }
+        if let Some(area) = saved.patrol_area {
+            entity_commands.insert(area);
+        }
+        if let Some(commission) = saved.commission {
+            entity_commands.insert(commission);
+        }
+        player_contracts.active.push(entity_commands.id());
+    }
+}
+
+/// Charts the explored waters again on the regenerated map.
+fn restore_exploration(snapshot: Res<RunSnapshot>, mut fog_of_war: ResMut<FogOfWar>) {
+    fog_of_war.reveal_tiles(snapshot.explored_tiles.iter().copied());
+}
+
+/// Signs the saved companions on again, in place of those of the run being replaced.
+fn restore_companions(
+    mut commands: Commands,
+    snapshot: Res<RunSnapshot>,
+    companions: Query<Entity, With<Companion>>,
+) {
+    for entity in &companions {
+        commands.entity(entity).despawn_recursive();
+    }
+    for saved in &snapshot.companions {
+        spawn_companion(&mut commands, saved.name.clone(), saved.role, saved.service);
+    }
+}
+
+fn finish_run_restore(mut commands: Commands) {
+    commands.remove_resource::<PendingRunRestore>();
+    info!("Run restored from save");
+}
+
+/// A new run starts with nothing recorded.
+fn reset_run_snapshot(mut snapshot: ResMut<RunSnapshot>) {
+    snapshot.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use crate::systems::spawn_player_ship;
+
+    #[test]
+    fn test_battle_keeps_the_flagships_damage_and_purse() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_resource::<ShipDefinitions>();
+        let mut health = Health::default();
+        health.hull = 40.0;
+        app.insert_resource(RunSnapshot {
+            flagship: Some(SavedFlagship {
+                position: Vec2::ZERO,
+                rotation: Quat::IDENTITY,
+                health,
+                cargo: Cargo::armed(50),
+                gold: 1234,
+                ship_type: ShipType::Sloop,
+                class: String::new(),
+            }),
+            ..default()
+        });
+
+        // She goes into battle as she left the High Seas
+        app.world_mut().run_system_once(spawn_player_ship).unwrap();
+        let mut player = app.world_mut().query_filtered::<(&mut Health, &mut Gold), With<Player>>();
+        let (mut health, mut gold) = player.single_mut(app.world_mut());
+        assert_eq!((health.hull, gold.0), (40.0, 1234));
+
+        // And comes out of it as the battle left her
+        health.hull = 25.0;
+        gold.add(300);
+        app.world_mut().run_system_once(record_flagship).unwrap();
+        let flagship = app.world().resource::<RunSnapshot>().flagship.clone().unwrap();
+        assert_eq!((flagship.health.hull, flagship.gold), (25.0, 1534));
+    }
+}
@@ -0,0 +1,106 @@
+//! Time acceleration on the High Seas.
+//!
+//! `.` and `,` (or the d-pad left and right) step the game speed between
+//! paused, 1×, 2× and 4×, and the buttons over the sail trim readout pick one
+//! directly. The speed is applied to `Time<Virtual>` except while a rest or a
+//! chase has the clock. It drops back to 1× when the player's ship arrives
+//! at its destination or a chase begins, and leaving the High Seas (docking
+//! or joining battle) always restores full speed. See `resources::sail_ahead`.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use leafwing_input_manager::prelude::*;
+
+use crate::components::{Destination, Player};
+use crate::plugins::core::GameState;
+use crate::plugins::input::PlayerAction;
+use crate::plugins::worldmap::HighSeasPlayer;
+use crate::resources::{GameSpeed, PursuitState, RestState, SailAhead};
+
+/// Plugin for the High Seas game speed.
+pub struct SailAheadPlugin;
+
+impl Plugin for SailAheadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SailAhead>()
+            .add_systems(
+                Update,
+                (
+                    change_game_speed,
+                    drop_speed_on_arrival,
+                    apply_game_speed,
+                    game_speed_hud.after(EguiSet::InitContexts),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::HighSeas)),
+            )
+            .add_systems(OnExit(GameState::HighSeas), end_sail_ahead);
+    }
+}
+
+/// Steps the game speed up or down.
+fn change_game_speed(action_query: Query<&ActionState<PlayerAction>>, mut sail_ahead: ResMut<SailAhead>) {
+    let Ok(action_state) = action_query.get_single() else {
+        return;
+    };
+    if action_state.just_pressed(&PlayerAction::SpeedUp) {
+        sail_ahead.speed = sail_ahead.speed.faster();
+    }
+    if action_state.just_pressed(&PlayerAction::SlowDown) {
+        sail_ahead.speed = sail_ahead.speed.slower();
+    }
+}
+
+/// Back to 1× when the player's ship makes its destination or a chase begins.
+fn drop_speed_on_arrival(
+    mut sail_ahead: ResMut<SailAhead>,
+    mut arrivals: RemovedComponents<Destination>,
+    player_query: Query<(), (With<Player>, With<HighSeasPlayer>)>,
+    pursuit: Res<PursuitState>,
+) {
+    let arrived = arrivals.read().any(|entity| player_query.contains(entity));
+    if (arrived || pursuit.is_chasing()) && sail_ahead.drop_to_normal() {
+        info!("Back to 1×: {}", if arrived { "arrived" } else { "giving chase" });
+    }
+}
+
+/// Runs `Time<Virtual>` at the chosen speed, unless a rest or chase has it.
+fn apply_game_speed(
+    sail_ahead: Res<SailAhead>,
+    rest: Res<RestState>,
+    pursuit: Res<PursuitState>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if rest.is_resting() || pursuit.is_chasing() {
+        return;
+    }
+    let scale = sail_ahead.speed.scale();
+    if time.relative_speed() != scale {
+        time.set_relative_speed(scale);
+    }
+}
+
+/// The speed buttons, the chosen one lit.
+fn game_speed_hud(mut contexts: EguiContexts, mut sail_ahead: ResMut<SailAhead>, rest: Res<RestState>) {
+    if rest.is_resting() {
+        return;
+    }
+    egui::Area::new(egui::Id::new("game_speed_hud"))
+        .anchor(egui::Align2::LEFT_BOTTOM, [16.0, -96.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                for speed in GameSpeed::ALL {
+                    ui.selectable_value(&mut sail_ahead.speed, speed, speed.label());
+                }
+                ui.label("(, / .)");
+            });
+        });
+}
+
+/// Docking or joining battle puts the world back to full speed.
+fn end_sail_ahead(mut sail_ahead: ResMut<SailAhead>, rest: Res<RestState>, mut time: ResMut<Time<Virtual>>) {
+    sail_ahead.drop_to_normal();
+    if !rest.is_resting() {
+        time.set_relative_speed(1.0);
+    }
+}
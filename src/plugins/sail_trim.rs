@@ -0,0 +1,105 @@
+//! Sail trim orders for the player's ship.
+//!
+//! Every ship carries a `SailTrim`. X sets more sail and Z shortens it; the
+//! trim scales speed in combat physics and High Seas movement, steadies the
+//! guns and shields canvas from damage (see `SailTrim`). Enemy ships pick
+//! their own trim in `ai_sail_trim_system`.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use leafwing_input_manager::prelude::*;
+
+use crate::components::sails::SailTrim;
+use crate::components::{Health, Player, Ship};
+use crate::plugins::core::GameState;
+use crate::plugins::input::PlayerAction;
+
+/// Plugin for sail trim orders and the trim indicator.
+pub struct SailTrimPlugin;
+
+impl Plugin for SailTrimPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, attach_sail_trim)
+            .add_systems(Update, (
+                player_sail_trim_system,
+                sail_trim_hud_system.after(EguiSet::InitContexts),
+            ).run_if(in_state(GameState::Combat).or(in_state(GameState::HighSeas))));
+    }
+}
+
+/// Ships put to sea under full sail.
+fn attach_sail_trim(mut commands: Commands, ship_query: Query<Entity, (With<Ship>, Without<SailTrim>)>) {
+    for entity in &ship_query {
+        commands.entity(entity).insert(SailTrim::default());
+    }
+}
+
+/// X sets more sail, Z takes some in.
+fn player_sail_trim_system(
+    action_query: Query<&ActionState<PlayerAction>>,
+    mut player_query: Query<&mut SailTrim, (With<Player>, With<Ship>)>,
+) {
+    let Ok(action_state) = action_query.get_single() else {
+        return;
+    };
+    let Ok(mut trim) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let ordered = if action_state.just_pressed(&PlayerAction::MoreSail) {
+        trim.more()
+    } else if action_state.just_pressed(&PlayerAction::LessSail) {
+        trim.less()
+    } else {
+        return;
+    };
+    if *trim != ordered {
+        info!("Sails: {}", ordered.name());
+        *trim = ordered;
+    }
+}
+
+/// Current trim and the speed it gives with the sails' damage.
+fn sail_trim_hud_system(
+    mut contexts: EguiContexts,
+    player_query: Query<(&SailTrim, Option<&Health>), (With<Player>, With<Ship>)>,
+) {
+    let Ok((trim, health)) = player_query.get_single() else {
+        return;
+    };
+    let drive = trim.speed_factor() * health.map_or(1.0, Health::sail_power);
+    let color = match health.map(Health::sails_ratio) {
+        Some(ratio) if ratio < 0.33 => egui::Color32::from_rgb(200, 60, 50),
+        Some(ratio) if ratio < 0.66 => egui::Color32::from_rgb(200, 150, 50),
+        _ => egui::Color32::from_rgb(235, 225, 200),
+    };
+
+    egui::Area::new(egui::Id::new("sail_trim_hud"))
+        .anchor(egui::Align2::LEFT_BOTTOM, [16.0, -56.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(32.0, 32.0), egui::Sense::hover());
+                draw_sail(ui.painter(), rect, *trim, color);
+                ui.colored_label(color, format!("{} - {:.0}% speed  (Z/X)", trim.name(), drive * 100.0));
+            });
+        });
+}
+
+/// A mast with as much canvas drawn as the trim sets.
+fn draw_sail(painter: &egui::Painter, rect: egui::Rect, trim: SailTrim, color: egui::Color32) {
+    let stroke = egui::Stroke::new(2.0, color);
+    let mast_x = rect.center().x;
+    painter.line_segment([egui::pos2(mast_x, rect.top() + 2.0), egui::pos2(mast_x, rect.bottom() - 2.0)], stroke);
+
+    let depth = match trim {
+        SailTrim::Full => 0.75,
+        SailTrim::Battle => 0.45,
+        SailTrim::Furled => 0.12,
+    };
+    let top = rect.top() + 4.0;
+    let sail = egui::Rect::from_min_max(
+        egui::pos2(rect.left() + 5.0, top),
+        egui::pos2(rect.right() - 5.0, top + (rect.height() - 8.0) * depth),
+    );
+    painter.rect_stroke(sail, 2.0, stroke);
+}
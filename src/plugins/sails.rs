@@ -0,0 +1,179 @@
+//! Animated sail overlays on ship sprites.
+//!
+//! Every ship gets one canvas child sprite per mast from
+//! `ShipType::sail_anchors`. Sails are furled when the ship is stationary,
+//! reefed in storms, set full running downwind and trimmed otherwise, never
+//! showing more canvas than the ship's `SailTrim` allows. Sail damage from
+//! `Health::sails` narrows and darkens the canvas.
+
+use bevy::prelude::*;
+
+use crate::components::health::Health;
+use crate::components::sails::{SailDamage, SailRig, SailSet, SailSprite, SailTrim};
+use crate::components::ship::{Ship, ShipType};
+use crate::resources::Wind;
+
+/// Below this speed (world units per second) a ship counts as anchored.
+const SAIL_FURL_SPEED: f32 = 8.0;
+/// Heading alignment with the wind above which a ship is running downwind.
+const DOWNWIND_ALIGNMENT: f32 = 0.5;
+/// Rate at which sails ease to a new depth (per second, exponential).
+const SAIL_EASE_RATE: f32 = 6.0;
+/// Relative depth change of a filled sail billowing in the wind.
+const BILLOW_AMOUNT: f32 = 0.08;
+/// Ship sprite size that `SailAnchor` units are defined against.
+const ANCHOR_SPRITE_SIZE: f32 = 64.0;
+
+/// Plugin that composes and animates sail sprites on ships.
+pub struct SailsPlugin;
+
+impl Plugin for SailsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (attach_sail_rigs, update_sail_rigs, animate_sails).chain(),
+        );
+    }
+}
+
+/// Sail set for a ship moving at `speed` along `heading` in the given wind.
+pub fn choose_sail_set(speed: f32, heading: Vec2, wind: &Wind) -> SailSet {
+    if speed < SAIL_FURL_SPEED {
+        SailSet::Furled
+    } else if wind.is_storm() {
+        SailSet::Reefed
+    } else if heading.dot(wind.direction_vec()) > DOWNWIND_ALIGNMENT {
+        SailSet::Full
+    } else {
+        SailSet::Trimmed
+    }
+}
+
+/// Adds a `SailRig` and sail child sprites to new ships.
+fn attach_sail_rigs(
+    mut commands: Commands,
+    ship_query: Query<(Entity, Option<&ShipType>, &Sprite), (With<Ship>, Without<SailRig>)>,
+) {
+    for (entity, ship_type, sprite) in &ship_query {
+        // A ship without a ShipType is rigged as a sloop
+        let ship_type = ship_type.copied().unwrap_or_default();
+        let scale = sprite.custom_size.map_or(1.0, |size| size.x / ANCHOR_SPRITE_SIZE);
+        let depth = SailSet::Furled.depth() * scale;
+
+        commands
+            .entity(entity)
+            .insert(SailRig {
+                scale,
+                ..default()
+            })
+            .with_children(|parent| {
+                for (index, anchor) in ship_type.sail_anchors().iter().enumerate() {
+                    let width = anchor.width * scale;
+                    parent.spawn((
+                        Sprite {
+                            color: SailDamage::Intact.color(),
+                            custom_size: Some(Vec2::new(width, depth)),
+                            ..default()
+                        },
+                        // Slightly above the hull sprite
+                        Transform::from_translation((anchor.offset * scale).extend(0.1)),
+                        SailSprite {
+                            width,
+                            depth,
+                            phase: index as f32 * 1.7,
+                        },
+                    ));
+                }
+            });
+    }
+}
+
+/// Chooses each ship's sail set from its motion and the wind, and its damage from health.
+fn update_sail_rigs(
+    time: Res<Time>,
+    wind: Res<Wind>,
+    mut ship_query: Query<(&Transform, Option<&Health>, Option<&SailTrim>, &mut SailRig)>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (transform, health, trim, mut rig) in &mut ship_query {
+        let position = transform.translation.truncate();
+        let speed = rig.last_position.map_or(0.0, |last| last.distance(position) / delta);
+        rig.last_position = Some(position);
+
+        // Ships face +Y in local space
+        let heading = (transform.rotation * Vec3::Y).truncate().normalize_or_zero();
+        let set = choose_sail_set(speed, heading, &wind);
+        let set = trim.map_or(set, |trim| trim.limit_set(set));
+        let damage = health.map_or(SailDamage::Intact, |h| SailDamage::from_ratio(h.sails_ratio()));
+
+        if rig.set != set {
+            trace!("Sails {:?} -> {:?}", rig.set, set);
+            rig.set = set;
+        }
+        rig.damage = damage;
+    }
+}
+
+/// Eases sail sprites towards their rig's set and billows filled canvas.
+fn animate_sails(
+    time: Res<Time>,
+    rig_query: Query<&SailRig>,
+    mut sail_query: Query<(&Parent, &mut SailSprite, &mut Sprite)>,
+) {
+    let ease = 1.0 - (-SAIL_EASE_RATE * time.delta_secs()).exp();
+    let elapsed = time.elapsed_secs();
+
+    for (parent, mut sail, mut sprite) in &mut sail_query {
+        let Ok(rig) = rig_query.get(parent.get()) else {
+            continue;
+        };
+
+        let target = rig.set.depth() * rig.scale;
+        sail.depth += (target - sail.depth) * ease;
+
+        let billow = match rig.set {
+            SailSet::Full | SailSet::Trimmed => (elapsed * 3.0 + sail.phase).sin() * BILLOW_AMOUNT,
+            SailSet::Furled | SailSet::Reefed => 0.0,
+        };
+        let size = Vec2::new(
+            sail.width * rig.damage.width_factor(),
+            sail.depth * (1.0 + billow),
+        );
+
+        sprite.custom_size = Some(size);
+        sprite.color = rig.damage.color();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wind(direction: f32, strength: f32) -> Wind {
+        Wind { direction, strength }
+    }
+
+    #[test]
+    fn test_stationary_ship_furls() {
+        assert_eq!(choose_sail_set(0.0, Vec2::X, &wind(0.0, 0.5)), SailSet::Furled);
+        // Even in a storm
+        assert_eq!(choose_sail_set(0.0, Vec2::X, &wind(0.0, 1.0)), SailSet::Furled);
+    }
+
+    #[test]
+    fn test_storm_reefs() {
+        assert_eq!(choose_sail_set(200.0, Vec2::X, &wind(0.0, 0.9)), SailSet::Reefed);
+    }
+
+    #[test]
+    fn test_downwind_is_full_and_upwind_trimmed() {
+        // Wind blowing East
+        assert_eq!(choose_sail_set(200.0, Vec2::X, &wind(0.0, 0.5)), SailSet::Full);
+        assert_eq!(choose_sail_set(200.0, Vec2::NEG_X, &wind(0.0, 0.5)), SailSet::Trimmed);
+        assert_eq!(choose_sail_set(200.0, Vec2::Y, &wind(0.0, 0.5)), SailSet::Trimmed);
+    }
+}
@@ -1,13 +1,27 @@
+use bevy::ecs::system::RunSystemOnce;
 use bevy::prelude::*;
+use bevy::reflect::GetTypeRegistration;
+use std::any::TypeId;
 use bevy_save::prelude::*;
 
 use crate::components::{
-    Ship, Player, AI, PlayerOwned, Surrendered, Faction, FactionId, ShipType,
-    Health, WaterIntake, Cargo, Gold, GoodType, GoodsTrait,
-    Destination, NavigationPath, Projectile, TargetComponent, Order, OrderQueue,
+    Ship, Player, AI, PlayerOwned, Surrendered, Faction, FactionId, ShipType, ShipClass,
+    Health, WaterIntake, Fire, Crew, Cargo, Gold, GoodType, GoodsTrait,
+    Destination, NavigationPath, Projectile, TargetComponent, AmmoType, Order, OrderQueue,
 };
-use crate::resources::{Wind, WorldClock, CliArgs};
+use crate::components::{AcceptedContract, CombatEntity, Contract, HighSeasEntity, PortEntity};
+use crate::components::weather::WeatherCell;
+use crate::resources::{
+    save_file_path, content_hash, run_migrations, FactionRegistry, Journal, JournalCategory, JournalEntry, Migration,
+    MooredShips, PendingRunRestore, PortEvents, UpgradeInventory, PendingBounties, PortMarkets, DockQueue, WreckSites, DigSites, PendingWorldGeneration, PlayerCrew, PlayerFleet, RunSettings, RunSnapshot,
+    SavedAiShip, SavedFlagship, Wind, WorldClock, CliArgs, PendingSave, SaveCompatDialog, SaveIssue, SaveManifest,
+    SAVE_FORMAT_VERSION, GameSettings, MetaProfile, StormTracker,
+};
+use crate::plugins::companion::TavernRosters;
 use crate::plugins::core::GameState;
+use crate::plugins::main_menu::SelectedArchetype;
+use crate::plugins::port_ui::PlayerContracts;
+use crate::plugins::run_state::{record_run_state, RunStatePlugin};
 
 /// Marker resource indicating a CLI-triggered load is pending.
 /// Consumed after the load is attempted.
@@ -25,6 +39,9 @@ impl Plugin for PersistencePlugin {
         // - SaveablesPlugin (common type registrations)
         app.add_plugins(SavePlugins);
 
+        // Run state that has to be rebuilt by hand after a load (see `RunSnapshot`)
+        app.add_plugins(RunStatePlugin);
+
         // Register all game-specific types that need to be saved
         register_saveable_types(app);
 
@@ -45,6 +62,8 @@ impl Plugin for PersistencePlugin {
         // Autosave when entering safe states (Port, HighSeas)
         app.add_systems(OnEnter(GameState::Port), autosave_system);
         app.add_systems(OnEnter(GameState::HighSeas), autosave_on_highseas);
+        // And every so often at sea, as often as the player's settings ask
+        app.add_systems(Update, interval_autosave.run_if(in_state(GameState::HighSeas)));
 
         // Debug preset generation (F6-F8 keys)
         // Only enabled in HighSeas for safety
@@ -58,43 +77,95 @@ impl Plugin for PersistencePlugin {
 /// Registers all custom game types with the Bevy type registry.
 /// Types must be registered for bevy_save to serialize/deserialize them.
 fn register_saveable_types(app: &mut App) {
+    app.init_resource::<SaveableTypes>();
+
     // Ship marker components
-    app.register_type::<Ship>()
-        .register_type::<Player>()
-        .register_type::<AI>()
-        .register_type::<PlayerOwned>()
-        .register_type::<Surrendered>();
+    app.register_saveable::<Ship>()
+        .register_saveable::<Player>()
+        .register_saveable::<AI>()
+        .register_saveable::<PlayerOwned>()
+        .register_saveable::<Surrendered>();
 
     // Ship classification
-    app.register_type::<Faction>()
-        .register_type::<FactionId>()
-        .register_type::<ShipType>();
+    app.register_saveable::<Faction>()
+        .register_saveable::<FactionId>()
+        .register_saveable::<ShipType>()
+        .register_saveable::<ShipClass>();
 
     // Health and damage
-    app.register_type::<Health>()
-        .register_type::<WaterIntake>();
+    app.register_saveable::<Health>()
+        .register_saveable::<WaterIntake>()
+        .register_saveable::<Fire>()
+        .register_saveable::<Crew>();
 
     // Economy
-    app.register_type::<Cargo>()
-        .register_type::<Gold>()
-        .register_type::<GoodType>()
-        .register_type::<GoodsTrait>();
+    app.register_saveable::<Cargo>()
+        .register_saveable::<Gold>()
+        .register_saveable::<GoodType>()
+        .register_saveable::<GoodsTrait>();
 
     // Navigation
-    app.register_type::<Destination>()
-        .register_type::<NavigationPath>();
+    app.register_saveable::<Destination>()
+        .register_saveable::<NavigationPath>();
 
     // Combat
-    app.register_type::<Projectile>()
-        .register_type::<TargetComponent>();
+    app.register_saveable::<Projectile>()
+        .register_saveable::<TargetComponent>()
+        .register_saveable::<AmmoType>();
 
     // AI Orders
-    app.register_type::<Order>()
-        .register_type::<OrderQueue>();
+    app.register_saveable::<Order>()
+        .register_saveable::<OrderQueue>();
+
+    // Weather
+    app.register_saveable::<WeatherCell>()
+        .register_saveable::<StormTracker>();
 
     // Resources
-    app.register_type::<Wind>()
-        .register_type::<WorldClock>();
+    app.register_saveable::<Wind>()
+        .register_saveable::<WorldClock>()
+        .register_saveable::<MooredShips>()
+        .register_saveable::<PortEvents>();
+
+    // The run: its settings (map seed) and what the player has built up
+    app.register_saveable::<RunSettings>()
+        .register_saveable::<SelectedArchetype>()
+        .register_saveable::<PlayerFleet>()
+        .register_saveable::<PlayerCrew>()
+        .register_saveable::<FactionRegistry>()
+        .register_saveable::<Journal>()
+        .register_saveable::<JournalEntry>()
+        .register_saveable::<JournalCategory>()
+        .register_saveable::<RunSnapshot>();
+
+    // What the ports remember between visits, what is owed or stowed, and
+    // the wrecks waiting to be dived and the treasure waiting to be dug
+    app.register_saveable::<UpgradeInventory>()
+        .register_saveable::<PendingBounties>()
+        .register_saveable::<PortMarkets>()
+        .register_saveable::<DockQueue>()
+        .register_saveable::<TavernRosters>()
+        .register_saveable::<WreckSites>()
+        .register_saveable::<DigSites>();
+}
+
+/// Game types a save can hold, as registered by `register_saveable_types`.
+/// Only these go into the manifest's content hash, so reflecting a new UI or
+/// editor type does not flag every save as written by another build.
+#[derive(Resource, Debug, Default)]
+pub struct SaveableTypes(Vec<TypeId>);
+
+trait RegisterSaveable {
+    /// Registers `T` for reflection and records it in `SaveableTypes`.
+    fn register_saveable<T: GetTypeRegistration>(&mut self) -> &mut Self;
+}
+
+impl RegisterSaveable for App {
+    fn register_saveable<T: GetTypeRegistration>(&mut self) -> &mut Self {
+        self.register_type::<T>();
+        self.world_mut().resource_mut::<SaveableTypes>().0.push(TypeId::of::<T>());
+        self
+    }
 }
 
 /// System that triggers a quicksave when F5 is pressed.
@@ -114,9 +185,9 @@ fn save_game_system(world: &mut World) {
 
         info!("Saving game to '{}'...", save_name);
 
-        match world.save(save_name.as_str()) {
+        match save_slot(world, &save_name) {
             Ok(_) => info!("Game saved successfully to '{}'", save_name),
-            Err(e) => error!("Failed to save game: {:?}", e),
+            Err(e) => error!("Failed to save game: {}", e),
         }
     }
 }
@@ -131,18 +202,225 @@ fn load_game_system(world: &mut World) {
 
     if should_load {
         info!("Loading game...");
+        request_load(world, "quicksave");
+    }
+}
+
+/// Hash of the `SaveableTypes`, i.e. everything a save can contain.
+pub fn registry_content_hash(world: &World) -> u64 {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let saveable = world.resource::<SaveableTypes>();
+    content_hash(
+        saveable
+            .0
+            .iter()
+            .filter_map(|type_id| registry.get(*type_id))
+            .map(|registration| registration.type_info().type_path()),
+    )
+}
+
+/// Saves the world to `slot` and writes the manifest describing this build.
+pub fn save_slot(world: &mut World, slot: &str) -> Result<(), String> {
+    // Straight after a load the snapshot is the loaded one, not yet applied
+    if !world.contains_resource::<PendingRunRestore>() {
+        world.run_system_once(record_run_state).map_err(|e| format!("{:?}", e))?;
+    }
+    world.save(slot).map_err(|e| format!("{:?}", e))?;
+    SaveManifest::current(registry_content_hash(world)).write(slot)
+}
+
+/// Loads `slot` if its manifest matches this build. Otherwise the save
+/// compatibility dialog is opened and the load waits on the player's choice.
+/// Returns true if the save was loaded.
+pub fn request_load(world: &mut World, slot: &str) -> bool {
+    // A missing save is a plain load failure, not a version question
+    if !save_file_path(slot).is_some_and(|path| path.exists()) {
+        return load_slot(world, slot);
+    }
 
-        match world.load("quicksave") {
-            Ok(_) => {
-                info!("Game loaded successfully from 'quicksave'");
+    let current = SaveManifest::current(registry_content_hash(world));
+    let issue = match SaveManifest::read(slot) {
+        Ok(found) => SaveIssue::check(found.as_ref(), &current),
+        Err(e) => Some(SaveIssue::UnreadableManifest(e)),
+    };
+    match issue {
+        None => load_slot(world, slot),
+        Some(issue) => {
+            warn!("Save '{}' needs confirmation before loading: {:?}", slot, issue);
+            world.resource_mut::<SaveCompatDialog>().open(PendingSave::Run {
+                slot: slot.to_string(),
+                issue,
+            });
+            false
+        }
+    }
+}
 
-                // After loading, transition to HighSeas state (most common saved state)
-                if let Some(mut next_state) = world.get_resource_mut::<NextState<GameState>>() {
-                    next_state.set(GameState::HighSeas);
-                    info!("Transitioned to HighSeas state after load");
-                }
+/// Loads `slot` without checks and heads to the High Seas (the most common
+/// saved state), where the run is rebuilt from its `RunSnapshot`. A failure is
+/// reported in the save compatibility dialog.
+pub fn load_slot(world: &mut World, slot: &str) -> bool {
+    let previous_seed = world.resource::<RunSettings>().seed;
+    // Saves from before manifests are format 0
+    let format = SaveManifest::read(slot).ok().flatten().map_or(0, |manifest| manifest.format);
+    match world.load(slot) {
+        Ok(_) => {
+            info!("Game loaded successfully from '{}'", slot);
+            if let Err(e) = run_migrations(world, RUN_SAVE_MIGRATIONS, format, SAVE_FORMAT_VERSION) {
+                warn!("Save '{}' loaded without migrating: {}", slot, e);
             }
-            Err(e) => error!("Failed to load game: {:?}", e),
+            prepare_run_restore(world, previous_seed);
+            if let Some(mut next_state) = world.get_resource_mut::<NextState<GameState>>() {
+                next_state.set(GameState::HighSeas);
+                info!("Transitioned to HighSeas state after load");
+            }
+            true
+        }
+        Err(e) => {
+            error!("Failed to load game '{}': {:?}", slot, e);
+            world.resource_mut::<SaveCompatDialog>().open(PendingSave::Failed {
+                message: format!("The save '{}' could not be loaded. It may be missing or damaged.", slot),
+            });
+            false
+        }
+    }
+}
+
+/// Upgrades for run saves written by earlier releases, oldest first. Each
+/// step runs on the freshly loaded world, before the run is rebuilt.
+pub const RUN_SAVE_MIGRATIONS: &[Migration<World>] = &[
+    Migration {
+        from: 0,
+        description: "manifests added; layout unchanged",
+        apply: |_| {},
+    },
+    Migration {
+        from: 1,
+        description: "record the flagship and ships at sea into the run snapshot",
+        apply: snapshot_loaded_ships,
+    },
+    Migration {
+        from: 2,
+        description: "charted dig sites saved; older saves had none",
+        apply: |world| world.insert_resource(DigSites::default()),
+    },
+    Migration {
+        from: 3,
+        description: "named storms saved; older saves had none at sea",
+        apply: |world| world.insert_resource(StormTracker::default()),
+    },
+    Migration {
+        from: 4,
+        description: "port and run ledgers saved; older saves had none",
+        apply: reset_port_and_run_ledgers,
+    },
+];
+
+/// Upgrades, bounties, port markets, dock jobs, tavern rosters, wrecks and
+/// port events were not saved before format 5, so they start empty rather
+/// than carrying over from whatever run was loaded before.
+fn reset_port_and_run_ledgers(world: &mut World) {
+    world.insert_resource(UpgradeInventory::default());
+    world.insert_resource(PendingBounties::default());
+    world.insert_resource(PortMarkets::default());
+    world.insert_resource(DockQueue::default());
+    world.insert_resource(TavernRosters::default());
+    world.insert_resource(WreckSites::default());
+    world.insert_resource(PortEvents::default());
+}
+
+/// Before `RunSnapshot`, saves held the flagship and AI ships as bare entities.
+/// They are recorded into the snapshot, so the run is rebuilt with them.
+fn snapshot_loaded_ships(world: &mut World) {
+    let mut ships = world.query_filtered::<(
+        &Transform,
+        Has<Player>,
+        Option<&Health>,
+        Option<&Cargo>,
+        Option<&Gold>,
+        Option<&ShipType>,
+        Option<&Faction>,
+    ), (With<Ship>, Without<HighSeasEntity>, Without<PortEntity>, Without<CombatEntity>)>();
+
+    let mut flagship = None;
+    let mut ai_ships = Vec::new();
+    for (transform, is_player, health, cargo, gold, ship_type, faction) in ships.iter(world) {
+        let health = health.cloned().unwrap_or_default();
+        if is_player {
+            let Some(cargo) = cargo else {
+                continue;
+            };
+            flagship = Some(SavedFlagship {
+                position: transform.translation.truncate(),
+                rotation: transform.rotation,
+                health,
+                cargo: cargo.clone(),
+                gold: gold.map_or(0, |gold| gold.0),
+                ship_type: ship_type.copied().unwrap_or_default(),
+                class: String::new(),
+            });
+        } else if let Some(faction) = faction {
+            ai_ships.push(SavedAiShip {
+                position: transform.translation.truncate(),
+                ship_type: ship_type.copied().unwrap_or_default(),
+                class: String::new(),
+                faction: faction.0,
+                health,
+            });
+        }
+    }
+
+    let mut snapshot = world.resource_mut::<RunSnapshot>();
+    if snapshot.flagship.is_none() {
+        snapshot.flagship = flagship;
+    }
+    if snapshot.ai_ships.is_empty() {
+        snapshot.ai_ships = ai_ships;
+    }
+}
+
+/// Readies the world for the loaded run to be rebuilt on entering the High Seas.
+fn prepare_run_restore(world: &mut World, previous_seed: u32) {
+    // From the main menu the loaded run's world is generated from its seed.
+    // Elsewhere the current world stays, and a save of another world would
+    // put the ships on the wrong map
+    let in_main_menu = world
+        .get_resource::<State<GameState>>()
+        .is_some_and(|state| *state.get() == GameState::MainMenu);
+    let seed = world.resource::<RunSettings>().seed;
+    if in_main_menu {
+        world.insert_resource(PendingWorldGeneration);
+    } else if seed != previous_seed {
+        warn!("Loaded a run from another world (seed {}); continue it from the main menu instead", seed);
+    }
+
+    // Ships the load brought back bare (without their sprites and markers),
+    // and the contracts of the run being replaced, make way for the restored ones
+    let mut stale = world.query_filtered::<Entity, (
+        With<Ship>,
+        Without<HighSeasEntity>,
+        Without<PortEntity>,
+        Without<CombatEntity>,
+    )>();
+    let mut contracts = world.query_filtered::<Entity, (With<Contract>, With<AcceptedContract>)>();
+    let doomed: Vec<Entity> = stale.iter(world).chain(contracts.iter(world)).collect();
+    for entity in doomed {
+        world.entity_mut(entity).despawn_recursive();
+    }
+    world.resource_mut::<PlayerContracts>().active.clear();
+    world.insert_resource(PendingRunRestore);
+}
+
+/// Writes the autosave slot, and the meta profile with whatever the run has
+/// done to it since it was last written (legacy wrecks decaying or stripped).
+fn write_autosave(world: &mut World) {
+    match save_slot(world, "autosave") {
+        Ok(_) => info!("Autosave completed successfully"),
+        Err(e) => error!("Autosave failed: {}", e),
+    }
+    if let Some(profile) = world.get_resource::<MetaProfile>() {
+        if let Err(e) = profile.save_to_file() {
+            error!("Failed to save profile on autosave: {}", e);
         }
     }
 }
@@ -152,10 +430,7 @@ fn load_game_system(world: &mut World) {
 fn autosave_system(world: &mut World) {
     info!("Autosaving on Port entry...");
 
-    match world.save("autosave") {
-        Ok(_) => info!("Autosave completed successfully"),
-        Err(e) => error!("Autosave failed: {:?}", e),
-    }
+    write_autosave(world);
 }
 
 /// Autosave system for HighSeas entry.
@@ -166,10 +441,32 @@ fn autosave_on_highseas(world: &mut World) {
     // In a more complex implementation, we'd track previous state
     info!("Autosaving on HighSeas entry...");
 
-    match world.save("autosave") {
-        Ok(_) => info!("Autosave completed successfully"),
-        Err(e) => error!("Autosave failed: {:?}", e),
+    write_autosave(world);
+}
+
+/// Autosaves on the High Seas every `GameSettings::autosave_interval` of
+/// real time at play. Time spent paused (the pause menu, a chase, a sea event,
+/// or the game speed set to paused) does not count, so no save is written with
+/// a menu open.
+fn interval_autosave(world: &mut World, mut elapsed: Local<f32>) {
+    let Some(interval) = world.resource::<GameSettings>().autosave_interval() else {
+        *elapsed = 0.0;
+        return;
+    };
+    let time = world.resource::<Time<Virtual>>();
+    // The paused game speed stops the clock by its relative speed, not `pause()`
+    if time.is_paused() || time.relative_speed() == 0.0 {
+        return;
     }
+    // Real time, so sailing at 4x speed does not save four times as often
+    *elapsed += world.resource::<Time<Real>>().delta_secs();
+    if *elapsed < interval {
+        return;
+    }
+    *elapsed = 0.0;
+    info!("Autosaving after {:.0} minutes at sea...", interval / 60.0);
+
+    write_autosave(world);
 }
 
 // ============================================================================
@@ -190,8 +487,8 @@ pub fn debug_preset_system(world: &mut World) {
     if create_rich {
         info!("Creating 'rich' preset...");
         apply_rich_preset(world);
-        if let Err(e) = world.save("preset_rich") {
-            error!("Failed to save rich preset: {:?}", e);
+        if let Err(e) = save_slot(world, "preset_rich") {
+            error!("Failed to save rich preset: {}", e);
         } else {
             info!("Saved 'preset_rich' - Player has 10,000 gold");
         }
@@ -200,8 +497,8 @@ pub fn debug_preset_system(world: &mut World) {
     if create_damaged {
         info!("Creating 'damaged' preset...");
         apply_damaged_preset(world);
-        if let Err(e) = world.save("preset_damaged") {
-            error!("Failed to save damaged preset: {:?}", e);
+        if let Err(e) = save_slot(world, "preset_damaged") {
+            error!("Failed to save damaged preset: {}", e);
         } else {
             info!("Saved 'preset_damaged' - Ship at 25% health");
         }
@@ -210,8 +507,8 @@ pub fn debug_preset_system(world: &mut World) {
     if create_advanced {
         info!("Creating 'advanced' preset...");
         apply_advanced_preset(world);
-        if let Err(e) = world.save("preset_advanced") {
-            error!("Failed to save advanced preset: {:?}", e);
+        if let Err(e) = save_slot(world, "preset_advanced") {
+            error!("Failed to save advanced preset: {}", e);
         } else {
             info!("Saved 'preset_advanced' - Day 30, advanced game state");
         }
@@ -275,19 +572,174 @@ fn cli_load_system(world: &mut World) {
 
     info!("CLI: Loading save '{}'...", save_name);
 
-    match world.load(save_name.as_str()) {
-        Ok(_) => {
-            info!("CLI: Save '{}' loaded successfully", save_name);
+    // Loads straight into the High Seas (bypassing main menu) if the save matches
+    // this build; otherwise the main menu shows the compatibility dialog
+    if !request_load(world, &save_name) {
+        info!("CLI: Save '{}' not loaded yet, staying on main menu", save_name);
+    }
+}
 
-            // Transition to HighSeas state (bypassing main menu)
-            if let Some(mut next_state) = world.get_resource_mut::<NextState<GameState>>() {
-                next_state.set(GameState::HighSeas);
-                info!("CLI: Transitioned to HighSeas state");
-            }
-        }
-        Err(e) => {
-            error!("CLI: Failed to load save '{}': {:?}", save_name, e);
-            error!("CLI: Falling back to main menu");
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Saves `world`'s resources and loads them into a fresh app.
+    fn round_trip(world: &World) -> App {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let snapshot = Snapshot::builder(world).extract_all_resources().build();
+        let mut bytes = Vec::new();
+        DefaultFormat::serialize(&mut bytes, &snapshot.serializer(&registry)).expect("snapshot serializes");
+        let loaded = DefaultFormat::deserialize(&bytes[..], SnapshotDeserializer { registry: &registry })
+            .expect("snapshot deserializes");
+        drop(registry);
+
+        let mut fresh = App::new();
+        register_saveable_types(&mut fresh);
+        loaded.apply(fresh.world_mut()).expect("snapshot applies");
+        fresh
+    }
+
+    #[test]
+    fn test_content_hash_ignores_types_saves_do_not_hold() {
+        #[derive(Reflect)]
+        struct Unrelated;
+
+        let mut app = App::new();
+        register_saveable_types(&mut app);
+        let before = registry_content_hash(app.world());
+        app.register_type::<Unrelated>();
+        assert_eq!(registry_content_hash(app.world()), before);
+    }
+
+    #[test]
+    fn test_port_and_crew_state_survives_a_save() {
+        use crate::components::companion::{CompanionMemory, CompanionRole, CompanionService};
+        use crate::components::upgrade::UpgradeKind;
+        use crate::components::Inventory;
+        use crate::events::RepairType;
+        use crate::plugins::companion::{RecruitableCompanion, TavernRoster};
+        use crate::resources::{BountyClaim, DockJob, DockService, HunterAtSea, PortMarket, SavedCompanion};
+
+        let port = IVec2::new(640, -320);
+        let mut app = App::new();
+        register_saveable_types(&mut app);
+        let world = app.world_mut();
+        world.insert_resource(UpgradeInventory {
+            stowed: vec![UpgradeKind::ReinforcedHull],
+            flagship: vec![UpgradeKind::CopperSheathing],
+        });
+        world.insert_resource(PendingBounties {
+            claims: vec![BountyClaim { ship_name: "Gull".to_string(), victim: FactionId::Pirates, amount: 150, day: 4 }],
+            paid: Vec::new(),
+            hunters: vec![HunterAtSea { faction: FactionId::NationB, ship_type: ShipType::Frigate, position: Vec2::new(90.0, 12.0) }],
+        });
+        let mut inventory = Inventory::new();
+        inventory.set_good(GoodType::Rum, 40, 12.5);
+        world.insert_resource(PortMarkets {
+            markets: [(port, PortMarket { inventory, produces: Some(GoodType::Rum), consumes: None })].into(),
+        });
+        world.insert_resource(DockQueue {
+            jobs: vec![DockJob { service: DockService::Repair(RepairType::Hull, 30), ready_at: 900 }],
+        });
+        let memory = CompanionMemory { regard: -10, times_dismissed: 1 };
+        world.insert_resource(TavernRosters {
+            rosters: [(
+                port,
+                TavernRoster {
+                    companions: vec![RecruitableCompanion {
+                        name: "Old Tom".to_string(),
+                        role: CompanionRole::Gunner,
+                        cost: 300,
+                        id: 7,
+                        memory: Some(memory),
+                        veteran: false,
+                    }],
+                    generated_on: 3,
+                },
+            )]
+            .into(),
+        });
+        world.insert_resource(RunSnapshot {
+            companions: vec![SavedCompanion {
+                name: "Maggie".to_string(),
+                role: CompanionRole::Navigator,
+                service: CompanionService { hired_on: 2, memory },
+            }],
+            ..default()
+        });
+
+        let mut fresh = round_trip(world);
+        let world = fresh.world_mut();
+
+        let upgrades = world.resource::<UpgradeInventory>();
+        assert_eq!(upgrades.stowed, vec![UpgradeKind::ReinforcedHull]);
+        assert_eq!(upgrades.flagship, vec![UpgradeKind::CopperSheathing]);
+        assert_eq!(world.resource::<PendingBounties>().claims[0].amount, 150);
+        assert_eq!(world.resource::<PendingBounties>().hunters_from(FactionId::NationB), 1);
+        let market = world.resource::<PortMarkets>().get(port).expect("market kept").clone();
+        assert_eq!(market.produces, Some(GoodType::Rum));
+        assert_eq!(market.inventory.get_good(&GoodType::Rum).map(|item| item.quantity), Some(40));
+        assert_eq!(
+            world.resource::<DockQueue>().jobs[0],
+            DockJob { service: DockService::Repair(RepairType::Hull, 30), ready_at: 900 }
+        );
+        let roster = &world.resource::<TavernRosters>().rosters[&port];
+        assert_eq!(roster.generated_on, 3);
+        assert_eq!(roster.companions[0].memory, Some(memory));
+        let companion = &world.resource::<RunSnapshot>().companions[0];
+        assert_eq!((companion.name.as_str(), companion.role), ("Maggie", CompanionRole::Navigator));
+        assert_eq!(companion.service.memory, memory);
+    }
+
+    #[test]
+    fn test_charted_treasure_survives_a_save() {
+        use crate::resources::MapData;
+        use rand::SeedableRng;
+
+        let map = MapData::new(16, 16);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let mut app = App::new();
+        register_saveable_types(&mut app);
+        let mut dig_sites = DigSites::default();
+        let id = dig_sites.chart(&map, IVec2::new(4, 9), &mut rng).expect("charted");
+        app.world_mut().insert_resource(dig_sites);
+
+        let mut fresh = round_trip(app.world());
+        let world = fresh.world_mut();
+        let mut loaded = world.resource_mut::<DigSites>();
+        assert_eq!(loaded.get(id), app.world().resource::<DigSites>().get(id));
+        // Ids carry on from where the saved run left off
+        let next = loaded.chart(&map, IVec2::new(12, 2), &mut rng).expect("charted");
+        assert_ne!(next, id);
+    }
+
+    #[test]
+    fn test_named_storms_survive_a_save() {
+        use crate::resources::Storm;
+
+        let mut app = App::new();
+        register_saveable_types(&mut app);
+        let mut tracker = StormTracker::default();
+        let name = tracker.take_name();
+        let mut storm = Storm {
+            name,
+            position: Vec2::new(300.0, -120.0),
+            velocity: Vec2::new(20.0, 5.0),
+            radius: 180.0,
+            hours_left: 30,
+            track: vec![Vec2::new(280.0, -125.0)],
+            report: None,
+        };
+        storm.report(60, 0.5);
+        tracker.storms.push(storm);
+        app.world_mut().insert_resource(tracker);
+
+        let fresh = round_trip(app.world());
+        let tracker = fresh.world().resource::<StormTracker>();
+        let storm = &tracker.storms[0];
+        assert_eq!((storm.name.as_str(), storm.hours_left), ("Abigail", 30));
+        assert_eq!(storm.track, vec![Vec2::new(280.0, -125.0)]);
+        assert_eq!(storm.report.as_ref().map(|report| report.tick), Some(60));
+        assert_eq!(tracker.next_name, 1);
     }
 }
@@ -0,0 +1,128 @@
+//! Save compatibility dialog.
+//!
+//! Loads whose save does not match this build (see `SaveIssue`), profiles that
+//! fail to parse and loads that fail outright all end up in
+//! `SaveCompatDialog`. The dialog explains what is wrong and offers to attempt
+//! a migration, load anyway or cancel; the choice is carried out by an
+//! exclusive system, since loading needs the whole `World`.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::plugins::save::{load_slot, save_slot};
+use crate::resources::{migrate_profile_json, PendingSave, SaveChoice, SaveCompatDialog};
+
+/// Plugin for the save compatibility dialog.
+pub struct SaveCompatPlugin;
+
+impl Plugin for SaveCompatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveCompatDialog>()
+            .add_systems(Update, (
+                save_compat_dialog_ui.after(EguiSet::InitContexts),
+                resolve_save_choice.after(save_compat_dialog_ui),
+            ));
+    }
+}
+
+fn save_compat_dialog_ui(mut contexts: EguiContexts, mut dialog: ResMut<SaveCompatDialog>) {
+    if dialog.choice.is_some() {
+        return;
+    }
+    let Some(pending) = &dialog.pending else {
+        return;
+    };
+
+    let mut choice = None;
+    egui::Window::new("Save Compatibility")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(contexts.ctx_mut(), |ui| match pending {
+            PendingSave::Run { slot, issue } => {
+                ui.strong(format!("Save \"{}\"", slot));
+                ui.label(issue.description());
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if issue.can_migrate() && ui.button("Attempt Migration").clicked() {
+                        choice = Some(SaveChoice::Migrate);
+                    }
+                    if ui.button("Load Anyway").clicked() {
+                        choice = Some(SaveChoice::LoadAnyway);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        choice = Some(SaveChoice::Cancel);
+                    }
+                });
+            }
+            PendingSave::Profile { error, .. } => {
+                ui.strong("Captain's profile");
+                ui.label("Your profile could not be read, so a fresh one is in use. The old file was kept as profile.json.bak.");
+                ui.small(error.as_str());
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Attempt Migration").clicked() {
+                        choice = Some(SaveChoice::Migrate);
+                    }
+                    if ui.button("Keep Fresh Profile").clicked() {
+                        choice = Some(SaveChoice::Cancel);
+                    }
+                });
+            }
+            PendingSave::Failed { message } => {
+                ui.label(message.as_str());
+                ui.separator();
+                if ui.button("OK").clicked() {
+                    choice = Some(SaveChoice::Cancel);
+                }
+            }
+        });
+
+    dialog.choice = choice;
+}
+
+/// Carries out the player's choice from the dialog.
+fn resolve_save_choice(world: &mut World) {
+    let (pending, choice) = {
+        let mut dialog = world.resource_mut::<SaveCompatDialog>();
+        let Some(choice) = dialog.choice.take() else {
+            return;
+        };
+        (dialog.pending.take(), choice)
+    };
+
+    match (pending, choice) {
+        (Some(PendingSave::Run { slot, .. }), SaveChoice::Migrate) => {
+            // Re-saving writes the slot and its manifest in the current format
+            if load_slot(world, &slot) {
+                match save_slot(world, &slot) {
+                    Ok(_) => info!("Migrated save '{}' to the current format", slot),
+                    Err(e) => error!("Failed to migrate save '{}': {}", slot, e),
+                }
+            }
+        }
+        (Some(PendingSave::Run { slot, .. }), SaveChoice::LoadAnyway) => {
+            load_slot(world, &slot);
+        }
+        (Some(PendingSave::Profile { contents, .. }), SaveChoice::Migrate) => match migrate_profile_json(&contents) {
+            Some(profile) => {
+                if let Err(e) = profile.save_to_file() {
+                    error!("Failed to save migrated profile: {}", e);
+                }
+                info!("Recovered profile: {} runs completed", profile.runs_completed);
+                world.insert_resource(profile);
+            }
+            None => {
+                world.resource_mut::<SaveCompatDialog>().open(PendingSave::Failed {
+                    message: "The old profile could not be recovered. The fresh profile stays in use; profile.json.bak is untouched."
+                        .to_string(),
+                });
+            }
+        },
+        (pending, _) => {
+            if pending.is_some() {
+                info!("Save compatibility dialog dismissed");
+            }
+        }
+    }
+}
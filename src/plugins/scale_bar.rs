@@ -8,11 +8,10 @@
 
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
-use bevy::window::PrimaryWindow;
 use bevy_prototype_lyon::prelude::*;
 
 use crate::plugins::core::{GameState, MainCamera};
-use crate::plugins::overlay_ui::{UI_LAYER, COLOR_INK, COLOR_PARCHMENT};
+use crate::plugins::overlay_ui::{ScreenAnchor, ScreenEdge, UI_LAYER, COLOR_INK, COLOR_PARCHMENT};
 use crate::components::HighSeasEntity;
 
 pub struct ScaleBarPlugin;
@@ -22,10 +21,7 @@ impl Plugin for ScaleBarPlugin {
         app
             .init_resource::<ScaleBarConfig>()
             .add_systems(OnEnter(GameState::HighSeas), spawn_scale_bar)
-            .add_systems(Update, (
-                update_scale_bar_position,
-                update_scale_bar_scale,
-            ).run_if(in_state(GameState::HighSeas)));
+            .add_systems(Update, update_scale_bar_scale.run_if(in_state(GameState::HighSeas)));
     }
 }
 
@@ -76,27 +72,15 @@ pub struct ScaleBarLabel;
 
 fn spawn_scale_bar(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
     asset_server: Res<AssetServer>,
 ) {
-    // Calculate initial position (bottom-left)
-    let mut initial_pos = Vec3::new(0.0, 0.0, 0.0);
-    if let Ok(window) = window_query.get_single() {
-        let half_w = window.width() / 2.0;
-        let half_h = window.height() / 2.0;
-        initial_pos = Vec3::new(
-            -half_w + MARGIN.x + BASE_BAR_WIDTH / 2.0,
-            -half_h + MARGIN.y,
-            0.0
-        );
-    }
-
-    // Spawn Scale Bar Root (this is what we scale)
+    // Spawn Scale Bar Root (this is what we scale), pinned to the bottom-left corner
     let root = commands.spawn((
         Name::new("Scale Bar Root"),
         ScaleBar,
         ScaleBarRoot,
-        Transform::from_translation(initial_pos),
+        ScreenAnchor::new(ScreenEdge::BottomLeft, bar_margin(1.0)),
+        Transform::default(),
         Visibility::Inherited,
         RenderLayers::layer(UI_LAYER),
         HighSeasEntity,
@@ -184,30 +168,18 @@ fn spawn_scale_bar(
     info!("Spawned Scale Bar");
 }
 
-/// Keeps the scale bar in the bottom-left corner when window is resized.
-fn update_scale_bar_position(
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    config: Res<ScaleBarConfig>,
-    mut root_query: Query<&mut Transform, With<ScaleBarRoot>>,
-) {
-    let Ok(window) = window_query.get_single() else { return; };
-    let Ok(mut transform) = root_query.get_single_mut() else { return; };
-
-    let half_w = window.width() / 2.0;
-    let half_h = window.height() / 2.0;
-    
-    // Adjust X position based on current bar width
-    let current_width = BASE_BAR_WIDTH * config.width_scale;
-    transform.translation.x = -half_w + MARGIN.x + current_width / 2.0;
-    transform.translation.y = -half_h + MARGIN.y;
+/// Anchor margin for a bar `width_scale` times its base width.
+/// The root sits at the bar's centre, so its left end stays `MARGIN.x` from the edge.
+fn bar_margin(width_scale: f32) -> Vec2 {
+    Vec2::new(MARGIN.x + BASE_BAR_WIDTH * width_scale / 2.0, MARGIN.y)
 }
 
 /// Updates the scale bar width and label based on camera zoom.
 fn update_scale_bar_scale(
     camera_query: Query<&OrthographicProjection, (With<MainCamera>, Changed<OrthographicProjection>)>,
     mut config: ResMut<ScaleBarConfig>,
-    mut root_query: Query<&mut Transform, With<ScaleBarRoot>>,
-    mut label_query: Query<(&mut Text2d, &mut Transform), (With<ScaleBarLabel>, Without<ScaleBarRoot>)>,
+    mut root_query: Query<&mut ScreenAnchor, With<ScaleBarRoot>>,
+    mut label_query: Query<(&mut Text2d, &mut Transform), With<ScaleBarLabel>>,
 ) {
     let Ok(projection) = camera_query.get_single() else { return; };
     
@@ -219,9 +191,10 @@ fn update_scale_bar_scale(
     config.segment_miles = segment_miles;
     config.total_miles = total_miles;
     
-    // Scale the root transform (X axis only to change width)
-    if let Ok(mut transform) = root_query.get_single_mut() {
-        transform.scale.x = width_scale;
+    // Stretch the root (X axis only to change width); the anchor layout applies it
+    if let Ok(mut anchor) = root_query.get_single_mut() {
+        anchor.base_scale.x = width_scale;
+        anchor.margin = bar_margin(width_scale);
     }
     
     // Update label text and counter-scale to keep text size consistent
@@ -0,0 +1,198 @@
+//! Sea events: cards drawn from the event deck while sailing.
+//!
+//! Every `SEA_EVENT_EVERY_HOURS` hours on the High Seas, with
+//! `SEA_EVENT_CHANCE`, a card is drawn from `SeaEventDeck` (see
+//! `resources::sea_events`). The world holds still and the game speed drops
+//! to 1× while the player makes a choice; its outcome is applied to the
+//! player's ship and standing, written in the journal and shown until the
+//! player sails on. No card is drawn during a rest or a chase.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use rand::Rng;
+
+use crate::components::{Cargo, Crew, Gold, Player};
+use crate::plugins::core::GameState;
+use crate::plugins::settings::PauseMenu;
+use crate::plugins::worldmap::HighSeasPlayer;
+use crate::resources::{
+    FactionRegistry, Journal, PursuitState, RestState, SailAhead, SeaEventDeck, SeaEventResult, SeaEventState, WorldClock,
+};
+use crate::systems::world_tick_system;
+
+/// Hours at sea between chances of a card.
+const SEA_EVENT_EVERY_HOURS: u32 = 4;
+/// Chance of a card each time one may be drawn.
+const SEA_EVENT_CHANCE: f64 = 0.3;
+
+/// Plugin for the sea event deck.
+pub struct SeaEventsPlugin;
+
+impl Plugin for SeaEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SeaEventState>()
+            .add_systems(
+                FixedUpdate,
+                draw_sea_event.after(world_tick_system).run_if(in_state(GameState::HighSeas)),
+            )
+            .add_systems(
+                Update,
+                (sea_event_dialog.after(EguiSet::InitContexts), resolve_sea_event)
+                    .chain()
+                    .run_if(in_state(GameState::HighSeas)),
+            )
+            .add_systems(OnExit(GameState::HighSeas), abandon_sea_event);
+    }
+}
+
+/// The player's ship on the High Seas, as a card's outcome touches her.
+type PlayerShip<'w, 's> =
+    Query<'w, 's, (&'static mut Gold, &'static mut Cargo, &'static mut Crew), (With<Player>, With<HighSeasPlayer>)>;
+
+/// Draws a card every few hours, holding the world still until it is answered.
+fn draw_sea_event(
+    world_clock: Res<WorldClock>,
+    deck: Res<SeaEventDeck>,
+    mut state: ResMut<SeaEventState>,
+    rest: Res<RestState>,
+    pursuit: Res<PursuitState>,
+    mut sail_ahead: ResMut<SailAhead>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if world_clock.tick != 0 || !world_clock.hour.is_multiple_of(SEA_EVENT_EVERY_HOURS) {
+        return;
+    }
+    if state.is_open() || rest.is_resting() || pursuit.is_chasing() {
+        return;
+    }
+    let mut rng = rand::thread_rng();
+    if !rng.gen_bool(SEA_EVENT_CHANCE) {
+        return;
+    }
+    let Some(index) = deck.draw(world_clock.day, &mut rng) else {
+        return;
+    };
+
+    info!("Sea event: {}", deck.cards[index].title);
+    state.pending = Some(index);
+    state.chosen = None;
+    sail_ahead.drop_to_normal();
+    state.stopped_world = !virtual_time.is_paused();
+    if state.stopped_world {
+        virtual_time.pause();
+    }
+}
+
+/// Starts the world again if the card stopped it. With the pause menu open
+/// over the card, the menu starts it on resuming instead.
+fn release_world(state: &mut SeaEventState, menu: &mut PauseMenu, virtual_time: &mut Time<Virtual>) {
+    if std::mem::take(&mut state.stopped_world) {
+        if menu.open {
+            menu.hold_world();
+        } else {
+            virtual_time.unpause();
+        }
+    }
+}
+
+/// The card and its choices, then what came of the choice made.
+fn sea_event_dialog(
+    mut contexts: EguiContexts,
+    deck: Res<SeaEventDeck>,
+    mut state: ResMut<SeaEventState>,
+    player_query: Query<&Gold, (With<Player>, With<HighSeasPlayer>)>,
+    mut pause_menu: ResMut<PauseMenu>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if let Some(result) = &state.result {
+        let mut sail_on = false;
+        egui::Window::new(result.title.as_str())
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .resizable(false)
+            .collapsible(false)
+            .show(contexts.ctx_mut(), |ui| {
+                ui.label(result.text.as_str());
+                if !result.effects.is_empty() {
+                    ui.separator();
+                    for effect in &result.effects {
+                        ui.label(format!("• {}", effect));
+                    }
+                }
+                ui.separator();
+                sail_on = ui.button("⛵ Sail on").clicked();
+            });
+        if sail_on {
+            state.result = None;
+            release_world(&mut state, &mut pause_menu, &mut virtual_time);
+        }
+        return;
+    }
+
+    let Some(card) = state.pending.filter(|_| state.chosen.is_none()).and_then(|index| deck.cards.get(index)) else {
+        return;
+    };
+    let gold = player_query.get_single().map_or(0, |gold| gold.0);
+
+    let mut chosen = None;
+    egui::Window::new(card.title.as_str())
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .resizable(false)
+        .collapsible(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(card.text.as_str());
+            ui.separator();
+            for (index, choice) in card.choices.iter().enumerate() {
+                if ui.add_enabled(choice.affordable(gold), egui::Button::new(choice.label.as_str())).clicked() {
+                    chosen = Some(index);
+                }
+            }
+        });
+    if chosen.is_some() {
+        state.chosen = chosen;
+    }
+}
+
+/// Draws how the choice made turns out and applies it.
+fn resolve_sea_event(
+    deck: Res<SeaEventDeck>,
+    mut state: ResMut<SeaEventState>,
+    mut player_query: PlayerShip,
+    mut factions: ResMut<FactionRegistry>,
+    mut journal: ResMut<Journal>,
+    world_clock: Res<WorldClock>,
+) {
+    let (Some(card_index), Some(choice_index)) = (state.pending, state.chosen) else {
+        return;
+    };
+    state.pending = None;
+    state.chosen = None;
+    let Some(card) = deck.cards.get(card_index) else {
+        return;
+    };
+    // The dialog stays up until the player sails on, even if nothing came of it
+    let outcome = card.choices.get(choice_index).and_then(|choice| choice.roll(&mut rand::thread_rng()));
+    let (text, effects) = match (outcome, player_query.get_single_mut()) {
+        (Some(outcome), Ok((mut gold, mut cargo, mut crew))) => {
+            (outcome.text.clone(), outcome.apply(&mut gold, &mut cargo, &mut crew, &mut factions))
+        }
+        (outcome, _) => (outcome.map(|outcome| outcome.text.clone()).unwrap_or_default(), Vec::new()),
+    };
+    journal.record(&world_clock, format!("{}: {}", card.title, text));
+    state.result = Some(SeaEventResult {
+        title: card.title.clone(),
+        text,
+        effects,
+    });
+}
+
+/// Leaving the High Seas with a card open (a quickload) puts it back in the deck.
+fn abandon_sea_event(
+    mut state: ResMut<SeaEventState>,
+    mut pause_menu: ResMut<PauseMenu>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if state.is_open() {
+        release_world(&mut state, &mut pause_menu, &mut virtual_time);
+        *state = SeaEventState::default();
+    }
+}
@@ -0,0 +1,521 @@
+//! Sea monsters: a kraken or a leviathan roaming the High Seas, and the
+//! battle against it.
+//!
+//! On the hour a sea monster may surface near the player (never with the
+//! "Historical waters" modifier) and roam until the High Seas are left. A
+//! ship that strays within `MONSTER_ENCOUNTER_RADIUS` of it must fight. In
+//! battle the enemy ships the encounter would have brought are sent away and
+//! the monster rises in their place: a body that swims after the player's
+//! ships, ringed by tentacles (a leviathan's coils) that reach for any ship
+//! within reach and hold her fast, crushing her hull, until their grip gives
+//! or they are shot away. The battle is won when the body is slain; its hoard
+//! is offered on the loot screen, and the first of each kind slain is
+//! remembered in the profile.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use rand::Rng;
+
+use crate::components::sea_monster::{Grabbed, SeaMonster, Tentacle, CRUSH_SECONDS};
+use crate::components::{
+    CombatEntity, FactionId, Health, HighSeasEntity, Player, PlayerOwned, Projectile, Ship, TargetComponent, AI,
+};
+use crate::events::{CombatTriggeredEvent, ShipHitEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::{EncounterCooldown, HighSeasPlayer};
+use crate::resources::{
+    Journal, JournalCategory, MapData, MetaProfile, MonsterKind, RunSettings, SeaMonsterState, SpoilsSource,
+    VictorySpoils, WorldClock, MONSTER_ENCOUNTER_RADIUS, MONSTER_SURFACING_DISTANCE, SEA_MONSTER_CHANCE,
+};
+use crate::systems::{combat_ai_system, record_ship_hits, ship_physics_system, spawn_combat_enemies, world_tick_system};
+use crate::utils::pathfinding::{tile_to_world, world_to_tile};
+
+/// How fast a sea monster roams the High Seas (world units per second).
+const ROAM_SPEED: f32 = 35.0;
+/// How sharply a roaming monster wanders off its course (radians per second).
+const ROAM_WANDER: f32 = 0.3;
+/// Radius of a sea monster's back showing above the water on the High Seas.
+const SURFACED_RADIUS: f32 = 22.0;
+/// Where the monster rises in the arena, ahead of the player's ship.
+const ARENA_POSITION: Vec2 = Vec2::new(0.0, 380.0);
+/// Share of its reach at which the monster stops swimming closer.
+const STANDOFF_SHARE: f32 = 0.6;
+/// How fast a tentacle reaches for a ship, or draws back (world units per second).
+const LASH_SPEED: f32 = 220.0;
+/// How close a tentacle's tip must come to a ship to seize her.
+const SEIZE_RADIUS: f32 = 30.0;
+/// Radius of a tentacle's tip, and of the target it makes for shot.
+const TIP_RADIUS: f32 = 16.0;
+/// Share of shot aimed at rigging or rudder that does any harm to the body.
+const GLANCING_SHARE: f32 = 0.25;
+
+const KRAKEN_COLOR: Color = Color::srgb(0.45, 0.16, 0.22);
+const LEVIATHAN_COLOR: Color = Color::srgb(0.14, 0.32, 0.3);
+
+/// Plugin for sea monsters on the High Seas and in battle.
+pub struct SeaMonstersPlugin;
+
+impl Plugin for SeaMonstersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SeaMonsterState>()
+            .add_systems(
+                FixedUpdate,
+                surface_sea_monsters.after(world_tick_system).run_if(in_state(GameState::HighSeas)),
+            )
+            .add_systems(
+                Update,
+                (roam_sea_monsters, monster_encounter_system).chain().run_if(in_state(GameState::HighSeas)),
+            )
+            // Encounters are read whatever the state, as combat may begin the same frame
+            .add_systems(Update, note_monster_encounter)
+            .add_systems(OnEnter(GameState::Combat), raise_sea_monster.after(spawn_combat_enemies))
+            .add_systems(
+                FixedUpdate,
+                hold_grabbed_ships
+                    .after(ship_physics_system)
+                    .after(combat_ai_system)
+                    .run_if(in_state(GameState::Combat)),
+            )
+            .add_systems(
+                Update,
+                (swim_monsters, reach_tentacles, monster_hit_system, monster_wounds_system, draw_tentacles)
+                    .chain()
+                    .before(record_ship_hits)
+                    .run_if(in_state(GameState::Combat)),
+            )
+            .add_systems(OnExit(GameState::Combat), end_monster_encounter)
+            .add_systems(OnEnter(GameState::MainMenu), end_monster_encounter);
+    }
+}
+
+fn monster_color(kind: MonsterKind) -> Color {
+    match kind {
+        MonsterKind::Kraken => KRAKEN_COLOR,
+        MonsterKind::Leviathan => LEVIATHAN_COLOR,
+    }
+}
+
+/// Where a tentacle lies coiled beside its body when it is not reaching for a ship.
+pub fn rest_position(body: Vec2, kind: MonsterKind, bearing: f32) -> Vec2 {
+    body + Vec2::from_angle(bearing) * (kind.body_radius() + TIP_RADIUS)
+}
+
+/// A tentacle tip at `tip` moved up to `step` toward `target`.
+pub fn lash_toward(tip: Vec2, target: Vec2, step: f32) -> Vec2 {
+    let offset = target - tip;
+    if offset.length() <= step {
+        target
+    } else {
+        tip + offset.normalize() * step
+    }
+}
+
+/// Rolls for a sea monster near the player on the hour.
+fn surface_sea_monsters(
+    mut commands: Commands,
+    world_clock: Res<WorldClock>,
+    run_settings: Res<RunSettings>,
+    map_data: Res<MapData>,
+    player_query: Query<&Transform, (With<Player>, With<HighSeasPlayer>)>,
+    monsters: Query<(), With<SeaMonster>>,
+) {
+    if world_clock.tick != 0 || run_settings.modifiers.historical_waters || !monsters.is_empty() {
+        return;
+    }
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let mut rng = rand::thread_rng();
+    if !rng.gen_bool(SEA_MONSTER_CHANCE) {
+        return;
+    }
+
+    let bearing = rng.gen_range(0.0..std::f32::consts::TAU);
+    let sighted = player_transform.translation.truncate() + Vec2::from_angle(bearing) * MONSTER_SURFACING_DISTANCE;
+    let tile = world_to_tile(sighted, map_data.width, map_data.height);
+    let Some(water) = map_data.nearest_navigable(tile, 6) else {
+        return;
+    };
+    let position = tile_to_world(water, map_data.width, map_data.height);
+    let kind = MonsterKind::roll(&mut rng);
+    let back = shapes::Circle { radius: SURFACED_RADIUS, center: Vec2::ZERO };
+    commands.spawn((
+        Name::new(kind.name()),
+        SeaMonster { kind },
+        ShapeBundle {
+            path: GeometryBuilder::build_as(&back),
+            transform: Transform::from_xyz(position.x, position.y, 1.0)
+                .with_rotation(Quat::from_rotation_z(rng.gen_range(0.0..std::f32::consts::TAU))),
+            ..default()
+        },
+        Fill::color(monster_color(kind)),
+        Stroke::new(Color::BLACK, 2.0),
+        HighSeasEntity,
+    ));
+    info!("{} surfaces at ({:.0}, {:.0})", kind.name(), position.x, position.y);
+}
+
+/// Sea monsters wander the open water, turning back from the shallows.
+fn roam_sea_monsters(
+    time: Res<Time>,
+    map_data: Res<MapData>,
+    mut monsters: Query<&mut Transform, (With<SeaMonster>, With<HighSeasEntity>)>,
+) {
+    let t = time.elapsed_secs();
+    for mut transform in &mut monsters {
+        let wander = (t * 0.4 + transform.translation.x * 0.01).sin() * ROAM_WANDER * time.delta_secs();
+        transform.rotate_z(wander);
+        let forward = (transform.rotation * Vec3::Y).truncate();
+        let next = transform.translation.truncate() + forward * ROAM_SPEED * time.delta_secs();
+        let tile = world_to_tile(next, map_data.width, map_data.height);
+        if map_data.in_bounds(tile.x, tile.y) && map_data.is_navigable(tile.x as u32, tile.y as u32) {
+            transform.translation = next.extend(transform.translation.z);
+        } else {
+            transform.rotate_z(std::f32::consts::FRAC_PI_2);
+        }
+    }
+}
+
+/// A sea monster attacks the player's ship when she comes within reach.
+fn monster_encounter_system(
+    player_query: Query<&Transform, (With<Player>, With<HighSeasPlayer>)>,
+    monsters: Query<(Entity, &Transform), With<SeaMonster>>,
+    encounter_cooldown: Res<EncounterCooldown>,
+    mut combat_events: EventWriter<CombatTriggeredEvent>,
+) {
+    if encounter_cooldown.active {
+        return;
+    }
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+    if let Some((monster, _)) = monsters
+        .iter()
+        .find(|(_, transform)| transform.translation.truncate().distance(player_pos) <= MONSTER_ENCOUNTER_RADIUS)
+    {
+        combat_events.send(CombatTriggeredEvent {
+            enemy_entity: monster,
+            enemy_faction: FactionId::Pirates,
+        });
+    }
+}
+
+/// Remembers that the coming battle is against a sea monster.
+fn note_monster_encounter(
+    mut events: EventReader<CombatTriggeredEvent>,
+    monsters: Query<&SeaMonster>,
+    mut state: ResMut<SeaMonsterState>,
+) {
+    for event in events.read() {
+        if let Ok(monster) = monsters.get(event.enemy_entity) {
+            state.engaged = Some(monster.kind);
+        }
+    }
+}
+
+/// Raises the monster in place of the enemy ships the encounter brought.
+fn raise_sea_monster(
+    mut commands: Commands,
+    state: Res<SeaMonsterState>,
+    enemies: Query<Entity, (With<AI>, With<CombatEntity>, Without<PlayerOwned>)>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    let Some(kind) = state.engaged else {
+        return;
+    };
+    for enemy in &enemies {
+        commands.entity(enemy).despawn_recursive();
+    }
+
+    let body = shapes::Circle { radius: kind.body_radius(), center: Vec2::ZERO };
+    let monster = commands
+        .spawn((
+            Name::new(kind.name()),
+            SeaMonster { kind },
+            Health::new(0.0, 0.0, kind.hull()),
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&body),
+                transform: Transform::from_translation(ARENA_POSITION.extend(1.0)),
+                ..default()
+            },
+            Fill::color(monster_color(kind)),
+            Stroke::new(Color::BLACK, 3.0),
+            RigidBody::Kinematic,
+            Collider::circle(kind.body_radius()),
+            LinearVelocity(Vec2::ZERO),
+            CombatEntity,
+        ))
+        .id();
+
+    let tip = shapes::Circle { radius: TIP_RADIUS, center: Vec2::ZERO };
+    for index in 0..kind.limbs() {
+        let bearing = std::f32::consts::TAU * index as f32 / kind.limbs() as f32;
+        let position = rest_position(ARENA_POSITION, kind, bearing);
+        commands.spawn((
+            Name::new(format!("{} {}", kind.name(), kind.limb_name())),
+            Tentacle::new(monster, bearing),
+            Health::new(0.0, 0.0, kind.limb_hull()),
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&tip),
+                transform: Transform::from_translation(position.extend(1.5)),
+                ..default()
+            },
+            Fill::color(monster_color(kind)),
+            Stroke::new(Color::BLACK, 2.0),
+            RigidBody::Kinematic,
+            Collider::circle(TIP_RADIUS),
+            Sensor,
+            CombatEntity,
+        ));
+    }
+
+    journal.record_as(
+        &world_clock,
+        JournalCategory::Combat,
+        format!(
+            "{} rose from the deep beside us. Shoot away a {} that takes hold of a ship, and put the guns into its body.",
+            kind.name(),
+            kind.limb_name().to_lowercase()
+        ),
+    );
+    info!("{} rises with {} {}s", kind.name(), kind.limbs(), kind.limb_name().to_lowercase());
+}
+
+/// The monster swims after the nearest of the player's ships until she is within its reach.
+fn swim_monsters(
+    mut monsters: Query<(&Transform, &SeaMonster, &mut LinearVelocity)>,
+    targets: Query<&Transform, (With<Ship>, Or<(With<Player>, With<PlayerOwned>)>)>,
+) {
+    for (transform, monster, mut velocity) in &mut monsters {
+        let position = transform.translation.truncate();
+        let nearest = targets
+            .iter()
+            .map(|target| target.translation.truncate())
+            .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)));
+        velocity.0 = match nearest {
+            Some(target) if target.distance(position) > monster.kind.reach() * STANDOFF_SHARE => {
+                (target - position).normalize_or_zero() * monster.kind.swim_speed()
+            }
+            _ => Vec2::ZERO,
+        };
+    }
+}
+
+/// Tentacles reach for the player's ships within reach, hold them fast and
+/// crush them blow by blow, and let go when their grip gives.
+fn reach_tentacles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut ship_hit_events: EventWriter<ShipHitEvent>,
+    mut tentacles: Query<(Entity, &mut Tentacle, &mut Transform)>,
+    bodies: Query<(&Transform, &SeaMonster), Without<Tentacle>>,
+    mut ships: Query<
+        (Entity, &Transform, &mut Health, Has<Grabbed>),
+        (With<Ship>, Or<(With<Player>, With<PlayerOwned>)>, Without<Tentacle>),
+    >,
+) {
+    let step = LASH_SPEED * time.delta_secs();
+    for (entity, mut tentacle, mut transform) in &mut tentacles {
+        let Ok((body_transform, monster)) = bodies.get(tentacle.monster) else {
+            continue;
+        };
+        let kind = monster.kind;
+        let body = body_transform.translation.truncate();
+        let tip = transform.translation.truncate();
+
+        let target = if let Some(held) = tentacle.holding {
+            tentacle.grip.tick(time.delta());
+            tentacle.crush.tick(time.delta());
+            match ships.get_mut(held) {
+                Ok((_, ship_transform, mut health, _)) if !tentacle.grip.finished() => {
+                    let position = ship_transform.translation.truncate();
+                    let blows = tentacle.crush.times_finished_this_tick();
+                    if blows > 0 {
+                        let damage = kind.crush_per_second() * CRUSH_SECONDS * blows as f32;
+                        health.hull -= damage;
+                        ship_hit_events.send(ShipHitEvent {
+                            ship_entity: held,
+                            hit_position: position,
+                            damage,
+                            attacker: tentacle.monster,
+                            component: TargetComponent::Hull,
+                        });
+                    }
+                    position
+                }
+                Ok(_) => {
+                    commands.entity(held).remove::<Grabbed>();
+                    tentacle.release();
+                    rest_position(body, kind, tentacle.bearing)
+                }
+                Err(_) => {
+                    tentacle.release();
+                    rest_position(body, kind, tentacle.bearing)
+                }
+            }
+        } else {
+            tentacle.recover.tick(time.delta());
+            let prey = ships
+                .iter()
+                .filter(|(_, _, _, grabbed)| !grabbed)
+                .map(|(ship, ship_transform, _, _)| (ship, ship_transform.translation.truncate()))
+                .filter(|(_, position)| position.distance(body) <= kind.reach())
+                .min_by(|a, b| a.1.distance_squared(tip).total_cmp(&b.1.distance_squared(tip)));
+            match prey {
+                Some((ship, position)) if tentacle.is_ready() => {
+                    if position.distance(tip) <= SEIZE_RADIUS {
+                        tentacle.seize(ship);
+                        commands.entity(ship).try_insert(Grabbed { by: entity });
+                        info!("A {} seizes a ship!", kind.limb_name().to_lowercase());
+                    }
+                    position
+                }
+                _ => rest_position(body, kind, tentacle.bearing),
+            }
+        };
+        transform.translation = lash_toward(tip, target, step).extend(transform.translation.z);
+    }
+}
+
+/// Ships in a monster's grip are held fast where they were seized.
+fn hold_grabbed_ships(mut ships: Query<&mut LinearVelocity, (With<Ship>, With<Grabbed>)>) {
+    for mut velocity in &mut ships {
+        velocity.0 = Vec2::ZERO;
+    }
+}
+
+/// The player's shot wounds the monster's body and tentacles. Shot into the
+/// rigging or at the rudder only glances off its hide, but any shot tells on
+/// a tentacle.
+fn monster_hit_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<Collision>,
+    projectiles: Query<&Projectile>,
+    mut wounded: Query<(&mut Health, Has<Tentacle>), Or<(With<SeaMonster>, With<Tentacle>)>>,
+    player_side: Query<(), (With<Ship>, Or<(With<Player>, With<PlayerOwned>)>)>,
+) {
+    for Collision(contacts) in collision_events.read() {
+        let (shot, target) = if projectiles.contains(contacts.entity1) && wounded.contains(contacts.entity2) {
+            (contacts.entity1, contacts.entity2)
+        } else if projectiles.contains(contacts.entity2) && wounded.contains(contacts.entity1) {
+            (contacts.entity2, contacts.entity1)
+        } else {
+            continue;
+        };
+        let (Ok(projectile), Ok((mut health, is_tentacle))) = (projectiles.get(shot), wounded.get_mut(target)) else {
+            continue;
+        };
+        if !player_side.contains(projectile.source) {
+            continue;
+        }
+
+        health.hull -= match projectile.target {
+            TargetComponent::Hull => projectile.damage,
+            _ if is_tentacle => projectile.damage,
+            TargetComponent::Sails | TargetComponent::Rudder => projectile.damage * GLANCING_SHARE,
+        };
+        commands.entity(shot).despawn_recursive();
+    }
+}
+
+/// Tentacles shot away let go of their ship; a slain monster sinks with all
+/// its tentacles, and its hoard is offered on the loot screen.
+fn monster_wounds_system(
+    mut commands: Commands,
+    tentacles: Query<(Entity, &Tentacle, &Health)>,
+    bodies: Query<(Entity, &SeaMonster, &Health)>,
+    mut spoils: ResMut<VictorySpoils>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+    mut profile: ResMut<MetaProfile>,
+) {
+    let slain: Vec<(Entity, MonsterKind)> = bodies
+        .iter()
+        .filter(|(_, _, health)| health.is_destroyed())
+        .map(|(entity, monster, _)| (entity, monster.kind))
+        .collect();
+
+    for (entity, tentacle, health) in &tentacles {
+        let body_slain = slain.iter().any(|(body, _)| *body == tentacle.monster);
+        if !health.is_destroyed() && !body_slain {
+            continue;
+        }
+        if let Some(held) = tentacle.holding {
+            if let Some(mut ship) = commands.get_entity(held) {
+                ship.remove::<Grabbed>();
+            }
+        }
+        commands.entity(entity).despawn_recursive();
+    }
+
+    for (entity, kind) in slain {
+        commands.entity(entity).despawn_recursive();
+        let (gold, hold) = kind.hoard();
+        spoils.sources.push(SpoilsSource {
+            name: format!("{}'s hoard", kind.name()),
+            surrendered: false,
+            gold,
+            hold,
+            prize: None,
+        });
+        journal.record_as(
+            &world_clock,
+            JournalCategory::Combat,
+            format!(
+                "Slew {}. It sank in a welter of foam, and up with it came the gold and stores of the ships it had dragged down.",
+                kind.name()
+            ),
+        );
+        if profile.record_monster_slain(kind) {
+            if let Err(e) = profile.save_to_file() {
+                error!("Failed to save profile after slaying {}: {}", kind.name(), e);
+            }
+        }
+        info!("{} is slain!", kind.name());
+    }
+}
+
+/// Draws each tentacle as an arm from the body out to its tip.
+fn draw_tentacles(
+    tentacles: Query<(&Tentacle, &Transform)>,
+    bodies: Query<(&Transform, &SeaMonster), Without<Tentacle>>,
+    mut gizmos: Gizmos,
+) {
+    for (tentacle, transform) in &tentacles {
+        let Ok((body_transform, monster)) = bodies.get(tentacle.monster) else {
+            continue;
+        };
+        let body = body_transform.translation.truncate();
+        let tip = transform.translation.truncate();
+        let color = monster_color(monster.kind);
+        // A slight curl, so the arms do not look like spars
+        let middle = (body + tip) / 2.0 + (tip - body).perp().normalize_or_zero() * 12.0;
+        gizmos.line_2d(body, middle, color);
+        gizmos.line_2d(middle, tip, color);
+    }
+}
+
+fn end_monster_encounter(mut state: ResMut<SeaMonsterState>) {
+    state.engaged = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tentacles_reach_no_faster_than_they_lash() {
+        let body = Vec2::ZERO;
+        let rest = rest_position(body, MonsterKind::Kraken, 0.0);
+        assert_eq!(rest, Vec2::new(MonsterKind::Kraken.body_radius() + TIP_RADIUS, 0.0));
+
+        let ship = Vec2::new(200.0, 0.0);
+        let reached = lash_toward(rest, ship, 50.0);
+        assert_eq!(reached.distance(rest), 50.0);
+        // Within a step the tip closes right up
+        assert_eq!(lash_toward(Vec2::new(190.0, 0.0), ship, 50.0), ship);
+    }
+}
@@ -0,0 +1,307 @@
+//! The settings window, and the pause menu it is reached from in play.
+//!
+//! `GameSettings` is read at startup. The display settings (window mode,
+//! size, vsync) and the UI layout and scale (through `UiLayout`) are applied here
+//! whenever they change. Volumes, camera shake and the autosave interval are
+//! read where they are used: `MusicPlugin`, `SpatialAudioPlugin`,
+//! `camera_shake_system` and `PersistencePlugin`, and the harbour approach
+//! option by `HarborApproachPlugin`. The sound plugins are only built with the
+//! `audio` feature; without it the window says so and greys out the volumes. The window opens from the
+//! main menu, or from the pause menu that Escape brings up on the High Seas
+//! and in battle. Settings are saved to file when the window closes.
+
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, WindowMode};
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::plugins::core::GameState;
+use crate::resources::{DisplayMode, GameSettings, LayoutPreference, UiLayout, AUTOSAVE_CHOICES, RESOLUTIONS, UI_SCALE_RANGE};
+
+/// Whether this build plays sound. The `audio` feature is off by default, as
+/// the music and effects it plays are not in the repository.
+const SOUND_BUILT: bool = cfg!(feature = "audio");
+
+/// Plugin for the settings window and the pause menu.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameSettings>()
+            .init_resource::<SettingsWindow>()
+            .init_resource::<PauseMenu>()
+            .add_systems(Startup, load_settings)
+            .add_systems(
+                Update,
+                (
+                    toggle_pause_menu.after(EguiSet::InitContexts),
+                    pause_menu_ui.after(toggle_pause_menu),
+                )
+                    .run_if(in_state(GameState::HighSeas).or(in_state(GameState::Combat))),
+            )
+            .add_systems(
+                Update,
+                (apply_display_settings, settings_ui.after(EguiSet::InitContexts).after(pause_menu_ui)),
+            )
+            .add_systems(OnExit(GameState::HighSeas), close_pause_menu)
+            .add_systems(OnExit(GameState::Combat), close_pause_menu);
+    }
+}
+
+/// Whether the settings window is showing.
+#[derive(Resource, Debug, Default)]
+pub struct SettingsWindow {
+    pub open: bool,
+}
+
+/// The pause menu on the High Seas and in battle.
+#[derive(Resource, Debug, Default)]
+pub struct PauseMenu {
+    pub open: bool,
+    /// Whether the menu stopped `Time<Virtual>`, and so should start it again.
+    /// A chase or a sea event that already holds the world still keeps it.
+    stopped_world: bool,
+}
+
+impl PauseMenu {
+    fn pause(&mut self, time: &mut Time<Virtual>) {
+        self.open = true;
+        self.stopped_world = !time.is_paused();
+        if self.stopped_world {
+            time.pause();
+        }
+    }
+
+    /// Takes over the world something else stopped and has let go of while
+    /// the menu is open, to start it again on resuming.
+    pub fn hold_world(&mut self) {
+        self.stopped_world = true;
+    }
+
+    fn resume(&mut self, time: &mut Time<Virtual>) {
+        if self.stopped_world {
+            time.unpause();
+        }
+        *self = Self::default();
+    }
+}
+
+fn load_settings(mut commands: Commands) {
+    commands.insert_resource(GameSettings::load_from_file());
+}
+
+/// Applies the window and UI scale settings when they change.
+fn apply_display_settings(
+    settings: Res<GameSettings>,
+    mut layout: ResMut<UiLayout>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    // Only write on change so `apply_ui_layout` restyles once per change
+    if layout.preference != settings.ui_layout {
+        layout.preference = settings.ui_layout;
+    }
+    if layout.scale != settings.ui_scale {
+        layout.scale = settings.ui_scale;
+    }
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+
+    let mode = match settings.display_mode {
+        DisplayMode::Windowed => WindowMode::Windowed,
+        DisplayMode::Borderless => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+        DisplayMode::Fullscreen => WindowMode::Fullscreen(MonitorSelection::Current),
+    };
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    let present_mode = if settings.vsync { PresentMode::AutoVsync } else { PresentMode::AutoNoVsync };
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+    let (width, height) = (settings.resolution.0 as f32, settings.resolution.1 as f32);
+    if settings.display_mode == DisplayMode::Windowed
+        && (window.resolution.width() != width || window.resolution.height() != height)
+    {
+        window.resolution.set(width, height);
+    }
+}
+
+/// Escape opens the pause menu, closes the settings window over it, or resumes.
+fn toggle_pause_menu(
+    mut contexts: EguiContexts,
+    mut menu: ResMut<PauseMenu>,
+    mut settings_window: ResMut<SettingsWindow>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if !contexts.ctx_mut().input(|i| i.key_pressed(egui::Key::Escape)) {
+        return;
+    }
+    if settings_window.open {
+        settings_window.open = false;
+    } else if menu.open {
+        menu.resume(&mut time);
+    } else {
+        menu.pause(&mut time);
+    }
+}
+
+/// The pause menu: resume, or open the settings.
+fn pause_menu_ui(
+    mut contexts: EguiContexts,
+    mut menu: ResMut<PauseMenu>,
+    mut settings_window: ResMut<SettingsWindow>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if !menu.open {
+        return;
+    }
+    let mut resume = false;
+    egui::Window::new("⏸ Paused")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .resizable(false)
+        .collapsible(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.vertical_centered(|ui| {
+                resume = ui.button("▶ Resume").clicked();
+                if ui.button("⚙ Settings").clicked() {
+                    settings_window.open = true;
+                }
+                ui.weak("Esc to resume");
+            });
+        });
+    if resume {
+        menu.resume(&mut time);
+    }
+}
+
+/// Leaving the High Seas or a battle (a quickload, a battle joined) puts the
+/// pause menu away.
+fn close_pause_menu(mut menu: ResMut<PauseMenu>, mut time: ResMut<Time<Virtual>>) {
+    if menu.open {
+        menu.resume(&mut time);
+    }
+}
+
+/// The settings window. Changes take effect at once and are saved to file
+/// when the window closes.
+fn settings_ui(
+    mut contexts: EguiContexts,
+    mut window: ResMut<SettingsWindow>,
+    mut settings: ResMut<GameSettings>,
+    mut was_open: Local<bool>,
+) {
+    if *was_open && !window.open {
+        if let Err(e) = settings.save_to_file() {
+            error!("Failed to save settings: {}", e);
+        }
+    }
+    *was_open = window.open;
+    if !window.open {
+        return;
+    }
+
+    let mut edited = settings.clone();
+    let mut open = true;
+    let mut done = false;
+    egui::Window::new("⚙ Settings")
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .resizable(false)
+        .collapsible(false)
+        .show(contexts.ctx_mut(), |ui| {
+            render_settings(ui, &mut edited);
+            ui.separator();
+            done = ui.button("Done").clicked();
+        });
+    // Only write on change so the settings are applied once per change
+    if edited != *settings {
+        *settings = edited;
+    }
+    if !open || done {
+        window.open = false;
+    }
+}
+
+fn percent_slider(value: &mut f32) -> egui::Slider<'_> {
+    egui::Slider::new(value, 0.0..=1.0).custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
+}
+
+fn render_settings(ui: &mut egui::Ui, settings: &mut GameSettings) {
+    egui::Grid::new("settings_grid").num_columns(2).spacing([16.0, 8.0]).show(ui, |ui| {
+        ui.strong("Display");
+        ui.horizontal(|ui| {
+            for mode in DisplayMode::ALL {
+                ui.selectable_value(&mut settings.display_mode, mode, mode.name());
+            }
+        });
+        ui.end_row();
+
+        ui.label("Window size");
+        ui.add_enabled_ui(settings.display_mode == DisplayMode::Windowed, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for resolution in RESOLUTIONS {
+                    let text = format!("{}×{}", resolution.0, resolution.1);
+                    ui.selectable_value(&mut settings.resolution, resolution, text);
+                }
+            });
+        });
+        ui.end_row();
+
+        ui.label("Vsync");
+        ui.checkbox(&mut settings.vsync, "Wait for the display's refresh");
+        ui.end_row();
+
+        ui.strong("Interface");
+        ui.horizontal(|ui| {
+            for preference in LayoutPreference::ALL {
+                ui.selectable_value(&mut settings.ui_layout, preference, preference.name());
+            }
+        });
+        ui.end_row();
+
+        ui.label("UI scale");
+        ui.add(
+            egui::Slider::new(&mut settings.ui_scale, UI_SCALE_RANGE.0..=UI_SCALE_RANGE.1)
+                .step_by(0.05)
+                .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+        );
+        ui.end_row();
+
+        ui.strong("Sound");
+        ui.add_enabled(SOUND_BUILT, percent_slider(&mut settings.master_volume).text("Master"));
+        ui.end_row();
+        ui.label("");
+        ui.add_enabled(SOUND_BUILT, percent_slider(&mut settings.music_volume).text("Music"));
+        ui.end_row();
+        ui.label("");
+        ui.add_enabled(SOUND_BUILT, percent_slider(&mut settings.effects_volume).text("Effects"));
+        ui.end_row();
+        if !SOUND_BUILT {
+            ui.label("");
+            ui.weak("This build has no sound: music and effects are not included yet.");
+            ui.end_row();
+        }
+
+        ui.label("Screen shake");
+        ui.add(percent_slider(&mut settings.screen_shake));
+        ui.end_row();
+
+        ui.label("Autosave at sea");
+        ui.horizontal(|ui| {
+            for minutes in AUTOSAVE_CHOICES {
+                let text = if minutes == 0 { "Off".to_string() } else { format!("{} min", minutes) };
+                ui.selectable_value(&mut settings.autosave_minutes, minutes, text);
+            }
+        });
+        ui.end_row();
+
+        ui.label("Harbour approach");
+        ui.checkbox(&mut settings.harbor_approach, "Show the scene when docking at a town or city");
+        ui.end_row();
+    });
+    ui.weak("Auto picks the compact layout on small screens such as the Steam Deck.");
+    ui.weak("Autosaves are also made on coming into and leaving port.");
+}
@@ -0,0 +1,210 @@
+//! Buying new hulls at port and selling fleet ships.
+//!
+//! See `resources::ship_market`. Deals are made from the Shipyard tab: a
+//! bought hull joins `PlayerFleet` with an empty hold, and a sold ship leaves
+//! it, her fitted upgrades stripped out and stowed first. A ship with cargo
+//! aboard cannot be sold until her hold is emptied.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::components::cargo::{Gold, GoodType};
+use crate::components::ship::{Player, Ship};
+use crate::events::{ShipMarketAction, ShipMarketEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::CurrentPort;
+use crate::resources::{
+    new_hull, FactionRegistry, GoodsTable, PlayerFleet, PortEvents, PortMarkets, ShipData, ShipDefinitions, ShipMarketTerms,
+    UpgradeInventory,
+};
+
+/// Plugin for the ship market.
+pub struct ShipMarketPlugin;
+
+impl Plugin for ShipMarketPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ShipMarketEvent>()
+            .add_systems(Update, ship_market_system.run_if(in_state(GameState::Port)));
+    }
+}
+
+/// The shipyard's terms at the current port: its trade terms by the player's
+/// standing, and the timber price in its market (see `PortMarkets`) against
+/// the base price in `goods`.
+pub fn current_ship_terms(
+    current_port: &CurrentPort,
+    factions: &FactionRegistry,
+    markets: &PortMarkets,
+    goods: &GoodsTable,
+) -> ShipMarketTerms {
+    let timber_ratio = current_port
+        .market(markets)
+        .and_then(|market| market.inventory.get_good(&GoodType::Timber))
+        .map(|item| item.price / goods.base_price(GoodType::Timber));
+    ShipMarketTerms::new(current_port.trade_terms(factions), timber_ratio)
+}
+
+/// Everything the shipyard's terms at the current port depend on, and the
+/// ship classes it deals in.
+#[derive(SystemParam)]
+struct Shipyard<'w> {
+    ships: Res<'w, ShipDefinitions>,
+    current_port: Res<'w, CurrentPort>,
+    factions: Res<'w, FactionRegistry>,
+    goods: Res<'w, GoodsTable>,
+    markets: Res<'w, PortMarkets>,
+}
+
+impl Shipyard<'_> {
+    fn terms(&self) -> ShipMarketTerms {
+        current_ship_terms(&self.current_port, &self.factions, &self.markets, &self.goods)
+    }
+}
+
+/// Whether `ship` has goods aboard, and so cannot be sold.
+pub fn has_cargo_aboard(ship: &ShipData) -> bool {
+    ship.cargo.as_ref().is_some_and(|cargo| cargo.total_units() > 0)
+}
+
+/// Buys and sells ships at the current port, unless its dockworkers are on strike.
+fn ship_market_system(
+    mut events: EventReader<ShipMarketEvent>,
+    shipyard: Shipyard,
+    mut player_fleet: ResMut<PlayerFleet>,
+    mut upgrades: ResMut<UpgradeInventory>,
+    port_events: Res<PortEvents>,
+    mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
+) {
+    let on_strike = port_events.docks_closed(shipyard.current_port.position);
+    for event in events.read() {
+        if on_strike {
+            info!("Ship market deal refused: the dockworkers are on strike");
+            continue;
+        }
+        let Ok(mut gold) = player_query.get_single_mut() else {
+            warn!("Ship market deal failed: Player not found");
+            continue;
+        };
+        let terms = shipyard.terms();
+
+        match &event.action {
+            ShipMarketAction::Buy(class) => {
+                let Some(ship) = shipyard.ships.get(class).filter(|ship| ship.for_sale) else {
+                    warn!("Ship purchase failed: No {} for sale", class);
+                    continue;
+                };
+                let price = terms.hull_price(ship);
+                if !gold.spend(price) {
+                    warn!("Ship purchase failed: Insufficient gold");
+                    continue;
+                }
+                let name = format!("{} No. {}", ship.name, player_fleet.ships.len() + 1);
+                info!("Bought {} for {}g", name, price);
+                player_fleet.ships.push(new_hull(ship, name));
+            }
+            &ShipMarketAction::Sell(index) => {
+                let Some(ship) = player_fleet.ships.get(index) else {
+                    continue;
+                };
+                if has_cargo_aboard(ship) {
+                    warn!("Ship sale failed: {} has cargo aboard", ship.name);
+                    continue;
+                }
+                let price = terms.sale_price(ship, &shipyard.ships);
+                let ship = player_fleet.ships.remove(index);
+                upgrades.stowed.extend(ship.upgrades.iter().copied());
+                gold.add(price);
+                info!("Sold {} for {}g", ship.name, price);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::cargo::Cargo;
+
+    #[test]
+    fn test_ships_with_cargo_cannot_be_sold() {
+        let mut ship = ShipData::default();
+        assert!(!has_cargo_aboard(&ship));
+        let mut hold = Cargo::new(50);
+        hold.add(GoodType::Rum, 5);
+        ship.cargo = Some(hold);
+        assert!(has_cargo_aboard(&ship));
+    }
+
+    #[test]
+    fn test_buying_and_selling_ships() {
+        use crate::components::upgrade::UpgradeKind;
+
+        let mut app = App::new();
+        app.add_event::<ShipMarketEvent>()
+            .init_resource::<ShipDefinitions>()
+            .init_resource::<CurrentPort>()
+            .init_resource::<FactionRegistry>()
+            .init_resource::<GoodsTable>()
+            .init_resource::<PlayerFleet>()
+            .init_resource::<UpgradeInventory>()
+            .init_resource::<PortEvents>()
+            .init_resource::<PortMarkets>()
+            .add_systems(Update, ship_market_system);
+        let player = app.world_mut().spawn((Player, Ship, Gold(2000))).id();
+        let deal = |app: &mut App, action: ShipMarketAction| {
+            app.world_mut().send_event(ShipMarketEvent { action });
+            app.update();
+        };
+        let gold = |app: &App| app.world().get::<Gold>(player).unwrap().0;
+
+        // A schooner at a neutral port with no timber in the market
+        deal(&mut app, ShipMarketAction::Buy("schooner".to_string()));
+        assert_eq!(gold(&app), 500);
+        let fleet = &app.world().resource::<PlayerFleet>().ships;
+        assert_eq!((fleet[0].name.as_str(), fleet[0].class.as_str()), ("Schooner No. 1", "schooner"));
+
+        // Too dear, not built at the yards, or no such class
+        deal(&mut app, ShipMarketAction::Buy("sloop".to_string()));
+        deal(&mut app, ShipMarketAction::Buy("raft".to_string()));
+        deal(&mut app, ShipMarketAction::Buy("man_o_war".to_string()));
+        assert_eq!(gold(&app), 500);
+        assert_eq!(app.world().resource::<PlayerFleet>().ships.len(), 1);
+
+        // Sold, she fetches her resale share at the port's selling rate, and
+        // her upgrades are stowed
+        app.world_mut().resource_mut::<PlayerFleet>().ships[0].upgrades.push(UpgradeKind::ExpandedHold);
+        deal(&mut app, ShipMarketAction::Sell(0));
+        let resale = (1500.0 * crate::resources::RESALE_SHARE * crate::resources::SELL_PRICE_MODIFIER).round() as u32;
+        assert_eq!(gold(&app), 500 + resale);
+        assert!(app.world().resource::<PlayerFleet>().ships.is_empty());
+        assert_eq!(app.world().resource::<UpgradeInventory>().stowed, vec![UpgradeKind::ExpandedHold]);
+    }
+
+    #[test]
+    fn test_timber_stock_sets_hull_prices() {
+        use crate::components::port::Inventory;
+        use crate::components::port_key;
+        use crate::resources::{PortMarket, WorldClock};
+        use crate::systems::price_at_stock;
+
+        let goods = GoodsTable::default();
+        let factions = FactionRegistry::default();
+        let position = Vec2::new(320.0, 160.0);
+        let current_port = CurrentPort { position: Some(position), ..default() };
+        let galleon = ShipDefinitions::default().get("galleon").cloned().unwrap();
+        let hull_price_with_timber = |quantity: u32| {
+            let price = price_at_stock(&goods, &GoodType::Timber, quantity, 1.0, WorldClock::default().season());
+            let mut inventory = Inventory::new();
+            inventory.set_good(GoodType::Timber, quantity, price);
+            let mut markets = PortMarkets::default();
+            markets.markets.insert(port_key(position), PortMarket { inventory, produces: None, consumes: None });
+            current_ship_terms(&current_port, &factions, &markets, &goods).hull_price(&galleon)
+        };
+
+        let unknown = current_ship_terms(&current_port, &factions, &PortMarkets::default(), &goods).hull_price(&galleon);
+        let scarce = hull_price_with_timber(2);
+        let plentiful = hull_price_with_timber(goods.base_quantity(GoodType::Timber) * 4);
+        assert!(scarce > unknown, "{} should be dearer than {}", scarce, unknown);
+        assert!(plentiful < unknown, "{} should be cheaper than {}", plentiful, unknown);
+    }
+}
@@ -0,0 +1,247 @@
+//! Positional sound effects and ambience.
+//!
+//! The main camera carries the `SpatialListener`, so everything is heard from
+//! where the player is looking: distant broadsides are faint and panned, each
+//! port on the world map hums with harbor ambience as you sail past, and a
+//! surf loop follows the nearest stretch of coastline, louder the more shore
+//! there is around the ship. Everything is scaled by the player's effects
+//! volume (`GameSettings::effects_gain`).
+//!
+//! Like the music, the sounds are not in the repository, so the plugin is
+//! only added with the `audio` feature.
+
+use bevy::audio::{SpatialScale, Volume};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::components::combat::Projectile;
+use crate::events::ShipHitEvent;
+use crate::plugins::core::{GameState, MainCamera};
+use crate::plugins::worldmap::{HighSeasPlayer, HighSeasPort};
+use crate::resources::{GameSettings, MapData};
+use crate::utils::pathfinding::{tile_to_world, world_to_tile};
+
+/// World units per unit of audio distance. Sounds fall off with the square of
+/// audio distance, so a broadside 1000 units away plays at about 1%.
+const AUDIO_SCALE: f32 = 1.0 / 100.0;
+/// Distance between the listener's ears, in world units. Wider ears pan harder.
+const EAR_GAP: f32 = 400.0;
+/// Tiles around the ship sampled for coastline.
+const SURF_SAMPLE_RADIUS: i32 = 6;
+/// Fraction of sampled tiles that must be shore for the surf to play at full volume.
+const FULL_SURF_COVERAGE: f32 = 0.3;
+/// Volume of a port's harbor ambience, before the effects volume.
+const HARBOR_AMBIENCE_VOLUME: f32 = 0.7;
+
+/// The looping surf emitter that follows the coastline.
+#[derive(Component, Debug)]
+pub struct SurfEmitter;
+
+/// A harbor ambience loop attached to a port on the world map.
+#[derive(Component, Debug)]
+pub struct HarborAmbience;
+
+/// Plugin for positional audio.
+pub struct SpatialAudioPlugin;
+
+impl Plugin for SpatialAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_surf_emitter)
+            .add_systems(Update, (
+                attach_listener,
+                cannon_sound_system,
+                hit_sound_system,
+                harbor_ambience_system,
+                harbor_ambience_volume.after(harbor_ambience_system),
+                surf_system,
+            ));
+    }
+}
+
+/// Spatial playback settings in world units.
+fn spatial(settings: PlaybackSettings) -> PlaybackSettings {
+    PlaybackSettings {
+        spatial: true,
+        spatial_scale: Some(SpatialScale::new_2d(AUDIO_SCALE)),
+        ..settings
+    }
+}
+
+/// Gives the main camera ears. The camera lives across every state, so the
+/// listener does too.
+fn attach_listener(
+    mut commands: Commands,
+    camera_query: Query<Entity, (With<MainCamera>, Without<SpatialListener>)>,
+) {
+    for camera in &camera_query {
+        commands.entity(camera).insert(SpatialListener::new(EAR_GAP));
+    }
+}
+
+/// One cannon report per broadside, wherever it was fired.
+fn cannon_sound_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    projectile_query: Query<(&Projectile, &Transform), Added<Projectile>>,
+) {
+    let mut fired = HashSet::new();
+    for (projectile, transform) in &projectile_query {
+        if !fired.insert(projectile.source) {
+            continue;
+        }
+        commands.spawn((
+            AudioPlayer::new(asset_server.load("audio/sfx/cannon.ogg")),
+            spatial(PlaybackSettings::DESPAWN.with_volume(Volume::new(settings.effects_gain()))),
+            Transform::from_translation(transform.translation),
+        ));
+    }
+}
+
+/// Splintering wood where a shot lands.
+fn hit_sound_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    mut events: EventReader<ShipHitEvent>,
+) {
+    for event in events.read() {
+        commands.spawn((
+            AudioPlayer::new(asset_server.load("audio/sfx/hit.ogg")),
+            spatial(PlaybackSettings::DESPAWN.with_volume(Volume::new(0.8 * settings.effects_gain()))),
+            Transform::from_translation(event.hit_position.extend(0.0)),
+        ));
+    }
+}
+
+/// Starts a harbor loop on each port as the world map spawns it; it goes with
+/// the port when the High Seas scene is torn down.
+fn harbor_ambience_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    port_query: Query<Entity, Added<HighSeasPort>>,
+) {
+    for port in &port_query {
+        commands.entity(port).with_children(|parent| {
+            parent.spawn((
+                AudioPlayer::new(asset_server.load("audio/ambience/harbor.ogg")),
+                spatial(PlaybackSettings::LOOP.with_volume(Volume::new(HARBOR_AMBIENCE_VOLUME * settings.effects_gain()))),
+                Transform::default(),
+                HarborAmbience,
+            ));
+        });
+    }
+}
+
+/// Turns the playing harbor loops up or down with the effects volume.
+fn harbor_ambience_volume(settings: Res<GameSettings>, sink_query: Query<&SpatialAudioSink, With<HarborAmbience>>) {
+    if !settings.is_changed() {
+        return;
+    }
+    for sink in &sink_query {
+        sink.set_volume(HARBOR_AMBIENCE_VOLUME * settings.effects_gain());
+    }
+}
+
+fn spawn_surf_emitter(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Name::new("Surf"),
+        AudioPlayer::new(asset_server.load("audio/ambience/surf.ogg")),
+        spatial(PlaybackSettings::LOOP.with_volume(Volume::new(0.0))),
+        Transform::default(),
+        SurfEmitter,
+    ));
+}
+
+/// Where the shore around `center` is and how much of it there is: the world
+/// position of the shore tiles' centroid and a 0.0-1.0 strength.
+fn coast_sample(map_data: &MapData, center: IVec2, radius: i32) -> Option<(Vec2, f32)> {
+    let mut sum = Vec2::ZERO;
+    let mut shore = 0;
+    let mut sampled = 0;
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            if dx * dx + dy * dy > radius * radius {
+                continue;
+            }
+            let tile = center + IVec2::new(dx, dy);
+            if !map_data.in_bounds(tile.x, tile.y) {
+                continue;
+            }
+            sampled += 1;
+            if !map_data.is_navigable(tile.x as u32, tile.y as u32) {
+                sum += tile_to_world(tile, map_data.width, map_data.height);
+                shore += 1;
+            }
+        }
+    }
+    if shore == 0 {
+        return None;
+    }
+    let coverage = shore as f32 / sampled as f32;
+    Some((sum / shore as f32, (coverage / FULL_SURF_COVERAGE).min(1.0)))
+}
+
+/// Moves the surf loop to the nearby shore and sets its volume by how much
+/// coast is around the ship. Silent away from the High Seas.
+fn surf_system(
+    state: Res<State<GameState>>,
+    map_data: Option<Res<MapData>>,
+    settings: Res<GameSettings>,
+    player_query: Query<&Transform, (With<HighSeasPlayer>, Without<SurfEmitter>)>,
+    mut surf_query: Query<(&mut Transform, Option<&SpatialAudioSink>), With<SurfEmitter>>,
+) {
+    let Ok((mut surf_transform, sink)) = surf_query.get_single_mut() else {
+        return;
+    };
+
+    let sample = match (state.get(), map_data, player_query.get_single()) {
+        (GameState::HighSeas, Some(map_data), Ok(player)) => {
+            let tile = world_to_tile(player.translation.truncate(), map_data.width, map_data.height);
+            coast_sample(&map_data, tile, SURF_SAMPLE_RADIUS)
+        }
+        _ => None,
+    };
+
+    let volume = match sample {
+        Some((position, strength)) => {
+            surf_transform.translation = position.extend(0.0);
+            strength
+        }
+        None => 0.0,
+    };
+    if let Some(sink) = sink {
+        sink.set_volume(volume * settings.effects_gain());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::TileType;
+
+    #[test]
+    fn test_open_sea_has_no_surf() {
+        let map_data = MapData::new_filled(32, 32, crate::resources::Tile::from_type(TileType::DeepWater));
+        assert!(coast_sample(&map_data, IVec2::new(16, 16), SURF_SAMPLE_RADIUS).is_none());
+    }
+
+    #[test]
+    fn test_surf_centres_on_shore_and_grows_with_coverage() {
+        let mut map_data = MapData::new_filled(32, 32, crate::resources::Tile::from_type(TileType::DeepWater));
+        map_data.set_type(20, 16, TileType::Sand);
+        let (position, faint) = coast_sample(&map_data, IVec2::new(16, 16), SURF_SAMPLE_RADIUS).unwrap();
+        assert_eq!(position, tile_to_world(IVec2::new(20, 16), 32, 32));
+
+        for y in 0..32 {
+            for x in 18..32 {
+                map_data.set_type(x, y, TileType::Land);
+            }
+        }
+        let (position, loud) = coast_sample(&map_data, IVec2::new(16, 16), SURF_SAMPLE_RADIUS).unwrap();
+        assert!(loud > faint);
+        assert_eq!(loud, 1.0);
+        assert!(position.x > tile_to_world(IVec2::new(16, 16), 32, 32).x);
+    }
+}
@@ -0,0 +1,125 @@
+//! Rudder damage: the jury-rig action and the steering HUD indicator.
+//!
+//! Turn rate itself is scaled by `Health::steering` in the combat physics,
+//! combat AI and High Seas movement systems. This plugin lets a crippled
+//! rudder be patched with a jury-rig (`PlayerAction::JuryRig`, J by default,
+//! for the player; enemy crews do it on their own) and shows a ship's-wheel
+//! icon while the player's steering is hurt.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use leafwing_input_manager::prelude::*;
+
+use crate::components::{CombatEntity, Health, JuryRig, Player, Ship, SteeringState, Surrendered, AI};
+use crate::plugins::core::GameState;
+use crate::plugins::input::PlayerAction;
+
+/// Plugin for jury-rigging rudders and the steering indicator.
+pub struct SteeringPlugin;
+
+impl Plugin for SteeringPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            player_jury_rig_system,
+            ai_jury_rig_system.run_if(in_state(GameState::Combat)),
+            jury_rig_progress_system,
+            steering_hud_system.after(EguiSet::InitContexts),
+        ).run_if(in_state(GameState::Combat).or(in_state(GameState::HighSeas))));
+    }
+}
+
+/// J starts a jury-rig when the player's rudder is crippled.
+fn player_jury_rig_system(
+    mut commands: Commands,
+    action_query: Query<&ActionState<PlayerAction>>,
+    player_query: Query<(Entity, &Health), (With<Player>, With<Ship>, Without<JuryRig>)>,
+) {
+    if !action_query.get_single().is_ok_and(|action_state| action_state.just_pressed(&PlayerAction::JuryRig)) {
+        return;
+    }
+    let Ok((entity, health)) = player_query.get_single() else {
+        return;
+    };
+    if health.steering_state() == SteeringState::Crippled {
+        commands.entity(entity).insert(JuryRig::default());
+        info!("Crew rigging a makeshift rudder");
+    }
+}
+
+/// Enemy crews jury-rig as soon as their rudder is crippled.
+fn ai_jury_rig_system(
+    mut commands: Commands,
+    ai_query: Query<(Entity, &Health), (With<AI>, With<Ship>, With<CombatEntity>, Without<JuryRig>, Without<Surrendered>)>,
+) {
+    for (entity, health) in &ai_query {
+        if health.steering_state() == SteeringState::Crippled {
+            commands.entity(entity).insert(JuryRig::default());
+        }
+    }
+}
+
+/// Finishes jury-rigs whose time is up.
+fn jury_rig_progress_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut JuryRig, &mut Health)>,
+) {
+    for (entity, mut jury_rig, mut health) in &mut query {
+        if jury_rig.timer.tick(time.delta()).finished() {
+            health.jury_rig_rudder();
+            commands.entity(entity).remove::<JuryRig>();
+            debug!("Jury-rig finished on {:?}", entity);
+        }
+    }
+}
+
+/// Ship's-wheel icon in the corner while the player's steering is impaired.
+fn steering_hud_system(
+    mut contexts: EguiContexts,
+    player_query: Query<(&Health, Option<&JuryRig>), (With<Player>, With<Ship>)>,
+) {
+    let Ok((health, jury_rig)) = player_query.get_single() else {
+        return;
+    };
+    let state = health.steering_state();
+    if state == SteeringState::Sound && jury_rig.is_none() {
+        return;
+    }
+
+    let (color, status) = match (jury_rig, state) {
+        (Some(rig), _) => (
+            egui::Color32::from_rgb(200, 150, 50),
+            format!("Jury-rigging rudder... {:.0}%", rig.timer.fraction() * 100.0),
+        ),
+        (None, SteeringState::Crippled) => (
+            egui::Color32::from_rgb(200, 60, 50),
+            "Rudder crippled - J to jury-rig".to_string(),
+        ),
+        (None, _) => (
+            egui::Color32::from_rgb(200, 150, 50),
+            format!("Steering {:.0}%", health.steering() * 100.0),
+        ),
+    };
+
+    egui::Area::new(egui::Id::new("steering_hud"))
+        .anchor(egui::Align2::LEFT_BOTTOM, [16.0, -16.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(32.0, 32.0), egui::Sense::hover());
+                draw_wheel(ui.painter(), rect.center(), 13.0, color);
+                ui.colored_label(color, status);
+            });
+        });
+}
+
+/// A ship's wheel: rim, hub and eight spokes.
+fn draw_wheel(painter: &egui::Painter, center: egui::Pos2, radius: f32, color: egui::Color32) {
+    let stroke = egui::Stroke::new(2.0, color);
+    painter.circle_stroke(center, radius * 0.75, stroke);
+    painter.circle_filled(center, radius * 0.2, color);
+    for i in 0..8 {
+        let angle = i as f32 * std::f32::consts::FRAC_PI_4;
+        let dir = egui::vec2(angle.cos(), angle.sin());
+        painter.line_segment([center + dir * radius * 0.2, center + dir * radius], stroke);
+    }
+}
@@ -0,0 +1,274 @@
+//! Named hurricanes tracked on the chart.
+//!
+//! Storms form at the edge of the map, cross it over a few days and batter
+//! any ship caught inside. The chart shows where the player last heard a storm
+//! was, its track so far and a forecast cone drawn from that report
+//! (see `StormReport`). Reports come from harbor masters on arrival in port,
+//! from tavern intel, and from sighting the storm at sea.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::{Health, HighSeasEntity, Player};
+use crate::events::{IntelAcquiredEvent, WorldNewsEvent};
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::HighSeasPlayer;
+use crate::utils::pathfinding::map_half_extents;
+use crate::resources::{
+    MapData, NewsCategory, Storm, StormTracker, WorldClock, MAX_STORMS, PORT_REPORT_ACCURACY,
+    TAVERN_REPORT_ACCURACY,
+};
+
+/// Hour of the day new storms may form.
+const STORM_FORMATION_HOUR: u32 = 9;
+/// Chance each day that a storm forms, while fewer than `MAX_STORMS` are at sea,
+/// before the season's `storm_factor`.
+const STORM_FORMATION_CHANCE: f32 = 0.25;
+/// Distance beyond a storm's edge from which the player can see it.
+const STORM_SIGHT_RANGE: f32 = 600.0;
+/// Hull damage per hour to the player's ship inside a storm.
+const STORM_HULL_DAMAGE_PER_HOUR: f32 = 4.0;
+
+/// Gizmo group for the storm chart overlay, drawn above the sea.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct StormChartGizmos;
+
+/// Marker for the chart label naming a storm.
+#[derive(Component, Debug)]
+pub struct StormLabel {
+    pub name: String,
+}
+
+/// Plugin for named storms.
+pub struct StormPlugin;
+
+impl Plugin for StormPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StormTracker>()
+            .init_gizmo_group::<StormChartGizmos>()
+            .add_systems(Startup, configure_storm_gizmos)
+            .add_systems(FixedUpdate, (
+                storm_formation_system,
+                storm_movement_system.after(storm_formation_system),
+                storm_exposure_system
+                    .after(storm_movement_system)
+                    .run_if(in_state(GameState::HighSeas)),
+            ))
+            .add_systems(OnEnter(GameState::Port), harbor_storm_warnings)
+            .add_systems(OnEnter(GameState::MainMenu), reset_storms)
+            .add_systems(Update, tavern_storm_news.run_if(in_state(GameState::Port)))
+            .add_systems(Update, (
+                draw_storm_chart,
+                sync_storm_labels,
+            ).run_if(in_state(GameState::HighSeas)));
+    }
+}
+
+fn configure_storm_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<StormChartGizmos>();
+    config.line_width = 2.5;
+    config.depth_bias = -1.0;
+}
+
+/// Forms a new named storm at a random map edge, heading across the map,
+/// most often in the hurricane season.
+fn storm_formation_system(
+    world_clock: Res<WorldClock>,
+    map_data: Option<Res<MapData>>,
+    mut tracker: ResMut<StormTracker>,
+    mut news: EventWriter<WorldNewsEvent>,
+) {
+    if world_clock.tick != 0 || world_clock.hour != STORM_FORMATION_HOUR {
+        return;
+    }
+    let Some(map_data) = map_data else {
+        return;
+    };
+    let mut rng = rand::thread_rng();
+    let chance = STORM_FORMATION_CHANCE * world_clock.season().storm_factor();
+    if tracker.storms.len() >= MAX_STORMS || rng.gen::<f32>() > chance {
+        return;
+    }
+
+    let half = map_half_extents(map_data.width, map_data.height);
+    let (edge, position, heading) = match rng.gen_range(0..4) {
+        0 => ("eastern", Vec2::new(half.x, rng.gen_range(-half.y..half.y)), Vec2::NEG_X),
+        1 => ("western", Vec2::new(-half.x, rng.gen_range(-half.y..half.y)), Vec2::X),
+        2 => ("northern", Vec2::new(rng.gen_range(-half.x..half.x), half.y), Vec2::NEG_Y),
+        _ => ("southern", Vec2::new(rng.gen_range(-half.x..half.x), -half.y), Vec2::Y),
+    };
+    let velocity = Vec2::from_angle(rng.gen_range(-0.5..0.5)).rotate(heading) * rng.gen_range(80.0_f32..160.0);
+
+    let name = tracker.take_name();
+    info!("Hurricane {} forms off the {} edge of the map", name, edge);
+    news.send(WorldNewsEvent {
+        category: NewsCategory::Weather,
+        headline: format!("Hurricane {} gathers strength off the {} reaches", name, edge),
+    });
+    tracker.storms.push(Storm {
+        name,
+        position,
+        velocity,
+        radius: rng.gen_range(250.0..450.0),
+        hours_left: rng.gen_range(48..=96),
+        track: vec![position],
+        report: None,
+    });
+}
+
+/// Moves every storm one hour along its path; storms blow out with age or off the map.
+fn storm_movement_system(
+    world_clock: Res<WorldClock>,
+    map_data: Option<Res<MapData>>,
+    mut tracker: ResMut<StormTracker>,
+    mut news: EventWriter<WorldNewsEvent>,
+) {
+    if world_clock.tick != 0 {
+        return;
+    }
+    let mut rng = rand::thread_rng();
+    for storm in &mut tracker.storms {
+        storm.advance_hour(rng.gen_range(-0.15..0.15));
+    }
+
+    let half = map_data.map(|m| map_half_extents(m.width, m.height));
+    tracker.storms.retain(|storm| {
+        let off_map = half.is_some_and(|half| {
+            storm.position.x.abs() > half.x + storm.radius || storm.position.y.abs() > half.y + storm.radius
+        });
+        if storm.hours_left > 0 && !off_map {
+            return true;
+        }
+        info!("Hurricane {} has blown itself out", storm.name);
+        news.send(WorldNewsEvent {
+            category: NewsCategory::Weather,
+            headline: format!("Hurricane {} has blown itself out", storm.name),
+        });
+        false
+    });
+}
+
+/// Storms the player can see are reported exactly; storms the player is in damage the hull.
+fn storm_exposure_system(
+    world_clock: Res<WorldClock>,
+    mut tracker: ResMut<StormTracker>,
+    mut player_query: Query<(&Transform, &mut Health), (With<Player>, With<HighSeasPlayer>)>,
+) {
+    if world_clock.tick != 0 {
+        return;
+    }
+    let Ok((transform, mut health)) = player_query.get_single_mut() else {
+        return;
+    };
+    let player_pos = transform.translation.truncate();
+    let tick = world_clock.total_ticks();
+
+    for storm in &mut tracker.storms {
+        if storm.position.distance(player_pos) <= storm.radius + STORM_SIGHT_RANGE {
+            storm.report(tick, 1.0);
+        }
+        if storm.contains(player_pos) {
+            health.hull = (health.hull - STORM_HULL_DAMAGE_PER_HOUR).max(1.0);
+        }
+    }
+}
+
+/// Harbor masters post warnings for every storm at sea.
+fn harbor_storm_warnings(world_clock: Res<WorldClock>, mut tracker: ResMut<StormTracker>) {
+    tracker.report_all(world_clock.total_ticks(), PORT_REPORT_ACCURACY);
+}
+
+/// Sailors selling intel in the tavern also know where the weather is.
+fn tavern_storm_news(
+    mut events: EventReader<IntelAcquiredEvent>,
+    world_clock: Res<WorldClock>,
+    mut tracker: ResMut<StormTracker>,
+) {
+    if events.read().count() > 0 {
+        tracker.report_all(world_clock.total_ticks(), TAVERN_REPORT_ACCURACY);
+    }
+}
+
+fn reset_storms(mut tracker: ResMut<StormTracker>) {
+    tracker.clear();
+}
+
+/// Draws reported storms: last known position, track so far and forecast cone.
+fn draw_storm_chart(
+    mut gizmos: Gizmos<StormChartGizmos>,
+    world_clock: Res<WorldClock>,
+    tracker: Res<StormTracker>,
+) {
+    let ink = Color::srgba(0.45, 0.12, 0.12, 0.8);
+    let cone_ink = Color::srgba(0.45, 0.12, 0.12, 0.45);
+    let now = world_clock.total_ticks();
+
+    for storm in &tracker.storms {
+        let Some(report) = &storm.report else {
+            continue;
+        };
+
+        // Track up to the report
+        gizmos.linestrip_2d(storm.track[..report.track_len].iter().copied(), ink);
+
+        // Last known position
+        gizmos.circle_2d(Isometry2d::from_translation(report.position), storm.radius, ink);
+        gizmos.circle_2d(Isometry2d::from_translation(report.position), storm.radius * 0.3, ink);
+
+        // Forecast cone
+        let cone = report.forecast(now, storm.radius);
+        let across = report.velocity.normalize_or_zero().perp();
+        gizmos.linestrip_2d(cone.iter().map(|(center, width)| *center + across * *width), cone_ink);
+        gizmos.linestrip_2d(cone.iter().map(|(center, width)| *center - across * *width), cone_ink);
+        if let Some((center, width)) = cone.last() {
+            gizmos.line_2d(*center + across * *width, *center - across * *width, cone_ink);
+        }
+    }
+}
+
+/// Keeps a name label over each reported storm.
+fn sync_storm_labels(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    tracker: Res<StormTracker>,
+    mut label_query: Query<(Entity, &StormLabel, &mut Transform)>,
+) {
+    let reported = |name: &str| {
+        tracker
+            .storms
+            .iter()
+            .find(|storm| storm.name == name)
+            .and_then(|storm| storm.report.as_ref().map(|report| (storm, report)))
+    };
+
+    for (entity, label, mut transform) in &mut label_query {
+        match reported(&label.name) {
+            Some((storm, report)) => {
+                transform.translation = (report.position + Vec2::Y * (storm.radius + 40.0)).extend(6.0);
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+
+    for storm in &tracker.storms {
+        let Some(report) = &storm.report else {
+            continue;
+        };
+        if label_query.iter().any(|(_, label, _)| label.name == storm.name) {
+            continue;
+        }
+        let position = report.position + Vec2::Y * (storm.radius + 40.0);
+        commands.spawn((
+            Text2d::new(format!("Hurricane {}", storm.name)),
+            TextFont {
+                font: asset_server.load("fonts/Quintessential-Regular.ttf"),
+                font_size: 28.0,
+                ..default()
+            },
+            TextColor(Color::srgba(0.45, 0.12, 0.12, 0.9)),
+            Transform::from_xyz(position.x, position.y, 6.0),
+            StormLabel { name: storm.name.clone() },
+            HighSeasEntity,
+        ));
+    }
+}
@@ -0,0 +1,267 @@
+//! Ghost ships and cursed fog: rare supernatural encounters at night.
+//!
+//! On each night hour a ghost ship or a cursed fog bank may appear near the
+//! player (never with the "Historical waters" modifier); both vanish at
+//! dawn. A ghost ship fights as a `Spectral` enemy whose hull shot cannot touch
+//! until its lanterns are shot out of the rigging, and carries cursed
+//! doubloons. Inside a fog bank the compass spins and nothing is charted,
+//! but a derelict with treasure aboard lies at its heart.
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use rand::Rng;
+
+use crate::components::supernatural::{CursedFogBank, GhostShip, Spectral};
+use crate::components::{EncounteredShip, Faction, FactionId, Gold, HighSeasEntity, Health, NavigationPath, Order, OrderQueue, Player, Ship, ShipType, AI};
+use crate::events::CombatTriggeredEvent;
+use crate::plugins::compass_rose::CompassDeviation;
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::{HighSeasAI, HighSeasPlayer};
+use crate::resources::{
+    clock_darkness, is_dark, Journal, MapData, RunSettings, ShipDefinitions, SupernaturalState, WorldClock, APPARITION_DISTANCE, FOG_BANK_CHANCE,
+    FOG_BANK_HEART_RADIUS, FOG_BANK_RADIUS, FOG_BANK_TREASURE, GHOST_SHIP_CHANCE, GHOST_SHIP_GOLD, GHOST_SHIP_LANTERNS,
+};
+use crate::systems::{spawn_combat_enemies, world_tick_system};
+use crate::utils::pathfinding::{tile_to_world, world_to_tile};
+
+/// Plugin for ghost ships and cursed fog banks.
+pub struct SupernaturalPlugin;
+
+impl Plugin for SupernaturalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SupernaturalState>()
+            .add_systems(FixedUpdate, (
+                summon_apparitions.after(world_tick_system),
+                dispel_at_dawn.after(world_tick_system),
+            ).run_if(in_state(GameState::HighSeas)))
+            .add_systems(Update, (
+                track_cursed_fog,
+                scramble_compass.after(track_cursed_fog),
+            ).run_if(in_state(GameState::HighSeas)))
+            // Encounters are read whatever the state, as combat may begin the same frame
+            .add_systems(Update, note_ghost_encounter)
+            .add_systems(OnEnter(GameState::Combat), haunt_combat_enemy.after(spawn_combat_enemies))
+            .add_systems(OnExit(GameState::Combat), end_ghost_encounter)
+            .add_systems(OnExit(GameState::HighSeas), leave_cursed_fog)
+            .add_systems(OnEnter(GameState::MainMenu), reset_supernatural_state);
+    }
+}
+
+/// Name of the ghost ship, on the High Seas and in battle.
+const GHOST_SHIP_NAME: &str = "The Pale Lantern";
+/// Hull the ghost ship sails and fights as.
+const GHOST_SHIP_HULL: ShipType = ShipType::Frigate;
+/// Width and height of the ghost ship's sprite on the High Seas.
+const GHOST_SHIP_SPRITE_SIZE: f32 = 56.0;
+/// Pale, half-there tint of a ghost ship.
+const GHOST_TINT: Color = Color::srgba(0.7, 1.0, 0.85, 0.55);
+/// Colour of a cursed fog bank.
+const FOG_BANK_COLOR: Color = Color::srgba(0.78, 0.82, 0.8, 0.65);
+
+/// Run condition: the chart can be inked (the player is not lost in cursed fog).
+pub fn chart_legible(state: Option<Res<SupernaturalState>>) -> bool {
+    state.is_none_or(|state| !state.in_cursed_fog)
+}
+
+/// Rolls for a ghost ship or fog bank near the player on each night hour.
+fn summon_apparitions(
+    mut commands: Commands,
+    world_clock: Res<WorldClock>,
+    run_settings: Res<RunSettings>,
+    asset_server: Res<AssetServer>,
+    ships: Res<ShipDefinitions>,
+    map_data: Res<MapData>,
+    player_query: Query<&Transform, (With<Player>, With<HighSeasPlayer>)>,
+    apparitions: Query<Has<GhostShip>, Or<(With<GhostShip>, With<CursedFogBank>)>>,
+) {
+    if world_clock.tick != 0 || !is_dark(clock_darkness(&world_clock)) || run_settings.modifiers.historical_waters {
+        return;
+    }
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+    let ghost_abroad = apparitions.iter().any(|is_ghost| is_ghost);
+    let fog_abroad = apparitions.iter().any(|is_ghost| !is_ghost);
+    let mut rng = rand::thread_rng();
+
+    if !ghost_abroad && rng.gen_bool(GHOST_SHIP_CHANCE) {
+        let bearing = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+        let tile = world_to_tile(player_pos + bearing * APPARITION_DISTANCE, map_data.width, map_data.height);
+        if let Some(water) = map_data.nearest_navigable(tile, 6) {
+            let position = tile_to_world(water, map_data.width, map_data.height);
+            commands.spawn((
+                Name::new(GHOST_SHIP_NAME),
+                Ship,
+                GHOST_SHIP_HULL,
+                AI,
+                Faction(FactionId::Pirates),
+                HighSeasAI,
+                GhostShip,
+                Health::default(),
+                OrderQueue::with_order(Order::Patrol {
+                    center: position,
+                    radius: APPARITION_DISTANCE,
+                    waypoint_index: 0,
+                }),
+                NavigationPath::default(),
+                Sprite {
+                    image: asset_server.load(ships.enemy_sprite(GHOST_SHIP_HULL)),
+                    custom_size: Some(Vec2::splat(GHOST_SHIP_SPRITE_SIZE)),
+                    flip_y: true,
+                    color: GHOST_TINT,
+                    ..default()
+                },
+                Transform::from_xyz(position.x, position.y, 1.0),
+                HighSeasEntity,
+            ));
+            info!("A ghost ship appears at ({:.0}, {:.0})", position.x, position.y);
+        }
+    }
+
+    if !fog_abroad && rng.gen_bool(FOG_BANK_CHANCE) {
+        let bearing = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+        let center = player_pos + bearing * APPARITION_DISTANCE;
+        let circle = shapes::Circle { radius: FOG_BANK_RADIUS, center: Vec2::ZERO };
+        commands.spawn((
+            Name::new("Cursed Fog Bank"),
+            CursedFogBank { radius: FOG_BANK_RADIUS, plundered: false },
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&circle),
+                // Above ships, so whatever sails inside is lost to view
+                transform: Transform::from_xyz(center.x, center.y, 3.0),
+                ..default()
+            },
+            Fill::color(FOG_BANK_COLOR),
+            HighSeasEntity,
+        ));
+        info!("A cursed fog bank rolls in at ({:.0}, {:.0})", center.x, center.y);
+    }
+}
+
+/// Ghost ships and fog banks are gone at first light.
+fn dispel_at_dawn(
+    mut commands: Commands,
+    world_clock: Res<WorldClock>,
+    mut state: ResMut<SupernaturalState>,
+    mut journal: ResMut<Journal>,
+    apparitions: Query<Entity, Or<(With<GhostShip>, With<CursedFogBank>)>>,
+) {
+    if world_clock.tick != 0 || is_dark(clock_darkness(&world_clock)) || apparitions.is_empty() {
+        return;
+    }
+    for entity in &apparitions {
+        commands.entity(entity).despawn_recursive();
+    }
+    state.in_cursed_fog = false;
+    journal.record(&world_clock, "At first light the sea is empty again, as if nothing had been there at all.");
+}
+
+/// Notes when the player enters or leaves a cursed fog bank, and finds the derelict at its heart.
+fn track_cursed_fog(
+    mut commands: Commands,
+    mut player_query: Query<(&Transform, &mut Gold), (With<Player>, With<HighSeasPlayer>)>,
+    mut fog_query: Query<(Entity, &Transform, &mut CursedFogBank)>,
+    mut state: ResMut<SupernaturalState>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    let Ok((player_transform, mut gold)) = player_query.get_single_mut() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    let mut inside = false;
+    for (entity, transform, mut fog) in &mut fog_query {
+        let distance = transform.translation.truncate().distance(player_pos);
+        if distance > fog.radius {
+            continue;
+        }
+        inside = true;
+
+        if !fog.plundered && distance <= FOG_BANK_HEART_RADIUS {
+            fog.plundered = true;
+            gold.add(FOG_BANK_TREASURE);
+            journal.record(
+                &world_clock,
+                format!(
+                    "Found a derelict at the heart of the fog, her crew long gone. Took {} gold from her strongbox, and the fog lifted.",
+                    FOG_BANK_TREASURE
+                ),
+            );
+            commands.entity(entity).despawn_recursive();
+            inside = false;
+        }
+    }
+
+    if inside && !state.in_cursed_fog {
+        journal.record(&world_clock, "Sailed into a strange fog. The compass will not settle and the chart makes no sense.");
+    }
+    state.in_cursed_fog = inside;
+}
+
+/// Throws the compass off while the player is lost in cursed fog.
+fn scramble_compass(
+    time: Res<Time>,
+    state: Res<SupernaturalState>,
+    mut deviation: ResMut<CompassDeviation>,
+) {
+    let t = time.elapsed_secs();
+    let wander = if state.in_cursed_fog { (t * 1.7).sin() * 2.5 + (t * 6.3).sin() * 0.8 } else { 0.0 };
+    if deviation.0 != wander {
+        deviation.0 = wander;
+    }
+}
+
+/// Remembers that the coming battle is against a ghost ship.
+fn note_ghost_encounter(
+    mut events: EventReader<CombatTriggeredEvent>,
+    ghost_query: Query<(), With<GhostShip>>,
+    mut state: ResMut<SupernaturalState>,
+) {
+    for event in events.read() {
+        if ghost_query.contains(event.enemy_entity) {
+            state.ghost_engaged = true;
+        }
+    }
+}
+
+/// Turns the ship encountered on the High Seas into the ghost ship.
+fn haunt_combat_enemy(
+    mut commands: Commands,
+    state: Res<SupernaturalState>,
+    mut enemy_query: Query<(Entity, &mut Sprite), With<EncounteredShip>>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    if !state.ghost_engaged {
+        return;
+    }
+    for (entity, mut sprite) in &mut enemy_query {
+        sprite.color = GHOST_TINT;
+        commands.entity(entity).insert((
+            Name::new(GHOST_SHIP_NAME),
+            Spectral::new(GHOST_SHIP_LANTERNS),
+            Gold(GHOST_SHIP_GOLD),
+        ));
+    }
+    journal.record(
+        &world_clock,
+        format!(
+            "Engaged {}. Our shot passes through her hull; the men say to aim for the lanterns in her rigging.",
+            GHOST_SHIP_NAME
+        ),
+    );
+}
+
+fn end_ghost_encounter(mut state: ResMut<SupernaturalState>) {
+    state.ghost_engaged = false;
+}
+
+fn leave_cursed_fog(mut state: ResMut<SupernaturalState>) {
+    state.in_cursed_fog = false;
+}
+
+fn reset_supernatural_state(mut state: ResMut<SupernaturalState>) {
+    *state = SupernaturalState::default();
+}
@@ -0,0 +1,234 @@
+//! Tactical pause for sail combat.
+//!
+//! Space (`PlayerAction::TacticalPause`) drops combat to `TACTICAL_TIME_SCALE`.
+//! While slowed, the helm and guns stop taking live input: A/D queue turns in
+//! `QUEUED_TURN_STEP`s, Tab picks an enemy to bring a broadside to bear on, and
+//! R chooses the next shot. Space again resumes full speed and hands the orders to the helm
+//! (`HelmOrder`) and the gun deck (`CannonState`).
+//!
+//! Slowing `Time<Virtual>` slows the fixed timestep, so ship physics,
+//! projectiles and AI all slow with it.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+use leafwing_input_manager::prelude::*;
+
+use crate::components::{CombatEntity, Player, PlayerOwned, Ship, Surrendered, AI};
+use crate::plugins::core::GameState;
+use crate::plugins::input::PlayerAction;
+use crate::resources::{wrap_angle, CannonState, HelmOrder, TacticalPause, HELM_TOLERANCE, TACTICAL_TIME_SCALE};
+use crate::systems::{buffer_ship_input, ShipInputBuffer};
+
+/// Enemy ships that can be picked as a tactical target.
+type HostileFilter = (With<Ship>, With<AI>, With<CombatEntity>, Without<Surrendered>, Without<Player>, Without<PlayerOwned>);
+
+/// Plugin for the combat tactical pause and queued helm orders.
+pub struct TacticalPlugin;
+
+impl Plugin for TacticalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TacticalPause>()
+            .init_resource::<HelmOrder>()
+            .add_systems(Update, (
+                toggle_tactical_pause,
+                queue_tactical_orders.after(buffer_ship_input),
+                execute_helm_order.after(queue_tactical_orders),
+                draw_tactical_target,
+                tactical_overlay_ui.after(EguiSet::InitContexts),
+            ).chain().run_if(in_state(GameState::Combat)))
+            .add_systems(OnExit(GameState::Combat), reset_tactical_pause);
+    }
+}
+
+/// Heading (z-rotation) of a transform.
+fn heading_of(transform: &Transform) -> f32 {
+    transform.rotation.to_euler(EulerRot::ZYX).0
+}
+
+/// Space enters or leaves the tactical pause. Leaving carries out the orders.
+fn toggle_tactical_pause(
+    action_query: Query<&ActionState<PlayerAction>>,
+    mut time: ResMut<Time<Virtual>>,
+    mut tactical: ResMut<TacticalPause>,
+    mut helm: ResMut<HelmOrder>,
+    mut cannon_state: ResMut<CannonState>,
+    player_query: Query<&Transform, (With<Ship>, With<Player>)>,
+    target_query: Query<&Transform, HostileFilter>,
+) {
+    let Ok(action_state) = action_query.get_single() else {
+        return;
+    };
+    if !action_state.just_pressed(&PlayerAction::TacticalPause) {
+        return;
+    }
+
+    if !tactical.active {
+        tactical.active = true;
+        time.set_relative_speed(TACTICAL_TIME_SCALE);
+        info!("Tactical pause");
+        return;
+    }
+
+    let orders = std::mem::take(&mut tactical.orders);
+    tactical.active = false;
+    time.set_relative_speed(1.0);
+
+    if let Some(ammo) = orders.ammo {
+        cannon_state.current_ammo = ammo;
+        info!("Loading {}", ammo.name());
+    }
+    if let Ok(transform) = player_query.get_single() {
+        let target = orders
+            .target
+            .and_then(|entity| target_query.get(entity).ok())
+            .map(|t| t.translation.truncate());
+        if let Some(heading) = orders.helm_heading(heading_of(transform), transform.translation.truncate(), target) {
+            helm.heading = Some(heading);
+        }
+    }
+}
+
+/// While paused, turns, target and ammo keys queue orders instead of acting.
+fn queue_tactical_orders(
+    action_query: Query<&ActionState<PlayerAction>>,
+    mut tactical: ResMut<TacticalPause>,
+    mut input_buffer: ResMut<ShipInputBuffer>,
+    cannon_state: Res<CannonState>,
+    player_query: Query<&Transform, (With<Ship>, With<Player>)>,
+    target_query: Query<(Entity, &Transform), HostileFilter>,
+) {
+    if !tactical.active {
+        return;
+    }
+    // The helm and guns wait for the orders
+    input_buffer.turn_left = false;
+    input_buffer.turn_right = false;
+    input_buffer.fire_port = false;
+    input_buffer.fire_starboard = false;
+
+    let Ok(action_state) = action_query.get_single() else {
+        return;
+    };
+    if action_state.just_pressed(&PlayerAction::TurnLeft) {
+        tactical.orders.queue_turn(1.0);
+    }
+    if action_state.just_pressed(&PlayerAction::TurnRight) {
+        tactical.orders.queue_turn(-1.0);
+    }
+
+    if action_state.just_pressed(&PlayerAction::TacticalAmmo) {
+        tactical.orders.cycle_ammo(cannon_state.current_ammo);
+    }
+
+    if action_state.just_pressed(&PlayerAction::TacticalTarget) {
+        // Cycle through enemies nearest first
+        let origin = player_query.get_single().map(|t| t.translation.truncate()).unwrap_or_default();
+        let mut enemies: Vec<(Entity, f32)> = target_query
+            .iter()
+            .map(|(entity, t)| (entity, t.translation.truncate().distance(origin)))
+            .collect();
+        enemies.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let next = match tactical.orders.target.and_then(|current| enemies.iter().position(|(e, _)| *e == current)) {
+            Some(index) if index + 1 < enemies.len() => Some(enemies[index + 1].0),
+            Some(_) => None,
+            None => enemies.first().map(|(e, _)| *e),
+        };
+        tactical.orders.target = next;
+    }
+}
+
+/// Steers the player's ship toward the ordered heading until it arrives or the
+/// player turns by hand.
+fn execute_helm_order(
+    tactical: Res<TacticalPause>,
+    mut helm: ResMut<HelmOrder>,
+    mut input_buffer: ResMut<ShipInputBuffer>,
+    player_query: Query<&Transform, (With<Ship>, With<Player>)>,
+) {
+    if tactical.active {
+        return;
+    }
+    let Some(heading) = helm.heading else {
+        return;
+    };
+    if input_buffer.turn_left || input_buffer.turn_right {
+        helm.heading = None;
+        return;
+    }
+    let Ok(transform) = player_query.get_single() else {
+        helm.heading = None;
+        return;
+    };
+
+    let error = wrap_angle(heading - heading_of(transform));
+    if error.abs() <= HELM_TOLERANCE {
+        helm.heading = None;
+    } else if error > 0.0 {
+        input_buffer.turn_left = true;
+    } else {
+        input_buffer.turn_right = true;
+    }
+}
+
+/// Rings the queued target while paused.
+fn draw_tactical_target(
+    mut gizmos: Gizmos,
+    tactical: Res<TacticalPause>,
+    target_query: Query<&Transform, HostileFilter>,
+) {
+    if !tactical.active {
+        return;
+    }
+    if let Some(transform) = tactical.orders.target.and_then(|entity| target_query.get(entity).ok()) {
+        let position = transform.translation.truncate();
+        gizmos.circle_2d(Isometry2d::from_translation(position), 48.0, Color::srgb(0.9, 0.2, 0.1));
+    }
+}
+
+/// Lists the queued orders while paused.
+fn tactical_overlay_ui(
+    mut contexts: EguiContexts,
+    tactical: Res<TacticalPause>,
+    cannon_state: Res<CannonState>,
+    name_query: Query<&Name>,
+) {
+    if !tactical.active {
+        return;
+    }
+    let orders = &tactical.orders;
+
+    let turn = match orders.turn {
+        t if t > 0.0 => format!("{:.0}° to port", t.to_degrees()),
+        t if t < 0.0 => format!("{:.0}° to starboard", -t.to_degrees()),
+        _ if orders.target.is_some() => "Bring a broadside to bear".to_string(),
+        _ => "Hold course".to_string(),
+    };
+    let target = orders
+        .target
+        .map(|entity| name_query.get(entity).map(|n| n.as_str().to_string()).unwrap_or_else(|_| "Enemy ship".to_string()))
+        .unwrap_or_else(|| "None".to_string());
+    let ammo = orders.ammo.unwrap_or(cannon_state.current_ammo).name();
+
+    egui::Window::new("Tactical Pause")
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 16.0])
+        .resizable(false)
+        .collapsible(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!("Helm: {}", turn));
+            ui.label(format!("Target: {}", target));
+            ui.label(format!("Load: {}", ammo));
+            ui.separator();
+            ui.small("A/D queue turns · Tab target · R ammo · Space resume");
+        });
+}
+
+/// Leaving combat always restores full speed and drops any orders.
+fn reset_tactical_pause(
+    mut time: ResMut<Time<Virtual>>,
+    mut tactical: ResMut<TacticalPause>,
+    mut helm: ResMut<HelmOrder>,
+) {
+    time.set_relative_speed(1.0);
+    *tactical = TacticalPause::default();
+    helm.heading = None;
+}
@@ -0,0 +1,83 @@
+//! Applies the standard or compact UI layout (see `UiLayout`).
+//!
+//! The layout follows the window size unless forced in the settings
+//! (`GameSettings::ui_layout`) or with F10, which cycles Auto → Compact →
+//! Standard and saves the choice. Compact zooms egui and enlarges its targets;
+//! individual screens read `UiLayout` to reflow themselves.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts, EguiSet};
+
+use crate::resources::{GameSettings, UiLayout, COMPACT_TARGET_HEIGHT};
+
+/// Plugin for choosing and applying the UI layout.
+pub struct UiLayoutPlugin;
+
+impl Plugin for UiLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiLayout>()
+            .add_systems(Update, (
+                toggle_layout_preference,
+                update_ui_layout.after(toggle_layout_preference),
+                apply_ui_layout.after(update_ui_layout).after(EguiSet::InitContexts),
+            ));
+    }
+}
+
+/// F10 cycles the layout preference and saves it with the settings.
+fn toggle_layout_preference(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<GameSettings>,
+    mut layout: ResMut<UiLayout>,
+) {
+    if !keys.just_pressed(KeyCode::F10) {
+        return;
+    }
+    settings.ui_layout = settings.ui_layout.next();
+    layout.preference = settings.ui_layout;
+    info!("UI layout: {}", layout.preference.name());
+    if let Err(e) = settings.save_to_file() {
+        error!("Failed to save settings: {}", e);
+    }
+}
+
+/// Re-resolves the layout when the window or the preference changes.
+fn update_ui_layout(mut layout: ResMut<UiLayout>, window_query: Query<&Window, With<PrimaryWindow>>) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let mode = UiLayout::resolve(layout.preference, window.size());
+    // Only write on change so `apply_ui_layout` runs once per switch
+    if layout.mode != mode {
+        info!("Switching to {:?} UI layout for a {}x{} window", mode, window.width(), window.height());
+        layout.mode = mode;
+    }
+}
+
+/// Zooms egui and sizes its targets for the current layout.
+fn apply_ui_layout(mut contexts: EguiContexts, layout: Res<UiLayout>, mut applied: Local<bool>) {
+    if *applied && !layout.is_changed() {
+        return;
+    }
+    *applied = true;
+
+    let ctx = contexts.ctx_mut();
+    ctx.set_zoom_factor(layout.zoom());
+
+    // The theme only restyles visuals, so spacing set here survives it
+    let mut style = (*ctx.style()).clone();
+    let defaults = egui::style::Spacing::default();
+    if layout.is_compact() {
+        style.spacing.interact_size.y = COMPACT_TARGET_HEIGHT;
+        style.spacing.button_padding = egui::vec2(12.0, 8.0);
+        style.spacing.item_spacing = egui::vec2(10.0, 8.0);
+        style.spacing.scroll.bar_width = 14.0;
+    } else {
+        style.spacing.interact_size = defaults.interact_size;
+        style.spacing.button_padding = defaults.button_padding;
+        style.spacing.item_spacing = defaults.item_spacing;
+        style.spacing.scroll.bar_width = defaults.scroll.bar_width;
+    }
+    ctx.set_style(style);
+}
@@ -0,0 +1,258 @@
+//! Fading wake trails behind ships on the High Seas chart.
+//!
+//! Each ship records where it has been; every frame the trails of all visible
+//! ships are rebuilt into one batched ribbon mesh that widens and fades with
+//! age. Faster ships lay samples further apart and at higher strength, so their
+//! wakes are both longer and darker.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::components::{HighSeasEntity, Ship};
+use crate::plugins::core::GameState;
+use crate::utils::line_mesh::LineMeshBuilder;
+
+/// Minimum distance between recorded wake points.
+const WAKE_SAMPLE_SPACING: f32 = 16.0;
+/// Seconds a wake point lasts before it has fully faded.
+const WAKE_LIFETIME: f32 = 2.5;
+/// Speed (world units per second) at which a wake reaches full strength.
+const WAKE_FULL_SPEED: f32 = 300.0;
+/// Slower ships leave no wake.
+const WAKE_MIN_SPEED: f32 = 20.0;
+/// Per-frame jumps longer than this are treated as teleports and break the trail.
+const WAKE_MAX_JUMP: f32 = 400.0;
+const WAKE_START_WIDTH: f32 = 6.0;
+const WAKE_END_WIDTH: f32 = 28.0;
+const WAKE_MAX_ALPHA: f32 = 0.45;
+
+/// Plugin that draws ship wakes on the world map.
+pub struct WakePlugin;
+
+impl Plugin for WakePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::HighSeas), spawn_wake_mesh)
+            .add_systems(
+                Update,
+                (attach_wake_trails, record_wake_trails, rebuild_wake_mesh)
+                    .chain()
+                    .run_if(in_state(GameState::HighSeas)),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WakePoint {
+    position: Vec2,
+    age: f32,
+    /// 0..1 speed factor when the point was laid.
+    strength: f32,
+}
+
+/// Recent positions of a ship, oldest first.
+#[derive(Component, Debug, Default)]
+pub struct WakeTrail {
+    points: VecDeque<WakePoint>,
+    last_position: Option<Vec2>,
+    /// Strength of the most recent movement, used for the segment to the hull.
+    current_strength: f32,
+}
+
+impl WakeTrail {
+    /// Ages existing points and records the ship's new position.
+    pub fn record(&mut self, position: Vec2, delta_secs: f32) {
+        for point in &mut self.points {
+            point.age += delta_secs;
+        }
+        while self.points.front().is_some_and(|p| p.age >= WAKE_LIFETIME) {
+            self.points.pop_front();
+        }
+
+        let moved = self.last_position.map_or(0.0, |last| last.distance(position));
+        self.last_position = Some(position);
+        if moved > WAKE_MAX_JUMP {
+            self.points.clear();
+            self.current_strength = 0.0;
+            return;
+        }
+
+        let speed = if delta_secs > 0.0 { moved / delta_secs } else { 0.0 };
+        self.current_strength = if speed >= WAKE_MIN_SPEED {
+            (speed / WAKE_FULL_SPEED).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        if self.current_strength <= 0.0 {
+            return;
+        }
+
+        let far_enough = self
+            .points
+            .back()
+            .is_none_or(|last| last.position.distance(position) >= WAKE_SAMPLE_SPACING);
+        if far_enough {
+            self.points.push_back(WakePoint {
+                position,
+                age: 0.0,
+                strength: self.current_strength,
+            });
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+/// Width and opacity of a wake point of the given age and strength.
+pub fn wake_style(age: f32, strength: f32) -> (f32, f32) {
+    let t = (age / WAKE_LIFETIME).clamp(0.0, 1.0);
+    let width = (WAKE_START_WIDTH + (WAKE_END_WIDTH - WAKE_START_WIDTH) * t) * (0.5 + 0.5 * strength);
+    let alpha = (1.0 - t) * strength * WAKE_MAX_ALPHA;
+    (width, alpha)
+}
+
+/// Marker for the batched wake mesh.
+#[derive(Component)]
+struct WakeMesh;
+
+fn spawn_wake_mesh(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn((
+        Name::new("Wake Trails"),
+        WakeMesh,
+        // Placeholder until the first wake is built; hidden meanwhile
+        Mesh2d(meshes.add(Rectangle::default())),
+        MeshMaterial2d(materials.add(ColorMaterial::from(Color::WHITE))),
+        Transform::from_xyz(0.0, 0.0, 0.5), // Above fog, below ships
+        Visibility::Hidden,
+        HighSeasEntity,
+    ));
+}
+
+fn attach_wake_trails(
+    mut commands: Commands,
+    ship_query: Query<Entity, (With<Ship>, Without<WakeTrail>)>,
+) {
+    for entity in &ship_query {
+        commands.entity(entity).insert(WakeTrail::default());
+    }
+}
+
+fn record_wake_trails(time: Res<Time>, mut ship_query: Query<(&Transform, &mut WakeTrail)>) {
+    let delta = time.delta_secs();
+    for (transform, mut trail) in &mut ship_query {
+        trail.record(transform.translation.truncate(), delta);
+    }
+}
+
+/// Rebuilds the wake ribbons of all visible ships into the shared mesh.
+fn rebuild_wake_mesh(
+    ship_query: Query<(&Transform, &WakeTrail, Option<&Visibility>)>,
+    mut wake_query: Query<(&Mesh2d, &mut Visibility), (With<WakeMesh>, Without<WakeTrail>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let Ok((mesh, mut wake_visibility)) = wake_query.get_single_mut() else {
+        return;
+    };
+
+    let ink = Color::srgb(0.25, 0.18, 0.12);
+    let mut builder = LineMeshBuilder::new();
+    let mut points = Vec::new();
+    let mut widths = Vec::new();
+    let mut colors = Vec::new();
+
+    for (transform, trail, visibility) in &ship_query {
+        // Ships hidden by fog of war must not give themselves away
+        if trail.is_empty() || visibility == Some(&Visibility::Hidden) {
+            continue;
+        }
+        points.clear();
+        widths.clear();
+        colors.clear();
+
+        let hull = WakePoint {
+            position: transform.translation.truncate(),
+            age: 0.0,
+            strength: trail.current_strength,
+        };
+        for point in trail.points.iter().chain(std::iter::once(&hull)) {
+            let (width, alpha) = wake_style(point.age, point.strength);
+            points.push(point.position);
+            widths.push(width);
+            colors.push(ink.with_alpha(alpha));
+        }
+        builder.add_ribbon(&points, &widths, &colors);
+    }
+
+    if builder.is_empty() {
+        *wake_visibility = Visibility::Hidden;
+        return;
+    }
+    // Render-world-only meshes are dropped from the main world after upload,
+    // so replace the asset rather than mutating it
+    meshes.insert(&mesh.0, builder.build());
+    *wake_visibility = Visibility::Inherited;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moving_ship_lays_points() {
+        let mut trail = WakeTrail::default();
+        for i in 0..10 {
+            trail.record(Vec2::new(i as f32 * 5.0, 0.0), 1.0 / 60.0);
+        }
+        // 300 units/s, sampled every 16 units over 45 units of travel
+        assert_eq!(trail.len(), 3);
+        assert!((trail.current_strength - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_stationary_ship_has_no_wake() {
+        let mut trail = WakeTrail::default();
+        for _ in 0..10 {
+            trail.record(Vec2::ZERO, 1.0 / 60.0);
+        }
+        assert!(trail.is_empty());
+    }
+
+    #[test]
+    fn test_points_expire() {
+        let mut trail = WakeTrail::default();
+        trail.record(Vec2::ZERO, 0.1);
+        trail.record(Vec2::new(30.0, 0.0), 0.1);
+        assert_eq!(trail.len(), 1);
+        trail.record(Vec2::new(30.0, 0.0), WAKE_LIFETIME);
+        assert!(trail.is_empty());
+    }
+
+    #[test]
+    fn test_teleport_breaks_trail() {
+        let mut trail = WakeTrail::default();
+        trail.record(Vec2::ZERO, 0.1);
+        trail.record(Vec2::new(30.0, 0.0), 0.1);
+        trail.record(Vec2::new(5000.0, 0.0), 0.1);
+        assert!(trail.is_empty());
+    }
+
+    #[test]
+    fn test_wake_style_fades_and_spreads() {
+        let (young_width, young_alpha) = wake_style(0.0, 1.0);
+        let (old_width, old_alpha) = wake_style(WAKE_LIFETIME * 0.9, 1.0);
+        assert!(old_width > young_width);
+        assert!(old_alpha < young_alpha);
+        let (_, slow_alpha) = wake_style(0.0, 0.2);
+        assert!(slow_alpha < young_alpha);
+    }
+}
@@ -0,0 +1,227 @@
+//! Squalls: small weather cells drifting with the wind on the High Seas.
+//!
+//! Squalls form anywhere at sea, drift downwind and blow over within a day
+//! or so. A ship caught in one can barely see past her bowsprit (her `Vision`
+//! radius shrinks), her canvas slowly tears, and no hostile ship can find her
+//! to force an encounter. The chart shows each squall as a turning ink swirl.
+//! Squalls are entities that persist through port visits and saves, and only
+//! form and drift while the player is at sea; the great named hurricanes are
+//! `StormPlugin`'s.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::weather::{InWeather, WeatherCell};
+use crate::components::{Health, Player, Vision};
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::HighSeasPlayer;
+use crate::resources::{Journal, MapData, Wind, WorldClock, TICKS_PER_HOUR};
+use crate::systems::world_tick_system;
+use crate::utils::pathfinding::map_half_extents;
+
+/// Most squalls at sea at once.
+const MAX_WEATHER_CELLS: usize = 4;
+/// Chance each hour that a squall forms, while fewer than `MAX_WEATHER_CELLS` are at sea.
+const WEATHER_CELL_CHANCE: f32 = 0.2;
+/// Drift in a full gale, in world units per in-game hour.
+const WEATHER_DRIFT_PER_HOUR: f32 = 150.0;
+/// Share of her clear-weather `Vision` radius a ship keeps inside a squall.
+const SQUALL_VISION_FACTOR: f32 = 0.4;
+/// Sail damage per hour to the player's ship inside a squall.
+const SQUALL_SAIL_DAMAGE_PER_HOUR: f32 = 2.0;
+/// Share of her sails a squall leaves a ship, however long she stays in it.
+const SQUALL_SAIL_FLOOR: f32 = 0.25;
+/// Turns per second of a squall's ink swirl.
+const SWIRL_SPEED: f32 = 0.15;
+
+/// Gizmo group for squalls on the chart, drawn above the sea.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct WeatherGizmos;
+
+/// Plugin for squalls.
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_gizmo_group::<WeatherGizmos>()
+            .add_systems(Startup, configure_weather_gizmos)
+            .add_systems(FixedUpdate, (
+                form_weather_cells,
+                drift_weather_cells.after(form_weather_cells),
+                squall_sail_damage,
+            ).after(world_tick_system).run_if(in_state(GameState::HighSeas)))
+            .add_systems(Update, (
+                track_squalls,
+                draw_weather_cells,
+            ).run_if(in_state(GameState::HighSeas)))
+            .add_systems(OnEnter(GameState::MainMenu), clear_weather_cells);
+    }
+}
+
+fn configure_weather_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<WeatherGizmos>();
+    config.line_width = 2.0;
+    config.depth_bias = -1.0;
+}
+
+/// Rolls each hour for a new squall somewhere on the map.
+fn form_weather_cells(
+    mut commands: Commands,
+    world_clock: Res<WorldClock>,
+    map_data: Option<Res<MapData>>,
+    cells: Query<(), With<WeatherCell>>,
+) {
+    if world_clock.tick != 0 {
+        return;
+    }
+    let Some(map_data) = map_data else {
+        return;
+    };
+    let mut rng = rand::thread_rng();
+    if cells.iter().count() >= MAX_WEATHER_CELLS || rng.gen::<f32>() > WEATHER_CELL_CHANCE {
+        return;
+    }
+
+    let half = map_half_extents(map_data.width, map_data.height);
+    let position = Vec2::new(rng.gen_range(-half.x..half.x), rng.gen_range(-half.y..half.y));
+    commands.spawn((
+        Name::new("Squall"),
+        WeatherCell {
+            radius: rng.gen_range(180.0..320.0),
+            hours_left: rng.gen_range(12..=36),
+            swirl_phase: rng.gen_range(0.0..std::f32::consts::TAU),
+        },
+        Transform::from_xyz(position.x, position.y, 0.0),
+    ));
+    info!("A squall forms at ({:.0}, {:.0})", position.x, position.y);
+}
+
+/// Carries squalls downwind every tick; they blow over with age or off the map.
+fn drift_weather_cells(
+    mut commands: Commands,
+    world_clock: Res<WorldClock>,
+    wind: Res<Wind>,
+    map_data: Option<Res<MapData>>,
+    mut cells: Query<(Entity, &mut Transform, &mut WeatherCell)>,
+) {
+    let drift = wind.velocity() * WEATHER_DRIFT_PER_HOUR / TICKS_PER_HOUR as f32;
+    let half = map_data.map(|m| map_half_extents(m.width, m.height));
+
+    for (entity, mut transform, mut cell) in &mut cells {
+        transform.translation += drift.extend(0.0);
+        let blown_over = world_clock.tick == 0 && cell.age_hour();
+        let position = transform.translation.truncate();
+        let off_map = half.is_some_and(|half| {
+            position.x.abs() > half.x + cell.radius || position.y.abs() > half.y + cell.radius
+        });
+        if blown_over || off_map {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Closes in the player's horizon while she is in a squall, and opens it again after.
+fn track_squalls(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &Transform, &mut Vision, Option<&InWeather>), (With<Player>, With<HighSeasPlayer>)>,
+    cells: Query<(&Transform, &WeatherCell)>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    let Ok((entity, transform, mut vision, in_weather)) = player_query.get_single_mut() else {
+        return;
+    };
+    let player_pos = transform.translation.truncate();
+    let inside = cells
+        .iter()
+        .any(|(cell_transform, cell)| cell.contains(cell_transform.translation.truncate(), player_pos));
+
+    match (inside, in_weather) {
+        (true, None) => {
+            commands.entity(entity).insert(InWeather { clear_radius: vision.radius });
+            vision.radius *= SQUALL_VISION_FACTOR;
+            journal.record(&world_clock, "Ran into a squall. Rain in sheets; the lookouts can barely see the bowsprit.");
+        }
+        (false, Some(in_weather)) => {
+            vision.radius = in_weather.clear_radius;
+            commands.entity(entity).remove::<InWeather>();
+        }
+        _ => {}
+    }
+}
+
+/// Wind and rain slowly tear the canvas of a ship caught in a squall.
+fn squall_sail_damage(
+    world_clock: Res<WorldClock>,
+    mut player_query: Query<&mut Health, (With<Player>, With<HighSeasPlayer>, With<InWeather>)>,
+) {
+    if world_clock.tick != 0 {
+        return;
+    }
+    for mut health in &mut player_query {
+        health.sails = squall_torn_sails(health.sails, health.sails_max);
+    }
+}
+
+/// Sails left after an hour in a squall; never torn below `SQUALL_SAIL_FLOOR`,
+/// and never mended if already below it.
+fn squall_torn_sails(sails: f32, sails_max: f32) -> f32 {
+    let floor = sails_max * SQUALL_SAIL_FLOOR;
+    if sails > floor {
+        (sails - SQUALL_SAIL_DAMAGE_PER_HOUR).max(floor)
+    } else {
+        sails
+    }
+}
+
+/// Draws each squall as a slowly turning three-armed ink swirl inside a faint ring.
+fn draw_weather_cells(
+    mut gizmos: Gizmos<WeatherGizmos>,
+    time: Res<Time>,
+    cells: Query<(&Transform, &WeatherCell)>,
+) {
+    use std::f32::consts::TAU;
+    const ARMS: usize = 3;
+    const ARM_POINTS: usize = 16;
+
+    let ink = Color::srgba(0.2, 0.22, 0.3, 0.55);
+    let ring_ink = Color::srgba(0.2, 0.22, 0.3, 0.2);
+    let turn = time.elapsed_secs() * SWIRL_SPEED * TAU;
+
+    for (transform, cell) in &cells {
+        let center = transform.translation.truncate();
+        gizmos.circle_2d(Isometry2d::from_translation(center), cell.radius, ring_ink);
+        for arm in 0..ARMS {
+            let start = cell.swirl_phase + turn + arm as f32 * TAU / ARMS as f32;
+            gizmos.linestrip_2d(
+                (0..=ARM_POINTS).map(|i| {
+                    let along = i as f32 / ARM_POINTS as f32;
+                    let radius = cell.radius * (0.15 + 0.8 * along);
+                    center + Vec2::from_angle(start + along * TAU * 0.4) * radius
+                }),
+                ink,
+            );
+        }
+    }
+}
+
+/// A new game starts under clear skies.
+fn clear_weather_cells(mut commands: Commands, cells: Query<Entity, With<WeatherCell>>) {
+    for entity in &cells {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_squall_tears_sails_down_to_the_floor_only() {
+        assert_eq!(squall_torn_sails(100.0, 100.0), 100.0 - SQUALL_SAIL_DAMAGE_PER_HOUR);
+        assert_eq!(squall_torn_sails(26.0, 100.0), 25.0);
+        assert_eq!(squall_torn_sails(25.0, 100.0), 25.0);
+        // Sails already shot away below the floor are left as they are
+        assert_eq!(squall_torn_sails(10.0, 100.0), 10.0);
+    }
+}
@@ -0,0 +1,424 @@
+//! Ambient wildlife on the High Seas chart.
+//!
+//! Gull flocks circle over coastal water, whales surface in deep water and a
+//! pod of dolphins occasionally rides the player's bow. Wildlife is purely
+//! atmospheric: it is spawned near the camera on tiles of the right type,
+//! only where the chart has been explored, and despawned once far away.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::HighSeasEntity;
+use crate::plugins::core::{GameState, MainCamera};
+use crate::plugins::worldmap::HighSeasPlayer;
+use crate::resources::{FogOfWar, MapData, TileType};
+use crate::utils::line_mesh::LineMeshBuilder;
+use crate::utils::pathfinding::world_to_tile;
+
+/// Seconds between spawn attempts.
+const SPAWN_INTERVAL: f32 = 1.0;
+/// Spawn distance from the camera centre at scale 1.0, as a (min, max) ring.
+const SPAWN_RING: (f32, f32) = (500.0, 1100.0);
+/// Wildlife beyond this distance at scale 1.0 is despawned.
+const DESPAWN_DISTANCE: f32 = 1800.0;
+const MAX_GULL_FLOCKS: usize = 6;
+const MAX_WHALES: usize = 3;
+/// Chance per spawn attempt that dolphins join a moving player.
+const DOLPHIN_CHANCE: f64 = 0.05;
+/// Player speed (world units per second) needed for dolphins to ride the bow.
+const DOLPHIN_MIN_SPEED: f32 = 80.0;
+/// Seconds of a whale's surfacing cycle, and how much of it is spent surfaced.
+const WHALE_CYCLE: f32 = 9.0;
+const WHALE_SURFACED: f32 = 3.5;
+
+/// Plugin that spawns and animates ambient wildlife.
+pub struct WildlifePlugin;
+
+impl Plugin for WildlifePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WildlifeSpawnTimer>()
+            .add_systems(Startup, setup_wildlife_assets)
+            .add_systems(
+                Update,
+                (spawn_wildlife, animate_wildlife, despawn_far_wildlife)
+                    .chain()
+                    .run_if(in_state(GameState::HighSeas)),
+            );
+    }
+}
+
+/// Kinds of ambient wildlife.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WildlifeKind {
+    GullFlock,
+    Whale,
+    Dolphins,
+}
+
+impl WildlifeKind {
+    /// Returns true if this animal can appear over the tile at (x, y).
+    pub fn habitat_matches(&self, map_data: &MapData, x: u32, y: u32) -> bool {
+        let Some(tile) = map_data.tile(x, y) else {
+            return false;
+        };
+        match self {
+            // Gulls stay within sight of land
+            WildlifeKind::GullFlock => {
+                tile.tile_type.is_navigable() && has_land_neighbor(map_data, x as i32, y as i32)
+            }
+            WildlifeKind::Whale => tile.tile_type == TileType::DeepWater,
+            WildlifeKind::Dolphins => tile.tile_type.is_navigable(),
+        }
+    }
+}
+
+fn has_land_neighbor(map_data: &MapData, x: i32, y: i32) -> bool {
+    (-1..=1).any(|dy| {
+        (-1..=1).any(|dx| {
+            map_data.in_bounds(x + dx, y + dy)
+                && !map_data.is_navigable((x + dx) as u32, (y + dy) as u32)
+        })
+    })
+}
+
+/// An ambient wildlife entity.
+#[derive(Component, Debug)]
+pub struct Wildlife {
+    pub kind: WildlifeKind,
+    /// Centre the animal moves around (unused by dolphins, which follow the player).
+    pub anchor: Vec2,
+    /// Seconds since spawning.
+    pub age: f32,
+    /// Per-entity animation offset.
+    pub phase: f32,
+    /// Seconds until the animal leaves, if it does not wait to be out of range.
+    pub lifetime: Option<f32>,
+}
+
+#[derive(Resource)]
+struct WildlifeSpawnTimer(Timer);
+
+impl Default for WildlifeSpawnTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(SPAWN_INTERVAL, TimerMode::Repeating))
+    }
+}
+
+/// Shared meshes for wildlife glyphs.
+#[derive(Resource)]
+struct WildlifeAssets {
+    gulls: Handle<Mesh>,
+    whale: Handle<Mesh>,
+    dolphins: Handle<Mesh>,
+    material: Handle<ColorMaterial>,
+}
+
+/// How far out of the water a whale is at `age`, from 0 (submerged) to 1.
+pub fn whale_surface_factor(age: f32, phase: f32) -> f32 {
+    let t = (age + phase).rem_euclid(WHALE_CYCLE);
+    if t < WHALE_SURFACED {
+        (t / WHALE_SURFACED * std::f32::consts::PI).sin()
+    } else {
+        0.0
+    }
+}
+
+fn setup_wildlife_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let ink = Color::srgba(0.2, 0.15, 0.1, 0.85);
+
+    // Chart-style gulls: a loose flock of shallow "m" strokes
+    let mut gulls = LineMeshBuilder::new();
+    for offset in [Vec2::new(0.0, 0.0), Vec2::new(22.0, 10.0), Vec2::new(-18.0, 14.0), Vec2::new(8.0, -16.0)] {
+        gulls.add_polyline(
+            &[
+                offset + Vec2::new(-9.0, 4.0),
+                offset + Vec2::new(-4.0, 1.0),
+                offset,
+                offset + Vec2::new(4.0, 1.0),
+                offset + Vec2::new(9.0, 4.0),
+            ],
+            false,
+            1.8,
+            ink,
+        );
+    }
+
+    // Whale seen side-on: humped back, tail flukes and a spout
+    let mut whale = LineMeshBuilder::new();
+    let back: Vec<Vec2> = (0..=12)
+        .map(|i| {
+            let t = i as f32 / 12.0;
+            Vec2::new(-30.0 + 60.0 * t, (t * std::f32::consts::PI).sin() * 12.0)
+        })
+        .collect();
+    whale.add_polyline(&back, false, 2.5, ink);
+    whale.add_polyline(&[Vec2::new(30.0, 0.0), Vec2::new(38.0, 9.0)], false, 2.0, ink);
+    whale.add_polyline(&[Vec2::new(30.0, 0.0), Vec2::new(40.0, 2.0)], false, 2.0, ink);
+    for dx in [-4.0, 0.0, 4.0] {
+        whale.add_polyline(
+            &[Vec2::new(-18.0, 14.0), Vec2::new(-18.0 + dx, 26.0)],
+            false,
+            1.2,
+            ink.with_alpha(0.6),
+        );
+    }
+
+    // Three leaping dolphins in a row
+    let mut dolphins = LineMeshBuilder::new();
+    for dx in [-26.0, 0.0, 26.0] {
+        let arc: Vec<Vec2> = (0..=8)
+            .map(|i| {
+                let t = i as f32 / 8.0;
+                Vec2::new(dx - 9.0 + 18.0 * t, (t * std::f32::consts::PI).sin() * 6.0)
+            })
+            .collect();
+        dolphins.add_polyline(&arc, false, 1.8, ink);
+        // Dorsal fin
+        dolphins.add_polyline(
+            &[Vec2::new(dx - 1.0, 6.0), Vec2::new(dx + 1.0, 10.0), Vec2::new(dx + 3.0, 5.5)],
+            false,
+            1.4,
+            ink,
+        );
+    }
+
+    commands.insert_resource(WildlifeAssets {
+        gulls: meshes.add(gulls.build()),
+        whale: meshes.add(whale.build()),
+        dolphins: meshes.add(dolphins.build()),
+        material: materials.add(ColorMaterial::from(Color::WHITE)),
+    });
+}
+
+/// World state wildlife spawning looks at.
+#[derive(bevy::ecs::system::SystemParam)]
+struct Surroundings<'w, 's> {
+    map_data: Res<'w, MapData>,
+    fog: Res<'w, FogOfWar>,
+    camera_query: Query<'w, 's, (&'static Transform, &'static OrthographicProjection), With<MainCamera>>,
+    player_query: Query<'w, 's, &'static Transform, With<HighSeasPlayer>>,
+}
+
+/// Periodically tops up wildlife near the camera.
+fn spawn_wildlife(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut timer: ResMut<WildlifeSpawnTimer>,
+    assets: Option<Res<WildlifeAssets>>,
+    surroundings: Surroundings,
+    wildlife_query: Query<&Wildlife>,
+    mut last_player_position: Local<Option<Vec2>>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Surroundings { map_data, fog, camera_query, player_query } = surroundings;
+    let (Some(assets), Ok((camera, projection))) = (assets, camera_query.get_single()) else {
+        return;
+    };
+
+    let mut rng = rand::thread_rng();
+    let center = camera.translation.truncate();
+    let scale = projection.scale;
+    let count = |kind| wildlife_query.iter().filter(|w| w.kind == kind).count();
+
+    for (kind, cap) in [(WildlifeKind::GullFlock, MAX_GULL_FLOCKS), (WildlifeKind::Whale, MAX_WHALES)] {
+        if count(kind) >= cap {
+            continue;
+        }
+        // A few random probes; most land on the wrong terrain
+        for _ in 0..6 {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let distance = rng.gen_range(SPAWN_RING.0..SPAWN_RING.1) * scale;
+            let position = center + Vec2::from_angle(angle) * distance;
+            let tile = world_to_tile(position, map_data.width, map_data.height);
+            if tile.x < 0 || tile.y < 0 || !fog.is_explored(tile) {
+                continue;
+            }
+            if !kind.habitat_matches(&map_data, tile.x as u32, tile.y as u32) {
+                continue;
+            }
+
+            let (mesh, z, name) = match kind {
+                WildlifeKind::GullFlock => (assets.gulls.clone(), 3.0, "Gull Flock"),
+                _ => (assets.whale.clone(), 0.7, "Whale"),
+            };
+            commands.spawn((
+                Name::new(name),
+                Wildlife {
+                    kind,
+                    anchor: position,
+                    age: 0.0,
+                    phase: rng.gen_range(0.0..WHALE_CYCLE),
+                    // Whales move on after a few surfacings so they cannot drift ashore
+                    lifetime: (kind == WildlifeKind::Whale).then_some(WHALE_CYCLE * 4.0),
+                },
+                Mesh2d(mesh),
+                MeshMaterial2d(assets.material.clone()),
+                Transform::from_translation(position.extend(z)),
+                HighSeasEntity,
+            ));
+            debug!("Spawned {} at ({:.0}, {:.0})", name, position.x, position.y);
+            break;
+        }
+    }
+
+    // Dolphins only join a player making way through open water
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let player_position = player.translation.truncate();
+    let speed = last_player_position.map_or(0.0, |last| last.distance(player_position) / SPAWN_INTERVAL);
+    *last_player_position = Some(player_position);
+
+    if count(WildlifeKind::Dolphins) > 0 || speed < DOLPHIN_MIN_SPEED || !rng.gen_bool(DOLPHIN_CHANCE) {
+        return;
+    }
+    let tile = world_to_tile(player_position, map_data.width, map_data.height);
+    if tile.x < 0 || tile.y < 0 || !WildlifeKind::Dolphins.habitat_matches(&map_data, tile.x as u32, tile.y as u32) {
+        return;
+    }
+    commands.spawn((
+        Name::new("Dolphins"),
+        Wildlife {
+            kind: WildlifeKind::Dolphins,
+            anchor: player_position,
+            age: 0.0,
+            phase: 0.0,
+            lifetime: Some(rng.gen_range(15.0..25.0)),
+        },
+        Mesh2d(assets.dolphins.clone()),
+        MeshMaterial2d(assets.material.clone()),
+        Transform::from_translation(player_position.extend(0.8)),
+        Visibility::Hidden,
+        HighSeasEntity,
+    ));
+    info!("Dolphins are riding the bow");
+}
+
+/// Circles gull flocks, surfaces whales and keeps dolphins ahead of the player.
+fn animate_wildlife(
+    time: Res<Time>,
+    player_query: Query<&Transform, (With<HighSeasPlayer>, Without<Wildlife>)>,
+    mut wildlife_query: Query<(&mut Wildlife, &mut Transform, &mut Visibility)>,
+) {
+    let delta = time.delta_secs();
+    let player = player_query.get_single().ok();
+
+    for (mut wildlife, mut transform, mut visibility) in &mut wildlife_query {
+        wildlife.age += delta;
+        let t = wildlife.age + wildlife.phase;
+
+        match wildlife.kind {
+            WildlifeKind::GullFlock => {
+                let orbit = Vec2::from_angle(t * 0.4) * 60.0;
+                let position = wildlife.anchor + orbit;
+                transform.translation.x = position.x;
+                transform.translation.y = position.y;
+                // Wingbeats
+                transform.scale.y = 1.0 + (t * 6.0).sin() * 0.25;
+            }
+            WildlifeKind::Whale => {
+                let surfaced = whale_surface_factor(wildlife.age, wildlife.phase);
+                transform.scale.y = surfaced;
+                // Drift slowly between surfacings
+                let drift = Vec2::from_angle(wildlife.phase) * wildlife.age * 4.0;
+                let position = wildlife.anchor + drift;
+                transform.translation.x = position.x;
+                transform.translation.y = position.y;
+                let shown = surfaced > 0.05;
+                if shown != (*visibility != Visibility::Hidden) {
+                    *visibility = if shown { Visibility::Inherited } else { Visibility::Hidden };
+                }
+            }
+            WildlifeKind::Dolphins => {
+                let Some(player) = player else {
+                    continue;
+                };
+                let forward = (player.rotation * Vec3::Y).truncate();
+                let weave = forward.perp() * (t * 1.3).sin() * 12.0;
+                let position = player.translation.truncate() + forward * 70.0 + weave;
+                transform.translation.x = position.x;
+                transform.translation.y = position.y;
+                // Side-on glyph leaping along the direction of travel
+                transform.rotation = Quat::from_rotation_z(forward.to_angle());
+                transform.scale.y = 0.6 + (t * 4.0).sin().abs() * 0.6;
+                *visibility = Visibility::Inherited;
+            }
+        }
+    }
+}
+
+/// Removes wildlife that is far from the camera or has outlived its visit.
+fn despawn_far_wildlife(
+    mut commands: Commands,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    wildlife_query: Query<(Entity, &Wildlife, &Transform), Without<MainCamera>>,
+) {
+    let Ok((camera, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let center = camera.translation.truncate();
+    let max_distance = DESPAWN_DISTANCE * projection.scale.max(1.0);
+
+    for (entity, wildlife, transform) in &wildlife_query {
+        let far = transform.translation.truncate().distance(center) > max_distance;
+        let expired = wildlife.lifetime.is_some_and(|lifetime| wildlife.age > lifetime)
+            && (wildlife.kind != WildlifeKind::Whale
+                || whale_surface_factor(wildlife.age, wildlife.phase) == 0.0);
+        if far || expired {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn island_map() -> MapData {
+        // Deep water with a 3x3 island in the middle and a shallow ring around it
+        let mut map = MapData::new(16, 16);
+        for y in 5..=11 {
+            for x in 5..=11 {
+                map.set_type(x, y, TileType::ShallowWater);
+            }
+        }
+        for y in 7..=9 {
+            for x in 7..=9 {
+                map.set_type(x, y, TileType::Land);
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn test_gulls_need_nearby_land() {
+        let map = island_map();
+        assert!(WildlifeKind::GullFlock.habitat_matches(&map, 6, 8));
+        assert!(!WildlifeKind::GullFlock.habitat_matches(&map, 1, 1));
+        // Never over land itself
+        assert!(!WildlifeKind::GullFlock.habitat_matches(&map, 8, 8));
+    }
+
+    #[test]
+    fn test_whales_need_deep_water() {
+        let map = island_map();
+        assert!(WildlifeKind::Whale.habitat_matches(&map, 1, 1));
+        assert!(!WildlifeKind::Whale.habitat_matches(&map, 6, 6));
+        assert!(!WildlifeKind::Whale.habitat_matches(&map, 8, 8));
+    }
+
+    #[test]
+    fn test_whale_surfaces_then_dives() {
+        assert_eq!(whale_surface_factor(0.0, 0.0), 0.0);
+        assert!(whale_surface_factor(WHALE_SURFACED / 2.0, 0.0) > 0.99);
+        assert_eq!(whale_surface_factor(WHALE_SURFACED + 1.0, 0.0), 0.0);
+        // Cycles
+        assert!(whale_surface_factor(WHALE_CYCLE + WHALE_SURFACED / 2.0, 0.0) > 0.99);
+    }
+}
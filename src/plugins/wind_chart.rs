@@ -0,0 +1,82 @@
+//! Wind arrows on the High Seas chart.
+//!
+//! A loose grid of arrows around the camera shows which way the wind is
+//! blowing and how hard: arrows lengthen as it freshens, drift downwind at
+//! a pace set by its strength and turn red at storm strength. In the lee of an
+//! island they shrink and fade with the `WindShadow`. How the wind speeds or
+//! slows a ship on each point of sail is `Wind::sailing_factor`.
+
+use bevy::prelude::*;
+
+use crate::plugins::core::{GameState, MainCamera};
+use crate::resources::{Wind, WindShadow};
+
+/// Distance between arrows at camera scale 1.0 (world units).
+const ARROW_SPACING: f32 = 420.0;
+/// Arrows drawn out from the camera centre in each direction.
+const ARROW_CELLS: i32 = 4;
+/// Arrow length in a full gale at camera scale 1.0 (world units).
+const ARROW_LENGTH: f32 = 90.0;
+/// Drift speed of the arrows in a full gale (world units per second).
+const ARROW_DRIFT_SPEED: f32 = 60.0;
+/// Arrow length and ink left in the deepest wind shadow, as a share of the open sea.
+const SHADOW_ARROW_SHARE: f32 = 0.35;
+
+/// Gizmo group for the wind arrows, drawn above the sea.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct WindChartGizmos;
+
+/// Plugin drawing the wind on the chart.
+pub struct WindChartPlugin;
+
+impl Plugin for WindChartPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_gizmo_group::<WindChartGizmos>()
+            .add_systems(Startup, configure_wind_gizmos)
+            .add_systems(Update, draw_wind_arrows.run_if(in_state(GameState::HighSeas)));
+    }
+}
+
+fn configure_wind_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<WindChartGizmos>();
+    config.line_width = 1.5;
+    config.depth_bias = -1.0;
+}
+
+/// Draws a grid of arrows around the camera, drifting with the wind.
+fn draw_wind_arrows(
+    mut gizmos: Gizmos<WindChartGizmos>,
+    wind: Res<Wind>,
+    wind_shadow: Res<WindShadow>,
+    time: Res<Time>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    let Ok((camera, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let scale = projection.scale.max(1.0);
+    let spacing = ARROW_SPACING * scale;
+    let direction = wind.direction_vec();
+    let length = ARROW_LENGTH * scale * (0.3 + 0.7 * wind.strength);
+    let ink = if wind.is_storm() {
+        Color::srgba(0.5, 0.1, 0.1, 0.45)
+    } else {
+        Color::srgba(0.15, 0.2, 0.3, 0.3)
+    };
+
+    // Arrows stay put on the chart as the camera pans, and drift downwind
+    let drift = direction * (time.elapsed_secs() * ARROW_DRIFT_SPEED * wind.strength * scale);
+    let center = camera.translation.truncate();
+    let origin = ((center - drift) / spacing).floor() * spacing + drift;
+
+    for x in -ARROW_CELLS..=ARROW_CELLS {
+        for y in -ARROW_CELLS..=ARROW_CELLS {
+            // Stagger alternate rows so the grid doesn't read as a grid
+            let stagger = if y % 2 == 0 { 0.0 } else { 0.5 };
+            let position = origin + Vec2::new(x as f32 + stagger, y as f32) * spacing;
+            let share = 1.0 - wind_shadow.shelter_at(position) * (1.0 - SHADOW_ARROW_SHARE);
+            let half = direction * length * share * 0.5;
+            gizmos.arrow_2d(position - half, position + half, ink.with_alpha(ink.alpha() * share));
+        }
+    }
+}
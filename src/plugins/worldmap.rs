@@ -1,3 +1,4 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::render::render_asset::RenderAssetUsages;
 use bevy_ecs_tilemap::prelude::*;
@@ -5,11 +6,16 @@ use bevy_prototype_lyon::prelude::*;
 use crate::plugins::core::GameState;
 use crate::plugins::port::{spawn_port, generate_port_name};
 use crate::plugins::debug_ui::DebugToggles;
-use crate::resources::{MapData, FogOfWar, RouteCache};
-use crate::components::{Player, Ship, Health, Vision, AI, Faction, FactionId, Order, OrderQueue, HighSeasEntity};
-use crate::components::ship::ShipType;
+use crate::plugins::pacing::StandsOff;
+use crate::plugins::map_lod::{decoration_visibility, LodLevel, MapDetail, MapLod};
+use crate::resources::{MapData, FogOfWar, RouteCache, RunSettings, PendingWorldGeneration, GoodsTable, BalanceConfig, FactionRegistry, WorldClock, clock_darkness, night_encounter_factor};
+use crate::components::{Player, Ship, Health, Vision, AI, Faction, FactionId, Order, OrderQueue, HighSeasEntity, BountyHunter};
+use crate::components::weather::InWeather;
+use crate::components::patrol::Plundered;
+use crate::components::contract::{AcceptedContract, Commission};
+use crate::components::ship::{ShipClass, ShipType};
 use crate::systems::{
-    fog_of_war_update_system, FogTile,
+    fog_of_war_update_system, publish_fog_reveals, FogTile,
     click_to_navigate_system,
     path_visualization_system, port_arrival_system, order_execution_system,
     contract_delegation_system,
@@ -19,15 +25,16 @@ use crate::systems::{
     arrival_detection_system, sync_destination_to_agent_target,
     coastline_avoidance_system,
 };
-use crate::utils::pathfinding::{tile_to_world, world_to_tile};
+use crate::utils::pathfinding::{map_half_extents, tile_to_world, world_to_tile};
 use crate::utils::spatial_hash::SpatialHash;
 use crate::utils::geometry::{extract_contours, CoastlinePolygon, offset_polygon, build_landmass_navmeshes};
-use crate::resources::{NavMeshResource, PendingNavMeshes, LandmassArchipelagos, ShoreBufferTier};
+use crate::utils::line_mesh::LineMeshBuilder;
+use crate::resources::{NavMeshResource, PendingNavMeshes, LandmassArchipelagos, ShoreBufferTier, ShipDefinition, ShipDefinitions};
 use bevy_landmass::prelude::*;
 use bevy_landmass::NavMeshHandle;
 use bevy_landmass::debug::{Landmass2dDebugPlugin, EnableLandmassDebug};
 use std::sync::Arc;
-use crate::events::CombatTriggeredEvent;
+use crate::events::{CombatTriggeredEvent, FogRevealedEvent};
 use crate::resources::stippling_material::StipplingMaterial;
 
 /// Plugin managing the world map tilemap for the High Seas view.
@@ -41,7 +48,7 @@ pub struct WorldMapPlugin;
 impl Plugin for WorldMapPlugin {
     fn build(&self, app: &mut App) {
         app
-            // Vector graphics for coastlines
+            // Vector graphics for the compass rose, scale bar and cartouche
             .add_plugins(ShapePlugin)
             // Landmass navigation plugin
             .add_plugins(Landmass2dPlugin::default())
@@ -54,6 +61,7 @@ impl Plugin for WorldMapPlugin {
             .init_resource::<FogOfWar>()
             .init_resource::<RouteCache>()
             .init_resource::<CoastlineData>()
+            .init_resource::<crate::systems::StuckStats>()
             .init_resource::<NavMeshResource>()
             .init_resource::<EncounterSpatialHash>()
             .init_resource::<EncounterCooldown>()
@@ -61,6 +69,7 @@ impl Plugin for WorldMapPlugin {
             .init_resource::<crate::resources::PlayerFleet>()
             .init_resource::<crate::resources::FleetEntities>()
             .add_event::<CombatTriggeredEvent>()
+            .add_event::<FogRevealedEvent>()
             .add_systems(Startup, (
                 generate_procedural_map,
                 create_tileset_texture,
@@ -68,23 +77,35 @@ impl Plugin for WorldMapPlugin {
                 initialize_archipelagos.after(extract_coastlines_system),
                 spawn_navigation_islands.after(initialize_archipelagos),
             ))
+            // Regenerate the world from RunSettings when a new game is started
+            .add_systems(OnExit(GameState::MainMenu), (
+                clear_generated_world,
+                generate_procedural_map,
+                extract_coastlines_system,
+                initialize_archipelagos,
+                spawn_navigation_islands,
+                start_run_reputation.run_if(not(resource_exists::<crate::resources::PendingRunRestore>)),
+                finish_world_generation,
+            ).chain().run_if(resource_exists::<PendingWorldGeneration>))
             .add_systems(OnEnter(GameState::HighSeas), (
                 spawn_tilemap_from_map_data,
                 spawn_coastline_shapes,
                 spawn_elevation_markers.after(spawn_coastline_shapes),
                 spawn_high_seas_player,
-                spawn_high_seas_ai_ships,
+                spawn_high_seas_ai_ships.run_if(not(crate::plugins::run_state::restoring_ai_ships)),
                 spawn_player_fleet,
                 spawn_port_entities,
                 spawn_location_labels.after(spawn_port_entities),
                 spawn_legacy_wrecks,
                 reset_encounter_cooldown,
-                show_tilemap,
+                show_world_layers,
             ))
             // Fog of war and visibility systems
             .add_systems(Update, (
-                fog_of_war_update_system,
-                crate::systems::ink_reveal::spawn_ink_reveals.after(fog_of_war_update_system),
+                // A cursed fog bank leaves nothing to chart
+                fog_of_war_update_system.run_if(crate::plugins::supernatural::chart_legible),
+                publish_fog_reveals.after(fog_of_war_update_system),
+                crate::systems::ink_reveal::spawn_ink_reveals.after(publish_fog_reveals),
                 crate::systems::ink_reveal::animate_ink_reveals.after(crate::systems::ink_reveal::spawn_ink_reveals),
                 fog_of_war_ai_visibility_system,
                 coastline_visibility_system,
@@ -94,12 +115,17 @@ impl Plugin for WorldMapPlugin {
                 rebuild_encounter_spatial_hash,
                 encounter_detection_system.after(rebuild_encounter_spatial_hash),
                 handle_combat_trigger_system.after(encounter_detection_system),
+                record_encounter_squadron.after(rebuild_encounter_spatial_hash),
             ).run_if(in_state(GameState::HighSeas)))
             // Navigation systems (landmass-only, no grid fallback)
             .add_systems(Update, (
-                click_to_navigate_system,
+                click_to_navigate_system.after(crate::plugins::picking::emit_picking_results),
                 order_execution_system,
-                sync_destination_to_agent_target.after(click_to_navigate_system).after(order_execution_system),
+                crate::systems::formation_station_system,
+                sync_destination_to_agent_target
+                    .after(click_to_navigate_system)
+                    .after(order_execution_system)
+                    .after(crate::systems::formation_station_system),
             ).run_if(in_state(GameState::HighSeas)))
             // Movement systems (landmass velocity-based)
             .add_systems(Update, (
@@ -108,9 +134,13 @@ impl Plugin for WorldMapPlugin {
                 arrival_detection_system
                     .after(landmass_player_movement_system)
                     .after(landmass_ai_movement_system),
-                coastline_avoidance_system
+                crate::systems::friendly_separation_system
                     .after(landmass_player_movement_system)
                     .after(landmass_ai_movement_system),
+                coastline_avoidance_system
+                    .after(crate::systems::friendly_separation_system),
+                crate::systems::stuck_recovery_system.after(coastline_avoidance_system),
+                crate::systems::berthing_system.after(arrival_detection_system),
             ).run_if(in_state(GameState::HighSeas)))
             // Visualization and other systems
             .add_systems(Update, (
@@ -121,8 +151,7 @@ impl Plugin for WorldMapPlugin {
                 wreck_exploration_system,
                 toggle_navmesh_debug,
             ).run_if(in_state(GameState::HighSeas)))
-            .add_systems(OnEnter(GameState::Combat), hide_tilemap)
-            .add_systems(OnExit(GameState::HighSeas), clear_fleet_entities);
+            .add_systems(OnExit(GameState::HighSeas), (hide_world_layers, clear_fleet_entities));
     }
 }
 
@@ -164,9 +193,6 @@ pub struct EncounterSpatialHash {
     pub hash: SpatialHash<Entity>,
 }
 
-/// Encounter detection radius in world units (4 tiles = 256 units)
-const ENCOUNTER_RADIUS: f32 = 256.0;
-
 /// Cooldown to prevent rapid encounter re-triggering.
 #[derive(Resource, Default)]
 pub struct EncounterCooldown {
@@ -174,11 +200,61 @@ pub struct EncounterCooldown {
     pub active: bool,
 }
 
+/// Which flags the player may fall upon: those hostile to the player, and the rival
+/// named in a letter of marque.
+#[derive(SystemParam)]
+pub struct Hostility<'w, 's> {
+    factions: Res<'w, FactionRegistry>,
+    commissions: Query<'w, 's, &'static Commission, With<AcceptedContract>>,
+}
+
+impl Hostility<'_, '_> {
+    pub fn is_hostile(&self, faction: FactionId) -> bool {
+        self.factions.is_hostile(faction) || self.commissions.iter().any(|commission| commission.rival == faction)
+    }
+}
+
+/// How close a hostile ship must come to fall upon the player: the balance's
+/// encounter radius, stretched after dark.
+#[derive(SystemParam)]
+pub struct EncounterRange<'w> {
+    pub balance: Res<'w, BalanceConfig>,
+    world_clock: Res<'w, WorldClock>,
+}
+
+impl EncounterRange<'_> {
+    pub fn radius(&self) -> f32 {
+        self.balance.encounter.radius * night_encounter_factor(clock_darkness(&self.world_clock))
+    }
+}
+
+/// How fast AI ships sail: the balance's shares of each ship class's speed.
+#[derive(SystemParam)]
+pub struct AiShipSpeeds<'w> {
+    balance: Res<'w, BalanceConfig>,
+    ships: Res<'w, ShipDefinitions>,
+}
+
+impl AiShipSpeeds<'_> {
+    /// Landmass agent settings for an AI ship of `ship_type` and `class`.
+    pub fn agent_settings(&self, ship_type: ShipType, class: Option<&ShipClass>) -> AgentSettings {
+        self.balance.ai_speed.agent_settings(ship_type, self.ships.speed(class, ship_type))
+    }
+}
+
 /// Resource storing data about the last encountered enemy for combat spawning.
 #[derive(Resource, Default)]
 pub struct EncounteredEnemy {
     /// Faction of the encountered enemy.
     pub faction: Option<FactionId>,
+    /// Where on the High Seas the player was when the encounter began.
+    pub position: Option<Vec2>,
+    /// Fleet ships escorting the player into the battle, by index into
+    /// `PlayerFleet::ships`.
+    pub escorts: Vec<usize>,
+    /// Hulls and classes of the encountered ship and the consorts sailing
+    /// with her (same flag, within `SQUADRON_RADIUS`), her own first.
+    pub squadron: Vec<(ShipType, ShipClass)>,
 }
 
 /// Resource storing extracted coastline polygons for rendering.
@@ -190,10 +266,14 @@ pub struct CoastlineData {
 }
 
 /// Marker component for coastline shape entities.
-/// Used to toggle visibility and clean up on state exit.
+/// Used to toggle visibility and clean up on world regeneration.
 #[derive(Component)]
 pub struct CoastlineShape;
 
+/// Marker component for the stippled water depth overlay.
+#[derive(Component)]
+pub struct StippleOverlay;
+
 /// Marker component for location label Text2d entities.
 /// Used to clean up labels on state exit.
 #[derive(Component)]
@@ -280,29 +360,70 @@ fn create_tileset_texture(
 }
 
 /// Generates the world map using procedural noise.
-/// Uses a random seed for variety between game sessions.
-fn generate_procedural_map(mut map_data: ResMut<MapData>) {
-    use crate::utils::procgen::{generate_world_map, MapGenConfig};
-    use rand::Rng;
-    
-    // Generate random seed for this game session
-    let seed: u32 = rand::thread_rng().gen();
-    
-    let config = MapGenConfig {
-        seed,
-        width: 512,
-        height: 512,
-        ..Default::default()
-    };
-    
+/// Seed and size come from `RunSettings` (random seed unless chosen on the New Game screen).
+fn generate_procedural_map(mut map_data: ResMut<MapData>, run_settings: Res<RunSettings>) {
+    use crate::utils::procgen::generate_world_map;
+
+    let config = run_settings.map_gen_config();
+    info!(
+        "Generating {}x{} world map with seed {}",
+        config.width, config.height, config.seed
+    );
+
     *map_data = generate_world_map(config);
 }
 
+/// Despawns navigation data and render layers from a previously generated world
+/// and resets exploration, so a new world can be generated in its place.
+fn clear_generated_world(
+    mut commands: Commands,
+    generated_query: Query<Entity, Or<(With<Archipelago2d>, With<Island>, With<CoastlineShape>, With<StippleOverlay>)>>,
+    tilemap_query: Query<Entity, Or<(With<WorldMap>, With<FogMap>, With<WorldMapTile>, With<FogTile>)>>,
+    mut fog_of_war: ResMut<FogOfWar>,
+) {
+    let mut count = 0;
+    for entity in generated_query.iter().chain(tilemap_query.iter()) {
+        commands.entity(entity).despawn_recursive();
+        count += 1;
+    }
+    *fog_of_war = FogOfWar::default();
+    info!("Cleared previous world ({} entities) for regeneration", count);
+}
+
+/// A new run starts with the nations' opinion of the captain fresh, plus the
+/// archetype's reputation bonuses. A loaded run keeps its saved reputations.
+fn start_run_reputation(
+    mut faction_registry: ResMut<FactionRegistry>,
+    selected_archetype: Res<crate::plugins::main_menu::SelectedArchetype>,
+    registry: Res<crate::resources::ArchetypeRegistry>,
+) {
+    *faction_registry = FactionRegistry::new();
+    let Some(config) = registry.get(selected_archetype.0) else {
+        return;
+    };
+    for (faction_id, rep_modifier) in &config.faction_reputation {
+        if let Some(reputation) = faction_registry.adjust_reputation(*faction_id, *rep_modifier) {
+            info!("Applied {:+} reputation to {:?} (now {})", rep_modifier, faction_id, reputation);
+        }
+    }
+}
+
+/// Marks world generation as complete.
+fn finish_world_generation(mut commands: Commands) {
+    commands.remove_resource::<PendingWorldGeneration>();
+}
+
 /// Spawns the tilemap from MapData resource.
 /// Skips if tilemap already exists (persists across state transitions).
+///
+/// The world render layers (tilemaps, stipple overlay, coastlines, elevation
+/// markers) are not scene entities: they are built once per generated world and
+/// hidden while away from the High Seas, since rebuilding them costs
+/// O(width x height) on every state change.
 fn spawn_tilemap_from_map_data(
     mut commands: Commands,
     map_data: Res<MapData>,
+    fog_of_war: Res<FogOfWar>,
     tileset: Option<Res<TilesetHandle>>,
     existing_tilemap: Query<Entity, With<WorldMap>>,
     mut images: ResMut<Assets<Image>>,
@@ -348,7 +469,6 @@ fn spawn_tilemap_from_map_data(
                     ..Default::default()
                 },
                 WorldMapTile,
-                HighSeasEntity,
             ))
             .id();
         tile_storage.set(&tile_pos, tile_entity);
@@ -376,7 +496,6 @@ fn spawn_tilemap_from_map_data(
             ..Default::default()
         },
         WorldMap,
-        HighSeasEntity,
     ));
 
     info!("World map tilemap spawned: {}x{} tiles", map_size.x, map_size.y);
@@ -388,6 +507,12 @@ fn spawn_tilemap_from_map_data(
     for x in 0..map_data.width {
         for y in 0..map_data.height {
             let tile_pos = TilePos { x, y };
+            // Tiles explored before this layer was built (e.g. from a loaded save) start revealed
+            let color = if fog_of_war.is_explored(IVec2::new(x as i32, y as i32)) {
+                TileColor(Color::srgba(1.0, 1.0, 1.0, 0.0))
+            } else {
+                TileColor::default()
+            };
             
             let tile_entity = commands
                 .spawn((
@@ -395,10 +520,10 @@ fn spawn_tilemap_from_map_data(
                         position: tile_pos,
                         tilemap_id: TilemapId(fog_tilemap_entity),
                         texture_index: TileTextureIndex(7), // Fog/Parchment tile
+                        color,
                         ..Default::default()
                     },
                     FogTile,
-                    HighSeasEntity,
                 ))
                 .id();
             fog_storage.set(&tile_pos, tile_entity);
@@ -422,86 +547,73 @@ fn spawn_tilemap_from_map_data(
             ..Default::default()
         },
         FogMap,
-        HighSeasEntity,
     ));
 
     info!("Fog tilemap spawned: {}x{} tiles", map_size.x, map_size.y);
 }
 
+/// The captain the player sets sail as, the ship classes the starting
+/// ship is found in, and the hold she brought back from her last battle.
+#[derive(SystemParam)]
+pub struct StartingOutfit<'w> {
+    selected_archetype: Res<'w, crate::plugins::main_menu::SelectedArchetype>,
+    registry: Res<'w, crate::resources::ArchetypeRegistry>,
+    ships: Res<'w, ShipDefinitions>,
+    snapshot: Res<'w, crate::resources::RunSnapshot>,
+}
+
 /// Spawns the player ship in the High Seas view.
-/// Applies archetype bonuses from the selected starting character.
-fn spawn_high_seas_player(
+/// Applies archetype bonuses from the selected starting character; its
+/// reputation bonuses are applied once, by `start_run_reputation`.
+pub fn spawn_high_seas_player(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     map_data: Res<MapData>,
-    selected_archetype: Res<crate::plugins::main_menu::SelectedArchetype>,
-    registry: Res<crate::resources::ArchetypeRegistry>,
-    mut faction_registry: ResMut<crate::resources::FactionRegistry>,
+    outfit: StartingOutfit,
     archipelagos: Option<Res<LandmassArchipelagos>>,
+    run_settings: Res<RunSettings>,
 ) {
     use crate::components::{Cargo, Gold};
 
     // Get archetype configuration
-    let archetype_config = registry.get(selected_archetype.0);
-    let (starting_gold, ship_type) = archetype_config
-        .map(|c| (c.starting_gold, c.ship_type))
-        .unwrap_or((500, ShipType::Sloop)); // Fallback to defaults
+    let archetype_config = outfit.registry.get(outfit.selected_archetype.0);
+    let (starting_gold, ship) = archetype_config
+        .map(|c| (c.starting_gold, outfit.ships.definition(&c.ship, ShipType::Sloop)))
+        .unwrap_or_else(|| (500, ShipDefinition::default())); // Fallback to defaults
+    let ship_type = ship.hull;
+    let starting_gold =
+        (starting_gold as f32 * run_settings.difficulty.starting_gold_multiplier()).round() as u32;
 
     info!(
-        "Spawning player for High Seas with archetype {:?}: {} gold, {:?}",
-        selected_archetype.0, starting_gold, ship_type
+        "Spawning player for High Seas with archetype {:?}: {} gold, {}",
+        outfit.selected_archetype.0, starting_gold, ship.name
     );
 
-    // Apply faction reputation bonuses from archetype
-    if let Some(config) = archetype_config {
-        for (faction_id, rep_modifier) in &config.faction_reputation {
-            if let Some(faction_state) = faction_registry.get_mut(*faction_id) {
-                faction_state.player_reputation += rep_modifier;
-                info!(
-                    "Applied {:+} reputation to {:?} (now {})",
-                    rep_modifier, faction_id, faction_state.player_reputation
-                );
-            }
-        }
-    }
-
     // Spawn at dynamically found valid water tile
     use crate::utils::pathfinding::tile_to_world;
     let spawn_pos = tile_to_world(map_data.spawn_tile, map_data.width, map_data.height);
     let center_x = spawn_pos.x;
     let center_y = spawn_pos.y;
 
-    // Select sprite based on ship type
-    let sprite_path = match ship_type {
-        ShipType::Sloop => "sprites/ships/player.png",
-        ShipType::Frigate => "sprites/ships/frigate.png",
-        ShipType::Schooner => "sprites/ships/schooner.png",
-        ShipType::Raft => "sprites/ships/raft.png",
-    };
-    let texture_handle: Handle<Image> = asset_server.load(sprite_path);
-
-    // Adjust cargo capacity based on ship type
-    let cargo_capacity = match ship_type {
-        ShipType::Sloop => 100,
-        ShipType::Frigate => 200,
-        ShipType::Schooner => 150,
-        ShipType::Raft => 30,
-    };
+    let texture_handle: Handle<Image> = asset_server.load(&ship.sprite);
 
     // Get appropriate archipelago for ship type
     let tier = ShoreBufferTier::from_ship_type(ship_type);
     let archipelago_entity = archipelagos.as_ref().map(|a| a.get(tier));
 
+    // Back from port or a battle she keeps her damage, hold and purse
+    let flagship = outfit.snapshot.flagship.as_ref();
     let mut entity_commands = commands.spawn((
         Name::new("High Seas Player"),
         Player,
         Ship,
         ship_type, // ShipType component for turn rate calculations
+        ShipClass(ship.id.clone()),
         HighSeasPlayer,
         Vision { radius: 10.0 }, // Sight radius in tiles
-        Health::default(),       // Required by camera follow
-        Cargo::new(cargo_capacity),
-        Gold(starting_gold),
+        flagship.map_or_else(Health::default, |flagship| flagship.health.clone()),
+        flagship.map_or_else(|| Cargo::armed(ship.cargo), |flagship| flagship.cargo.clone()),
+        Gold(flagship.map_or(starting_gold, |flagship| flagship.gold)),
         Sprite {
             image: texture_handle,
             custom_size: Some(Vec2::splat(64.0)),
@@ -519,8 +631,8 @@ fn spawn_high_seas_player(
                 agent: Default::default(),
                 settings: AgentSettings {
                     radius: tier.agent_radius(),
-                    desired_speed: ship_type.base_speed(),
-                    max_speed: ship_type.base_speed() * 1.3,
+                    desired_speed: ship.speed,
+                    max_speed: ship.speed * 1.3,
                 },
                 archipelago_ref: ArchipelagoRef2d::new(arch_entity),
             },
@@ -536,12 +648,18 @@ fn spawn_legacy_wrecks(
     asset_server: Res<AssetServer>,
     profile: Res<crate::resources::MetaProfile>,
     existing_wrecks: Query<Entity, With<LegacyWreckMarker>>,
+    run_settings: Res<RunSettings>,
 ) {
     // Don't spawn duplicates if wrecks already exist
     if !existing_wrecks.is_empty() {
         return;
     }
 
+    if run_settings.modifiers.no_legacy_wrecks {
+        debug!("Legacy wrecks disabled by run modifier");
+        return;
+    }
+
     const TILE_SIZE: f32 = 16.0;
 
     for (index, wreck) in profile.legacy_wrecks.iter().enumerate() {
@@ -661,60 +779,66 @@ fn parse_good_type(s: &str) -> Option<crate::components::cargo::GoodType> {
         "Timber" => Some(GoodType::Timber),
         "Cloth" => Some(GoodType::Cloth),
         "Weapons" => Some(GoodType::Weapons),
+        "Fish" => Some(GoodType::Fish),
+        "Fruit" => Some(GoodType::Fruit),
         _ => None,
     }
 }
 
-/// Despawns the world map when leaving HighSeas state.
-pub fn despawn_tilemap(
-    mut commands: Commands,
-    tilemap_query: Query<Entity, Or<(With<WorldMap>, With<FogMap>)>>,
-    tile_query: Query<Entity, Or<(With<WorldMapTile>, With<FogTile>)>>,
-) {
-    // Despawn all tiles first
-    for entity in tile_query.iter() {
-        commands.entity(entity).despawn_recursive();
-    }
-
-    // Despawn the tilemap entity
-    for entity in tilemap_query.iter() {
-        commands.entity(entity).despawn_recursive();
-    }
-
-    info!("Tilemaps despawned");
-}
-
-/// Hides the world map and fog tilemaps (used when entering Combat).
-fn hide_tilemap(
-    mut tilemap_query: Query<&mut Visibility, Or<(With<WorldMap>, With<FogMap>)>>,
+/// Hides the persistent world render layers when leaving the High Seas.
+fn hide_world_layers(
+    mut layer_query: Query<
+        &mut Visibility,
+        Or<(With<WorldMap>, With<FogMap>, With<StippleOverlay>, With<CoastlineShape>)>,
+    >,
 ) {
-    for mut visibility in &mut tilemap_query {
+    for mut visibility in &mut layer_query {
         *visibility = Visibility::Hidden;
     }
 }
 
-/// Shows the world map and fog tilemaps (used when entering HighSeas).
-fn show_tilemap(
-    mut tilemap_query: Query<&mut Visibility, Or<(With<WorldMap>, With<FogMap>)>>,
+/// Shows the persistent world render layers when entering the High Seas,
+/// respecting the debug visibility toggles.
+fn show_world_layers(
+    toggles: Res<DebugToggles>,
+    lod: Res<MapLod>,
+    mut tilemap_query: Query<&mut Visibility, (Or<(With<WorldMap>, With<FogMap>)>, Without<CoastlineShape>)>,
+    mut stipple_query: Query<&mut Visibility, (With<StippleOverlay>, Without<WorldMap>, Without<FogMap>, Without<CoastlineShape>)>,
+    mut coastline_query: Query<(&mut Visibility, Option<&MapDetail>), (With<CoastlineShape>, Without<WorldMap>, Without<FogMap>)>,
 ) {
+    let visible = |shown: bool| if shown { Visibility::Inherited } else { Visibility::Hidden };
+
     for mut visibility in &mut tilemap_query {
+        *visibility = visible(toggles.show_tilemap);
+    }
+    for mut visibility in &mut stipple_query {
         *visibility = Visibility::Inherited;
     }
+    for (mut visibility, detail) in &mut coastline_query {
+        *visibility = decoration_visibility(toggles.show_coastlines, &lod, detail);
+    }
 }
 
-/// Spawns AI ships on the High Seas map at random navigable locations.
-fn spawn_high_seas_ai_ships(
+/// Chance that a ship roaming the High Seas flies the black flag; the rest
+/// sail for one of the nations, picked evenly.
+const ROAMING_PIRATE_CHANCE: f64 = 0.5;
+
+/// Spawns AI ships on the High Seas map at random navigable locations, each
+/// flying a rolled flag, so the player's standing with it decides whether
+/// she is hostile.
+pub fn spawn_high_seas_ai_ships(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     map_data: Res<MapData>,
     archipelagos: Option<Res<LandmassArchipelagos>>,
+    run_settings: Res<RunSettings>,
+    balance: Res<crate::resources::BalanceConfig>,
+    ships: Res<ShipDefinitions>,
 ) {
     use rand::prelude::*;
 
     let mut rng = rand::thread_rng();
-    let num_ships = 50;
-
-    let texture_handle: Handle<Image> = asset_server.load("sprites/ships/enemy.png");
+    let num_ships = run_settings.ai_ship_count();
 
     // Collect navigable tiles (deep water only for AI ships)
     let navigable_tiles: Vec<(u32, u32)> = map_data.iter()
@@ -727,75 +851,85 @@ fn spawn_high_seas_ai_ships(
         return;
     }
 
-    // AI ships use random ship types with weighted distribution
-    let ship_types = [
-        (ShipType::Sloop, 0.4),     // 40% small pirates
-        (ShipType::Schooner, 0.35), // 35% merchants
-        (ShipType::Frigate, 0.15),  // 15% heavy ships
-        (ShipType::Raft, 0.1),      // 10% rafts
-    ];
-
     for i in 0..num_ships {
         let (tile_x, tile_y) = navigable_tiles[rng.gen_range(0..navigable_tiles.len())];
         let world_pos = tile_to_world(IVec2::new(tile_x as i32, tile_y as i32), map_data.width, map_data.height);
 
-        // Select ship type based on weighted distribution
-        let roll: f32 = rng.gen();
-        let mut cumulative = 0.0;
-        let ship_type = ship_types.iter()
-            .find(|(_, weight)| {
-                cumulative += weight;
-                roll < cumulative
-            })
-            .map(|(t, _)| *t)
-            .unwrap_or(ShipType::Sloop);
+        // Ship classes are drawn by their spawn weights
+        let ship = ships.roll_spawn(&mut rng);
 
-        let tier = ShoreBufferTier::from_ship_type(ship_type);
-        let archipelago_entity = archipelagos.as_ref().map(|a| a.get(tier));
+        let faction = if rng.gen_bool(ROAMING_PIRATE_CHANCE) {
+            FactionId::Pirates
+        } else {
+            *[FactionId::NationA, FactionId::NationB, FactionId::NationC].choose(&mut rng).unwrap()
+        };
+        spawn_high_seas_ai_ship(
+            &mut commands,
+            &asset_server,
+            archipelagos.as_deref(),
+            format!("High Seas AI Ship {}", i),
+            &ship,
+            faction,
+            world_pos,
+            &balance.ai_speed,
+        );
+    }
 
-        // All ships are Pirates for now (until reputation system in Phase 5)
-        let faction = FactionId::Pirates;
+    info!("Spawned {} AI ships on High Seas map", num_ships);
+}
 
-        let mut entity_commands = commands.spawn((
-            Name::new(format!("High Seas AI Ship {}", i)),
-            Ship,
-            ship_type, // ShipType component for turn rate calculations
-            AI,
-            Faction(faction),
-            HighSeasAI,
-            Health::default(),
-            Sprite {
-                image: texture_handle.clone(),
-                custom_size: Some(Vec2::splat(48.0)), // Slightly smaller than player
-                flip_y: true,
-                ..default()
+/// Spawns one patrolling AI ship of class `ship` on the High Seas, under
+/// her class's sprite, with a landmass agent if the navigation meshes are
+/// ready. Returns the ship's entity.
+pub fn spawn_high_seas_ai_ship(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    archipelagos: Option<&LandmassArchipelagos>,
+    name: String,
+    ship: &ShipDefinition,
+    faction: FactionId,
+    world_pos: Vec2,
+    ai_speed: &crate::resources::AiSpeedBalance,
+) -> Entity {
+    let ship_type = ship.hull;
+    let tier = ShoreBufferTier::from_ship_type(ship_type);
+    let archipelago_entity = archipelagos.map(|a| a.get(tier));
+
+    let mut entity_commands = commands.spawn((
+        Name::new(name),
+        Ship,
+        ship_type, // ShipType component for turn rate calculations
+        ShipClass(ship.id.clone()),
+        AI,
+        Faction(faction),
+        HighSeasAI,
+        Health::default(),
+        Sprite {
+            image: asset_server.load(ship.enemy_sprite()),
+            custom_size: Some(Vec2::splat(48.0)), // Slightly smaller than player
+            flip_y: true,
+            ..default()
+        },
+        Transform::from_xyz(world_pos.x, world_pos.y, 1.0), // Same layer as player
+        OrderQueue::with_order(Order::Patrol {
+            center: world_pos,
+            radius: 1500.0, // Approx 23 tiles
+            waypoint_index: 0,
+        }),
+        HighSeasEntity,
+    ));
+
+    // Add landmass agent components if archipelago is available
+    if let Some(arch_entity) = archipelago_entity {
+        entity_commands.insert((
+            Agent2dBundle {
+                agent: Default::default(),
+                settings: ai_speed.agent_settings(ship_type, ship.speed), // AI slower than player
+                archipelago_ref: ArchipelagoRef2d::new(arch_entity),
             },
-            Transform::from_xyz(world_pos.x, world_pos.y, 1.0), // Same layer as player
-            OrderQueue::with_order(Order::Patrol {
-                center: world_pos,
-                radius: 1500.0, // Approx 23 tiles
-                waypoint_index: 0,
-            }),
-            HighSeasEntity,
         ));
-
-        // Add landmass agent components if archipelago is available
-        if let Some(arch_entity) = archipelago_entity {
-            entity_commands.insert((
-                Agent2dBundle {
-                    agent: Default::default(),
-                    settings: AgentSettings {
-                        radius: tier.agent_radius(),
-                        desired_speed: ship_type.base_speed() * 0.5, // AI slower than player
-                        max_speed: ship_type.base_speed() * 0.65,
-                    },
-                    archipelago_ref: ArchipelagoRef2d::new(arch_entity),
-                },
-            ));
-        }
     }
-
-    info!("Spawned {} AI ships on High Seas map", num_ships);
+    entity_commands.id()
 }
 
 /// Updates AI ship visibility based on fog of war.
@@ -803,7 +937,7 @@ fn spawn_high_seas_ai_ships(
 fn fog_of_war_ai_visibility_system(
     fog_of_war: Res<FogOfWar>,
     map_data: Res<MapData>,
-    mut query: Query<(&Transform, &mut Visibility), With<HighSeasAI>>,
+    mut query: Query<(&Transform, &mut Visibility), (With<HighSeasAI>, Without<crate::plugins::ai_activation::Dormant>)>,
 ) {
     for (transform, mut visibility) in &mut query {
         let world_pos = transform.translation.truncate();
@@ -818,7 +952,8 @@ fn fog_of_war_ai_visibility_system(
 }
 
 /// Rebuilds the encounter spatial hash from current AI ship positions.
-/// Runs each frame to keep positions current.
+/// Runs each frame to keep positions current. Dormant ships are included, so
+/// one moved into range by an abstract step can still trigger an encounter.
 fn rebuild_encounter_spatial_hash(
     mut encounter_hash: ResMut<EncounterSpatialHash>,
     ai_query: Query<(Entity, &Transform), With<HighSeasAI>>,
@@ -832,37 +967,45 @@ fn rebuild_encounter_spatial_hash(
 }
 
 /// Detects when the player is near hostile AI ships and triggers combat.
-fn encounter_detection_system(
+/// A ship is hostile when its faction is (by the player's reputation with it),
+/// and bounty hunters are hostile whatever their flag. Squalls hide the player
+/// from hostile ships, and darkness lets them close in unseen.
+pub fn encounter_detection_system(
     encounter_hash: Res<EncounterSpatialHash>,
     encounter_cooldown: Res<EncounterCooldown>,
-    player_query: Query<&Transform, (With<Player>, With<HighSeasPlayer>)>,
-    ai_query: Query<(Entity, &Transform, &Faction, Option<&Name>), With<HighSeasAI>>,
+    hostility: Hostility,
+    player_query: Query<(&Transform, Has<InWeather>), (With<Player>, With<HighSeasPlayer>)>,
+    // A merchant that has heaved to and been plundered is left to sail on, and
+    // a ship too heavy for the player yet stands off (see `plugins::pacing`)
+    ai_query: Query<(Entity, &Transform, &Faction, Option<&Name>, Has<BountyHunter>), (With<HighSeasAI>, Without<Plundered>, Without<StandsOff>)>,
     mut combat_events: EventWriter<CombatTriggeredEvent>,
+    encounter_range: EncounterRange,
 ) {
     // Don't trigger new encounters while one is being processed
     if encounter_cooldown.active {
         return;
     }
     
-    let Ok(player_transform) = player_query.get_single() else {
+    // Nobody finds a ship hidden in a squall
+    let Ok((player_transform, false)) = player_query.get_single() else {
         return;
     };
     
     let player_pos = player_transform.translation.truncate();
-    let nearby_ships = encounter_hash.hash.query(player_pos, ENCOUNTER_RADIUS);
+    let encounter_radius = encounter_range.radius();
+    let nearby_ships = encounter_hash.hash.query(player_pos, encounter_radius);
     
     for &entity_ref in &nearby_ships {
         let entity = *entity_ref;
-        if let Ok((_, ai_transform, faction, name)) = ai_query.get(entity) {
+        if let Ok((_, ai_transform, faction, name, bounty_hunter)) = ai_query.get(entity) {
             let ai_pos = ai_transform.translation.truncate();
             let distance = player_pos.distance(ai_pos);
             
             // Double-check distance (spatial hash is approximate)
-            if distance <= ENCOUNTER_RADIUS {
-                // Hostility check (3.6.4): Pirates are always hostile
-                let is_hostile = matches!(faction.0, FactionId::Pirates);
-                
-                if is_hostile {
+            if distance <= encounter_radius {
+                // Hostility check (3.6.4)
+                // A letter of marque makes the rival's ships fair game
+                if bounty_hunter || hostility.is_hostile(faction.0) {
                     let ship_name = name.map(|n| n.as_str()).unwrap_or("Unknown Ship");
                     info!(
                         "Hostile encounter! {} ({:?}) at distance {:.0} - triggering combat!",
@@ -889,6 +1032,9 @@ fn handle_combat_trigger_system(
     mut next_state: ResMut<NextState<GameState>>,
     mut encounter_cooldown: ResMut<EncounterCooldown>,
     mut encountered_enemy: ResMut<EncounteredEnemy>,
+    player_query: Query<(Entity, &Transform), With<HighSeasPlayer>>,
+    fleet_entities: Res<crate::resources::FleetEntities>,
+    fleet_orders: Query<&crate::components::OrderQueue>,
 ) {
     for event in combat_events.read() {
         info!(
@@ -898,6 +1044,22 @@ fn handle_combat_trigger_system(
         
         // Store encounter data for combat spawning (3.6.7)
         encountered_enemy.faction = Some(event.enemy_faction);
+        let player = player_query.get_single().ok();
+        encountered_enemy.position = player.map(|(_, t)| t.translation.truncate());
+
+        // Ships escorting the player follow her into battle
+        encountered_enemy.escorts = fleet_entities
+            .entities
+            .iter()
+            .enumerate()
+            .filter(|(_, entity)| {
+                fleet_orders.get(**entity).is_ok_and(|orders| {
+                    matches!(orders.current(), Some(crate::components::Order::Escort { target, .. })
+                        if player.is_some_and(|(player, _)| player == *target))
+                })
+            })
+            .map(|(index, _)| index)
+            .collect();
         
         // Set cooldown to prevent re-triggering
         encounter_cooldown.active = true;
@@ -910,6 +1072,56 @@ fn handle_combat_trigger_system(
     }
 }
 
+/// Ships of the encountered ship's flag within this of her join the battle (world units).
+pub const SQUADRON_RADIUS: f32 = 384.0;
+/// Most ships sighted in one encountered squadron.
+pub const MAX_SIGHTED_SQUADRON: usize = 4;
+
+/// Records the make-up of the squadron the player has run into: the
+/// encountered ship and the ships of her flag sailing near her, save any
+/// standing off.
+fn record_encounter_squadron(
+    mut combat_events: EventReader<CombatTriggeredEvent>,
+    mut encountered_enemy: ResMut<EncounteredEnemy>,
+    encounter_hash: Res<EncounterSpatialHash>,
+    ships: Query<(&Transform, &Faction, Option<&ShipType>, Option<&ShipClass>, Has<StandsOff>), With<HighSeasAI>>,
+) {
+    let sighted = |ship_type: Option<&ShipType>, class: Option<&ShipClass>| {
+        let ship_type = ship_type.copied().unwrap_or_default();
+        (ship_type, class.cloned().unwrap_or_else(|| ShipClass::of(ship_type)))
+    };
+    // Only the first encounter of a frame is fought
+    let Some(event) = combat_events.read().next() else {
+        return;
+    };
+    let Ok((transform, faction, ship_type, class, _)) = ships.get(event.enemy_entity) else {
+        encountered_enemy.squadron.clear();
+        return;
+    };
+    let position = transform.translation.truncate();
+
+    let mut consorts: Vec<(f32, (ShipType, ShipClass))> = encounter_hash
+        .hash
+        .query(position, SQUADRON_RADIUS)
+        .into_iter()
+        .filter(|entity| **entity != event.enemy_entity)
+        .filter_map(|entity| ships.get(*entity).ok())
+        // Ships standing off keep out of it
+        .filter(|(_, other, _, _, standing_off)| other.0 == faction.0 && !standing_off)
+        .map(|(other, _, other_type, other_class, _)| {
+            (other.translation.truncate().distance(position), sighted(other_type, other_class))
+        })
+        .filter(|(distance, _)| *distance <= SQUADRON_RADIUS)
+        .collect();
+    consorts.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    encountered_enemy.squadron = std::iter::once(sighted(ship_type, class))
+        .chain(consorts.into_iter().map(|(_, ship)| ship))
+        .take(MAX_SIGHTED_SQUADRON)
+        .collect();
+    info!("Encountered squadron: {:?}", encountered_enemy.squadron);
+}
+
 /// Resets the encounter cooldown when entering HighSeas state.
 fn reset_encounter_cooldown(mut cooldown: ResMut<EncounterCooldown>) {
     cooldown.active = false;
@@ -921,9 +1133,11 @@ pub struct HighSeasPort;
 
 /// Spawns port entities at port tile locations on the map.
 /// Each port gets an Inventory with random goods, a generated name, and a faction.
-fn spawn_port_entities(
+pub fn spawn_port_entities(
     mut commands: Commands,
     map_data: Res<MapData>,
+    run_settings: Res<RunSettings>,
+    goods: Res<GoodsTable>,
 ) {
     use rand::Rng;
     
@@ -949,7 +1163,14 @@ fn spawn_port_entities(
             };
             
             // Spawn the port entity using the port plugin function
-            let entity = spawn_port(&mut commands, world_pos, name.clone(), Faction(faction));
+            let entity = spawn_port(
+                &mut commands,
+                world_pos,
+                name.clone(),
+                Faction(faction),
+                &goods,
+                run_settings.port_stock_multiplier(),
+            );
             
             // Add the HighSeasPort marker for cleanup
             commands.entity(entity).insert((HighSeasPort, HighSeasEntity));
@@ -967,9 +1188,10 @@ fn spawn_location_labels(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     coastline_data: Res<CoastlineData>,
+    map_data: Res<MapData>,
     port_query: Query<(&Transform, &crate::components::port::PortName), With<crate::components::port::Port>>,
 ) {
-    use crate::components::location_label::{LocationLabel, LabelImportance};
+    use crate::components::location_label::{LabelImportance, LabelPlacement, LocationLabel};
     use rand::Rng;
 
     let font = asset_server.load("fonts/Quintessential-Regular.ttf");
@@ -999,9 +1221,7 @@ fn spawn_location_labels(
         let label = LocationLabel::new(port_name.0.clone(), importance, angle);
 
         // Offset label position inland (perpendicular to coast)
-        let offset_distance = 40.0 + font_size; // Move label away from port icon
-        let offset = Vec2::from_angle(angle) * offset_distance;
-        let label_pos = port_pos + offset;
+        let label_pos = inland_label_position(port_pos, &label, &map_data);
 
         commands.spawn((
             Text2d::new(label.name.clone()),
@@ -1010,10 +1230,13 @@ fn spawn_location_labels(
                 font_size,
                 ..default()
             },
-            TextColor(ink_color),
+            // Starts transparent; the label layout pass fades it in
+            TextColor(ink_color.with_alpha(0.0)),
             Transform::from_xyz(label_pos.x, label_pos.y, 5.0)
                 .with_rotation(Quat::from_rotation_z(angle - std::f32::consts::FRAC_PI_2)),
+            Visibility::Hidden,
             LocationLabelMarker,
+            LabelPlacement::new(label_pos),
             label,
             HighSeasEntity,
         ));
@@ -1024,6 +1247,41 @@ fn spawn_location_labels(
     info!("Spawned {} location labels", label_count);
 }
 
+/// Picks a label centre inland of the port, stepping further inland until the
+/// text no longer crosses the coastline. Falls back to the nearest offset.
+fn inland_label_position(
+    port_pos: Vec2,
+    label: &crate::components::location_label::LocationLabel,
+    map_data: &MapData,
+) -> Vec2 {
+    let inland = Vec2::from_angle(label.angle);
+    // Text runs along the coast, perpendicular to the inland direction
+    let along = inland.perp();
+    let half_width = label.estimated_size().x / 2.0;
+    let base_distance = 40.0 + label.importance.font_size(); // Clear of the port icon
+
+    let on_land = |p: Vec2| {
+        let tile = world_to_tile(p, map_data.width, map_data.height);
+        tile.x >= 0
+            && tile.y >= 0
+            && map_data
+                .tile(tile.x as u32, tile.y as u32)
+                .is_some_and(|t| !t.tile_type.is_navigable())
+    };
+
+    for step in 0..4 {
+        let center = port_pos + inland * (base_distance + step as f32 * 24.0);
+        let clear = [-1.0, -0.5, 0.0, 0.5, 1.0]
+            .iter()
+            .all(|&t| on_land(center + along * half_width * t));
+        if clear {
+            return center;
+        }
+    }
+
+    port_pos + inland * base_distance
+}
+
 /// Calculates the angle perpendicular to the nearest coastline edge, pointing inland.
 /// Returns the angle in radians.
 fn calculate_coastline_perpendicular(pos: Vec2, coastline_data: &CoastlineData) -> f32 {
@@ -1070,6 +1328,9 @@ pub fn spawn_player_fleet(
     mut fleet_entities: ResMut<crate::resources::FleetEntities>,
     player_query: Query<(Entity, &Transform), With<crate::components::Player>>,
     asset_server: Res<AssetServer>,
+    archipelagos: Option<Res<LandmassArchipelagos>>,
+    formation: Res<crate::systems::FleetFormation>,
+    speeds: AiShipSpeeds,
 ) {
     // Clear any stale entity references
     fleet_entities.entities.clear();
@@ -1077,26 +1338,32 @@ pub fn spawn_player_fleet(
     let Ok((player_entity, player_transform)) = player_query.get_single() else {
         return;
     };
-    let player_pos = player_transform.translation.truncate();
+    let count = player_fleet.ships.len();
 
     for (i, ship_data) in player_fleet.ships.iter().enumerate() {
-        // Spawn fleet ships in a formation behind the player
-        let offset = Vec2::new(30.0 * (i as f32 + 1.0), -30.0 * (i as f32 + 1.0));
-        let spawn_pos = player_pos + offset;
+        // Spawn fleet ships on station in the fleet's formation
+        let spawn_pos = formation.formation.slot_position(i, count, player_transform);
         
         let texture_handle = asset_server.load(&ship_data.sprite_path);
+        let hull_bonus = crate::components::upgrade::upgrades_hull_bonus(&ship_data.upgrades);
 
-        let entity = commands.spawn((
+        let ship_type = ship_data.ship_type;
+        let class = ShipClass(ship_data.class.clone());
+        let mut entity_commands = commands.spawn((
             Name::new(format!("Fleet Ship: {}", ship_data.name)),
             crate::components::Ship,
             crate::components::AI,
             crate::components::PlayerOwned,
             HighSeasAI,
+            ship_type,
+            class.clone(),
+            crate::systems::FormationSlot(i),
             crate::components::Health {
-                hull: ship_data.hull_health,
-                hull_max: ship_data.max_hull_health,
+                hull: ship_data.hull_health + hull_bonus,
+                hull_max: ship_data.max_hull_health + hull_bonus,
                 ..default()
             },
+            crate::components::upgrade::Fittings::of(&ship_data.upgrades),
             Sprite {
                 image: texture_handle,
                 custom_size: Some(Vec2::splat(48.0)),
@@ -1110,7 +1377,18 @@ pub fn spawn_player_fleet(
                 follow_distance: 60.0 + (i as f32 * 20.0),
             }),
             HighSeasEntity,
-        )).id();
+        ));
+
+        // Fleet ships make for their formation slots through landmass
+        if let Some(archipelagos) = &archipelagos {
+            let tier = ShoreBufferTier::from_ship_type(ship_type);
+            entity_commands.insert(Agent2dBundle {
+                agent: Default::default(),
+                settings: speeds.agent_settings(ship_type, Some(&class)),
+                archipelago_ref: ArchipelagoRef2d::new(archipelagos.get(tier)),
+            });
+        }
+        let entity = entity_commands.id();
 
         // Track entity ID for UI access
         fleet_entities.entities.push(entity);
@@ -1306,48 +1584,34 @@ const WATERLINE_COUNT: usize = 3;
 /// Distance between waterlines in world units
 const WATERLINE_SPACING: f32 = 10.0;
 
-/// Spawns Lyon shape entities for each coastline polygon.
-/// Runs when entering HighSeas state.
-fn spawn_coastline_shapes(
+/// Builds batched coastline and waterline meshes from the coastline polygons.
+/// Runs when entering HighSeas state; skipped if the meshes already exist.
+/// Other decorations tagged `CoastlineShape` spawn after it, so they never
+/// look like existing meshes.
+pub fn spawn_coastline_shapes(
     mut commands: Commands,
     coastline_data: Res<CoastlineData>,
     existing_shapes: Query<Entity, With<CoastlineShape>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     // Don't spawn if already exists
     if !existing_shapes.is_empty() {
         return;
     }
 
-    let mut shapes_spawned = 0;
+    let mut coastlines = LineMeshBuilder::new();
+    let mut waterlines = LineMeshBuilder::new();
 
     for polygon in &coastline_data.polygons {
         if polygon.points.len() < 2 {
             continue;
         }
 
-        // --- 1. Spawn Main Coastline ---
-        let mut path_builder = PathBuilder::new();
-        let first = polygon.points[0];
-        path_builder.move_to(Vec2::new(first.x, first.y));
-        for point in polygon.points.iter().skip(1) {
-            path_builder.line_to(Vec2::new(point.x, point.y));
-        }
-        path_builder.close();
-        let path = path_builder.build();
-
-        commands.spawn((
-            CoastlineShape,
-            ShapeBundle {
-                path,
-                transform: Transform::from_xyz(0.0, 0.0, -8.0), // Between tilemap (-10) and fog (-5)
-                ..default()
-            },
-            Stroke::new(COASTLINE_INK_COLOR, COASTLINE_STROKE_WIDTH),
-            HighSeasEntity,
-        ));
-        shapes_spawned += 1;
+        // --- 1. Main Coastline ---
+        coastlines.add_polyline(&polygon.points, true, COASTLINE_STROKE_WIDTH, COASTLINE_INK_COLOR);
 
-        // --- 2. Spawn Waterlines (Ripples) ---
+        // --- 2. Waterlines (Ripples) ---
         // Cascade: each waterline offsets from the previous one
         let mut current_points = polygon.points.clone();
         
@@ -1356,18 +1620,8 @@ fn spawn_coastline_shapes(
             let offset_points = offset_polygon(&current_points, WATERLINE_SPACING);
             
             if offset_points.len() < 3 {
-                break; // Polygon collapsed, stop spawning more waterlines
-            }
-
-            // Build path for waterline
-            let mut wb = PathBuilder::new();
-            let p0 = offset_points[0];
-            wb.move_to(Vec2::new(p0.x, p0.y));
-            for p in offset_points.iter().skip(1) {
-                wb.line_to(Vec2::new(p.x, p.y));
+                break; // Polygon collapsed, stop adding more waterlines
             }
-            wb.close();
-            let water_path = wb.build();
 
             // Calculate fading color and width
             // Alpha decreases: 0.4 -> 0.27 -> 0.2
@@ -1376,25 +1630,40 @@ fn spawn_coastline_shapes(
             // Width decreases slightly
             let width = (COASTLINE_STROKE_WIDTH - (i as f32 * 0.4)).max(0.5);
 
-            commands.spawn((
-                CoastlineShape, // Managed by same cleanup/toggle system
-                ShapeBundle {
-                    path: water_path,
-                    // Slightly lower z-index for each successive line
-                    transform: Transform::from_xyz(0.0, 0.0, -8.1 - (i as f32 * 0.01)), 
-                    ..default()
-                },
-                Stroke::new(color, width),
-                HighSeasEntity,
-            ));
-            shapes_spawned += 1;
+            waterlines.add_polyline(&offset_points, true, width, color);
             
             // Update for next iteration
             current_points = offset_points;
         }
     }
 
-    info!("Spawned {} coastline shape entities (including waterlines)", shapes_spawned);
+    // Vertex colors carry the ink color, so one white material serves every stroke
+    let material = materials.add(ColorMaterial::from(Color::WHITE));
+    let coastline_vertices = coastlines.vertex_count();
+    let waterline_vertices = waterlines.vertex_count();
+
+    commands.spawn((
+        Name::new("Waterlines"),
+        CoastlineShape,
+        MapDetail::up_to(LodLevel::Standard),
+        Mesh2d(meshes.add(waterlines.build())),
+        MeshMaterial2d(material.clone()),
+        Transform::from_xyz(0.0, 0.0, -8.1), // Just below the coastline ink
+    ));
+    commands.spawn((
+        Name::new("Coastlines"),
+        CoastlineShape,
+        Mesh2d(meshes.add(coastlines.build())),
+        MeshMaterial2d(material),
+        Transform::from_xyz(0.0, 0.0, -8.0), // Between tilemap (-10) and fog (-5)
+    ));
+
+    info!(
+        "Built coastline meshes for {} polygons ({} coastline + {} waterline vertices)",
+        coastline_data.polygons.len(),
+        coastline_vertices,
+        waterline_vertices
+    );
 }
 
 /// Marker for elevation decoration entities (hills hachures, mountain peaks).
@@ -1402,12 +1671,14 @@ fn spawn_coastline_shapes(
 #[derive(Component)]
 pub struct ElevationMarker;
 
-/// Spawns decorative Lyon shapes for hills (hachures) and mountains (peaks).
-/// Part of the coastline visibility group.
+/// Builds a batched mesh of decorative strokes for grass, hills (hachures) and
+/// mountains (peaks). Part of the coastline visibility group.
 fn spawn_elevation_markers(
     mut commands: Commands,
     map_data: Res<MapData>,
     existing_markers: Query<Entity, With<ElevationMarker>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     use rand::prelude::*;
     use crate::resources::TileType;
@@ -1421,7 +1692,8 @@ fn spawn_elevation_markers(
     let ink_color = Color::srgba(0.15, 0.12, 0.08, 0.7); // Darker ink with transparency
     
     let mut rng = rand::thread_rng();
-    let mut markers_spawned = 0;
+    let mut strokes = LineMeshBuilder::new();
+    let mut markers_added = 0;
 
     for (x, y, tile) in map_data.iter() {
         let tile_type = tile.tile_type;
@@ -1433,14 +1705,11 @@ fn spawn_elevation_markers(
         let world_y = y as f32 * TILE_SIZE - (map_data.height as f32 * TILE_SIZE / 2.0);
 
         if tile_type == TileType::Land {
-            // Very SPARSE: Only spawn grass on 30% of land tiles
+            // Very SPARSE: Only add grass on 30% of land tiles
             if !rng.gen_bool(0.3) {
                 continue;
             }
 
-            // Draw 1 grass tuft
-            let mut path_builder = PathBuilder::new();
-            
             // Random position jitter (anywhere within ~2 tiles range)
             let jitter_x = rng.gen_range(-64.0..64.0);
             let jitter_y = rng.gen_range(-64.0..64.0);
@@ -1456,36 +1725,28 @@ fn spawn_elevation_markers(
                 let height = rng.gen_range(6.0..10.0);
                 let x_offset = (i as f32 - (num_blades as f32 - 1.0) / 2.0) * 3.0; // Spread horizontally
                 
-                path_builder.move_to(Vec2::new(center_x + x_offset, center_y));
                 // Curve slightly outwards
                 let tip_x = center_x + x_offset + angle_offset * 10.0;
                 let tip_y = center_y + height;
                 
-                // Quadratic bezier for curve? Or simple line.
                 // Simple line with slight curve via 2 segments
                 let mid_x = (center_x + x_offset + tip_x) / 2.0 + rng.gen_range(-1.0..1.0);
                 let mid_y = (center_y + tip_y) / 2.0;
                 
-                path_builder.line_to(Vec2::new(mid_x, mid_y));
-                path_builder.line_to(Vec2::new(tip_x, tip_y));
+                strokes.add_polyline(
+                    &[
+                        Vec2::new(center_x + x_offset, center_y),
+                        Vec2::new(mid_x, mid_y),
+                        Vec2::new(tip_x, tip_y),
+                    ],
+                    false,
+                    1.5,
+                    ink_color,
+                );
             }
-            
-            let path = path_builder.build();
-            
-            commands.spawn((
-                CoastlineShape,
-                ElevationMarker,
-                ShapeBundle {
-                    path,
-                    transform: Transform::from_xyz(0.0, 0.0, -7.95), // Slightly below hills
-                    ..default()
-                },
-                Stroke::new(ink_color, 1.5), 
-                HighSeasEntity,
-            ));
-            markers_spawned += 1;
+            markers_added += 1;
         } else if tile_type == TileType::Hills {
-            // SPARSE: Only spawn hachures on 50% of hill tiles
+            // SPARSE: Only add hachures on 50% of hill tiles
             if !rng.gen_bool(0.5) {
                 continue;
             }
@@ -1493,7 +1754,7 @@ fn spawn_elevation_markers(
             // Draw 1-2 wavy horizontal hachure lines per hills tile
             let num_lines = rng.gen_range(1..=2);
             for line_idx in 0..num_lines {
-                let mut path_builder = PathBuilder::new();
+                let mut points = Vec::with_capacity(16);
                 
                 // Randomized base y within tile, but keep away from top/bottom edges
                 let step = TILE_SIZE / (num_lines as f32 + 1.0);
@@ -1509,18 +1770,25 @@ fn spawn_elevation_markers(
                 let freq1: f32 = rng.gen_range(0.1..0.2); 
                 let amp2: f32 = rng.gen_range(1.0..2.0);
                 let freq2: f32 = rng.gen_range(0.3..0.6);
+
+                // Random position jitter (anywhere within ~2 tiles range),
+                // plus a half-tile nudge down-left
+                let origin = Vec2::new(
+                    world_x - 32.0 + rng.gen_range(-64.0..64.0),
+                    world_y - 32.0 + rng.gen_range(-64.0..64.0),
+                );
                 
                 // Start point
                 let start_val = 16.0; 
                 let end_val = 48.0;
                 let total_width = end_val - start_val;
 
-                // Move to start with arc + noise
+                // Start with arc + noise
                 let t_start = 0.0;
                 let arc_start = (t_start * std::f32::consts::PI).sin() * arc_height;
                 let noise_start = (start_val * freq1 + phase).sin() * amp1 + (start_val * freq2).cos() * amp2;
                 let start_y = base_y + y_offset + noise_start + arc_start;
-                path_builder.move_to(Vec2::new(world_x + start_val, world_y + start_y));
+                points.push(origin + Vec2::new(start_val, start_y));
                 
                 // Draw wavy line across tile with arc
                 for px in (20..=48).step_by(2) { 
@@ -1534,148 +1802,105 @@ fn spawn_elevation_markers(
                     
                     // Clamp y roughly
                     let clamped_y = local_y.clamp(8.0, 56.0);
-                    path_builder.line_to(Vec2::new(world_x + x_f32, world_y + clamped_y));
+                    points.push(origin + Vec2::new(x_f32, clamped_y));
                 }
                 
-                let path = path_builder.build();
-                
-                // Random position jitter (anywhere within ~2 tiles range)
-                let jitter_x = rng.gen_range(-64.0..64.0);
-                let jitter_y = rng.gen_range(-64.0..64.0);
-                
-                commands.spawn((
-                    CoastlineShape,
-                    ElevationMarker,
-                    ShapeBundle {
-                        path,
-                        // Nudge half-tile down-left + random jitter
-                        transform: Transform::from_xyz(-32.0 + jitter_x, -32.0 + jitter_y, -7.9), 
-                        ..default()
-                    },
-                    Stroke::new(ink_color, 2.0), // Thicker stroke for larger resolution
-                    HighSeasEntity,
-                ));
-                markers_spawned += 1;
+                strokes.add_polyline(&points, false, 2.0, ink_color); // Thicker stroke for larger resolution
+                markers_added += 1;
             }
         } else if tile_type == TileType::Mountains {
-            // SPARSE: Only spawn mountains on 70% of mountain tiles
+            // SPARSE: Only add mountains on 70% of mountain tiles
             if !rng.gen_bool(0.7) {
                 continue;
             }
 
             // Draw 1 peak symbol per mountain tile
-            let num_peaks = 1; 
-            for _ in 0..num_peaks {
-                let mut path_builder = PathBuilder::new();
-                
-                // Center peak within tile, clamped offset
-                let peak_x_offset: f32 = TILE_SIZE / 2.0 + rng.gen_range(-4.0..4.0);
-                
-                let peak_height: f32 = rng.gen_range(24.0..36.0); // Scaled height
-                let base_width: f32 = rng.gen_range(20.0..28.0);  // Scaled width
-                
-                let wave_amp: f32 = rng.gen_range(1.0..2.5);
-                let wave_freq: f32 = rng.gen_range(0.1..0.3);
-                let phase: f32 = rng.gen_range(0.0..std::f32::consts::TAU);
-                
-                // Left base point
-                let left_x = world_x + peak_x_offset - base_width / 2.0;
-                let base_y = world_y + TILE_SIZE / 2.0 - peak_height / 2.0;
-                let peak_top_y = base_y + peak_height;
-                
-                path_builder.move_to(Vec2::new(left_x, base_y));
-                
-                // Draw left slope
-                for i in 1..=8 {
-                    let t = i as f32 / 8.0;
-                    let px = left_x + (base_width / 2.0) * t + ((i as f32) * wave_freq + phase).sin() * wave_amp;
-                    let py = base_y + peak_height * t;
-                    path_builder.line_to(Vec2::new(px, py));
-                }
-                
-                // Peak point
-                let peak_top = Vec2::new(world_x + peak_x_offset, peak_top_y);
-                path_builder.line_to(peak_top);
-                
-                // Draw right slope
-                let right_slope_points: Vec<Vec2> = (0..=8).rev().map(|i| {
-                    let t = i as f32 / 8.0;
-                    let px = world_x + peak_x_offset + (base_width / 2.0) * (1.0 - t) + ((i as f32) * wave_freq + phase + 1.0).sin() * wave_amp;
-                    let py = base_y + peak_height * t;
-                    Vec2::new(px, py)
-                }).collect();
-                
-                for p in &right_slope_points {
-                    path_builder.line_to(*p);
-                }
-                
-                let path = path_builder.build();
-
-                // Random position jitter for mountains
-                let jitter_x = rng.gen_range(-64.0..64.0);
-                let jitter_y = rng.gen_range(-64.0..64.0);
-                
-                commands.spawn((
-                    CoastlineShape,
-                    ElevationMarker,
-                    ShapeBundle {
-                        path,
-                        transform: Transform::from_xyz(-32.0 + jitter_x, -32.0 + jitter_y, -7.8),
-                        ..default()
-                    },
-                    Stroke::new(ink_color, 2.5), // Thicker stroke
-                    HighSeasEntity,
-                ));
-                markers_spawned += 1;
-
-                // Spawn shading lines on the right slope
-                // Simple hatching: draw short horizontal lines from right slope inward
-                if rng.gen_bool(0.7) { // 70% chance of shading
-                    let mut shade_builder = PathBuilder::new();
-                    for i in 1..3 {
-                        if i < right_slope_points.len() - 1 {
-                            let p = right_slope_points[i];
-                            shade_builder.move_to(p);
-                            shade_builder.line_to(Vec2::new(p.x - 2.5, p.y - 1.0)); // Hash mark
-                        }
-                    }
-                    commands.spawn((
-                        CoastlineShape,
-                        ElevationMarker,
-                        ShapeBundle {
-                            path: shade_builder.build(),
-                            transform: Transform::from_xyz(-32.0 + jitter_x, -32.0 + jitter_y, -7.85), // Same jitter as parent
-                            ..default()
-                        },
-                        Stroke::new(Color::srgba(0.15, 0.12, 0.08, 0.5), 1.0), // Fainter ink
-                        HighSeasEntity,
-                    ));
-                    markers_spawned += 1;
+            let mut points = Vec::with_capacity(19);
+            
+            // Center peak within tile, clamped offset
+            let peak_x_offset: f32 = TILE_SIZE / 2.0 + rng.gen_range(-4.0..4.0);
+            
+            let peak_height: f32 = rng.gen_range(24.0..36.0); // Scaled height
+            let base_width: f32 = rng.gen_range(20.0..28.0);  // Scaled width
+            
+            let wave_amp: f32 = rng.gen_range(1.0..2.5);
+            let wave_freq: f32 = rng.gen_range(0.1..0.3);
+            let phase: f32 = rng.gen_range(0.0..std::f32::consts::TAU);
+
+            // Random position jitter for mountains, plus a half-tile nudge down-left
+            let jitter = Vec2::new(
+                -32.0 + rng.gen_range(-64.0..64.0),
+                -32.0 + rng.gen_range(-64.0..64.0),
+            );
+            
+            // Left base point
+            let left_x = world_x + peak_x_offset - base_width / 2.0;
+            let base_y = world_y + TILE_SIZE / 2.0 - peak_height / 2.0;
+            let peak_top_y = base_y + peak_height;
+            
+            points.push(Vec2::new(left_x, base_y) + jitter);
+            
+            // Draw left slope
+            for i in 1..=8 {
+                let t = i as f32 / 8.0;
+                let px = left_x + (base_width / 2.0) * t + ((i as f32) * wave_freq + phase).sin() * wave_amp;
+                let py = base_y + peak_height * t;
+                points.push(Vec2::new(px, py) + jitter);
+            }
+            
+            // Peak point
+            points.push(Vec2::new(world_x + peak_x_offset, peak_top_y) + jitter);
+            
+            // Draw right slope
+            let right_slope_points: Vec<Vec2> = (0..=8).rev().map(|i| {
+                let t = i as f32 / 8.0;
+                let px = world_x + peak_x_offset + (base_width / 2.0) * (1.0 - t) + ((i as f32) * wave_freq + phase + 1.0).sin() * wave_amp;
+                let py = base_y + peak_height * t;
+                Vec2::new(px, py) + jitter
+            }).collect();
+            points.extend_from_slice(&right_slope_points);
+            
+            strokes.add_polyline(&points, false, 2.5, ink_color); // Thicker stroke
+            markers_added += 1;
+
+            // Shading lines on the right slope
+            // Simple hatching: short hash marks from right slope inward
+            if rng.gen_bool(0.7) { // 70% chance of shading
+                let shade_color = Color::srgba(0.15, 0.12, 0.08, 0.5); // Fainter ink
+                for p in right_slope_points.iter().take(3).skip(1) {
+                    strokes.add_polyline(&[*p, Vec2::new(p.x - 2.5, p.y - 1.0)], false, 1.0, shade_color);
                 }
             }
         }
     }
 
-    info!("Spawned {} elevation markers (hills/mountains)", markers_spawned);
+    let vertex_count = strokes.vertex_count();
+    commands.spawn((
+        Name::new("Elevation Markers"),
+        CoastlineShape,
+        ElevationMarker,
+        MapDetail::up_to(LodLevel::Standard),
+        Mesh2d(meshes.add(strokes.build())),
+        MeshMaterial2d(materials.add(ColorMaterial::from(Color::WHITE))),
+        Transform::from_xyz(0.0, 0.0, -7.9), // Above coastlines, below fog
+    ));
+
+    info!("Built elevation marker mesh: {} markers, {} vertices", markers_added, vertex_count);
 }
 
-/// Updates coastline visibility based on debug toggle.
+/// Updates coastline and decoration visibility based on the debug toggle
+/// and the zoom level of detail.
 fn coastline_visibility_system(
     toggles: Res<DebugToggles>,
-    mut query: Query<&mut Visibility, With<CoastlineShape>>,
+    lod: Res<MapLod>,
+    mut query: Query<(&mut Visibility, Option<&MapDetail>), With<CoastlineShape>>,
 ) {
-    if !toggles.is_changed() {
+    if !toggles.is_changed() && !lod.is_changed() {
         return;
     }
 
-    let visibility = if toggles.show_coastlines {
-        Visibility::Inherited
-    } else {
-        Visibility::Hidden
-    };
-
-    for mut vis in &mut query {
-        *vis = visibility;
+    for (mut vis, detail) in &mut query {
+        *vis = decoration_visibility(toggles.show_coastlines, &lod, detail);
     }
 }
 
@@ -1749,10 +1974,12 @@ fn spawn_stipple_overlay(
     let material_handle = materials.add(StipplingMaterial {
         color: LinearRgba::new(0.15, 0.25, 0.4, 0.45), // Subdued nautical blue dots
         dot_spacing: 32.0, // Spacing in world units (half a tile)
+        map_size_world: map_half_extents(width, height).x * 2.0,
         depth_texture: image_handle,
     });
 
-    let mesh_handle = meshes.add(Rectangle::new(width as f32 * 64.0, height as f32 * 64.0));
+    let map_extents = map_half_extents(width, height) * 2.0;
+    let mesh_handle = meshes.add(Rectangle::new(map_extents.x, map_extents.y));
 
     commands.spawn((
         Mesh2d(mesh_handle),
@@ -1760,7 +1987,7 @@ fn spawn_stipple_overlay(
         // Align with map - offset by half tile down and left
         Transform::from_xyz(-32.0, -32.0, -9.0), // Above map (-10), below ships
         Name::new("StippleOverlay"),
-        HighSeasEntity,
+        StippleOverlay,
     ));
     
     info!("Spawned Stipple Overlay");
@@ -0,0 +1,303 @@
+//! Wrecks of enemy ships sunk in battle, charted on the High Seas.
+//!
+//! Ships sunk in combat are recorded in `WreckSites` with what the battle
+//! salvage left aboard. On returning to the High Seas they are charted around
+//! the spot where the encounter began (`EncounteredEnemy::position`), and
+//! sailing over a wreck dives it for its gold and cargo. Cargo that does not
+//! fit in the hold stays aboard for a later dive.
+//!
+//! Battle and legacy wrecks decay with each in-game day until they break up,
+//! and once a wreck has lain charted for `SCAVENGER_GRACE_DAYS` the nearest
+//! pirate within `SCAVENGER_RANGE` is sent to strip her (`Order::Scavenge`).
+//! What decay and scavengers do to legacy wrecks reaches the profile file at
+//! the next autosave (see `plugins::save`).
+
+use bevy::prelude::*;
+
+use crate::components::{BountyHunter, Cargo, Faction, FactionId, Gold, HighSeasEntity, Order, OrderQueue, Player, PlayerOwned};
+use crate::plugins::core::GameState;
+use crate::plugins::worldmap::{EncounteredEnemy, HighSeasAI, HighSeasPlayer, LegacyWreckMarker};
+use crate::resources::{
+    surviving_wreck_index, Journal, MetaProfile, RunSettings, WorldClock, WreckSites, SCAVENGER_GRACE_DAYS,
+    WRECK_DIVE_RADIUS,
+};
+use crate::systems::world_tick_system;
+
+/// How far a pirate will sail to strip a wreck (world units; 64 tiles).
+const SCAVENGER_RANGE: f32 = 1024.0;
+/// Hour of the day scavengers are sent out.
+const SCAVENGER_HOUR: u32 = 6;
+/// Size of a wreck's chart marker (world units).
+const WRECK_MARKER_SIZE: f32 = 40.0;
+/// Wreck markers lie between the fog and the ships.
+const WRECK_MARKER_Z: f32 = 1.5;
+
+/// Plugin for charting and diving battle wrecks.
+pub struct WreckSitesPlugin;
+
+impl Plugin for WreckSitesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::HighSeas), (chart_wreck_sites, spawn_wreck_sites).chain())
+            .add_systems(
+                Update,
+                (dive_wreck_sites, scavenge_wrecks.after(dive_wreck_sites)).run_if(in_state(GameState::HighSeas)),
+            )
+            // Wrecks decay whether the player is at sea, in port or in battle
+            .add_systems(
+                FixedUpdate,
+                (decay_wreck_sites, decay_legacy_wrecks)
+                    .after(world_tick_system)
+                    .run_if(not(in_state(GameState::MainMenu))),
+            )
+            .add_systems(
+                FixedUpdate,
+                dispatch_scavengers.after(world_tick_system).run_if(in_state(GameState::HighSeas)),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), reset_wreck_sites);
+    }
+}
+
+/// Map marker for a charted wreck.
+#[derive(Component)]
+pub struct WreckSiteMarker {
+    /// `WreckSite::id` of the wreck shown.
+    pub id: u32,
+}
+
+/// Charts ships sunk in the last battle around where it began.
+fn chart_wreck_sites(
+    mut wrecks: ResMut<WreckSites>,
+    mut encountered_enemy: ResMut<EncounteredEnemy>,
+    world_clock: Res<WorldClock>,
+) {
+    match encountered_enemy.position.take() {
+        Some(position) => wrecks.chart(position, world_clock.day),
+        // Nowhere to put them (e.g. combat entered from the debug menu)
+        None => wrecks.sinkings.clear(),
+    }
+}
+
+fn spawn_wreck_sites(mut commands: Commands, asset_server: Res<AssetServer>, wrecks: Res<WreckSites>) {
+    for site in &wrecks.sites {
+        commands.spawn((
+            Name::new(format!("Wreck Site: {}", site.ship_name)),
+            WreckSiteMarker { id: site.id },
+            Sprite {
+                image: asset_server.load("sprites/loot/wreck.png"),
+                custom_size: Some(Vec2::splat(WRECK_MARKER_SIZE)),
+                color: Color::srgba(0.55, 0.6, 0.65, 0.9), // Fresh timber, still grey with sea water
+                ..default()
+            },
+            Transform::from_xyz(site.position.x, site.position.y, WRECK_MARKER_Z),
+            HighSeasEntity,
+        ));
+    }
+}
+
+/// Dives any wreck the player sails over, moving its gold and what fits of
+/// its cargo aboard. A wreck with cargo left in her stays charted.
+fn dive_wreck_sites(
+    mut commands: Commands,
+    mut player_query: Query<(&Transform, &mut Gold, &mut Cargo), (With<HighSeasPlayer>, With<Player>)>,
+    marker_query: Query<(Entity, &Transform, &WreckSiteMarker)>,
+    mut wrecks: ResMut<WreckSites>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    let Ok((player_transform, mut gold, mut cargo)) = player_query.get_single_mut() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    for (entity, transform, marker) in &marker_query {
+        if transform.translation.truncate().distance(player_pos) > WRECK_DIVE_RADIUS {
+            continue;
+        }
+        let Some(site) = wrecks.get_mut(marker.id) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        let (recovered_gold, recovered) = site.salvage_into(&mut cargo);
+        let ship_name = site.ship_name.clone();
+        let emptied = site.is_empty();
+        if emptied {
+            commands.entity(entity).despawn_recursive();
+            wrecks.dive(marker.id);
+        }
+        // Lying over a wreck with a full hold brings nothing more up
+        if recovered_gold == 0 && recovered == 0 {
+            continue;
+        }
+        gold.add(recovered_gold);
+        let left = if emptied { "" } else { " The rest is still down there." };
+        journal.record(
+            &world_clock,
+            format!(
+                "Dived the wreck of the {}: recovered {} gold and {} units of cargo.{}",
+                ship_name, recovered_gold, recovered, left
+            ),
+        );
+    }
+}
+
+/// Any wreck's map marker.
+type AnyWreck = Or<(With<WreckSiteMarker>, With<LegacyWreckMarker>)>;
+
+/// Decays battle wrecks each day, removing the markers of those that break up.
+fn decay_wreck_sites(
+    mut commands: Commands,
+    marker_query: Query<(Entity, &WreckSiteMarker)>,
+    mut wrecks: ResMut<WreckSites>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    if !world_clock.is_day_start() {
+        return;
+    }
+
+    for site in wrecks.decay(1, world_clock.day) {
+        for (entity, marker) in &marker_query {
+            if marker.id == site.id {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        journal.record(&world_clock, format!("The wreck of the {} has broken up.", site.ship_name));
+    }
+}
+
+/// Decays legacy wrecks each day, removing the markers of those lost and
+/// re-pointing the rest at their new place in the profile.
+fn decay_legacy_wrecks(
+    mut commands: Commands,
+    mut marker_query: Query<(Entity, &mut LegacyWreckMarker)>,
+    mut profile: ResMut<MetaProfile>,
+    world_clock: Res<WorldClock>,
+    run_settings: Res<RunSettings>,
+) {
+    if !world_clock.is_day_start() || run_settings.modifiers.no_legacy_wrecks || profile.legacy_wrecks.is_empty() {
+        return;
+    }
+
+    let removed = profile.decay_legacy_wrecks(1);
+    for (entity, mut marker) in &mut marker_query {
+        match surviving_wreck_index(marker.wreck_index, &removed) {
+            Some(index) => marker.wreck_index = index,
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+    if !removed.is_empty() {
+        info!("{} legacy wrecks broke up, {} remaining", removed.len(), profile.legacy_wrecks.len());
+    }
+}
+
+type Scavengers<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static Faction, &'static mut OrderQueue),
+    (With<HighSeasAI>, Without<PlayerOwned>, Without<BountyHunter>),
+>;
+
+/// Once a day, sends the nearest free pirate to each wreck that has lain
+/// charted past `SCAVENGER_GRACE_DAYS` and has no scavenger bound for her.
+fn dispatch_scavengers(
+    world_clock: Res<WorldClock>,
+    wrecks: Res<WreckSites>,
+    wreck_query: Query<(Entity, &Transform, Option<&WreckSiteMarker>), AnyWreck>,
+    mut scavengers: Scavengers,
+) {
+    if world_clock.tick != 0 || world_clock.hour != SCAVENGER_HOUR {
+        return;
+    }
+
+    let mut claimed: Vec<Entity> = scavengers
+        .iter()
+        .filter_map(|(_, _, orders)| match orders.current() {
+            Some(Order::Scavenge { wreck, .. }) => Some(*wreck),
+            _ => None,
+        })
+        .collect();
+
+    for (wreck, transform, site_marker) in &wreck_query {
+        // Legacy wrecks have lain there since the run began
+        let charted = site_marker
+            .and_then(|marker| wrecks.sites.iter().find(|site| site.id == marker.id))
+            .map_or(1, |site| site.day);
+        if claimed.contains(&wreck) || world_clock.day.saturating_sub(charted) < SCAVENGER_GRACE_DAYS {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let nearest = scavengers
+            .iter_mut()
+            .filter(|(ship, faction, orders)| {
+                faction.0 == FactionId::Pirates
+                    && !matches!(orders.current(), Some(Order::Scavenge { .. }))
+                    && ship.translation.truncate().distance(position) <= SCAVENGER_RANGE
+            })
+            .min_by(|(a, _, _), (b, _, _)| {
+                a.translation.truncate().distance(position).total_cmp(&b.translation.truncate().distance(position))
+            });
+        if let Some((_, _, mut orders)) = nearest {
+            orders.push_front(Order::Scavenge { wreck, position });
+            claimed.push(wreck);
+        }
+    }
+}
+
+/// Strips the wreck a scavenger has reached, and stands down scavengers whose
+/// wreck is already gone.
+fn scavenge_wrecks(
+    mut commands: Commands,
+    mut scavengers: Query<(&Transform, &mut OrderQueue), With<HighSeasAI>>,
+    mut wreck_query: Query<(Entity, Option<&WreckSiteMarker>, Option<&mut LegacyWreckMarker>), AnyWreck>,
+    mut wrecks: ResMut<WreckSites>,
+    mut profile: ResMut<MetaProfile>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    for (transform, mut orders) in &mut scavengers {
+        let Some(&Order::Scavenge { wreck, position }) = orders.current() else {
+            continue;
+        };
+        let Ok((_, site_marker, legacy_marker)) = wreck_query.get(wreck) else {
+            // Dived, broken up or stripped by another
+            orders.pop();
+            continue;
+        };
+        let site_id = site_marker.map(|marker| marker.id);
+        let legacy_index = legacy_marker.map(|marker| marker.wreck_index);
+        if transform.translation.truncate().distance(position) > WRECK_DIVE_RADIUS {
+            continue;
+        }
+        orders.pop();
+        commands.entity(wreck).despawn_recursive();
+
+        let ship_name = if let Some(id) = site_id {
+            wrecks.dive(id).map(|site| site.ship_name)
+        } else if let Some(index) = legacy_index.filter(|index| *index < profile.legacy_wrecks.len()) {
+            let stripped = profile.legacy_wrecks.remove(index);
+            for (_, _, marker) in &mut wreck_query {
+                if let Some(mut marker) = marker {
+                    if let Some(shifted) = surviving_wreck_index(marker.wreck_index, &[index]) {
+                        marker.wreck_index = shifted;
+                    }
+                }
+            }
+            Some(stripped.ship_name)
+        } else {
+            None
+        };
+
+        if let Some(ship_name) = ship_name {
+            journal.record(
+                &world_clock,
+                format!("Scavengers have stripped the wreck of the {} before we could reach her.", ship_name),
+            );
+        }
+    }
+}
+
+fn reset_wreck_sites(mut wrecks: ResMut<WreckSites>) {
+    wrecks.clear();
+}
@@ -0,0 +1,81 @@
+//! Distance-based simulation fidelity for High Seas AI ships.
+//!
+//! Ships near the player are simulated in full: landmass steering, coastline
+//! avoidance and a visible sprite. Distant ships go dormant and are moved
+//! along cached tile routes every `ABSTRACT_TICK_SECONDS` instead.
+
+use bevy::prelude::*;
+
+/// Dormant ships closer than this to the player wake up (world units).
+pub const ACTIVE_RADIUS: f32 = 2000.0;
+/// Active ships farther than this from the player go dormant (world units).
+/// Wider than `ACTIVE_RADIUS` so ships near the edge do not flicker between the two.
+pub const DORMANT_RADIUS: f32 = 2600.0;
+/// Seconds between abstract moves of dormant ships.
+pub const ABSTRACT_TICK_SECONDS: f32 = 0.5;
+
+/// How faithfully a ship is simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fidelity {
+    Full,
+    Abstract,
+}
+
+impl Fidelity {
+    /// Fidelity a ship at `distance` from the player should have, given its current one.
+    pub fn at_distance(self, distance: f32) -> Self {
+        match self {
+            Fidelity::Full if distance > DORMANT_RADIUS => Fidelity::Abstract,
+            Fidelity::Abstract if distance < ACTIVE_RADIUS => Fidelity::Full,
+            current => current,
+        }
+    }
+}
+
+/// Clock for abstract moves, and how many ships are at each fidelity.
+#[derive(Resource, Debug)]
+pub struct AiActivation {
+    pub timer: Timer,
+    pub active: usize,
+    pub dormant: usize,
+}
+
+impl Default for AiActivation {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(ABSTRACT_TICK_SECONDS, TimerMode::Repeating),
+            active: 0,
+            dormant: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::NavigationPath;
+
+    #[test]
+    fn test_fidelity_has_hysteresis() {
+        let between = (ACTIVE_RADIUS + DORMANT_RADIUS) / 2.0;
+        assert_eq!(Fidelity::Full.at_distance(between), Fidelity::Full);
+        assert_eq!(Fidelity::Abstract.at_distance(between), Fidelity::Abstract);
+        assert_eq!(Fidelity::Full.at_distance(DORMANT_RADIUS + 1.0), Fidelity::Abstract);
+        assert_eq!(Fidelity::Abstract.at_distance(ACTIVE_RADIUS - 1.0), Fidelity::Full);
+    }
+
+    #[test]
+    fn test_abstract_move_follows_route() {
+        let mut path = NavigationPath {
+            waypoints: vec![Vec2::new(100.0, 0.0), Vec2::new(100.0, 100.0)],
+        };
+        let position = path.advance(Vec2::ZERO, 150.0);
+        assert!(position.distance(Vec2::new(100.0, 50.0)) < 1e-3);
+        assert_eq!(path.waypoints.len(), 1);
+
+        // Overshooting the end stops at the last waypoint
+        let position = path.advance(position, 500.0);
+        assert_eq!(position, Vec2::new(100.0, 100.0));
+        assert!(path.is_empty());
+    }
+}
@@ -0,0 +1,248 @@
+//! Tunable gameplay numbers, read from `assets/data/balance.ron`.
+//!
+//! The values systems balance against (how close a hostile must come to force
+//! a battle, how hard and how often cannon fire, what the shipwrights charge,
+//! how fast AI ships sail, how strongly hulls stir the water, how soon the
+//! seas grow dangerous) live in `BalanceConfig` rather than in constants. Any
+//! field missing from the file keeps its default, so the file need only hold
+//! what is being tuned. It is a `ContentTable`, so it is reloaded on save
+//! like the other tables.
+
+use bevy::prelude::*;
+use bevy_landmass::prelude::AgentSettings;
+use serde::{Deserialize, Serialize};
+
+use crate::components::ship::ShipType;
+use crate::events::RepairType;
+use crate::resources::{ContentTable, Difficulty, ShoreBufferTier};
+use crate::systems::ai::AI_RELOAD_SECONDS;
+use crate::systems::repair::repair_config;
+
+/// Encounters on the High Seas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncounterBalance {
+    /// How close a hostile ship must come to force a battle (world units; 4 tiles).
+    pub radius: f32,
+}
+
+impl Default for EncounterBalance {
+    fn default() -> Self {
+        Self { radius: 256.0 }
+    }
+}
+
+/// Broadside gunnery.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CannonBalance {
+    /// Scales the damage of every broadside shot.
+    pub damage_multiplier: f32,
+    /// Seconds the player's guns take to reload, before crew and companion bonuses.
+    pub player_reload_seconds: f32,
+    /// Seconds an AI ship's guns take to reload, before crew bonuses.
+    pub ai_reload_seconds: f32,
+}
+
+impl Default for CannonBalance {
+    fn default() -> Self {
+        Self {
+            damage_multiplier: 1.0,
+            player_reload_seconds: 2.0,
+            ai_reload_seconds: AI_RELOAD_SECONDS,
+        }
+    }
+}
+
+/// Shipwrights' prices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepairBalance {
+    pub sails_cost_per_hp: f32,
+    pub rudder_cost_per_hp: f32,
+    pub hull_cost_per_hp: f32,
+}
+
+impl Default for RepairBalance {
+    fn default() -> Self {
+        Self {
+            sails_cost_per_hp: repair_config::SAILS_COST_PER_HP,
+            rudder_cost_per_hp: repair_config::RUDDER_COST_PER_HP,
+            hull_cost_per_hp: repair_config::HULL_COST_PER_HP,
+        }
+    }
+}
+
+impl RepairBalance {
+    /// Gold per point of damage to `repair_type`.
+    pub fn cost_per_hp(&self, repair_type: RepairType) -> f32 {
+        match repair_type {
+            RepairType::Sails => self.sails_cost_per_hp,
+            RepairType::Rudder => self.rudder_cost_per_hp,
+            RepairType::Hull => self.hull_cost_per_hp,
+        }
+    }
+}
+
+/// AI ships on the High Seas, as shares of their class's base speed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiSpeedBalance {
+    /// Speed AI ships cruise at; below 1.0 the player can outsail them.
+    pub cruise_share: f32,
+    /// Fastest a landmass agent may go to keep to its path.
+    pub max_share: f32,
+}
+
+impl Default for AiSpeedBalance {
+    fn default() -> Self {
+        Self {
+            cruise_share: 0.5,
+            max_share: 0.65,
+        }
+    }
+}
+
+impl AiSpeedBalance {
+    /// Cruising speed of an AI ship whose class sails at `speed`.
+    pub fn cruise_speed(&self, speed: f32) -> f32 {
+        speed * self.cruise_share
+    }
+
+    /// Landmass agent settings for an AI ship of `ship_type` whose class
+    /// sails at `speed`.
+    pub fn agent_settings(&self, ship_type: ShipType, speed: f32) -> AgentSettings {
+        AgentSettings {
+            radius: ShoreBufferTier::from_ship_type(ship_type).agent_radius(),
+            desired_speed: self.cruise_speed(speed),
+            max_speed: speed * self.max_share,
+        }
+    }
+}
+
+/// How hulls and the simulated water push on each other in battle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WakeBalance {
+    /// Scales the flow a moving hull pushes into the water.
+    pub displacement_force: f32,
+    /// Pull of the water's flow on a hull, per unit of relative velocity.
+    pub drag_force: f32,
+}
+
+impl Default for WakeBalance {
+    fn default() -> Self {
+        Self {
+            displacement_force: 1.0,
+            drag_force: 5.0,
+        }
+    }
+}
+
+/// How fast one difficulty lets the seas grow dangerous (see `PacingDirector`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PacingCurve {
+    /// Days at sea before the threat starts to rise.
+    pub grace_days: f32,
+    /// Days after that over which it rises to its full.
+    pub ramp_days: f32,
+    /// Threat added by each ship in the player's fleet, and each class the
+    /// flagship stands above a sloop.
+    pub per_ship: f32,
+    /// Threat added by each upgrade fitted across the fleet.
+    pub per_upgrade: f32,
+    /// Chance an enemy met at no threat at all turns tail; falls to nothing at full threat.
+    pub flee_chance: f32,
+}
+
+impl Default for PacingCurve {
+    fn default() -> Self {
+        Self {
+            grace_days: 2.0,
+            ramp_days: 12.0,
+            per_ship: 0.25,
+            per_upgrade: 0.08,
+            flee_chance: 0.35,
+        }
+    }
+}
+
+/// Early-run pacing at each difficulty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PacingBalance {
+    pub easy: PacingCurve,
+    pub normal: PacingCurve,
+    pub hard: PacingCurve,
+}
+
+impl Default for PacingBalance {
+    fn default() -> Self {
+        Self {
+            easy: PacingCurve {
+                grace_days: 5.0,
+                ramp_days: 20.0,
+                per_ship: 0.2,
+                per_upgrade: 0.05,
+                flee_chance: 0.5,
+            },
+            normal: PacingCurve::default(),
+            hard: PacingCurve {
+                grace_days: 0.0,
+                ramp_days: 6.0,
+                per_ship: 0.3,
+                per_upgrade: 0.1,
+                flee_chance: 0.2,
+            },
+        }
+    }
+}
+
+impl PacingBalance {
+    /// The curve for `difficulty`.
+    pub fn curve(&self, difficulty: Difficulty) -> &PacingCurve {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+}
+
+/// Every tunable number, loaded from `assets/data/balance.ron`.
+#[derive(Asset, TypePath, Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BalanceConfig {
+    pub encounter: EncounterBalance,
+    pub cannons: CannonBalance,
+    pub repair: RepairBalance,
+    pub ai_speed: AiSpeedBalance,
+    pub wake: WakeBalance,
+    pub pacing: PacingBalance,
+}
+
+impl ContentTable for BalanceConfig {
+    const ASSET_PATH: &'static str = "data/balance.ron";
+    const NAME: &'static str = "balance";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_balance_matches_defaults() {
+        let shipped = BalanceConfig::from_ron(include_str!("../../assets/data/balance.ron")).unwrap();
+        assert_eq!(shipped, BalanceConfig::default());
+    }
+
+    #[test]
+    fn test_missing_fields_keep_defaults() {
+        let balance = BalanceConfig::from_ron("(cannons: (damage_multiplier: 1.5))").unwrap();
+        assert_eq!(balance.cannons, CannonBalance { damage_multiplier: 1.5, ..Default::default() });
+        assert_eq!(balance.encounter, EncounterBalance::default());
+
+        assert!(BalanceConfig::from_ron("(cannons: (damage_multiplier: \"lots\"))").is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! A bird's-eye trace of the last battle, for the replay viewer.
+//!
+//! While a battle lasts every ship's position and heading and every
+//! cannonball in flight are sampled `REPLAY_SAMPLE_HZ` times a second, along
+//! with where shots struck. The battle summary offers to play the trace back
+//! on a plain chart, and to write it out as JSON to share.
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::MetaProfile;
+
+/// Samples taken per second of battle.
+pub const REPLAY_SAMPLE_HZ: f32 = 10.0;
+/// Longest stretch of battle kept, so a stalemate cannot fill memory (seconds).
+pub const REPLAY_MAX_SECONDS: f32 = 900.0;
+
+/// Whose ship a traced ship is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplaySide {
+    Player,
+    /// One of the player's fleet.
+    Fleet,
+    Enemy,
+}
+
+/// A ship at one sample.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReplayShip {
+    /// Stable for the battle, to follow a ship from sample to sample.
+    pub id: u32,
+    pub side: ReplaySide,
+    pub position: Vec2,
+    /// Angle of the bow from the +X axis (radians).
+    pub heading: f32,
+}
+
+/// Everything afloat at one moment of the battle.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    /// Seconds since battle was joined.
+    pub time: f32,
+    pub ships: Vec<ReplayShip>,
+    /// Cannonballs in flight.
+    pub shots: Vec<Vec2>,
+}
+
+/// A shot striking a ship.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReplayHit {
+    pub time: f32,
+    pub position: Vec2,
+    /// Whether the ship struck was the player's or one of the fleet.
+    pub on_player_side: bool,
+}
+
+/// The trace of the current (or last) battle.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BattleTrace {
+    pub frames: Vec<ReplayFrame>,
+    pub hits: Vec<ReplayHit>,
+}
+
+impl BattleTrace {
+    /// Adds a sample, unless the trace is already full.
+    pub fn record(&mut self, frame: ReplayFrame) -> bool {
+        if frame.time > REPLAY_MAX_SECONDS {
+            return false;
+        }
+        self.frames.push(frame);
+        true
+    }
+
+    pub fn record_hit(&mut self, hit: ReplayHit) {
+        if hit.time <= REPLAY_MAX_SECONDS {
+            self.hits.push(hit);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Seconds of battle traced.
+    pub fn duration(&self) -> f32 {
+        self.frames.last().map_or(0.0, |frame| frame.time)
+    }
+
+    /// Index of the last sample taken at or before `time`.
+    pub fn index_at(&self, time: f32) -> Option<usize> {
+        self.frames.partition_point(|frame| frame.time <= time).checked_sub(1)
+    }
+
+    /// The last sample taken at or before `time`.
+    pub fn frame_at(&self, time: f32) -> Option<&ReplayFrame> {
+        self.index_at(time).map(|index| &self.frames[index])
+    }
+
+    /// Hits struck in the `window` seconds up to `time`, with how long ago.
+    pub fn recent_hits(&self, time: f32, window: f32) -> impl Iterator<Item = (&ReplayHit, f32)> {
+        self.hits
+            .iter()
+            .filter(move |hit| hit.time <= time && time - hit.time < window)
+            .map(move |hit| (hit, time - hit.time))
+    }
+
+    /// Smallest rectangle holding every ship's track.
+    pub fn bounds(&self) -> Option<Rect> {
+        let mut positions = self.frames.iter().flat_map(|frame| frame.ships.iter().map(|ship| ship.position));
+        let first = positions.next()?;
+        Some(positions.fold(Rect::from_center_size(first, Vec2::ZERO), |bounds, position| {
+            bounds.union_point(position)
+        }))
+    }
+
+    /// Where shared replays are written.
+    pub fn export_path() -> Option<PathBuf> {
+        MetaProfile::get_save_dir().map(|dir| dir.join("replays").join("last_battle.json"))
+    }
+
+    /// Writes the trace out as JSON, returning where it went.
+    pub fn export(&self) -> Result<PathBuf, String> {
+        let Some(path) = Self::export_path() else {
+            return Err("Could not determine save directory".to_string());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create replay directory: {}", e))?;
+        }
+        let json = serde_json::to_string(self).map_err(|e| format!("Failed to serialize replay: {}", e))?;
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write replay: {}", e))?;
+        Ok(path)
+    }
+}
+
+/// Playback state of the replay viewer.
+#[derive(Resource, Debug, Clone)]
+pub struct ReplayViewer {
+    pub open: bool,
+    pub playing: bool,
+    /// Seconds into the battle being shown.
+    pub time: f32,
+    /// Playback speed, times real time.
+    pub speed: f32,
+    /// Outcome of the last export, shown under the controls.
+    pub export_note: Option<String>,
+}
+
+impl Default for ReplayViewer {
+    fn default() -> Self {
+        Self {
+            open: false,
+            playing: false,
+            time: 0.0,
+            speed: 1.0,
+            export_note: None,
+        }
+    }
+}
+
+impl ReplayViewer {
+    /// Opens the viewer at the start of the battle, playing.
+    pub fn open(&mut self) {
+        *self = Self {
+            open: true,
+            playing: true,
+            speed: self.speed,
+            ..default()
+        };
+    }
+
+    /// Moves playback on by `delta` real seconds, stopping at the end.
+    pub fn advance(&mut self, delta: f32, duration: f32) {
+        if !self.playing {
+            return;
+        }
+        self.time = (self.time + delta * self.speed).min(duration);
+        if self.time >= duration {
+            self.playing = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(time: f32, x: f32) -> ReplayFrame {
+        ReplayFrame {
+            time,
+            ships: vec![ReplayShip { id: 1, side: ReplaySide::Player, position: Vec2::new(x, -x), heading: 0.0 }],
+            shots: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_scrubbing_finds_the_last_sample() {
+        let mut trace = BattleTrace::default();
+        assert!(trace.frame_at(1.0).is_none());
+        for i in 0..5 {
+            trace.record(frame(i as f32 / REPLAY_SAMPLE_HZ, i as f32 * 10.0));
+        }
+        assert!(!trace.record(frame(REPLAY_MAX_SECONDS + 1.0, 0.0)));
+
+        assert!((trace.duration() - 0.4).abs() < 1e-5);
+        assert_eq!(trace.index_at(0.25), Some(2));
+        assert_eq!(trace.index_at(9.0), Some(4));
+        assert!(trace.index_at(-1.0).is_none());
+
+        let bounds = trace.bounds().unwrap();
+        assert_eq!(bounds.min, Vec2::new(0.0, -40.0));
+        assert_eq!(bounds.max, Vec2::new(40.0, 0.0));
+    }
+
+    #[test]
+    fn test_playback_stops_at_the_end() {
+        let mut viewer = ReplayViewer::default();
+        viewer.open();
+        viewer.speed = 4.0;
+        viewer.advance(1.0, 10.0);
+        assert_eq!(viewer.time, 4.0);
+        viewer.advance(2.0, 10.0);
+        assert_eq!(viewer.time, 10.0);
+        assert!(!viewer.playing);
+
+        let mut trace = BattleTrace::default();
+        trace.record_hit(ReplayHit { time: 3.0, position: Vec2::ZERO, on_player_side: false });
+        assert_eq!(trace.recent_hits(3.2, 0.5).count(), 1);
+        assert_eq!(trace.recent_hits(4.0, 0.5).count(), 0);
+    }
+}
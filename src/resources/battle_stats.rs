@@ -0,0 +1,177 @@
+//! Tally of the current battle, shown on the summary screen once it is over.
+
+use bevy::prelude::*;
+
+use crate::components::TargetComponent;
+
+/// Experience for sinking a ship, split by damage share (see `DamageLedger`).
+pub const XP_PER_SINK: u32 = 100;
+/// Experience for each ship that strikes its colours.
+pub const XP_PER_CAPTURE: u32 = 150;
+
+/// Damage per ship component.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ComponentDamage {
+    pub hull: f32,
+    pub sails: f32,
+    pub rudder: f32,
+}
+
+impl ComponentDamage {
+    pub fn add(&mut self, component: TargetComponent, damage: f32) {
+        match component {
+            TargetComponent::Hull => self.hull += damage,
+            TargetComponent::Sails => self.sails += damage,
+            TargetComponent::Rudder => self.rudder += damage,
+        }
+    }
+
+    pub fn total(&self) -> f32 {
+        self.hull + self.sails + self.rudder
+    }
+}
+
+/// How a battle ended.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BattleOutcome {
+    #[default]
+    Won,
+    /// Every enemy left afloat got away, and none was sunk or taken.
+    Escaped,
+    /// The player's flagship went down.
+    Lost,
+}
+
+/// What happened in the current battle, from the player's side.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BattleStats {
+    pub dealt: ComponentDamage,
+    pub taken: ComponentDamage,
+    /// Cannonballs the player's guns fired.
+    pub shots_fired: u32,
+    /// Of those, how many struck a ship.
+    pub shots_hit: u32,
+    /// Names of enemy ships sunk.
+    pub sunk: Vec<String>,
+    /// Names of enemy ships that surrendered.
+    pub captured: Vec<String>,
+    /// Names of the player's fleet ships sunk.
+    pub lost: Vec<String>,
+    /// Names of enemy ships that got clean away.
+    pub escaped: Vec<String>,
+    pub outcome: BattleOutcome,
+    pub xp: u32,
+    /// Player gold and hold units when battle was joined, to work out the loot.
+    pub gold_at_start: u32,
+    pub cargo_at_start: u32,
+    /// Whether the summary screen is showing.
+    pub open: bool,
+}
+
+impl BattleStats {
+    /// A fresh tally for a battle joined with this purse and hold.
+    pub fn begin(gold: u32, cargo_units: u32) -> Self {
+        Self {
+            gold_at_start: gold,
+            cargo_at_start: cargo_units,
+            ..default()
+        }
+    }
+
+    /// Records an enemy sunk, with the player's share of the damage it took.
+    pub fn record_sunk(&mut self, name: impl Into<String>, player_share: f32) {
+        self.sunk.push(name.into());
+        self.xp += (XP_PER_SINK as f32 * player_share.clamp(0.0, 1.0)).round() as u32;
+    }
+
+    pub fn record_captured(&mut self, name: impl Into<String>) {
+        self.captured.push(name.into());
+        self.xp += XP_PER_CAPTURE;
+    }
+
+    /// Records one of the player's fleet ships sunk.
+    pub fn record_lost(&mut self, name: impl Into<String>) {
+        self.lost.push(name.into());
+    }
+
+    pub fn record_escaped(&mut self, name: impl Into<String>) {
+        self.escaped.push(name.into());
+    }
+
+    /// Whether, with no enemy left on the water, they all got away rather
+    /// than being sunk.
+    pub fn enemy_got_away(&self) -> bool {
+        !self.escaped.is_empty() && self.sunk.is_empty()
+    }
+
+    /// Share of shots fired that hit, 0.0 to 1.0.
+    pub fn accuracy(&self) -> f32 {
+        match self.shots_fired {
+            0 => 0.0,
+            fired => (self.shots_hit as f32 / fired as f32).min(1.0),
+        }
+    }
+
+    /// One-line account of the battle for the journal.
+    pub fn journal_line(&self) -> String {
+        match self.outcome {
+            BattleOutcome::Won => format!(
+                "Battle won: {} sunk, {} captured, {:.0}% of shots on target, {} XP.",
+                self.sunk.len(),
+                self.captured.len(),
+                self.accuracy() * 100.0,
+                self.xp
+            ),
+            BattleOutcome::Escaped => format!(
+                "The enemy got away: {} escaped, {:.0}% of shots on target.",
+                self.escaped.len(),
+                self.accuracy() * 100.0
+            ),
+            BattleOutcome::Lost => format!(
+                "Battle lost: {} sunk before she went down, {:.0}% of shots on target.",
+                self.sunk.len(),
+                self.accuracy() * 100.0
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accuracy_and_damage_tally() {
+        let mut stats = BattleStats::begin(100, 5);
+        assert_eq!(stats.accuracy(), 0.0);
+        stats.shots_fired = 8;
+        stats.shots_hit = 2;
+        assert!((stats.accuracy() - 0.25).abs() < 1e-5);
+
+        stats.dealt.add(TargetComponent::Hull, 10.0);
+        stats.dealt.add(TargetComponent::Sails, 5.0);
+        assert_eq!(stats.dealt.total(), 15.0);
+        assert_eq!(stats.taken.total(), 0.0);
+    }
+
+    #[test]
+    fn test_xp_follows_damage_share() {
+        let mut stats = BattleStats::default();
+        stats.record_sunk("Black Gull", 1.0);
+        stats.record_sunk("Sea Wolf", 0.5);
+        stats.record_captured("Red Kite");
+        assert_eq!(stats.xp, XP_PER_SINK + XP_PER_SINK / 2 + XP_PER_CAPTURE);
+        assert_eq!(stats.sunk.len(), 2);
+    }
+
+    #[test]
+    fn test_enemy_got_away_only_if_none_sunk() {
+        let mut stats = BattleStats::default();
+        assert!(!stats.enemy_got_away());
+        stats.record_escaped("Black Gull");
+        assert!(stats.enemy_got_away());
+        // One of them sunk makes it a won battle
+        stats.record_sunk("Sea Wolf", 1.0);
+        assert!(!stats.enemy_got_away());
+    }
+}
@@ -0,0 +1,377 @@
+//! Boarding actions: grappling an enemy alongside and carrying her by storm.
+//!
+//! Two ships must lie within `BOARDING_RANGE` of each other, both slower than
+//! `BOARDING_MAX_SPEED`. The melee is then fought over up to `MELEE_ROUNDS`
+//! rounds, the captain calling a tactic for each within `ROUND_SECONDS`: a
+//! musket volley, a charge with cold steel, or a parley for her surrender.
+//! Each round won or lost moves the `Melee`'s momentum, the share of her deck
+//! the boarders hold; taking all of it carries her, losing all of it throws
+//! them back. Who wins a round is settled by crew strength: hands aboard, their
+//! morale (the ship's `Crew` spirits, or for a ship without one, less fight
+//! aboard a battered hull), the player's companions, and for a volley the
+//! muskets in the hold.
+
+use bevy::prelude::*;
+
+use crate::components::companion::CompanionRole;
+
+/// Greatest distance between hulls for a boarding (world units).
+pub const BOARDING_RANGE: f32 = 90.0;
+/// Both ships must be slower than this to grapple (world units per second).
+pub const BOARDING_MAX_SPEED: f32 = 40.0;
+/// Most rounds a melee on deck runs before it is settled on the deck held.
+pub const MELEE_ROUNDS: u32 = 4;
+/// Time to call each round before the boarders fight on unbidden (seconds).
+pub const ROUND_SECONDS: f32 = 3.0;
+/// Share of the deck the boarders hold when they first swarm over the rail.
+pub const MELEE_FOOTHOLD: f32 = 0.5;
+/// Hands one ton of weapons in the hold puts a musket in the hands of.
+pub const MUSKETS_PER_TON: f32 = 4.0;
+/// How readily a sound, unbroken crew hears a parley.
+const PARLEY_BASE: f32 = 0.2;
+/// Time before a repelled crew can try again (seconds).
+pub const BOARDING_COOLDOWN: f32 = 10.0;
+/// Share of the losing side's hands lost in the melee.
+pub const LOSER_CASUALTIES: f32 = 0.4;
+/// Share of the winning side's hands lost in the melee.
+pub const WINNER_CASUALTIES: f32 = 0.15;
+
+/// Whether two ships `distance` apart, moving at these speeds, can be grappled.
+pub fn can_board(distance: f32, attacker_speed: f32, defender_speed: f32) -> bool {
+    distance <= BOARDING_RANGE && attacker_speed < BOARDING_MAX_SPEED && defender_speed < BOARDING_MAX_SPEED
+}
+
+/// Fighting spirit of a crew, 0.5 aboard a wreck up to 1.0 aboard a sound ship.
+pub fn crew_morale(hull_ratio: f32) -> f32 {
+    0.5 + 0.5 * hull_ratio.clamp(0.0, 1.0)
+}
+
+/// A tactic the captain calls for a round of the melee.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardingTactic {
+    /// Muskets fired into the defenders from the rail.
+    Volley,
+    /// Cutlasses and boarding pikes across her deck.
+    Charge,
+    /// A call on her crew to strike before more blood is spilt.
+    Parley,
+}
+
+impl BoardingTactic {
+    pub const ALL: [BoardingTactic; 3] = [BoardingTactic::Volley, BoardingTactic::Charge, BoardingTactic::Parley];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BoardingTactic::Volley => "Volley",
+            BoardingTactic::Charge => "Charge",
+            BoardingTactic::Parley => "Parley",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            BoardingTactic::Volley => "Fire into them from the rail. Steady, and better the more muskets we carry.",
+            BoardingTactic::Charge => "Cold steel across her deck. Wins or loses the most ground.",
+            BoardingTactic::Parley => "Call on them to strike. Likelier the more of her deck we hold, and the worse she is battered.",
+        }
+    }
+
+    /// Share of the deck a round won or lost with this tactic moves.
+    pub fn swing(&self) -> f32 {
+        match self {
+            BoardingTactic::Volley => 0.2,
+            BoardingTactic::Charge => 0.3,
+            // A refused parley gives the defenders a breather to rally
+            BoardingTactic::Parley => 0.15,
+        }
+    }
+
+    /// How bloody a round of this tactic is, as a share of a straight melee's casualties.
+    pub fn ferocity(&self) -> f32 {
+        match self {
+            BoardingTactic::Volley => 0.6,
+            BoardingTactic::Charge => 1.0,
+            BoardingTactic::Parley => 0.2,
+        }
+    }
+}
+
+/// Extra boarding strength a companion brings to a tactic, as a share of the crew's.
+pub fn companion_boarding_bonus(role: CompanionRole, tactic: BoardingTactic) -> f32 {
+    match (role, tactic) {
+        // Swivel guns and grenadoes swept across the enemy's deck
+        (CompanionRole::Gunner, BoardingTactic::Volley) => 0.25,
+        (CompanionRole::Gunner, BoardingTactic::Charge) => 0.1,
+        // Leads the boarders and keeps them together, and knows what terms a crew will take
+        (CompanionRole::Quartermaster, BoardingTactic::Charge) => 0.15,
+        (CompanionRole::Quartermaster, BoardingTactic::Parley) => 0.15,
+        _ => 0.0,
+    }
+}
+
+/// Share of `hands` that can be armed with muskets from `weapons` tons in the hold.
+pub fn musket_share(weapons: u32, hands: u32) -> f32 {
+    if hands == 0 {
+        return 0.0;
+    }
+    (weapons as f32 * MUSKETS_PER_TON / hands as f32).min(1.0)
+}
+
+/// Fighting strength of a boarding party or the crew defending against one.
+pub fn boarding_strength(hands: u32, morale: f32, bonus: f32) -> f32 {
+    hands as f32 * morale * (1.0 + bonus)
+}
+
+/// Chance the boarders carry the enemy's deck, 0.0 to 1.0.
+pub fn boarding_odds(attacker: f32, defender: f32) -> f32 {
+    if attacker + defender <= 0.0 {
+        return 0.0;
+    }
+    attacker / (attacker + defender)
+}
+
+/// How a boarding action ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardingOutcome {
+    /// The enemy struck her colours; she is a prize.
+    Captured,
+    /// The boarders were thrown back.
+    Repelled,
+}
+
+/// Hands lost by (attackers, defenders) in a melee with this outcome, fought
+/// at `ferocity` (see `BoardingTactic::ferocity`).
+pub fn boarding_casualties(outcome: BoardingOutcome, attacker_hands: u32, defender_hands: u32, ferocity: f32) -> (u32, u32) {
+    let losses = |hands: u32, share: f32| (hands as f32 * share * ferocity).ceil() as u32;
+    match outcome {
+        BoardingOutcome::Captured => (losses(attacker_hands, WINNER_CASUALTIES), losses(defender_hands, LOSER_CASUALTIES)),
+        BoardingOutcome::Repelled => (losses(attacker_hands, LOSER_CASUALTIES), losses(defender_hands, WINNER_CASUALTIES)),
+    }
+}
+
+/// The two crews squared up for a melee.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeleeCrews {
+    pub attacker_hands: u32,
+    pub attacker_morale: f32,
+    pub defender_hands: u32,
+    pub defender_morale: f32,
+    /// Companions leading the boarders.
+    pub companions: Vec<CompanionRole>,
+    /// Share of the boarders carrying muskets (see `musket_share`).
+    pub muskets: f32,
+}
+
+impl MeleeCrews {
+    /// Chance the boarders win a round with `tactic` while holding `momentum` of her deck.
+    pub fn round_odds(&self, tactic: BoardingTactic, momentum: f32) -> f32 {
+        let bonus: f32 = self.companions.iter().map(|role| companion_boarding_bonus(*role, tactic)).sum();
+        match tactic {
+            BoardingTactic::Volley => {
+                // Half a volley without muskets: pistols and thrown shot
+                let fire = 0.5 + self.muskets;
+                boarding_odds(
+                    boarding_strength(self.attacker_hands, self.attacker_morale, bonus) * fire,
+                    boarding_strength(self.defender_hands, self.defender_morale, 0.0),
+                )
+            }
+            BoardingTactic::Charge => boarding_odds(
+                boarding_strength(self.attacker_hands, self.attacker_morale, bonus),
+                boarding_strength(self.defender_hands, self.defender_morale, 0.0),
+            ),
+            BoardingTactic::Parley => {
+                let broken = PARLEY_BASE + (1.0 - self.defender_morale) * 2.0 + bonus;
+                (broken * momentum).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+/// The fight for the enemy's deck, round by round.
+#[derive(Debug, Clone)]
+pub struct Melee {
+    pub crews: MeleeCrews,
+    /// Share of her deck the boarders hold, 0.0 to 1.0.
+    pub momentum: f32,
+    /// Rounds fought so far.
+    pub round: u32,
+    /// Time left to call this round.
+    pub timer: Timer,
+    /// Tactic called for this round, if the captain has called one.
+    pub called: Option<BoardingTactic>,
+    /// The last round's tactic, and whether the boarders won it.
+    pub last: Option<(BoardingTactic, bool)>,
+    /// Sum of the rounds' ferocity.
+    bloodshed: f32,
+}
+
+impl Melee {
+    pub fn new(crews: MeleeCrews) -> Self {
+        Self {
+            crews,
+            momentum: MELEE_FOOTHOLD,
+            round: 0,
+            timer: Timer::from_seconds(ROUND_SECONDS, TimerMode::Once),
+            called: None,
+            last: None,
+            bloodshed: 0.0,
+        }
+    }
+
+    /// Fights a round with `tactic`, the boarders winning it if `roll` falls
+    /// under their odds, and returns how the melee ended, if it has.
+    pub fn fight_round(&mut self, tactic: BoardingTactic, roll: f32) -> Option<BoardingOutcome> {
+        let won = roll < self.crews.round_odds(tactic, self.momentum);
+        self.round += 1;
+        self.bloodshed += tactic.ferocity();
+        self.last = Some((tactic, won));
+        self.called = None;
+        self.timer.reset();
+
+        if tactic == BoardingTactic::Parley && won {
+            // She strikes her colours
+            return Some(BoardingOutcome::Captured);
+        }
+        let swing = if won { tactic.swing() } else { -tactic.swing() };
+        self.momentum = (self.momentum + swing).clamp(0.0, 1.0);
+        if self.momentum >= 1.0 {
+            Some(BoardingOutcome::Captured)
+        } else if self.momentum <= 0.0 {
+            Some(BoardingOutcome::Repelled)
+        } else if self.round >= MELEE_ROUNDS {
+            // Settled on the deck held; a split deck is the defenders'
+            Some(if self.momentum > MELEE_FOOTHOLD { BoardingOutcome::Captured } else { BoardingOutcome::Repelled })
+        } else {
+            None
+        }
+    }
+
+    /// How bloody the melee has been on the whole (see `BoardingTactic::ferocity`).
+    pub fn ferocity(&self) -> f32 {
+        if self.round == 0 {
+            return 0.0;
+        }
+        self.bloodshed / self.round as f32
+    }
+}
+
+/// A melee in progress on the enemy's deck.
+#[derive(Debug, Clone)]
+pub struct BoardingAction {
+    /// Ship being boarded.
+    pub defender: Entity,
+    pub melee: Melee,
+}
+
+/// The player's boarding action, if any.
+#[derive(Resource, Debug, Default)]
+pub struct BoardingState {
+    pub action: Option<BoardingAction>,
+    /// Seconds until the crew can board again after being repelled.
+    pub cooldown: f32,
+}
+
+impl BoardingState {
+    pub fn is_boarding(&self) -> bool {
+        self.action.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_both_ships_must_be_close_and_slow() {
+        assert!(can_board(BOARDING_RANGE, 0.0, BOARDING_MAX_SPEED - 1.0));
+        assert!(!can_board(BOARDING_RANGE + 1.0, 0.0, 0.0));
+        assert!(!can_board(10.0, BOARDING_MAX_SPEED, 0.0));
+        assert!(!can_board(10.0, 0.0, BOARDING_MAX_SPEED));
+    }
+
+    #[test]
+    fn test_battered_crews_fight_worse() {
+        assert_eq!(crew_morale(1.0), 1.0);
+        assert_eq!(crew_morale(0.0), 0.5);
+        let fresh = boarding_strength(12, crew_morale(1.0), 0.0);
+        let battered = boarding_strength(12, crew_morale(0.2), 0.0);
+        assert!(fresh > battered);
+        let gunner = companion_boarding_bonus(CompanionRole::Gunner, BoardingTactic::Volley);
+        assert!(boarding_strength(12, 1.0, gunner) > fresh);
+    }
+
+    #[test]
+    fn test_odds_favour_the_stronger_side() {
+        assert_eq!(boarding_odds(10.0, 10.0), 0.5);
+        assert!(boarding_odds(20.0, 5.0) > 0.75);
+        assert_eq!(boarding_odds(0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_losers_bleed_more() {
+        assert_eq!(boarding_casualties(BoardingOutcome::Captured, 12, 10, 1.0), (2, 4));
+        assert_eq!(boarding_casualties(BoardingOutcome::Repelled, 12, 10, 1.0), (5, 2));
+        // A parleyed surrender costs little blood
+        assert_eq!(boarding_casualties(BoardingOutcome::Captured, 12, 10, 0.2), (1, 1));
+    }
+
+    fn crews(muskets: f32, defender_morale: f32) -> MeleeCrews {
+        MeleeCrews {
+            attacker_hands: 12,
+            attacker_morale: 1.0,
+            defender_hands: 12,
+            defender_morale,
+            companions: Vec::new(),
+            muskets,
+        }
+    }
+
+    #[test]
+    fn test_muskets_and_companions_favour_their_tactics() {
+        assert!(crews(1.0, 1.0).round_odds(BoardingTactic::Volley, 0.5) > crews(0.0, 1.0).round_odds(BoardingTactic::Volley, 0.5));
+        assert_eq!(musket_share(3, 12), 1.0);
+        assert_eq!(musket_share(0, 12), 0.0);
+        assert_eq!(musket_share(5, 0), 0.0);
+
+        let mut led = crews(0.0, 1.0);
+        led.companions.push(CompanionRole::Quartermaster);
+        assert!(led.round_odds(BoardingTactic::Charge, 0.5) > crews(0.0, 1.0).round_odds(BoardingTactic::Charge, 0.5));
+        assert!(led.round_odds(BoardingTactic::Parley, 0.5) > crews(0.0, 1.0).round_odds(BoardingTactic::Parley, 0.5));
+    }
+
+    #[test]
+    fn test_battered_crews_hear_a_parley_sooner() {
+        let sound = crews(0.0, 1.0);
+        let battered = crews(0.0, crew_morale(0.1));
+        assert!(battered.round_odds(BoardingTactic::Parley, 0.5) > sound.round_odds(BoardingTactic::Parley, 0.5));
+        assert!(sound.round_odds(BoardingTactic::Parley, 0.9) > sound.round_odds(BoardingTactic::Parley, 0.5));
+
+        let mut melee = Melee::new(battered);
+        assert_eq!(melee.fight_round(BoardingTactic::Parley, 0.0), Some(BoardingOutcome::Captured));
+        assert_eq!(melee.ferocity(), BoardingTactic::Parley.ferocity());
+    }
+
+    #[test]
+    fn test_melee_is_won_or_lost_on_the_deck_held() {
+        // Two charges won carry her deck
+        let mut melee = Melee::new(crews(0.0, 1.0));
+        assert_eq!(melee.fight_round(BoardingTactic::Charge, 0.0), None);
+        assert_eq!(melee.fight_round(BoardingTactic::Charge, 0.0), Some(BoardingOutcome::Captured));
+
+        // A refused parley loses ground, and two lost charges lose the rest
+        let mut melee = Melee::new(crews(0.0, 1.0));
+        assert_eq!(melee.fight_round(BoardingTactic::Parley, 1.0), None);
+        assert!(melee.momentum < MELEE_FOOTHOLD);
+        assert_eq!(melee.fight_round(BoardingTactic::Charge, 1.0), None);
+        assert_eq!(melee.fight_round(BoardingTactic::Charge, 1.0), Some(BoardingOutcome::Repelled));
+
+        // Out of rounds, the boarders carry her if they hold the greater part of her deck
+        let mut melee = Melee::new(crews(0.0, 1.0));
+        for roll in [0.0, 1.0, 1.0] {
+            assert_eq!(melee.fight_round(BoardingTactic::Volley, roll), None);
+        }
+        assert_eq!(melee.round, MELEE_ROUNDS - 1);
+        assert_eq!(melee.fight_round(BoardingTactic::Charge, 0.0), Some(BoardingOutcome::Captured));
+        assert!(melee.ferocity() < 1.0);
+    }
+}
@@ -0,0 +1,153 @@
+//! Bounties owed to the player for sinking outlaws.
+//!
+//! Kills are credited as they happen in battle and paid out the next time the
+//! player docks at a port that honours them.
+
+use bevy::prelude::*;
+
+use crate::components::{FactionId, ShipType};
+
+/// Gold a lawful port pays for each pirate ship sunk.
+pub const PIRATE_BOUNTY: u32 = 150;
+
+/// Bounty owed for sinking a ship of `victim`, if anyone pays for it.
+pub fn bounty_for(victim: FactionId) -> Option<u32> {
+    match victim {
+        FactionId::Pirates => Some(PIRATE_BOUNTY),
+        _ => None,
+    }
+}
+
+/// A kill waiting to be paid for.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct BountyClaim {
+    /// Name of the ship sunk.
+    pub ship_name: String,
+    pub victim: FactionId,
+    pub amount: u32,
+    /// Day of the kill.
+    pub day: u32,
+}
+
+impl BountyClaim {
+    /// Any lawful port pays, except the victim's own.
+    pub fn payable_at(&self, port: FactionId) -> bool {
+        port != FactionId::Pirates && port != self.victim
+    }
+}
+
+/// A bounty hunter a nation has sent after the player, still at sea.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct HunterAtSea {
+    pub faction: FactionId,
+    pub ship_type: ShipType,
+    /// Where she was when the player last left the High Seas.
+    pub position: Vec2,
+}
+
+/// Bounty claims owed to the player, and those paid at the current port,
+/// and the hunters sent after the player in turn.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PendingBounties {
+    pub claims: Vec<BountyClaim>,
+    /// Claims settled on docking, listed in the port until departure.
+    pub paid: Vec<BountyClaim>,
+    /// Hunters paid for and sent out. They outlast the High Seas scene and
+    /// are put back to sea on returning to it, until one closes to fight.
+    #[reflect(default)]
+    pub hunters: Vec<HunterAtSea>,
+}
+
+impl PendingBounties {
+    /// Credits a kill if its flag carries a bounty. Returns the amount owed.
+    pub fn credit(&mut self, ship_name: impl Into<String>, victim: FactionId, day: u32) -> Option<u32> {
+        let amount = bounty_for(victim)?;
+        self.claims.push(BountyClaim {
+            ship_name: ship_name.into(),
+            victim,
+            amount,
+            day,
+        });
+        Some(amount)
+    }
+
+    /// Settles every claim `port` honours, moving them to `paid`. Returns the total.
+    pub fn settle(&mut self, port: FactionId) -> u32 {
+        let (payable, unpaid) = std::mem::take(&mut self.claims)
+            .into_iter()
+            .partition::<Vec<_>, _>(|claim| claim.payable_at(port));
+        self.claims = unpaid;
+        let total = payable.iter().map(|claim| claim.amount).sum();
+        self.paid = payable;
+        total
+    }
+
+    /// Gold owed across all outstanding claims.
+    pub fn outstanding(&self) -> u32 {
+        self.claims.iter().map(|claim| claim.amount).sum()
+    }
+
+    /// Hunters `faction` has at sea.
+    pub fn hunters_from(&self, faction: FactionId) -> usize {
+        self.hunters.iter().filter(|hunter| hunter.faction == faction).count()
+    }
+
+    pub fn clear(&mut self) {
+        self.claims.clear();
+        self.paid.clear();
+        self.hunters.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_outlaws_carry_a_bounty() {
+        let mut bounties = PendingBounties::default();
+        assert_eq!(bounties.credit("Black Gull", FactionId::Pirates, 3), Some(PIRATE_BOUNTY));
+        assert_eq!(bounties.credit("Merchantman", FactionId::NationA, 3), None);
+        assert_eq!(bounties.claims.len(), 1);
+        assert_eq!(bounties.outstanding(), PIRATE_BOUNTY);
+    }
+
+    #[test]
+    fn test_settle_pays_claims_the_port_honours() {
+        let mut bounties = PendingBounties::default();
+        bounties.credit("Black Gull", FactionId::Pirates, 3);
+        bounties.claims.push(BountyClaim {
+            ship_name: "Royal Oak".to_string(),
+            victim: FactionId::NationB,
+            amount: 200,
+            day: 4,
+        });
+
+        assert_eq!(bounties.settle(FactionId::Pirates), 0);
+        assert_eq!(bounties.settle(FactionId::NationB), PIRATE_BOUNTY);
+        assert_eq!(bounties.paid.len(), 1);
+        assert_eq!(bounties.claims.len(), 1);
+
+        // A nation never pays for its own ships
+        assert_eq!(bounties.settle(FactionId::NationA), 200);
+        assert!(bounties.claims.is_empty());
+    }
+
+    #[test]
+    fn test_hunters_are_counted_by_nation() {
+        let mut bounties = PendingBounties::default();
+        for faction in [FactionId::NationA, FactionId::NationA, FactionId::NationB] {
+            bounties.hunters.push(HunterAtSea {
+                faction,
+                ship_type: ShipType::Schooner,
+                position: Vec2::ZERO,
+            });
+        }
+
+        assert_eq!(bounties.hunters_from(FactionId::NationA), 2);
+        assert_eq!(bounties.hunters_from(FactionId::NationC), 0);
+        bounties.clear();
+        assert_eq!(bounties.hunters_from(FactionId::NationA), 0);
+    }
+}
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::resources::MapSizePreset;
+
 /// Command-line arguments parsed at startup.
 /// Used for test automation and save-based feature verification.
 #[derive(Resource, Debug, Default)]
@@ -12,6 +14,10 @@ pub struct CliArgs {
     /// Usage: `cargo run -- --save-as test_feature`
     /// Then press F5 in-game to save to "test_feature" instead of "quicksave".
     pub save_as: Option<String>,
+
+    /// Map size for newly generated worlds. Useful for benchmarking large maps.
+    /// Usage: `cargo run -- --map-size large` (or `small`, `medium`, `256`, `512`, `1024`)
+    pub map_size: Option<MapSizePreset>,
 }
 
 impl CliArgs {
@@ -19,6 +25,7 @@ impl CliArgs {
     /// Supports:
     /// - `--load <save_name>`: Load specified save on startup
     /// - `--save-as <save_name>`: Override F5 quicksave name
+    /// - `--map-size <size>`: Map size preset for world generation
     pub fn parse() -> Self {
         let args: Vec<String> = std::env::args().collect();
         let mut cli = CliArgs::default();
@@ -46,6 +53,23 @@ impl CliArgs {
                         i += 1;
                     }
                 }
+                "--map-size" => {
+                    match args.get(i + 1).map(|s| (s, MapSizePreset::parse(s))) {
+                        Some((_, Some(preset))) => {
+                            cli.map_size = Some(preset);
+                            info!("CLI: Generating {} maps", preset.name());
+                            i += 2;
+                        }
+                        Some((value, None)) => {
+                            warn!("CLI: Unknown map size '{}' (expected small, medium, large, 256, 512 or 1024)", value);
+                            i += 2;
+                        }
+                        None => {
+                            warn!("CLI: --map-size requires a size argument");
+                            i += 1;
+                        }
+                    }
+                }
                 arg => {
                     if arg.starts_with('-') {
                         warn!("CLI: Unknown argument '{}'", arg);
@@ -0,0 +1,170 @@
+//! Data-driven codex of game concepts.
+//!
+//! Entries are read from `assets/data/codex.ron`, a content table (see
+//! `resources::content`). Which entries the player has unlocked is stored in
+//! `MetaProfile` so the codex persists across runs.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::cargo::GoodType;
+use crate::components::ship::{FactionId, ShipClass};
+use crate::resources::ContentTable;
+
+/// Top-level grouping for codex entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CodexCategory {
+    Ships,
+    Goods,
+    Factions,
+    Mechanics,
+    Lore,
+}
+
+impl CodexCategory {
+    /// Returns all categories in display order.
+    pub fn all() -> &'static [CodexCategory] {
+        &[
+            CodexCategory::Ships,
+            CodexCategory::Goods,
+            CodexCategory::Factions,
+            CodexCategory::Mechanics,
+            CodexCategory::Lore,
+        ]
+    }
+
+    /// Display name for the category tab.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CodexCategory::Ships => "Ships",
+            CodexCategory::Goods => "Goods",
+            CodexCategory::Factions => "Factions",
+            CodexCategory::Mechanics => "Mechanics",
+            CodexCategory::Lore => "Lore",
+        }
+    }
+}
+
+/// A single codex page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodexEntry {
+    /// Stable identifier, e.g. `good.rum` or `mechanic.wind`.
+    pub id: String,
+    /// Category the entry is listed under.
+    pub category: CodexCategory,
+    /// Display title.
+    pub title: String,
+    /// Body text.
+    pub body: String,
+    /// Identifiers of related entries shown as links.
+    #[serde(default)]
+    pub see_also: Vec<String>,
+}
+
+/// All codex entries available in the game. Without its file the codex is empty.
+#[derive(Asset, TypePath, Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodexDatabase {
+    pub entries: Vec<CodexEntry>,
+}
+
+impl ContentTable for CodexDatabase {
+    const ASSET_PATH: &'static str = "data/codex.ron";
+    const NAME: &'static str = "codex";
+}
+
+impl CodexDatabase {
+    /// Looks up an entry by identifier.
+    pub fn get(&self, id: &str) -> Option<&CodexEntry> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+
+    /// Iterates over entries in a category, in file order.
+    pub fn by_category(&self, category: CodexCategory) -> impl Iterator<Item = &CodexEntry> {
+        self.entries.iter().filter(move |e| e.category == category)
+    }
+
+    /// Returns `(entry, missing_link)` pairs for every `see_also` that does not resolve.
+    pub fn broken_links(&self) -> Vec<(String, String)> {
+        let mut broken = Vec::new();
+        for entry in &self.entries {
+            for link in &entry.see_also {
+                if self.get(link).is_none() {
+                    broken.push((entry.id.clone(), link.clone()));
+                }
+            }
+        }
+        broken
+    }
+}
+
+/// Codex identifier for a trade good.
+pub fn good_codex_id(good: GoodType) -> String {
+    format!("good.{}", format!("{:?}", good).to_lowercase())
+}
+
+/// Codex identifier for a ship class, by its id in `ShipDefinitions`.
+pub fn ship_codex_id(class: &ShipClass) -> String {
+    format!("ship.{}", class.0)
+}
+
+/// Codex identifier for a faction.
+pub fn faction_codex_id(faction: FactionId) -> String {
+    match faction {
+        FactionId::Pirates => "faction.pirates",
+        FactionId::NationA => "faction.nation_a",
+        FactionId::NationB => "faction.nation_b",
+        FactionId::NationC => "faction.nation_c",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ShipDefinitions;
+
+    fn shipped_codex() -> CodexDatabase {
+        CodexDatabase::from_ron(include_str!("../../assets/data/codex.ron"))
+            .expect("shipped codex should parse")
+    }
+
+    #[test]
+    fn test_shipped_codex_links_resolve() {
+        let db = shipped_codex();
+        assert!(db.broken_links().is_empty(), "broken links: {:?}", db.broken_links());
+    }
+
+    #[test]
+    fn test_shipped_codex_covers_goods_ships_and_factions() {
+        let db = shipped_codex();
+        for good in [
+            GoodType::Rum,
+            GoodType::Sugar,
+            GoodType::Spices,
+            GoodType::Timber,
+            GoodType::Cloth,
+            GoodType::Weapons,
+            GoodType::Fish,
+            GoodType::Fruit,
+        ] {
+            assert!(db.get(&good_codex_id(good)).is_some(), "missing entry for {:?}", good);
+        }
+        for ship in &ShipDefinitions::default().ships {
+            let class = ShipClass(ship.id.clone());
+            assert!(db.get(&ship_codex_id(&class)).is_some(), "missing entry for {:?}", class);
+        }
+        for faction in [FactionId::Pirates, FactionId::NationA, FactionId::NationB, FactionId::NationC] {
+            assert!(db.get(&faction_codex_id(faction)).is_some(), "missing entry for {:?}", faction);
+        }
+    }
+
+    #[test]
+    fn test_see_also_defaults_to_empty() {
+        let db = CodexDatabase::from_ron(
+            r#"(entries: [(id: "a", category: Mechanics, title: "A", body: "...")])"#,
+        )
+        .unwrap();
+        assert!(db.get("a").unwrap().see_also.is_empty());
+    }
+}
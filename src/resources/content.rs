@@ -0,0 +1,111 @@
+//! Content tables: designer-editable data read from `assets/data/*.ron`.
+//!
+//! Each table (`GoodsTable`, `ArchetypeRegistry`, `ContractRewards`,
+//! `ShipDefinitions`, `BalanceConfig`, `EncounterTemplates`, `SeaEventDeck`, `CodexDatabase`) is both a Bevy asset, loaded by its own typed
+//! `RonContentLoader`, and a resource systems read. `ContentPlugin` reads
+//! every table from disk before the first frame so nothing ever sees a
+//! half-loaded registry, then loads it again through the `AssetServer`, which
+//! watches the file in dev builds: a saved change replaces the resource, and a
+//! file that no longer parses is reported while the table in play is kept. Every table falls back to the built-in
+//! values it replaced, and fields left out of a file keep them.
+
+use std::marker::PhantomData;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+
+/// A table of game content loaded from a RON file under `assets/`.
+pub trait ContentTable: Asset + Resource + Clone + PartialEq + Default + DeserializeOwned {
+    /// Path of its file, relative to `assets/`.
+    const ASSET_PATH: &'static str;
+    /// What the table holds, for the log.
+    const NAME: &'static str;
+
+    /// Parses a table file.
+    fn from_ron(ron: &str) -> Result<Self, String> {
+        ron::from_str(ron).map_err(|e| format!("Failed to parse {} data: {}", Self::NAME, e))
+    }
+
+    /// Reads the table from its file, returning the built-in table on failure.
+    fn load_from_file() -> Self {
+        let path = format!("assets/{}", Self::ASSET_PATH);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => match Self::from_ron(&contents) {
+                Ok(table) => {
+                    info!("Loaded {} data from {}", Self::NAME, path);
+                    table
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                error!("Failed to read {} data from {}: {}", Self::NAME, path, e);
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Why a content file could not be loaded as an asset.
+#[derive(Debug)]
+pub enum ContentLoadError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for ContentLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentLoadError::Io(e) => write!(f, "could not read content file: {}", e),
+            ContentLoadError::Parse(e) => write!(f, "could not parse content file: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ContentLoadError {}
+
+impl From<std::io::Error> for ContentLoadError {
+    fn from(e: std::io::Error) -> Self {
+        ContentLoadError::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for ContentLoadError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        ContentLoadError::Parse(e)
+    }
+}
+
+/// Loads a `ContentTable` from RON. Every table's files end in `.ron`, so the
+/// `AssetServer` picks the loader by the asset type asked for.
+pub struct RonContentLoader<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for RonContentLoader<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: ContentTable> AssetLoader for RonContentLoader<T> {
+    type Asset = T;
+    type Settings = ();
+    type Error = ContentLoadError;
+
+    async fn load(&self, reader: &mut dyn Reader, _settings: &(), _load_context: &mut LoadContext<'_>) -> Result<T, ContentLoadError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+/// Keeps a content table's asset loaded, so changes to its file are picked up.
+#[derive(Resource)]
+pub struct ContentHandle<T: ContentTable>(pub Handle<T>);
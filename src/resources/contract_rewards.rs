@@ -0,0 +1,88 @@
+//! What port boards post and pay, read from `assets/data/contracts.ron`.
+//!
+//! Covers transport contracts (how many a board posts, how much cargo each
+//! carries and what it pays per unit), patrol contracts (paid per smuggler
+//! asked for) and letters of marque (paid per prize). Fields left out of the
+//! file keep the built-in values.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{ContentTable, PATROL_REWARD_PER_SMUGGLER, PRIZE_MONEY};
+
+/// An inclusive range a value is rolled from.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Span {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl Span {
+    pub const fn new(min: u32, max: u32) -> Self {
+        Self { min, max }
+    }
+
+    /// A value rolled evenly from the span; a span given backwards is turned round.
+    pub fn roll(&self, rng: &mut impl Rng) -> u32 {
+        rng.gen_range(self.min.min(self.max)..=self.min.max(self.max))
+    }
+}
+
+/// Rewards and sizes of the contracts port boards post.
+#[derive(Asset, TypePath, Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContractRewards {
+    /// Transport contracts a board posts when it turns over.
+    pub transport_contracts: Span,
+    /// Units of cargo a transport contract carries.
+    pub transport_quantity: Span,
+    /// Gold a transport contract pays per unit delivered.
+    pub transport_gold_per_unit: Span,
+    /// Gold a patrol pays per smuggler it asks for.
+    pub patrol_per_smuggler: u32,
+    /// Gold a letter of marque pays per rival ship sunk.
+    pub prize_money: u32,
+}
+
+impl Default for ContractRewards {
+    fn default() -> Self {
+        Self {
+            transport_contracts: Span::new(2, 4),
+            transport_quantity: Span::new(5, 20),
+            transport_gold_per_unit: Span::new(15, 30),
+            patrol_per_smuggler: PATROL_REWARD_PER_SMUGGLER,
+            prize_money: PRIZE_MONEY,
+        }
+    }
+}
+
+impl ContentTable for ContractRewards {
+    const ASSET_PATH: &'static str = "data/contracts.ron";
+    const NAME: &'static str = "contract";
+}
+
+impl ContractRewards {
+    /// Gold offered for a patrol that must intercept `required` smugglers.
+    pub fn patrol_reward(&self, required: u32) -> u32 {
+        required * self.patrol_per_smuggler
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_rewards_match_builtin() {
+        let shipped = ContractRewards::from_ron(include_str!("../../assets/data/contracts.ron")).unwrap();
+        assert_eq!(shipped, ContractRewards::default());
+        assert_eq!(shipped.patrol_reward(3), 3 * PATROL_REWARD_PER_SMUGGLER);
+
+        let mut rng = rand::thread_rng();
+        let backwards = Span::new(9, 3);
+        for _ in 0..20 {
+            assert!((3..=9).contains(&backwards.roll(&mut rng)));
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! The player's crew, kept between scenes.
+//!
+//! Ships are respawned whenever the player moves between the High Seas, a
+//! battle and port, so the player's `Crew` lives here and is put aboard
+//! whichever flagship is spawned (see `plugins::crew`).
+
+use bevy::prelude::*;
+
+use crate::components::{Crew, ShipType};
+
+/// The player's crew; `None` until the flagship is first crewed.
+#[derive(Resource, Debug, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct PlayerCrew {
+    pub crew: Option<Crew>,
+}
+
+impl PlayerCrew {
+    /// The crew, signing on a fresh one for a `ship_type` if there is none yet.
+    pub fn get_or_muster(&mut self, ship_type: ShipType) -> Crew {
+        *self.crew.get_or_insert_with(|| Crew::for_ship(ship_type))
+    }
+}
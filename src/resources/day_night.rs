@@ -0,0 +1,88 @@
+//! Day and night on the `WorldClock`.
+//!
+//! The light follows the clock round: darkest at midnight, brightest at noon.
+//! After dark the world map and the battle are shaded, the lookout sees less
+//! far, hostile ships can steal up closer before they are seen, the night
+//! music rises, and once it is darker than `NIGHT_DARKNESS` port markets
+//! shut and ghost ships and cursed fog may come abroad.
+
+use crate::resources::{WorldClock, TICKS_PER_HOUR};
+
+/// Share of a ship's vision radius left at midnight.
+pub const NIGHT_VISION_FACTOR: f32 = 0.6;
+/// How much further off hostile ships fall upon the player at midnight.
+pub const NIGHT_ENCOUNTER_FACTOR: f32 = 1.4;
+/// Opacity of the night shade over the world at midnight (0-255).
+pub const NIGHT_SHADE_ALPHA: f32 = 120.0;
+/// Darkness from which it is night: from about half past seven in the
+/// evening until half past four in the morning.
+pub const NIGHT_DARKNESS: f32 = 0.7;
+
+/// How dark it is at a time of day, 0.0 at noon to 1.0 at midnight.
+pub fn night_darkness(hour: u32, tick: u32) -> f32 {
+    let hour = hour as f32 + tick as f32 / TICKS_PER_HOUR as f32;
+    0.5 + 0.5 * (hour / 24.0 * std::f32::consts::TAU).cos()
+}
+
+/// How dark it is by the clock.
+pub fn clock_darkness(clock: &WorldClock) -> f32 {
+    night_darkness(clock.hour, clock.tick)
+}
+
+/// Whether `darkness` is deep enough to count as night.
+pub fn is_dark(darkness: f32) -> bool {
+    darkness >= NIGHT_DARKNESS
+}
+
+/// Multiplier on vision radius in `darkness`.
+pub fn night_vision_factor(darkness: f32) -> f32 {
+    1.0 - (1.0 - NIGHT_VISION_FACTOR) * darkness.clamp(0.0, 1.0)
+}
+
+/// Multiplier on the encounter radius in `darkness`.
+pub fn night_encounter_factor(darkness: f32) -> f32 {
+    1.0 + (NIGHT_ENCOUNTER_FACTOR - 1.0) * darkness.clamp(0.0, 1.0)
+}
+
+/// Whether port markets trade in `darkness`.
+pub fn markets_open(darkness: f32) -> bool {
+    !is_dark(darkness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::MORNING_HOUR;
+
+    #[test]
+    fn test_darkest_at_midnight() {
+        assert!((night_darkness(0, 0) - 1.0).abs() < 0.001);
+        assert!(night_darkness(12, 0) < 0.001);
+        assert!(night_darkness(18, 0) < night_darkness(22, 0));
+    }
+
+    #[test]
+    fn test_night_shortens_sight_and_lengthens_ambush() {
+        assert_eq!(night_vision_factor(0.0), 1.0);
+        assert!((night_vision_factor(1.0) - NIGHT_VISION_FACTOR).abs() < 0.001);
+        assert_eq!(night_encounter_factor(0.0), 1.0);
+        assert!(night_encounter_factor(clock_darkness(&WorldClock { day: 1, hour: 23, tick: 0 })) > 1.3);
+    }
+
+    #[test]
+    fn test_night_falls_after_dusk() {
+        assert!(!is_dark(night_darkness(19, 0)));
+        assert!(is_dark(night_darkness(20, 0)));
+        assert!(is_dark(night_darkness(0, 0)));
+        assert!(is_dark(night_darkness(4, 0)));
+        assert!(!is_dark(night_darkness(5, 0)));
+    }
+
+    #[test]
+    fn test_markets_shut_overnight() {
+        assert!(markets_open(night_darkness(12, 0)));
+        assert!(markets_open(night_darkness(MORNING_HOUR, 0)));
+        assert!(!markets_open(night_darkness(20, 0)));
+        assert!(!markets_open(night_darkness(0, 0)));
+    }
+}
@@ -0,0 +1,166 @@
+//! What the player keeps and loses when their flagship is sunk.
+//!
+//! Part of the gold and cargo aboard goes down with the ship and the rest is
+//! left in the legacy wreck. Companions survive and turn up in taverns on later
+//! runs. Fleet ships scatter; some can be reclaimed at ports on later runs.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::companion::CompanionRole;
+use crate::resources::ShipData;
+
+/// Loss rules applied on defeat, set by difficulty.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DefeatRules {
+    /// Fraction of gold aboard lost with the ship.
+    pub gold_loss: f32,
+    /// Fraction of each cargo stack lost with the ship.
+    pub cargo_loss: f32,
+    /// Chance for each fleet ship to survive the scattering and be reclaimable.
+    pub ship_recovery_chance: f32,
+}
+
+impl DefeatRules {
+    /// Splits gold into (left in the wreck, lost).
+    pub fn split_gold(&self, gold: u32) -> (u32, u32) {
+        let lost = (gold as f32 * self.gold_loss).round() as u32;
+        (gold - lost.min(gold), lost.min(gold))
+    }
+
+    /// Cargo left in the wreck; empty stacks are dropped.
+    pub fn salvage_cargo(&self, cargo: &[(String, u32)]) -> Vec<(String, u32)> {
+        cargo
+            .iter()
+            .map(|(good, quantity)| (good.clone(), (*quantity as f32 * (1.0 - self.cargo_loss)).floor() as u32))
+            .filter(|(_, quantity)| *quantity > 0)
+            .collect()
+    }
+
+    /// Player-facing summary of the rules, one line each.
+    pub fn describe(&self) -> Vec<String> {
+        vec![
+            format!("{:.0}% of the gold aboard is lost; the rest lies in your wreck.", self.gold_loss * 100.0),
+            format!("{:.0}% of the cargo is lost; the rest lies in your wreck.", self.cargo_loss * 100.0),
+            "Companions survive and may be found in taverns.".to_string(),
+            format!(
+                "Fleet ships scatter; each has a {:.0}% chance to be reclaimed at a port.",
+                self.ship_recovery_chance * 100.0
+            ),
+        ]
+    }
+}
+
+/// A companion from a previous run waiting to be rehired in a tavern.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VeteranCompanion {
+    pub name: String,
+    pub role: CompanionRole,
+}
+
+/// A fleet ship that survived its flagship's sinking and can be reclaimed at a port.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScatteredShip {
+    pub name: String,
+    pub sprite_path: String,
+    pub hull_health: f32,
+    pub max_hull_health: f32,
+}
+
+impl ScatteredShip {
+    /// Gold asked by the harbor master to hand the ship back.
+    pub fn reclaim_cost(&self) -> u32 {
+        100 + (self.max_hull_health * 2.0) as u32
+    }
+
+    /// Fleet entry for a reclaimed ship. Its cargo was lost at sea.
+    pub fn to_ship_data(&self) -> ShipData {
+        ShipData {
+            sprite_path: self.sprite_path.clone(),
+            hull_health: self.hull_health,
+            max_hull_health: self.max_hull_health,
+            cargo: None,
+            name: self.name.clone(),
+            upgrades: Vec::new(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&ShipData> for ScatteredShip {
+    fn from(ship: &ShipData) -> Self {
+        Self {
+            name: ship.name.clone(),
+            sprite_path: ship.sprite_path.clone(),
+            hull_health: ship.hull_health,
+            max_hull_health: ship.max_hull_health,
+        }
+    }
+}
+
+/// Splits fleet ships into (reclaimable later, lost for good).
+pub fn scatter_fleet(ships: &[ShipData], rules: &DefeatRules, rng: &mut impl Rng) -> (Vec<ScatteredShip>, Vec<String>) {
+    let mut recoverable = Vec::new();
+    let mut lost = Vec::new();
+    for ship in ships {
+        if rng.gen::<f32>() < rules.ship_recovery_chance {
+            recoverable.push(ScatteredShip::from(ship));
+        } else {
+            lost.push(ship.name.clone());
+        }
+    }
+    (recoverable, lost)
+}
+
+/// What happened to the player's possessions on their last defeat, for the game over screen.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct DefeatOutcome {
+    pub ship_name: String,
+    pub rules: Vec<String>,
+    pub gold_lost: u32,
+    pub wreck_gold: u32,
+    pub wreck_cargo: Vec<(String, u32)>,
+    pub companions_to_taverns: Vec<String>,
+    pub ships_recoverable: Vec<String>,
+    pub ships_lost: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn rules(loss: f32, recovery: f32) -> DefeatRules {
+        DefeatRules {
+            gold_loss: loss,
+            cargo_loss: loss,
+            ship_recovery_chance: recovery,
+        }
+    }
+
+    #[test]
+    fn test_split_gold() {
+        assert_eq!(rules(0.5, 0.5).split_gold(101), (50, 51));
+        assert_eq!(rules(0.0, 0.5).split_gold(80), (80, 0));
+        assert_eq!(rules(1.0, 0.5).split_gold(80), (0, 80));
+    }
+
+    #[test]
+    fn test_salvage_cargo_drops_empty_stacks() {
+        let cargo = vec![("Rum".to_string(), 10), ("Spices".to_string(), 1)];
+        assert_eq!(rules(0.5, 0.5).salvage_cargo(&cargo), vec![("Rum".to_string(), 5)]);
+    }
+
+    #[test]
+    fn test_scatter_fleet_extremes() {
+        let ships = vec![ShipData::default(), ShipData::default()];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+
+        let (recoverable, lost) = scatter_fleet(&ships, &rules(0.5, 1.0), &mut rng);
+        assert_eq!((recoverable.len(), lost.len()), (2, 0));
+
+        let (recoverable, lost) = scatter_fleet(&ships, &rules(0.5, 0.0), &mut rng);
+        assert_eq!((recoverable.len(), lost.len()), (0, 2));
+    }
+}
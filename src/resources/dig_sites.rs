@@ -0,0 +1,106 @@
+//! Buried treasure charted from treasure maps.
+//!
+//! Each treasure location the player comes by, bought in a tavern or fished
+//! from a bottle, is charted as a `DigSite` at the nearest water a boat can
+//! be put ashore from. Hove to within `DIG_RADIUS` of it, the crew can dig up
+//! its gold, though some are watched by pirates content to let someone else
+//! do the digging.
+
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use super::MapData;
+
+/// How close the player's ship must be to a dig site to put a boat ashore (world units).
+pub const DIG_RADIUS: f32 = 96.0;
+/// How far from the marked spot to look for water to land from (tiles).
+const LANDING_SEARCH_RADIUS: i32 = 8;
+/// Chance that pirates lie in wait at a dig site.
+const AMBUSH_CHANCE: f32 = 0.3;
+/// Gold buried at a site.
+const TREASURE_GOLD: RangeInclusive<u32> = 150..=450;
+
+/// A spot marked on a treasure map.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct DigSite {
+    pub id: u32,
+    /// Where the boat goes ashore: the water nearest the marked spot.
+    pub tile: IVec2,
+    pub gold: u32,
+    /// Whether pirates lie in wait for whoever digs it up.
+    pub guarded: bool,
+}
+
+/// Treasure charted this run and not yet dug up.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct DigSites {
+    pub sites: Vec<DigSite>,
+    next_id: u32,
+}
+
+impl DigSites {
+    /// Charts the treasure marked at `marked`, landing from the nearest water.
+    /// Returns the site's id, or `None` if that treasure is already charted.
+    pub fn chart(&mut self, map: &MapData, marked: IVec2, rng: &mut impl Rng) -> Option<u32> {
+        let tile = map.nearest_navigable(marked, LANDING_SEARCH_RADIUS).unwrap_or(marked);
+        if self.sites.iter().any(|site| site.tile == tile) {
+            return None;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.sites.push(DigSite {
+            id,
+            tile,
+            gold: rng.gen_range(TREASURE_GOLD),
+            guarded: rng.gen::<f32>() < AMBUSH_CHANCE,
+        });
+        Some(id)
+    }
+
+    pub fn get(&self, id: u32) -> Option<&DigSite> {
+        self.sites.iter().find(|site| site.id == id)
+    }
+
+    /// Digs up a site, removing it from the chart.
+    pub fn dig(&mut self, id: u32) -> Option<DigSite> {
+        let index = self.sites.iter().position(|site| site.id == id)?;
+        Some(self.sites.remove(index))
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::TileType;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_chart_lands_from_water_once() {
+        let mut map = MapData::new(16, 16);
+        for x in 0..8 {
+            for y in 0..16 {
+                map.set_type(x, y, TileType::Land);
+            }
+        }
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut sites = DigSites::default();
+
+        let id = sites.chart(&map, IVec2::new(5, 4), &mut rng).unwrap();
+        let site = sites.get(id).unwrap();
+        assert_eq!(site.tile, IVec2::new(8, 4));
+        assert!(TREASURE_GOLD.contains(&site.gold));
+        // The same spot from a second map is not charted twice
+        assert!(sites.chart(&map, IVec2::new(6, 4), &mut rng).is_none());
+
+        assert_eq!(sites.dig(id).map(|site| site.id), Some(id));
+        assert!(sites.dig(id).is_none());
+        assert!(sites.sites.is_empty());
+    }
+}
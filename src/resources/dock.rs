@@ -0,0 +1,119 @@
+//! Dock services: shipyard work that takes world time.
+//!
+//! Repairs and refits ordered at port join a single queue, worked one after
+//! another by the port's shipwrights. While any job is unfinished the ship is
+//! laid up and cannot depart; the captain can wait in port until she is ready,
+//! or pay for an emergency repair that is done at once.
+
+use bevy::prelude::*;
+
+use crate::components::upgrade::UpgradeKind;
+use crate::events::RepairType;
+use crate::resources::TICKS_PER_HOUR;
+
+/// Cost of an emergency repair, as a multiple of the normal price.
+pub const EMERGENCY_REPAIR_MULTIPLIER: u32 = 2;
+
+/// Work the shipwrights can be doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum DockService {
+    /// Making good this many points of damage to a component.
+    Repair(RepairType, u32),
+    /// Fitting or removing an upgrade.
+    Refit(UpgradeKind),
+}
+
+impl DockService {
+    pub fn label(&self) -> String {
+        match self {
+            DockService::Repair(RepairType::Sails, _) => "Repairing sails".to_string(),
+            DockService::Repair(RepairType::Rudder, _) => "Repairing rudder".to_string(),
+            DockService::Repair(RepairType::Hull, _) => "Repairing hull".to_string(),
+            DockService::Refit(upgrade) => format!("Refitting {}", upgrade.name()),
+        }
+    }
+}
+
+/// A job in the dock queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct DockJob {
+    pub service: DockService,
+    /// World tick (`WorldClock::total_ticks`) the job is finished.
+    pub ready_at: u32,
+}
+
+/// Shipyard jobs on the player's ship at the current port, in order.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct DockQueue {
+    pub jobs: Vec<DockJob>,
+}
+
+impl DockQueue {
+    /// Queues a job taking `hours`, starting once the jobs ahead of it are done.
+    pub fn schedule(&mut self, service: DockService, hours: u32, now: u32) -> &DockJob {
+        let start = self.ready_at().unwrap_or(now).max(now);
+        self.jobs.push(DockJob { service, ready_at: start + hours * TICKS_PER_HOUR });
+        self.jobs.last().expect("job was just queued")
+    }
+
+    /// World tick the last queued job is finished, if any are queued.
+    pub fn ready_at(&self) -> Option<u32> {
+        self.jobs.iter().map(|job| job.ready_at).max()
+    }
+
+    /// Whether unfinished work keeps the ship in port.
+    pub fn is_laid_up(&self) -> bool {
+        !self.jobs.is_empty()
+    }
+
+    /// Whether a repair of this kind is already queued.
+    pub fn has_repair(&self, repair_type: RepairType) -> bool {
+        self.jobs.iter().any(|job| matches!(job.service, DockService::Repair(part, _) if part == repair_type))
+    }
+
+    /// Whole hours, rounded up, until the ship is ready to sail.
+    pub fn hours_remaining(&self, now: u32) -> u32 {
+        self.ready_at().map_or(0, |ready_at| ready_at.saturating_sub(now).div_ceil(TICKS_PER_HOUR))
+    }
+
+    /// Removes and returns the jobs finished by `now`, in queue order.
+    pub fn take_finished(&mut self, now: u32) -> Vec<DockJob> {
+        let (finished, pending) = self.jobs.iter().partition(|job| job.ready_at <= now);
+        self.jobs = pending;
+        finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_are_worked_one_after_another() {
+        let mut queue = DockQueue::default();
+        let now = 10 * TICKS_PER_HOUR;
+        queue.schedule(DockService::Repair(RepairType::Sails, 100), 4, now);
+        queue.schedule(DockService::Repair(RepairType::Hull, 60), 6, now);
+        assert!(queue.is_laid_up());
+        assert!(queue.has_repair(RepairType::Hull));
+        assert_eq!(queue.hours_remaining(now), 10);
+
+        let finished = queue.take_finished(now + 4 * TICKS_PER_HOUR);
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].service, DockService::Repair(RepairType::Sails, 100));
+        assert_eq!(queue.hours_remaining(now + 4 * TICKS_PER_HOUR + 1), 6);
+
+        queue.take_finished(now + 10 * TICKS_PER_HOUR);
+        assert!(!queue.is_laid_up());
+    }
+
+    #[test]
+    fn test_idle_shipwrights_start_now() {
+        let mut queue = DockQueue::default();
+        queue.schedule(DockService::Repair(RepairType::Rudder, 40), 2, 0);
+        queue.take_finished(2 * TICKS_PER_HOUR);
+        let job = queue.schedule(DockService::Refit(UpgradeKind::Icehouse), 4, 100 * TICKS_PER_HOUR);
+        assert_eq!(job.ready_at, 104 * TICKS_PER_HOUR);
+    }
+}
@@ -0,0 +1,223 @@
+//! Encounter templates, read from `assets/data/encounters.ron`.
+//!
+//! A template is the make-up of a battle: which ships the enemy brings, how
+//! stout they are, what shot is in their magazines and how well their
+//! captains fight them. Each template says which flags, danger of waters
+//! (pirates prowling the nearby trade lanes) and difficulties it suits, and
+//! `spawn_combat_enemies` draws one of those that fit by weight.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{AmmoType, FactionId, ShipType};
+use crate::resources::{class_rank, ContentTable, Difficulty};
+
+/// One enemy ship in an encounter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncounterShip {
+    /// Hull form she handles as, and reckoned by for pacing.
+    pub ship_type: ShipType,
+    /// Her class in `ShipDefinitions`, setting her sprite and hull; `None`
+    /// for the plain class of `ship_type`. The class's hull replaces
+    /// `ship_type` in battle, so the two should agree.
+    pub class: Option<String>,
+    /// Scales her sails, rudder and hull.
+    pub health_multiplier: f32,
+    /// Specialty shot in her magazine, and how many rounds of it.
+    pub ammo: Vec<(AmmoType, u32)>,
+    /// How well her captain fights her; 1.0 is an ordinary captain.
+    pub captain_skill: f32,
+}
+
+impl Default for EncounterShip {
+    fn default() -> Self {
+        Self {
+            ship_type: ShipType::Sloop,
+            class: None,
+            health_multiplier: 1.0,
+            ammo: Vec::new(),
+            captain_skill: 1.0,
+        }
+    }
+}
+
+impl EncounterShip {
+    /// Id of her class in `ShipDefinitions`.
+    pub fn class_id(&self) -> &str {
+        self.class.as_deref().unwrap_or(self.ship_type.class_id())
+    }
+}
+
+/// The make-up of one kind of battle, and where it is met.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncounterTemplate {
+    pub name: String,
+    /// Flags it is met under; empty for any.
+    pub factions: Vec<FactionId>,
+    /// Least danger of the waters it is met in.
+    pub min_danger: u32,
+    /// Most danger of the waters it is met in; `None` for no limit.
+    pub max_danger: Option<u32>,
+    /// Difficulties it is met at; empty for any.
+    pub difficulties: Vec<Difficulty>,
+    /// Relative chance of drawing it among those that fit.
+    pub weight: f32,
+    pub ships: Vec<EncounterShip>,
+}
+
+impl Default for EncounterTemplate {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            factions: Vec::new(),
+            min_danger: 0,
+            max_danger: None,
+            difficulties: Vec::new(),
+            weight: 1.0,
+            ships: vec![EncounterShip::default()],
+        }
+    }
+}
+
+impl EncounterTemplate {
+    /// Whether the template suits a battle with `faction` in waters of
+    /// `danger` at `difficulty`.
+    pub fn fits(&self, faction: FactionId, danger: u32, difficulty: Difficulty) -> bool {
+        (self.factions.is_empty() || self.factions.contains(&faction))
+            && danger >= self.min_danger
+            && self.max_danger.is_none_or(|max| danger <= max)
+            && (self.difficulties.is_empty() || self.difficulties.contains(&difficulty))
+            && !self.ships.is_empty()
+    }
+
+    /// Class rank (see `class_rank`) of the heaviest ship it brings.
+    pub fn heaviest_rank(&self) -> u32 {
+        self.ships.iter().map(|ship| class_rank(ship.ship_type)).max().unwrap_or(0)
+    }
+}
+
+/// Every encounter template, loaded from `assets/data/encounters.ron`.
+#[derive(Asset, TypePath, Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncounterTemplates {
+    pub templates: Vec<EncounterTemplate>,
+}
+
+impl ContentTable for EncounterTemplates {
+    const ASSET_PATH: &'static str = "data/encounters.ron";
+    const NAME: &'static str = "encounter";
+}
+
+impl EncounterTemplates {
+    /// Draws a template that fits the battle, by weight; `None` if none fit.
+    pub fn pick(
+        &self,
+        faction: FactionId,
+        danger: u32,
+        difficulty: Difficulty,
+        rng: &mut impl Rng,
+    ) -> Option<&EncounterTemplate> {
+        self.pick_among(rng, |template| template.fits(faction, danger, difficulty))
+    }
+
+    /// Draws as `pick` does among the templates whose ships are no heavier
+    /// than class rank `heaviest`, falling back to any that fit if none are.
+    pub fn pick_within(
+        &self,
+        faction: FactionId,
+        danger: u32,
+        difficulty: Difficulty,
+        heaviest: u32,
+        rng: &mut impl Rng,
+    ) -> Option<&EncounterTemplate> {
+        self.pick_among(rng, |template| {
+            template.fits(faction, danger, difficulty) && template.heaviest_rank() <= heaviest
+        })
+        .or_else(|| self.pick(faction, danger, difficulty, rng))
+    }
+
+    fn pick_among(&self, rng: &mut impl Rng, fits: impl Fn(&EncounterTemplate) -> bool) -> Option<&EncounterTemplate> {
+        let fitting: Vec<&EncounterTemplate> = self
+            .templates
+            .iter()
+            .filter(|template| template.weight > 0.0 && fits(template))
+            .collect();
+        let total: f32 = fitting.iter().map(|template| template.weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0.0..total);
+        for template in &fitting {
+            if roll < template.weight {
+                return Some(template);
+            }
+            roll -= template.weight;
+        }
+        fitting.last().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{ContentTable, ShipDefinitions};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_shipped_templates_parse_and_cover_every_flag() {
+        let shipped = EncounterTemplates::from_ron(include_str!("../../assets/data/encounters.ron")).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let factions = [FactionId::Pirates, FactionId::NationA, FactionId::NationB, FactionId::NationC];
+        for faction in factions {
+            for &difficulty in Difficulty::all() {
+                assert!(
+                    shipped.pick(faction, 0, difficulty, &mut rng).is_some(),
+                    "no encounter for {:?} at {:?}",
+                    faction,
+                    difficulty
+                );
+            }
+        }
+
+        // Every class a template names is a shipped class on the same hull
+        let ships = ShipDefinitions::from_ron(include_str!("../../assets/data/ships.ron")).unwrap();
+        for ship in shipped.templates.iter().flat_map(|template| &template.ships) {
+            assert_eq!(ships.get(ship.class_id()).map(|class| class.hull), Some(ship.ship_type), "{}", ship.class_id());
+        }
+    }
+
+    #[test]
+    fn test_pick_only_draws_fitting_templates() {
+        let templates = EncounterTemplates::from_ron(
+            r#"(templates: [
+                (name: "Lone Sloop", max_danger: Some(1)),
+                (name: "Raiders", factions: [Pirates], min_danger: 2, ships: [(ship_type: Sloop), (ship_type: Schooner, ammo: [(GrapeShot, 6)])]),
+                (name: "Never", weight: 0.0),
+            ])"#,
+        )
+        .unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+
+        for _ in 0..20 {
+            assert_eq!(templates.pick(FactionId::Pirates, 0, Difficulty::Normal, &mut rng).unwrap().name, "Lone Sloop");
+            let raiders = templates.pick(FactionId::Pirates, 3, Difficulty::Normal, &mut rng).unwrap();
+            assert_eq!(raiders.name, "Raiders");
+            assert_eq!(raiders.ships[1].ammo, vec![(AmmoType::GrapeShot, 6)]);
+        }
+        assert!(templates.pick(FactionId::NationA, 3, Difficulty::Normal, &mut rng).is_none());
+
+        // Paced to sloops, a frigate stays away unless nothing else fits
+        let paced = EncounterTemplates::from_ron(
+            r#"(templates: [(name: "Lone Sloop"), (name: "Frigate", weight: 10.0, ships: [(ship_type: Frigate)])])"#,
+        )
+        .unwrap();
+        for _ in 0..20 {
+            assert_eq!(paced.pick_within(FactionId::Pirates, 0, Difficulty::Normal, 1, &mut rng).unwrap().name, "Lone Sloop");
+        }
+        assert_eq!(templates.pick_within(FactionId::Pirates, 3, Difficulty::Normal, 1, &mut rng).unwrap().name, "Raiders");
+    }
+}
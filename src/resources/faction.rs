@@ -7,15 +7,70 @@ use std::collections::HashMap;
 
 use crate::components::FactionId;
 
+/// Lowest reputation the player can hold with a faction.
+pub const MIN_REPUTATION: i32 = -100;
+/// Highest reputation the player can hold with a faction.
+pub const MAX_REPUTATION: i32 = 100;
+/// Reputation below which a faction's ships attack the player and its ports
+/// close their harbours.
+pub const HOSTILE_REPUTATION: i32 = -50;
+/// Reputation below which a nation posts a bounty and sends hunters after the player.
+pub const BOUNTY_REPUTATION: i32 = -30;
+/// Reputation below which a faction's ports trade on worse terms.
+pub const UNFRIENDLY_REPUTATION: i32 = -10;
+/// Reputation above which a faction's ports trade on better terms.
+pub const FRIENDLY_REPUTATION: i32 = 25;
+
+/// How a faction regards the player, by reputation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Standing {
+    /// Ships attack on sight; ports are closed.
+    Hostile,
+    /// Ports trade, at a premium.
+    Unfriendly,
+    Neutral,
+    /// Ports trade at a discount.
+    Friendly,
+}
+
+impl Standing {
+    pub fn from_reputation(reputation: i32) -> Self {
+        if reputation < HOSTILE_REPUTATION {
+            Standing::Hostile
+        } else if reputation < UNFRIENDLY_REPUTATION {
+            Standing::Unfriendly
+        } else if reputation > FRIENDLY_REPUTATION {
+            Standing::Friendly
+        } else {
+            Standing::Neutral
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Standing::Hostile => "Hostile",
+            Standing::Unfriendly => "Unfriendly",
+            Standing::Neutral => "Neutral",
+            Standing::Friendly => "Friendly",
+        }
+    }
+
+    /// Whether the faction's ports let the player in.
+    pub fn allows_port_entry(&self) -> bool {
+        *self != Standing::Hostile
+    }
+}
+
 /// State for a single faction in the world simulation.
 /// Tracks economic and military capabilities.
 #[derive(Debug, Clone, Reflect)]
 pub struct FactionState {
     /// Faction's treasury.
     pub gold: u32,
-    /// Number of ships owned by this faction.
+    /// Number of ships this faction has afloat, recounted hourly by
+    /// `faction_fleet_census_system`.
     pub ships: u32,
-    /// Reputation with the player (-100 to 100).
+    /// Reputation with the player (`MIN_REPUTATION` to `MAX_REPUTATION`).
     pub player_reputation: i32,
     /// Trade routes managed by this faction (origin port Entity, destination port Entity).
     pub trade_routes: Vec<(Entity, Entity)>,
@@ -47,7 +102,7 @@ impl FactionRegistry {
         factions.insert(FactionId::Pirates, FactionState {
             gold: 5_000,
             ships: 20,
-            player_reputation: -100, // Always hostile
+            player_reputation: MIN_REPUTATION, // Always hostile
             ..Default::default()
         });
         factions.insert(FactionId::NationA, FactionState::default());
@@ -66,10 +121,69 @@ impl FactionRegistry {
         self.factions.get_mut(&faction)
     }
 
+    /// The player's reputation with a faction; 0 for one not in the registry.
+    pub fn reputation(&self, faction: FactionId) -> i32 {
+        self.factions.get(&faction).map_or(0, |state| state.player_reputation)
+    }
+
+    /// Shifts the player's reputation with a faction by `delta`, kept within
+    /// `MIN_REPUTATION..=MAX_REPUTATION`. Returns the new reputation, or `None`
+    /// for a faction not in the registry.
+    pub fn adjust_reputation(&mut self, faction: FactionId, delta: i32) -> Option<i32> {
+        let state = self.factions.get_mut(&faction)?;
+        state.player_reputation = (state.player_reputation + delta).clamp(MIN_REPUTATION, MAX_REPUTATION);
+        Some(state.player_reputation)
+    }
+
+    /// How a faction regards the player.
+    pub fn standing(&self, faction: FactionId) -> Standing {
+        Standing::from_reputation(self.reputation(faction))
+    }
+
     /// Returns true if the faction is hostile to the player.
     pub fn is_hostile(&self, faction: FactionId) -> bool {
-        self.factions
-            .get(&faction)
-            .map_or(false, |state| state.player_reputation < -50)
+        self.standing(faction) == Standing::Hostile
+    }
+
+    /// Whether a nation has put a price on the player's head. The Brethren
+    /// settle their own scores and post no bounties.
+    pub fn has_bounty(&self, faction: FactionId) -> bool {
+        faction != FactionId::Pirates && self.reputation(faction) < BOUNTY_REPUTATION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standing_follows_reputation() {
+        let mut registry = FactionRegistry::new();
+        assert_eq!(registry.standing(FactionId::NationA), Standing::Neutral);
+        assert!(registry.is_hostile(FactionId::Pirates));
+        assert!(!registry.has_bounty(FactionId::Pirates));
+
+        registry.get_mut(FactionId::NationA).unwrap().player_reputation = -40;
+        assert_eq!(registry.standing(FactionId::NationA), Standing::Unfriendly);
+        assert!(registry.standing(FactionId::NationA).allows_port_entry());
+        assert!(registry.has_bounty(FactionId::NationA));
+
+        registry.get_mut(FactionId::NationA).unwrap().player_reputation = -60;
+        assert!(registry.is_hostile(FactionId::NationA));
+        assert!(!registry.standing(FactionId::NationA).allows_port_entry());
+
+        registry.get_mut(FactionId::NationB).unwrap().player_reputation = 40;
+        assert_eq!(registry.standing(FactionId::NationB), Standing::Friendly);
+    }
+
+    #[test]
+    fn test_adjust_reputation_stays_in_bounds() {
+        let mut registry = FactionRegistry::new();
+        assert_eq!(registry.adjust_reputation(FactionId::NationA, 30), Some(30));
+        assert_eq!(registry.adjust_reputation(FactionId::NationA, 500), Some(MAX_REPUTATION));
+        assert_eq!(registry.adjust_reputation(FactionId::Pirates, -10), Some(MIN_REPUTATION));
+
+        registry.factions.remove(&FactionId::NationC);
+        assert_eq!(registry.adjust_reputation(FactionId::NationC, 10), None);
     }
 }
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 use crate::components::Cargo;
+use crate::components::ship::ShipType;
+use crate::components::upgrade::{upgrades_cargo_bonus, UpgradeKind};
 
 /// Data structure to persist a ship's state across game states (Combat -> High Seas).
 #[derive(Clone, Debug, Reflect)]
@@ -14,6 +16,15 @@ pub struct ShipData {
     pub cargo: Option<Cargo>,
     /// Name of the ship.
     pub name: String,
+    /// Upgrades fitted to this hull.
+    pub upgrades: Vec<UpgradeKind>,
+    /// Hull form she handles as.
+    #[reflect(default)]
+    pub ship_type: ShipType,
+    /// Her class in `ShipDefinitions`, for her speed and her price at a
+    /// shipyard; a class not in the table falls back to her hull's.
+    #[reflect(default)]
+    pub class: String,
 }
 
 impl Default for ShipData {
@@ -24,10 +35,37 @@ impl Default for ShipData {
             max_hull_health: 100.0,
             cargo: None,
             name: "Captured Ship".to_string(),
+            upgrades: Vec::new(),
+            ship_type: ShipType::default(),
+            class: ShipType::default().class_id().to_string(),
         }
     }
 }
 
+impl ShipData {
+    /// This ship's hold, with fitted upgrades counted in its capacity.
+    /// Ships that never carried cargo get an empty default hold.
+    pub fn hold(&self) -> Cargo {
+        let mut hold = self.cargo.clone().unwrap_or_default();
+        hold.capacity += upgrades_cargo_bonus(&self.upgrades);
+        hold
+    }
+
+    /// Stores a hold obtained from `hold`, taking the upgrade bonus back out.
+    pub fn set_hold(&mut self, mut hold: Cargo) {
+        hold.capacity = hold.capacity.saturating_sub(upgrades_cargo_bonus(&self.upgrades));
+        self.cargo = Some(hold);
+    }
+}
+
+/// A cargo hold in the player's fleet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CargoHold {
+    Flagship,
+    /// Index into `PlayerFleet::ships`.
+    Fleet(usize),
+}
+
 /// Resource that tracks the player's fleet of ships.
 #[derive(Resource, Debug, Default, Reflect)]
 #[reflect(Resource)]
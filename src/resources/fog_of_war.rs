@@ -6,6 +6,11 @@ use bevy::utils::HashSet;
 /// This is used by:
 /// - `FogOfWarSystem` to update visibility
 /// - Rendering systems to apply the fog overlay
+///
+/// Features that chart waters (intel, bottles, lookouts) reveal through
+/// `reveal_tiles`, `reveal_circle` and `reveal_cone`. Tiles revealed in a
+/// frame are batched and published as one `FogRevealedEvent` by
+/// `publish_fog_reveals`, which the ink-reveal animation reads.
 #[derive(Resource, Default, Debug)]
 pub struct FogOfWar {
     /// Set of tile coordinates (x, y) that have been revealed.
@@ -38,12 +43,35 @@ impl FogOfWar {
         self.explored_tiles.len()
     }
 
+    /// Every explored tile, in no particular order.
+    pub fn explored_tiles(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.explored_tiles.iter().copied()
+    }
+
     /// Clears all explored tiles (e.g., for a new game).
     pub fn clear(&mut self) {
         self.explored_tiles.clear();
         self.newly_explored.clear();
     }
 
+    /// Reveals every tile in `tiles`. Returns how many were newly explored.
+    pub fn reveal_tiles(&mut self, tiles: impl IntoIterator<Item = IVec2>) -> usize {
+        tiles.into_iter().filter(|tile| self.explore(*tile)).count()
+    }
+
+    /// Reveals the disc of `radius` tiles around `center`.
+    /// Returns how many tiles were newly explored.
+    pub fn reveal_circle(&mut self, center: IVec2, radius: i32) -> usize {
+        self.reveal_tiles(circle_tiles(center, radius))
+    }
+
+    /// Reveals the cone of `range` tiles from `origin` along `direction`,
+    /// `half_angle` radians either side of it, as a lighthouse beam or a
+    /// spyglass sweeps. Returns how many tiles were newly explored.
+    pub fn reveal_cone(&mut self, origin: IVec2, direction: Vec2, range: i32, half_angle: f32) -> usize {
+        self.reveal_tiles(cone_tiles(origin, direction, range, half_angle))
+    }
+
     /// Returns and clears the list of newly explored tiles.
     /// Call this after updating the tilemap to reset for next frame.
     pub fn take_newly_explored(&mut self) -> Vec<IVec2> {
@@ -55,3 +83,54 @@ impl FogOfWar {
         !self.newly_explored.is_empty()
     }
 }
+
+/// Tiles of the disc of `radius` around `center`.
+pub fn circle_tiles(center: IVec2, radius: i32) -> impl Iterator<Item = IVec2> {
+    (-radius..=radius)
+        .flat_map(move |dy| (-radius..=radius).map(move |dx| IVec2::new(dx, dy)))
+        .filter(move |offset| offset.length_squared() <= radius * radius)
+        .map(move |offset| center + offset)
+}
+
+/// Tiles of the cone of `range` from `origin` along `direction`, `half_angle`
+/// radians either side. The origin tile is always in it.
+pub fn cone_tiles(origin: IVec2, direction: Vec2, range: i32, half_angle: f32) -> impl Iterator<Item = IVec2> {
+    let direction = direction.normalize_or_zero();
+    circle_tiles(IVec2::ZERO, range)
+        .filter(move |offset| *offset == IVec2::ZERO || direction.angle_to(offset.as_vec2()).abs() <= half_angle)
+        .map(move |offset| origin + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circle_is_a_disc() {
+        let tiles: Vec<IVec2> = circle_tiles(IVec2::new(10, 10), 2).collect();
+        assert!(tiles.contains(&IVec2::new(12, 10)));
+        assert!(!tiles.contains(&IVec2::new(12, 12)));
+        assert_eq!(tiles.len(), 13);
+    }
+
+    #[test]
+    fn test_cone_looks_one_way() {
+        let tiles: Vec<IVec2> = cone_tiles(IVec2::ZERO, Vec2::X, 5, 0.4).collect();
+        assert!(tiles.contains(&IVec2::ZERO));
+        assert!(tiles.contains(&IVec2::new(5, 0)));
+        assert!(tiles.contains(&IVec2::new(4, 1)));
+        assert!(!tiles.contains(&IVec2::new(-3, 0)));
+        assert!(!tiles.contains(&IVec2::new(0, 4)));
+    }
+
+    #[test]
+    fn test_reveals_batch_only_new_tiles() {
+        let mut fog = FogOfWar::default();
+        assert_eq!(fog.reveal_circle(IVec2::new(10, 10), 2), 13);
+        // Overlapping the disc only counts the fresh tiles
+        assert_eq!(fog.reveal_tiles([IVec2::new(10, 10), IVec2::new(20, 20)]), 1);
+        assert_eq!(fog.take_newly_explored().len(), 14);
+        assert!(!fog.has_newly_explored());
+        assert_eq!(fog.explored_count(), 14);
+    }
+}
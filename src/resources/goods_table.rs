@@ -0,0 +1,83 @@
+//! Trade goods' market baselines, read from `assets/data/goods.ron`.
+//!
+//! A port prices each good against its base price, scaled by how its stock
+//! stands against the base quantity (see `systems::economy`), and draws its
+//! starting stock around both (see `plugins::port`). Goods left out of the
+//! file keep the built-in values in `price_config`.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::GoodType;
+use crate::resources::ContentTable;
+use crate::systems::economy::price_config;
+
+/// What one good trades around.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GoodStats {
+    /// Price at equilibrium, before supply, demand and season.
+    pub base_price: f32,
+    /// Stock a port holds at equilibrium.
+    pub base_quantity: u32,
+}
+
+impl GoodStats {
+    /// The values built into the game.
+    pub fn builtin(good: GoodType) -> Self {
+        Self {
+            base_price: price_config::base_price(&good),
+            base_quantity: price_config::base_quantity(&good),
+        }
+    }
+}
+
+/// Every good's market baselines.
+#[derive(Asset, TypePath, Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GoodsTable {
+    pub goods: HashMap<GoodType, GoodStats>,
+}
+
+impl Default for GoodsTable {
+    fn default() -> Self {
+        Self {
+            goods: GoodType::ALL.iter().map(|good| (*good, GoodStats::builtin(*good))).collect(),
+        }
+    }
+}
+
+impl ContentTable for GoodsTable {
+    const ASSET_PATH: &'static str = "data/goods.ron";
+    const NAME: &'static str = "goods";
+}
+
+impl GoodsTable {
+    pub fn stats(&self, good: GoodType) -> GoodStats {
+        self.goods.get(&good).copied().unwrap_or_else(|| GoodStats::builtin(good))
+    }
+
+    pub fn base_price(&self, good: GoodType) -> f32 {
+        self.stats(good).base_price
+    }
+
+    pub fn base_quantity(&self, good: GoodType) -> u32 {
+        self.stats(good).base_quantity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_goods_match_builtin_and_gaps_fall_back() {
+        let shipped = GoodsTable::from_ron(include_str!("../../assets/data/goods.ron")).unwrap();
+        assert_eq!(shipped, GoodsTable::default());
+
+        let partial = GoodsTable::from_ron("(goods: { Rum: (base_price: 30.0, base_quantity: 50) })").unwrap();
+        assert_eq!(partial.base_price(GoodType::Rum), 30.0);
+        assert_eq!(partial.stats(GoodType::Fish), GoodStats::builtin(GoodType::Fish));
+    }
+}
@@ -0,0 +1,96 @@
+//! The harbour approach: a short side-on scene of the player's ship standing
+//! in to a port, played between the High Seas and the port screen.
+//!
+//! Only towns and cities get one; a cove trading in a few goods is entered
+//! at once. The scene is drawn from the port's `PortSize`, its flag, the
+//! weather the ship sailed in on and the hour, and the player may skip it or
+//! turn it off in the settings (`GameSettings::harbor_approach`).
+
+use bevy::prelude::*;
+
+use crate::components::{FactionId, PortSize};
+
+/// How long the approach plays before the port screen opens (seconds).
+pub const APPROACH_SECONDS: f32 = 4.0;
+/// Wind strength from which the harbour is shown blowing.
+pub const BLOWING_WIND: f32 = 0.6;
+
+/// The weather the ship stands in on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HarborWeather {
+    Fair,
+    Blowing,
+    /// The ship came in out of a squall.
+    Squall,
+}
+
+impl HarborWeather {
+    pub fn from_conditions(wind_strength: f32, in_squall: bool) -> Self {
+        if in_squall {
+            HarborWeather::Squall
+        } else if wind_strength >= BLOWING_WIND {
+            HarborWeather::Blowing
+        } else {
+            HarborWeather::Fair
+        }
+    }
+}
+
+/// How far a scene layer has slid at `progress`: the nearer the layer
+/// (`depth` towards 1.0), the further it moves across `pan`.
+pub fn parallax_offset(progress: f32, depth: f32, pan: f32) -> f32 {
+    -progress.clamp(0.0, 1.0) * depth * pan
+}
+
+/// An approach under way.
+#[derive(Debug, Clone)]
+pub struct Approach {
+    pub port_name: String,
+    pub faction: Option<FactionId>,
+    pub size: PortSize,
+    pub weather: HarborWeather,
+    /// How dark it was as the ship came in (`night_darkness`).
+    pub darkness: f32,
+    /// Seconds played.
+    pub elapsed: f32,
+}
+
+impl Approach {
+    /// Share of the approach played, 0.0 to 1.0.
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / APPROACH_SECONDS).min(1.0)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= APPROACH_SECONDS
+    }
+}
+
+/// The harbour approach being played, if any.
+#[derive(Resource, Debug, Default)]
+pub struct HarborApproach {
+    pub approach: Option<Approach>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_towns_and_cities_get_an_approach() {
+        assert!(!PortSize::from_goods(3).is_major());
+        assert_eq!(PortSize::from_goods(4), PortSize::Town);
+        assert!(PortSize::from_goods(5).has_fort());
+        assert!(PortSize::City.buildings() > PortSize::Town.buildings());
+        // Squalls outrank the wind
+        assert_eq!(HarborWeather::from_conditions(0.9, true), HarborWeather::Squall);
+        assert_eq!(HarborWeather::from_conditions(BLOWING_WIND, false), HarborWeather::Blowing);
+    }
+
+    #[test]
+    fn test_near_layers_slide_further() {
+        assert_eq!(parallax_offset(0.0, 1.0, 200.0), 0.0);
+        assert!(parallax_offset(1.0, 1.0, 200.0) < parallax_offset(1.0, 0.2, 200.0));
+        assert_eq!(parallax_offset(2.0, 1.0, 200.0), -200.0);
+    }
+}
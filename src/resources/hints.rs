@@ -0,0 +1,113 @@
+//! Contextual hints shown the first time a player meets a mechanic.
+//!
+//! Which hints have been seen is stored in `MetaProfile`, so a tip fires at
+//! most once per profile rather than once per run.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Identifiers for one-time contextual hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HintId {
+    /// Wind has risen to storm strength for the first time.
+    FirstStorm,
+    /// Illegal goods are in the player's hold for the first time.
+    FirstContraband,
+    /// An enemy ship can be boarded for the first time.
+    FirstBoarding,
+}
+
+impl HintId {
+    /// Returns all hint variants for iteration.
+    pub fn all() -> &'static [HintId] {
+        &[HintId::FirstStorm, HintId::FirstContraband, HintId::FirstBoarding]
+    }
+
+    /// Short heading shown at the top of the hint window.
+    pub fn title(&self) -> &'static str {
+        match self {
+            HintId::FirstStorm => "Heavy Weather",
+            HintId::FirstContraband => "Contraband Aboard",
+            HintId::FirstBoarding => "Alongside",
+        }
+    }
+
+    /// Codex entry unlocked alongside this hint.
+    pub fn codex_id(&self) -> &'static str {
+        match self {
+            HintId::FirstStorm => "mechanic.storms",
+            HintId::FirstContraband => "mechanic.contraband",
+            HintId::FirstBoarding => "mechanic.boarding",
+        }
+    }
+
+    /// Body text explaining the mechanic.
+    pub fn text(&self) -> &'static str {
+        match self {
+            HintId::FirstStorm => {
+                "Strong winds push every ship hard. Sailing downwind is fast, \
+                 but beating into a gale will crawl and strain your sails."
+            }
+            HintId::FirstContraband => {
+                "Some goods are illegal in certain ports. Contraband sells dear, \
+                 but patrols and harbour masters may take an interest in your hold."
+            }
+            HintId::FirstBoarding => {
+                "She is close and slow enough to board: press G to throw the grapnels \
+                 and fight for her deck. A ship that strikes her colours is your prize. \
+                 Win the battle, then choose her fate: take her into your fleet, \
+                 let her go, press her crew or ransom her officers."
+            }
+        }
+    }
+}
+
+/// Queue of hints waiting to be displayed, shown one at a time.
+#[derive(Resource, Default, Debug)]
+pub struct HintQueue {
+    pending: VecDeque<HintId>,
+}
+
+impl HintQueue {
+    /// Adds a hint to the back of the queue unless it is already waiting.
+    pub fn push(&mut self, hint: HintId) {
+        if !self.pending.contains(&hint) {
+            self.pending.push_back(hint);
+        }
+    }
+
+    /// Returns the hint currently being displayed, if any.
+    pub fn current(&self) -> Option<HintId> {
+        self.pending.front().copied()
+    }
+
+    /// Dismisses the hint currently being displayed.
+    pub fn dismiss(&mut self) {
+        self.pending.pop_front();
+    }
+
+    /// Drops every pending hint (used when hints are disabled).
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_deduplicates_pending_hints() {
+        let mut queue = HintQueue::default();
+        queue.push(HintId::FirstStorm);
+        queue.push(HintId::FirstStorm);
+        queue.push(HintId::FirstBoarding);
+
+        assert_eq!(queue.current(), Some(HintId::FirstStorm));
+        queue.dismiss();
+        assert_eq!(queue.current(), Some(HintId::FirstBoarding));
+        queue.dismiss();
+        assert_eq!(queue.current(), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! The captain's journal: timestamped entries for notable happenings in a run.
+//!
+//! Entries are filed under a `JournalCategory` so the journal window can be
+//! browsed by kind. The journal is saved with the run.
+
+use bevy::prelude::*;
+
+use super::{CalendarDate, WorldClock};
+
+/// What a journal entry is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum JournalCategory {
+    /// Contracts taken, failed or lapsed.
+    Contract,
+    /// Intel bought in taverns.
+    Intel,
+    /// Ports sighted for the first time.
+    Discovery,
+    /// Battles fought and what came of them.
+    Combat,
+    /// Everything else worth writing down.
+    #[default]
+    Event,
+}
+
+impl JournalCategory {
+    pub const ALL: [JournalCategory; 5] = [
+        JournalCategory::Contract,
+        JournalCategory::Intel,
+        JournalCategory::Discovery,
+        JournalCategory::Combat,
+        JournalCategory::Event,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            JournalCategory::Contract => "Contracts",
+            JournalCategory::Intel => "Intel",
+            JournalCategory::Discovery => "Discoveries",
+            JournalCategory::Combat => "Battles",
+            JournalCategory::Event => "Events",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            JournalCategory::Contract => "📜",
+            JournalCategory::Intel => "💬",
+            JournalCategory::Discovery => "🗺",
+            JournalCategory::Combat => "⚔",
+            JournalCategory::Event => "✒",
+        }
+    }
+}
+
+/// One journal entry, stamped with the in-game time it was written.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct JournalEntry {
+    pub day: u32,
+    pub hour: u32,
+    pub category: JournalCategory,
+    pub text: String,
+}
+
+impl JournalEntry {
+    /// When the entry was written, e.g. "14 March 1715, 09:00".
+    pub fn stamp(&self) -> String {
+        format!("{}, {:02}:00", CalendarDate::from_day(self.day).formatted(), self.hour)
+    }
+}
+
+/// Entries written during the current run, oldest first.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Journal {
+    pub entries: Vec<JournalEntry>,
+    /// Tiles of the ports already noted as sighted.
+    pub ports_sighted: Vec<IVec2>,
+}
+
+impl Journal {
+    /// Writes an `Event` entry stamped with the current world time.
+    pub fn record(&mut self, clock: &WorldClock, text: impl Into<String>) {
+        self.record_as(clock, JournalCategory::Event, text);
+    }
+
+    /// Writes an entry under `category`, stamped with the current world time.
+    pub fn record_as(&mut self, clock: &WorldClock, category: JournalCategory, text: impl Into<String>) {
+        let text = text.into();
+        info!("Journal (Day {}, Hour {}): {}", clock.day, clock.hour, text);
+        self.entries.push(JournalEntry {
+            day: clock.day,
+            hour: clock.hour,
+            category,
+            text,
+        });
+    }
+
+    /// Notes the port on `tile` as sighted. Returns whether it is the first time.
+    pub fn sight_port(&mut self, tile: IVec2) -> bool {
+        if self.ports_sighted.contains(&tile) {
+            return false;
+        }
+        self.ports_sighted.push(tile);
+        true
+    }
+
+    /// Entries under `category` (all of them for `None`), newest first.
+    pub fn newest_first(&self, category: Option<JournalCategory>) -> impl Iterator<Item = &JournalEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(move |entry| category.is_none() || category == Some(entry.category))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.ports_sighted.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_stamps_world_time() {
+        let mut journal = Journal::default();
+        let clock = WorldClock { day: 3, hour: 14, tick: 7 };
+        journal.record(&clock, "Weighed anchor");
+        assert_eq!(journal.len(), 1);
+        assert_eq!(
+            journal.entries[0],
+            JournalEntry { day: 3, hour: 14, category: JournalCategory::Event, text: "Weighed anchor".into() }
+        );
+        assert_eq!(journal.entries[0].stamp(), "3 January 1715, 14:00");
+    }
+
+    #[test]
+    fn test_browse_by_category_and_sight_ports_once() {
+        let mut journal = Journal::default();
+        let clock = WorldClock { day: 1, hour: 8, tick: 0 };
+        journal.record_as(&clock, JournalCategory::Contract, "Took a contract");
+        journal.record(&clock, "Saw a whale");
+        journal.record_as(&clock, JournalCategory::Contract, "Contract lapsed");
+
+        let contracts: Vec<&str> = journal
+            .newest_first(Some(JournalCategory::Contract))
+            .map(|entry| entry.text.as_str())
+            .collect();
+        assert_eq!(contracts, ["Contract lapsed", "Took a contract"]);
+        assert_eq!(journal.newest_first(None).count(), 3);
+
+        assert!(journal.sight_port(IVec2::new(4, 5)));
+        assert!(!journal.sight_port(IVec2::new(4, 5)));
+    }
+}
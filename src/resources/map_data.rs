@@ -170,6 +170,21 @@ impl MapData {
     pub fn is_navigable(&self, x: u32, y: u32) -> bool {
         self.tile(x, y).map(|t| t.tile_type.is_navigable()).unwrap_or(false)
     }
+
+    /// Returns the navigable tile closest to `tile`, searching out to `max_radius` tiles.
+    pub fn nearest_navigable(&self, tile: IVec2, max_radius: i32) -> Option<IVec2> {
+        for radius in 0..=max_radius {
+            let ring = (-radius..=radius)
+                .flat_map(|dy| (-radius..=radius).map(move |dx| IVec2::new(dx, dy)))
+                .filter(|offset| offset.x.abs() == radius || offset.y.abs() == radius)
+                .map(|offset| tile + offset)
+                .filter(|pos| self.in_bounds(pos.x, pos.y) && self.is_navigable(pos.x as u32, pos.y as u32));
+            if let Some(found) = ring.min_by_key(|pos| (*pos - tile).length_squared()) {
+                return Some(found);
+            }
+        }
+        None
+    }
 }
 
 impl Default for MapData {
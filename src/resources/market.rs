@@ -0,0 +1,225 @@
+//! Bulk trading at a port market.
+//!
+//! The market tab builds a `TradeTicket`: units of each good to buy or sell.
+//! Its `TradePreview` shows what the whole ticket costs and where it leaves
+//! the hold, and the ticket is sent as one `BulkTradeEvent`, which sells
+//! before it buys so the proceeds and freed space count towards the purchases.
+
+use std::collections::HashMap;
+
+use crate::components::cargo::{Cargo, GoodType};
+use crate::components::port::Inventory;
+use crate::resources::Standing;
+
+/// Share of a port's price it pays for goods the player sells.
+pub const SELL_PRICE_MODIFIER: f32 = 0.8;
+
+/// What a port charges for its goods and pays for the player's, as shares
+/// of its listed prices. Ports trade on worse terms with those they distrust.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeTerms {
+    pub buy: f32,
+    pub sell: f32,
+}
+
+impl Default for TradeTerms {
+    fn default() -> Self {
+        Self {
+            buy: 1.0,
+            sell: SELL_PRICE_MODIFIER,
+        }
+    }
+}
+
+impl TradeTerms {
+    pub fn for_standing(standing: Standing) -> Self {
+        let (buy, sell) = match standing {
+            Standing::Hostile | Standing::Unfriendly => (1.25, 0.85),
+            Standing::Neutral => (1.0, 1.0),
+            Standing::Friendly => (0.9, 1.1),
+        };
+        Self {
+            buy,
+            sell: SELL_PRICE_MODIFIER * sell,
+        }
+    }
+
+    /// Price per unit the player pays for goods listed at `price`.
+    pub fn buy_price(&self, price: f32) -> u32 {
+        (price * self.buy) as u32
+    }
+
+    /// Price per unit the player is paid for goods listed at `price`.
+    pub fn sell_price(&self, price: f32) -> f32 {
+        price * self.sell
+    }
+}
+
+/// Units of each good to trade: positive to buy, negative to sell.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TradeTicket {
+    orders: HashMap<GoodType, i32>,
+    terms: TradeTerms,
+}
+
+impl TradeTicket {
+    pub fn get(&self, good: GoodType) -> i32 {
+        *self.orders.get(&good).unwrap_or(&0)
+    }
+
+    pub fn set(&mut self, good: GoodType, quantity: i32) {
+        if quantity == 0 {
+            self.orders.remove(&good);
+        } else {
+            self.orders.insert(good, quantity);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.orders.clear();
+    }
+
+    pub fn terms(&self) -> TradeTerms {
+        self.terms
+    }
+
+    /// Prices the ticket on the terms of the port it is drawn up at.
+    pub fn set_terms(&mut self, terms: TradeTerms) {
+        self.terms = terms;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// Orders sells first, then buys, each group in a stable order.
+    pub fn orders(&self) -> Vec<(GoodType, i32)> {
+        let mut orders: Vec<(GoodType, i32)> = self.orders.iter().map(|(good, qty)| (*good, *qty)).collect();
+        orders.sort_by_key(|(good, qty)| (*qty > 0, format!("{:?}", good)));
+        orders
+    }
+
+    /// Most units of `good` that can be bought, given the rest of the ticket.
+    pub fn max_buy(&self, good: GoodType, inventory: &Inventory, cargo: &Cargo, gold: u32) -> u32 {
+        let Some(item) = inventory.get_good(&good) else {
+            return 0;
+        };
+        let mut others = self.clone();
+        others.set(good, 0);
+        let preview = others.preview(inventory, cargo, gold);
+        let space = cargo.capacity.saturating_sub(preview.cargo_after);
+        let funds = preview.gold_after.max(0) as u32;
+        let affordable = match self.terms.buy_price(item.price) {
+            0 => u32::MAX,
+            price => funds / price,
+        };
+        item.quantity.min(space).min(affordable)
+    }
+
+    /// Most units of `good` that can be sold.
+    pub fn max_sell(&self, good: GoodType, cargo: &Cargo) -> u32 {
+        cargo.get(good)
+    }
+
+    /// What the ticket would cost and leave in the hold, at current prices.
+    pub fn preview(&self, inventory: &Inventory, cargo: &Cargo, gold: u32) -> TradePreview {
+        let mut preview = TradePreview {
+            cargo_after: cargo.total_units(),
+            capacity: cargo.capacity,
+            ..Default::default()
+        };
+        for (good, quantity) in self.orders() {
+            let price = inventory.get_good(&good).map_or(0.0, |item| item.price);
+            let units = quantity.unsigned_abs();
+            if quantity > 0 {
+                preview.cost += self.terms.buy_price(price) * units;
+                preview.cargo_after += units;
+            } else {
+                preview.revenue += (units as f32 * self.terms.sell_price(price)) as u32;
+                preview.cargo_after = preview.cargo_after.saturating_sub(units);
+            }
+        }
+        preview.gold_after = gold as i64 + preview.revenue as i64 - preview.cost as i64;
+        preview
+    }
+}
+
+/// Outcome of a `TradeTicket` at current prices.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TradePreview {
+    /// Gold paid for purchases.
+    pub cost: u32,
+    /// Gold received for sales.
+    pub revenue: u32,
+    /// Gold aboard after the trade; negative if the ticket can't be paid for.
+    pub gold_after: i64,
+    /// Units in the hold after the trade.
+    pub cargo_after: u32,
+    pub capacity: u32,
+}
+
+impl TradePreview {
+    /// Whether the ticket can be paid for and fits in the hold.
+    pub fn is_feasible(&self) -> bool {
+        self.gold_after >= 0 && self.cargo_after <= self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market() -> Inventory {
+        let mut inventory = Inventory::new();
+        inventory.set_good(GoodType::Rum, 50, 10.0);
+        inventory.set_good(GoodType::Timber, 200, 5.0);
+        inventory
+    }
+
+    #[test]
+    fn test_sales_fund_purchases() {
+        let mut cargo = Cargo::new(20);
+        cargo.add(GoodType::Timber, 20);
+        let mut ticket = TradeTicket::default();
+        // A full hold and no gold: nothing can be bought yet
+        assert_eq!(ticket.max_buy(GoodType::Rum, &market(), &cargo, 0), 0);
+
+        ticket.set(GoodType::Timber, -20);
+        // Selling 20 timber at 4g frees the hold and pays for 8 rum
+        assert_eq!(ticket.max_buy(GoodType::Rum, &market(), &cargo, 0), 8);
+        ticket.set(GoodType::Rum, 8);
+        let preview = ticket.preview(&market(), &cargo, 0);
+        assert_eq!((preview.revenue, preview.cost, preview.gold_after), (80, 80, 0));
+        assert_eq!(preview.cargo_after, 8);
+        assert!(preview.is_feasible());
+        assert_eq!(ticket.orders()[0], (GoodType::Timber, -20));
+    }
+
+    #[test]
+    fn test_overreaching_ticket_is_not_feasible() {
+        let cargo = Cargo::new(10);
+        let mut ticket = TradeTicket::default();
+        ticket.set(GoodType::Rum, 11);
+        let preview = ticket.preview(&market(), &cargo, 1000);
+        assert!(!preview.is_feasible());
+        assert_eq!(ticket.max_buy(GoodType::Rum, &market(), &cargo, 1000), 10);
+        ticket.set(GoodType::Rum, 0);
+        assert!(ticket.is_empty());
+    }
+
+    #[test]
+    fn test_terms_follow_standing() {
+        let cargo = Cargo::new(20);
+        let mut ticket = TradeTicket::default();
+        ticket.set(GoodType::Rum, 4);
+        assert_eq!(ticket.preview(&market(), &cargo, 1000).cost, 40);
+
+        ticket.set_terms(TradeTerms::for_standing(Standing::Unfriendly));
+        assert_eq!(ticket.preview(&market(), &cargo, 1000).cost, 48);
+        assert_eq!(ticket.max_buy(GoodType::Rum, &market(), &cargo, 60), 5);
+
+        ticket.set_terms(TradeTerms::for_standing(Standing::Friendly));
+        assert_eq!(ticket.preview(&market(), &cargo, 1000).cost, 36);
+        assert_eq!(TradeTerms::for_standing(Standing::Neutral), TradeTerms::default());
+    }
+}
@@ -5,11 +5,24 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use super::defeat::{ScatteredShip, VeteranCompanion};
+use super::hints::HintId;
+use super::sea_monsters::MonsterKind;
+use super::save_compat::{upgrade_profile_json, PendingSave, PROFILE_VERSION};
+use super::wreck_sites::decayed;
+use super::ContentTable;
+
+/// In-game days a legacy wreck lasts before she breaks up, counted across runs.
+pub const LEGACY_WRECK_LIFETIME_DAYS: u32 = 40;
+
 /// Persistent profile that tracks meta-progression across runs.
 ///
 /// This resource is loaded from file on app start and saved on death/quit.
 #[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct MetaProfile {
+    /// Layout version (`PROFILE_VERSION`); absent in profiles that predate it.
+    #[serde(default)]
+    pub version: u32,
     /// Player stats that affect gameplay systems.
     pub stats: PlayerStats,
     /// Unlocked starting archetypes.
@@ -24,11 +37,37 @@ pub struct MetaProfile {
     pub runs_completed: u32,
     /// Number of deaths.
     pub deaths: u32,
+    /// Contextual hints the player has already seen.
+    #[serde(default)]
+    pub seen_hints: Vec<HintId>,
+    /// Whether contextual hints are shown at all.
+    #[serde(default = "default_hints_enabled")]
+    pub hints_enabled: bool,
+    /// Codex entry identifiers the player has unlocked.
+    #[serde(default)]
+    pub codex_unlocked: Vec<String>,
+    /// Summary of the most recently finished run.
+    #[serde(default)]
+    pub last_run: Option<RunSummary>,
+    /// Companions who survived a defeat and wait in taverns to be rehired.
+    #[serde(default)]
+    pub veteran_companions: Vec<VeteranCompanion>,
+    /// Fleet ships scattered by a defeat that can be reclaimed at ports.
+    #[serde(default)]
+    pub scattered_ships: Vec<ScatteredShip>,
+    /// Sea monsters the player has slain, in any run.
+    #[serde(default)]
+    pub slain_monsters: Vec<MonsterKind>,
+}
+
+fn default_hints_enabled() -> bool {
+    true
 }
 
 impl Default for MetaProfile {
     fn default() -> Self {
         Self {
+            version: PROFILE_VERSION,
             stats: PlayerStats::default(),
             unlocked_archetypes: vec![ArchetypeId::Default],
             legacy_wrecks: Vec::new(),
@@ -36,6 +75,13 @@ impl Default for MetaProfile {
             lifetime_captures: 0,
             runs_completed: 0,
             deaths: 0,
+            seen_hints: Vec::new(),
+            hints_enabled: true,
+            codex_unlocked: Vec::new(),
+            last_run: None,
+            veteran_companions: Vec::new(),
+            scattered_ships: Vec::new(),
+            slain_monsters: Vec::new(),
         }
     }
 }
@@ -80,6 +126,83 @@ impl MetaProfile {
         self.runs_completed += 1;
         self.recalculate_stats();
     }
+
+    /// Records a run lost at sea for the main menu's profile card.
+    pub fn record_run_end(&mut self, archetype: ArchetypeId, ship_name: String, days_survived: u32, final_gold: u32) {
+        self.last_run = Some(RunSummary {
+            archetype,
+            ship_name,
+            days_survived,
+            final_gold,
+        });
+    }
+
+    /// Returns true if the hint is enabled and has not been seen yet.
+    pub fn should_show_hint(&self, hint: HintId) -> bool {
+        self.hints_enabled && !self.seen_hints.contains(&hint)
+    }
+
+    /// Records a hint as seen. Returns false if it was already recorded.
+    pub fn mark_hint_seen(&mut self, hint: HintId) -> bool {
+        if self.seen_hints.contains(&hint) {
+            return false;
+        }
+        self.seen_hints.push(hint);
+        true
+    }
+
+    /// Returns true if the codex entry has been unlocked.
+    pub fn is_codex_unlocked(&self, id: &str) -> bool {
+        self.codex_unlocked.iter().any(|e| e == id)
+    }
+
+    /// Unlocks a codex entry. Returns false if it was already unlocked.
+    pub fn unlock_codex_entry(&mut self, id: &str) -> bool {
+        if self.is_codex_unlocked(id) {
+            return false;
+        }
+        self.codex_unlocked.push(id.to_string());
+        true
+    }
+
+    /// Records a sea monster slain. Returns false if one of its kind has fallen before.
+    pub fn record_monster_slain(&mut self, kind: MonsterKind) -> bool {
+        if self.slain_monsters.contains(&kind) {
+            return false;
+        }
+        self.slain_monsters.push(kind);
+        true
+    }
+
+    /// Decays every legacy wreck by `days` and strikes those lost. Returns
+    /// the indices they had, in ascending order.
+    pub fn decay_legacy_wrecks(&mut self, days: u32) -> Vec<usize> {
+        let mut removed = Vec::new();
+        let mut index = 0;
+        self.legacy_wrecks.retain_mut(|wreck| {
+            wreck.decay(days);
+            let lost = wreck.is_lost();
+            if lost {
+                removed.push(index);
+            }
+            index += 1;
+            !lost
+        });
+        removed
+    }
+}
+
+/// Brief record of a finished run, shown on the main menu.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// Archetype the run started with.
+    pub archetype: ArchetypeId,
+    /// Name of the player's ship at the end of the run.
+    pub ship_name: String,
+    /// In-game day on which the run ended.
+    pub days_survived: u32,
+    /// Gold held when the run ended.
+    pub final_gold: u32,
 }
 
 /// Player stats that persist and grow across runs.
@@ -136,7 +259,7 @@ impl PlayerStats {
 }
 
 /// Starting archetype identifiers.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
 pub enum ArchetypeId {
     /// Default starting character with balanced stats.
     Default,
@@ -164,16 +287,16 @@ use crate::components::ship::{FactionId, ShipType};
 use std::collections::HashMap;
 
 /// Configuration defining starting bonuses for an archetype.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArchetypeConfig {
     /// Display name shown in UI.
-    pub name: &'static str,
+    pub name: String,
     /// Short description of the archetype's playstyle.
-    pub description: &'static str,
+    pub description: String,
     /// Starting gold amount.
     pub starting_gold: u32,
-    /// Starting ship type.
-    pub ship_type: ShipType,
+    /// Starting ship class, by its id in `ShipDefinitions`.
+    pub ship: String,
     /// Faction reputation modifiers (added to base 0).
     pub faction_reputation: HashMap<FactionId, i32>,
     /// Condition required to unlock this archetype.
@@ -181,7 +304,7 @@ pub struct ArchetypeConfig {
 }
 
 /// Conditions that unlock archetypes based on lifetime stats.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum UnlockCondition {
     /// Always available from the start.
     AlwaysUnlocked,
@@ -193,12 +316,19 @@ pub enum UnlockCondition {
     QuickDeath(u32),
 }
 
-/// Global registry mapping archetype IDs to their configurations.
-#[derive(Resource, Debug)]
+/// Global registry mapping archetype IDs to their configurations, read from
+/// `assets/data/archetypes.ron` (see `ContentTable`).
+#[derive(Asset, TypePath, Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ArchetypeRegistry {
     configs: HashMap<ArchetypeId, ArchetypeConfig>,
 }
 
+impl ContentTable for ArchetypeRegistry {
+    const ASSET_PATH: &'static str = "data/archetypes.ron";
+    const NAME: &'static str = "archetype";
+}
+
 impl Default for ArchetypeRegistry {
     fn default() -> Self {
         let mut configs = HashMap::new();
@@ -207,10 +337,10 @@ impl Default for ArchetypeRegistry {
         configs.insert(
             ArchetypeId::Default,
             ArchetypeConfig {
-                name: "Freebooter",
-                description: "A balanced start for any aspiring captain.",
+                name: "Freebooter".to_string(),
+                description: "A balanced start for any aspiring captain.".to_string(),
                 starting_gold: 500,
-                ship_type: ShipType::Sloop,
+                ship: ShipType::Sloop.class_id().to_string(),
                 faction_reputation: HashMap::new(),
                 unlock_condition: UnlockCondition::AlwaysUnlocked,
             },
@@ -223,10 +353,10 @@ impl Default for ArchetypeRegistry {
         configs.insert(
             ArchetypeId::RoyalNavyCaptain,
             ArchetypeConfig {
-                name: "Royal Navy Captain",
-                description: "A disgraced officer seeking fortune on the high seas.",
+                name: "Royal Navy Captain".to_string(),
+                description: "A disgraced officer seeking fortune on the high seas.".to_string(),
                 starting_gold: 1000,
-                ship_type: ShipType::Frigate,
+                ship: ShipType::Frigate.class_id().to_string(),
                 faction_reputation: navy_rep,
                 unlock_condition: UnlockCondition::RunsCompleted(5),
             },
@@ -238,10 +368,10 @@ impl Default for ArchetypeRegistry {
         configs.insert(
             ArchetypeId::Smuggler,
             ArchetypeConfig {
-                name: "Smuggler",
-                description: "Fast ship, light pockets, and underworld connections.",
+                name: "Smuggler".to_string(),
+                description: "Fast ship, light pockets, and underworld connections.".to_string(),
                 starting_gold: 300,
-                ship_type: ShipType::Schooner,
+                ship: ShipType::Schooner.class_id().to_string(),
                 faction_reputation: smuggler_rep,
                 unlock_condition: UnlockCondition::LifetimeGold(10_000),
             },
@@ -255,10 +385,10 @@ impl Default for ArchetypeRegistry {
         configs.insert(
             ArchetypeId::Castaway,
             ArchetypeConfig {
-                name: "Castaway",
-                description: "Washed ashore with nothing. Prove your worth.",
+                name: "Castaway".to_string(),
+                description: "Washed ashore with nothing. Prove your worth.".to_string(),
                 starting_gold: 0,
-                ship_type: ShipType::Raft,
+                ship: ShipType::Raft.class_id().to_string(),
                 faction_reputation: castaway_rep,
                 unlock_condition: UnlockCondition::QuickDeath(24), // Die within 1 in-game day
             },
@@ -305,6 +435,35 @@ pub struct LegacyWreck {
     pub ship_name: String,
     /// Run number when this wreck was created.
     pub run_number: u32,
+    /// In-game days she has lain on the chart, across every run since.
+    #[serde(default)]
+    pub days_adrift: u32,
+}
+
+impl LegacyWreck {
+    /// Lets `days` of decay take their share of her gold and cargo.
+    pub fn decay(&mut self, days: u32) {
+        self.days_adrift += days;
+        self.gold = decayed(self.gold, days);
+        for (_, quantity) in &mut self.cargo {
+            *quantity = decayed(*quantity, days);
+        }
+        self.cargo.retain(|(_, quantity)| *quantity > 0);
+    }
+
+    /// Whether she has broken up or been picked clean.
+    pub fn is_lost(&self) -> bool {
+        self.days_adrift >= LEGACY_WRECK_LIFETIME_DAYS || (self.gold == 0 && self.cargo.is_empty())
+    }
+}
+
+/// Index of the legacy wreck once at `index` after those at `removed` (their
+/// indices before removal) are struck; `None` if she was one of them.
+pub fn surviving_wreck_index(index: usize, removed: &[usize]) -> Option<usize> {
+    if removed.contains(&index) {
+        return None;
+    }
+    Some(index - removed.iter().filter(|&&struck| struck < index).count())
 }
 
 /// Transient resource capturing player state at death for legacy wreck creation.
@@ -336,6 +495,7 @@ impl PlayerDeathData {
             cargo: self.cargo.clone(),
             ship_name: self.ship_name.clone(),
             run_number,
+            days_adrift: 0,
         })
     }
 
@@ -359,30 +519,42 @@ impl MetaProfile {
     /// - Linux: ~/.local/share/pirates/
     /// - Windows: %APPDATA%/pirates/
     pub fn load_from_file() -> Self {
+        Self::load_from_file_checked().0
+    }
+
+    /// A profile from an earlier release is upgraded to the current version.
+    /// Like `load_from_file`, but a profile that exists and still fails to parse is
+    /// also returned as a `PendingSave::Profile` for the compatibility dialog.
+    /// The unreadable file is copied to `profile.json.bak` first, so saving
+    /// the fresh profile over it loses nothing.
+    pub fn load_from_file_checked() -> (Self, Option<PendingSave>) {
         let Some(path) = Self::get_save_path() else {
             warn!("Could not determine save directory, using default profile");
-            return Self::default();
+            return (Self::default(), None);
         };
 
         if !path.exists() {
             info!("No existing profile found, creating fresh profile");
-            return Self::default();
+            return (Self::default(), None);
         }
 
         match std::fs::read_to_string(&path) {
-            Ok(contents) => match serde_json::from_str(&contents) {
+            Ok(contents) => match upgrade_profile_json(&contents) {
                 Ok(profile) => {
                     info!("Loaded profile from {:?}", path);
-                    profile
+                    (profile, None)
                 }
                 Err(e) => {
                     error!("Failed to parse profile file: {}", e);
-                    Self::default()
+                    if let Err(e) = std::fs::copy(&path, path.with_extension("json.bak")) {
+                        error!("Failed to back up unreadable profile: {}", e);
+                    }
+                    (Self::default(), Some(PendingSave::Profile { contents, error: e }))
                 }
             },
             Err(e) => {
                 error!("Failed to read profile file: {}", e);
-                Self::default()
+                (Self::default(), None)
             }
         }
     }
@@ -435,3 +607,80 @@ impl MetaProfile {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_archetypes_match_builtin() {
+        let shipped = ArchetypeRegistry::from_ron(include_str!("../../assets/data/archetypes.ron")).unwrap();
+        assert_eq!(shipped, ArchetypeRegistry::default());
+    }
+
+    #[test]
+    fn test_hint_marked_seen_once() {
+        let mut profile = MetaProfile::default();
+        assert!(profile.should_show_hint(HintId::FirstStorm));
+        assert!(profile.mark_hint_seen(HintId::FirstStorm));
+        assert!(!profile.mark_hint_seen(HintId::FirstStorm));
+        assert!(!profile.should_show_hint(HintId::FirstStorm));
+        assert!(profile.should_show_hint(HintId::FirstBoarding));
+    }
+
+    #[test]
+    fn test_hints_disabled_suppresses_all() {
+        let mut profile = MetaProfile::default();
+        profile.hints_enabled = false;
+        for &hint in HintId::all() {
+            assert!(!profile.should_show_hint(hint));
+        }
+    }
+
+    #[test]
+    fn test_profile_without_hint_fields_loads_with_defaults() {
+        let json = r#"{
+            "stats": { "charisma": 1, "navigation": 1, "logistics": 1 },
+            "unlocked_archetypes": ["Default"],
+            "legacy_wrecks": [],
+            "lifetime_gold": 0,
+            "lifetime_captures": 0,
+            "runs_completed": 0,
+            "deaths": 0
+        }"#;
+        let profile: MetaProfile = serde_json::from_str(json).expect("legacy profile should parse");
+        assert!(profile.seen_hints.is_empty());
+        assert!(profile.hints_enabled);
+        assert!(profile.codex_unlocked.is_empty());
+        assert!(profile.last_run.is_none());
+        assert!(profile.slain_monsters.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_wrecks_decay_and_survivors_keep_their_markers() {
+        let wreck = |ship_name: &str, gold: u32, days_adrift: u32| LegacyWreck {
+            position: IVec2::ZERO,
+            gold,
+            cargo: vec![("Rum".to_string(), 10)],
+            ship_name: ship_name.to_string(),
+            run_number: 1,
+            days_adrift,
+        };
+        let mut profile = MetaProfile::default();
+        profile.legacy_wrecks = vec![
+            wreck("Old Hulk", 500, LEGACY_WRECK_LIFETIME_DAYS - 1),
+            wreck("Dauntless", 100, 0),
+            wreck("Swift", 50, LEGACY_WRECK_LIFETIME_DAYS - 1),
+        ];
+
+        let removed = profile.decay_legacy_wrecks(1);
+        assert_eq!(removed, vec![0, 2]);
+        assert_eq!(profile.legacy_wrecks.len(), 1);
+        assert_eq!(profile.legacy_wrecks[0].gold, 90);
+        assert_eq!(profile.legacy_wrecks[0].cargo, vec![("Rum".to_string(), 9)]);
+
+        assert_eq!(surviving_wreck_index(0, &removed), None);
+        assert_eq!(surviving_wreck_index(1, &removed), Some(0));
+        assert_eq!(surviving_wreck_index(2, &removed), None);
+    }
+}
@@ -22,6 +22,105 @@ pub use fleet::*;
 pub mod meta_profile;
 pub use meta_profile::*;
 
+pub mod hints;
+pub use hints::*;
+
+pub mod codex;
+pub use codex::*;
+
+pub mod run_settings;
+pub use run_settings::*;
+
+pub mod news;
+pub use news::*;
+
+pub mod journal;
+pub use journal::*;
+
+pub mod spoils;
+pub use spoils::*;
+
+pub mod defeat;
+pub use defeat::*;
+
+pub mod upgrades;
+pub use upgrades::*;
+
+pub mod trade_lanes;
+pub use trade_lanes::*;
+
+pub mod storms;
+pub use storms::*;
+
+pub mod music;
+pub use music::*;
+
+pub mod tactical;
+pub use tactical::*;
+
+pub mod bounties;
+pub use bounties::*;
+
+pub mod battle_stats;
+pub use battle_stats::*;
+
+pub mod wreck_sites;
+pub use wreck_sites::*;
+
+pub mod ai_activation;
+pub use ai_activation::*;
+
+pub mod picking;
+pub use picking::*;
+
+pub mod save_compat;
+pub use save_compat::*;
+
+pub mod ui_layout;
+pub use ui_layout::*;
+
+pub mod patrols;
+pub use patrols::*;
+
+pub mod privateering;
+pub use privateering::*;
+
+pub mod crew;
+pub use crew::*;
+
+pub mod ship_market;
+pub use ship_market::*;
+
+pub mod balance;
+pub use balance::*;
+
+pub mod encounter_templates;
+pub use encounter_templates::*;
+
+pub mod supernatural;
+pub use supernatural::*;
+
+pub mod boarding;
+pub use boarding::*;
+
+pub mod market;
+pub use market::*;
+
+pub mod dock;
+pub use dock::*;
+
+pub mod port_markets;
+pub use port_markets::*;
+
+pub mod rest;
+pub use rest::*;
+
+pub mod mooring;
+pub use mooring::*;
+
+pub mod run_state;
+pub use run_state::*;
+
 pub mod landmass;
 pub use landmass::*;
 
@@ -36,3 +135,53 @@ pub use stippling_material::*;
 pub mod cli;
 pub use cli::*;
 
+pub mod port_events;
+pub use port_events::*;
+
+pub mod pursuit;
+pub use pursuit::*;
+
+pub mod day_night;
+pub use day_night::*;
+
+pub mod harbor_approach;
+pub use harbor_approach::*;
+
+pub mod season;
+pub use season::*;
+
+pub mod battle_replay;
+pub use battle_replay::*;
+
+pub mod sail_ahead;
+pub use sail_ahead::*;
+
+pub mod wind_shadow;
+pub use wind_shadow::*;
+
+pub mod dig_sites;
+pub use dig_sites::*;
+
+pub mod sea_monsters;
+pub use sea_monsters::*;
+
+pub mod sea_events;
+pub use sea_events::*;
+
+pub mod content;
+pub use content::*;
+
+pub mod goods_table;
+pub use goods_table::*;
+
+pub mod contract_rewards;
+pub use contract_rewards::*;
+
+pub mod pacing;
+pub use pacing::*;
+
+pub mod ship_definitions;
+pub use ship_definitions::*;
+
+pub mod settings;
+pub use settings::*;
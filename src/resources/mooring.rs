@@ -0,0 +1,128 @@
+//! Ships left at moorings in port.
+//!
+//! A fleet ship can be moored at a port for a fee, leaving the travelling
+//! fleet, and taken back into it on a later visit to the same port. Port
+//! entities are respawned on every visit to the High Seas, so moorings are
+//! keyed by `port_key`, and the port's name is
+//! remembered as it was when the ship was moored. The harbor keeps moored
+//! hulls in slow repair.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::resources::ShipData;
+
+/// One-off fee to moor a ship (gold).
+pub const MOORING_FEE: u32 = 25;
+/// Hull restored to each moored ship every hour.
+pub const MOORED_REPAIR_PER_HOUR: f32 = 1.0;
+
+/// A ship lying at a mooring.
+#[derive(Clone, Debug, Reflect)]
+pub struct MooredShip {
+    pub ship: ShipData,
+    /// Name of the port when she was moored.
+    pub port_name: String,
+    /// Day she was moored (`WorldClock::day`).
+    pub moored_on: u32,
+}
+
+/// Every ship the player has moored, by port.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct MooredShips {
+    pub ports: HashMap<IVec2, Vec<MooredShip>>,
+}
+
+impl MooredShips {
+    pub fn moor(&mut self, key: IVec2, ship: ShipData, port_name: &str, day: u32) {
+        self.ports.entry(key).or_default().push(MooredShip {
+            ship,
+            port_name: port_name.to_string(),
+            moored_on: day,
+        });
+    }
+
+    /// Takes the `index`th ship moored at `key` off her mooring.
+    pub fn retrieve(&mut self, key: IVec2, index: usize) -> Option<ShipData> {
+        let moored = self.ports.get_mut(&key)?;
+        if index >= moored.len() {
+            return None;
+        }
+        let ship = moored.remove(index).ship;
+        if moored.is_empty() {
+            self.ports.remove(&key);
+        }
+        Some(ship)
+    }
+
+    /// Ships moored at `key`.
+    pub fn at(&self, key: IVec2) -> &[MooredShip] {
+        self.ports.get(&key).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every moored ship with her port's key, grouped by port in a stable order.
+    pub fn all(&self) -> Vec<(IVec2, &MooredShip)> {
+        let mut keys: Vec<&IVec2> = self.ports.keys().collect();
+        keys.sort_by_key(|key| (key.x, key.y));
+        keys.into_iter()
+            .flat_map(|key| self.ports[key].iter().map(move |moored| (*key, moored)))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ports.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ports.is_empty()
+    }
+
+    /// An hour's work by the harbor on every moored hull.
+    pub fn repair_hour(&mut self) {
+        for moored in self.ports.values_mut().flatten() {
+            let ship = &mut moored.ship;
+            ship.hull_health = (ship.hull_health + MOORED_REPAIR_PER_HOUR).min(ship.max_hull_health);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.ports.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::port_key;
+
+    fn ship(name: &str, hull_health: f32) -> ShipData {
+        ShipData { name: name.to_string(), hull_health, ..default() }
+    }
+
+    #[test]
+    fn test_moor_and_retrieve() {
+        let mut moored = MooredShips::default();
+        let here = port_key(Vec2::new(96.4, -31.8));
+        moored.moor(here, ship("Swift", 60.0), "Port Royal", 3);
+        moored.moor(IVec2::new(900, 0), ship("Gull", 80.0), "Tortuga", 4);
+        assert_eq!(moored.len(), 2);
+        assert_eq!(moored.at(here)[0].port_name, "Port Royal");
+
+        // Only from the port she is moored at
+        assert!(moored.retrieve(IVec2::ZERO, 0).is_none());
+        assert_eq!(moored.retrieve(here, 0).map(|ship| ship.name), Some("Swift".to_string()));
+        assert!(moored.at(here).is_empty());
+        assert_eq!(moored.all().len(), 1);
+    }
+
+    #[test]
+    fn test_moored_ships_mend_slowly() {
+        let mut moored = MooredShips::default();
+        moored.moor(IVec2::ZERO, ship("Swift", 99.5), "Port Royal", 1);
+        moored.moor(IVec2::ZERO, ship("Gull", 50.0), "Port Royal", 1);
+        moored.repair_hour();
+        let hulls: Vec<f32> = moored.at(IVec2::ZERO).iter().map(|m| m.ship.hull_health).collect();
+        assert_eq!(hulls, vec![100.0, 50.0 + MOORED_REPAIR_PER_HOUR]);
+    }
+}
@@ -0,0 +1,222 @@
+//! Adaptive music: which stems should be playing, and how loud.
+//!
+//! The soundtrack is a set of looping stems that all play at once. Rather than
+//! switching tracks, the game evaluates how intense the moment is and fades
+//! each stem toward a target volume (see `MusicMix::evaluate`).
+
+use bevy::prelude::*;
+
+/// Seconds for a stem to fade from silent to full volume.
+pub const STEM_FADE_SECONDS: f32 = 3.0;
+/// Hostile ships at which combat music reaches full intensity.
+pub const FULL_INTENSITY_HOSTILES: usize = 4;
+
+/// One looping layer of the soundtrack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicStem {
+    /// The sea shanty melody under everything at sea.
+    Shanty,
+    /// Harbor fiddle and accordion while in port.
+    Harbor,
+    /// Hushed strings for night sailing.
+    Night,
+    /// Low rumbling drones while a storm is near.
+    Storm,
+    /// Drums that come in as soon as a fight starts.
+    Drums,
+    /// Brass that swells as the battle grows.
+    Brass,
+    /// Strings that rise when the player's ship is in trouble.
+    Tension,
+}
+
+impl MusicStem {
+    pub const ALL: [MusicStem; 7] = [
+        MusicStem::Shanty,
+        MusicStem::Harbor,
+        MusicStem::Night,
+        MusicStem::Storm,
+        MusicStem::Drums,
+        MusicStem::Brass,
+        MusicStem::Tension,
+    ];
+
+    /// Asset path of the stem's loop.
+    pub fn path(&self) -> &'static str {
+        match self {
+            MusicStem::Shanty => "audio/music/stems/shanty.ogg",
+            MusicStem::Harbor => "audio/music/stems/harbor.ogg",
+            MusicStem::Night => "audio/music/stems/night.ogg",
+            MusicStem::Storm => "audio/music/stems/storm.ogg",
+            MusicStem::Drums => "audio/music/stems/drums.ogg",
+            MusicStem::Brass => "audio/music/stems/brass.ogg",
+            MusicStem::Tension => "audio/music/stems/tension.ogg",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Which part of the game the music is scoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MusicScene {
+    #[default]
+    Silent,
+    Port,
+    HighSeas,
+    Combat,
+}
+
+/// What the intensity evaluation looks at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MusicInputs {
+    pub scene: MusicScene,
+    /// Hostile ships still fighting.
+    pub hostiles: usize,
+    /// Player hull as a fraction of maximum.
+    pub player_health: f32,
+    /// 0.0 far from any storm, 1.0 inside one.
+    pub storm: f32,
+    /// 0.0 at noon, 1.0 at midnight (`clock_darkness`).
+    pub night: f32,
+}
+
+impl Default for MusicInputs {
+    fn default() -> Self {
+        Self {
+            scene: MusicScene::Silent,
+            hostiles: 0,
+            player_health: 1.0,
+            storm: 0.0,
+            night: 0.0,
+        }
+    }
+}
+
+/// Target and current stem volumes.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MusicMix {
+    /// Overall intensity of the moment, 0.0 (calm) to 1.0 (desperate battle).
+    pub intensity: f32,
+    targets: [f32; MusicStem::ALL.len()],
+    volumes: [f32; MusicStem::ALL.len()],
+}
+
+impl MusicMix {
+    /// Sets stem targets from the current game situation.
+    pub fn evaluate(&mut self, inputs: &MusicInputs) {
+        self.targets = [0.0; MusicStem::ALL.len()];
+        let danger = 1.0 - inputs.player_health.clamp(0.0, 1.0);
+
+        match inputs.scene {
+            MusicScene::Silent => self.intensity = 0.0,
+            MusicScene::Port => {
+                self.intensity = 0.0;
+                self.set_target(MusicStem::Harbor, 1.0);
+            }
+            MusicScene::HighSeas => {
+                let storm = inputs.storm.clamp(0.0, 1.0);
+                let night = inputs.night.clamp(0.0, 1.0);
+                self.intensity = storm * 0.5;
+                // Night and storms both push the shanty into the background
+                self.set_target(MusicStem::Shanty, 1.0 - 0.6 * night.max(storm));
+                self.set_target(MusicStem::Night, night * (1.0 - storm));
+                self.set_target(MusicStem::Storm, storm);
+            }
+            MusicScene::Combat => {
+                let numbers = (inputs.hostiles as f32 / FULL_INTENSITY_HOSTILES as f32).min(1.0);
+                self.intensity = if inputs.hostiles == 0 {
+                    0.0
+                } else {
+                    (0.3 + 0.4 * numbers + 0.3 * danger).min(1.0)
+                };
+                self.set_target(MusicStem::Shanty, 0.4 * (1.0 - self.intensity));
+                self.set_target(MusicStem::Drums, if inputs.hostiles > 0 { 1.0 } else { 0.3 });
+                self.set_target(MusicStem::Brass, numbers);
+                self.set_target(MusicStem::Tension, danger);
+            }
+        }
+    }
+
+    /// Fades every stem toward its target over `STEM_FADE_SECONDS`.
+    pub fn crossfade(&mut self, delta_secs: f32) {
+        let step = delta_secs / STEM_FADE_SECONDS;
+        for (volume, target) in self.volumes.iter_mut().zip(self.targets) {
+            *volume += (target - *volume).clamp(-step, step);
+        }
+    }
+
+    pub fn target(&self, stem: MusicStem) -> f32 {
+        self.targets[stem.index()]
+    }
+
+    pub fn volume(&self, stem: MusicStem) -> f32 {
+        self.volumes[stem.index()]
+    }
+
+    fn set_target(&mut self, stem: MusicStem, volume: f32) {
+        self.targets[stem.index()] = volume.clamp(0.0, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::night_darkness;
+
+    fn combat(hostiles: usize, player_health: f32) -> MusicMix {
+        let mut mix = MusicMix::default();
+        mix.evaluate(&MusicInputs {
+            scene: MusicScene::Combat,
+            hostiles,
+            player_health,
+            ..default()
+        });
+        mix
+    }
+
+    #[test]
+    fn test_combat_intensity_scales_with_hostiles_and_damage() {
+        let skirmish = combat(1, 1.0);
+        let battle = combat(4, 1.0);
+        let desperate = combat(4, 0.2);
+        assert!(battle.intensity > skirmish.intensity);
+        assert!(desperate.intensity > battle.intensity);
+        assert!(battle.target(MusicStem::Brass) > skirmish.target(MusicStem::Brass));
+        assert_eq!(battle.target(MusicStem::Tension), 0.0);
+        assert!(desperate.target(MusicStem::Tension) > 0.5);
+    }
+
+    #[test]
+    fn test_night_and_storm_shift_high_seas_mix() {
+        let mut mix = MusicMix::default();
+        mix.evaluate(&MusicInputs { scene: MusicScene::HighSeas, night: night_darkness(12, 0), ..default() });
+        assert_eq!(mix.target(MusicStem::Shanty), 1.0);
+        assert_eq!(mix.target(MusicStem::Night), 0.0);
+
+        mix.evaluate(&MusicInputs { scene: MusicScene::HighSeas, night: night_darkness(0, 0), ..default() });
+        assert_eq!(mix.target(MusicStem::Night), 1.0);
+        assert!(mix.target(MusicStem::Shanty) < 1.0);
+
+        mix.evaluate(&MusicInputs { scene: MusicScene::HighSeas, night: 1.0, storm: 1.0, ..default() });
+        assert_eq!(mix.target(MusicStem::Storm), 1.0);
+        assert_eq!(mix.target(MusicStem::Night), 0.0);
+    }
+
+    #[test]
+    fn test_crossfade_ramps_instead_of_switching() {
+        let mut mix = MusicMix::default();
+        mix.evaluate(&MusicInputs { scene: MusicScene::Port, ..default() });
+        mix.crossfade(STEM_FADE_SECONDS / 2.0);
+        assert!((mix.volume(MusicStem::Harbor) - 0.5).abs() < 1e-5);
+        mix.crossfade(STEM_FADE_SECONDS);
+        assert_eq!(mix.volume(MusicStem::Harbor), 1.0);
+
+        mix.evaluate(&MusicInputs { scene: MusicScene::HighSeas, ..default() });
+        mix.crossfade(STEM_FADE_SECONDS / 4.0);
+        assert!(mix.volume(MusicStem::Harbor) > 0.0);
+        assert!(mix.volume(MusicStem::Shanty) > 0.0);
+    }
+}
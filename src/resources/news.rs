@@ -0,0 +1,134 @@
+//! Harbor gossip: a rolling feed of world simulation happenings.
+//!
+//! Filled from `WorldNewsEvent`s sent by the faction and economy systems and
+//! shown as a ticker in the port UI.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+/// Most recent headlines kept in the feed.
+pub const MAX_NEWS_ITEMS: usize = 12;
+
+/// What a headline is about, used for its ticker glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NewsCategory {
+    /// Trade routes and merchant shipping.
+    Trade,
+    /// Price spikes and gluts.
+    Market,
+    /// Naval deployments and hostilities.
+    Military,
+    /// Notorious captains sighted.
+    Sighting,
+    /// Storms forming and blowing out.
+    Weather,
+    /// Festivals and strikes in port.
+    Harbor,
+}
+
+impl NewsCategory {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            NewsCategory::Trade => "⚓",
+            NewsCategory::Market => "⚖",
+            NewsCategory::Military => "⚔",
+            NewsCategory::Sighting => "☠",
+            NewsCategory::Weather => "🌀",
+            NewsCategory::Harbor => "⚑",
+        }
+    }
+}
+
+/// A single headline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewsItem {
+    /// In-game day the news broke.
+    pub day: u32,
+    pub category: NewsCategory,
+    pub headline: String,
+}
+
+/// Recent headlines, newest last.
+#[derive(Resource, Debug, Default)]
+pub struct NewsFeed {
+    items: VecDeque<NewsItem>,
+}
+
+impl NewsFeed {
+    /// Adds a headline, dropping the oldest beyond `MAX_NEWS_ITEMS`.
+    /// The same headline is not repeated on the same day.
+    pub fn push(&mut self, day: u32, category: NewsCategory, headline: impl Into<String>) {
+        let headline = headline.into();
+        if self.items.iter().any(|item| item.day == day && item.headline == headline) {
+            return;
+        }
+        self.items.push_back(NewsItem { day, category, headline });
+        while self.items.len() > MAX_NEWS_ITEMS {
+            self.items.pop_front();
+        }
+    }
+
+    /// Headlines from newest to oldest.
+    pub fn newest_first(&self) -> impl Iterator<Item = &NewsItem> {
+        self.items.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// All headlines joined into one ticker line, newest first.
+    pub fn ticker_text(&self) -> String {
+        self.newest_first()
+            .map(|item| format!("{} {}", item.category.icon(), item.headline))
+            .collect::<Vec<_>>()
+            .join("     ")
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_caps_length() {
+        let mut feed = NewsFeed::default();
+        for i in 0..(MAX_NEWS_ITEMS + 5) {
+            feed.push(1, NewsCategory::Trade, format!("Headline {}", i));
+        }
+        assert_eq!(feed.len(), MAX_NEWS_ITEMS);
+        assert_eq!(
+            feed.newest_first().next().unwrap().headline,
+            format!("Headline {}", MAX_NEWS_ITEMS + 4)
+        );
+    }
+
+    #[test]
+    fn test_same_day_duplicates_are_dropped() {
+        let mut feed = NewsFeed::default();
+        feed.push(3, NewsCategory::Market, "Rum is dear");
+        feed.push(3, NewsCategory::Market, "Rum is dear");
+        assert_eq!(feed.len(), 1);
+        feed.push(4, NewsCategory::Market, "Rum is dear");
+        assert_eq!(feed.len(), 2);
+    }
+
+    #[test]
+    fn test_ticker_text_newest_first() {
+        let mut feed = NewsFeed::default();
+        feed.push(1, NewsCategory::Trade, "old");
+        feed.push(2, NewsCategory::Sighting, "new");
+        let text = feed.ticker_text();
+        assert!(text.find("new").unwrap() < text.find("old").unwrap());
+        assert!(text.starts_with(NewsCategory::Sighting.icon()));
+    }
+}
@@ -0,0 +1,117 @@
+//! Encounter pacing: how much danger the seas may throw at the player yet.
+//!
+//! A new captain should not run into a frigate on the first morning. The
+//! `PacingDirector` holds a threat between 0 (a green captain in a lone hull)
+//! and 1 (the seas at their worst), rising with days at sea after a grace
+//! period and with the player's strength: fleet ships, flagship class and
+//! fitted upgrades. How fast it rises is set per difficulty by
+//! `PacingBalance`. The threat caps the heaviest class of ship that will seek
+//! the player out or turn up in a battle, and makes the enemies met early
+//! likelier to run than fight (see `plugins::pacing`).
+
+use bevy::prelude::*;
+
+use crate::components::ShipType;
+use crate::resources::PacingCurve;
+
+/// How heavy a class of ship is: 0 for a raft up to 3 for a frigate.
+pub fn class_rank(ship_type: ShipType) -> u32 {
+    match ship_type {
+        ShipType::Raft => 0,
+        ShipType::Sloop => 1,
+        ShipType::Schooner => 2,
+        ShipType::Frigate => 3,
+    }
+}
+
+/// What the player brings to a fight.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PlayerStrength {
+    /// Ships in the fleet besides the flagship.
+    pub fleet_ships: usize,
+    /// Class of the flagship.
+    pub flagship: ShipType,
+    /// Upgrades fitted across the flagship and fleet.
+    pub upgrades: usize,
+}
+
+impl PlayerStrength {
+    /// Threat the player's strength warrants under `curve`; a flagship's
+    /// every class above a sloop counts as another ship.
+    pub fn threat(&self, curve: &PacingCurve) -> f32 {
+        let ships = self.fleet_ships as f32 + class_rank(self.flagship).saturating_sub(1) as f32;
+        ships * curve.per_ship + self.upgrades as f32 * curve.per_upgrade
+    }
+}
+
+/// Threat after `days_at_sea` for a player of `strength`, under `curve`.
+pub fn pacing_threat(curve: &PacingCurve, days_at_sea: f32, strength: PlayerStrength) -> f32 {
+    let seasoned = if curve.ramp_days > 0.0 {
+        ((days_at_sea - curve.grace_days) / curve.ramp_days).clamp(0.0, 1.0)
+    } else if days_at_sea >= curve.grace_days {
+        1.0
+    } else {
+        0.0
+    };
+    (seasoned + strength.threat(curve)).clamp(0.0, 1.0)
+}
+
+/// The danger the seas may throw at the player right now.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub struct PacingDirector {
+    /// 0 for the gentlest seas, 1 for the worst.
+    pub threat: f32,
+    /// Chance an enemy met now turns tail instead of fighting.
+    pub flee_chance: f32,
+}
+
+impl PacingDirector {
+    /// The director's view of a player of `strength` after `days_at_sea`.
+    pub fn assess(curve: &PacingCurve, days_at_sea: f32, strength: PlayerStrength) -> Self {
+        let threat = pacing_threat(curve, days_at_sea, strength);
+        Self {
+            threat,
+            flee_chance: curve.flee_chance * (1.0 - threat),
+        }
+    }
+
+    /// Heaviest class rank let near the player: sloops below half threat,
+    /// schooners below full, frigates at full.
+    pub fn heaviest_rank(&self) -> u32 {
+        (1.0 + self.threat.clamp(0.0, 1.0) * 2.0).floor() as u32
+    }
+
+    /// Whether a ship of `ship_type` may seek the player out.
+    pub fn allows(&self, ship_type: ShipType) -> bool {
+        class_rank(ship_type) <= self.heaviest_rank()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::PacingBalance;
+
+    #[test]
+    fn test_threat_ramps_with_days_and_strength() {
+        let balance = PacingBalance::default();
+        let green = PlayerStrength { flagship: ShipType::Sloop, ..default() };
+
+        // A new captain meets nothing heavier than a sloop, and often one that runs
+        let first_day = PacingDirector::assess(&balance.normal, 0.0, green);
+        assert_eq!(first_day.threat, 0.0);
+        assert!(first_day.allows(ShipType::Sloop) && !first_day.allows(ShipType::Schooner));
+        assert_eq!(first_day.flee_chance, balance.normal.flee_chance);
+
+        // The seas open up with time at sea, sooner on harder difficulties
+        let normal = PacingDirector::assess(&balance.normal, 10.0, green);
+        let hard = PacingDirector::assess(&balance.hard, 10.0, green);
+        assert!(normal.allows(ShipType::Schooner) && !normal.allows(ShipType::Frigate));
+        assert!(hard.allows(ShipType::Frigate));
+        assert_eq!(hard.flee_chance, 0.0);
+
+        // So does a stronger fleet: a frigate flagship and two consorts
+        let strong = PlayerStrength { fleet_ships: 2, flagship: ShipType::Frigate, upgrades: 0 };
+        assert!(PacingDirector::assess(&balance.normal, 0.0, strong).allows(ShipType::Frigate));
+    }
+}
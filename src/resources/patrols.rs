@@ -0,0 +1,92 @@
+//! Border patrol contracts for navy-aligned players.
+//!
+//! A nation that trusts the player (`PATROL_MIN_REPUTATION`) posts a patrol
+//! contract at its ports: sail the waters around the port, hail merchants
+//! through the spyglass, and capture or sink the smugglers among them before
+//! the deadline. An inspection is only a read of the ship's papers and hold,
+//! so it can be wrong both ways; attacking a merchant who turns out honest
+//! costs reputation and a fine.
+
+use bevy::prelude::*;
+
+use crate::components::FactionId;
+
+/// Reputation with a nation needed before it offers patrol contracts.
+pub const PATROL_MIN_REPUTATION: i32 = 25;
+/// Radius of the waters a patrol covers, around the issuing port (world units).
+pub const PATROL_RADIUS: f32 = 1200.0;
+/// Time allowed to finish a patrol (3 in-game days).
+pub const PATROL_DURATION_TICKS: u32 = 3 * 24 * 60;
+/// Gold paid per smuggler the contract asks for, unless `ContractRewards` says otherwise.
+pub const PATROL_REWARD_PER_SMUGGLER: u32 = 250;
+/// Reputation gained with the issuer on completing a patrol.
+pub const PATROL_REPUTATION_REWARD: i32 = 10;
+/// Merchants found in a patrol area each time the player puts to sea.
+pub const PATROL_TRAFFIC: usize = 5;
+/// Of those, how many are running contraband.
+pub const PATROL_SMUGGLERS: usize = 2;
+/// Chance a nation's newly commissioned merchantman runs contraband.
+pub const MERCHANT_SMUGGLER_CHANCE: f64 = 0.1;
+/// Spyglass range for hailing a ship (world units).
+pub const HAIL_RANGE: f32 = 400.0;
+/// Chance an inspection of a smuggler finds the contraband.
+pub const SMUGGLER_DETECTION_CHANCE: f32 = 0.8;
+/// Chance an inspection wrongly flags an honest merchant.
+pub const FALSE_ALARM_CHANCE: f32 = 0.15;
+/// Reputation lost with an honest merchant's nation for attacking it.
+pub const FALSE_POSITIVE_REPUTATION_PENALTY: i32 = 15;
+/// Fine for attacking an honest merchant.
+pub const FALSE_POSITIVE_FINE: u32 = 150;
+
+/// Whether a nation at `reputation` with the player offers patrol contracts.
+pub fn offers_patrols(faction: FactionId, reputation: i32) -> bool {
+    faction != FactionId::Pirates && reputation >= PATROL_MIN_REPUTATION
+}
+
+/// Whether an inspection reads a ship as smuggling, given a uniform `roll` in [0, 1).
+pub fn inspection_suspicious(is_smuggler: bool, roll: f32) -> bool {
+    if is_smuggler {
+        roll < SMUGGLER_DETECTION_CHANCE
+    } else {
+        roll < FALSE_ALARM_CHANCE
+    }
+}
+
+/// A ship the player chose to intercept, awaiting the battle's outcome.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interception {
+    /// The High Seas ship engaged.
+    pub ship: Entity,
+    /// Flag the ship flew.
+    pub faction: FactionId,
+    /// Whether it really was a smuggler.
+    pub smuggler: bool,
+    /// Where the interception began.
+    pub position: Vec2,
+}
+
+/// The interception in progress, if the current battle is one.
+#[derive(Resource, Debug, Default)]
+pub struct PatrolEngagement {
+    pub interception: Option<Interception>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_trusting_nations_offer_patrols() {
+        assert!(offers_patrols(FactionId::NationA, PATROL_MIN_REPUTATION));
+        assert!(!offers_patrols(FactionId::NationA, PATROL_MIN_REPUTATION - 1));
+        assert!(!offers_patrols(FactionId::Pirates, 100));
+    }
+
+    #[test]
+    fn test_inspection_can_err_both_ways() {
+        assert!(inspection_suspicious(true, 0.0));
+        assert!(!inspection_suspicious(true, SMUGGLER_DETECTION_CHANCE));
+        assert!(inspection_suspicious(false, 0.0));
+        assert!(!inspection_suspicious(false, FALSE_ALARM_CHANCE));
+    }
+}
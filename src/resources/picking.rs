@@ -0,0 +1,143 @@
+//! Click picking on the High Seas map.
+//!
+//! Every visible `Pickable` is indexed each frame in a spatial hash with its
+//! position and click radius. A click is resolved against the index to the
+//! object under the cursor, or to open water or land when there is none.
+
+use bevy::prelude::*;
+
+use crate::components::picking::PickKind;
+use crate::utils::spatial_hash::SpatialHash;
+
+/// Hash cell size; about the largest pick radius, so a click checks few cells.
+pub const PICK_CELL_SIZE: f32 = 64.0;
+
+/// What a click on the map landed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickTarget {
+    Ship(Entity),
+    Port(Entity),
+    Wreck(Entity),
+    Water,
+    Land,
+}
+
+impl PickTarget {
+    /// The picked object, if the click hit one.
+    pub fn entity(self) -> Option<Entity> {
+        match self {
+            PickTarget::Ship(entity) | PickTarget::Port(entity) | PickTarget::Wreck(entity) => Some(entity),
+            PickTarget::Water | PickTarget::Land => None,
+        }
+    }
+}
+
+/// An indexed pickable object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickCandidate {
+    pub entity: Entity,
+    pub kind: PickKind,
+    pub position: Vec2,
+    pub radius: f32,
+}
+
+impl PickCandidate {
+    pub fn target(&self) -> PickTarget {
+        match self.kind {
+            PickKind::Ship => PickTarget::Ship(self.entity),
+            PickKind::Port => PickTarget::Port(self.entity),
+            PickKind::Wreck => PickTarget::Wreck(self.entity),
+        }
+    }
+}
+
+/// Spatial index of everything clickable on the High Seas.
+#[derive(Resource, Debug)]
+pub struct PickIndex {
+    hash: SpatialHash<PickCandidate>,
+    /// Largest radius indexed, so queries reach every candidate that could be hit.
+    max_radius: f32,
+}
+
+impl Default for PickIndex {
+    fn default() -> Self {
+        Self {
+            hash: SpatialHash::new(PICK_CELL_SIZE),
+            max_radius: 0.0,
+        }
+    }
+}
+
+impl PickIndex {
+    pub fn clear(&mut self) {
+        self.hash.clear();
+        self.max_radius = 0.0;
+    }
+
+    pub fn insert(&mut self, candidate: PickCandidate) {
+        self.max_radius = self.max_radius.max(candidate.radius);
+        self.hash.insert(candidate.position, candidate);
+    }
+
+    /// The object under `point`. Where radii overlap, the one whose centre
+    /// is nearest (relative to its radius) wins, so a ship anchored off a
+    /// port can still be clicked.
+    pub fn pick(&self, point: Vec2) -> Option<PickCandidate> {
+        self.hash
+            .query(point, self.max_radius)
+            .into_iter()
+            .filter(|candidate| candidate.position.distance(point) <= candidate.radius)
+            .min_by(|a, b| {
+                let reach = |c: &PickCandidate| c.position.distance(point) / c.radius;
+                reach(a).total_cmp(&reach(b))
+            })
+            .copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.hash.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hash.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(index: u32, kind: PickKind, position: Vec2) -> PickCandidate {
+        PickCandidate {
+            entity: Entity::from_raw(index),
+            kind,
+            position,
+            radius: kind.radius(),
+        }
+    }
+
+    #[test]
+    fn test_pick_respects_radius() {
+        let mut index = PickIndex::default();
+        index.insert(candidate(1, PickKind::Wreck, Vec2::new(100.0, 100.0)));
+
+        let hit = index.pick(Vec2::new(110.0, 100.0)).unwrap();
+        assert_eq!(hit.target(), PickTarget::Wreck(Entity::from_raw(1)));
+        // Same hash cell, but outside the wreck's radius
+        assert!(index.pick(Vec2::new(100.0 + PickKind::Wreck.radius() + 1.0, 100.0)).is_none());
+    }
+
+    #[test]
+    fn test_pick_prefers_object_under_cursor() {
+        let mut index = PickIndex::default();
+        index.insert(candidate(1, PickKind::Port, Vec2::ZERO));
+        index.insert(candidate(2, PickKind::Ship, Vec2::new(40.0, 0.0)));
+
+        assert_eq!(index.pick(Vec2::new(38.0, 0.0)).unwrap().entity, Entity::from_raw(2));
+        assert_eq!(index.pick(Vec2::new(5.0, 0.0)).unwrap().entity, Entity::from_raw(1));
+
+        index.clear();
+        assert!(index.is_empty());
+        assert!(index.pick(Vec2::ZERO).is_none());
+    }
+}
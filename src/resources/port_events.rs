@@ -0,0 +1,220 @@
+//! Festivals and dockworkers' strikes in individual ports.
+//!
+//! Every morning a port may declare a festival or have its docks walk out
+//! (see `PortEventsPlugin`). A festival makes the tavern cheaper, marks down
+//! the goods in the market and draws a guest to the tavern who is found
+//! nowhere else. A strike closes the market and the repair yard until it is
+//! settled. Either lasts a few days.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::components::companion::CompanionRole;
+use crate::components::port_key;
+
+/// Share of their usual price tavern intel, sailors and companions cost at a festival.
+pub const FESTIVAL_TAVERN_RATE: f32 = 0.5;
+/// Share of their listed price goods are sold for at a festival.
+pub const FESTIVAL_GOODS_RATE: f32 = 0.85;
+
+/// Guests who come to town for a festival, one per port. They can only be
+/// recruited while it lasts.
+pub const FESTIVAL_GUESTS: [(&str, CompanionRole); 5] = [
+    ("Madame Celestine", CompanionRole::Mystic),
+    ("Ned \"Longshot\" Harrow", CompanionRole::Gunner),
+    ("Old Tobias Crane", CompanionRole::Navigator),
+    ("Perpetua Vance", CompanionRole::Quartermaster),
+    ("Kestrel", CompanionRole::Lookout),
+];
+
+/// What is happening in a port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum PortEventKind {
+    Festival,
+    Strike,
+}
+
+impl PortEventKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PortEventKind::Festival => "Festival",
+            PortEventKind::Strike => "Dockworkers' Strike",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            PortEventKind::Festival => "🎉",
+            PortEventKind::Strike => "✊",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PortEventKind::Festival => "The tavern is cheap, the market marked down, and strangers have come to town.",
+            PortEventKind::Strike => "The dockworkers have walked out: no trading and no repairs until it is settled.",
+        }
+    }
+
+    /// In-game days it lasts.
+    pub fn days(&self) -> u32 {
+        match self {
+            PortEventKind::Festival => 3,
+            PortEventKind::Strike => 2,
+        }
+    }
+
+    /// Share of the usual price the tavern charges.
+    pub fn tavern_rate(&self) -> f32 {
+        match self {
+            PortEventKind::Festival => FESTIVAL_TAVERN_RATE,
+            PortEventKind::Strike => 1.0,
+        }
+    }
+
+    /// Share of the listed price goods are sold for.
+    pub fn goods_rate(&self) -> f32 {
+        match self {
+            PortEventKind::Festival => FESTIVAL_GOODS_RATE,
+            PortEventKind::Strike => 1.0,
+        }
+    }
+
+    /// Whether the market, the repair yard, the shipyard and the moorings are shut.
+    pub fn closes_docks(&self) -> bool {
+        matches!(self, PortEventKind::Strike)
+    }
+}
+
+/// A festival or strike under way in one port.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct PortEvent {
+    pub kind: PortEventKind,
+    pub port_name: String,
+    /// Day it began.
+    pub started_on: u32,
+    /// Day it is over.
+    pub ends_on: u32,
+    /// Index into `FESTIVAL_GUESTS` of the guest in town for a festival.
+    pub guest: usize,
+}
+
+impl PortEvent {
+    /// Days left as of `day`, counting today.
+    pub fn days_left(&self, day: u32) -> u32 {
+        self.ends_on.saturating_sub(day)
+    }
+
+    /// The festival's guest, if this is a festival.
+    pub fn festival_guest(&self) -> Option<(&'static str, CompanionRole)> {
+        (self.kind == PortEventKind::Festival).then(|| FESTIVAL_GUESTS[self.guest % FESTIVAL_GUESTS.len()])
+    }
+}
+
+/// Festivals and strikes under way, by port.
+///
+/// Keyed by `port_key`, since port entities are respawned every High Seas visit.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PortEvents {
+    events: HashMap<IVec2, PortEvent>,
+}
+
+impl PortEvents {
+    /// The event under way at the port at `position`.
+    pub fn at(&self, position: Vec2) -> Option<&PortEvent> {
+        self.events.get(&port_key(position))
+    }
+
+    /// The event under way at the port at `position`, if there is a port.
+    pub fn at_port(&self, position: Option<Vec2>) -> Option<&PortEvent> {
+        position.and_then(|position| self.at(position))
+    }
+
+    /// Whether the docks are shut at the port at `position`, if there is a port.
+    pub fn docks_closed(&self, position: Option<Vec2>) -> bool {
+        self.at_port(position).is_some_and(|event| event.kind.closes_docks())
+    }
+
+    /// Starts `kind` at a port on `day`, unless something is already under way
+    /// there. Returns whether it started.
+    pub fn start(&mut self, position: Vec2, port_name: impl Into<String>, kind: PortEventKind, day: u32, guest: usize) -> bool {
+        let key = port_key(position);
+        if self.events.contains_key(&key) {
+            return false;
+        }
+        self.events.insert(
+            key,
+            PortEvent {
+                kind,
+                port_name: port_name.into(),
+                started_on: day,
+                ends_on: day + kind.days(),
+                guest,
+            },
+        );
+        true
+    }
+
+    /// Ends and returns every event that is over by `day`.
+    pub fn expire(&mut self, day: u32) -> Vec<PortEvent> {
+        let over: Vec<IVec2> = self
+            .events
+            .iter()
+            .filter(|(_, event)| event.ends_on <= day)
+            .map(|(key, _)| *key)
+            .collect();
+        let mut ended: Vec<PortEvent> = over.iter().filter_map(|key| self.events.remove(key)).collect();
+        ended.sort_by(|a, b| a.port_name.cmp(&b.port_name));
+        ended
+    }
+
+    /// Events under way.
+    pub fn iter(&self) -> impl Iterator<Item = &PortEvent> {
+        self.events.values()
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+/// `cost` at `rate` of the usual price, rounded to the nearest gold.
+pub fn event_price(cost: u32, rate: f32) -> u32 {
+    (cost as f32 * rate).round() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_event_per_port_until_it_ends() {
+        let mut events = PortEvents::default();
+        let tortuga = Vec2::new(64.0, 128.0);
+        assert!(events.start(tortuga, "Tortuga", PortEventKind::Strike, 4, 0));
+        assert!(!events.start(tortuga, "Tortuga", PortEventKind::Festival, 4, 0));
+        assert!(events.at(tortuga).unwrap().kind.closes_docks());
+        assert!(events.at(Vec2::new(640.0, 0.0)).is_none());
+
+        assert!(events.expire(4 + PortEventKind::Strike.days() - 1).is_empty());
+        assert_eq!(events.at(tortuga).unwrap().days_left(5), 1);
+        let ended = events.expire(4 + PortEventKind::Strike.days());
+        assert_eq!(ended[0].port_name, "Tortuga");
+        assert!(events.at(tortuga).is_none());
+        assert!(events.start(tortuga, "Tortuga", PortEventKind::Festival, 7, 0));
+    }
+
+    #[test]
+    fn test_festival_prices_and_guest() {
+        let mut events = PortEvents::default();
+        let nassau = Vec2::new(-32.0, 16.0);
+        events.start(nassau, "Nassau", PortEventKind::Festival, 1, FESTIVAL_GUESTS.len() + 1);
+        let festival = events.at(nassau).unwrap();
+
+        assert_eq!(event_price(30, festival.kind.tavern_rate()), 15);
+        assert_eq!(festival.festival_guest(), Some(FESTIVAL_GUESTS[1]));
+        assert!(!festival.kind.closes_docks());
+    }
+}
@@ -0,0 +1,200 @@
+//! Port markets that live on between visits.
+//!
+//! Port entities, and their `Inventory`, are respawned every time the High
+//! Seas are entered. `PortMarkets` keeps each market's stock in the meantime,
+//! keyed by `port_key`, along with the regional trade it takes part
+//! in: a good its hinterland produces, which piles up, and a good its people
+//! consume, which runs down. Each day stock also flows between neighbouring
+//! ports, so a port the player has bought out slowly draws on its neighbours'
+//! surplus, and theirs rises in price in turn.
+
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+
+use crate::components::cargo::GoodType;
+use crate::components::port::Inventory;
+
+/// Share of a good's base quantity a port produces each day.
+pub const PRODUCTION_PER_DAY: f32 = 0.1;
+/// Share of a good's base quantity a port consumes each day.
+pub const CONSUMPTION_PER_DAY: f32 = 0.08;
+/// Ports closer than this trade stock with each other (world units).
+pub const NEIGHBOUR_DISTANCE: f32 = 2000.0;
+/// Share of the difference in stock between neighbours that evens out each day.
+pub const DIFFUSION_PER_DAY: f32 = 0.15;
+/// Change in `GlobalDemand` per unit the player buys (raises) or sells (lowers).
+pub const TRADE_DEMAND_PER_UNIT: f32 = 0.001;
+/// Share of the way back to normal demand each good recovers each day.
+pub const DEMAND_RECOVERY_PER_DAY: f32 = 0.1;
+
+/// One port's market, kept between visits.
+#[derive(Debug, Clone, Reflect)]
+pub struct PortMarket {
+    pub inventory: Inventory,
+    /// Good the port's hinterland produces, if any.
+    pub produces: Option<GoodType>,
+    /// Good the port's people consume, if any.
+    pub consumes: Option<GoodType>,
+}
+
+impl PortMarket {
+    /// A newly charted market: it produces one of the goods it stocks and consumes another.
+    pub fn chart(inventory: Inventory, rng: &mut impl Rng) -> Self {
+        let mut goods: Vec<GoodType> = inventory.goods.keys().copied().collect();
+        goods.sort_by_key(|good| format!("{:?}", good));
+        goods.shuffle(rng);
+        Self {
+            produces: goods.first().copied(),
+            consumes: goods.get(1).copied(),
+            inventory,
+        }
+    }
+
+    /// Change in stock of `good` over one day, given its equilibrium `base_quantity`.
+    pub fn daily_change(&self, good: GoodType, base_quantity: u32) -> i32 {
+        let base = base_quantity as f32;
+        if self.produces == Some(good) {
+            (base * PRODUCTION_PER_DAY).round() as i32
+        } else if self.consumes == Some(good) {
+            -(base * CONSUMPTION_PER_DAY).round() as i32
+        } else {
+            0
+        }
+    }
+
+    /// Produces and consumes a day's goods.
+    fn run_production(&mut self, base_quantity: &impl Fn(&GoodType) -> u32) {
+        let changes: Vec<(GoodType, i32)> = self
+            .inventory
+            .goods
+            .keys()
+            .map(|good| (*good, self.daily_change(*good, base_quantity(good))))
+            .collect();
+        for (good, change) in changes {
+            if let Some(item) = self.inventory.goods.get_mut(&good) {
+                item.quantity = item.quantity.saturating_add_signed(change);
+            }
+        }
+    }
+}
+
+/// Units moving from a port with `from` in stock to a neighbour with `to` in a day.
+/// Negative when the neighbour has more.
+pub fn daily_flow(from: u32, to: u32) -> i32 {
+    ((from as f32 - to as f32) * DIFFUSION_PER_DAY / 2.0).round() as i32
+}
+
+/// Every market the player's ships have seen.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PortMarkets {
+    pub markets: HashMap<IVec2, PortMarket>,
+}
+
+impl PortMarkets {
+    pub fn get(&self, key: IVec2) -> Option<&PortMarket> {
+        self.markets.get(&key)
+    }
+
+    pub fn get_mut(&mut self, key: IVec2) -> Option<&mut PortMarket> {
+        self.markets.get_mut(&key)
+    }
+
+    /// Records a live port's stock, charting its market if it is new.
+    pub fn observe(&mut self, key: IVec2, inventory: &Inventory, rng: &mut impl Rng) {
+        match self.markets.get_mut(&key) {
+            Some(market) => market.inventory = inventory.clone(),
+            None => {
+                self.markets.insert(key, PortMarket::chart(inventory.clone(), rng));
+            }
+        }
+    }
+
+    /// Runs a day of trade: each port produces and consumes, then stock flows between neighbours.
+    pub fn run_day(&mut self, base_quantity: impl Fn(&GoodType) -> u32) {
+        for market in self.markets.values_mut() {
+            market.run_production(&base_quantity);
+        }
+
+        // Flows are worked out from the morning's stock, so the order ports are visited doesn't matter
+        let mut keys: Vec<IVec2> = self.markets.keys().copied().collect();
+        keys.sort_by_key(|key| (key.x, key.y));
+        let mut flows: Vec<(IVec2, GoodType, i32)> = Vec::new();
+        for (index, a) in keys.iter().enumerate() {
+            for b in &keys[index + 1..] {
+                if a.as_vec2().distance(b.as_vec2()) > NEIGHBOUR_DISTANCE {
+                    continue;
+                }
+                for (good, item) in &self.markets[a].inventory.goods {
+                    let Some(other) = self.markets[b].inventory.get_good(good) else {
+                        continue;
+                    };
+                    let flow = daily_flow(item.quantity, other.quantity);
+                    flows.push((*a, *good, -flow));
+                    flows.push((*b, *good, flow));
+                }
+            }
+        }
+        for (key, good, change) in flows {
+            if let Some(item) = self.markets.get_mut(&key).and_then(|market| market.inventory.goods.get_mut(&good)) {
+                item.quantity = item.quantity.saturating_add_signed(change);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.markets.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market(rum: u32, produces: Option<GoodType>, consumes: Option<GoodType>) -> PortMarket {
+        let mut inventory = Inventory::new();
+        inventory.set_good(GoodType::Rum, rum, 15.0);
+        PortMarket { inventory, produces, consumes }
+    }
+
+    fn rum(markets: &PortMarkets, key: IVec2) -> u32 {
+        markets.get(key).unwrap().inventory.get_good(&GoodType::Rum).unwrap().quantity
+    }
+
+    #[test]
+    fn test_production_and_consumption() {
+        let mut markets = PortMarkets::default();
+        markets.markets.insert(IVec2::ZERO, market(50, Some(GoodType::Rum), None));
+        markets.markets.insert(IVec2::new(9000, 0), market(50, None, Some(GoodType::Rum)));
+        markets.run_day(|_| 100);
+        assert_eq!(rum(&markets, IVec2::ZERO), 60);
+        assert_eq!(rum(&markets, IVec2::new(9000, 0)), 42);
+    }
+
+    #[test]
+    fn test_neighbours_even_out_stock() {
+        let mut markets = PortMarkets::default();
+        markets.markets.insert(IVec2::ZERO, market(0, None, None));
+        markets.markets.insert(IVec2::new(500, 0), market(100, None, None));
+        // Too far away to take part
+        markets.markets.insert(IVec2::new(9000, 0), market(100, None, None));
+        markets.run_day(|_| 100);
+        assert_eq!(rum(&markets, IVec2::ZERO), 8);
+        assert_eq!(rum(&markets, IVec2::new(500, 0)), 92);
+        assert_eq!(rum(&markets, IVec2::new(9000, 0)), 100);
+    }
+
+    #[test]
+    fn test_observe_keeps_regional_trade() {
+        let mut markets = PortMarkets::default();
+        let mut rng = rand::thread_rng();
+        markets.markets.insert(IVec2::ZERO, market(50, Some(GoodType::Rum), None));
+        let mut bought_out = Inventory::new();
+        bought_out.set_good(GoodType::Rum, 0, 45.0);
+        markets.observe(IVec2::ZERO, &bought_out, &mut rng);
+        assert_eq!(rum(&markets, IVec2::ZERO), 0);
+        assert_eq!(markets.get(IVec2::ZERO).unwrap().produces, Some(GoodType::Rum));
+    }
+}
@@ -0,0 +1,59 @@
+//! Letters of marque: privateering commissions against a rival nation.
+//!
+//! A nation on decent terms with the player (`COMMISSION_MIN_REPUTATION`)
+//! posts a commission at its ports naming one of its rivals. While it is
+//! accepted, that rival's ships are fair game on the High Seas, and every one
+//! the player sinks or helps sink pays prize money on the spot, raises the
+//! issuer's opinion and lowers the rival's. Sinking the number of ships the
+//! commission asks for completes it; a commission that runs out simply lapses.
+
+use crate::components::FactionId;
+
+/// Reputation with a nation needed before it issues letters of marque.
+pub const COMMISSION_MIN_REPUTATION: i32 = 10;
+/// Time a commission runs (4 in-game days).
+pub const COMMISSION_DURATION_TICKS: u32 = 4 * 24 * 60;
+/// Gold paid for each rival ship sunk under a commission, unless
+/// `ContractRewards` says otherwise.
+pub const PRIZE_MONEY: u32 = 200;
+/// Reputation gained with the issuer for each rival ship sunk.
+pub const PRIZE_REPUTATION_GAIN: i32 = 3;
+/// Reputation lost with the rival for each of its ships sunk.
+pub const PRIZE_REPUTATION_LOSS: i32 = 6;
+/// Reputation gained with the issuer on completing a commission.
+pub const COMMISSION_REPUTATION_REWARD: i32 = 8;
+
+/// The nations a commission can be issued against.
+const NATIONS: [FactionId; 3] = [FactionId::NationA, FactionId::NationB, FactionId::NationC];
+
+/// Whether a faction at `reputation` with the player issues letters of marque.
+/// The Brethren have no letters to give.
+pub fn offers_commissions(faction: FactionId, reputation: i32) -> bool {
+    faction != FactionId::Pirates && reputation >= COMMISSION_MIN_REPUTATION
+}
+
+/// The rival `issuer` names in a commission, picked by `roll` in [0, 1).
+pub fn commission_rival(issuer: FactionId, roll: f32) -> FactionId {
+    let rivals: Vec<FactionId> = NATIONS.into_iter().filter(|&nation| nation != issuer).collect();
+    let index = ((roll * rivals.len() as f32) as usize).min(rivals.len() - 1);
+    rivals[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nations_commission_against_each_other() {
+        assert!(offers_commissions(FactionId::NationB, COMMISSION_MIN_REPUTATION));
+        assert!(!offers_commissions(FactionId::NationB, COMMISSION_MIN_REPUTATION - 1));
+        assert!(!offers_commissions(FactionId::Pirates, 100));
+
+        for roll in [0.0, 0.49, 0.5, 0.999] {
+            let rival = commission_rival(FactionId::NationA, roll);
+            assert!(rival != FactionId::NationA && rival != FactionId::Pirates);
+        }
+        assert_eq!(commission_rival(FactionId::NationA, 0.0), FactionId::NationB);
+        assert_eq!(commission_rival(FactionId::NationA, 0.999), FactionId::NationC);
+    }
+}
@@ -0,0 +1,314 @@
+//! Running down a fleeing merchant on the High Seas.
+//!
+//! A hostile merchant that sights the player cracks on sail and runs. If the
+//! player's ship is the faster, the chase is played out in a pursuit of
+//! `CHASE_MIN_SECONDS` to `CHASE_MAX_SECONDS` while the world holds still.
+//! Every `CHASE_LEG_SECONDS` the wind shifts and the player must choose how
+//! to steer: straight after her, or off the wind on a faster point of sail
+//! that makes less ground good. She always runs on her best course. Within
+//! `WARNING_SHOT_RANGE` a warning shot across her bows shakes her master's
+//! nerve; once it breaks she heaves to and gives up her cargo. If the chase
+//! runs its time first, she turns to fight.
+
+use std::f32::consts::FRAC_PI_6;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::FactionId;
+use crate::resources::Wind;
+
+/// Lead the merchant has when the chase begins (cables).
+pub const CHASE_START_GAP: f32 = 5.0;
+/// Furthest she can draw ahead (cables).
+pub const CHASE_MAX_GAP: f32 = 10.0;
+/// Shortest and longest a chase can last before she turns to fight (real seconds).
+pub const CHASE_MIN_SECONDS: f32 = 30.0;
+pub const CHASE_MAX_SECONDS: f32 = 60.0;
+/// Time between shifts of the wind (real seconds).
+pub const CHASE_LEG_SECONDS: f32 = 6.0;
+/// Most the wind veers or backs at each shift (radians).
+pub const CHASE_WIND_SHIFT: f32 = 1.0;
+/// Cables gained or lost per second for each unit of speed made good over hers.
+pub const GAP_PER_SPEED: f32 = 0.001;
+/// Closest a warning shot can be fired from (cables).
+pub const WARNING_SHOT_RANGE: f32 = 3.0;
+/// Time to reload the bow gun after a warning shot (real seconds).
+pub const WARNING_SHOT_RELOAD: f32 = 5.0;
+/// Nerve a warning shot takes from her master at the limit of range, and the
+/// extra it takes fired from right under her stern.
+pub const WARNING_SHOT_SHAKE: f32 = 0.2;
+pub const WARNING_SHOT_CLOSE_SHAKE: f32 = 0.3;
+/// Extra nerve of a master with contraband in the hold to lose.
+pub const SMUGGLER_RESOLVE: f32 = 0.3;
+
+/// How the player steers after her, off the straight line to her.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChaseCourse {
+    HardToPort,
+    ToPort,
+    #[default]
+    Direct,
+    ToStarboard,
+    HardToStarboard,
+}
+
+impl ChaseCourse {
+    pub const ALL: [ChaseCourse; 5] = [
+        ChaseCourse::HardToPort,
+        ChaseCourse::ToPort,
+        ChaseCourse::Direct,
+        ChaseCourse::ToStarboard,
+        ChaseCourse::HardToStarboard,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChaseCourse::HardToPort => "Hard to port",
+            ChaseCourse::ToPort => "To port",
+            ChaseCourse::Direct => "Straight after her",
+            ChaseCourse::ToStarboard => "To starboard",
+            ChaseCourse::HardToStarboard => "Hard to starboard",
+        }
+    }
+
+    /// Angle off the straight line to her; positive to port (counter-clockwise).
+    pub fn offset(&self) -> f32 {
+        match self {
+            ChaseCourse::HardToPort => 2.0 * FRAC_PI_6,
+            ChaseCourse::ToPort => FRAC_PI_6,
+            ChaseCourse::Direct => 0.0,
+            ChaseCourse::ToStarboard => -FRAC_PI_6,
+            ChaseCourse::HardToStarboard => -2.0 * FRAC_PI_6,
+        }
+    }
+
+    /// Speed made good along `line` steering this course at `speed` in `wind`.
+    pub fn made_good(&self, speed: f32, wind: &Wind, line: Vec2) -> f32 {
+        let heading = Vec2::from_angle(self.offset()).rotate(line);
+        speed * wind.sailing_factor(heading) * self.offset().cos()
+    }
+
+    /// The course that makes the most good along `line`.
+    pub fn best(speed: f32, wind: &Wind, line: Vec2) -> ChaseCourse {
+        Self::ALL
+            .into_iter()
+            .max_by(|a, b| a.made_good(speed, wind, line).total_cmp(&b.made_good(speed, wind, line)))
+            .unwrap_or_default()
+    }
+}
+
+/// Whether a ship at `player_speed` can run down one at `quarry_speed`.
+pub fn outsails(player_speed: f32, quarry_speed: f32) -> bool {
+    player_speed > quarry_speed
+}
+
+/// Where the wind is, seen from a ship sailing along `line`.
+pub fn wind_on_line(wind: &Wind, line: Vec2) -> &'static str {
+    let angle = wind.angle_off_wind(line).to_degrees();
+    if angle < 30.0 {
+        "dead ahead"
+    } else if angle < 75.0 {
+        "forward of the beam"
+    } else if angle < 105.0 {
+        "abeam"
+    } else if angle < 150.0 {
+        "on the quarter"
+    } else {
+        "dead astern"
+    }
+}
+
+/// How a chase ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PursuitOutcome {
+    /// Her nerve broke: she heaves to and gives up her cargo.
+    HeaveTo,
+    /// The chase ran its time: she turns to fight.
+    Fight,
+}
+
+/// The merchant being chased.
+#[derive(Debug, Clone)]
+pub struct Quarry {
+    pub entity: Entity,
+    pub name: String,
+    pub faction: FactionId,
+    /// Her best speed, all sail set.
+    pub speed: f32,
+    /// Whether she has contraband in the hold.
+    pub smuggler: bool,
+}
+
+/// A chase under way.
+#[derive(Debug, Clone)]
+pub struct Chase {
+    pub quarry: Quarry,
+    pub player_speed: f32,
+    /// Direction from the player to her, which she runs along.
+    pub line: Vec2,
+    /// The wind over the chase, shifting every leg.
+    pub wind: Wind,
+    pub course: ChaseCourse,
+    /// Her lead (cables).
+    pub gap: f32,
+    /// Seconds run, and the seconds until she turns to fight.
+    pub elapsed: f32,
+    pub duration: f32,
+    /// Seconds into the current leg.
+    pub leg: f32,
+    /// Her master's nerve; she heaves to when it is gone.
+    pub resolve: f32,
+    /// Seconds until the bow gun is loaded again.
+    pub reload: f32,
+    pub outcome: Option<PursuitOutcome>,
+}
+
+impl Chase {
+    pub fn new(quarry: Quarry, player_speed: f32, line: Vec2, wind: Wind, rng: &mut impl Rng) -> Self {
+        let resolve = rng.gen_range(0.8..=1.2) + if quarry.smuggler { SMUGGLER_RESOLVE } else { 0.0 };
+        Self {
+            quarry,
+            player_speed,
+            line: line.normalize_or(Vec2::Y),
+            wind,
+            course: ChaseCourse::Direct,
+            gap: CHASE_START_GAP,
+            elapsed: 0.0,
+            duration: rng.gen_range(CHASE_MIN_SECONDS..=CHASE_MAX_SECONDS),
+            leg: 0.0,
+            resolve,
+            reload: 0.0,
+            outcome: None,
+        }
+    }
+
+    /// Cables closed each second on the course steered (negative when she
+    /// draws ahead).
+    pub fn closing_rate(&self) -> f32 {
+        let ours = self.course.made_good(self.player_speed, &self.wind, self.line);
+        let speed = self.quarry.speed;
+        let hers = ChaseCourse::best(speed, &self.wind, self.line).made_good(speed, &self.wind, self.line);
+        (ours - hers) * GAP_PER_SPEED
+    }
+
+    pub fn seconds_left(&self) -> f32 {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    /// Runs the chase on `delta` seconds, shifting the wind at the end of
+    /// each leg. She turns to fight when the time is up.
+    pub fn step(&mut self, delta: f32, rng: &mut impl Rng) {
+        if self.outcome.is_some() {
+            return;
+        }
+        self.gap = (self.gap - self.closing_rate() * delta).clamp(0.0, CHASE_MAX_GAP);
+        self.reload = (self.reload - delta).max(0.0);
+        self.elapsed += delta;
+        self.leg += delta;
+        if self.leg >= CHASE_LEG_SECONDS {
+            self.leg -= CHASE_LEG_SECONDS;
+            self.wind.direction += rng.gen_range(-CHASE_WIND_SHIFT..=CHASE_WIND_SHIFT);
+        }
+        if self.elapsed >= self.duration {
+            self.outcome = Some(PursuitOutcome::Fight);
+        }
+    }
+
+    pub fn can_fire_warning_shot(&self) -> bool {
+        self.outcome.is_none() && self.reload <= 0.0 && self.gap <= WARNING_SHOT_RANGE
+    }
+
+    /// Fires a shot across her bows: the closer, the more it shakes her.
+    /// Returns whether it was fired.
+    pub fn warning_shot(&mut self) -> bool {
+        if !self.can_fire_warning_shot() {
+            return false;
+        }
+        let closeness = 1.0 - self.gap / WARNING_SHOT_RANGE;
+        self.resolve -= WARNING_SHOT_SHAKE + WARNING_SHOT_CLOSE_SHAKE * closeness;
+        self.reload = WARNING_SHOT_RELOAD;
+        if self.resolve <= 0.0 {
+            self.outcome = Some(PursuitOutcome::HeaveTo);
+        }
+        true
+    }
+}
+
+/// The chase the player is in, if any.
+#[derive(Resource, Debug, Default)]
+pub struct PursuitState {
+    pub chase: Option<Chase>,
+    /// Whether the chase stopped `Time<Virtual>`, and so should start it again.
+    pub stopped_world: bool,
+}
+
+impl PursuitState {
+    pub fn is_chasing(&self) -> bool {
+        self.chase.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn chase(wind: Wind) -> Chase {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let quarry = Quarry {
+            entity: Entity::from_raw(1),
+            name: "Merchant".into(),
+            faction: FactionId::NationA,
+            speed: 220.0,
+            smuggler: false,
+        };
+        Chase::new(quarry, 300.0, Vec2::Y, wind, &mut rng)
+    }
+
+    #[test]
+    fn test_off_the_wind_beats_straight_into_it() {
+        // She lies dead upwind: straight after her is in irons
+        let wind = Wind { direction: -std::f32::consts::FRAC_PI_2, strength: 1.0 };
+        let best = ChaseCourse::best(300.0, &wind, Vec2::Y);
+        assert_ne!(best, ChaseCourse::Direct);
+        assert!(best.made_good(300.0, &wind, Vec2::Y) > ChaseCourse::Direct.made_good(300.0, &wind, Vec2::Y));
+
+        // With the wind astern, straight after her is as good as any
+        let astern = Wind { direction: std::f32::consts::FRAC_PI_2, strength: 1.0 };
+        assert_eq!(wind_on_line(&astern, Vec2::Y), "dead astern");
+        let mut running = chase(astern);
+        assert!(running.closing_rate() > 0.0);
+        running.course = ChaseCourse::HardToPort;
+        assert!(running.closing_rate() < chase(astern).closing_rate());
+    }
+
+    #[test]
+    fn test_warning_shots_break_her_nerve() {
+        let mut chase = chase(Wind::default());
+        assert!(!chase.warning_shot(), "out of range");
+
+        chase.gap = 0.0;
+        let mut shots = 0;
+        while chase.outcome.is_none() {
+            assert!(chase.warning_shot());
+            assert!(!chase.warning_shot(), "still reloading");
+            chase.reload = 0.0;
+            shots += 1;
+        }
+        assert_eq!(chase.outcome, Some(PursuitOutcome::HeaveTo));
+        assert!(shots >= 2);
+    }
+
+    #[test]
+    fn test_she_fights_when_time_runs_out() {
+        let mut chase = chase(Wind::default());
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        while chase.outcome.is_none() {
+            chase.step(1.0, &mut rng);
+            assert!((0.0..=CHASE_MAX_GAP).contains(&chase.gap));
+        }
+        assert_eq!(chase.outcome, Some(PursuitOutcome::Fight));
+        assert!(chase.elapsed >= CHASE_MIN_SECONDS && chase.elapsed <= CHASE_MAX_SECONDS + 1.0);
+    }
+}
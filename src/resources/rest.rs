@@ -0,0 +1,172 @@
+//! Waiting out the hours in port or at anchor.
+//!
+//! Resting fast-forwards `Time<Virtual>` by `REST_TIME_SCALE`, so every
+//! world-tick system runs as usual, only quicker. Ashore it is always safe.
+//! At sea the ship must be at anchor (no course set) in safe water: no
+//! pirates in sight and no storm close by. A rest ends early when a storm
+//! approaches, pirates come into view or an accepted contract's deadline
+//! draws near.
+
+use bevy::prelude::*;
+
+use crate::resources::{StormTracker, WorldClock, TICKS_PER_HOUR};
+
+/// How much faster time runs while resting (12 in-game hours per real second at 60Hz).
+pub const REST_TIME_SCALE: f32 = 12.0;
+/// Hour a rest "until morning" ends.
+pub const MORNING_HOUR: u32 = 6;
+/// Pirates closer than this make an anchorage unsafe (world units).
+pub const SAFE_ANCHORAGE_RADIUS: f32 = 800.0;
+/// A storm whose edge is closer than this makes an anchorage unsafe (world units).
+pub const STORM_WARNING_DISTANCE: f32 = 500.0;
+/// Longest rest that can be asked for at once (hours).
+pub const MAX_REST_HOURS: u32 = 48;
+
+/// How long to rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestUntil {
+    /// For a number of hours.
+    Hours(u32),
+    /// Until the next `MORNING_HOUR`.
+    Morning,
+    /// Until a world tick (`WorldClock::total_ticks`).
+    Tick(u32),
+}
+
+impl RestUntil {
+    /// World tick the rest ends.
+    pub fn target_tick(&self, clock: &WorldClock) -> u32 {
+        let now = clock.total_ticks();
+        match *self {
+            RestUntil::Hours(hours) => now + hours * TICKS_PER_HOUR,
+            RestUntil::Morning => {
+                let hours = (MORNING_HOUR + 24 - clock.hour - 1) % 24 + 1;
+                now - clock.tick + hours * TICKS_PER_HOUR
+            }
+            RestUntil::Tick(tick) => tick.max(now),
+        }
+    }
+}
+
+/// Something that makes an anchorage unsafe.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnchorageHazard {
+    /// The ship has a course set.
+    UnderWay,
+    Pirates,
+    /// A storm, by name.
+    Storm(String),
+}
+
+impl AnchorageHazard {
+    pub fn describe(&self) -> String {
+        match self {
+            AnchorageHazard::UnderWay => "We are under way.".to_string(),
+            AnchorageHazard::Pirates => "Pirate sails in sight!".to_string(),
+            AnchorageHazard::Storm(name) => format!("Storm {} is closing on the anchorage.", name),
+        }
+    }
+}
+
+/// Why the ship can't safely lie at anchor at `position`, if anything.
+pub fn anchorage_hazard(
+    position: Vec2,
+    under_way: bool,
+    mut pirates: impl Iterator<Item = Vec2>,
+    storms: &StormTracker,
+) -> Option<AnchorageHazard> {
+    if under_way {
+        return Some(AnchorageHazard::UnderWay);
+    }
+    if pirates.any(|pirate| pirate.distance(position) <= SAFE_ANCHORAGE_RADIUS) {
+        return Some(AnchorageHazard::Pirates);
+    }
+    storms
+        .storms
+        .iter()
+        .find(|storm| storm.position.distance(position) - storm.radius <= STORM_WARNING_DISTANCE)
+        .map(|storm| AnchorageHazard::Storm(storm.name.clone()))
+}
+
+/// The player's rest, if they are resting.
+#[derive(Resource, Debug, Default)]
+pub struct RestState {
+    /// World tick the rest began.
+    pub started_at: u32,
+    /// World tick the rest ends.
+    pub until: Option<u32>,
+    /// Accepted contracts already close to their deadline when the rest began.
+    pub urgent_contracts: Vec<Entity>,
+    /// Hours picked in the rest controls.
+    pub hours_choice: u32,
+}
+
+impl RestState {
+    pub fn is_resting(&self) -> bool {
+        self.until.is_some()
+    }
+
+    /// Share of the rest done, 0.0 to 1.0.
+    pub fn progress(&self, now: u32) -> f32 {
+        match self.until {
+            Some(until) if until > self.started_at => {
+                (now.saturating_sub(self.started_at) as f32 / (until - self.started_at) as f32).min(1.0)
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::Storm;
+
+    fn clock(day: u32, hour: u32, tick: u32) -> WorldClock {
+        WorldClock { day, hour, tick }
+    }
+
+    #[test]
+    fn test_rest_until_morning() {
+        // Before dawn: this morning
+        let night = clock(2, 3, 30);
+        assert_eq!(RestUntil::Morning.target_tick(&night), clock(2, 6, 0).total_ticks());
+        // Already morning: tomorrow
+        let morning = clock(2, 6, 10);
+        assert_eq!(RestUntil::Morning.target_tick(&morning), clock(3, 6, 0).total_ticks());
+        let evening = clock(2, 21, 0);
+        assert_eq!(RestUntil::Morning.target_tick(&evening), clock(3, 6, 0).total_ticks());
+    }
+
+    #[test]
+    fn test_rest_for_hours() {
+        let now = clock(1, 10, 0);
+        assert_eq!(RestUntil::Hours(4).target_tick(&now), clock(1, 14, 0).total_ticks());
+        // A tick already passed ends the rest at once
+        assert_eq!(RestUntil::Tick(0).target_tick(&now), now.total_ticks());
+    }
+
+    #[test]
+    fn test_anchorage_hazards() {
+        let mut storms = StormTracker::default();
+        assert_eq!(anchorage_hazard(Vec2::ZERO, false, std::iter::empty(), &storms), None);
+        assert_eq!(anchorage_hazard(Vec2::ZERO, true, std::iter::empty(), &storms), Some(AnchorageHazard::UnderWay));
+        assert_eq!(
+            anchorage_hazard(Vec2::ZERO, false, [Vec2::new(300.0, 0.0)].into_iter(), &storms),
+            Some(AnchorageHazard::Pirates)
+        );
+        storms.storms.push(Storm {
+            name: "Abigail".to_string(),
+            position: Vec2::new(900.0, 0.0),
+            velocity: Vec2::ZERO,
+            radius: 450.0,
+            hours_left: 10,
+            track: Vec::new(),
+            report: None,
+        });
+        assert_eq!(
+            anchorage_hazard(Vec2::ZERO, false, std::iter::empty(), &storms),
+            Some(AnchorageHazard::Storm("Abigail".to_string()))
+        );
+    }
+}
@@ -2,10 +2,19 @@
 //!
 //! Stores calculated Theta* paths between map tiles to avoid re-running
 //! expensive pathfinding for repeated journeys (e.g., trade routes).
+//! Routes are planned for the wind of the moment, so the cache empties
+//! itself when the wind backs or veers by a point, or freshens.
 
 use bevy::prelude::*;
 use std::collections::HashMap;
 
+use crate::resources::Wind;
+
+/// Compass points the wind is rounded to when deciding whether routes are stale.
+const WIND_POINTS: f32 = 16.0;
+/// Bands wind strength is rounded to, as above.
+const WIND_STRENGTH_BANDS: f32 = 4.0;
+
 /// Cached paths between map tiles.
 /// 
 /// Key is (start_tile, goal_tile).
@@ -14,6 +23,8 @@ use std::collections::HashMap;
 pub struct RouteCache {
     /// Map from (start, goal) to path.
     cache: HashMap<(IVec2, IVec2), Vec<IVec2>>,
+    /// Compass point and strength band of the wind the cached routes were planned in.
+    wind: Option<(i32, i32)>,
 }
 
 impl RouteCache {
@@ -21,6 +32,17 @@ impl RouteCache {
     pub fn new() -> Self {
         Self {
             cache: HashMap::new(),
+            wind: None,
+        }
+    }
+
+    /// Clears the cache if the wind has shifted since its routes were planned.
+    pub fn plan_for_wind(&mut self, wind: &Wind) {
+        let point = (wind.direction / std::f32::consts::TAU * WIND_POINTS).round().rem_euclid(WIND_POINTS) as i32;
+        let band = (wind.strength * WIND_STRENGTH_BANDS).round() as i32;
+        if self.wind != Some((point, band)) {
+            self.cache.clear();
+            self.wind = Some((point, band));
         }
     }
 
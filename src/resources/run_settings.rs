@@ -0,0 +1,284 @@
+//! Settings chosen on the New Game screen for a single run.
+//!
+//! Feeds world generation (`MapGenConfig`) and the spawn systems that scale
+//! with difficulty and run modifiers.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{CliArgs, DefeatRules};
+use crate::utils::procgen::MapGenConfig;
+
+/// Map size presets offered on the New Game screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum MapSizePreset {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl MapSizePreset {
+    /// Returns all presets in display order.
+    pub fn all() -> &'static [MapSizePreset] {
+        &[MapSizePreset::Small, MapSizePreset::Medium, MapSizePreset::Large]
+    }
+
+    /// Width and height of the map in tiles.
+    pub fn tiles(&self) -> u32 {
+        match self {
+            MapSizePreset::Small => 256,
+            MapSizePreset::Medium => 512,
+            MapSizePreset::Large => 1024,
+        }
+    }
+
+    /// Display name for the UI.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MapSizePreset::Small => "Small (256)",
+            MapSizePreset::Medium => "Medium (512)",
+            MapSizePreset::Large => "Large (1024)",
+        }
+    }
+
+    /// Returns the preset with the given tile size, if any.
+    pub fn from_tiles(tiles: u32) -> Option<Self> {
+        Self::all().iter().copied().find(|p| p.tiles() == tiles)
+    }
+
+    /// Parses a preset from a name (`small`, `medium`, `large`) or a tile size (`1024`).
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "small" => Some(MapSizePreset::Small),
+            "medium" => Some(MapSizePreset::Medium),
+            "large" => Some(MapSizePreset::Large),
+            other => other.parse().ok().and_then(Self::from_tiles),
+        }
+    }
+}
+
+/// Difficulty levels affecting starting resources and enemy density.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Returns all difficulties in display order.
+    pub fn all() -> &'static [Difficulty] {
+        &[Difficulty::Easy, Difficulty::Normal, Difficulty::Hard]
+    }
+
+    /// Display name for the UI.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// Multiplier applied to the archetype's starting gold.
+    pub fn starting_gold_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.75,
+        }
+    }
+
+    /// What is lost when the player's flagship sinks.
+    pub fn defeat_rules(&self) -> DefeatRules {
+        match self {
+            Difficulty::Easy => DefeatRules { gold_loss: 0.25, cargo_loss: 0.25, ship_recovery_chance: 0.75 },
+            Difficulty::Normal => DefeatRules { gold_loss: 0.5, cargo_loss: 0.5, ship_recovery_chance: 0.5 },
+            Difficulty::Hard => DefeatRules { gold_loss: 0.75, cargo_loss: 0.75, ship_recovery_chance: 0.25 },
+        }
+    }
+
+    /// Multiplier applied to the number of hostile ships spawned.
+    pub fn enemy_ship_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+}
+
+/// Optional rule changes for a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub struct RunModifiers {
+    /// Wrecks from previous runs are not placed on the map.
+    pub no_legacy_wrecks: bool,
+    /// Twice as many pirate ships roam the seas.
+    pub double_pirates: bool,
+    /// Ports start with much larger stocks of goods.
+    pub merchant_boom: bool,
+    /// No ghost ships, cursed fog or other supernatural encounters.
+    pub historical_waters: bool,
+}
+
+/// Configuration for the current run, edited on the New Game screen.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct RunSettings {
+    /// World generation seed.
+    pub seed: u32,
+    pub map_size: MapSizePreset,
+    pub difficulty: Difficulty,
+    pub modifiers: RunModifiers,
+}
+
+impl Default for RunSettings {
+    fn default() -> Self {
+        Self {
+            seed: rand::thread_rng().gen(),
+            map_size: MapSizePreset::default(),
+            difficulty: Difficulty::default(),
+            modifiers: RunModifiers::default(),
+        }
+    }
+}
+
+/// Number of high seas AI ships on a 512x512 map at Normal difficulty.
+const BASE_AI_SHIP_COUNT: f32 = 50.0;
+
+impl RunSettings {
+    /// Default settings with any command-line overrides applied.
+    pub fn from_cli(cli: &CliArgs) -> Self {
+        let mut settings = Self::default();
+        if let Some(map_size) = cli.map_size {
+            settings.map_size = map_size;
+        }
+        settings
+    }
+
+    /// Builds the map generation config for these settings.
+    ///
+    /// Port counts scale with map area so density matches the 512x512 default.
+    pub fn map_gen_config(&self) -> MapGenConfig {
+        let defaults = MapGenConfig::default();
+        let tiles = self.map_size.tiles();
+        let area_ratio = (tiles * tiles) as f32 / (defaults.width * defaults.height) as f32;
+        let scale_ports = |n: usize| ((n as f32 * area_ratio).round() as usize).max(3);
+
+        MapGenConfig {
+            seed: self.seed,
+            width: tiles,
+            height: tiles,
+            min_ports: scale_ports(defaults.min_ports),
+            max_ports: scale_ports(defaults.max_ports),
+            ..defaults
+        }
+    }
+
+    /// Number of AI ships to spawn on the high seas, scaled by map area,
+    /// difficulty and the double pirates modifier.
+    pub fn ai_ship_count(&self) -> usize {
+        let tiles = self.map_size.tiles() as f32;
+        let area_ratio = (tiles * tiles) / (512.0 * 512.0);
+        let mut count = BASE_AI_SHIP_COUNT * area_ratio * self.difficulty.enemy_ship_multiplier();
+        if self.modifiers.double_pirates {
+            count *= 2.0;
+        }
+        count.round() as usize
+    }
+
+    /// Multiplier applied to port starting stock.
+    pub fn port_stock_multiplier(&self) -> f32 {
+        if self.modifiers.merchant_boom {
+            2.5
+        } else {
+            1.0
+        }
+    }
+
+    /// Converts seed text from the UI into a numeric seed.
+    ///
+    /// Numbers are used as-is; any other text is hashed (FNV-1a) so word seeds
+    /// like "kraken" are reproducible.
+    pub fn seed_from_text(text: &str) -> u32 {
+        let trimmed = text.trim();
+        if let Ok(seed) = trimmed.parse::<u32>() {
+            return seed;
+        }
+        let mut hash: u32 = 0x811c_9dc5;
+        for byte in trimmed.bytes() {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+        hash
+    }
+}
+
+/// Marker resource requesting the world be regenerated from `RunSettings`
+/// when the main menu closes.
+#[derive(Resource, Debug, Default)]
+pub struct PendingWorldGeneration;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_gen_config_uses_preset_size_and_seed() {
+        let settings = RunSettings {
+            seed: 1234,
+            map_size: MapSizePreset::Large,
+            ..Default::default()
+        };
+        let config = settings.map_gen_config();
+        assert_eq!(config.seed, 1234);
+        assert_eq!(config.width, 1024);
+        assert_eq!(config.height, 1024);
+        assert!(config.min_ports > MapGenConfig::default().min_ports);
+    }
+
+    #[test]
+    fn test_small_map_keeps_minimum_ports() {
+        let settings = RunSettings {
+            map_size: MapSizePreset::Small,
+            ..Default::default()
+        };
+        assert!(settings.map_gen_config().min_ports >= 3);
+    }
+
+    #[test]
+    fn test_ai_ship_count_scales_with_modifiers() {
+        let mut settings = RunSettings::default();
+        assert_eq!(settings.ai_ship_count(), 50);
+        settings.modifiers.double_pirates = true;
+        assert_eq!(settings.ai_ship_count(), 100);
+        settings.difficulty = Difficulty::Hard;
+        assert_eq!(settings.ai_ship_count(), 150);
+    }
+
+    #[test]
+    fn test_map_size_parse() {
+        assert_eq!(MapSizePreset::parse("small"), Some(MapSizePreset::Small));
+        assert_eq!(MapSizePreset::parse("Large"), Some(MapSizePreset::Large));
+        assert_eq!(MapSizePreset::parse("512"), Some(MapSizePreset::Medium));
+        assert_eq!(MapSizePreset::parse("300"), None);
+        assert_eq!(MapSizePreset::parse("huge"), None);
+    }
+
+    #[test]
+    fn test_seed_from_text() {
+        assert_eq!(RunSettings::seed_from_text("42"), 42);
+        assert_eq!(RunSettings::seed_from_text(" 42 "), 42);
+        assert_eq!(
+            RunSettings::seed_from_text("kraken"),
+            RunSettings::seed_from_text("kraken")
+        );
+        assert_ne!(
+            RunSettings::seed_from_text("kraken"),
+            RunSettings::seed_from_text("leviathan")
+        );
+    }
+}
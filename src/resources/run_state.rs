@@ -0,0 +1,189 @@
+//! The parts of a run that a save has to rebuild by hand.
+//!
+//! Most run state is saved as it stands: `RunSettings` (and with it the map
+//! seed), `PlayerFleet`, `FactionRegistry` and the rest are registered with
+//! bevy_save. The flagship, the ships at sea, accepted contracts and the
+//! explored chart are different: the High Seas are respawned on every visit
+//! and the world is regenerated from its seed when a run is continued from
+//! the main menu, so they are recorded here and applied again once the world
+//! has been rebuilt. Contracts remember their ports by position, since port
+//! entities do not survive a respawn. Companions are entities the save does
+//! not hold, so they are recorded here too.
+
+use bevy::prelude::*;
+
+use crate::components::companion::{CompanionRole, CompanionService};
+use crate::components::contract::{Commission, ContractDetails, ContractProgress, ContractType, PatrolArea};
+use crate::components::{Cargo, FactionId, GoodType, Gold, Health, ShipType};
+
+/// How far a saved port position may be from a respawned port (world units).
+const PORT_MATCH_DISTANCE: f32 = 32.0;
+
+/// The player's flagship on the High Seas.
+#[derive(Clone, Debug, Reflect)]
+pub struct SavedFlagship {
+    pub position: Vec2,
+    pub rotation: Quat,
+    pub health: Health,
+    pub cargo: Cargo,
+    pub gold: u32,
+    #[reflect(default)]
+    pub ship_type: ShipType,
+    /// Her class in `ShipDefinitions`; empty for her hull's own.
+    #[reflect(default)]
+    pub class: String,
+}
+
+impl SavedFlagship {
+    /// Takes her damage, hold and purse as a scene leaves them, for the next
+    /// scene to spawn her with.
+    pub fn keep(&mut self, health: &Health, cargo: &Cargo, gold: &Gold) {
+        self.health = health.clone();
+        self.cargo = cargo.clone();
+        self.gold = gold.0;
+    }
+}
+
+/// An AI ship on the High Seas.
+#[derive(Clone, Debug, Reflect)]
+pub struct SavedAiShip {
+    pub position: Vec2,
+    pub ship_type: ShipType,
+    /// Her class in `ShipDefinitions`; one not in the table falls back to her hull's.
+    #[reflect(default)]
+    pub class: String,
+    pub faction: FactionId,
+    pub health: Health,
+}
+
+/// An accepted contract, with its ports by position.
+#[derive(Clone, Debug, Reflect)]
+pub struct SavedContract {
+    pub contract_type: ContractType,
+    pub origin_port: Option<Vec2>,
+    pub destination: Option<Vec2>,
+    pub reward_gold: u32,
+    pub cargo_required: Option<(GoodType, u32)>,
+    pub description: String,
+    pub expiry_tick: Option<u32>,
+    pub issuer: Option<FactionId>,
+    pub progress: ContractProgress,
+    pub patrol_area: Option<PatrolArea>,
+    #[reflect(default)]
+    pub commission: Option<Commission>,
+}
+
+impl SavedContract {
+    pub fn new(
+        details: &ContractDetails,
+        origin_port: Option<Vec2>,
+        destination: Option<Vec2>,
+        progress: ContractProgress,
+        patrol_area: Option<PatrolArea>,
+        commission: Option<Commission>,
+    ) -> Self {
+        Self {
+            contract_type: details.contract_type,
+            origin_port,
+            destination,
+            reward_gold: details.reward_gold,
+            cargo_required: details.cargo_required,
+            description: details.description.clone(),
+            expiry_tick: details.expiry_tick,
+            issuer: details.issuer,
+            progress,
+            patrol_area,
+            commission,
+        }
+    }
+
+    /// The contract's details, bound to the ports it now refers to. A port
+    /// that could not be found leaves the origin a placeholder.
+    pub fn details(&self, origin_port: Option<Entity>, destination: Option<Entity>) -> ContractDetails {
+        ContractDetails {
+            contract_type: self.contract_type,
+            origin_port: origin_port.unwrap_or(Entity::PLACEHOLDER),
+            destination,
+            reward_gold: self.reward_gold,
+            cargo_required: self.cargo_required,
+            description: self.description.clone(),
+            expiry_tick: self.expiry_tick,
+            issuer: self.issuer,
+        }
+    }
+}
+
+/// A companion serving aboard.
+#[derive(Clone, Debug, Reflect)]
+pub struct SavedCompanion {
+    pub name: String,
+    pub role: CompanionRole,
+    pub service: CompanionService,
+}
+
+/// Run state recorded for a save and applied again after loading.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct RunSnapshot {
+    /// `None` until the player has first been on the High Seas.
+    pub flagship: Option<SavedFlagship>,
+    pub ai_ships: Vec<SavedAiShip>,
+    pub contracts: Vec<SavedContract>,
+    pub explored_tiles: Vec<IVec2>,
+    #[reflect(default)]
+    pub companions: Vec<SavedCompanion>,
+}
+
+impl RunSnapshot {
+    /// The port among `ports` standing at `position`, if any.
+    pub fn find_port(position: Vec2, ports: impl IntoIterator<Item = (Entity, Vec2)>) -> Option<Entity> {
+        ports
+            .into_iter()
+            .map(|(entity, port)| (entity, port.distance(position)))
+            .filter(|(_, distance)| *distance <= PORT_MATCH_DISTANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity)
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Marker resource: a save has just been loaded, and the High Seas should be
+/// rebuilt from `RunSnapshot` the next time they are entered.
+#[derive(Resource, Debug, Default)]
+pub struct PendingRunRestore;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_contract_keeps_its_terms() {
+        let details = ContractDetails::transport_with_expiry(
+            Entity::from_raw(3), Entity::from_raw(4), GoodType::Rum, 12, 240, 100,
+        )
+        .with_issuer(FactionId::NationA);
+        let progress = ContractProgress { cargo_delivered: 5, ..default() };
+        let saved = SavedContract::new(&details, Some(Vec2::new(64.0, 0.0)), None, progress, None, None);
+
+        let restored = saved.details(None, Some(Entity::from_raw(9)));
+        assert_eq!(restored.origin_port, Entity::PLACEHOLDER);
+        assert_eq!(restored.destination, Some(Entity::from_raw(9)));
+        assert_eq!(restored.cargo_required, Some((GoodType::Rum, 12)));
+        assert_eq!(restored.expiry_tick, details.expiry_tick);
+        assert_eq!(restored.issuer, Some(FactionId::NationA));
+        assert_eq!(saved.progress.cargo_delivered, 5);
+    }
+
+    #[test]
+    fn test_find_port_by_position() {
+        let ports = [
+            (Entity::from_raw(1), Vec2::new(0.0, 0.0)),
+            (Entity::from_raw(2), Vec2::new(640.0, 320.0)),
+        ];
+        assert_eq!(RunSnapshot::find_port(Vec2::new(636.0, 322.0), ports), Some(Entity::from_raw(2)));
+        assert_eq!(RunSnapshot::find_port(Vec2::new(300.0, 0.0), ports), None);
+    }
+}
@@ -0,0 +1,92 @@
+//! Sailing ahead: running the High Seas at 2× or 4× speed, or holding them
+//! still.
+//!
+//! Like a rest, the game speed is set on `Time<Virtual>`. Ship movement, AI
+//! orders, the `WorldClock` and every deadline counted on it therefore speed
+//! up or stop together. Speed drops back to 1× whenever the player arrives
+//! somewhere or an encounter begins.
+
+use bevy::prelude::*;
+
+/// How fast the High Seas run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum GameSpeed {
+    Paused,
+    #[default]
+    Normal,
+    Double,
+    Quadruple,
+}
+
+impl GameSpeed {
+    pub const ALL: [GameSpeed; 4] = [GameSpeed::Paused, GameSpeed::Normal, GameSpeed::Double, GameSpeed::Quadruple];
+
+    /// Relative speed of `Time<Virtual>`.
+    pub fn scale(&self) -> f32 {
+        match self {
+            GameSpeed::Paused => 0.0,
+            GameSpeed::Normal => 1.0,
+            GameSpeed::Double => 2.0,
+            GameSpeed::Quadruple => 4.0,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameSpeed::Paused => "⏸",
+            GameSpeed::Normal => "1×",
+            GameSpeed::Double => "2×",
+            GameSpeed::Quadruple => "4×",
+        }
+    }
+
+    /// One step faster, up to 4×.
+    pub fn faster(&self) -> Self {
+        match self {
+            GameSpeed::Paused => GameSpeed::Normal,
+            GameSpeed::Normal => GameSpeed::Double,
+            GameSpeed::Double | GameSpeed::Quadruple => GameSpeed::Quadruple,
+        }
+    }
+
+    /// One step slower, down to paused.
+    pub fn slower(&self) -> Self {
+        match self {
+            GameSpeed::Quadruple => GameSpeed::Double,
+            GameSpeed::Double => GameSpeed::Normal,
+            GameSpeed::Normal | GameSpeed::Paused => GameSpeed::Paused,
+        }
+    }
+}
+
+/// The game speed the player has picked on the High Seas.
+#[derive(Resource, Debug, Default)]
+pub struct SailAhead {
+    pub speed: GameSpeed,
+}
+
+impl SailAhead {
+    /// Back to 1×. Returns whether the speed changed.
+    pub fn drop_to_normal(&mut self) -> bool {
+        let changed = self.speed != GameSpeed::Normal;
+        self.speed = GameSpeed::Normal;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speed_steps_stop_at_the_ends() {
+        assert_eq!(GameSpeed::Normal.faster().faster().faster(), GameSpeed::Quadruple);
+        assert_eq!(GameSpeed::Normal.slower().slower(), GameSpeed::Paused);
+        assert_eq!(GameSpeed::Paused.scale(), 0.0);
+        assert_eq!(GameSpeed::Quadruple.scale(), 4.0);
+
+        let mut sail_ahead = SailAhead { speed: GameSpeed::Double };
+        assert!(sail_ahead.drop_to_normal());
+        assert!(!sail_ahead.drop_to_normal());
+    }
+}
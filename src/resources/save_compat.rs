@@ -0,0 +1,391 @@
+//! Compatibility checks for run saves and the meta-profile.
+//!
+//! Every run save gets a sidecar `SaveManifest` recording the save format,
+//! the game version and a hash of the registered (saveable) type set. Before
+//! a save is loaded its manifest is compared with the running game's; any
+//! mismatch becomes a `SaveIssue` shown in a dialog rather than a silent
+//! default or a failed load.
+//!
+//! Run saves and the profile both carry a version, and each has a registry of
+//! `Migration` steps that upgrade data written by earlier releases one
+//! version at a time. The run save registry lives with the save plugin, since
+//! its steps work on the loaded world.
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::MetaProfile;
+
+/// Layout version of run saves. Bump when saved components change shape, and
+/// add a step to the save plugin's `RUN_SAVE_MIGRATIONS`.
+pub const SAVE_FORMAT_VERSION: u32 = 5;
+/// Layout version of `MetaProfile`. Bump when it changes shape, and add a
+/// step to `PROFILE_MIGRATIONS`. Profiles written before it existed are v0.
+pub const PROFILE_VERSION: u32 = 1;
+/// Version of the running game.
+pub const GAME_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Written next to each run save, describing what wrote it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveManifest {
+    pub format: u32,
+    pub game_version: String,
+    /// `content_hash` of the type set the save was written with.
+    pub content_hash: u64,
+}
+
+impl SaveManifest {
+    /// Manifest for a save written by this build.
+    pub fn current(content_hash: u64) -> Self {
+        Self {
+            format: SAVE_FORMAT_VERSION,
+            game_version: GAME_VERSION.to_string(),
+            content_hash,
+        }
+    }
+
+    /// Path of the manifest for save slot `slot`.
+    pub fn path(slot: &str) -> Option<PathBuf> {
+        MetaProfile::get_save_dir().map(|dir| dir.join("saves").join(format!("{}.meta.json", slot)))
+    }
+
+    /// Reads a slot's manifest. `Ok(None)` means the save predates manifests.
+    pub fn read(slot: &str) -> Result<Option<Self>, String> {
+        let Some(path) = Self::path(slot) else {
+            return Err("Could not determine save directory".to_string());
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read save manifest: {}", e))?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse save manifest: {}", e))
+    }
+
+    /// Writes this manifest for save slot `slot`.
+    pub fn write(&self, slot: &str) -> Result<(), String> {
+        let Some(path) = Self::path(slot) else {
+            return Err("Could not determine save directory".to_string());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create save directory: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize save manifest: {}", e))?;
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write save manifest: {}", e))
+    }
+}
+
+/// Path of the run save in slot `slot`.
+pub fn save_file_path(slot: &str) -> Option<PathBuf> {
+    MetaProfile::get_save_dir().map(|dir| dir.join("saves").join(format!("{}.sav", slot)))
+}
+
+/// Order-independent FNV-1a hash of a set of type paths.
+/// Stable across runs and platforms, unlike `std`'s default hasher.
+pub fn content_hash<'a>(type_paths: impl IntoIterator<Item = &'a str>) -> u64 {
+    let mut paths: Vec<&str> = type_paths.into_iter().collect();
+    paths.sort_unstable();
+    paths.dedup();
+
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for path in paths {
+        for byte in path.bytes().chain(std::iter::once(0)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Why a save may not load cleanly into this build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveIssue {
+    /// Written before saves carried a manifest.
+    MissingManifest,
+    /// Written by an older save format.
+    OlderFormat { found: u32 },
+    /// Written by a newer game than this one.
+    NewerFormat { found: u32, game_version: String },
+    /// Same format, but saved with a different set of game types.
+    ContentMismatch { game_version: String },
+    /// The manifest exists but could not be read.
+    UnreadableManifest(String),
+}
+
+impl SaveIssue {
+    /// Compares a save's manifest against the running game's.
+    pub fn check(found: Option<&SaveManifest>, current: &SaveManifest) -> Option<Self> {
+        let Some(found) = found else {
+            return Some(SaveIssue::MissingManifest);
+        };
+        if found.format < current.format {
+            Some(SaveIssue::OlderFormat { found: found.format })
+        } else if found.format > current.format {
+            Some(SaveIssue::NewerFormat {
+                found: found.format,
+                game_version: found.game_version.clone(),
+            })
+        } else if found.content_hash != current.content_hash {
+            Some(SaveIssue::ContentMismatch {
+                game_version: found.game_version.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Whether loading and re-saving in the current format is worth offering.
+    /// Saves from a newer game cannot be brought back down.
+    pub fn can_migrate(&self) -> bool {
+        !matches!(self, SaveIssue::NewerFormat { .. })
+    }
+
+    /// Plain-language explanation for the dialog.
+    pub fn description(&self) -> String {
+        match self {
+            SaveIssue::MissingManifest => {
+                "This save was made by an earlier version of the game that did not record its version.".to_string()
+            }
+            SaveIssue::OlderFormat { found } => format!(
+                "This save uses an older format (v{}; this game writes v{}). Some details may be lost.",
+                found, SAVE_FORMAT_VERSION
+            ),
+            SaveIssue::NewerFormat { found, game_version } => format!(
+                "This save was made by a newer version of the game ({}, format v{}). Loading it may not work.",
+                game_version, found
+            ),
+            SaveIssue::ContentMismatch { game_version } => format!(
+                "This save was made with different game content ({}). Ships or cargo may be missing.",
+                game_version
+            ),
+            SaveIssue::UnreadableManifest(error) => format!("The save's version information is damaged: {}", error),
+        }
+    }
+}
+
+/// A load waiting on the player's decision.
+#[derive(Debug, Clone)]
+pub enum PendingSave {
+    /// A run save whose manifest does not match this build.
+    Run { slot: String, issue: SaveIssue },
+    /// A profile that failed to parse; the fresh default is in use meanwhile.
+    Profile { contents: String, error: String },
+    /// A load that failed outright.
+    Failed { message: String },
+}
+
+/// What the player chose in the compatibility dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveChoice {
+    /// Load, then rewrite the save (or profile) in the current format.
+    Migrate,
+    /// Load as-is and leave the save untouched.
+    LoadAnyway,
+    /// Do not load (for a profile: keep the fresh one).
+    Cancel,
+}
+
+/// State of the save compatibility dialog.
+#[derive(Resource, Debug, Default)]
+pub struct SaveCompatDialog {
+    pub pending: Option<PendingSave>,
+    pub choice: Option<SaveChoice>,
+}
+
+impl SaveCompatDialog {
+    pub fn open(&mut self, pending: PendingSave) {
+        self.pending = Some(pending);
+        self.choice = None;
+    }
+}
+
+/// One step of a migration registry: upgrades data written at version `from`
+/// to version `from + 1`.
+pub struct Migration<T> {
+    pub from: u32,
+    pub description: &'static str,
+    pub apply: fn(&mut T),
+}
+
+/// Brings `target` from version `from` up to version `to`, applying each
+/// step of `migrations` in turn. Every version in between needs its step.
+pub fn run_migrations<T>(target: &mut T, migrations: &[Migration<T>], from: u32, to: u32) -> Result<(), String> {
+    if from > to {
+        return Err(format!("written by a newer version (v{}; this game writes v{})", from, to));
+    }
+    for version in from..to {
+        let Some(step) = migrations.iter().find(|step| step.from == version) else {
+            return Err(format!("no migration from v{}", version));
+        };
+        info!("Migrating v{} to v{}: {}", version, version + 1, step.description);
+        (step.apply)(target);
+    }
+    Ok(())
+}
+
+/// Upgrades for profiles written by earlier releases, oldest first.
+pub const PROFILE_MIGRATIONS: &[Migration<serde_json::Value>] = &[Migration {
+    from: 0,
+    description: "fill in fields added since the first release",
+    apply: profile_fill_missing_fields,
+}];
+
+/// Unversioned profiles predate several of the fields the profile now
+/// requires; they start out as they would in a fresh profile.
+fn profile_fill_missing_fields(json: &mut serde_json::Value) {
+    let Ok(serde_json::Value::Object(defaults)) = serde_json::to_value(MetaProfile::default()) else {
+        return;
+    };
+    let Some(fields) = json.as_object_mut() else {
+        return;
+    };
+    for (key, value) in defaults {
+        fields.entry(key).or_insert(value);
+    }
+}
+
+/// Parses a profile, first upgrading it through `PROFILE_MIGRATIONS` if it
+/// was written by an earlier release. A profile from a newer release is
+/// parsed as it is, which keeps every field this game knows about.
+pub fn upgrade_profile_json(json: &str) -> Result<MetaProfile, String> {
+    let mut value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let version = value
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .map_or(0, |version| u32::try_from(version).unwrap_or(u32::MAX));
+    if version < PROFILE_VERSION {
+        run_migrations(&mut value, PROFILE_MIGRATIONS, version, PROFILE_VERSION)?;
+        if let Some(fields) = value.as_object_mut() {
+            fields.insert("version".to_string(), PROFILE_VERSION.into());
+        }
+    }
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+/// Best-effort recovery of a profile that no longer parses.
+///
+/// Starts from a fresh profile and keeps every top-level field from `json`
+/// that still fits, so one changed field does not wipe out the rest.
+pub fn migrate_profile_json(json: &str) -> Option<MetaProfile> {
+    let serde_json::Value::Object(old) = serde_json::from_str(json).ok()? else {
+        return None;
+    };
+    let mut merged = serde_json::to_value(MetaProfile::default()).ok()?;
+    for (key, value) in old {
+        let fields = merged.as_object_mut()?;
+        if !fields.contains_key(&key) {
+            continue;
+        }
+        let previous = fields.insert(key.clone(), value);
+        if serde_json::from_value::<MetaProfile>(merged.clone()).is_err() {
+            if let (Some(fields), Some(previous)) = (merged.as_object_mut(), previous) {
+                fields.insert(key, previous);
+            }
+        }
+    }
+    serde_json::from_value(merged).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_ignores_order() {
+        let a = content_hash(["pirates::Ship", "pirates::Gold"]);
+        let b = content_hash(["pirates::Gold", "pirates::Ship", "pirates::Ship"]);
+        assert_eq!(a, b);
+        assert_ne!(a, content_hash(["pirates::Ship"]));
+    }
+
+    #[test]
+    fn test_check_reports_each_mismatch() {
+        let current = SaveManifest::current(7);
+        assert_eq!(SaveIssue::check(Some(&current), &current), None);
+        assert_eq!(SaveIssue::check(None, &current), Some(SaveIssue::MissingManifest));
+
+        let mut older = current.clone();
+        older.format = 0;
+        assert_eq!(SaveIssue::check(Some(&older), &current), Some(SaveIssue::OlderFormat { found: 0 }));
+
+        let mut newer = current.clone();
+        newer.format = SAVE_FORMAT_VERSION + 1;
+        let issue = SaveIssue::check(Some(&newer), &current).unwrap();
+        assert!(!issue.can_migrate());
+
+        let mut other_content = current.clone();
+        other_content.content_hash = 8;
+        assert!(matches!(
+            SaveIssue::check(Some(&other_content), &current),
+            Some(SaveIssue::ContentMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_profile_migration_keeps_readable_fields() {
+        let json = r#"{
+            "stats": { "charisma": 1, "navigation": 1, "logistics": 1 },
+            "unlocked_archetypes": ["Default"],
+            "legacy_wrecks": "not a list",
+            "lifetime_gold": 4200,
+            "runs_completed": 3,
+            "deaths": 2
+        }"#;
+        assert!(serde_json::from_str::<MetaProfile>(json).is_err());
+
+        let profile = migrate_profile_json(json).expect("profile should be recovered");
+        assert_eq!(profile.lifetime_gold, 4200);
+        assert_eq!(profile.runs_completed, 3);
+        assert!(profile.legacy_wrecks.is_empty());
+        assert!(migrate_profile_json("not json").is_none());
+    }
+
+    #[test]
+    fn test_unversioned_profile_is_upgraded() {
+        // An early profile, before captures were counted
+        let json = r#"{
+            "stats": { "charisma": 2, "navigation": 1, "logistics": 1 },
+            "unlocked_archetypes": ["Default"],
+            "legacy_wrecks": [],
+            "lifetime_gold": 900,
+            "runs_completed": 1,
+            "deaths": 4
+        }"#;
+        assert!(serde_json::from_str::<MetaProfile>(json).is_err());
+
+        let profile = upgrade_profile_json(json).expect("profile should be upgraded");
+        assert_eq!(profile.version, PROFILE_VERSION);
+        assert_eq!(profile.stats.charisma, 2);
+        assert_eq!(profile.lifetime_gold, 900);
+        assert_eq!(profile.deaths, 4);
+        assert_eq!(profile.lifetime_captures, 0);
+    }
+
+    #[test]
+    fn test_run_migrations_in_order() {
+        fn push_one(log: &mut Vec<u32>) {
+            log.push(1);
+        }
+        fn push_two(log: &mut Vec<u32>) {
+            log.push(2);
+        }
+        let migrations = [
+            Migration { from: 1, description: "second", apply: push_two },
+            Migration { from: 0, description: "first", apply: push_one },
+        ];
+
+        let mut log = Vec::new();
+        assert!(run_migrations(&mut log, &migrations, 0, 2).is_ok());
+        assert_eq!(log, vec![1, 2]);
+
+        let mut log = Vec::new();
+        assert!(run_migrations(&mut log, &migrations, 1, 2).is_ok());
+        assert_eq!(log, vec![2]);
+
+        assert!(run_migrations(&mut Vec::new(), &migrations, 0, 3).is_err());
+        assert!(run_migrations(&mut Vec::new(), &migrations, 3, 2).is_err());
+    }
+}
@@ -0,0 +1,294 @@
+//! The sea event deck, read from `assets/data/sea_events.ron`.
+//!
+//! While the player sails the High Seas a card may be drawn from the deck
+//! every few hours (see `SeaEventsPlugin`): a survivor adrift, cargo afloat,
+//! fever aboard, a crew on the edge of mutiny. Each card offers choices, and
+//! each choice draws one of its outcomes by weight. An outcome may pay or
+//! cost gold, hands, spirits, goods and standing with the flags.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{Cargo, Crew, FactionId, GoodType, Gold};
+use crate::resources::{ContentTable, FactionRegistry};
+
+/// One way a choice can turn out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeaEventOutcome {
+    /// Relative chance of this outcome among the choice's.
+    pub weight: f32,
+    /// What came of it, for the dialog and the journal.
+    pub text: String,
+    pub gold: i32,
+    /// Hands signed on (berths permitting) or lost.
+    pub crew: i32,
+    /// Change in the crew's spirits.
+    pub morale: f32,
+    /// Goods taken aboard (hold permitting) or lost.
+    pub cargo: Vec<(GoodType, i32)>,
+    /// Change in standing with each flag.
+    pub reputation: Vec<(FactionId, i32)>,
+}
+
+impl Default for SeaEventOutcome {
+    fn default() -> Self {
+        Self {
+            weight: 1.0,
+            text: String::new(),
+            gold: 0,
+            crew: 0,
+            morale: 0.0,
+            cargo: Vec::new(),
+            reputation: Vec::new(),
+        }
+    }
+}
+
+impl SeaEventOutcome {
+    /// Applies the outcome to the player's ship and standing. Returns what
+    /// actually changed, one line each, as the hold, berths and purse allowed.
+    pub fn apply(&self, gold: &mut Gold, cargo: &mut Cargo, crew: &mut Crew, factions: &mut FactionRegistry) -> Vec<String> {
+        let mut effects = Vec::new();
+
+        if self.gold > 0 {
+            gold.add(self.gold as u32);
+            effects.push(format!("+{} gold", self.gold));
+        } else if self.gold < 0 {
+            let paid = gold.0.min(self.gold.unsigned_abs());
+            gold.0 -= paid;
+            effects.push(format!("-{} gold", paid));
+        }
+
+        if self.crew > 0 {
+            let hired = crew.hire(self.crew as u32);
+            if hired > 0 {
+                effects.push(format!("{} hands signed on", hired));
+            }
+        } else if self.crew < 0 {
+            let lost = crew.lose(self.crew.unsigned_abs());
+            if lost > 0 {
+                effects.push(format!("{} hands lost", lost));
+            }
+        }
+
+        if self.morale != 0.0 {
+            crew.morale = (crew.morale + self.morale).clamp(0.0, 1.0);
+            effects.push(if self.morale > 0.0 { "The crew's spirits rise" } else { "The crew's spirits fall" }.to_string());
+        }
+
+        for &(good, amount) in &self.cargo {
+            let moved = if amount > 0 {
+                cargo.add(good, amount as u32) as i32
+            } else {
+                -(cargo.remove(good, amount.unsigned_abs()) as i32)
+            };
+            if moved != 0 {
+                effects.push(format!("{:+} {:?}", moved, good));
+            }
+        }
+
+        for &(faction, change) in &self.reputation {
+            if factions.adjust_reputation(faction, change).is_some() {
+                effects.push(format!("{:+} standing with {}", change, faction.display_name()));
+            }
+        }
+
+        effects
+    }
+}
+
+/// One choice a card offers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeaEventChoice {
+    pub label: String,
+    /// Gold the player must have to make this choice.
+    pub requires_gold: u32,
+    pub outcomes: Vec<SeaEventOutcome>,
+}
+
+impl Default for SeaEventChoice {
+    fn default() -> Self {
+        Self {
+            label: String::new(),
+            requires_gold: 0,
+            outcomes: vec![SeaEventOutcome::default()],
+        }
+    }
+}
+
+impl SeaEventChoice {
+    /// Whether a player with `gold` can make this choice.
+    pub fn affordable(&self, gold: u32) -> bool {
+        gold >= self.requires_gold
+    }
+
+    /// Draws how the choice turns out, by weight.
+    pub fn roll(&self, rng: &mut impl Rng) -> Option<&SeaEventOutcome> {
+        weighted(&self.outcomes, |outcome| outcome.weight, rng)
+    }
+}
+
+/// One card in the deck.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeaEventCard {
+    pub title: String,
+    /// What the player finds, as the dialog tells it.
+    pub text: String,
+    /// Relative chance of drawing it.
+    pub weight: f32,
+    /// First day of the run it can be drawn on.
+    pub min_day: u32,
+    pub choices: Vec<SeaEventChoice>,
+}
+
+impl Default for SeaEventCard {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            text: String::new(),
+            weight: 1.0,
+            min_day: 0,
+            choices: Vec::new(),
+        }
+    }
+}
+
+/// Every sea event card, loaded from `assets/data/sea_events.ron`.
+#[derive(Asset, TypePath, Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeaEventDeck {
+    pub cards: Vec<SeaEventCard>,
+}
+
+impl ContentTable for SeaEventDeck {
+    const ASSET_PATH: &'static str = "data/sea_events.ron";
+    const NAME: &'static str = "sea event";
+}
+
+impl SeaEventDeck {
+    /// Draws the index of a card that can come up on `day`, by weight; `None`
+    /// if none can.
+    pub fn draw(&self, day: u32, rng: &mut impl Rng) -> Option<usize> {
+        let playable: Vec<(usize, &SeaEventCard)> = self
+            .cards
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| day >= card.min_day && !card.choices.is_empty())
+            .collect();
+        weighted(&playable, |(_, card)| card.weight, rng).map(|(index, _)| *index)
+    }
+}
+
+/// Draws one of `items` by `weight`, passing over any weighted zero or less.
+fn weighted<'a, T>(items: &'a [T], weight: impl Fn(&T) -> f32, rng: &mut impl Rng) -> Option<&'a T> {
+    let total: f32 = items.iter().map(|item| weight(item).max(0.0)).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut roll = rng.gen_range(0.0..total);
+    for item in items.iter().filter(|item| weight(item) > 0.0) {
+        if roll < weight(item) {
+            return Some(item);
+        }
+        roll -= weight(item);
+    }
+    items.iter().rev().find(|item| weight(item) > 0.0)
+}
+
+/// What came of a card, shown until the player sails on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeaEventResult {
+    pub title: String,
+    pub text: String,
+    pub effects: Vec<String>,
+}
+
+/// The card in front of the player, if any.
+#[derive(Resource, Debug, Default)]
+pub struct SeaEventState {
+    /// Index into `SeaEventDeck::cards` of the card awaiting a choice.
+    pub pending: Option<usize>,
+    /// Index of the choice made, awaiting its outcome.
+    pub chosen: Option<usize>,
+    /// What came of the last choice.
+    pub result: Option<SeaEventResult>,
+    /// Whether the card stopped `Time<Virtual>`, and so should start it again.
+    pub stopped_world: bool,
+}
+
+impl SeaEventState {
+    /// Whether a card or its outcome is on screen.
+    pub fn is_open(&self) -> bool {
+        self.pending.is_some() || self.result.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_shipped_deck_parses_and_every_card_can_be_answered() {
+        let deck = SeaEventDeck::from_ron(include_str!("../../assets/data/sea_events.ron")).unwrap();
+        assert!(deck.cards.len() >= 4);
+        for card in &deck.cards {
+            assert!(!card.choices.is_empty(), "{} offers no choice", card.title);
+            // Some way through every card costs nothing up front
+            assert!(card.choices.iter().any(|choice| choice.affordable(0)), "{} needs gold", card.title);
+            for choice in &card.choices {
+                assert!(choice.outcomes.iter().any(|outcome| outcome.weight > 0.0), "{} / {}", card.title, choice.label);
+            }
+        }
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        assert!(deck.draw(0, &mut rng).is_some());
+    }
+
+    #[test]
+    fn test_draw_respects_day_and_weight() {
+        let deck = SeaEventDeck::from_ron(
+            r#"(cards: [
+                (title: "Early", choices: [(label: "Ok")]),
+                (title: "Late", min_day: 10, weight: 5.0, choices: [(label: "Ok")]),
+                (title: "Never", weight: 0.0, choices: [(label: "Ok")]),
+                (title: "Mute"),
+            ])"#,
+        )
+        .unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+        for _ in 0..20 {
+            assert_eq!(deck.draw(2, &mut rng), Some(0));
+            assert_ne!(deck.draw(12, &mut rng), Some(2));
+        }
+    }
+
+    #[test]
+    fn test_outcome_applies_only_what_the_ship_allows() {
+        let mut gold = Gold(30);
+        let mut cargo = Cargo::new(10);
+        let mut crew = Crew::for_ship(crate::components::ShipType::Sloop);
+        let hands = crew.hands;
+        let mut factions = FactionRegistry::new();
+        let outcome = SeaEventOutcome {
+            gold: -50,
+            crew: -2,
+            morale: 0.1,
+            cargo: vec![(GoodType::Rum, 15), (GoodType::Fish, -5)],
+            reputation: vec![(FactionId::NationA, 5)],
+            ..default()
+        };
+
+        let effects = outcome.apply(&mut gold, &mut cargo, &mut crew, &mut factions);
+        assert_eq!(gold.0, 0);
+        assert_eq!(crew.hands, hands - 2);
+        assert_eq!(cargo.goods.get(&GoodType::Rum), Some(&10));
+        assert!(effects.contains(&"-30 gold".to_string()));
+        // No fish aboard to lose
+        assert!(!effects.iter().any(|line| line.contains("Fish")));
+        assert_eq!(factions.reputation(FactionId::NationA), 5);
+    }
+}
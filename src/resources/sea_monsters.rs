@@ -0,0 +1,151 @@
+//! Sea monsters: rare beasts roaming the open sea.
+//!
+//! On the hour a kraken or a leviathan may surface near the player (never
+//! with the "Historical waters" modifier). It roams until the player leaves
+//! the High Seas, and a ship that strays within reach of it must fight. In
+//! battle it seizes ships with its tentacles or coils and crushes them, and
+//! when slain gives up the hoard of the ships it has dragged down.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{Cargo, GoodType};
+
+/// Chance per hour that a sea monster surfaces near the player.
+pub const SEA_MONSTER_CHANCE: f64 = 0.01;
+/// How far from the player a sea monster surfaces (world units).
+pub const MONSTER_SURFACING_DISTANCE: f32 = 800.0;
+/// How close a ship may come to a sea monster before it attacks (world units).
+pub const MONSTER_ENCOUNTER_RADIUS: f32 = 96.0;
+
+/// The sea monsters there are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MonsterKind {
+    /// A vast squid that seizes ships with its many tentacles.
+    Kraken,
+    /// A great sea serpent that wraps ships in its coils.
+    Leviathan,
+}
+
+impl MonsterKind {
+    pub fn all() -> &'static [MonsterKind] {
+        &[MonsterKind::Kraken, MonsterKind::Leviathan]
+    }
+
+    /// A monster of either kind, the kraken the more often.
+    pub fn roll(rng: &mut impl Rng) -> Self {
+        if rng.gen_bool(0.6) {
+            MonsterKind::Kraken
+        } else {
+            MonsterKind::Leviathan
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MonsterKind::Kraken => "The Kraken",
+            MonsterKind::Leviathan => "The Leviathan",
+        }
+    }
+
+    /// What it seizes ships with.
+    pub fn limb_name(&self) -> &'static str {
+        match self {
+            MonsterKind::Kraken => "Tentacle",
+            MonsterKind::Leviathan => "Coil",
+        }
+    }
+
+    /// Hull of its body.
+    pub fn hull(&self) -> f32 {
+        match self {
+            MonsterKind::Kraken => 400.0,
+            MonsterKind::Leviathan => 550.0,
+        }
+    }
+
+    /// Tentacles or coils it fights with.
+    pub fn limbs(&self) -> usize {
+        match self {
+            MonsterKind::Kraken => 6,
+            MonsterKind::Leviathan => 3,
+        }
+    }
+
+    /// Hull of each tentacle or coil; one shot away loses its grip.
+    pub fn limb_hull(&self) -> f32 {
+        match self {
+            MonsterKind::Kraken => 30.0,
+            MonsterKind::Leviathan => 60.0,
+        }
+    }
+
+    /// Radius of its body in the arena.
+    pub fn body_radius(&self) -> f32 {
+        match self {
+            MonsterKind::Kraken => 70.0,
+            MonsterKind::Leviathan => 55.0,
+        }
+    }
+
+    /// How fast it swims in battle (world units per second).
+    pub fn swim_speed(&self) -> f32 {
+        match self {
+            MonsterKind::Kraken => 45.0,
+            MonsterKind::Leviathan => 95.0,
+        }
+    }
+
+    /// How far from its body its tentacles or coils reach.
+    pub fn reach(&self) -> f32 {
+        match self {
+            MonsterKind::Kraken => 260.0,
+            MonsterKind::Leviathan => 190.0,
+        }
+    }
+
+    /// Hull damage each second to a ship in its grip.
+    pub fn crush_per_second(&self) -> f32 {
+        match self {
+            MonsterKind::Kraken => 3.0,
+            MonsterKind::Leviathan => 6.0,
+        }
+    }
+
+    /// Gold and goods from the ships it has dragged down, found when it is slain.
+    pub fn hoard(&self) -> (u32, Cargo) {
+        let (gold, goods): (u32, &[(GoodType, u32)]) = match self {
+            MonsterKind::Kraken => (900, &[(GoodType::Spices, 12), (GoodType::Rum, 10)]),
+            MonsterKind::Leviathan => (1400, &[(GoodType::Weapons, 8), (GoodType::Cloth, 14)]),
+        };
+        let mut hold = Cargo::new(goods.iter().map(|(_, amount)| amount).sum());
+        for &(good, amount) in goods {
+            hold.add(good, amount);
+        }
+        (gold, hold)
+    }
+}
+
+/// The sea monster the player is caught up with.
+#[derive(Resource, Debug, Default)]
+pub struct SeaMonsterState {
+    /// The current battle is against this monster.
+    pub engaged: Option<MonsterKind>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_monster_has_a_hoard_and_limbs() {
+        for kind in MonsterKind::all() {
+            let (gold, hold) = kind.hoard();
+            assert!(gold > 0);
+            assert!(hold.total_units() > 0 && hold.is_full());
+            assert!(kind.limbs() > 0);
+            assert!(kind.reach() > kind.body_radius());
+        }
+    }
+}
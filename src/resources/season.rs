@@ -0,0 +1,110 @@
+//! The seasons of the calendar and what they do to the sea and the markets.
+//!
+//! Winter brings steady trade winds and few storms, spring the sugar harvest
+//! that gluts the markets, summer slack winds and the first hurricanes, and
+//! autumn the height of the hurricane season. See `CalendarDate::season`.
+
+use crate::components::GoodType;
+
+/// Season of the year, three months each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Season {
+    Winter,
+    Spring,
+    Summer,
+    Autumn,
+}
+
+impl Season {
+    /// The season of a month (0 = January).
+    pub fn of_month(month: u32) -> Self {
+        match month % 12 {
+            11 | 0 | 1 => Season::Winter,
+            2..=4 => Season::Spring,
+            5..=7 => Season::Summer,
+            _ => Season::Autumn,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Season::Winter => "Winter",
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Season::Winter => "🌬",
+            Season::Spring => "🌾",
+            Season::Summer => "☀",
+            Season::Autumn => "🌀",
+        }
+    }
+
+    /// What the season means for a sailor and a trader.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Season::Winter => "Strong trade winds and few storms. Fruit and fish are dear.",
+            Season::Spring => "The sugar harvest is in: sugar and rum are cheap everywhere.",
+            Season::Summer => "Slack winds and the first hurricanes. Fruit is plentiful.",
+            Season::Autumn => "The height of the hurricane season. Sugar runs short before the harvest.",
+        }
+    }
+
+    /// Multiplier on the wind's strength.
+    pub fn wind_factor(&self) -> f32 {
+        match self {
+            Season::Winter => 1.2,
+            Season::Spring => 1.0,
+            Season::Summer => 0.85,
+            Season::Autumn => 1.0,
+        }
+    }
+
+    /// Multiplier on the chance a hurricane forms.
+    pub fn storm_factor(&self) -> f32 {
+        match self {
+            Season::Winter => 0.3,
+            Season::Spring => 0.6,
+            Season::Summer => 1.3,
+            Season::Autumn => 1.8,
+        }
+    }
+
+    /// Multiplier on what a good fetches in port this season.
+    pub fn price_factor(&self, good: GoodType) -> f32 {
+        match (self, good) {
+            (Season::Spring, GoodType::Sugar) => 0.7,
+            (Season::Spring, GoodType::Rum) => 0.9,
+            (Season::Autumn, GoodType::Sugar) => 1.2,
+            (Season::Summer, GoodType::Fruit) => 0.8,
+            (Season::Winter, GoodType::Fruit) => 1.2,
+            (Season::Winter, GoodType::Fish) => 1.15,
+            _ => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{CalendarDate, DAYS_PER_MONTH};
+
+    #[test]
+    fn test_seasons_follow_the_months() {
+        assert_eq!(CalendarDate::from_day(1).season(), Season::Winter);
+        assert_eq!(CalendarDate::from_day(3 * DAYS_PER_MONTH).season(), Season::Spring);
+        assert_eq!(Season::of_month(11), Season::Winter);
+        assert_eq!(Season::of_month(9), Season::Autumn);
+    }
+
+    #[test]
+    fn test_harvest_gluts_sugar_and_autumn_brews_storms() {
+        assert!(Season::Spring.price_factor(GoodType::Sugar) < 1.0);
+        assert_eq!(Season::Spring.price_factor(GoodType::Weapons), 1.0);
+        assert!(Season::Autumn.storm_factor() > Season::Winter.storm_factor());
+    }
+}
@@ -0,0 +1,195 @@
+//! Player options that hold across runs: the window, the UI layout and scale,
+//! sound, camera shake, how often the game autosaves and whether docking plays
+//! the harbour approach.
+//!
+//! Stored as `settings.json` beside the profile (see `MetaProfile::get_save_dir`).
+//! Fields missing from an older file take their defaults, and values out of
+//! range are pulled back in when the file is read.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{LayoutPreference, MetaProfile};
+
+/// Window sizes offered for windowed play (logical pixels).
+pub const RESOLUTIONS: [(u32, u32); 5] = [(1280, 720), (1280, 800), (1600, 900), (1920, 1080), (2560, 1440)];
+/// Smallest and largest UI scale.
+pub const UI_SCALE_RANGE: (f32, f32) = (0.75, 1.5);
+/// Autosave intervals offered on the High Seas, in minutes; 0 saves only on
+/// coming into and leaving port.
+pub const AUTOSAVE_CHOICES: [u32; 5] = [0, 5, 10, 15, 30];
+
+/// File name for settings storage.
+const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// How the game window is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    /// A window without borders covering the screen.
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub const ALL: [DisplayMode; 3] = [DisplayMode::Windowed, DisplayMode::Borderless, DisplayMode::Fullscreen];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "Windowed",
+            DisplayMode::Borderless => "Borderless",
+            DisplayMode::Fullscreen => "Fullscreen",
+        }
+    }
+}
+
+/// The player's options.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    pub display_mode: DisplayMode,
+    /// Window size when windowed.
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    /// Standard or compact layout, or `Auto` to follow the window size.
+    pub ui_layout: LayoutPreference,
+    /// Multiplies the UI zoom of the current layout.
+    pub ui_scale: f32,
+    /// Volumes, 0.0 to 1.0. Music and effects are also scaled by the master volume.
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub effects_volume: f32,
+    /// Share of the full camera shake, 0.0 (none) to 1.0.
+    pub screen_shake: f32,
+    /// Minutes between autosaves on the High Seas (see `AUTOSAVE_CHOICES`).
+    pub autosave_minutes: u32,
+    /// Whether docking at a town or city plays the harbour approach scene.
+    pub harbor_approach: bool,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            display_mode: DisplayMode::Windowed,
+            resolution: (1280, 720),
+            vsync: true,
+            ui_layout: LayoutPreference::Auto,
+            ui_scale: 1.0,
+            master_volume: 1.0,
+            music_volume: 1.0,
+            effects_volume: 1.0,
+            screen_shake: 1.0,
+            autosave_minutes: 10,
+            harbor_approach: true,
+        }
+    }
+}
+
+impl GameSettings {
+    /// Volume for music, with the master volume applied.
+    pub fn music_gain(&self) -> f32 {
+        self.master_volume * self.music_volume
+    }
+
+    /// Volume for sound effects and ambience, with the master volume applied.
+    pub fn effects_gain(&self) -> f32 {
+        self.master_volume * self.effects_volume
+    }
+
+    /// Real seconds of unpaused play between autosaves on the High Seas, if they are on.
+    pub fn autosave_interval(&self) -> Option<f32> {
+        (self.autosave_minutes > 0).then_some(self.autosave_minutes as f32 * 60.0)
+    }
+
+    /// The settings with every value pulled back into its range.
+    pub fn clamped(mut self) -> Self {
+        self.resolution = (self.resolution.0.max(640), self.resolution.1.max(480));
+        self.ui_scale = self.ui_scale.clamp(UI_SCALE_RANGE.0, UI_SCALE_RANGE.1);
+        self.master_volume = self.master_volume.clamp(0.0, 1.0);
+        self.music_volume = self.music_volume.clamp(0.0, 1.0);
+        self.effects_volume = self.effects_volume.clamp(0.0, 1.0);
+        self.screen_shake = self.screen_shake.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Parses settings from `json`, filling in what is missing.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str::<Self>(json).map(Self::clamped).map_err(|e| e.to_string())
+    }
+
+    /// Returns the platform-specific path for the settings file.
+    pub fn get_save_path() -> Option<std::path::PathBuf> {
+        MetaProfile::get_save_dir().map(|dir| dir.join(SETTINGS_FILE_NAME))
+    }
+
+    /// Loads the settings from the default save location, or the defaults if
+    /// there are none or they cannot be read.
+    pub fn load_from_file() -> Self {
+        let Some(path) = Self::get_save_path().filter(|path| path.exists()) else {
+            return Self::default();
+        };
+        match std::fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|json| Self::from_json(&json)) {
+            Ok(settings) => {
+                info!("Loaded settings from {:?}", path);
+                settings
+            }
+            Err(e) => {
+                error!("Failed to read settings file, using defaults: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Saves the settings to the default save location.
+    pub fn save_to_file(&self) -> Result<(), String> {
+        let (Some(dir), Some(path)) = (MetaProfile::get_save_dir(), Self::get_save_path()) else {
+            return Err("Could not determine save directory".to_string());
+        };
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create save directory: {}", e))?;
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write settings file: {}", e))?;
+        info!("Saved settings to {:?}", path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_round_trip_and_fill_missing_fields() {
+        let settings = GameSettings {
+            display_mode: DisplayMode::Borderless,
+            music_volume: 0.25,
+            autosave_minutes: 0,
+            ui_layout: LayoutPreference::Compact,
+            ..default()
+        };
+        let json = serde_json::to_string(&settings).unwrap();
+        assert_eq!(GameSettings::from_json(&json), Ok(settings));
+
+        // A file from before a setting existed keeps the default for it
+        let old = GameSettings::from_json(r#"{ "vsync": false }"#).unwrap();
+        assert!(!old.vsync);
+        assert_eq!(old.screen_shake, 1.0);
+        assert_eq!(old.ui_layout, LayoutPreference::Auto);
+        assert!(old.harbor_approach);
+        assert!(GameSettings::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_pulled_in() {
+        let settings = GameSettings::from_json(r#"{ "ui_scale": 9.0, "master_volume": -1.0, "screen_shake": 2.0 }"#).unwrap();
+        assert_eq!(settings.ui_scale, UI_SCALE_RANGE.1);
+        assert_eq!(settings.master_volume, 0.0);
+        assert_eq!(settings.screen_shake, 1.0);
+
+        let quiet = GameSettings { master_volume: 0.5, music_volume: 0.5, ..default() };
+        assert_eq!(quiet.music_gain(), 0.25);
+        assert_eq!(quiet.effects_gain(), 0.5);
+        assert_eq!(GameSettings { autosave_minutes: 0, ..default() }.autosave_interval(), None);
+        assert_eq!(GameSettings::default().autosave_interval(), Some(600.0));
+    }
+}
@@ -0,0 +1,239 @@
+//! Ship classes, read from `assets/data/ships.ron`.
+//!
+//! Each class is keyed by a string id and sets a ship's speed, hull, hold,
+//! sprite and shipyard price, and how often she turns up at sea. Its `hull`
+//! is the `ShipType` she handles as: turning, heeling, sail plan, and how
+//! heavy the pacing director reckons her. New classes need only a new entry
+//! in the file; archetypes start the player in one by id, shipyards build the
+//! ones marked for sale, and AI ships are drawn from the spawn weights. The
+//! four plain classes (one per `ShipType`, with ids from
+//! `ShipType::class_id`) are compiled in from the shipped file, and a class
+//! missing from the table falls back to the plain class of her hull.
+
+use std::sync::LazyLock;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{ShipClass, ShipType};
+use crate::resources::ContentTable;
+
+/// One class of ship.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ShipEntry")]
+pub struct ShipDefinition {
+    /// Key other data refers to the class by.
+    pub id: String,
+    /// Display name.
+    pub name: String,
+    /// Hull form she handles as.
+    pub hull: ShipType,
+    /// Sprite, relative to `assets/`.
+    pub sprite: String,
+    /// Top speed on the High Seas.
+    pub speed: f32,
+    /// Maximum hull of a new ship.
+    pub hull_strength: f32,
+    /// Cargo capacity.
+    pub cargo: u32,
+    /// Price of a new ship at a neutral port with timber at its base price.
+    pub price: u32,
+    /// Whether shipyards build her.
+    pub for_sale: bool,
+    /// How often she is drawn for an AI ship at sea, against the other classes.
+    pub spawn_weight: f32,
+}
+
+/// A class as written in the file, where any field left out keeps the
+/// shipped plain sloop's value. Only an entry missing a field looks the sloop
+/// up, so the shipped table (which spells every field out) reads itself.
+#[derive(Deserialize)]
+struct ShipEntry {
+    #[serde(default, deserialize_with = "present")]
+    id: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    name: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    hull: Option<ShipType>,
+    #[serde(default, deserialize_with = "present")]
+    sprite: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    speed: Option<f32>,
+    #[serde(default, deserialize_with = "present")]
+    hull_strength: Option<f32>,
+    #[serde(default, deserialize_with = "present")]
+    cargo: Option<u32>,
+    #[serde(default, deserialize_with = "present")]
+    price: Option<u32>,
+    #[serde(default, deserialize_with = "present")]
+    for_sale: Option<bool>,
+    #[serde(default, deserialize_with = "present")]
+    spawn_weight: Option<f32>,
+}
+
+/// Reads a field that is written out as its plain value.
+fn present<'de, D: serde::Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
+
+impl From<ShipEntry> for ShipDefinition {
+    fn from(entry: ShipEntry) -> Self {
+        let sloop = || ShipDefinition::builtin(ShipType::Sloop);
+        Self {
+            id: entry.id.unwrap_or_else(|| sloop().id),
+            name: entry.name.unwrap_or_else(|| sloop().name),
+            hull: entry.hull.unwrap_or_else(|| sloop().hull),
+            sprite: entry.sprite.unwrap_or_else(|| sloop().sprite),
+            speed: entry.speed.unwrap_or_else(|| sloop().speed),
+            hull_strength: entry.hull_strength.unwrap_or_else(|| sloop().hull_strength),
+            cargo: entry.cargo.unwrap_or_else(|| sloop().cargo),
+            price: entry.price.unwrap_or_else(|| sloop().price),
+            for_sale: entry.for_sale.unwrap_or_else(|| sloop().for_sale),
+            spawn_weight: entry.spawn_weight.unwrap_or_else(|| sloop().spawn_weight),
+        }
+    }
+}
+
+impl Default for ShipDefinition {
+    fn default() -> Self {
+        Self::builtin(ShipType::default())
+    }
+}
+
+impl ShipDefinition {
+    /// The plain class built on `hull`, as shipped in `assets/data/ships.ron`.
+    pub fn builtin(hull: ShipType) -> Self {
+        BUILTIN
+            .get(hull.class_id())
+            .cloned()
+            .unwrap_or_else(|| panic!("assets/data/ships.ron has no plain {} class", hull.class_id()))
+    }
+
+    /// Sprite an AI ship of this class sails under. Sloops fly the enemy
+    /// colours, so they are not mistaken for the player's; other hulls use
+    /// their class's sprite.
+    pub fn enemy_sprite(&self) -> &str {
+        match self.hull {
+            ShipType::Sloop => ENEMY_SLOOP_SPRITE,
+            _ => &self.sprite,
+        }
+    }
+}
+
+/// Sprite of AI sloops, relative to `assets/`.
+const ENEMY_SLOOP_SPRITE: &str = "sprites/ships/enemy.png";
+
+/// The shipped table, compiled in so every hull keeps her plain class
+/// however the file on disk is edited.
+static BUILTIN: LazyLock<ShipDefinitions> = LazyLock::new(|| {
+    ShipDefinitions::from_ron(include_str!("../../assets/data/ships.ron"))
+        .expect("assets/data/ships.ron should parse")
+});
+
+/// Every ship class, in shipyard display order.
+#[derive(Asset, TypePath, Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShipDefinitions {
+    #[serde(default = "shipped_classes")]
+    pub ships: Vec<ShipDefinition>,
+}
+
+fn shipped_classes() -> Vec<ShipDefinition> {
+    BUILTIN.ships.clone()
+}
+
+impl Default for ShipDefinitions {
+    fn default() -> Self {
+        BUILTIN.clone()
+    }
+}
+
+impl ContentTable for ShipDefinitions {
+    const ASSET_PATH: &'static str = "data/ships.ron";
+    const NAME: &'static str = "ship class";
+}
+
+impl ShipDefinitions {
+    pub fn get(&self, id: &str) -> Option<&ShipDefinition> {
+        self.ships.iter().find(|ship| ship.id == id)
+    }
+
+    /// The class `id`, or the plain class of `hull` if there is no such class.
+    pub fn definition(&self, id: &str, hull: ShipType) -> ShipDefinition {
+        self.get(id).cloned().unwrap_or_else(|| ShipDefinition::builtin(hull))
+    }
+
+    /// Sprite an AI ship of the plain class of `hull` sails under.
+    pub fn enemy_sprite(&self, hull: ShipType) -> String {
+        self.definition(hull.class_id(), hull).enemy_sprite().to_string()
+    }
+
+    /// Top speed of a ship of `class`, or of the plain class of her `hull`.
+    pub fn speed(&self, class: Option<&ShipClass>, hull: ShipType) -> f32 {
+        class
+            .and_then(|class| self.get(&class.0))
+            .map_or_else(|| ShipDefinition::builtin(hull).speed, |ship| ship.speed)
+    }
+
+    /// The classes shipyards build, in display order.
+    pub fn for_sale(&self) -> impl Iterator<Item = &ShipDefinition> {
+        self.ships.iter().filter(|ship| ship.for_sale)
+    }
+
+    /// Class for a new AI ship at sea, drawn by spawn weight; a plain sloop
+    /// if no class has any weight.
+    pub fn roll_spawn(&self, rng: &mut impl Rng) -> ShipDefinition {
+        let total: f32 = self.ships.iter().map(|ship| ship.spawn_weight.max(0.0)).sum();
+        if total <= 0.0 {
+            return ShipDefinition::default();
+        }
+        let mut roll = rng.gen::<f32>() * total;
+        self.ships
+            .iter()
+            .filter(|ship| ship.spawn_weight > 0.0)
+            .find(|ship| {
+                roll -= ship.spawn_weight;
+                roll < 0.0
+            })
+            .or_else(|| self.ships.iter().rfind(|ship| ship.spawn_weight > 0.0))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_shipped_classes_keep_builtin_hulls_and_add_new_ones() {
+        let shipped = ShipDefinitions::default();
+        for hull in [ShipType::Sloop, ShipType::Schooner, ShipType::Frigate, ShipType::Raft] {
+            assert_eq!(ShipDefinition::builtin(hull).hull, hull);
+        }
+        for id in ["galleon", "brig", "cutter"] {
+            assert!(shipped.get(id).is_some_and(|ship| ship.for_sale && ship.spawn_weight > 0.0));
+        }
+
+        // Unknown classes fall back to their hull
+        assert_eq!(shipped.definition("man_o_war", ShipType::Frigate), ShipDefinition::builtin(ShipType::Frigate));
+        assert_eq!(shipped.speed(Some(&ShipClass("man_o_war".to_string())), ShipType::Raft), 150.0);
+        assert_eq!(shipped.speed(None, ShipType::Sloop), 300.0);
+    }
+
+    #[test]
+    fn test_spawns_follow_weights() {
+        let table = ShipDefinitions::from_ron(
+            r#"(ships: [(id: "cutter", hull: Sloop, spawn_weight: 1.0), (id: "hulk", hull: Frigate, spawn_weight: 0.0)])"#,
+        )
+        .unwrap();
+        // Fields left out keep the shipped sloop's
+        assert_eq!(table.get("hulk").unwrap().cargo, ShipDefinition::builtin(ShipType::Sloop).cargo);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            assert_eq!(table.roll_spawn(&mut rng).id, "cutter");
+        }
+        assert_eq!(ShipDefinitions { ships: Vec::new() }.roll_spawn(&mut rng), ShipDefinition::default());
+    }
+}
@@ -0,0 +1,105 @@
+//! The ship market: buying new hulls at port and selling fleet ships.
+//!
+//! Every port's shipyard builds the ship classes `ShipDefinitions` marks for
+//! sale to order, and buys any fleet ship the player wants rid of. Prices
+//! follow the port's `TradeTerms` (so the player's standing with its flag
+//! counts), and the local price of timber, which makes a yard in a timber-starved port dear and one
+//! in a timber port cheap. A ship sold fetches a share of a new hull's price,
+//! less the damage she carries.
+
+use crate::components::cargo::Cargo;
+use crate::resources::{ShipData, ShipDefinition, ShipDefinitions, TradeTerms};
+use crate::systems::economy::price_config::{MAX_PRICE_MULTIPLIER, MIN_PRICE_MULTIPLIER};
+
+/// Share of a new hull's price a sound ship fetches when sold.
+pub const RESALE_SHARE: f32 = 0.6;
+/// Share of a hull's price that is timber, and follows the local timber price.
+const TIMBER_SHARE: f32 = 0.3;
+
+/// A new ship of class `ship` from the yard, sound and with an empty hold.
+pub fn new_hull(ship: &ShipDefinition, name: String) -> ShipData {
+    ShipData {
+        sprite_path: ship.sprite.clone(),
+        hull_health: ship.hull_strength,
+        max_hull_health: ship.hull_strength,
+        cargo: Some(Cargo::new(ship.cargo)),
+        name,
+        upgrades: Vec::new(),
+        ship_type: ship.hull,
+        class: ship.id.clone(),
+    }
+}
+
+/// What a port's shipyard charges for hulls and pays for ships.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShipMarketTerms {
+    pub buy: f32,
+    pub sell: f32,
+}
+
+impl ShipMarketTerms {
+    /// Terms at a port trading on `trade` terms, where timber sells at
+    /// `timber_ratio` times its base price (or at its base price if the port
+    /// has none).
+    pub fn new(trade: TradeTerms, timber_ratio: Option<f32>) -> Self {
+        let timber = timber_ratio.unwrap_or(1.0).clamp(MIN_PRICE_MULTIPLIER, MAX_PRICE_MULTIPLIER);
+        let yard = 1.0 + TIMBER_SHARE * (timber - 1.0);
+        Self {
+            buy: trade.buy * yard,
+            sell: trade.sell * yard,
+        }
+    }
+
+    /// Price of a new ship of class `ship`.
+    pub fn hull_price(&self, ship: &ShipDefinition) -> u32 {
+        (ship.price as f32 * self.buy).round() as u32
+    }
+
+    /// Gold offered for `ship`, by her class in `ships` and the state of her hull.
+    pub fn sale_price(&self, ship: &ShipData, ships: &ShipDefinitions) -> u32 {
+        let condition = if ship.max_hull_health > 0.0 {
+            (ship.hull_health / ship.max_hull_health).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let price = ships.definition(&ship.class, ship.ship_type).price;
+        (price as f32 * RESALE_SHARE * condition * self.sell).round() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::ship::ShipType;
+
+    #[test]
+    fn test_timber_and_standing_move_prices() {
+        let schooner = ShipDefinition::builtin(ShipType::Schooner);
+        let frigate = ShipDefinition::builtin(ShipType::Frigate);
+        let neutral = ShipMarketTerms::new(TradeTerms { buy: 1.0, sell: 1.0 }, None);
+        assert_eq!(neutral.hull_price(&schooner), 1500);
+
+        let scarce = ShipMarketTerms::new(TradeTerms { buy: 1.0, sell: 1.0 }, Some(2.0));
+        assert_eq!(scarce.hull_price(&schooner), 1950);
+
+        let friendly = ShipMarketTerms::new(TradeTerms { buy: 0.9, sell: 1.1 }, None);
+        assert!(friendly.hull_price(&frigate) < neutral.hull_price(&frigate));
+    }
+
+    #[test]
+    fn test_damaged_ships_sell_for_less() {
+        let terms = ShipMarketTerms::new(TradeTerms { buy: 1.0, sell: 1.0 }, None);
+        let ships = ShipDefinitions::default();
+        let sloop = ShipDefinition::builtin(ShipType::Sloop);
+        let mut ship = new_hull(&sloop, "Swift".to_string());
+        assert_eq!(terms.sale_price(&ship, &ships), 480);
+        assert!(terms.sale_price(&ship, &ships) < terms.hull_price(&sloop));
+
+        ship.hull_health = ship.max_hull_health / 2.0;
+        assert_eq!(terms.sale_price(&ship, &ships), 240);
+
+        // A ship of a class no longer in the table sells by her hull
+        ship.class = "man_o_war".to_string();
+        assert_eq!(terms.sale_price(&ship, &ships), 240);
+    }
+}
@@ -0,0 +1,314 @@
+//! Spoils of a won battle, picked over on the loot screen before leaving combat.
+//!
+//! Each ship that struck her colours is also a prize, and the player decides
+//! her fate (`PrizeChoice`): sail her home in the fleet, let her go with her
+//! hold plundered, press her hands into the crew, or ransom her officers.
+
+use bevy::prelude::*;
+
+use crate::components::cargo::{Cargo, GoodType, Gold};
+use crate::components::{Crew, FactionId};
+use crate::resources::ShipData;
+
+/// Share of a sunk ship's gold and cargo that can be fished out of the wreckage.
+pub const SUNK_SALVAGE_FRACTION: f32 = 0.5;
+/// Ransom paid for a prize's officers, per berth of her hull.
+pub const RANSOM_PER_BERTH: u32 = 8;
+/// Share of a prize's hands that can be pressed into the player's crew.
+pub const PRESSED_SHARE: f32 = 0.5;
+
+/// What becomes of a surrendered ship.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrizeChoice {
+    /// A prize crew sails her home in the fleet.
+    #[default]
+    TakeShip,
+    /// Her hold is plundered and she is let go.
+    Release,
+    /// Her hands are pressed into the crew and the hulk is scuttled.
+    PressCrew,
+    /// Her officers are ransomed and she is let go.
+    Ransom,
+}
+
+impl PrizeChoice {
+    pub const ALL: [PrizeChoice; 4] =
+        [PrizeChoice::TakeShip, PrizeChoice::Release, PrizeChoice::PressCrew, PrizeChoice::Ransom];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrizeChoice::TakeShip => "Take her",
+            PrizeChoice::Release => "Let her go",
+            PrizeChoice::PressCrew => "Press her crew",
+            PrizeChoice::Ransom => "Ransom her officers",
+        }
+    }
+
+    /// Reputation with her flag: mercy is remembered, and so is the press gang.
+    pub fn reputation(&self) -> i32 {
+        match self {
+            PrizeChoice::TakeShip => -3,
+            PrizeChoice::Release => 3,
+            PrizeChoice::PressCrew => -5,
+            PrizeChoice::Ransom => -1,
+        }
+    }
+}
+
+/// A surrendered ship awaiting the player's decision.
+#[derive(Debug, Clone)]
+pub struct Prize {
+    /// Fleet entry, should she be taken; sails with whatever is left in her hold.
+    pub ship: ShipData,
+    /// Her flag, if she flew one.
+    pub faction: Option<FactionId>,
+    /// Hands left aboard her.
+    pub hands: u32,
+    pub choice: PrizeChoice,
+}
+
+impl Prize {
+    pub fn new(ship: ShipData, faction: Option<FactionId>, hands: u32) -> Self {
+        Self { ship, faction, hands, choice: PrizeChoice::default() }
+    }
+
+    /// Gold her officers fetch.
+    pub fn ransom(&self) -> u32 {
+        Crew::berths_for(self.ship.ship_type) * RANSOM_PER_BERTH
+    }
+
+    /// Hands that can be pressed from her, berths permitting.
+    pub fn pressed_hands(&self) -> u32 {
+        (self.hands as f32 * PRESSED_SHARE).ceil() as u32
+    }
+
+    /// What her fate brings, for the loot screen.
+    pub fn outcome(&self) -> String {
+        let reputation = self.choice.reputation();
+        let result = match self.choice {
+            PrizeChoice::TakeShip => "joins your fleet".to_string(),
+            PrizeChoice::Release => "sails home".to_string(),
+            PrizeChoice::PressCrew => format!("+{} hands", self.pressed_hands()),
+            PrizeChoice::Ransom => format!("+{} gold", self.ransom()),
+        };
+        match self.faction {
+            Some(faction) => format!("{}, {:+} with {}", result, reputation, faction.display_name()),
+            None => result,
+        }
+    }
+}
+
+/// One defeated ship's hold.
+#[derive(Debug, Clone)]
+pub struct SpoilsSource {
+    /// Ship name shown on the loot screen.
+    pub name: String,
+    /// Whether the ship surrendered (intact hold) rather than sank (salvage only).
+    pub surrendered: bool,
+    pub gold: u32,
+    /// What is left in the hold.
+    pub hold: Cargo,
+    /// A surrendered ship, and what is to become of her.
+    pub prize: Option<Prize>,
+}
+
+impl SpoilsSource {
+    /// A surrendered ship: its whole hold is up for grabs.
+    pub fn surrendered(name: impl Into<String>, gold: u32, hold: Cargo, prize: Prize) -> Self {
+        Self {
+            name: name.into(),
+            surrendered: true,
+            gold,
+            hold,
+            prize: Some(prize),
+        }
+    }
+
+    /// A sunk ship: only `SUNK_SALVAGE_FRACTION` of its gold and cargo is recovered.
+    pub fn sunk(name: impl Into<String>, gold: u32, cargo: Option<&Cargo>) -> Self {
+        let salvage = |amount: u32| (amount as f32 * SUNK_SALVAGE_FRACTION).floor() as u32;
+        let mut hold = Cargo::new(cargo.map_or(0, |c| c.capacity));
+        if let Some(cargo) = cargo {
+            for (good, quantity) in &cargo.goods {
+                hold.add(*good, salvage(*quantity));
+            }
+        }
+        Self {
+            name: name.into(),
+            surrendered: false,
+            gold: salvage(gold),
+            hold,
+            prize: None,
+        }
+    }
+
+    /// Goods in the hold in a stable display order.
+    pub fn goods(&self) -> Vec<(GoodType, u32)> {
+        let mut goods: Vec<_> = self.hold.goods.iter().map(|(g, q)| (*g, *q)).collect();
+        goods.sort_by_key(|(good, _)| format!("{:?}", good));
+        goods
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gold == 0 && self.hold.total_units() == 0
+    }
+}
+
+/// Holds of the ships defeated in the current battle.
+///
+/// Filled as enemies sink or surrender. Once the battle is won the loot screen
+/// opens and the return to the High Seas waits until the player closes it.
+#[derive(Resource, Debug, Default)]
+pub struct VictorySpoils {
+    pub sources: Vec<SpoilsSource>,
+    /// Whether the loot screen is showing.
+    pub open: bool,
+}
+
+impl VictorySpoils {
+    /// Moves as much of a good as fits from a source into `cargo`. Returns units moved.
+    pub fn take_good(&mut self, source: usize, good: GoodType, cargo: &mut Cargo) -> u32 {
+        let Some(source) = self.sources.get_mut(source) else {
+            return 0;
+        };
+        let moved = cargo.add(good, source.hold.get(good));
+        source.hold.remove(good, moved);
+        moved
+    }
+
+    /// Moves a source's gold into `gold`. Returns the amount moved.
+    pub fn take_gold(&mut self, source: usize, gold: &mut Gold) -> u32 {
+        let Some(source) = self.sources.get_mut(source) else {
+            return 0;
+        };
+        let moved = std::mem::take(&mut source.gold);
+        gold.add(moved);
+        moved
+    }
+
+    /// Takes a source's gold and as much of each good as fits.
+    pub fn take_all(&mut self, source: usize, cargo: &mut Cargo, gold: &mut Gold) {
+        self.take_gold(source, gold);
+        let goods = self.sources.get(source).map(|s| s.goods()).unwrap_or_default();
+        for (good, _) in goods {
+            self.take_good(source, good, cargo);
+        }
+    }
+
+    /// Whether nothing is left to take.
+    pub fn is_empty(&self) -> bool {
+        self.sources.iter().all(SpoilsSource::is_empty)
+    }
+
+    /// Whether any surrendered ship awaits the player's decision.
+    pub fn has_prizes(&self) -> bool {
+        self.sources.iter().any(|source| source.prize.is_some())
+    }
+
+    /// Decides the fate of a source's prize.
+    pub fn choose(&mut self, source: usize, choice: PrizeChoice) {
+        if let Some(prize) = self.sources.get_mut(source).and_then(|source| source.prize.as_mut()) {
+            prize.choice = choice;
+        }
+    }
+
+    /// Closes the screen and returns the prizes, their ships carrying what was left aboard.
+    pub fn finish(&mut self) -> Vec<Prize> {
+        self.open = false;
+        self.sources
+            .drain(..)
+            .filter_map(|source| {
+                let hold = source.hold;
+                source.prize.map(|mut prize| {
+                    prize.ship.cargo = Some(hold);
+                    prize
+                })
+            })
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.sources.clear();
+        self.open = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::ShipType;
+
+    fn hold(goods: &[(GoodType, u32)], capacity: u32) -> Cargo {
+        let mut cargo = Cargo::new(capacity);
+        for (good, quantity) in goods {
+            cargo.add(*good, *quantity);
+        }
+        cargo
+    }
+
+    #[test]
+    fn test_sunk_ship_salvages_half() {
+        let cargo = hold(&[(GoodType::Rum, 11), (GoodType::Spices, 4)], 50);
+        let source = SpoilsSource::sunk("Wreck", 101, Some(&cargo));
+        assert_eq!(source.gold, 50);
+        assert_eq!(source.hold.get(GoodType::Rum), 5);
+        assert_eq!(source.hold.get(GoodType::Spices), 2);
+        assert!(source.prize.is_none());
+    }
+
+    #[test]
+    fn test_take_good_respects_capacity() {
+        let mut spoils = VictorySpoils::default();
+        spoils.sources.push(SpoilsSource::surrendered(
+            "Prize",
+            0,
+            hold(&[(GoodType::Timber, 40)], 100),
+            Prize::new(ShipData::default(), None, 0),
+        ));
+        let mut player_hold = hold(&[(GoodType::Sugar, 90)], 100);
+
+        assert_eq!(spoils.take_good(0, GoodType::Timber, &mut player_hold), 10);
+        assert_eq!(player_hold.available_capacity(), 0);
+        assert_eq!(spoils.sources[0].hold.get(GoodType::Timber), 30);
+    }
+
+    #[test]
+    fn test_finish_leaves_remaining_cargo_on_prize() {
+        let mut spoils = VictorySpoils { open: true, ..default() };
+        spoils.sources.push(SpoilsSource::surrendered(
+            "Prize",
+            25,
+            hold(&[(GoodType::Cloth, 30)], 80),
+            Prize::new(ShipData::default(), None, 0),
+        ));
+        spoils.sources.push(SpoilsSource::sunk("Wreck", 10, None));
+
+        let mut player_hold = Cargo::new(20);
+        let mut gold = Gold(0);
+        spoils.take_all(0, &mut player_hold, &mut gold);
+        assert_eq!(gold.0, 25);
+        assert_eq!(player_hold.get(GoodType::Cloth), 20);
+
+        let prizes = spoils.finish();
+        assert!(!spoils.open);
+        assert!(spoils.sources.is_empty());
+        assert_eq!(prizes.len(), 1);
+        assert_eq!(prizes[0].ship.cargo.as_ref().unwrap().get(GoodType::Cloth), 10);
+    }
+
+    #[test]
+    fn test_prize_choices_carry_through() {
+        let mut spoils = VictorySpoils::default();
+        let ship = ShipData { ship_type: ShipType::Frigate, ..default() };
+        spoils.sources.push(SpoilsSource::surrendered("Prize", 0, Cargo::new(10), Prize::new(ship, Some(FactionId::NationB), 9)));
+        assert!(spoils.has_prizes());
+        assert!(spoils.is_empty());
+
+        spoils.choose(0, PrizeChoice::Ransom);
+        let prizes = spoils.finish();
+        assert_eq!(prizes[0].choice, PrizeChoice::Ransom);
+        assert_eq!(prizes[0].ransom(), Crew::berths_for(ShipType::Frigate) * RANSOM_PER_BERTH);
+        assert_eq!(prizes[0].pressed_hands(), 5);
+        assert!(PrizeChoice::Release.reputation() > PrizeChoice::TakeShip.reputation());
+    }
+}
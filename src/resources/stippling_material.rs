@@ -9,6 +9,9 @@ pub struct StipplingMaterial {
     pub color: LinearRgba,
     #[uniform(0)]
     pub dot_spacing: f32,
+    /// Width of the map in world units, for converting world offsets to UVs.
+    #[uniform(0)]
+    pub map_size_world: f32,
     #[texture(1)]
     #[sampler(2)]
     pub depth_texture: Handle<Image>,
@@ -25,6 +28,7 @@ impl Default for StipplingMaterial {
         Self {
             color: LinearRgba::BLUE,
             dot_spacing: 1.0,
+            map_size_world: 32768.0,
             depth_texture: Handle::default(),
         }
     }
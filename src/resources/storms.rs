@@ -0,0 +1,200 @@
+//! Named hurricanes crossing the High Seas.
+//!
+//! Storms move every in-game hour. The chart never shows a storm's true
+//! position: it shows the last `StormReport` the player received, projected
+//! forward as a forecast cone that widens with time and with poorer reports.
+
+use bevy::prelude::*;
+
+use crate::resources::TICKS_PER_HOUR;
+
+/// Storm names, handed out in order like a hurricane season's list.
+pub const STORM_NAMES: [&str; 26] = [
+    "Abigail", "Bartholomew", "Calico", "Dorothea", "Edmund", "Fenella", "Gideon",
+    "Hester", "Ignatius", "Jemima", "Kit", "Lavinia", "Mordecai", "Nell", "Obadiah",
+    "Prudence", "Quentin", "Rosalind", "Silas", "Temperance", "Ulric", "Verity",
+    "Wilhelmina", "Xavier", "Yolanda", "Zebedee",
+];
+
+/// Most storms at sea at once.
+pub const MAX_STORMS: usize = 2;
+/// Hours ahead the forecast cone reaches.
+pub const FORECAST_HOURS: u32 = 24;
+/// Cone widening per hour of projection for a report with no accuracy.
+pub const CONE_SPREAD_PER_HOUR: f32 = 40.0;
+/// Widening kept even by a perfect report: weather is never certain.
+pub const MIN_UNCERTAINTY: f32 = 0.15;
+/// Accuracy of the harbor master's storm warnings on arrival in port.
+pub const PORT_REPORT_ACCURACY: f32 = 0.5;
+/// Accuracy of storm news picked up with tavern intel.
+pub const TAVERN_REPORT_ACCURACY: f32 = 0.8;
+
+/// What the player last heard about a storm.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct StormReport {
+    pub position: Vec2,
+    /// World units per in-game hour.
+    pub velocity: Vec2,
+    /// World tick the report was made.
+    pub tick: u32,
+    /// 0.0 (hearsay) to 1.0 (seen with your own eyes).
+    pub accuracy: f32,
+    /// Points of `Storm::track` known when the report was made.
+    pub track_len: usize,
+}
+
+impl StormReport {
+    /// Forecast cone from `now_tick` onward: (center, half-width) for each hour
+    /// up to `FORECAST_HOURS` ahead, projected from the report.
+    pub fn forecast(&self, now_tick: u32, radius: f32) -> Vec<(Vec2, f32)> {
+        let age = now_tick.saturating_sub(self.tick) as f32 / TICKS_PER_HOUR as f32;
+        let spread = CONE_SPREAD_PER_HOUR * (1.0 - self.accuracy + MIN_UNCERTAINTY);
+        (0..=FORECAST_HOURS)
+            .map(|hour| {
+                let hours = age + hour as f32;
+                (self.position + self.velocity * hours, radius + spread * hours)
+            })
+            .collect()
+    }
+}
+
+/// A hurricane at sea.
+#[derive(Debug, Clone, Reflect)]
+pub struct Storm {
+    pub name: String,
+    pub position: Vec2,
+    /// World units per in-game hour.
+    pub velocity: Vec2,
+    pub radius: f32,
+    pub hours_left: u32,
+    /// Hourly positions since the storm formed.
+    pub track: Vec<Vec2>,
+    pub report: Option<StormReport>,
+}
+
+impl Storm {
+    /// Moves the storm one hour along, veering by `turn` radians.
+    pub fn advance_hour(&mut self, turn: f32) {
+        self.velocity = Vec2::from_angle(turn).rotate(self.velocity);
+        self.position += self.velocity;
+        self.track.push(self.position);
+        self.hours_left = self.hours_left.saturating_sub(1);
+    }
+
+    /// Replaces the player's report with a fresh one, unless the current one is
+    /// from this tick and more accurate.
+    pub fn report(&mut self, tick: u32, accuracy: f32) {
+        if self.report.as_ref().is_some_and(|r| r.tick == tick && r.accuracy >= accuracy) {
+            return;
+        }
+        self.report = Some(StormReport {
+            position: self.position,
+            velocity: self.velocity,
+            tick,
+            accuracy,
+            track_len: self.track.len(),
+        });
+    }
+
+    /// Whether `point` is inside the storm.
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.position.distance(point) <= self.radius
+    }
+}
+
+/// All storms at sea. Saved with the run, so a hurricane and the player's
+/// last report of her are still on the chart after a load.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct StormTracker {
+    pub storms: Vec<Storm>,
+    /// Index of the next name in `STORM_NAMES`.
+    pub next_name: usize,
+}
+
+impl StormTracker {
+    /// Next name on the list; wraps around with a numeral after Zebedee.
+    pub fn take_name(&mut self) -> String {
+        let name = STORM_NAMES[self.next_name % STORM_NAMES.len()];
+        let season = self.next_name / STORM_NAMES.len();
+        self.next_name += 1;
+        if season == 0 {
+            name.to_string()
+        } else {
+            format!("{} {}", name, season + 1)
+        }
+    }
+
+    /// Gives the player a report on every storm.
+    pub fn report_all(&mut self, tick: u32, accuracy: f32) {
+        for storm in &mut self.storms {
+            storm.report(tick, accuracy);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.storms.clear();
+        self.next_name = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storm() -> Storm {
+        Storm {
+            name: "Abigail".to_string(),
+            position: Vec2::ZERO,
+            velocity: Vec2::new(100.0, 0.0),
+            radius: 200.0,
+            hours_left: 2,
+            track: Vec::new(),
+            report: None,
+        }
+    }
+
+    #[test]
+    fn test_names_wrap_with_season_number() {
+        let mut tracker = StormTracker::default();
+        assert_eq!(tracker.take_name(), "Abigail");
+        tracker.next_name = STORM_NAMES.len();
+        assert_eq!(tracker.take_name(), "Abigail 2");
+    }
+
+    #[test]
+    fn test_storm_moves_and_blows_out() {
+        let mut storm = storm();
+        storm.advance_hour(0.0);
+        storm.advance_hour(0.0);
+        assert_eq!(storm.position, Vec2::new(200.0, 0.0));
+        assert_eq!(storm.track.len(), 2);
+        assert_eq!(storm.hours_left, 0);
+    }
+
+    #[test]
+    fn test_forecast_cone_widens_with_age_and_inaccuracy() {
+        let mut storm = storm();
+        storm.report(0, 1.0);
+        let sharp = storm.report.clone().unwrap();
+        storm.report(TICKS_PER_HOUR, 0.5);
+        let vague = storm.report.clone().unwrap();
+
+        let now = TICKS_PER_HOUR * 2;
+        let sharp_cone = sharp.forecast(now, storm.radius);
+        let vague_cone = vague.forecast(now, storm.radius);
+        assert_eq!(sharp_cone.len(), FORECAST_HOURS as usize + 1);
+        // A two-hour-old report starts its cone two hours downwind
+        assert_eq!(sharp_cone[0].0, Vec2::new(200.0, 0.0));
+        assert!(sharp_cone[FORECAST_HOURS as usize].1 > sharp_cone[0].1);
+        assert!(vague_cone[FORECAST_HOURS as usize].1 > sharp_cone[FORECAST_HOURS as usize].1);
+    }
+
+    #[test]
+    fn test_better_report_same_tick_is_kept() {
+        let mut storm = storm();
+        storm.report(5, 1.0);
+        storm.report(5, 0.5);
+        assert_eq!(storm.report.as_ref().unwrap().accuracy, 1.0);
+    }
+}
@@ -0,0 +1,34 @@
+//! Rare supernatural encounters on the night sea.
+//!
+//! On the hour, once it is dark (`is_dark`), a ghost ship or a cursed fog
+//! bank may appear near the player. Neither ever appears in a run started
+//! with the "Historical waters" modifier.
+
+use bevy::prelude::*;
+
+/// Chance per night hour that a ghost ship appears.
+pub const GHOST_SHIP_CHANCE: f64 = 0.03;
+/// Chance per night hour that a cursed fog bank rolls in.
+pub const FOG_BANK_CHANCE: f64 = 0.04;
+/// How far from the player a supernatural encounter appears (world units).
+pub const APPARITION_DISTANCE: f32 = 700.0;
+/// Lanterns in a ghost ship's rigging; shot passes through her hull while any burn.
+pub const GHOST_SHIP_LANTERNS: u32 = 3;
+/// Cursed doubloons in a ghost ship's hold.
+pub const GHOST_SHIP_GOLD: u32 = 800;
+/// Radius of a cursed fog bank (world units).
+pub const FOG_BANK_RADIUS: f32 = 450.0;
+/// How close to the heart of a fog bank the derelict lies (world units).
+pub const FOG_BANK_HEART_RADIUS: f32 = 80.0;
+/// Gold aboard the derelict at the heart of a fog bank.
+pub const FOG_BANK_TREASURE: u32 = 400;
+
+/// Supernatural goings-on the player is caught up in.
+#[derive(Resource, Debug, Default)]
+pub struct SupernaturalState {
+    /// The current battle is against a ghost ship.
+    pub ghost_engaged: bool,
+    /// The player is inside a cursed fog bank.
+    pub in_cursed_fog: bool,
+}
+
@@ -0,0 +1,135 @@
+//! Tactical pause: slow combat right down and queue orders for the player ship.
+
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI, TAU};
+
+use bevy::prelude::*;
+
+use crate::components::AmmoType;
+
+/// Game speed while the tactical pause is held.
+pub const TACTICAL_TIME_SCALE: f32 = 0.25;
+/// Heading change queued by each turn press (radians).
+pub const QUEUED_TURN_STEP: f32 = FRAC_PI_4;
+/// Largest turn that can be queued, either way (radians).
+pub const MAX_QUEUED_TURN: f32 = PI;
+/// The helm stops steering once within this angle of the ordered heading (radians).
+pub const HELM_TOLERANCE: f32 = 0.05;
+
+/// Orders queued during a tactical pause, carried out on resume.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TacticalOrders {
+    /// Heading change in radians; positive turns to port (counter-clockwise).
+    pub turn: f32,
+    /// Enemy ship to bring a broadside to bear on.
+    pub target: Option<Entity>,
+    /// Shot to load next.
+    pub ammo: Option<AmmoType>,
+}
+
+impl TacticalOrders {
+    /// Adds one turn step to port (`direction` > 0) or starboard.
+    pub fn queue_turn(&mut self, direction: f32) {
+        self.turn = (self.turn + QUEUED_TURN_STEP * direction.signum())
+            .clamp(-MAX_QUEUED_TURN, MAX_QUEUED_TURN);
+    }
+
+    /// Loads the next shot in rotation after `current`.
+    pub fn cycle_ammo(&mut self, current: AmmoType) {
+        self.ammo = Some(self.ammo.unwrap_or(current).next());
+    }
+
+    /// Heading the helm should steer for on resume. An explicit turn wins;
+    /// otherwise the ship turns to present its nearer broadside to the target.
+    pub fn helm_heading(&self, heading: f32, position: Vec2, target: Option<Vec2>) -> Option<f32> {
+        if self.turn != 0.0 {
+            return Some(wrap_angle(heading + self.turn));
+        }
+        target.map(|target| wrap_angle(heading + broadside_turn(heading, position, target)))
+    }
+}
+
+/// State of the tactical pause.
+#[derive(Resource, Debug, Default)]
+pub struct TacticalPause {
+    pub active: bool,
+    pub orders: TacticalOrders,
+}
+
+/// Heading the player's helm is steering for, set when a tactical pause ends.
+/// Cleared on arrival or when the player takes the helm by hand.
+#[derive(Resource, Debug, Default)]
+pub struct HelmOrder {
+    pub heading: Option<f32>,
+}
+
+/// Wraps an angle into (-PI, PI].
+pub fn wrap_angle(angle: f32) -> f32 {
+    let wrapped = (angle + PI).rem_euclid(TAU) - PI;
+    if wrapped <= -PI {
+        wrapped + TAU
+    } else {
+        wrapped
+    }
+}
+
+/// Smallest turn (radians, positive to port) that puts `target` square on
+/// either beam of a ship at `position` with z-rotation `heading`. Ships face
+/// local +Y, so their forward bearing is `heading + PI/2`.
+pub fn broadside_turn(heading: f32, position: Vec2, target: Vec2) -> f32 {
+    let to_target = target - position;
+    let bearing = to_target.y.atan2(to_target.x);
+    let relative = wrap_angle(bearing - (heading + FRAC_PI_2));
+    let to_port_beam = wrap_angle(relative - FRAC_PI_2);
+    let to_starboard_beam = wrap_angle(relative + FRAC_PI_2);
+    if to_port_beam.abs() <= to_starboard_beam.abs() {
+        to_port_beam
+    } else {
+        to_starboard_beam
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns_queue_in_steps_and_clamp() {
+        let mut orders = TacticalOrders::default();
+        orders.queue_turn(1.0);
+        orders.queue_turn(1.0);
+        assert!((orders.turn - FRAC_PI_2).abs() < 1e-5);
+        for _ in 0..10 {
+            orders.queue_turn(-1.0);
+        }
+        assert_eq!(orders.turn, -MAX_QUEUED_TURN);
+    }
+
+    #[test]
+    fn test_ammo_cycles_from_loaded_shot() {
+        let mut orders = TacticalOrders::default();
+        orders.cycle_ammo(AmmoType::RoundShot);
+        assert_eq!(orders.ammo, Some(AmmoType::ChainShot));
+        orders.cycle_ammo(AmmoType::RoundShot);
+        assert_eq!(orders.ammo, Some(AmmoType::BarShot));
+    }
+
+    #[test]
+    fn test_broadside_turn_picks_nearer_beam() {
+        // Facing +Y, target dead ahead: a quarter turn either way
+        let turn = broadside_turn(0.0, Vec2::ZERO, Vec2::new(0.0, 100.0));
+        assert!((turn.abs() - FRAC_PI_2).abs() < 1e-5);
+        // Target already on the starboard beam: no turn
+        assert!(broadside_turn(0.0, Vec2::ZERO, Vec2::new(100.0, 0.0)).abs() < 1e-5);
+        // Target slightly forward of the port beam: small turn to starboard
+        let turn = broadside_turn(0.0, Vec2::ZERO, Vec2::new(-100.0, 20.0));
+        assert!(turn < 0.0 && turn > -0.5);
+    }
+
+    #[test]
+    fn test_explicit_turn_beats_target() {
+        let orders = TacticalOrders { turn: QUEUED_TURN_STEP, ..default() };
+        let heading = orders.helm_heading(0.0, Vec2::ZERO, Some(Vec2::new(0.0, 100.0)));
+        assert!((heading.unwrap() - QUEUED_TURN_STEP).abs() < 1e-5);
+        assert_eq!(TacticalOrders::default().helm_heading(0.0, Vec2::ZERO, None), None);
+    }
+}
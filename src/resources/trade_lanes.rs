@@ -0,0 +1,183 @@
+//! Trade lane traffic and danger.
+//!
+//! Tracks how much merchant traffic sails between each pair of ports and how
+//! many pirates prowl along the way. Pirates are drawn to busy lanes and
+//! factions answer dangerous lanes with escorted convoys.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::components::port_key;
+
+/// Fraction of a lane's traffic volume kept each hour.
+pub const LANE_VOLUME_DECAY: f32 = 0.95;
+/// Traffic volume that attracts one pirate raider to a lane.
+pub const VOLUME_PER_RAIDER: f32 = 15.0;
+/// Most raiders sent to a single lane.
+pub const MAX_RAIDERS_PER_LANE: usize = 4;
+/// Distance from a lane within which a pirate counts towards its danger.
+pub const LANE_DANGER_RADIUS: f32 = 400.0;
+/// Danger (pirates near the lane) at which factions start escorting merchants.
+pub const CONVOY_DANGER_THRESHOLD: u32 = 1;
+
+/// Identifies a lane by its two ports' `port_key`s, in a fixed order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub struct LaneKey(pub IVec2, pub IVec2);
+
+impl LaneKey {
+    pub fn new(a: Vec2, b: Vec2) -> Self {
+        let (a, b) = (port_key(a), port_key(b));
+        if (a.x, a.y) <= (b.x, b.y) {
+            Self(a, b)
+        } else {
+            Self(b, a)
+        }
+    }
+}
+
+/// Traffic and danger on one lane.
+#[derive(Debug, Clone, Default)]
+pub struct LaneStats {
+    pub from: Vec2,
+    pub to: Vec2,
+    /// Decaying sum of merchants seen on the lane each hour.
+    pub volume: f32,
+    /// Pirates within `LANE_DANGER_RADIUS` of the lane at the last count.
+    pub danger: u32,
+}
+
+impl LaneStats {
+    /// Raiders this lane's traffic attracts.
+    pub fn desired_raiders(&self) -> usize {
+        ((self.volume / VOLUME_PER_RAIDER) as usize).min(MAX_RAIDERS_PER_LANE)
+    }
+
+    /// Point a fraction `t` of the way along the lane.
+    pub fn point_along(&self, t: f32) -> Vec2 {
+        self.from.lerp(self.to, t)
+    }
+
+    /// Distance from `point` to the lane.
+    pub fn distance_to(&self, point: Vec2) -> f32 {
+        let lane = self.to - self.from;
+        let t = if lane.length_squared() > 0.0 {
+            ((point - self.from).dot(lane) / lane.length_squared()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        point.distance(self.point_along(t))
+    }
+}
+
+/// Per-lane merchant traffic and pirate danger, updated hourly.
+#[derive(Resource, Debug, Default)]
+pub struct TradeLaneTraffic {
+    pub lanes: HashMap<LaneKey, LaneStats>,
+}
+
+impl TradeLaneTraffic {
+    /// Decays every lane's volume, then adds this hour's merchants.
+    pub fn record_hour(&mut self, merchants: &[(Vec2, Vec2)]) {
+        for stats in self.lanes.values_mut() {
+            stats.volume *= LANE_VOLUME_DECAY;
+        }
+        for &(from, to) in merchants {
+            let stats = self.lanes.entry(LaneKey::new(from, to)).or_insert_with(|| LaneStats {
+                from,
+                to,
+                ..default()
+            });
+            stats.volume += 1.0;
+        }
+    }
+
+    /// Counts pirates near each lane.
+    pub fn count_danger(&mut self, pirates: &[Vec2]) {
+        for stats in self.lanes.values_mut() {
+            stats.danger = pirates
+                .iter()
+                .filter(|pirate| stats.distance_to(**pirate) <= LANE_DANGER_RADIUS)
+                .count() as u32;
+        }
+    }
+
+    /// Pirates near the lane between two ports; 0 for unknown lanes.
+    pub fn danger_between(&self, a: Vec2, b: Vec2) -> u32 {
+        self.lanes.get(&LaneKey::new(a, b)).map_or(0, |stats| stats.danger)
+    }
+
+    /// Danger of the waters around `point`: the most pirates counted on any
+    /// lane passing within `LANE_DANGER_RADIUS` of it; 0 off the lanes.
+    pub fn danger_near(&self, point: Vec2) -> u32 {
+        self.lanes
+            .values()
+            .filter(|stats| stats.distance_to(point) <= LANE_DANGER_RADIUS)
+            .map(|stats| stats.danger)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Lanes busy enough to attract raiders, busiest first.
+    pub fn hot_lanes(&self) -> Vec<(LaneKey, &LaneStats)> {
+        let mut hot: Vec<_> = self
+            .lanes
+            .iter()
+            .filter(|(_, stats)| stats.desired_raiders() > 0)
+            .map(|(key, stats)| (*key, stats))
+            .collect();
+        hot.sort_by(|a, b| b.1.volume.total_cmp(&a.1.volume));
+        hot
+    }
+
+    pub fn clear(&mut self) {
+        self.lanes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lane_key_ignores_direction() {
+        let (a, b) = (Vec2::new(10.2, 5.0), Vec2::new(-3.0, 7.7));
+        assert_eq!(LaneKey::new(a, b), LaneKey::new(b, a));
+    }
+
+    #[test]
+    fn test_busy_lanes_attract_raiders_over_time() {
+        let lane = (Vec2::ZERO, Vec2::new(1000.0, 0.0));
+        let mut traffic = TradeLaneTraffic::default();
+
+        traffic.record_hour(&[lane]);
+        assert!(traffic.hot_lanes().is_empty());
+
+        for _ in 0..48 {
+            traffic.record_hour(&[lane]);
+        }
+        let hot = traffic.hot_lanes();
+        assert_eq!(hot.len(), 1);
+        assert_eq!(hot[0].1.desired_raiders(), 1);
+
+        // Traffic dries up once merchants stop sailing the lane
+        for _ in 0..48 {
+            traffic.record_hour(&[]);
+        }
+        assert!(traffic.hot_lanes().is_empty());
+    }
+
+    #[test]
+    fn test_danger_counts_pirates_near_lane() {
+        let (a, b) = (Vec2::ZERO, Vec2::new(1000.0, 0.0));
+        let mut traffic = TradeLaneTraffic::default();
+        traffic.record_hour(&[(a, b)]);
+
+        traffic.count_danger(&[Vec2::new(500.0, 100.0), Vec2::new(1200.0, 0.0), Vec2::new(500.0, 900.0)]);
+        assert_eq!(traffic.danger_between(b, a), 2);
+        assert_eq!(traffic.danger_between(a, Vec2::ONE), 0);
+
+        // Waters near the lane share its danger; open sea far off it has none
+        assert_eq!(traffic.danger_near(Vec2::new(300.0, -250.0)), 2);
+        assert_eq!(traffic.danger_near(Vec2::new(300.0, 2000.0)), 0);
+    }
+}
@@ -0,0 +1,133 @@
+//! Standard and compact UI layouts.
+//!
+//! Compact is for small screens such as the Steam Deck (1280x800): egui is
+//! zoomed so the HUD and text stay legible, interactive targets are enlarged
+//! for touch and gamepad, and the port UI reflows its tabs. It is picked
+//! automatically from the window size unless the player forces a layout,
+//! which is kept in `GameSettings::ui_layout`.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Windows no wider than this (logical pixels) get the compact layout on `Auto`.
+pub const COMPACT_MAX_WIDTH: f32 = 1280.0;
+/// Windows no taller than this (logical pixels) get the compact layout on `Auto`.
+pub const COMPACT_MAX_HEIGHT: f32 = 800.0;
+/// egui zoom in the compact layout.
+pub const COMPACT_ZOOM: f32 = 1.2;
+/// Minimum height of buttons and other targets in the compact layout (points).
+pub const COMPACT_TARGET_HEIGHT: f32 = 36.0;
+
+/// How the UI is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UiLayoutMode {
+    #[default]
+    Standard,
+    Compact,
+}
+
+impl UiLayoutMode {
+    /// Layout suited to a window of `size` logical pixels.
+    pub fn for_window(size: Vec2) -> Self {
+        if size.x <= COMPACT_MAX_WIDTH || size.y <= COMPACT_MAX_HEIGHT {
+            UiLayoutMode::Compact
+        } else {
+            UiLayoutMode::Standard
+        }
+    }
+}
+
+/// Which layout the player asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LayoutPreference {
+    /// Follow the window size.
+    #[default]
+    Auto,
+    Standard,
+    Compact,
+}
+
+impl LayoutPreference {
+    pub const ALL: [LayoutPreference; 3] = [LayoutPreference::Auto, LayoutPreference::Standard, LayoutPreference::Compact];
+
+    /// The next preference when toggling.
+    pub fn next(self) -> Self {
+        match self {
+            LayoutPreference::Auto => LayoutPreference::Compact,
+            LayoutPreference::Compact => LayoutPreference::Standard,
+            LayoutPreference::Standard => LayoutPreference::Auto,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LayoutPreference::Auto => "Auto",
+            LayoutPreference::Standard => "Standard",
+            LayoutPreference::Compact => "Compact",
+        }
+    }
+}
+
+/// The player's layout preference and the layout in use.
+#[derive(Resource, Debug)]
+pub struct UiLayout {
+    pub preference: LayoutPreference,
+    pub mode: UiLayoutMode,
+    /// The player's UI scale (`GameSettings::ui_scale`), on top of the layout's zoom.
+    pub scale: f32,
+}
+
+impl Default for UiLayout {
+    fn default() -> Self {
+        Self {
+            preference: LayoutPreference::default(),
+            mode: UiLayoutMode::default(),
+            scale: 1.0,
+        }
+    }
+}
+
+impl UiLayout {
+    /// Layout for the preference and a window of `size`.
+    pub fn resolve(preference: LayoutPreference, size: Vec2) -> UiLayoutMode {
+        match preference {
+            LayoutPreference::Auto => UiLayoutMode::for_window(size),
+            LayoutPreference::Standard => UiLayoutMode::Standard,
+            LayoutPreference::Compact => UiLayoutMode::Compact,
+        }
+    }
+
+    pub fn is_compact(&self) -> bool {
+        self.mode == UiLayoutMode::Compact
+    }
+
+    /// egui zoom factor for the current layout and the player's UI scale.
+    pub fn zoom(&self) -> f32 {
+        let zoom = match self.mode {
+            UiLayoutMode::Standard => 1.0,
+            UiLayoutMode::Compact => COMPACT_ZOOM,
+        };
+        zoom * self.scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steam_deck_resolution_is_compact() {
+        assert_eq!(UiLayoutMode::for_window(Vec2::new(1280.0, 800.0)), UiLayoutMode::Compact);
+        assert_eq!(UiLayoutMode::for_window(Vec2::new(1920.0, 1080.0)), UiLayoutMode::Standard);
+        // A short ultrawide window is still cramped vertically
+        assert_eq!(UiLayoutMode::for_window(Vec2::new(2560.0, 720.0)), UiLayoutMode::Compact);
+    }
+
+    #[test]
+    fn test_preference_overrides_detection() {
+        let deck = Vec2::new(1280.0, 800.0);
+        assert_eq!(UiLayout::resolve(LayoutPreference::Standard, deck), UiLayoutMode::Standard);
+        assert_eq!(UiLayout::resolve(LayoutPreference::Compact, Vec2::new(3840.0, 2160.0)), UiLayoutMode::Compact);
+        assert_eq!(LayoutPreference::Auto.next().next().next(), LayoutPreference::Auto);
+    }
+}
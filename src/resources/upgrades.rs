@@ -0,0 +1,145 @@
+//! Upgrade items owned by the player and refits that move them between hulls.
+
+use bevy::prelude::*;
+
+use crate::components::upgrade::{UpgradeKind, UPGRADE_SLOTS};
+use crate::resources::PlayerFleet;
+
+/// Gold charged by the shipyard for each upgrade fitted or removed.
+pub const REFIT_FEE: u32 = 50;
+/// In-game hours each refit job takes.
+pub const REFIT_HOURS: u32 = 4;
+
+/// Which hull a refit works on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefitTarget {
+    Flagship,
+    /// Index into `PlayerFleet::ships`.
+    Fleet(usize),
+}
+
+/// Why a refit could not be done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefitError {
+    NoSuchShip,
+    NoSuchUpgrade,
+    SlotsFull,
+}
+
+/// Upgrades the player owns: stowed in the hold, or fitted to the flagship.
+/// Fleet ships keep their fitted upgrades in `ShipData::upgrades`.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct UpgradeInventory {
+    /// Upgrades not fitted to any hull.
+    pub stowed: Vec<UpgradeKind>,
+    /// Upgrades fitted to the flagship.
+    pub flagship: Vec<UpgradeKind>,
+}
+
+impl UpgradeInventory {
+    /// Fitted upgrades of a hull.
+    pub fn fitted<'a>(&'a self, fleet: &'a PlayerFleet, target: RefitTarget) -> Option<&'a [UpgradeKind]> {
+        match target {
+            RefitTarget::Flagship => Some(&self.flagship),
+            RefitTarget::Fleet(index) => fleet.ships.get(index).map(|ship| ship.upgrades.as_slice()),
+        }
+    }
+
+    /// Removes the upgrade in `slot` of a hull and stows it.
+    pub fn remove(&mut self, fleet: &mut PlayerFleet, target: RefitTarget, slot: usize) -> Result<UpgradeKind, RefitError> {
+        let slots = fitted_mut(&mut self.flagship, fleet, target)?;
+        if slot >= slots.len() {
+            return Err(RefitError::NoSuchUpgrade);
+        }
+        let upgrade = slots.remove(slot);
+        self.stowed.push(upgrade);
+        Ok(upgrade)
+    }
+
+    /// Fits the stowed upgrade at `stowed_index` to a hull.
+    pub fn install(&mut self, fleet: &mut PlayerFleet, stowed_index: usize, target: RefitTarget) -> Result<UpgradeKind, RefitError> {
+        if stowed_index >= self.stowed.len() {
+            return Err(RefitError::NoSuchUpgrade);
+        }
+        let slots = fitted_mut(&mut self.flagship, fleet, target)?;
+        if slots.len() >= UPGRADE_SLOTS {
+            return Err(RefitError::SlotsFull);
+        }
+        let upgrade = self.stowed.remove(stowed_index);
+        slots.push(upgrade);
+        Ok(upgrade)
+    }
+
+    pub fn clear(&mut self) {
+        self.stowed.clear();
+        self.flagship.clear();
+    }
+}
+
+fn fitted_mut<'a>(
+    flagship: &'a mut Vec<UpgradeKind>,
+    fleet: &'a mut PlayerFleet,
+    target: RefitTarget,
+) -> Result<&'a mut Vec<UpgradeKind>, RefitError> {
+    match target {
+        RefitTarget::Flagship => Ok(flagship),
+        RefitTarget::Fleet(index) => fleet
+            .ships
+            .get_mut(index)
+            .map(|ship| &mut ship.upgrades)
+            .ok_or(RefitError::NoSuchShip),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ShipData;
+
+    fn fleet_of(count: usize) -> PlayerFleet {
+        PlayerFleet {
+            ships: vec![ShipData::default(); count],
+        }
+    }
+
+    #[test]
+    fn test_move_upgrade_between_hulls() {
+        let mut fleet = fleet_of(1);
+        let mut inventory = UpgradeInventory {
+            flagship: vec![UpgradeKind::ReinforcedHull],
+            ..default()
+        };
+
+        assert_eq!(inventory.remove(&mut fleet, RefitTarget::Flagship, 0), Ok(UpgradeKind::ReinforcedHull));
+        assert!(inventory.flagship.is_empty());
+        assert_eq!(inventory.stowed, vec![UpgradeKind::ReinforcedHull]);
+
+        assert_eq!(inventory.install(&mut fleet, 0, RefitTarget::Fleet(0)), Ok(UpgradeKind::ReinforcedHull));
+        assert!(inventory.stowed.is_empty());
+        assert_eq!(fleet.ships[0].upgrades, vec![UpgradeKind::ReinforcedHull]);
+    }
+
+    #[test]
+    fn test_install_respects_slots() {
+        let mut fleet = fleet_of(0);
+        let mut inventory = UpgradeInventory {
+            stowed: vec![UpgradeKind::ExpandedHold; UPGRADE_SLOTS + 1],
+            ..default()
+        };
+        for _ in 0..UPGRADE_SLOTS {
+            inventory.install(&mut fleet, 0, RefitTarget::Flagship).unwrap();
+        }
+        assert_eq!(inventory.install(&mut fleet, 0, RefitTarget::Flagship), Err(RefitError::SlotsFull));
+        assert_eq!(inventory.stowed.len(), 1);
+    }
+
+    #[test]
+    fn test_bad_targets() {
+        let mut fleet = fleet_of(1);
+        let mut inventory = UpgradeInventory::default();
+        assert_eq!(inventory.remove(&mut fleet, RefitTarget::Fleet(3), 0), Err(RefitError::NoSuchShip));
+        assert_eq!(inventory.remove(&mut fleet, RefitTarget::Fleet(0), 0), Err(RefitError::NoSuchUpgrade));
+        assert_eq!(inventory.install(&mut fleet, 0, RefitTarget::Flagship), Err(RefitError::NoSuchUpgrade));
+    }
+}
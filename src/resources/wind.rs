@@ -1,9 +1,23 @@
 use bevy::prelude::*;
 
+/// Wind strength at or above which the weather counts as a storm.
+pub const STORM_WIND_STRENGTH: f32 = 0.75;
+
+/// Closest a square-rigger can point to the wind; any closer and she is in irons.
+pub const CLOSE_HAULED_ANGLE: f32 = std::f32::consts::FRAC_PI_4;
+/// Speed in irons, as a share of still-water speed, in a full gale.
+const IN_IRONS_SPEED: f32 = 0.35;
+/// Speed close-hauled, beam reach, broad reach and dead run, as above.
+const CLOSE_HAULED_SPEED: f32 = 0.75;
+const BEAM_REACH_SPEED: f32 = 1.15;
+const BROAD_REACH_SPEED: f32 = 1.2;
+const RUNNING_SPEED: f32 = 1.0;
+
 /// Resource representing the current wind conditions in the game world.
 ///
 /// Wind affects:
-/// - Navigation speed in High Seas (travel with wind = faster)
+/// - Sailing speed in High Seas by point of sail (see `sailing_factor`)
+/// - Theta* route costs, so routes tack rather than beat straight upwind
 /// - Ship movement in Combat (downwind = faster, upwind = slower)
 #[derive(Resource, Debug, Clone, Copy, Reflect)]
 #[reflect(Resource)]
@@ -34,6 +48,51 @@ impl Wind {
         self.direction_vec() * self.strength
     }
     
+    /// Angle between a heading and the wind's eye, 0 (dead upwind) to PI (dead downwind).
+    pub fn angle_off_wind(&self, heading: Vec2) -> f32 {
+        let heading = heading.normalize_or_zero();
+        if heading == Vec2::ZERO {
+            return std::f32::consts::PI;
+        }
+        (-heading.dot(self.direction_vec())).clamp(-1.0, 1.0).acos()
+    }
+
+    /// Sailing speed multiplier for a heading, by point of sail.
+    ///
+    /// Ships are fastest on a broad reach, a little slower running dead
+    /// downwind, slow close-hauled and all but stopped in irons. The effect
+    /// scales with wind strength: in a calm every heading is alike.
+    pub fn sailing_factor(&self, heading: Vec2) -> f32 {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        const BROAD_REACH_ANGLE: f32 = 3.0 * std::f32::consts::FRAC_PI_4;
+
+        let angle = self.angle_off_wind(heading);
+        let polar = if angle < CLOSE_HAULED_ANGLE {
+            IN_IRONS_SPEED
+        } else if angle < FRAC_PI_2 {
+            CLOSE_HAULED_SPEED.lerp(BEAM_REACH_SPEED, (angle - CLOSE_HAULED_ANGLE) / CLOSE_HAULED_ANGLE)
+        } else if angle < BROAD_REACH_ANGLE {
+            BEAM_REACH_SPEED.lerp(BROAD_REACH_SPEED, (angle - FRAC_PI_2) / (BROAD_REACH_ANGLE - FRAC_PI_2))
+        } else {
+            BROAD_REACH_SPEED.lerp(RUNNING_SPEED, (angle - BROAD_REACH_ANGLE) / (PI - BROAD_REACH_ANGLE))
+        };
+        1.0 + (polar - 1.0) * self.strength.clamp(0.0, 1.0)
+    }
+
+    /// Route cost multiplier for sailing a leg on `heading`: 1.0 on the
+    /// fastest point of sail, more the slower she goes.
+    ///
+    /// Never below 1.0, so the distance heuristic stays admissible.
+    pub fn route_cost_multiplier(&self, heading: Vec2) -> f32 {
+        let best = 1.0 + (BROAD_REACH_SPEED - 1.0) * self.strength.clamp(0.0, 1.0);
+        (best / self.sailing_factor(heading)).max(1.0)
+    }
+
+    /// Returns true if the wind is blowing at storm strength.
+    pub fn is_storm(&self) -> bool {
+        self.strength >= STORM_WIND_STRENGTH
+    }
+
     /// Returns a human-readable cardinal direction (N, NE, E, etc.)
     pub fn cardinal_direction(&self) -> &'static str {
         let deg = self.direction.to_degrees().rem_euclid(360.0);
@@ -50,3 +109,43 @@ impl Wind {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn westerly(strength: f32) -> Wind {
+        // Blowing East
+        Wind { direction: 0.0, strength }
+    }
+
+    #[test]
+    fn test_points_of_sail() {
+        let wind = westerly(1.0);
+        let upwind = wind.sailing_factor(Vec2::NEG_X);
+        let close_hauled = wind.sailing_factor(Vec2::new(-1.0, 1.2));
+        let beam = wind.sailing_factor(Vec2::Y);
+        let broad = wind.sailing_factor(Vec2::new(1.0, 1.0));
+        let running = wind.sailing_factor(Vec2::X);
+        assert!((upwind - IN_IRONS_SPEED).abs() < 0.001);
+        assert!(upwind < close_hauled && close_hauled < beam && beam < broad);
+        assert!(running < broad && running > close_hauled);
+    }
+
+    #[test]
+    fn test_calm_is_the_same_every_way() {
+        let calm = westerly(0.0);
+        assert_eq!(calm.sailing_factor(Vec2::NEG_X), 1.0);
+        assert_eq!(calm.route_cost_multiplier(Vec2::NEG_X), 1.0);
+    }
+
+    #[test]
+    fn test_tacking_beats_sailing_into_the_wind() {
+        let wind = westerly(0.8);
+        assert!(wind.route_cost_multiplier(Vec2::new(1.0, 1.0)) >= 1.0);
+        // Two legs 50 degrees off the wind against one straight into it
+        let leg = Vec2::from_angle(std::f32::consts::PI - 50f32.to_radians());
+        let tack = leg.length() / leg.x.abs() * wind.route_cost_multiplier(leg);
+        assert!(tack < wind.route_cost_multiplier(Vec2::NEG_X));
+    }
+}
@@ -0,0 +1,166 @@
+//! Wind shadows in the lee of islands.
+//!
+//! Land breaks the wind: downwind of a sizeable island the breeze is weaker
+//! and a ship hugging that shore loses way. The shadows are cast on a coarse
+//! grid of cells over `MapData`, by looking upwind from each cell for land
+//! between it and the open sea, and cast again whenever the wind has shifted
+//! far enough to move them.
+
+use bevy::prelude::*;
+
+use super::MapData;
+use crate::utils::pathfinding::world_to_tile;
+
+/// Side of a shadow cell (tiles).
+pub const SHADOW_CELL_TILES: u32 = 4;
+/// Furthest upwind land still casts a shadow (tiles).
+pub const SHADOW_REACH_TILES: u32 = 12;
+/// Land tiles upwind for a full shadow, so a lone rock barely shelters.
+const FULL_SHADOW_LAND: f32 = 5.0;
+/// Sailing speed in the deepest shadow in a full gale, as a share of the open sea.
+const DEEPEST_LEE_SPEED: f32 = 0.55;
+/// How far the wind must back or veer before the shadows are cast again (radians).
+const RECAST_ANGLE: f32 = 0.15;
+
+/// How sheltered each cell of the map is from the current wind.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct WindShadow {
+    /// Map size the shadows were cast over (tiles).
+    map_width: u32,
+    map_height: u32,
+    /// Grid size (cells).
+    width: u32,
+    height: u32,
+    /// Shelter per cell, row-major: 0.0 in the open, 1.0 in the deepest lee.
+    shelter: Vec<f32>,
+    /// Direction the wind was blowing toward when the shadows were cast.
+    cast_for: Option<Vec2>,
+}
+
+impl WindShadow {
+    /// Casts the shadows of the land on `map` for a wind blowing toward `wind_direction`.
+    pub fn cast(map: &MapData, wind_direction: Vec2) -> Self {
+        let width = map.width.div_ceil(SHADOW_CELL_TILES);
+        let height = map.height.div_ceil(SHADOW_CELL_TILES);
+        let upwind = -wind_direction.normalize_or_zero();
+        let centre = SHADOW_CELL_TILES as i32 / 2;
+        let shelter = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let tile = IVec2::new(x as i32, y as i32) * SHADOW_CELL_TILES as i32 + IVec2::splat(centre);
+                shelter_from_land(map, tile, upwind)
+            })
+            .collect();
+        Self {
+            map_width: map.width,
+            map_height: map.height,
+            width,
+            height,
+            shelter,
+            cast_for: Some(wind_direction),
+        }
+    }
+
+    /// Whether the wind has shifted enough since the shadows were cast to move them.
+    pub fn is_stale(&self, wind_direction: Vec2) -> bool {
+        match self.cast_for {
+            Some(cast_for) => cast_for.angle_to(wind_direction).abs() > RECAST_ANGLE,
+            None => true,
+        }
+    }
+
+    /// Shelter at a world position: 0.0 in the open, 1.0 in the deepest lee.
+    pub fn shelter_at(&self, position: Vec2) -> f32 {
+        let tile = world_to_tile(position, self.map_width, self.map_height);
+        if tile.x < 0 || tile.y < 0 {
+            return 0.0;
+        }
+        let (x, y) = (tile.x as u32 / SHADOW_CELL_TILES, tile.y as u32 / SHADOW_CELL_TILES);
+        if x >= self.width || y >= self.height {
+            return 0.0;
+        }
+        self.shelter[(y * self.width + x) as usize]
+    }
+
+    /// Sailing speed multiplier at a world position: 1.0 in the open, less in
+    /// the lee of land. The stronger the wind, the more shelter takes from it.
+    pub fn lee_factor(&self, position: Vec2, strength: f32) -> f32 {
+        1.0 - self.shelter_at(position) * (1.0 - DEEPEST_LEE_SPEED) * strength.clamp(0.0, 1.0)
+    }
+}
+
+/// How sheltered `tile` is by the land up to `SHADOW_REACH_TILES` upwind of it.
+///
+/// More land upwind deepens the shadow, and it fades with distance from the
+/// nearest shore.
+pub fn shelter_from_land(map: &MapData, tile: IVec2, upwind: Vec2) -> f32 {
+    let start = tile.as_vec2() + Vec2::splat(0.5);
+    let mut land = 0.0;
+    let mut nearest = None;
+    for step in 1..=SHADOW_REACH_TILES {
+        let sample = (start + upwind * step as f32).floor().as_ivec2();
+        if !map.in_bounds(sample.x, sample.y) {
+            break;
+        }
+        if !map.is_navigable(sample.x as u32, sample.y as u32) {
+            land += 1.0;
+            nearest.get_or_insert(step);
+        }
+    }
+    let Some(nearest) = nearest else {
+        return 0.0;
+    };
+    let fade = 1.0 - (nearest - 1) as f32 / SHADOW_REACH_TILES as f32;
+    (land / FULL_SHADOW_LAND).min(1.0) * fade
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::TileType;
+    use crate::utils::pathfinding::tile_to_world;
+
+    /// Open sea with a 6x6 island in the middle.
+    fn island() -> MapData {
+        let mut map = MapData::new(64, 64);
+        for x in 29..35 {
+            for y in 29..35 {
+                map.set_type(x, y, TileType::Land);
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn test_shadow_falls_leeward_of_land() {
+        let map = island();
+        // Blowing East: the lee is the eastern shore
+        let shadow = WindShadow::cast(&map, Vec2::X);
+        let at = |x, y| shadow.shelter_at(tile_to_world(IVec2::new(x, y), map.width, map.height));
+
+        assert!(at(37, 31) > 0.7);
+        assert_eq!(at(26, 31), 0.0);
+        assert_eq!(at(31, 40), 0.0);
+        assert!(at(45, 31) < at(37, 31));
+        assert_eq!(at(60, 31), 0.0);
+
+        let lee = tile_to_world(IVec2::new(37, 31), map.width, map.height);
+        assert!(shadow.lee_factor(lee, 1.0) < shadow.lee_factor(lee, 0.3));
+        assert!(shadow.lee_factor(lee, 1.0) >= DEEPEST_LEE_SPEED);
+        assert_eq!(shadow.lee_factor(lee, 0.0), 1.0);
+    }
+
+    #[test]
+    fn test_recast_only_when_the_wind_shifts() {
+        let map = island();
+        assert!(WindShadow::default().is_stale(Vec2::X));
+        let shadow = WindShadow::cast(&map, Vec2::X);
+        assert!(!shadow.is_stale(Vec2::from_angle(RECAST_ANGLE * 0.5)));
+        assert!(shadow.is_stale(Vec2::from_angle(-RECAST_ANGLE * 2.0)));
+
+        // A lone rock barely shelters
+        let mut rock = MapData::new(16, 16);
+        rock.set_type(8, 8, TileType::Land);
+        assert!(shelter_from_land(&rock, IVec2::new(9, 8), Vec2::NEG_X) <= 1.0 / FULL_SHADOW_LAND);
+    }
+}
@@ -1,8 +1,60 @@
 use bevy::prelude::*;
 
+use crate::resources::Season;
+
 /// In-game time constants.
 /// At 60Hz FixedUpdate, 1 in-game hour = ~1 real second.
 pub const TICKS_PER_HOUR: u32 = 60;
+/// Days in every month of the calendar.
+pub const DAYS_PER_MONTH: u32 = 30;
+/// Year the first day of a run falls in; day 1 is the 1st of January.
+pub const START_YEAR: u32 = 1715;
+/// Month names, January first.
+pub const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+/// A date on the calendar: twelve months of `DAYS_PER_MONTH` days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarDate {
+    /// Day of the month (1-indexed).
+    pub day: u32,
+    /// Month of the year (0 = January).
+    pub month: u32,
+    pub year: u32,
+}
+
+impl CalendarDate {
+    /// The date of a `WorldClock::day`.
+    pub fn from_day(day: u32) -> Self {
+        let days = day.saturating_sub(1);
+        let months = days / DAYS_PER_MONTH;
+        Self {
+            day: days % DAYS_PER_MONTH + 1,
+            month: months % MONTH_NAMES.len() as u32,
+            year: START_YEAR + months / MONTH_NAMES.len() as u32,
+        }
+    }
+
+    /// The date a world tick (`WorldClock::total_ticks`) falls on.
+    pub fn at_tick(tick: u32) -> Self {
+        Self::from_day(tick / (24 * TICKS_PER_HOUR) + 1)
+    }
+
+    pub fn month_name(&self) -> &'static str {
+        MONTH_NAMES[self.month as usize]
+    }
+
+    pub fn season(&self) -> Season {
+        Season::of_month(self.month)
+    }
+
+    /// E.g. "14 March 1715".
+    pub fn formatted(&self) -> String {
+        format!("{} {} {}", self.day, self.month_name(), self.year)
+    }
+}
 
 /// Resource tracking in-game time progression.
 ///
@@ -44,12 +96,27 @@ impl WorldClock {
         format!("Day {}, Hour {}", self.day, self.hour)
     }
 
+    /// Today's date on the calendar.
+    pub fn date(&self) -> CalendarDate {
+        CalendarDate::from_day(self.day)
+    }
+
+    pub fn season(&self) -> Season {
+        self.date().season()
+    }
+
     /// Returns total elapsed ticks since the start of the game.
     pub fn total_ticks(&self) -> u32 {
         let hours_total = (self.day - 1) * 24 + self.hour;
         hours_total * TICKS_PER_HOUR + self.tick
     }
 
+    /// Whether the clock has just turned to a new day. Systems checking this
+    /// run after `world_tick_system`, so they see each new day exactly once.
+    pub fn is_day_start(&self) -> bool {
+        self.hour == 0 && self.tick == 0
+    }
+
     /// Advances the clock by one tick.
     pub fn advance(&mut self) {
         self.tick += 1;
@@ -83,6 +150,16 @@ mod tests {
         assert_eq!(clock.formatted_time(), "Day 3, Hour 14");
     }
 
+    #[test]
+    fn test_calendar_dates() {
+        assert_eq!(CalendarDate::from_day(1).formatted(), "1 January 1715");
+        assert_eq!(CalendarDate::from_day(DAYS_PER_MONTH + 14).formatted(), "14 February 1715");
+        assert_eq!(CalendarDate::from_day(12 * DAYS_PER_MONTH + 1).formatted(), "1 January 1716");
+        // A deadline two days out from the last hour of the month
+        let clock = WorldClock { day: DAYS_PER_MONTH, hour: 23, tick: 0 };
+        assert_eq!(CalendarDate::at_tick(clock.total_ticks() + 2 * 24 * TICKS_PER_HOUR).formatted(), "2 February 1715");
+    }
+
     #[test]
     fn test_total_ticks() {
         let clock = WorldClock { day: 1, hour: 0, tick: 0 };
@@ -0,0 +1,248 @@
+//! Wrecks of enemy ships sunk in battle, left on the chart to be dived later.
+//!
+//! A battle only floats up `SUNK_SALVAGE_FRACTION` of a sunk ship's hold; the
+//! rest goes down with her. The wreck is charted near where the encounter
+//! began on the High Seas, and diving it recovers what was left aboard.
+//!
+//! Wrecks do not wait forever. Each day the sea takes
+//! `WRECK_DECAY_PERCENT_PER_DAY` of what is left aboard, and after `WRECK_LIFETIME_DAYS` she breaks up.
+//! From `SCAVENGER_GRACE_DAYS` on, pirates nearby come to strip her.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::components::cargo::Cargo;
+use crate::resources::SUNK_SALVAGE_FRACTION;
+
+/// How far from the encounter position wrecks are scattered (world units).
+pub const WRECK_SCATTER: f32 = 24.0;
+/// How close a ship must sail to dive a wreck (world units). Wider than any
+/// ship's arrival envelope, so a scavenger that has arrived can reach her.
+pub const WRECK_DIVE_RADIUS: f32 = 72.0;
+/// Percentage of what is left aboard a wreck that the sea takes each day.
+pub const WRECK_DECAY_PERCENT_PER_DAY: u32 = 10;
+/// Days a battle wreck lasts before she breaks up.
+pub const WRECK_LIFETIME_DAYS: u32 = 15;
+/// Days a wreck lies charted before scavengers come for her.
+pub const SCAVENGER_GRACE_DAYS: u32 = 2;
+
+/// What is left of `amount` after `days` of decay, rounding each day's loss up.
+pub fn decayed(amount: u32, days: u32) -> u32 {
+    let mut left = amount as u64;
+    for _ in 0..days {
+        if left == 0 {
+            break;
+        }
+        left = left * (100 - WRECK_DECAY_PERCENT_PER_DAY) as u64 / 100;
+    }
+    left as u32
+}
+
+/// A charted wreck waiting to be dived.
+#[derive(Debug, Clone, Reflect)]
+pub struct WreckSite {
+    /// Stable id, so map markers survive other wrecks being dived.
+    pub id: u32,
+    pub ship_name: String,
+    pub position: Vec2,
+    pub gold: u32,
+    pub hold: Cargo,
+    /// Day she went down.
+    pub day: u32,
+}
+
+impl WreckSite {
+    /// Whether nothing worth diving is left aboard.
+    pub fn is_empty(&self) -> bool {
+        self.gold == 0 && self.hold.total_units() == 0
+    }
+
+    /// Brings up her gold and what fits of her hold into `cargo`. What does
+    /// not fit stays aboard. Returns the gold and units recovered.
+    pub fn salvage_into(&mut self, cargo: &mut Cargo) -> (u32, u32) {
+        let gold = std::mem::take(&mut self.gold);
+        let hold: Vec<_> = self.hold.goods.iter().map(|(good, quantity)| (*good, *quantity)).collect();
+        let mut recovered = 0;
+        for (good, quantity) in hold {
+            let stowed = cargo.add(good, quantity);
+            self.hold.remove(good, stowed);
+            recovered += stowed;
+        }
+        (gold, recovered)
+    }
+
+    /// Lets `days` of decay take their share of her gold and hold.
+    pub fn decay(&mut self, days: u32) {
+        self.gold = decayed(self.gold, days);
+        let hold: Vec<_> = self.hold.goods.iter().map(|(good, quantity)| (*good, *quantity)).collect();
+        for (good, quantity) in hold {
+            self.hold.remove(good, quantity - decayed(quantity, days));
+        }
+    }
+}
+
+/// A ship sunk in the current battle, not yet charted.
+#[derive(Debug, Clone, Reflect)]
+pub struct Sinking {
+    pub ship_name: String,
+    pub gold: u32,
+    pub hold: Cargo,
+}
+
+/// Wrecks of enemy ships sunk this run.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct WreckSites {
+    pub sites: Vec<WreckSite>,
+    /// Sunk in the current battle, charted on return to the High Seas.
+    pub sinkings: Vec<Sinking>,
+    next_id: u32,
+}
+
+impl WreckSites {
+    /// Records a sinking with whatever the battle salvage did not float up.
+    pub fn record_sinking(&mut self, ship_name: impl Into<String>, gold: u32, cargo: Option<&Cargo>) {
+        let remains = |amount: u32| amount - (amount as f32 * SUNK_SALVAGE_FRACTION).floor() as u32;
+        let mut hold = Cargo::new(cargo.map_or(0, |c| c.capacity));
+        if let Some(cargo) = cargo {
+            for (good, quantity) in &cargo.goods {
+                hold.add(*good, remains(*quantity));
+            }
+        }
+        self.sinkings.push(Sinking {
+            ship_name: ship_name.into(),
+            gold: remains(gold),
+            hold,
+        });
+    }
+
+    /// Charts this battle's sinkings in a ring around `encounter`.
+    /// Sinkings with an empty hold leave nothing worth diving.
+    pub fn chart(&mut self, encounter: Vec2, day: u32) {
+        let sinkings = std::mem::take(&mut self.sinkings);
+        let count = sinkings.len().max(1) as f32;
+        for (index, sinking) in sinkings.into_iter().enumerate() {
+            if sinking.gold == 0 && sinking.hold.total_units() == 0 {
+                continue;
+            }
+            let angle = TAU * index as f32 / count;
+            let id = self.next_id;
+            self.next_id += 1;
+            self.sites.push(WreckSite {
+                id,
+                ship_name: sinking.ship_name,
+                position: encounter + Vec2::from_angle(angle) * WRECK_SCATTER,
+                gold: sinking.gold,
+                hold: sinking.hold,
+                day,
+            });
+        }
+    }
+
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut WreckSite> {
+        self.sites.iter_mut().find(|site| site.id == id)
+    }
+
+    /// Removes and returns the wreck with `id`.
+    pub fn dive(&mut self, id: u32) -> Option<WreckSite> {
+        let index = self.sites.iter().position(|site| site.id == id)?;
+        Some(self.sites.remove(index))
+    }
+
+    /// Decays every wreck by `days`. Wrecks picked clean, or older than
+    /// `WRECK_LIFETIME_DAYS` on `today`, break up; they are removed and returned.
+    pub fn decay(&mut self, days: u32, today: u32) -> Vec<WreckSite> {
+        let mut lost = Vec::new();
+        for mut site in std::mem::take(&mut self.sites) {
+            site.decay(days);
+            if site.is_empty() || today.saturating_sub(site.day) >= WRECK_LIFETIME_DAYS {
+                lost.push(site);
+            } else {
+                self.sites.push(site);
+            }
+        }
+        lost
+    }
+
+    pub fn clear(&mut self) {
+        self.sites.clear();
+        self.sinkings.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::cargo::GoodType;
+
+    #[test]
+    fn test_wreck_keeps_what_salvage_left() {
+        let mut cargo = Cargo::new(50);
+        cargo.add(GoodType::Rum, 9);
+        let mut wrecks = WreckSites::default();
+        wrecks.record_sinking("Black Gull", 101, Some(&cargo));
+
+        // Salvage floats up floor(half); the wreck keeps the rest
+        assert_eq!(wrecks.sinkings[0].gold, 51);
+        assert_eq!(wrecks.sinkings[0].hold.get(GoodType::Rum), 5);
+    }
+
+    #[test]
+    fn test_chart_and_dive_by_id() {
+        let mut wrecks = WreckSites::default();
+        wrecks.record_sinking("Black Gull", 100, None);
+        wrecks.record_sinking("Empty Hulk", 0, None);
+        wrecks.record_sinking("Sea Wolf", 40, None);
+        wrecks.chart(Vec2::new(500.0, 500.0), 3);
+
+        assert!(wrecks.sinkings.is_empty());
+        assert_eq!(wrecks.sites.len(), 2);
+        for site in &wrecks.sites {
+            assert!((site.position.distance(Vec2::new(500.0, 500.0)) - WRECK_SCATTER).abs() < 1e-3);
+        }
+
+        let sea_wolf = wrecks.sites[1].id;
+        assert_eq!(wrecks.dive(wrecks.sites[0].id).unwrap().ship_name, "Black Gull");
+        assert_eq!(wrecks.dive(sea_wolf).unwrap().gold, 20);
+        assert!(wrecks.dive(sea_wolf).is_none());
+    }
+
+    #[test]
+    fn test_salvage_leaves_what_does_not_fit() {
+        let mut cargo = Cargo::new(50);
+        cargo.add(GoodType::Rum, 30);
+        let mut wrecks = WreckSites::default();
+        wrecks.record_sinking("Black Gull", 100, Some(&cargo));
+        wrecks.chart(Vec2::ZERO, 1);
+
+        let mut hold = Cargo::new(10);
+        let site = &mut wrecks.sites[0];
+        assert_eq!(site.salvage_into(&mut hold), (50, 10));
+        assert_eq!(hold.get(GoodType::Rum), 10);
+        assert_eq!(site.hold.get(GoodType::Rum), 5);
+        assert!(!site.is_empty());
+    }
+
+    #[test]
+    fn test_wrecks_decay_and_break_up() {
+        let mut cargo = Cargo::new(50);
+        cargo.add(GoodType::Rum, 40);
+        let mut wrecks = WreckSites::default();
+        wrecks.record_sinking("Black Gull", 200, Some(&cargo));
+        wrecks.record_sinking("Sea Wolf", 2, None);
+        wrecks.chart(Vec2::ZERO, 1);
+
+        // A tenth a day: the small purse is gone, the rich wreck keeps the rest
+        let lost = wrecks.decay(2, 3);
+        assert_eq!(lost.len(), 1);
+        assert_eq!(lost[0].ship_name, "Sea Wolf");
+        assert_eq!(wrecks.sites[0].gold, 81);
+        assert_eq!(wrecks.sites[0].hold.get(GoodType::Rum), 16);
+
+        // However much is left, she breaks up in the end
+        let lost = wrecks.decay(0, 1 + WRECK_LIFETIME_DAYS);
+        assert_eq!(lost[0].ship_name, "Black Gull");
+        assert!(wrecks.sites.is_empty());
+    }
+}
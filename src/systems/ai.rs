@@ -1,14 +1,32 @@
 //! AI systems for controlling enemy ships in combat.
 //! 
 //! The AI uses a broadside circling strategy:
-//! - Circle around the player to maintain optimal firing range
-//! - Keep the player perpendicular (at broadside angle) for cannon fire
+//! - Circle around the nearest foe to maintain optimal firing range
+//! - Keep the foe perpendicular (at broadside angle) for cannon fire
 //! - Flee when health is critical
+//!
+//! Enemy ships fight the player and her fleet; the player's own fleet ships
+//! (`PlayerOwned`) fight the enemy with the same tactics. A ship with a
+//! `Lookout` only knows what she can see (see `plugins::line_of_sight`).
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use avian2d::prelude::*;
 
-use crate::components::{Ship, Player, Health, AI, Projectile, TargetComponent, CombatEntity};
+use crate::components::{Ship, Player, Health, AI, Projectile, AmmoType, CombatEntity, JuryRig, ShotHeight, Surrendered};
+use crate::components::sails::SailTrim;
+use crate::components::{EncounteredShip, ShipClass, ShipType};
+use crate::components::stations::CrewStations;
+use crate::components::{Cargo, Coast, Crew, Faction, Fire, Lookout, PlayerOwned, WaterIntake};
+use crate::resources::{
+    BalanceConfig, EncounterShip, EncounterTemplates, PacingDirector, RunSettings, ShipDefinitions, TradeLaneTraffic, WorldClock,
+};
+use crate::systems::combat::BROADSIDE_ROUNDS;
+use crate::systems::movement::hull_drag;
+use crate::plugins::grounding::coast_avoidance;
+use crate::plugins::pacing::TurnedTail;
+use crate::systems::separation::{separation, Side, COMBAT_SEPARATION};
+use rand::Rng;
 
 /// AI behavior state.
 #[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +38,10 @@ pub enum AIState {
     Fleeing,
 }
 
+/// Base AI reload time in seconds; the default for `CannonBalance::ai_reload_seconds`,
+/// and the first reload of a newly spawned ship.
+pub const AI_RELOAD_SECONDS: f32 = 2.0;
+
 /// Per-enemy cannon cooldown tracking.
 #[derive(Component, Debug)]
 pub struct AICannonCooldown {
@@ -29,11 +51,42 @@ pub struct AICannonCooldown {
 impl Default for AICannonCooldown {
     fn default() -> Self {
         Self {
-            timer: Timer::from_seconds(2.0, TimerMode::Once), // Slightly slower than player
+            timer: Timer::from_seconds(AI_RELOAD_SECONDS, TimerMode::Once),
         }
     }
 }
 
+/// How well an AI ship's captain fights her: above 1.0 her guns reload
+/// quicker and her broadsides fly truer, below it slower and wilder.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct CaptainSkill(pub f32);
+
+impl Default for CaptainSkill {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl CaptainSkill {
+    /// Multiplier on reload time and on the spread of her broadside.
+    pub fn gunnery_multiplier(&self) -> f32 {
+        1.0 / self.0.max(0.25)
+    }
+}
+
+/// Shot an AI ship loads for her next broadside: the first specialty shot
+/// her magazine still holds a broadside of, taken from it, else round shot.
+pub fn ai_broadside_shot(cargo: Option<&mut Cargo>) -> AmmoType {
+    let Some(cargo) = cargo else {
+        return AmmoType::RoundShot;
+    };
+    AmmoType::ALL
+        .into_iter()
+        .filter(|ammo| ammo.is_limited())
+        .find(|ammo| cargo.take_ammo(*ammo, BROADSIDE_ROUNDS))
+        .unwrap_or(AmmoType::RoundShot)
+}
+
 /// AI physics configuration.
 #[derive(Resource)]
 pub struct AIPhysicsConfig {
@@ -70,12 +123,69 @@ impl Default for AIPhysicsConfig {
     }
 }
 
-/// Main AI behavior system that controls enemy ship movement.
-/// Runs in FixedUpdate for physics consistency.
+/// Weight of the separation heading against an AI ship's own heading.
+const COMBAT_SEPARATION_WEIGHT: f32 = 1.5;
+/// How far ahead an AI ship's lookout watches for rocks (seconds at her present speed).
+const COAST_LOOKOUT_SECONDS: f32 = 1.5;
+/// Weight of bearing away from the rocks against an AI ship's own heading;
+/// heavy enough to turn her off a chase.
+const COAST_AVOIDANCE_WEIGHT: f32 = 3.0;
+
+/// Every ship in a battle, with the side it fights for.
+pub type Combatants<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Transform, Has<Player>, Has<PlayerOwned>, Option<&'static Faction>, Has<Surrendered>),
+    With<Ship>,
+>;
+
+/// Where each ship in a battle is and whose side it is on. A ship that has
+/// struck her colours fights for no one.
+pub fn battle_sides(combatants: &Combatants) -> Vec<(Entity, Vec2, Option<Side>)> {
+    combatants
+        .iter()
+        .map(|(entity, transform, is_player, owned, faction, struck)| {
+            let side = if struck { None } else { Side::of(is_player || owned, faction) };
+            (entity, transform.translation.truncate(), side)
+        })
+        .collect()
+}
+
+/// The side `entity` fights for in `sides`.
+pub fn side_of(entity: Entity, sides: &[(Entity, Vec2, Option<Side>)]) -> Option<Side> {
+    sides.iter().find(|(other, _, _)| *other == entity).and_then(|(_, _, side)| *side)
+}
+
+/// Position of the nearest ship at war with `side`, seen from `position`.
+/// Every battle is the player's: enemy flags never turn on each other.
+pub fn nearest_foe(side: Option<Side>, position: Vec2, sides: &[(Entity, Vec2, Option<Side>)]) -> Option<Vec2> {
+    let side = side?;
+    sides
+        .iter()
+        .filter(|(_, _, other)| other.is_some_and(|other| (other == Side::Player) != (side == Side::Player)))
+        .map(|(_, other_position, _)| *other_position)
+        .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)))
+}
+
+/// Position of the foe a ship at `position` may fire on: the one her
+/// `Lookout` has in sight, or without one the nearest.
+pub fn foe_in_sight(lookout: Option<&Lookout>, entity: Entity, position: Vec2, sides: &[(Entity, Vec2, Option<Side>)]) -> Option<Vec2> {
+    match lookout {
+        Some(lookout) => lookout.foe.filter(|_| lookout.in_sight),
+        None => nearest_foe(side_of(entity, sides), position, sides),
+    }
+}
+
+/// Main AI behavior system that controls AI ship movement.
+/// Runs in FixedUpdate for physics consistency. Ships steer clear of allies
+/// closer than `COMBAT_SEPARATION` rather than piling onto the same point,
+/// and bear away from any coast their lookout sees ahead.
+/// Enemy ships strike their colours when badly holed; the player's fleet
+/// ships break off and run instead.
 pub fn combat_ai_system(
     mut commands: Commands,
     config: Res<AIPhysicsConfig>,
-    player_query: Query<&Transform, (With<Player>, With<Ship>, Without<AI>)>,
+    combatants: Combatants,
     mut ai_query: Query<
         (
             Entity,
@@ -87,28 +197,45 @@ pub fn combat_ai_system(
             &mut ExternalForce,
             &mut ExternalTorque,
             &mut AIState,
+            Option<&JuryRig>,
+            Option<&SailTrim>,
+            Option<&CrewStations>,
+            Option<&Crew>,
+            Option<&ShipType>,
+            Option<&Lookout>,
         ),
-        (With<Ship>, With<AI>),
+        (With<Ship>, With<AI>, Without<Surrendered>),
     >,
+    coast: Query<&Transform, (With<Coast>, Without<Ship>)>,
 ) {
-    let Ok(player_transform) = player_query.get_single() else {
-        return; // No player to chase
-    };
-    let player_pos = player_transform.translation.truncate();
+    let sides = battle_sides(&combatants);
 
-    for (entity, transform, health, velocity, ang_velocity, mass, mut force, mut torque, ai_state) in &mut ai_query {
+    for (entity, transform, health, velocity, ang_velocity, mass, mut force, mut torque, mut ai_state, jury_rig, trim, stations, crew, ship_type, lookout) in &mut ai_query {
+        let maneuver = stations.map_or(1.0, CrewStations::maneuver_multiplier);
+        let side = side_of(entity, &sides);
         // Check for surrender condition
         if health.hull < 20.0 {
-            // Surrender - insert marker and stop AI logic
-            commands.entity(entity)
-                .insert(crate::components::Surrendered)
-                .insert(Name::new("Surrendered Ship"));
-            continue;
+            if side == Some(Side::Player) {
+                // The player's own ships never strike; they run for it
+                if *ai_state != AIState::Fleeing {
+                    *ai_state = AIState::Fleeing;
+                }
+            } else {
+                // Surrender - insert marker and stop AI logic
+                commands.entity(entity)
+                    .insert(Surrendered)
+                    .insert(Name::new("Surrendered Ship"));
+                continue;
+            }
         }
 
         let ai_pos = transform.translation.truncate();
-        let to_player = player_pos - ai_pos;
-        let distance = to_player.length();
+        // Steer for the foe she is after, seen or last seen
+        let Some(foe_pos) = lookout.map_or_else(|| nearest_foe(side, ai_pos, &sides), |lookout| lookout.foe) else {
+            continue; // Nobody left to fight
+        };
+        let to_foe = foe_pos - ai_pos;
+        let distance = to_foe.length();
 
         // Get ship's forward direction (Y+ in local space after flip_y)
         let forward = (transform.rotation * Vec3::Y).truncate();
@@ -117,39 +244,39 @@ pub fn combat_ai_system(
         // Calculate desired behavior based on state
         let (desired_direction, should_thrust) = match *ai_state {
             AIState::Circling => {
-                // Broadside circling: maintain perpendicular angle to player
-                // Dynamically choose which side to present based on player position
+                // Broadside circling: maintain perpendicular angle to the foe
+                // Dynamically choose which side to present based on the foe's position
                 
-                let to_player_normalized = if distance > 0.01 { 
-                    to_player / distance 
+                let to_foe_normalized = if distance > 0.01 { 
+                    to_foe / distance 
                 } else { 
                     Vec2::Y 
                 };
                 
-                // Determine which side the player is on relative to our heading
+                // Determine which side the foe is on relative to our heading
                 // Positive = starboard (right), Negative = port (left)
-                let player_side = right.dot(to_player_normalized);
+                let foe_side = right.dot(to_foe_normalized);
                 
-                // Circle direction: if player is to starboard, circle counter-clockwise
+                // Circle direction: if the foe is to starboard, circle counter-clockwise
                 // to keep them on starboard; if port, circle clockwise to keep them on port
-                let circle_direction = player_side.signum();
+                let circle_direction = foe_side.signum();
                 
                 // The tangent direction (perpendicular to line-of-sight)
-                // Direction depends on which side we want the player
+                // Direction depends on which side we want the foe
                 let tangent = Vec2::new(
-                    -to_player_normalized.y * circle_direction,
-                    to_player_normalized.x * circle_direction,
+                    -to_foe_normalized.y * circle_direction,
+                    to_foe_normalized.x * circle_direction,
                 );
                 
                 // Blend between closing in and circling based on range
                 let range_factor = (distance / config.optimal_range).clamp(0.5, 2.0);
                 
                 let desired = if distance > config.optimal_range * 1.2 {
-                    // Too far: move toward player while circling
-                    (to_player_normalized * 0.6 + tangent * 0.4).normalize_or_zero()
+                    // Too far: move toward the foe while circling
+                    (to_foe_normalized * 0.6 + tangent * 0.4).normalize_or_zero()
                 } else if distance < config.optimal_range * 0.8 {
                     // Too close: move away while circling
-                    (-to_player_normalized * 0.6 + tangent * 0.4).normalize_or_zero()
+                    (-to_foe_normalized * 0.6 + tangent * 0.4).normalize_or_zero()
                 } else {
                     // Good range: pure circling
                     tangent
@@ -158,12 +285,35 @@ pub fn combat_ai_system(
                 (desired, range_factor > 0.6)
             }
             AIState::Fleeing => {
-                // Run away from player
-                let away = if distance > 0.01 { -to_player / distance } else { -Vec2::Y };
+                // Run away from the foe
+                let away = if distance > 0.01 { -to_foe / distance } else { -Vec2::Y };
                 (away, true)
             }
         };
 
+        // Make room for allies crowding the same water
+        let push = side.map_or(Vec2::ZERO, |side| {
+            let friends = sides
+                .iter()
+                .filter(|(_, _, other_side)| *other_side == Some(side))
+                .map(|(other, position, _)| (*other, *position));
+            separation(entity, ai_pos, friends, COMBAT_SEPARATION)
+        });
+        let desired_direction = if push == Vec2::ZERO {
+            desired_direction
+        } else {
+            (desired_direction + push * COMBAT_SEPARATION_WEIGHT).normalize_or(desired_direction)
+        };
+
+        // Bear away from rocks ahead, however the fight is going
+        let lookout = ai_pos + velocity.0 * COAST_LOOKOUT_SECONDS;
+        let bear_away = coast_avoidance(lookout, coast.iter().map(|rock| rock.translation.truncate()));
+        let desired_direction = if bear_away == Vec2::ZERO {
+            desired_direction
+        } else {
+            (desired_direction + bear_away * COAST_AVOIDANCE_WEIGHT).normalize_or(desired_direction)
+        };
+
         // Calculate steering torque using a PD controller to prevent oscillation
         let desired_angle = desired_direction.y.atan2(desired_direction.x) - std::f32::consts::FRAC_PI_2;
         let current_angle = transform.rotation.to_euler(EulerRot::ZYX).0;
@@ -187,7 +337,11 @@ pub fn combat_ai_system(
         
         // Combine P and D terms, clamp to [-1, 1], then scale by max torque
         let torque_factor = (proportional * kp + derivative).clamp(-1.0, 1.0);
-        let torque_amount = torque_factor * config.torque;
+        // Rudder damage weakens the helm; none at all while jury-rigging. A
+        // short-handed crew cannot brace the yards round as smartly
+        let handling = crew.map_or(1.0, Crew::handling_multiplier);
+        let steering = if jury_rig.is_some() { 0.0 } else { health.steering() * handling };
+        let torque_amount = torque_factor * config.torque * steering * maneuver;
         torque.set_torque(torque_amount);
 
         // Apply thrust when roughly facing correct direction
@@ -196,30 +350,74 @@ pub fn combat_ai_system(
         let facing_right = forward.dot(desired_direction) > facing_threshold;
         
         let thrust_force = if should_thrust && facing_right {
-            forward * config.thrust
+            forward * config.thrust * health.sail_power() * trim.map_or(1.0, SailTrim::speed_factor) * maneuver
         } else {
             Vec2::ZERO
         };
 
         // Apply anisotropic drag (keel effect) - same as player
-        let vel = velocity.0;
-        let forward_speed = vel.dot(forward);
-        let lateral_speed = vel.dot(right);
-        
-        let drag_force = -forward * forward_speed * config.drag_forward * mass.0
-                        - right * lateral_speed * config.drag_lateral * mass.0;
+        let ship_type = ship_type.copied().unwrap_or_default();
+        let rudder = torque_factor * steering;
+        let drag_force = hull_drag(velocity.0, forward, rudder, ship_type, config.drag_forward, config.drag_lateral) * mass.0;
 
         force.set_force(thrust_force + drag_force);
     }
 }
 
-/// AI firing system - fires cannons when player is in broadside arc.
+/// AI sail handling: battle sail within reach of the guns for steadier
+/// broadsides, full sail to close the range or run.
+pub fn ai_sail_trim_system(
+    mut commands: Commands,
+    config: Res<AIPhysicsConfig>,
+    combatants: Combatants,
+    ai_query: Query<(Entity, &Transform, &AIState, Option<&SailTrim>), (With<Ship>, With<AI>)>,
+) {
+    let sides = battle_sides(&combatants);
+
+    for (entity, transform, ai_state, trim) in &ai_query {
+        let position = transform.translation.truncate();
+        let Some(foe_pos) = nearest_foe(side_of(entity, &sides), position, &sides) else {
+            continue;
+        };
+        let distance = position.distance(foe_pos);
+        let wanted = if *ai_state == AIState::Circling && distance <= config.firing_range * 1.5 {
+            SailTrim::Battle
+        } else {
+            SailTrim::Full
+        };
+        if trim != Some(&wanted) {
+            commands.entity(entity).insert(wanted);
+        }
+    }
+}
+
+/// AI crew heuristics: everyone to the sheets when running, to the pumps when
+/// badly holed, flooding or afire, otherwise to the guns.
+pub fn ai_crew_station_system(
+    mut ai_query: Query<(&AIState, &Health, Option<&WaterIntake>, Has<Fire>, &mut CrewStations), (With<Ship>, With<AI>)>,
+) {
+    for (ai_state, health, water_intake, burning, mut stations) in &mut ai_query {
+        let weights = if *ai_state == AIState::Fleeing {
+            [1, 4, 1]
+        } else if health.hull_ratio() < 0.5 || water_intake.is_some_and(|w| w.rate > 1.0) || burning {
+            [2, 1, 3]
+        } else {
+            [3, 2, 1]
+        };
+        let wanted = CrewStations::weighted(stations.hands(), weights);
+        if *stations != wanted {
+            *stations = wanted;
+        }
+    }
+}
+
+/// AI firing system - fires cannons when the nearest foe in sight is in broadside arc.
 pub fn ai_firing_system(
     mut commands: Commands,
     time: Res<Time>,
     config: Res<AIPhysicsConfig>,
     asset_server: Res<AssetServer>,
-    player_query: Query<&Transform, (With<Player>, With<Ship>, Without<AI>)>,
+    combatants: Combatants,
     mut ai_query: Query<
         (
             Entity,
@@ -227,16 +425,21 @@ pub fn ai_firing_system(
             &LinearVelocity,
             &AIState,
             &mut AICannonCooldown,
+            Option<&SailTrim>,
+            Option<&CrewStations>,
+            Option<&Crew>,
+            Option<&CaptainSkill>,
+            Option<&mut Cargo>,
+            Option<&Lookout>,
         ),
-        (With<Ship>, With<AI>),
+        (With<Ship>, With<AI>, Without<Surrendered>),
     >,
+    balance: Res<BalanceConfig>,
 ) {
-    let Ok(player_transform) = player_query.get_single() else {
-        return;
-    };
-    let player_pos = player_transform.translation.truncate();
+    let sides = battle_sides(&combatants);
+    let mut rng = rand::thread_rng();
 
-    for (entity, transform, velocity, ai_state, mut cooldown) in &mut ai_query {
+    for (entity, transform, velocity, ai_state, mut cooldown, trim, stations, crew, captain, cargo, lookout) in &mut ai_query {
         // Tick cooldown
         cooldown.timer.tick(time.delta());
 
@@ -251,29 +454,36 @@ pub fn ai_firing_system(
         }
 
         let ai_pos = transform.translation.truncate();
-        let to_player = player_pos - ai_pos;
-        let distance = to_player.length();
+        // Hold fire at a foe she cannot see
+        let Some(foe_pos) = foe_in_sight(lookout, entity, ai_pos, &sides) else {
+            continue;
+        };
+        let to_foe = foe_pos - ai_pos;
+        let distance = to_foe.length();
 
         // Check range
         if distance > config.firing_range {
             continue;
         }
 
-        // Check if player is in broadside arc
+        // Check if the foe is in broadside arc
         let right = (transform.rotation * Vec3::X).truncate();
-        let to_player_normalized = to_player / distance;
+        let to_foe_normalized = to_foe / distance;
         
         // Dot product with right vector: 1.0 = perfect starboard, -1.0 = perfect port
-        let broadside_dot = right.dot(to_player_normalized).abs();
+        let broadside_dot = right.dot(to_foe_normalized).abs();
         
-        // We want the dot to be high (player is to our side)
+        // We want the dot to be high (the foe is to our side)
         let in_arc = broadside_dot > (1.0 - config.firing_arc);
 
         if in_arc {
             // Fire! Determine which side (port or starboard)
-            let side = if right.dot(to_player_normalized) > 0.0 { 1.0 } else { -1.0 };
+            let side = if right.dot(to_foe_normalized) > 0.0 { 1.0 } else { -1.0 };
             
             let spawn_direction = right * side;
+            let gunnery = captain.copied().unwrap_or_default().gunnery_multiplier();
+            let spread = trim.map_or(SailTrim::Full.gun_spread(), SailTrim::gun_spread) * gunnery;
+            let ammo = ai_broadside_shot(cargo.map(Mut::into_inner));
             let spawn_pos_center = transform.translation + (Vec3::from((right * side * 40.0, 0.0))) + Vec3::Z * 5.0;
             let projectile_speed = 400.0;
 
@@ -293,54 +503,275 @@ pub fn ai_firing_system(
                     RigidBody::Dynamic,
                     Collider::circle(8.0),
                     Sensor,
-                    LinearVelocity(velocity.0 + spawn_direction * projectile_speed),
-                    Projectile {
-                        damage: 10.0,
-                        target: TargetComponent::Hull,
-                        source: entity,
-                    },
+                    LinearVelocity(velocity.0 + Vec2::from_angle(rng.gen_range(-spread..=spread)).rotate(spawn_direction) * projectile_speed),
+                    Projectile::new(ammo, entity).scaled(balance.cannons.damage_multiplier),
+                    ShotHeight::default(),
                     crate::systems::combat::ProjectileTimer::default(),
                     CombatEntity,
                 ));
             }
 
-            // Reset cooldown
+            // Reset cooldown, scaled by the hands on the guns, how many there are
+            // and the captain driving them
+            let reload = balance.cannons.ai_reload_seconds
+                * stations.map_or(1.0, CrewStations::reload_multiplier)
+                * crew.map_or(1.0, Crew::reload_multiplier)
+                * gunnery;
+            cooldown.timer.set_duration(std::time::Duration::from_secs_f32(reload));
             cooldown.timer.reset();
             
             info!(
-                "Enemy fired broadside to {}!",
+                "{} fired {} to {}!",
+                if side_of(entity, &sides) == Some(Side::Player) { "Consort" } else { "Enemy" },
+                ammo.name().to_lowercase(),
                 if side > 0.0 { "Starboard" } else { "Port" }
             );
         }
     }
 }
 
+/// Rolls what an enemy ship carries, for the loot screen if it is beaten.
+/// Pirates carry plunder in coin; navy and merchant ships carry trade goods.
+pub fn enemy_hold(faction: crate::components::FactionId, rng: &mut impl rand::Rng) -> (crate::components::Cargo, crate::components::Gold) {
+    use crate::components::{Cargo, FactionId, Gold, GoodType};
+
+    const GOODS: [GoodType; 8] = [
+        GoodType::Rum,
+        GoodType::Sugar,
+        GoodType::Spices,
+        GoodType::Timber,
+        GoodType::Cloth,
+        GoodType::Weapons,
+        GoodType::Fish,
+        GoodType::Fruit,
+    ];
+
+    let (gold, kinds, units) = match faction {
+        FactionId::Pirates => (rng.gen_range(60..=200), 1..=2, 5..=15),
+        _ => (rng.gen_range(20..=80), 2..=3, 10..=30),
+    };
+
+    let mut cargo = Cargo::new(100);
+    for _ in 0..rng.gen_range(kinds) {
+        let good = GOODS[rng.gen_range(0..GOODS.len())];
+        cargo.add(good, rng.gen_range(units.clone()));
+    }
+    (cargo, Gold(gold))
+}
+
+/// Most enemy ships in one battle.
+pub const MAX_SQUADRON: usize = 5;
+/// Days at sea over which enemy crews harden to their toughest.
+const VETERAN_DAYS: f32 = 120.0;
+/// Most the enemy's hulls and captains are toughened, as a share, late in a run.
+const VETERAN_BONUS: f32 = 0.3;
+
+/// The enemy ships for a battle. The squadron sighted on the High Seas sets
+/// how many come and their hulls and classes, the encounter template their loadouts
+/// (taken in turn). One more ship answers every two escorts in the player's
+/// fleet, and the longer the run has gone on, the stouter the hulls and the
+/// better the captains.
+pub fn muster_squadron(
+    template: &[EncounterShip],
+    sighted: &[(ShipType, ShipClass)],
+    escorts: usize,
+    day: u32,
+) -> Vec<EncounterShip> {
+    let fallback = [EncounterShip::default()];
+    let loadouts = if template.is_empty() { &fallback[..] } else { template };
+    let base = if sighted.is_empty() { loadouts.len() } else { sighted.len() };
+    let count = (base + escorts / 2).min(MAX_SQUADRON);
+    let veterancy = 1.0 + (day as f32 / VETERAN_DAYS).min(1.0) * VETERAN_BONUS;
+
+    (0..count)
+        .map(|rank| {
+            let mut ship = loadouts[rank % loadouts.len()].clone();
+            if let Some((ship_type, class)) = sighted.get(rank) {
+                ship.ship_type = *ship_type;
+                ship.class = Some(class.0.clone());
+            }
+            ship.health_multiplier *= veterancy;
+            ship.captain_skill *= veterancy;
+            ship
+        })
+        .collect()
+}
+
+/// How far north of the player the enemy's lead ship enters a battle.
+const ENEMY_LEAD_DISTANCE: f32 = 200.0;
+/// How far each pair of the enemy's other ships is spread abeam of her lead,
+/// and how much further off it stands.
+const ENEMY_LINE_SPACING: f32 = 140.0;
+const ENEMY_LINE_DEPTH: f32 = 60.0;
+
+/// Where the enemy's ships enter the battle: the first to the north, the
+/// rest spread to either side of her and further off.
+fn enemy_spawn_position(rank: usize) -> Vec2 {
+    if rank == 0 {
+        return Vec2::new(0.0, ENEMY_LEAD_DISTANCE);
+    }
+    let pair = rank.div_ceil(2) as f32;
+    let side = if rank % 2 == 1 { 1.0 } else { -1.0 };
+    Vec2::new(side * pair * ENEMY_LINE_SPACING, ENEMY_LEAD_DISTANCE + pair * ENEMY_LINE_DEPTH)
+}
+
+/// What sets the enemy's make-up in a battle: the encounter templates, the
+/// ship classes, the difficulty, and how much danger the pacing director
+/// allows yet.
+#[derive(SystemParam)]
+pub struct EnemyMuster<'w> {
+    templates: Res<'w, EncounterTemplates>,
+    ships: Res<'w, ShipDefinitions>,
+    run_settings: Res<'w, RunSettings>,
+    pacing: Res<'w, PacingDirector>,
+}
+
 /// System to spawn enemies when entering combat state.
-/// Uses the EncounteredEnemy resource to spawn the correct faction.
+/// Uses the EncounteredEnemy resource for the faction and the squadron
+/// sighted, and draws the loadouts from an encounter template that fits the
+/// faction, the danger of the waters the battle began in and the difficulty,
+/// and brings no ship heavier than the pacing director allows if one can
+/// (see `muster_squadron`). Early in a run the enemy may turn tail at once.
+/// The first ship is the one sighted, marked `EncounteredShip`.
 pub fn spawn_combat_enemies(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut encountered_enemy: ResMut<crate::plugins::worldmap::EncounteredEnemy>,
+    muster: EnemyMuster,
+    lane_traffic: Res<TradeLaneTraffic>,
+    world_clock: Res<WorldClock>,
 ) {
     use crate::components::FactionId;
     use crate::systems::ship::spawn_enemy_ship;
     
     // Get faction from encounter data, default to Pirates
     let faction = encountered_enemy.faction.take().unwrap_or(FactionId::Pirates);
+    let danger = encountered_enemy.position.map_or(0, |position| lane_traffic.danger_near(position));
     
-    // Spawn one enemy ship to the north
-    let enemy_id = spawn_enemy_ship(
-        &mut commands,
-        &asset_server,
-        Vec2::new(0.0, 200.0),
+    let mut rng = rand::thread_rng();
+    let template = muster.templates.pick_within(
         faction,
+        danger,
+        muster.run_settings.difficulty,
+        muster.pacing.heaviest_rank(),
+        &mut rng,
+    );
+    let flee_roll = rng.gen::<f32>();
+    let ai_state = if flee_roll < muster.pacing.flee_chance { AIState::Fleeing } else { AIState::default() };
+    let sighted = std::mem::take(&mut encountered_enemy.squadron);
+    let ships = muster_squadron(
+        template.map_or(&[][..], |template| template.ships.as_slice()),
+        &sighted,
+        encountered_enemy.escorts.len(),
+        world_clock.day,
     );
     
-    // Add AI-specific components
-    commands.entity(enemy_id).insert((
-        AIState::default(),
-        AICannonCooldown::default(),
-    ));
+    for (rank, ship) in ships.iter().enumerate() {
+        let enemy_id = spawn_enemy_ship(
+            &mut commands,
+            &asset_server,
+            enemy_spawn_position(rank),
+            faction,
+            ship,
+            &muster.ships,
+        );
+        
+        // Add AI-specific components
+        let (mut cargo, gold) = enemy_hold(faction, &mut rng);
+        for &(ammo, rounds) in &ship.ammo {
+            cargo.stow_ammo(ammo, rounds);
+        }
+        commands.entity(enemy_id).insert((
+            ai_state,
+            AICannonCooldown::default(),
+            cargo,
+            gold,
+        ));
+        if rank == 0 {
+            commands.entity(enemy_id).insert(EncounteredShip);
+        }
+        if ai_state == AIState::Fleeing {
+            commands.entity(enemy_id).insert(TurnedTail { roll: flee_roll });
+        }
+    }
     
-    info!("Combat enemy spawned with faction {:?}!", faction);
+    info!(
+        "Combat enemies spawned with faction {:?}: {} ({} ships, danger {}, threat {:.2}{})",
+        faction,
+        template.map_or("lone ship", |template| template.name.as_str()),
+        ships.len(),
+        danger,
+        muster.pacing.threat,
+        if ai_state == AIState::Fleeing { ", running" } else { "" }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::FactionId;
+
+    #[test]
+    fn test_nearest_foe_takes_sides() {
+        let player = Entity::from_raw(0);
+        let consort = Entity::from_raw(1);
+        let pirate = Entity::from_raw(2);
+        let navy = Entity::from_raw(3);
+        let prize = Entity::from_raw(4);
+        let sides = vec![
+            (player, Vec2::ZERO, Some(Side::Player)),
+            (consort, Vec2::new(0.0, -100.0), Some(Side::Player)),
+            (pirate, Vec2::new(0.0, 300.0), Some(Side::Faction(FactionId::Pirates))),
+            (navy, Vec2::new(0.0, -150.0), Some(Side::Faction(FactionId::NationA))),
+            (prize, Vec2::new(0.0, -90.0), None),
+        ];
+
+        // Consorts make for the nearest enemy, passing over a struck prize
+        assert_eq!(nearest_foe(side_of(consort, &sides), Vec2::new(0.0, -100.0), &sides), Some(Vec2::new(0.0, -150.0)));
+        // Enemies fight the player's side, never each other
+        assert_eq!(nearest_foe(side_of(pirate, &sides), Vec2::new(0.0, 300.0), &sides), Some(Vec2::ZERO));
+        assert_eq!(nearest_foe(side_of(navy, &sides), Vec2::new(0.0, -150.0), &sides), Some(Vec2::new(0.0, -100.0)));
+        // A struck ship has no quarrel left
+        assert_eq!(nearest_foe(side_of(prize, &sides), Vec2::ZERO, &sides), None);
+    }
+
+    #[test]
+    fn test_ai_loads_specialty_shot_until_it_runs_short() {
+        let mut cargo = Cargo::new(100);
+        cargo.stow_ammo(AmmoType::GrapeShot, BROADSIDE_ROUNDS * 2 - 1);
+
+        assert_eq!(ai_broadside_shot(Some(&mut cargo)), AmmoType::GrapeShot);
+        assert_eq!(ai_broadside_shot(Some(&mut cargo)), AmmoType::RoundShot);
+        assert_eq!(cargo.ammo_count(AmmoType::GrapeShot), Some(BROADSIDE_ROUNDS - 1));
+        assert_eq!(ai_broadside_shot(None), AmmoType::RoundShot);
+    }
+
+    #[test]
+    fn test_squadron_matches_sighting_and_scales_with_threat() {
+        let template = vec![
+            EncounterShip { ammo: vec![(AmmoType::GrapeShot, 9)], ..default() },
+            EncounterShip { ship_type: ShipType::Frigate, captain_skill: 1.2, ..default() },
+        ];
+
+        // The sighted classes are kept, loadouts taken from the template in turn
+        let brig = (ShipType::Schooner, ShipClass("brig".to_string()));
+        let squadron = muster_squadron(&template, &[brig], 0, 0);
+        assert_eq!(squadron.len(), 1);
+        assert_eq!(squadron[0].ship_type, ShipType::Schooner);
+        assert_eq!(squadron[0].class_id(), "brig");
+        assert_eq!(squadron[0].ammo, vec![(AmmoType::GrapeShot, 9)]);
+
+        // Unsighted, the template sets the make-up; escorts draw reinforcements
+        let squadron = muster_squadron(&template, &[], 4, 0);
+        assert_eq!(squadron.len(), 4);
+        assert_eq!(squadron[3].ship_type, ShipType::Frigate);
+        assert_eq!(squadron[3].class_id(), "frigate");
+        let sloops = vec![(ShipType::Sloop, ShipClass::of(ShipType::Sloop)); 4];
+        assert_eq!(muster_squadron(&template, &sloops, 8, 0).len(), MAX_SQUADRON);
+
+        // Late in a run crews are hardened, up to the veteran bonus
+        let veteran = muster_squadron(&[], &[], 0, 1000);
+        assert_eq!(veteran[0].ship_type, ShipType::Sloop);
+        assert!((veteran[0].health_multiplier - (1.0 + VETERAN_BONUS)).abs() < 1e-5);
+    }
 }
@@ -4,11 +4,14 @@ use bevy::prelude::*;
 use crate::components::camera::CameraShake;
 use crate::events::CannonFiredEvent;
 use crate::plugins::core::MainCamera;
+use crate::resources::GameSettings;
 
 /// System that applies camera shake based on trauma level.
-/// Uses noise-based offset for smooth, organic shake.
+/// Uses noise-based offset for smooth, organic shake, scaled by the player's
+/// screen shake setting.
 pub fn camera_shake_system(
     time: Res<Time>,
+    settings: Res<GameSettings>,
     mut query: Query<(&mut Transform, &mut CameraShake), With<MainCamera>>,
 ) {
     let Ok((mut transform, mut shake)) = query.get_single_mut() else {
@@ -19,7 +22,7 @@ pub fn camera_shake_system(
     shake.decay(time.delta_secs());
 
     // Calculate shake intensity (trauma²)
-    let intensity = shake.shake_intensity();
+    let intensity = shake.shake_intensity() * settings.screen_shake;
     
     if intensity > 0.001 {
         // Use simple pseudo-random based on noise_time for offset
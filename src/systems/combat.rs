@@ -1,21 +1,40 @@
 use bevy::prelude::*;
-use bevy::utils::HashSet;
+use bevy::utils::{HashMap, HashSet};
 use avian2d::prelude::*;
+use rand::Rng;
 use crate::components::*;
 use crate::resources::*;
+use crate::components::damage_ledger::DamageLedger;
+use crate::components::sea_monster::SeaMonster;
 use crate::systems::movement::ShipInputBuffer;
+use leafwing_input_manager::prelude::ActionState;
+
+/// Rounds of shot a broadside uses (one per gun).
+pub const BROADSIDE_ROUNDS: u32 = 3;
 
 /// System that handles cannon firing based on buffered input.
 /// Gunner companion provides -30% cannon cooldown.
+/// Specialty shot is drawn from the magazine; when it runs short the guns
+/// fall back to round shot.
 pub fn cannon_firing_system(
     mut commands: Commands,
     mut cannon_state: ResMut<CannonState>,
     input_buffer: Res<ShipInputBuffer>,
     time: Res<Time>,
-    query: Query<(Entity, &Transform, &LinearVelocity), (With<Ship>, With<Player>)>,
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &LinearVelocity,
+        Option<&crate::components::sails::SailTrim>,
+        Option<&crate::components::stations::CrewStations>,
+        Option<&Crew>,
+        Option<&mut Cargo>,
+        Option<&crate::components::upgrade::Fittings>,
+    ), (With<Ship>, With<Player>)>,
     companion_query: Query<&crate::components::companion::CompanionRole>,
     asset_server: Res<AssetServer>,
     mut cannon_fired_events: EventWriter<crate::events::CannonFiredEvent>,
+    balance: Res<BalanceConfig>,
 ) {
     // Tick cooldown
     if cannon_state.cooldown_remaining > 0.0 {
@@ -36,10 +55,19 @@ pub fn cannon_firing_system(
     }
 
     if let Some(side) = fired_side {
-        if let Ok((_player_ent, transform, ship_velocity)) = query.get_single() {
+        if let Ok((_player_ent, transform, ship_velocity, trim, stations, crew, cargo, fittings)) = query.get_single_mut() {
+            if let Some(mut cargo) = cargo {
+                if !cargo.take_ammo(cannon_state.current_ammo, BROADSIDE_ROUNDS) {
+                    info!("Out of {} - loading round shot", cannon_state.current_ammo.name());
+                    cannon_state.current_ammo = AmmoType::RoundShot;
+                }
+            }
+
             // Get ship's local right vector (X-axis in local space)
             let right = transform.rotation * Vec3::X;
             let spawn_direction = (right * side).truncate();
+            let spread = trim.copied().unwrap_or_default().gun_spread();
+            let mut rng = rand::thread_rng();
             
             // Spawn a spread of projectiles (broadside)
             let spawn_pos_center = transform.translation + (right * side * 40.0) + Vec3::new(0.0, 0.0, 5.0);
@@ -60,12 +88,9 @@ pub fn cannon_firing_system(
                     RigidBody::Dynamic,
                     Collider::circle(8.0),
                     Sensor,
-                    LinearVelocity(ship_velocity.0 + spawn_direction * projectile_speed),
-                    Projectile {
-                        damage: 10.0,
-                        target: TargetComponent::Hull, // Default to hull for now
-                        source: _player_ent,
-                    },
+                    LinearVelocity(ship_velocity.0 + Vec2::from_angle(rng.gen_range(-spread..=spread)).rotate(spawn_direction) * projectile_speed),
+                    Projectile::new(cannon_state.current_ammo, _player_ent).scaled(balance.cannons.damage_multiplier),
+                    ShotHeight::default(),
                     ProjectileTimer::default(),
                     CombatEntity,
                 ));
@@ -75,7 +100,13 @@ pub fn cannon_firing_system(
             let has_gunner = companion_query.iter().any(|role| *role == crate::components::companion::CompanionRole::Gunner);
             let gunner_bonus = if has_gunner { 0.7 } else { 1.0 };
             
-            cannon_state.cooldown_remaining = cannon_state.base_cooldown * gunner_bonus;
+            // Gun crews at their stations reload faster; a short-handed ship slower
+            let crew_bonus = stations.map_or(1.0, |s| s.reload_multiplier()) * crew.map_or(1.0, Crew::reload_multiplier);
+
+            // Extra gun crews from the shipwright serve the broadside faster
+            let fitted_bonus = fittings.map_or(1.0, |fittings| fittings.reload_multiplier);
+
+            cannon_state.cooldown_remaining = balance.cannons.player_reload_seconds * gunner_bonus * crew_bonus * fitted_bonus;
             
             // Emit cannon fired event for screen shake
             cannon_fired_events.send(crate::events::CannonFiredEvent {
@@ -99,6 +130,8 @@ pub fn consume_firing_input(
     // If the cannon was on cooldown during this tick, the input is discarded.
     input_buffer.fire_port = false;
     input_buffer.fire_starboard = false;
+    input_buffer.fire_chaser = false;
+    input_buffer.fire_mortar = false;
 }
 
 /// Component to handle projectile despawning after some time.
@@ -111,6 +144,64 @@ impl Default for ProjectileTimer {
     }
 }
 
+/// Flies shot along its height: it climbs out of the gun, falls, and is
+/// lost when it splashes into the sea. Mortar shells keep their own arc.
+pub fn shot_flight_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ShotHeight), Without<MortarShell>>,
+) {
+    for (entity, mut shot) in &mut query {
+        shot.step(time.delta_secs());
+        if shot.has_splashed() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Gives each new shot a shadow on the water.
+pub fn spawn_shot_shadows(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    shots: Query<Entity, Added<ShotHeight>>,
+) {
+    for entity in &shots {
+        let shadow = commands
+            .spawn((
+                Sprite {
+                    image: asset_server.load("sprites/projectile.png"),
+                    color: Color::srgba(0.0, 0.0, 0.0, 0.35),
+                    custom_size: Some(Vec2::new(14.0, 14.0)),
+                    ..default()
+                },
+                Transform::from_xyz(0.0, 0.0, -1.0),
+                ShotShadow,
+            ))
+            .id();
+        commands.entity(entity).add_child(shadow);
+    }
+}
+
+/// Draws shot larger the higher it flies, with its shadow falling further
+/// away below it (the light is high to the upper left).
+pub fn shot_height_render_system(
+    mut shots: Query<(&ShotHeight, &mut Transform, &Children), Without<ShotShadow>>,
+    mut shadows: Query<&mut Transform, With<ShotShadow>>,
+) {
+    for (shot, mut transform, children) in &mut shots {
+        let scale = shot.sprite_scale();
+        transform.scale = Vec3::splat(scale);
+        let offset = Vec2::new(0.5, -0.5) * shot.height.max(0.0);
+        for &child in children {
+            if let Ok(mut shadow) = shadows.get_mut(child) {
+                // Undo the shot's scale, so the shadow stays the size of the ball
+                shadow.translation = (offset / scale).extend(-1.0 / scale);
+                shadow.scale = Vec3::splat(1.0 / scale);
+            }
+        }
+    }
+}
+
 /// System that handles projectiles (timeout, etc).
 pub fn projectile_system(
     mut commands: Commands,
@@ -129,8 +220,19 @@ pub fn projectile_system(
 pub fn projectile_collision_system(
     mut commands: Commands,
     mut collision_events: EventReader<Collision>,
-    projectiles: Query<(&Projectile, &Transform)>,
-    mut ships: Query<(Entity, &mut Health, &Transform, Option<&Name>, Option<&mut WaterIntake>), With<Ship>>,
+    projectiles: Query<(&Projectile, &Transform, Option<&ShotHeight>)>,
+    mut ships: Query<(
+        Entity,
+        &mut Health,
+        Option<&Name>,
+        Option<&mut WaterIntake>,
+        Option<&crate::components::sails::SailTrim>,
+        Option<&mut crate::components::supernatural::Spectral>,
+        Option<&mut crate::components::stations::CrewStations>,
+        Option<&mut Fire>,
+        Option<&Freeboard>,
+    ), With<Ship>>,
+    player_side: Query<(), (With<Ship>, Or<(With<Player>, With<PlayerOwned>)>)>,
     asset_server: Res<AssetServer>,
     mut ship_hit_events: EventWriter<crate::events::ShipHitEvent>,
     mut processed_projectiles: Local<HashSet<Entity>>,
@@ -157,7 +259,7 @@ pub fn projectile_collision_system(
         }
         processed_projectiles.insert(proj_ent);
 
-        if let (Ok((projectile, proj_transform)), Ok((entity, mut health, _ship_transform, name, water_intake))) = 
+        if let (Ok((projectile, proj_transform, shot)), Ok((entity, mut health, name, water_intake, trim, spectral, stations, fire, freeboard))) = 
             (projectiles.get(proj_ent), ships.get_mut(ship_ent)) 
         {
             // Skip if the ship hit is the source that fired it
@@ -165,10 +267,37 @@ pub fn projectile_collision_system(
                 continue;
             }
 
+            // The player's ships hold their fire over each other's decks
+            if player_side.contains(projectile.source) && player_side.contains(ship_ent) {
+                continue;
+            }
+
+            // Shot flying higher than her side passes overhead
+            if shot.is_some_and(|shot| shot.clears(freeboard.copied().unwrap_or_default())) {
+                continue;
+            }
+
             // Apply damage
+            let lit = spectral.as_ref().is_some_and(|s| s.is_lit());
+            let applied = match projectile.target {
+                TargetComponent::Sails => projectile.damage * trim.map_or(1.0, |t| t.canvas_exposure()),
+                TargetComponent::Hull if lit => 0.0,
+                _ => projectile.damage,
+            };
             match projectile.target {
-                TargetComponent::Sails => health.sails -= projectile.damage,
+                TargetComponent::Sails => {
+                    health.sails -= applied;
+                    // Shot into a ghost ship's rigging puts out a lantern
+                    if let Some(mut spectral) = spectral {
+                        if spectral.shoot_lantern() {
+                            info!("The last lantern gutters out - her hull is solid timber now!");
+                        }
+                    }
+                }
                 TargetComponent::Rudder => health.rudder -= projectile.damage,
+                TargetComponent::Hull if lit => {
+                    info!("The shot passes clean through her ghostly hull!");
+                }
                 TargetComponent::Hull => {
                     health.hull -= projectile.damage;
                     
@@ -185,6 +314,34 @@ pub fn projectile_collision_system(
                 }
             }
 
+            // Grape and heated shot do their worst on deck; a ghost's hull is not there to strike
+            let profile = projectile.ammo.profile();
+            if !(lit && projectile.target == TargetComponent::Hull) {
+                if let Some(mut stations) = stations {
+                    let lost = stations.lose_hands(profile.crew_casualties);
+                    if lost > 0 {
+                        info!("Grape shot sweeps the deck - {} hands down!", lost);
+                    }
+                    // Shot through the hull sends splinters across the gun deck
+                    if projectile.target == TargetComponent::Hull {
+                        let splinters = hull_casualties(projectile.damage, rand::thread_rng().gen());
+                        let lost = stations.lose_hands(splinters);
+                        if lost > 0 {
+                            info!("Splinters fly - {} hands down!", lost);
+                        }
+                    }
+                }
+                if profile.fire_chance > 0.0 && rand::thread_rng().gen::<f32>() < profile.fire_chance {
+                    if let Some(mut fire) = fire {
+                        fire.stoke(FIRE_IGNITION);
+                        info!("Hot shot feeds the blaze! Fire at {:.0}%", fire.intensity * 100.0);
+                    } else {
+                        commands.entity(entity).try_insert(Fire::default());
+                        info!("Fire breaks out aboard!");
+                    }
+                }
+            }
+
             let ship_name = name.map(|n| n.as_str()).unwrap_or("Unknown Ship");
             info!(
                 "Hit! {} damaged by {:?}. New Health: S:{:.1} R:{:.1} H:{:.1}",
@@ -195,12 +352,14 @@ pub fn projectile_collision_system(
                 health.hull
             );
 
-            // Emit ShipHitEvent for damage splatter VFX
+            // Emit ShipHitEvent for the damage ledger and damage splatter VFX
             let hit_pos = proj_transform.translation.truncate();
             ship_hit_events.send(crate::events::ShipHitEvent {
                 ship_entity: ship_ent,
                 hit_position: hit_pos,
-                damage: projectile.damage,
+                damage: applied,
+                attacker: projectile.source,
+                component: projectile.target,
             });
 
             // Spawn loot at the projectile impact location
@@ -212,6 +371,100 @@ pub fn projectile_collision_system(
     }
 }
 
+/// Credits each ship's damage ledger with the hits she took, as applied.
+/// Every source of hits sends a `ShipHitEvent`, so they are all counted here.
+pub fn record_ship_hits(
+    mut commands: Commands,
+    mut hit_events: EventReader<crate::events::ShipHitEvent>,
+    mut ledgers: Query<Option<&mut DamageLedger>, With<Ship>>,
+) {
+    let mut new_ledgers: HashMap<Entity, DamageLedger> = HashMap::new();
+    for hit in hit_events.read() {
+        match ledgers.get_mut(hit.ship_entity) {
+            Ok(Some(mut ledger)) => ledger.record(hit.attacker, hit.damage),
+            Ok(None) => new_ledgers.entry(hit.ship_entity).or_default().record(hit.attacker, hit.damage),
+            Err(_) => {}
+        }
+    }
+    for (entity, ledger) in new_ledgers {
+        commands.entity(entity).try_insert(ledger);
+    }
+}
+
+/// Burning ships lose hull and canvas until the fire is put out or the
+/// flooded deck drowns it. Fire fighting is done by the repair station.
+/// The hull the fire burns is credited to whoever last struck her.
+pub fn fire_damage_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Health, &Fire, Option<&WaterIntake>, Option<&mut DamageLedger>)>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut health, fire, water_intake, ledger) in &mut query {
+        if fire.is_out() {
+            commands.entity(entity).remove::<Fire>();
+            info!("The fire is out");
+            continue;
+        }
+        if water_intake.is_some_and(|intake| intake.is_swamped()) {
+            commands.entity(entity).remove::<Fire>();
+            info!("Water floods the deck and drowns the fire");
+            continue;
+        }
+        let burnt = fire.hull_damage() * delta;
+        health.hull -= burnt;
+        health.sails = (health.sails - fire.sail_damage() * delta).max(0.0);
+        if let Some(mut ledger) = ledger {
+            ledger.record_lingering(burnt);
+        }
+    }
+}
+
+/// Holed ships take on water, and the water aboard strains the hull.
+/// Pumping and plugging leaks is done by the repair station.
+/// The strain is credited to whoever last struck her.
+pub fn flooding_system(time: Res<Time>, mut query: Query<(&mut Health, &mut WaterIntake, Option<&mut DamageLedger>)>) {
+    let delta = time.delta_secs();
+    for (mut health, mut intake, ledger) in &mut query {
+        intake.tick(delta);
+        let strain = intake.hull_strain() * delta;
+        health.hull -= strain;
+        if let Some(mut ledger) = ledger {
+            ledger.record_lingering(strain);
+        }
+    }
+}
+
+/// Loads the next shot the magazine has rounds of (V / gamepad West).
+pub fn ammo_selection_system(
+    action_query: Query<&ActionState<crate::plugins::input::PlayerAction>>,
+    mut cannon_state: ResMut<CannonState>,
+    player_query: Query<&Cargo, (With<Ship>, With<Player>)>,
+) {
+    let Ok(action_state) = action_query.get_single() else {
+        return;
+    };
+    if !action_state.just_pressed(&crate::plugins::input::PlayerAction::CycleAmmo) {
+        return;
+    }
+    let cargo = player_query.get_single().ok();
+    cannon_state.current_ammo = next_loadable_ammo(cannon_state.current_ammo, cargo);
+    info!("Loading {}", cannon_state.current_ammo.name());
+}
+
+/// The shot after `current` in rotation that the magazine has a broadside of.
+pub fn next_loadable_ammo(current: AmmoType, cargo: Option<&Cargo>) -> AmmoType {
+    let mut ammo = current.next();
+    while ammo != current {
+        let rounds = cargo.map_or(Some(0), |cargo| cargo.ammo_count(ammo));
+        if rounds.is_none_or(|rounds| rounds >= BROADSIDE_ROUNDS) {
+            return ammo;
+        }
+        ammo = ammo.next();
+    }
+    current
+}
+
 /// System to cycle target components (DEPRECATED: broadside focused).
 pub fn target_cycling_system() {}
 
@@ -251,11 +504,17 @@ pub fn ship_destruction_system(
         Option<&Transform>,
         Option<&crate::components::Gold>,
         Option<&crate::components::Cargo>,
+        Option<&crate::components::Faction>,
+        Option<&DamageLedger>,
+        Has<PlayerOwned>,
     ), With<Ship>>,
+    player_query: Query<Entity, With<Player>>,
     mut ship_destroyed_events: EventWriter<crate::events::ShipDestroyedEvent>,
     mut death_data: ResMut<crate::resources::PlayerDeathData>,
+    mut spoils: ResMut<crate::resources::VictorySpoils>,
+    mut wrecks: ResMut<WreckSites>,
 ) {
-    for (entity, health, player, name, transform, gold, cargo) in &query {
+    for (entity, health, player, name, transform, gold, cargo, faction, ledger, fleet_ship) in &query {
         if health.is_destroyed() {
             let ship_name = name.map(|n| n.as_str()).unwrap_or("Unknown Ship");
             let was_player = player.is_some();
@@ -282,12 +541,32 @@ pub fn ship_destruction_system(
                     death_data.gold,
                     death_data.cargo.len()
                 );
+            } else if fleet_ship {
+                // Her hull and hold are struck from the fleet when the battle ends
+                info!("Lost {} from the fleet", ship_name);
+            } else {
+                // Whatever floats free of the wreck is offered on the loot screen
+                spoils.sources.push(crate::resources::SpoilsSource::sunk(
+                    ship_name,
+                    gold.map(|g| g.0).unwrap_or(0),
+                    cargo,
+                ));
+                // The rest goes down with her, to be dived later
+                wrecks.record_sinking(ship_name, gold.map(|g| g.0).unwrap_or(0), cargo);
             }
 
             // Send the event before despawning
             ship_destroyed_events.send(crate::events::ShipDestroyedEvent {
                 entity,
                 was_player,
+                was_fleet_ship: fleet_ship,
+                name: ship_name.to_string(),
+                faction: faction.map(|f| f.0),
+                final_blow: ledger.and_then(DamageLedger::final_blow),
+                shares: ledger.map(DamageLedger::shares).unwrap_or_default(),
+                player_credited: ledger.is_some_and(|ledger| {
+                    player_query.iter().any(|player| ledger.is_credited(player))
+                }),
             });
 
             // Despawn the ship entity
@@ -296,6 +575,32 @@ pub fn ship_destruction_system(
     }
 }
 
+/// Notes each enemy the player sank or helped sink in the journal.
+pub fn journal_kill_system(
+    mut ship_destroyed_events: EventReader<crate::events::ShipDestroyedEvent>,
+    player_query: Query<Entity, With<Player>>,
+    world_clock: Res<WorldClock>,
+    mut journal: ResMut<Journal>,
+) {
+    for event in ship_destroyed_events.read() {
+        if event.was_player || event.was_fleet_ship || !event.player_credited {
+            continue;
+        }
+        let player_share = event
+            .shares
+            .iter()
+            .filter(|(attacker, _)| player_query.contains(*attacker))
+            .map(|(_, share)| share)
+            .sum::<f32>();
+        let text = if event.final_blow.is_some_and(|attacker| player_query.contains(attacker)) {
+            format!("Sank the {}.", event.name)
+        } else {
+            format!("Helped sink the {}, dealing {:.0}% of the damage.", event.name, player_share * 100.0)
+        };
+        journal.record_as(&world_clock, JournalCategory::Combat, text);
+    }
+}
+
 /// System that handles player death by transitioning to GameOver state.
 pub fn handle_player_death_system(
     mut ship_destroyed_events: EventReader<crate::events::ShipDestroyedEvent>,
@@ -390,17 +695,28 @@ pub fn loot_timer_system(
     }
 }
 
-/// System that detects combat victory when all AI ships are destroyed or surrendered.
+/// System that detects combat victory when all enemy ships are destroyed or
+/// surrendered and no sea monster is left alive. A battle in which every
+/// enemy got away, with none sunk or taken, ends without a victory.
 pub fn combat_victory_system(
-    ai_ships: Query<(Entity, Option<&Surrendered>), (With<Ship>, With<AI>)>,
+    ai_ships: Query<(Entity, Option<&Surrendered>), (With<Ship>, With<AI>, Without<PlayerOwned>)>,
     player_ships: Query<Entity, (With<Ship>, With<Player>)>,
+    monsters: Query<(), With<SeaMonster>>,
+    battle_stats: Res<BattleStats>,
     mut combat_ended_events: EventWriter<crate::events::CombatEndedEvent>,
 ) {
-    // Only check for victory if the player is still alive
-    if player_ships.is_empty() {
+    // Only check for victory if the player is still alive, and never while a monster lives
+    if player_ships.is_empty() || !monsters.is_empty() {
         return;
     }
-    
+
+    // A surrendered ship is still on the water, so any left make it a victory
+    if ai_ships.is_empty() && battle_stats.enemy_got_away() {
+        info!("Every enemy got away");
+        combat_ended_events.send(crate::events::CombatEndedEvent { victory: false });
+        return;
+    }
+
     // Victory when all AI ships are destroyed OR surrendered
     if ai_ships.is_empty() {
         // Technically this is victory if no enemies existed, but usually we spawn some
@@ -419,31 +735,80 @@ pub fn combat_victory_system(
     }
 }
 
-/// System that handles combat victory by capturing surrendered ships and transitioning state.
+/// System that handles combat victory by opening the loot screen.
+///
+/// Surrendered ships join the spoils with their whole hold, as prizes whose
+/// fate the player decides on the loot screen. If there is nothing to loot
+/// and no prize taken, the battle summary opens straight away, as it does
+/// when the enemy got away.
 pub fn handle_combat_victory_system(
     mut combat_ended_events: EventReader<crate::events::CombatEndedEvent>,
-    mut next_state: ResMut<NextState<crate::plugins::core::GameState>>,
-    surrendered_ships: Query<(&Health, &Name, Option<&Cargo>), (With<Ship>, With<Surrendered>)>,
-    mut player_fleet: ResMut<PlayerFleet>,
+    mut battle_stats: ResMut<BattleStats>,
+    surrendered_ships: Query<
+        (&Health, &Name, Option<&Cargo>, Option<&Gold>, Option<&ShipType>, Option<&ShipClass>, Option<&Faction>, Option<&Crew>),
+        (With<Ship>, With<Surrendered>),
+    >,
+    mut spoils: ResMut<crate::resources::VictorySpoils>,
+    ships: Res<ShipDefinitions>,
 ) {
     for event in combat_ended_events.read() {
-        if event.victory {
-            // Process surrendered ships
-            for (health, name, cargo) in &surrendered_ships {
-                let ship_data = ShipData {
-                    sprite_path: "sprites/ships/round_ship_small.png".to_string(), // Todo: preserve actual sprite
-                    hull_health: health.hull,
-                    max_hull_health: 100.0, // Hardcoded for now, should read from component
-                    cargo: cargo.cloned(),
-                    name: name.as_str().to_string(),
-                };
-                
-                info!("Captured ship: {}", ship_data.name);
-                player_fleet.ships.push(ship_data);
-            }
+        // The end is reported every frame until the scene changes
+        if spoils.open || battle_stats.open {
+            continue;
+        }
+        if !event.victory {
+            info!("The enemy got away. Opening battle summary.");
+            battle_stats.outcome = BattleOutcome::Escaped;
+            battle_stats.open = true;
+            continue;
+        }
 
-            info!("Combat victory! Transitioning to HighSeas state.");
-            next_state.set(crate::plugins::core::GameState::HighSeas);
+        // Process surrendered ships
+        for (health, name, cargo, gold, ship_type, class, faction, crew) in &surrendered_ships {
+            let ship_type = ship_type.copied().unwrap_or_default();
+            let class = class.map_or_else(|| ship_type.class_id().to_string(), |class| class.0.clone());
+            let definition = ships.definition(&class, ship_type);
+            let ship_data = ShipData {
+                sprite_path: definition.sprite,
+                hull_health: health.hull,
+                max_hull_health: definition.hull_strength,
+                cargo: None, // Filled with what is left aboard when the loot screen closes
+                name: name.as_str().to_string(),
+                upgrades: Vec::new(), // Captured hulls come bare; refit them at a shipyard
+                ship_type,
+                class,
+            };
+            battle_stats.record_captured(name.as_str());
+            spoils.sources.push(crate::resources::SpoilsSource::surrendered(
+                name.as_str(),
+                gold.map_or(0, |g| g.0),
+                cargo.cloned().unwrap_or_else(|| Cargo::new(0)),
+                crate::resources::Prize::new(ship_data, faction.map(|f| f.0), crew.map_or(0, |c| c.hands)),
+            ));
         }
+
+        if spoils.is_empty() && !spoils.has_prizes() {
+            spoils.clear();
+            info!("Combat victory! Opening battle summary.");
+            battle_stats.open = true;
+        } else {
+            info!("Combat victory! Opening loot screen for {} ships.", spoils.sources.len());
+            spoils.open = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycling_skips_empty_shot() {
+        let mut cargo = Cargo::new(10);
+        cargo.stow_ammo(AmmoType::GrapeShot, BROADSIDE_ROUNDS);
+        cargo.stow_ammo(AmmoType::ChainShot, BROADSIDE_ROUNDS - 1);
+        assert_eq!(next_loadable_ammo(AmmoType::RoundShot, Some(&cargo)), AmmoType::GrapeShot);
+        assert_eq!(next_loadable_ammo(AmmoType::GrapeShot, Some(&cargo)), AmmoType::RoundShot);
+        assert_eq!(next_loadable_ammo(AmmoType::RoundShot, None), AmmoType::RoundShot);
     }
 }
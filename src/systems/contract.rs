@@ -1,9 +1,11 @@
 use bevy::prelude::*;
 
 use crate::components::contract::{AcceptedContract, AssignedShip, Contract, ContractDetails, ContractProgress, ContractType};
-use crate::components::{Order, OrderQueue, Port, NavigationPath, PlayerOwned};
+use crate::components::{Order, OrderQueue, Port, NavigationPath, PlayerOwned, Player, Ship};
+use crate::components::cargo::Gold;
 use crate::events::{ContractExpiredEvent, ContractCompletedEvent};
-use crate::resources::WorldClock;
+use crate::plugins::port_ui::PlayerContracts;
+use crate::resources::{FactionRegistry, Journal, JournalCategory, WorldClock};
 
 /// System that checks for and removes expired contracts.
 /// 
@@ -25,6 +27,7 @@ pub fn contract_expiry_system(
             expire_events.send(ContractExpiredEvent {
                 contract_entity: entity,
                 was_accepted,
+                details: details.clone(),
             });
             
             if was_accepted {
@@ -38,6 +41,63 @@ pub fn contract_expiry_system(
     }
 }
 
+/// System that fails accepted contracts that ran out of time.
+///
+/// Runs on FixedUpdate after `contract_expiry_system`. The issuing faction loses
+/// `FAILURE_REPUTATION_PENALTY` reputation with the player, the player forfeits
+/// part of the reward from their purse, and the failure goes in the journal.
+/// Expired offers that were never accepted carry no penalty, and letters of
+/// marque lapse without one.
+pub fn contract_failure_system(
+    mut expire_events: EventReader<ContractExpiredEvent>,
+    world_clock: Res<WorldClock>,
+    mut faction_registry: ResMut<FactionRegistry>,
+    mut player_contracts: ResMut<PlayerContracts>,
+    mut journal: ResMut<Journal>,
+    mut player_query: Query<&mut Gold, (With<Player>, With<Ship>)>,
+) {
+    for event in expire_events.read() {
+        if !event.was_accepted {
+            continue;
+        }
+        player_contracts.active.retain(|&e| e != event.contract_entity);
+
+        let details = &event.details;
+        if !details.contract_type.fails_on_expiry() {
+            journal.record_as(&world_clock, JournalCategory::Contract, format!("Contract lapsed: {}", details.description));
+            continue;
+        }
+        let mut consequences = Vec::new();
+
+        if details
+            .issuer
+            .and_then(|f| faction_registry.adjust_reputation(f, -ContractDetails::FAILURE_REPUTATION_PENALTY))
+            .is_some()
+        {
+            consequences.push(format!(
+                "{} reputation with {}",
+                -ContractDetails::FAILURE_REPUTATION_PENALTY,
+                details.issuer.map_or("the issuer", |f| f.display_name())
+            ));
+        }
+
+        if let Ok(mut gold) = player_query.get_single_mut() {
+            let forfeit = details.failure_gold_penalty().min(gold.0);
+            if forfeit > 0 {
+                gold.0 -= forfeit;
+                consequences.push(format!("forfeited {} gold", forfeit));
+            }
+        }
+
+        let text = if consequences.is_empty() {
+            format!("Failed contract: {}", details.description)
+        } else {
+            format!("Failed contract: {} ({})", details.description, consequences.join(", "))
+        };
+        journal.record_as(&world_clock, JournalCategory::Contract, text);
+    }
+}
+
 /// System that processes delegated contracts and sets ship orders.
 ///
 /// For Transport contracts:
@@ -172,6 +232,75 @@ mod tests {
         assert!(!details.is_expired(1_000_000));
     }
 
+    #[test]
+    fn test_failure_penalty_and_time_remaining() {
+        let origin = create_test_entity();
+        let details = ContractDetails::transport_with_expiry(origin, origin, GoodType::Rum, 10, 200, 0)
+            .with_issuer(crate::components::FactionId::NationA);
+
+        assert_eq!(details.failure_gold_penalty(), 50);
+        assert_eq!(details.ticks_remaining(0), Some(ContractDetails::DEFAULT_DURATION_TICKS));
+        assert_eq!(details.ticks_remaining(u32::MAX), Some(0));
+        assert_eq!(details.issuer, Some(crate::components::FactionId::NationA));
+    }
+
+    #[test]
+    fn test_format_time_remaining() {
+        use crate::components::contract::format_time_remaining;
+        use crate::resources::TICKS_PER_HOUR;
+
+        assert_eq!(format_time_remaining(0), "<1h");
+        assert_eq!(format_time_remaining(5 * TICKS_PER_HOUR + 30), "5h");
+        assert_eq!(format_time_remaining(24 * TICKS_PER_HOUR), "1d");
+        assert_eq!(format_time_remaining(30 * TICKS_PER_HOUR), "1d 6h");
+    }
+
+    #[test]
+    fn test_patrol_contract_completion() {
+        use crate::components::contract::PatrolArea;
+        use crate::resources::PATROL_DURATION_TICKS;
+
+        let origin = create_test_entity();
+        let details = ContractDetails::patrol(origin, "Port Royal", 2, 500, 100);
+        assert_eq!(details.contract_type, ContractType::Patrol);
+        assert_eq!(details.expiry_tick, Some(100 + PATROL_DURATION_TICKS));
+
+        let area = PatrolArea { center: Vec2::ZERO, radius: 100.0, required: 2 };
+        assert!(area.contains(Vec2::new(60.0, 80.0)));
+        assert!(!area.contains(Vec2::new(100.0, 1.0)));
+
+        let mut progress = ContractProgress {
+            intercepted: 1,
+            false_positives: 3,
+            ..Default::default()
+        };
+        assert!(!area.is_complete(&progress));
+        progress.intercepted = 2;
+        assert!(area.is_complete(&progress));
+    }
+
+    #[test]
+    fn test_letter_of_marque_counts_prizes_and_lapses() {
+        use crate::components::contract::Commission;
+        use crate::components::FactionId;
+        use crate::resources::COMMISSION_DURATION_TICKS;
+
+        let origin = create_test_entity();
+        let details = ContractDetails::privateer(origin, FactionId::NationB, 3, 200, 100)
+            .with_issuer(FactionId::NationA);
+        assert_eq!(details.contract_type, ContractType::Privateer);
+        assert_eq!(details.reward_gold, 600);
+        assert_eq!(details.expiry_tick, Some(100 + COMMISSION_DURATION_TICKS));
+        assert!(!details.contract_type.fails_on_expiry());
+        assert!(ContractType::Transport.fails_on_expiry());
+
+        let commission = Commission { rival: FactionId::NationB, required: 3, prize_money: 200 };
+        let mut progress = ContractProgress { prizes: 2, ..Default::default() };
+        assert!(!commission.is_complete(&progress));
+        progress.prizes = 3;
+        assert!(commission.is_complete(&progress));
+    }
+
     #[test]
     fn test_transport_with_expiry() {
         let origin = create_test_entity();
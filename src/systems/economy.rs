@@ -1,14 +1,24 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
+use std::time::Duration;
 
 use crate::components::{
-    cargo::{GoodType, GoodsTrait},
-    port::{Inventory, InventoryItem, Port},
+    cargo::{Cargo, GoodType, GoodsTrait},
+    port::{port_key, Inventory, InventoryItem, Port},
+    ship::{Player, Ship},
+    upgrade::keeps_cargo_fresh,
 };
+use crate::events::TradeSettledEvent;
+use crate::resources::{
+    GoodStats, GoodsTable, Journal, PlayerFleet, PortMarkets, Season, UpgradeInventory, WorldClock,
+    DEMAND_RECOVERY_PER_DAY, TRADE_DEMAND_PER_UNIT,
+};
+use crate::utils::frame_budget::{FrameBudget, SlicedJob};
 
 /// Constants for price calculation.
 pub mod price_config {
-    /// Base prices for each good type (used for equilibrium reference).
+    /// Base prices for each good type (used for equilibrium reference), built
+    /// in; the prices in play come from `GoodsTable`.
     pub fn base_price(good: &super::GoodType) -> f32 {
         match good {
             super::GoodType::Rum => 15.0,
@@ -17,6 +27,8 @@ pub mod price_config {
             super::GoodType::Timber => 5.0,
             super::GoodType::Cloth => 12.0,
             super::GoodType::Weapons => 40.0,
+            super::GoodType::Fish => 10.0,
+            super::GoodType::Fruit => 9.0,
         }
     }
 
@@ -29,6 +41,8 @@ pub mod price_config {
             super::GoodType::Timber => 200,
             super::GoodType::Cloth => 80,
             super::GoodType::Weapons => 30,
+            super::GoodType::Fish => 90,
+            super::GoodType::Fruit => 110,
         }
     }
 
@@ -45,6 +59,45 @@ pub mod price_config {
     pub const PERISHABLE_DECAY_RATE: f32 = 0.0001;
 }
 
+/// Work one economy system may do per tick. Ports past the budget are
+/// picked up on the following ticks, so a large world never stalls a frame.
+pub const ECONOMY_SLICE_BUDGET: FrameBudget = FrameBudget::new(Duration::from_micros(500), 16);
+
+/// Resumable pass over port inventories, spread across ticks by `ECONOMY_SLICE_BUDGET`.
+#[derive(Default)]
+pub struct PortSweep {
+    job: SlicedJob,
+    /// Ports in the current pass, snapshotted when it began.
+    ports: Vec<Entity>,
+    /// World tick at which each port was last processed.
+    last_visit: HashMap<Entity, u32>,
+}
+
+impl PortSweep {
+    /// Snapshots the ports for a new pass; does nothing while a pass is under way.
+    pub fn begin_pass(&mut self, ports: impl Iterator<Item = Entity>) {
+        if self.job.in_progress() {
+            return;
+        }
+        self.ports.clear();
+        self.ports.extend(ports);
+        let current = &self.ports;
+        self.last_visit.retain(|entity, _| current.contains(entity));
+    }
+
+    /// Runs one budgeted slice of the pass. `step` gets each port and the
+    /// world ticks elapsed since that port was last processed.
+    /// Returns true if the slice finished the pass.
+    pub fn slice(&mut self, now: u32, budget: FrameBudget, mut step: impl FnMut(Entity, u32)) -> bool {
+        let Self { job, ports, last_visit } = self;
+        job.run(ports.len(), budget, |index| {
+            let entity = ports[index];
+            let previous = last_visit.insert(entity, now).unwrap_or(now.saturating_sub(1));
+            step(entity, now.saturating_sub(previous));
+        })
+    }
+}
+
 /// Resource tracking global demand levels for each good type.
 /// 
 /// Demand is a multiplier (1.0 = normal, >1.0 = high demand, <1.0 = low demand).
@@ -65,6 +118,8 @@ impl Default for GlobalDemand {
         demand.insert(GoodType::Timber, 1.0);
         demand.insert(GoodType::Cloth, 1.0);
         demand.insert(GoodType::Weapons, 1.0);
+        demand.insert(GoodType::Fish, 1.0);
+        demand.insert(GoodType::Fruit, 1.0);
         Self { demand }
     }
 }
@@ -91,40 +146,58 @@ impl GlobalDemand {
         let current = self.get(&good);
         self.set(good, current - amount);
     }
+
+    /// Moves every good's demand `share` of the way back to normal.
+    pub fn recover(&mut self, share: f32) {
+        for multiplier in self.demand.values_mut() {
+            *multiplier += (1.0 - *multiplier) * share;
+        }
+    }
 }
 
 /// System that recalculates prices for all ports based on supply and demand.
 /// 
-/// Runs every world tick (via FixedUpdate).
+/// Runs every world tick (via FixedUpdate), repricing as many ports as
+/// `ECONOMY_SLICE_BUDGET` allows and resuming with the rest next tick.
 /// 
 /// **Price Formula:**
 /// ```text
-/// price = base_price * supply_multiplier * demand_multiplier
+/// price = base_price * supply_multiplier * demand_multiplier * season_factor
 /// 
 /// supply_ratio = current_quantity / base_quantity
 /// supply_multiplier = clamp(1.0 / supply_ratio^sensitivity, min, max)
 /// demand_multiplier = global_demand ^ demand_sensitivity
+/// season_factor = Season::price_factor (e.g. the spring sugar harvest)
 /// ```
 /// 
 /// Low stock → higher prices, high stock → lower prices.
 /// High demand → higher prices everywhere, low demand → lower prices.
 pub fn price_calculation_system(
+    mut sweep: Local<PortSweep>,
+    ports: Query<Entity, With<Port>>,
     mut port_query: Query<&mut Inventory, With<Port>>,
     global_demand: Res<GlobalDemand>,
+    world_clock: Res<WorldClock>,
+    goods: Res<GoodsTable>,
 ) {
-    for mut inventory in port_query.iter_mut() {
+    sweep.begin_pass(ports.iter());
+    sweep.slice(world_clock.total_ticks(), ECONOMY_SLICE_BUDGET, |entity, _| {
+        let Ok(mut inventory) = port_query.get_mut(entity) else {
+            return;
+        };
+        let season = world_clock.season();
         for (good_type, item) in inventory.goods.iter_mut() {
             let demand_mult = global_demand.get(good_type);
-            let new_price = calculate_price(good_type, item, demand_mult);
+            let new_price = calculate_price(goods.stats(*good_type), item, demand_mult) * season.price_factor(*good_type);
             item.price = new_price;
         }
-    }
+    });
 }
 
-/// Calculates price based on supply and demand.
-fn calculate_price(good_type: &GoodType, item: &InventoryItem, demand_multiplier: f32) -> f32 {
-    let base_price = price_config::base_price(good_type);
-    let base_quantity = price_config::base_quantity(good_type) as f32;
+/// Calculates price based on supply and demand, around a good's `stats`.
+fn calculate_price(stats: GoodStats, item: &InventoryItem, demand_multiplier: f32) -> f32 {
+    let base_price = stats.base_price;
+    let base_quantity = stats.base_quantity as f32;
     
     // Handle edge case of zero quantity
     if item.quantity == 0 {
@@ -149,28 +222,110 @@ fn calculate_price(good_type: &GoodType, item: &InventoryItem, demand_multiplier
     base_price * clamped_multiplier
 }
 
+/// Price a port would ask for a good once its stock stands at `quantity`.
+///
+/// Used to preview how a large trade will move the market in `season`.
+pub fn price_at_stock(goods: &GoodsTable, good_type: &GoodType, quantity: u32, demand_multiplier: f32, season: Season) -> f32 {
+    calculate_price(goods.stats(*good_type), &InventoryItem::new(quantity, 0.0), demand_multiplier) * season.price_factor(*good_type)
+}
+
 /// Helper for tests - calculate supply-only price (backwards compatibility).
 #[cfg(test)]
 fn calculate_supply_price(good_type: &GoodType, item: &InventoryItem) -> f32 {
-    calculate_price(good_type, item, 1.0)
+    calculate_price(GoodStats::builtin(*good_type), item, 1.0)
+}
+
+/// Brings back each port's market when the High Seas are entered, and keeps
+/// `PortMarkets` up to date as trades and repricing change a live port's stock.
+pub fn port_market_sync_system(
+    mut markets: ResMut<PortMarkets>,
+    mut port_query: Query<(Ref<Port>, &Transform, &mut Inventory)>,
+) {
+    let mut rng = rand::thread_rng();
+    for (port, transform, mut inventory) in &mut port_query {
+        let key = port_key(transform.translation.truncate());
+        if port.is_added() {
+            if let Some(market) = markets.get(key) {
+                *inventory = market.inventory.clone();
+                continue;
+            }
+        }
+        if port.is_added() || inventory.is_changed() {
+            markets.observe(key, &inventory, &mut rng);
+        }
+    }
+}
+
+/// Runs the regional economy once per in-game day: production, consumption
+/// and stock flowing between neighbouring ports (see `PortMarkets`), seen or not.
+/// Demand pushed up or down by the player's trading also settles back.
+pub fn market_day_system(
+    world_clock: Res<WorldClock>,
+    mut markets: ResMut<PortMarkets>,
+    mut global_demand: ResMut<GlobalDemand>,
+    mut port_query: Query<(&Transform, &mut Inventory), With<Port>>,
+    goods: Res<GoodsTable>,
+) {
+    if !world_clock.is_day_start() {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    for (transform, inventory) in &port_query {
+        markets.observe(port_key(transform.translation.truncate()), inventory, &mut rng);
+    }
+    markets.run_day(|good| goods.base_quantity(*good));
+    global_demand.recover(DEMAND_RECOVERY_PER_DAY);
+
+    // Live ports take the new stock; `price_calculation_system` reprices it
+    for (transform, mut inventory) in &mut port_query {
+        let Some(market) = markets.get(port_key(transform.translation.truncate())) else {
+            continue;
+        };
+        for (good_type, item) in inventory.goods.iter_mut() {
+            if let Some(stored) = market.inventory.get_good(good_type) {
+                item.quantity = stored.quantity;
+            }
+        }
+    }
+}
+
+/// Player trades move global demand: buying up a good raises it everywhere,
+/// dumping one lowers it. Only trades that went through count.
+pub fn trade_demand_system(
+    mut trade_events: EventReader<TradeSettledEvent>,
+    mut global_demand: ResMut<GlobalDemand>,
+) {
+    for event in trade_events.read() {
+        let shift = event.quantity as f32 * TRADE_DEMAND_PER_UNIT;
+        if event.is_buy {
+            global_demand.increase(event.good_type, shift);
+        } else {
+            global_demand.decrease(event.good_type, shift);
+        }
+    }
 }
 
 /// System that decays perishable goods in port inventories over time.
 /// 
-/// Runs every world tick (via FixedUpdate).
-/// Perishable goods (Rum, Sugar) gradually lose quantity, simulating spoilage.
+/// Runs every world tick (via FixedUpdate), time-sliced like `price_calculation_system`.
+/// Perishable goods (Rum, Sugar, Fish, Fruit) gradually lose quantity, simulating spoilage.
+/// A port skipped for a few ticks decays for all of them when its turn comes.
 pub fn goods_decay_system(
+    mut sweep: Local<PortSweep>,
+    ports: Query<Entity, With<Port>>,
     mut port_query: Query<&mut Inventory, With<Port>>,
+    world_clock: Res<WorldClock>,
 ) {
-    for mut inventory in port_query.iter_mut() {
-        let decay_rate = price_config::PERISHABLE_DECAY_RATE;
-        
+    sweep.begin_pass(ports.iter());
+    sweep.slice(world_clock.total_ticks(), ECONOMY_SLICE_BUDGET, |entity, elapsed| {
+        let Ok(mut inventory) = port_query.get_mut(entity) else {
+            return;
+        };
         for (good_type, item) in inventory.goods.iter_mut() {
             if good_type.traits().contains(&GoodsTrait::Perishable) {
-                // Decay perishable goods
-                let decay_amount = (item.quantity as f32 * decay_rate).max(0.0);
-                let lost = decay_amount.floor() as u32;
-                
+                let lost = perishable_loss(item.quantity, elapsed);
+
                 // Use fractional accumulation for small decay amounts
                 // For now, just apply whole unit decay when enough accumulates
                 if lost > 0 {
@@ -178,9 +333,46 @@ pub fn goods_decay_system(
                 }
             }
         }
+    });
+}
+
+/// Fish and fruit rot in the holds of the player's ships, a share each day
+/// (see `GoodType::spoilage_per_day`), unless the hull has an icehouse fitted.
+pub fn cargo_spoilage_system(
+    world_clock: Res<WorldClock>,
+    upgrades: Option<Res<UpgradeInventory>>,
+    player_fleet: Option<ResMut<PlayerFleet>>,
+    mut journal: ResMut<Journal>,
+    mut player_query: Query<&mut Cargo, (With<Player>, With<Ship>)>,
+) {
+    if !world_clock.is_day_start() {
+        return;
+    }
+
+    let mut lost = Vec::new();
+    if !upgrades.is_some_and(|u| keeps_cargo_fresh(&u.flagship)) {
+        for mut cargo in &mut player_query {
+            lost.extend(cargo.spoil(1).into_iter().map(|(good, rotted)| ("in the hold".to_string(), good, rotted)));
+        }
+    }
+    if let Some(mut player_fleet) = player_fleet {
+        for ship in player_fleet.ships.iter_mut().filter(|ship| !keeps_cargo_fresh(&ship.upgrades)) {
+            if let Some(cargo) = ship.cargo.as_mut() {
+                lost.extend(cargo.spoil(1).into_iter().map(|(good, rotted)| (format!("aboard the {}", ship.name), good, rotted)));
+            }
+        }
+    }
+    for (hold, good, rotted) in lost {
+        journal.record(&world_clock, format!("{} {:?} rotted {} and went over the side.", rotted, good, hold));
     }
 }
 
+/// Whole units of a perishable stock of `quantity` lost over `ticks` world ticks.
+fn perishable_loss(quantity: u32, ticks: u32) -> u32 {
+    let decay_amount = (quantity as f32 * price_config::PERISHABLE_DECAY_RATE * ticks as f32).max(0.0);
+    decay_amount.floor() as u32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,7 +431,7 @@ mod tests {
         let base_qty = price_config::base_quantity(&GoodType::Rum);
         let item = InventoryItem::new(base_qty, 15.0);
         let high_demand = 1.5;
-        let price = calculate_price(&GoodType::Rum, &item, high_demand);
+        let price = calculate_price(GoodStats::builtin(GoodType::Rum), &item, high_demand);
         let base = price_config::base_price(&GoodType::Rum);
         assert!(price > base, "High demand should increase price");
     }
@@ -249,11 +441,54 @@ mod tests {
         let base_qty = price_config::base_quantity(&GoodType::Rum);
         let item = InventoryItem::new(base_qty, 15.0);
         let low_demand = 0.6;
-        let price = calculate_price(&GoodType::Rum, &item, low_demand);
+        let price = calculate_price(GoodStats::builtin(GoodType::Rum), &item, low_demand);
         let base = price_config::base_price(&GoodType::Rum);
         assert!(price < base, "Low demand should decrease price");
     }
 
+    #[test]
+    fn test_port_sweep_visits_each_port_once_per_pass() {
+        let ports: Vec<Entity> = (0..5).map(Entity::from_raw).collect();
+        let mut sweep = PortSweep::default();
+        let mut visits = Vec::new();
+
+        let mut tick = 10;
+        loop {
+            sweep.begin_pass(ports.iter().copied());
+            let finished = sweep.slice(tick, FrameBudget::items(2), |entity, elapsed| visits.push((entity, elapsed)));
+            tick += 1;
+            if finished {
+                break;
+            }
+        }
+        assert_eq!(visits.iter().map(|(entity, _)| *entity).collect::<Vec<_>>(), ports);
+        assert!(visits.iter().all(|(_, elapsed)| *elapsed == 1));
+
+        // The next pass reports the ticks each port waited
+        sweep.begin_pass(ports.iter().copied());
+        sweep.slice(tick, FrameBudget::items(5), |entity, elapsed| {
+            let expected = if entity.index() < 2 { 3 } else if entity.index() < 4 { 2 } else { 1 };
+            assert_eq!(elapsed, expected);
+        });
+    }
+
+    #[test]
+    fn test_perishable_loss_scales_with_ticks() {
+        assert_eq!(perishable_loss(100, 1), 0);
+        assert_eq!(perishable_loss(125_000, 1), 12);
+        assert_eq!(perishable_loss(125_000, 3), 37);
+    }
+
+    #[test]
+    fn test_demand_recovers_towards_normal() {
+        let mut gd = GlobalDemand::default();
+        gd.set(GoodType::Rum, 2.0);
+        gd.set(GoodType::Sugar, 0.5);
+        gd.recover(0.5);
+        assert!((gd.get(&GoodType::Rum) - 1.5).abs() < 0.001);
+        assert!((gd.get(&GoodType::Sugar) - 0.75).abs() < 0.001);
+    }
+
     #[test]
     fn test_global_demand_methods() {
         let mut gd = GlobalDemand::default();
@@ -2,11 +2,15 @@
 //!
 //! Runs per world tick to simulate faction economic and military decisions.
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use std::collections::HashMap;
 
-use crate::resources::{FactionRegistry, WorldClock};
-use crate::components::{FactionId, Faction, Port, Ship, AI, Health, Player, Order, OrderQueue, NavigationPath};
+use crate::resources::{FactionRegistry, HunterAtSea, MapData, NewsCategory, PendingBounties, ShipDefinitions, TradeLaneTraffic, WorldClock, CONVOY_DANGER_THRESHOLD, MERCHANT_SMUGGLER_CHANCE};
+use crate::components::patrol::Smuggler;
+use crate::components::{BountyHunter, FactionId, Faction, HighSeasEntity, LaneRaider, ShipType, Port, PortName, Ship, AI, Health, Player, Order, OrderQueue, NavigationPath};
+use crate::events::WorldNewsEvent;
+use crate::systems::enemy_hold;
 use crate::plugins::worldmap::{HighSeasAI, HighSeasPlayer};
 
 /// Runs faction simulation logic once per in-game hour.
@@ -36,6 +40,27 @@ pub fn faction_ai_system(
     }
 }
 
+/// Counts each faction's ships afloat into its `ships`, once per in-game hour
+/// ahead of the faction systems. Ships sunk, captured or lost with the High
+/// Seas scene no longer count against a faction's fleet.
+pub fn faction_fleet_census_system(
+    world_clock: Res<WorldClock>,
+    mut faction_registry: ResMut<FactionRegistry>,
+    ship_query: Query<&Faction, (With<Ship>, With<HighSeasAI>)>,
+) {
+    if world_clock.tick != 0 {
+        return;
+    }
+
+    let mut afloat: HashMap<FactionId, u32> = HashMap::new();
+    for faction in &ship_query {
+        *afloat.entry(faction.0).or_default() += 1;
+    }
+    for (faction_id, state) in faction_registry.factions.iter_mut() {
+        state.ships = afloat.get(faction_id).copied().unwrap_or(0);
+    }
+}
+
 /// Processes a single faction's hourly tick.
 fn run_faction_tick(
     faction_id: &FactionId,
@@ -80,6 +105,24 @@ const INTERCEPTOR_COST: u32 = 500;
 /// Maximum interceptors a faction can deploy per threat response.
 const MAX_INTERCEPTORS_PER_RESPONSE: u32 = 3;
 
+/// Cost in gold to send an escort with a merchant on a dangerous lane.
+const ESCORT_COST: u32 = 500;
+
+/// Cost in gold for the Brethren to fit out a new lane raider.
+const RAIDER_COST: u32 = 300;
+
+/// Radius of a lane raider's patrol around its ambush point.
+const RAIDER_PATROL_RADIUS: f32 = 300.0;
+
+/// Chance each hour that a nation with a bounty on the player sends a hunter.
+const BOUNTY_HUNTER_CHANCE: f64 = 0.2;
+
+/// Cost in gold for a nation to send out a bounty hunter.
+const BOUNTY_HUNTER_COST: u32 = 400;
+
+/// How far from the player a bounty hunter picks up the trail (world units).
+const BOUNTY_HUNTER_DISTANCE: f32 = 900.0;
+
 /// Generates trade routes between ports belonging to the same faction.
 /// 
 /// This system runs once per in-game day (at midnight) and:
@@ -89,7 +132,8 @@ const MAX_INTERCEPTORS_PER_RESPONSE: u32 = 3;
 pub fn trade_route_generation_system(
     world_clock: Res<WorldClock>,
     mut faction_registry: ResMut<FactionRegistry>,
-    port_query: Query<(Entity, &Faction), With<Port>>,
+    port_query: Query<(Entity, &Faction, &PortName), With<Port>>,
+    mut news: EventWriter<WorldNewsEvent>,
 ) {
     // Run once per day at midnight
     if world_clock.tick != 0 || world_clock.hour != 0 {
@@ -98,7 +142,7 @@ pub fn trade_route_generation_system(
 
     // Group ports by faction
     let mut ports_by_faction: HashMap<FactionId, Vec<Entity>> = HashMap::new();
-    for (entity, faction) in &port_query {
+    for (entity, faction, _) in &port_query {
         ports_by_faction
             .entry(faction.0)
             .or_default()
@@ -153,6 +197,20 @@ pub fn trade_route_generation_system(
                         state.trade_routes.len()
                     );
 
+                    if let (Ok((_, _, name_a)), Ok((_, _, name_b))) =
+                        (port_query.get(port_a), port_query.get(port_b))
+                    {
+                        news.send(WorldNewsEvent {
+                            category: NewsCategory::Trade,
+                            headline: format!(
+                                "{} opens a trade route between {} and {}",
+                                faction_id.display_name(),
+                                name_a.0,
+                                name_b.0
+                            ),
+                        });
+                    }
+
                     // Only create one route per day per faction
                     break;
                 }
@@ -171,12 +229,16 @@ pub fn trade_route_generation_system(
 /// - Checks each faction's ship count vs route count
 /// - Spawns ships at faction ports if gold permits
 /// - Each route needs at least one ship to be fulfilled
+/// - Merchants on lanes with pirates about sail with an escort if gold permits
 pub fn faction_ship_spawning_system(
     world_clock: Res<WorldClock>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    ships: Res<ShipDefinitions>,
     mut faction_registry: ResMut<FactionRegistry>,
     port_query: Query<(Entity, &Transform, &Faction), With<Port>>,
+    lane_traffic: Res<TradeLaneTraffic>,
+    mut news: EventWriter<WorldNewsEvent>,
 ) {
     // Run once per day at hour 6 (after midnight route generation)
     if world_clock.tick != 0 || world_clock.hour != 6 {
@@ -192,7 +254,8 @@ pub fn faction_ship_spawning_system(
             .push((entity, transform.translation.truncate()));
     }
 
-    let texture_handle: Handle<Image> = asset_server.load("sprites/ships/enemy.png");
+    // Merchants and their escorts sail as the default hull
+    let texture_handle: Handle<Image> = asset_server.load(ships.enemy_sprite(ShipType::default()));
 
     // For each faction, spawn ships if needed
     for (faction_id, state) in faction_registry.factions.iter_mut() {
@@ -214,6 +277,8 @@ pub fn faction_ship_spawning_system(
         
         // Track which route index to assign to new ships
         let mut route_index = state.ships as usize;
+        let ships_before = state.ships;
+        let mut escorts = 0;
         
         // Spawn ships until we have enough or run out of gold/capacity
         while state.ships < needed_ships 
@@ -230,6 +295,9 @@ pub fn faction_ship_spawning_system(
                 .find(|(e, _)| *e == origin)
                 .map(|(_, pos)| *pos)
                 .unwrap_or(faction_ports[0].1);
+            let destination_pos = faction_ports.iter()
+                .find(|(e, _)| *e == destination)
+                .map(|(_, pos)| *pos);
             
             let offset = Vec2::new(
                 rand::thread_rng().gen_range(-100.0..100.0),
@@ -244,8 +312,10 @@ pub fn faction_ship_spawning_system(
                 outbound: true,
             };
 
-            // Spawn the ship with OrderQueue and NavigationPath
-            commands.spawn((
+            // Spawn the ship with OrderQueue and NavigationPath, and a hold
+            // a chase can plunder
+            let (hold, purse) = enemy_hold(*faction_id, &mut rand::thread_rng());
+            let merchant = commands.spawn((
                 Name::new(format!("{:?} Merchant Ship", faction_id)),
                 Ship,
                 AI,
@@ -254,6 +324,8 @@ pub fn faction_ship_spawning_system(
                 Health::default(),
                 OrderQueue::with_order(order),
                 NavigationPath::default(),
+                hold,
+                purse,
                 Sprite {
                     image: texture_handle.clone(),
                     custom_size: Some(Vec2::splat(48.0)),
@@ -261,17 +333,71 @@ pub fn faction_ship_spawning_system(
                     ..default()
                 },
                 Transform::from_xyz(final_pos.x, final_pos.y, 1.0),
-            ));
+            )).id();
+            if rand::thread_rng().gen_bool(MERCHANT_SMUGGLER_CHANCE) {
+                commands.entity(merchant).insert(Smuggler);
+            }
 
             state.ships += 1;
             state.gold = state.gold.saturating_sub(SHIP_COMMISSION_COST);
             route_index += 1;
 
+            // Pirates prowl this lane: sail as a convoy
+            let dangerous = destination_pos
+                .is_some_and(|to| lane_traffic.danger_between(spawn_base, to) >= CONVOY_DANGER_THRESHOLD);
+            if dangerous && state.gold >= ESCORT_COST && state.ships < MAX_SHIPS_PER_FACTION {
+                commands.spawn((
+                    Name::new(format!("{:?} Convoy Escort", faction_id)),
+                    Ship,
+                    AI,
+                    Faction(*faction_id),
+                    HighSeasAI,
+                    Health::default(),
+                    OrderQueue::with_order(Order::Escort {
+                        target: merchant,
+                        follow_distance: 60.0,
+                    }),
+                    NavigationPath::default(),
+                    Sprite {
+                        image: texture_handle.clone(),
+                        custom_size: Some(Vec2::splat(48.0)),
+                        flip_y: true,
+                        ..default()
+                    },
+                    Transform::from_xyz(final_pos.x - 40.0, final_pos.y, 1.0),
+                ));
+                state.ships += 1;
+                state.gold = state.gold.saturating_sub(ESCORT_COST);
+                escorts += 1;
+            }
+
             info!(
                 "Faction {:?} commissioned new ship (total: {}, gold remaining: {})",
                 faction_id, state.ships, state.gold
             );
         }
+
+        let commissioned = state.ships - ships_before - escorts;
+        if escorts > 0 {
+            news.send(WorldNewsEvent {
+                category: NewsCategory::Military,
+                headline: format!(
+                    "{} sends its merchantmen out in escorted convoys",
+                    faction_id.display_name()
+                ),
+            });
+        }
+        if commissioned > 0 {
+            news.send(WorldNewsEvent {
+                category: NewsCategory::Trade,
+                headline: format!(
+                    "{} commissions {} new merchantm{}",
+                    faction_id.display_name(),
+                    commissioned,
+                    if commissioned == 1 { "an" } else { "en" }
+                ),
+            });
+        }
     }
 }
 
@@ -292,10 +418,12 @@ const THREAT_COOLDOWN_TICKS: u32 = 600;
 pub fn faction_threat_response_system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    ships: Res<ShipDefinitions>,
     mut faction_registry: ResMut<FactionRegistry>,
     mut cooldowns: ResMut<ThreatResponseCooldown>,
     player_query: Query<&Transform, (With<Player>, With<HighSeasPlayer>)>,
-    port_query: Query<(&Transform, &Faction), With<Port>>,
+    port_query: Query<(&Transform, &Faction, &PortName), With<Port>>,
+    mut news: EventWriter<WorldNewsEvent>,
 ) {
     // Early-exit if no player in High Seas
     let Ok(player_transform) = player_query.get_single() else {
@@ -309,15 +437,16 @@ pub fn faction_threat_response_system(
     }
 
     // Group ports by faction with their positions
-    let mut port_positions_by_faction: HashMap<FactionId, Vec<Vec2>> = HashMap::new();
-    for (transform, faction) in &port_query {
+    let mut port_positions_by_faction: HashMap<FactionId, Vec<(Vec2, &str)>> = HashMap::new();
+    for (transform, faction, name) in &port_query {
         port_positions_by_faction
             .entry(faction.0)
             .or_default()
-            .push(transform.translation.truncate());
+            .push((transform.translation.truncate(), name.0.as_str()));
     }
 
-    let texture_handle: Handle<Image> = asset_server.load("sprites/ships/enemy.png");
+    // Interceptors sail as the default hull
+    let texture_handle: Handle<Image> = asset_server.load(ships.enemy_sprite(ShipType::default()));
 
     // Check each faction for threat response
     for (faction_id, port_positions) in &port_positions_by_faction {
@@ -332,7 +461,7 @@ pub fn faction_threat_response_system(
         }
 
         // Check if player is near any of this faction's ports
-        let player_near_port = port_positions.iter().any(|port_pos| {
+        let player_near_port = port_positions.iter().any(|(port_pos, _)| {
             player_pos.distance(*port_pos) < THREAT_DETECTION_RADIUS
         });
 
@@ -348,13 +477,13 @@ pub fn faction_threat_response_system(
         // Find the closest port to spawn interceptors
         let closest_port = port_positions
             .iter()
-            .min_by(|a, b| {
-                player_pos.distance(**a)
-                    .partial_cmp(&player_pos.distance(**b))
+            .min_by(|(a, _), (b, _)| {
+                player_pos.distance(*a)
+                    .partial_cmp(&player_pos.distance(*b))
                     .unwrap_or(std::cmp::Ordering::Equal)
             });
 
-        let Some(spawn_base) = closest_port else {
+        let Some((spawn_base, port_name)) = closest_port else {
             continue;
         };
 
@@ -398,8 +527,264 @@ pub fn faction_threat_response_system(
                 faction_id, spawned
             );
 
+            news.send(WorldNewsEvent {
+                category: NewsCategory::Military,
+                headline: format!(
+                    "{} sends warships out of {} to hunt a notorious captain",
+                    faction_id.display_name(),
+                    port_name
+                ),
+            });
+
             // Set cooldown
             cooldowns.cooldowns.insert(*faction_id, THREAT_COOLDOWN_TICKS);
         }
     }
 }
+
+/// Spawns `hunter` on the High Seas, making for `player`.
+pub fn spawn_bounty_hunter(commands: &mut Commands, texture: Handle<Image>, hunter: &HunterAtSea, player: Entity) -> Entity {
+    commands
+        .spawn((
+            Name::new(format!("{} Bounty Hunter", hunter.faction.display_name())),
+            Ship,
+            hunter.ship_type,
+            AI,
+            Faction(hunter.faction),
+            HighSeasAI,
+            BountyHunter,
+            Health::default(),
+            OrderQueue::with_order(Order::Escort {
+                target: player,
+                follow_distance: 0.0,
+            }),
+            NavigationPath::default(),
+            Sprite {
+                image: texture,
+                custom_size: Some(Vec2::splat(48.0)),
+                flip_y: true,
+                ..default()
+            },
+            Transform::from_xyz(hunter.position.x, hunter.position.y, 1.0),
+            HighSeasEntity,
+        ))
+        .id()
+}
+
+/// The player, and the bounty hunters already after them.
+#[derive(SystemParam)]
+pub struct BountyHunt<'w, 's> {
+    player: Query<'w, 's, (Entity, &'static Transform), (With<Player>, With<HighSeasPlayer>)>,
+    bounties: ResMut<'w, PendingBounties>,
+}
+
+/// Sends bounty hunters after a player whose reputation with a nation has
+/// fallen below `BOUNTY_REPUTATION`, once per hour at most. A nation keeps one
+/// hunter at sea while the player is merely wanted, and two once it is hostile.
+pub fn bounty_hunter_system(
+    world_clock: Res<WorldClock>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ships: Res<ShipDefinitions>,
+    map_data: Res<MapData>,
+    mut faction_registry: ResMut<FactionRegistry>,
+    mut hunt: BountyHunt,
+    mut news: EventWriter<WorldNewsEvent>,
+) {
+    if world_clock.tick != 0 {
+        return;
+    }
+    let Ok((player, player_transform)) = hunt.player.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+
+    for faction_id in [FactionId::NationA, FactionId::NationB, FactionId::NationC] {
+        if !faction_registry.has_bounty(faction_id) {
+            continue;
+        }
+        let hostile = faction_registry.is_hostile(faction_id);
+        let wanted = if hostile { 2 } else { 1 };
+        let at_sea = hunt.bounties.hunters_from(faction_id);
+        if at_sea >= wanted || !rng.gen_bool(BOUNTY_HUNTER_CHANCE) {
+            continue;
+        }
+        let Some(state) = faction_registry.get_mut(faction_id) else {
+            continue;
+        };
+        if state.gold < BOUNTY_HUNTER_COST || state.ships >= MAX_SHIPS_PER_FACTION {
+            continue;
+        }
+
+        let heading = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+        let tile = crate::utils::pathfinding::world_to_tile(
+            player_pos + heading * BOUNTY_HUNTER_DISTANCE,
+            map_data.width,
+            map_data.height,
+        );
+        let Some(water) = map_data.nearest_navigable(tile, 6) else {
+            continue;
+        };
+        let spawn_pos = crate::utils::pathfinding::tile_to_world(water, map_data.width, map_data.height);
+
+        let hunter = HunterAtSea {
+            faction: faction_id,
+            ship_type: if hostile { ShipType::Frigate } else { ShipType::Schooner },
+            position: spawn_pos,
+        };
+        let texture_handle = asset_server.load(ships.enemy_sprite(hunter.ship_type));
+        spawn_bounty_hunter(&mut commands, texture_handle, &hunter, player);
+        hunt.bounties.hunters.push(hunter);
+        state.ships += 1;
+        state.gold = state.gold.saturating_sub(BOUNTY_HUNTER_COST);
+        info!("{:?} sent a bounty hunter after the player", faction_id);
+
+        news.send(WorldNewsEvent {
+            category: NewsCategory::Sighting,
+            headline: format!("{} posts a bounty on a notorious captain; hunters put to sea", faction_id.display_name()),
+        });
+    }
+}
+
+/// Measures merchant traffic and pirate danger on every trade lane, once per hour.
+///
+/// Each merchant sailing a `TradeRoute` adds to the volume of the lane between
+/// its two ports; volumes decay so lanes cool off when trade moves elsewhere.
+pub fn trade_lane_traffic_system(
+    world_clock: Res<WorldClock>,
+    mut lane_traffic: ResMut<TradeLaneTraffic>,
+    ship_query: Query<(&Transform, &Faction, Option<&OrderQueue>), With<Ship>>,
+    port_query: Query<&Transform, With<Port>>,
+) {
+    // Ports only exist on the High Seas; don't let lanes decay while docked
+    if world_clock.tick != 0 || port_query.is_empty() {
+        return;
+    }
+
+    let port_pos = |entity: Entity| port_query.get(entity).ok().map(|t| t.translation.truncate());
+    let mut merchants = Vec::new();
+    let mut pirates = Vec::new();
+    for (transform, faction, orders) in &ship_query {
+        if faction.0 == FactionId::Pirates {
+            pirates.push(transform.translation.truncate());
+            continue;
+        }
+        if let Some(Order::TradeRoute { origin, destination, .. }) = orders.and_then(|o| o.current()) {
+            if let (Some(from), Some(to)) = (port_pos(*origin), port_pos(*destination)) {
+                merchants.push((from, to));
+            }
+        }
+    }
+
+    lane_traffic.record_hour(&merchants);
+    lane_traffic.count_danger(&pirates);
+}
+
+/// Sends pirates to prowl the busiest trade lanes, once per day.
+///
+/// Free-roaming pirates are retasked to patrol an ambush point on each hot lane
+/// until it has as many raiders as its traffic attracts. If none are free, the
+/// Brethren fit out new raiders while their gold and ship count allow.
+pub fn pirate_lane_raiding_system(
+    world_clock: Res<WorldClock>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ships: Res<ShipDefinitions>,
+    mut faction_registry: ResMut<FactionRegistry>,
+    lane_traffic: Res<TradeLaneTraffic>,
+    mut pirate_query: Query<(Entity, &Transform, &Faction, &mut OrderQueue, Option<&LaneRaider>), With<HighSeasAI>>,
+    mut news: EventWriter<WorldNewsEvent>,
+) {
+    // Run once per day at hour 3 (between route planning and merchant commissioning)
+    if world_clock.tick != 0 || world_clock.hour != 3 {
+        return;
+    }
+
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    // Raiders sail as the default hull
+    let texture_handle: Handle<Image> = asset_server.load(ships.enemy_sprite(ShipType::default()));
+
+    let mut raiders_per_lane: HashMap<crate::resources::LaneKey, usize> = HashMap::new();
+    let mut free_pirates = Vec::new();
+    for (entity, transform, faction, _, raider) in &pirate_query {
+        if faction.0 != FactionId::Pirates {
+            continue;
+        }
+        match raider {
+            Some(raider) => *raiders_per_lane.entry(raider.lane).or_default() += 1,
+            None => free_pirates.push((entity, transform.translation.truncate())),
+        }
+    }
+
+    for (lane, stats) in lane_traffic.hot_lanes() {
+        let assigned = raiders_per_lane.get(&lane).copied().unwrap_or(0);
+        let wanted = stats.desired_raiders().saturating_sub(assigned);
+        let mut sent = 0;
+
+        for _ in 0..wanted {
+            let ambush = stats.point_along(rng.gen_range(0.25..0.75));
+            let patrol = Order::Patrol {
+                center: ambush,
+                radius: RAIDER_PATROL_RADIUS,
+                waypoint_index: 0,
+            };
+
+            // Prefer the nearest free-roaming pirate
+            let nearest = free_pirates
+                .iter()
+                .enumerate()
+                .min_by(|(_, (_, a)), (_, (_, b))| a.distance(ambush).total_cmp(&b.distance(ambush)))
+                .map(|(index, _)| index);
+            if let Some(index) = nearest {
+                let (entity, _) = free_pirates.swap_remove(index);
+                if let Ok((_, _, _, mut orders, _)) = pirate_query.get_mut(entity) {
+                    *orders = OrderQueue::with_order(patrol);
+                    commands.entity(entity).insert(LaneRaider { lane });
+                    sent += 1;
+                }
+                continue;
+            }
+
+            let Some(state) = faction_registry.get_mut(FactionId::Pirates) else {
+                break;
+            };
+            if state.gold < RAIDER_COST || state.ships >= MAX_SHIPS_PER_FACTION {
+                break;
+            }
+            commands.spawn((
+                Name::new("Pirate Lane Raider"),
+                Ship,
+                AI,
+                Faction(FactionId::Pirates),
+                HighSeasAI,
+                LaneRaider { lane },
+                Health::default(),
+                OrderQueue::with_order(patrol),
+                NavigationPath::default(),
+                Sprite {
+                    image: texture_handle.clone(),
+                    custom_size: Some(Vec2::splat(48.0)),
+                    flip_y: true,
+                    ..default()
+                },
+                Transform::from_xyz(ambush.x, ambush.y, 1.0),
+                HighSeasEntity,
+            ));
+            state.ships += 1;
+            state.gold = state.gold.saturating_sub(RAIDER_COST);
+            sent += 1;
+        }
+
+        if sent > 0 {
+            info!("Pirates sent {} raiders to a lane with traffic {:.1}", sent, stats.volume);
+            news.send(WorldNewsEvent {
+                category: NewsCategory::Sighting,
+                headline: "Pirate sails gather along a busy trade lane".to_string(),
+            });
+        }
+    }
+}
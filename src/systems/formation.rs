@@ -0,0 +1,203 @@
+//! Fleet formations on the High Seas.
+//!
+//! Fleet ships escorting the flagship each hold a slot in the formation the
+//! player picks in the fleet screen: line ahead, line abreast, wedge or
+//! screen. Slots are laid out in the flagship's own frame, so the formation
+//! turns with her. A ship off its slot is sent there through its landmass
+//! agent; one close enough keeps station directly, easing onto the slot and
+//! coming round to the flagship's heading. Slots are spaced well beyond
+//! `SHIP_SEPARATION`, so ships on station never crowd each other, and
+//! `friendly_separation_system` still parts any that cross on the way.
+
+use bevy::prelude::*;
+
+use crate::components::{Destination, Order, OrderQueue, Player};
+use crate::plugins::ai_activation::Dormant;
+use crate::plugins::worldmap::{HighSeasAI, HighSeasPlayer};
+use crate::resources::MapData;
+use crate::utils::pathfinding::world_to_tile;
+
+/// Distance between neighbouring slots (world units).
+pub const FORMATION_SPACING: f32 = 84.0;
+/// Within this of its slot a ship keeps station directly (world units).
+const STATION_TOLERANCE: f32 = 40.0;
+/// A ship's destination is moved once its slot has drifted this far from it.
+const RESTATION_DISTANCE: f32 = 48.0;
+/// How fast a ship on station closes the last of the gap (world units per second).
+const STATION_SPEED: f32 = 40.0;
+/// How quickly a ship on station comes round to the flagship's heading.
+const STATION_TURN: f32 = 1.5;
+
+/// The shape the fleet sails in around the flagship.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Formation {
+    /// Single file astern of the flagship.
+    #[default]
+    LineAhead,
+    /// Abreast of the flagship, alternating to either beam.
+    LineAbreast,
+    /// A V opening astern, alternating to either quarter.
+    Wedge,
+    /// Spread across the flagship's bow, scouting ahead of her.
+    Screen,
+}
+
+impl Formation {
+    /// Every formation, in display order.
+    pub const ALL: [Formation; 4] = [Formation::LineAhead, Formation::LineAbreast, Formation::Wedge, Formation::Screen];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Formation::LineAhead => "Line Ahead",
+            Formation::LineAbreast => "Line Abreast",
+            Formation::Wedge => "Wedge",
+            Formation::Screen => "Screen",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Formation::LineAhead => "Follow in the flagship's wake, one behind another.",
+            Formation::LineAbreast => "Sail alongside the flagship, a broad front to either beam.",
+            Formation::Wedge => "Fan out astern in a V, covering both quarters.",
+            Formation::Screen => "Spread ahead of the flagship to sight trouble first.",
+        }
+    }
+
+    /// Offset of slot `rank` of `count` from the flagship, in her frame
+    /// (bow along +Y).
+    pub fn slot_offset(&self, rank: usize, count: usize) -> Vec2 {
+        let pair = (rank / 2 + 1) as f32;
+        let side = if rank.is_multiple_of(2) { 1.0 } else { -1.0 };
+        match self {
+            Formation::LineAhead => Vec2::new(0.0, -(rank as f32 + 1.0) * FORMATION_SPACING),
+            Formation::LineAbreast => Vec2::new(side * pair * FORMATION_SPACING, 0.0),
+            Formation::Wedge => Vec2::new(side, -1.0) * pair * FORMATION_SPACING * std::f32::consts::FRAC_1_SQRT_2,
+            Formation::Screen => {
+                let across = rank as f32 - (count.max(1) - 1) as f32 / 2.0;
+                Vec2::new(across * FORMATION_SPACING, 2.0 * FORMATION_SPACING)
+            }
+        }
+    }
+
+    /// World position of slot `rank` of `count` around a flagship at `leader`.
+    pub fn slot_position(&self, rank: usize, count: usize, leader: &Transform) -> Vec2 {
+        let offset = leader.rotation * self.slot_offset(rank, count).extend(0.0);
+        leader.translation.truncate() + offset.truncate()
+    }
+}
+
+/// The formation the player's fleet keeps.
+#[derive(Resource, Debug, Default)]
+pub struct FleetFormation {
+    pub formation: Formation,
+}
+
+/// A fleet ship's place in the order of the formation; ships are ranked by
+/// it among those escorting the flagship.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct FormationSlot(pub usize);
+
+type LeaderQuery<'w, 's> = Query<'w, 's, (Entity, &'static Transform), (With<Player>, With<HighSeasPlayer>)>;
+
+type StationKeepers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Transform,
+        &'static FormationSlot,
+        &'static OrderQueue,
+        Option<&'static Destination>,
+    ),
+    (With<HighSeasAI>, Without<Player>, Without<Dormant>),
+>;
+
+/// Sends fleet ships escorting the flagship to their formation slots, and
+/// holds those already there on station.
+pub fn formation_station_system(
+    mut commands: Commands,
+    formation: Res<FleetFormation>,
+    leader: LeaderQuery,
+    mut escorts: StationKeepers,
+    map_data: Res<MapData>,
+    time: Res<Time>,
+) {
+    let Ok((leader_entity, leader_transform)) = leader.get_single() else {
+        return;
+    };
+
+    let mut ranks: Vec<(usize, Entity)> = escorts
+        .iter()
+        .filter(|(_, _, _, orders, _)| {
+            matches!(orders.current(), Some(Order::Escort { target, .. }) if *target == leader_entity)
+        })
+        .map(|(entity, _, slot, _, _)| (slot.0, entity))
+        .collect();
+    ranks.sort_unstable();
+
+    let count = ranks.len();
+    let delta_secs = time.delta_secs();
+    for (rank, (_, entity)) in ranks.into_iter().enumerate() {
+        let Ok((_, mut transform, _, _, destination)) = escorts.get_mut(entity) else {
+            continue;
+        };
+        let slot = formation.formation.slot_position(rank, count, leader_transform);
+        let position = transform.translation.truncate();
+
+        if position.distance(slot) > STATION_TOLERANCE {
+            if destination.is_none_or(|destination| destination.target.distance(slot) > RESTATION_DISTANCE) {
+                commands.entity(entity).insert(Destination { target: slot });
+            }
+            continue;
+        }
+
+        // On station: the agent stands down and the ship holds the slot itself,
+        // unless the slot has swung over land
+        if destination.is_some() {
+            commands.entity(entity).remove::<Destination>();
+        }
+        let tile = world_to_tile(slot, map_data.width, map_data.height);
+        if tile.x < 0 || tile.y < 0 || !map_data.is_navigable(tile.x as u32, tile.y as u32) {
+            continue;
+        }
+        let step = (slot - position).clamp_length_max(STATION_SPEED * delta_secs);
+        transform.translation.x += step.x;
+        transform.translation.y += step.y;
+        transform.rotation = transform.rotation.slerp(leader_transform.rotation, (STATION_TURN * delta_secs).min(1.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::SHIP_SEPARATION;
+
+    #[test]
+    fn test_slots_are_clear_of_each_other_and_the_flagship() {
+        for formation in Formation::ALL {
+            for count in 1..=8 {
+                let slots: Vec<Vec2> = (0..count).map(|rank| formation.slot_offset(rank, count)).collect();
+                for (i, a) in slots.iter().enumerate() {
+                    assert!(a.length() >= SHIP_SEPARATION, "{:?} slot {} crowds the flagship", formation, i);
+                    for b in &slots[i + 1..] {
+                        assert!(a.distance(*b) >= SHIP_SEPARATION, "{:?} slots crowd each other", formation);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_formation_turns_with_the_flagship() {
+        // Line ahead trails astern; with the flagship heading along +X, astern is -X
+        let leader = Transform::from_xyz(100.0, 50.0, 0.0)
+            .with_rotation(Quat::from_rotation_z(-std::f32::consts::FRAC_PI_2));
+        let slot = Formation::LineAhead.slot_position(0, 1, &leader);
+        assert!(slot.distance(Vec2::new(100.0 - FORMATION_SPACING, 50.0)) < 1e-3);
+
+        // A screen sails ahead of her
+        let ahead = Formation::Screen.slot_position(0, 1, &leader);
+        assert!(ahead.x > 100.0 && (ahead.y - 50.0).abs() < 1e-3);
+    }
+}
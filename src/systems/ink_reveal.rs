@@ -1,37 +1,81 @@
 //! Ink Reveal System
 //!
 //! Handles animated "ink spreading" effects when fog of war tiles are revealed.
+//!
+//! Tiles revealed together are grouped into contiguous blobs, and each blob
+//! is animated by a single `InkReveal` entity, so a large chart bought in a
+//! tavern costs one entity rather than hundreds. Past `INK_REVEAL_MAX_TILES`
+//! tiles in a frame, or `INK_REVEAL_MAX_BLOBS` blobs animating at once, the
+//! rest are revealed at once instead.
 
 use bevy::prelude::*;
+use bevy::utils::HashSet;
 use bevy_ecs_tilemap::prelude::*;
 
 use crate::components::ink_reveal::InkReveal;
 use crate::components::HighSeasEntity;
+use crate::events::FogRevealedEvent;
 use crate::plugins::worldmap::FogMap;
-use crate::resources::FogOfWar;
+
+/// Most tiles animated from one frame's reveals; beyond it they all appear at once.
+pub const INK_REVEAL_MAX_TILES: usize = 4096;
+/// Most blobs animating at once; further blobs appear at once.
+pub const INK_REVEAL_MAX_BLOBS: usize = 64;
+
+/// Groups tiles into blobs of edge-connected tiles.
+pub fn group_into_blobs(tiles: impl IntoIterator<Item = IVec2>) -> Vec<Vec<IVec2>> {
+    let mut remaining: HashSet<IVec2> = tiles.into_iter().collect();
+    let mut blobs = Vec::new();
+    while let Some(&seed) = remaining.iter().next() {
+        remaining.remove(&seed);
+        let mut blob = vec![seed];
+        let mut next = 0;
+        while next < blob.len() {
+            let tile = blob[next];
+            next += 1;
+            for step in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+                if remaining.remove(&(tile + step)) {
+                    blob.push(tile + step);
+                }
+            }
+        }
+        blobs.push(blob);
+    }
+    blobs
+}
 
 /// System that spawns InkReveal entities for newly explored tiles.
-/// Takes tiles from FogOfWar.take_newly_explored() and creates animation components.
+/// Reads the tiles from each `FogRevealedEvent` and creates one animation per blob.
 pub fn spawn_ink_reveals(
     mut commands: Commands,
-    mut fog: ResMut<FogOfWar>,
+    mut revealed: EventReader<FogRevealedEvent>,
     time: Res<Time>,
+    animating: Query<(), With<InkReveal>>,
 ) {
-    let newly_explored = fog.take_newly_explored();
-    
-    if newly_explored.is_empty() {
+    let tiles: Vec<IVec2> = revealed.read().flat_map(|event| event.tiles.iter().copied()).collect();
+    if tiles.is_empty() {
         return;
     }
-    
     let current_time = time.elapsed_secs();
-    
-    for tile_pos in newly_explored {
-        // Spawn an entity to track this tile's reveal animation
-        commands.spawn((
-            Name::new(format!("InkReveal_{},{}", tile_pos.x, tile_pos.y)),
-            InkReveal::new(tile_pos, current_time),
-            HighSeasEntity,
-        ));
+
+    // Too much at once to animate (a restored run, say): just lift the fog
+    if tiles.len() > INK_REVEAL_MAX_TILES {
+        commands.spawn((Name::new("InkReveal"), InkReveal::instant(tiles, current_time), HighSeasEntity));
+        return;
+    }
+
+    let mut room = INK_REVEAL_MAX_BLOBS.saturating_sub(animating.iter().count());
+    let mut overflow = Vec::new();
+    for blob in group_into_blobs(tiles) {
+        if room == 0 {
+            overflow.extend(blob);
+            continue;
+        }
+        room -= 1;
+        commands.spawn((Name::new("InkReveal"), InkReveal::new(blob, current_time), HighSeasEntity));
+    }
+    if !overflow.is_empty() {
+        commands.spawn((Name::new("InkReveal"), InkReveal::instant(overflow, current_time), HighSeasEntity));
     }
 }
 
@@ -45,42 +89,66 @@ pub fn animate_ink_reveals(
     mut tile_query: Query<&mut TileColor>,
 ) {
     let current_time = time.elapsed_secs();
-    
+
     // Get the fog tilemap
     let Ok((tilemap_size, tile_storage)) = fog_tilemap_query.get_single() else {
         return;
     };
-    
+
     for (entity, reveal) in reveals.iter() {
-        let progress = reveal.eased_progress(current_time);
-        
-        // Calculate tile position in tilemap coordinates
-        let tile_pos = TilePos {
-            x: reveal.tile_pos.x as u32,
-            y: reveal.tile_pos.y as u32,
-        };
-        
-        // Bounds check
-        if tile_pos.x >= tilemap_size.x || tile_pos.y >= tilemap_size.y {
-            // Clean up out-of-bounds reveals
-            if reveal.is_complete(current_time) {
-                commands.entity(entity).despawn();
+        for &tile in &reveal.tiles {
+            // Bounds check
+            if tile.x < 0 || tile.y < 0 || tile.x as u32 >= tilemap_size.x || tile.y as u32 >= tilemap_size.y {
+                continue;
             }
-            continue;
-        }
-        
-        // Get the tile entity
-        if let Some(tile_entity) = tile_storage.get(&tile_pos) {
-            if let Ok(mut tile_color) = tile_query.get_mut(tile_entity) {
-                // Animate alpha from 1.0 (fog) to 0.0 (revealed)
-                let alpha = 1.0 - progress;
-                tile_color.0 = Color::srgba(1.0, 1.0, 1.0, alpha);
+            let tile_pos = TilePos { x: tile.x as u32, y: tile.y as u32 };
+
+            // Get the tile entity
+            if let Some(tile_entity) = tile_storage.get(&tile_pos) {
+                if let Ok(mut tile_color) = tile_query.get_mut(tile_entity) {
+                    // Animate alpha from 1.0 (fog) to 0.0 (revealed)
+                    let alpha = 1.0 - reveal.eased_progress(tile, current_time);
+                    tile_color.0 = Color::srgba(1.0, 1.0, 1.0, alpha);
+                }
             }
         }
-        
+
         // Clean up completed animations
         if reveal.is_complete(current_time) {
             commands.entity(entity).despawn();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::circle_tiles;
+
+    #[test]
+    fn test_large_disc_is_one_blob() {
+        // A radius-15 chart is a single animation
+        let blobs = group_into_blobs(circle_tiles(IVec2::new(40, 40), 15));
+        assert_eq!(blobs.len(), 1);
+        assert!(blobs[0].len() < INK_REVEAL_MAX_TILES);
+    }
+
+    #[test]
+    fn test_separate_reveals_are_separate_blobs() {
+        let tiles = circle_tiles(IVec2::new(10, 10), 2).chain(circle_tiles(IVec2::new(30, 10), 1));
+        let mut sizes: Vec<usize> = group_into_blobs(tiles).iter().map(Vec::len).collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![5, 13]);
+        // Diagonal neighbours are not joined
+        assert_eq!(group_into_blobs([IVec2::ZERO, IVec2::ONE]).len(), 2);
+    }
+
+    #[test]
+    fn test_ink_spreads_from_the_middle() {
+        let reveal = InkReveal::new(circle_tiles(IVec2::ZERO, 5).collect(), 0.0);
+        let halfway = reveal.duration / 2.0;
+        assert!(reveal.eased_progress(IVec2::ZERO, halfway) > reveal.eased_progress(IVec2::new(5, 0), halfway));
+        assert_eq!(reveal.eased_progress(IVec2::new(5, 0), reveal.duration), 1.0);
+        assert!(InkReveal::instant(vec![IVec2::ZERO], 0.0).is_complete(0.0));
+    }
+}
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use crate::components::intel::{Intel, IntelData, IntelType, IntelExpiry, AcquiredIntel};
 use crate::events::IntelAcquiredEvent;
 use crate::resources::FogOfWar;
+use crate::utils::pathfinding::tile_to_world;
 
 /// System that processes acquired intel and applies its effects.
 /// 
@@ -30,10 +31,7 @@ pub fn intel_acquisition_system(
         match intel_data.intel_type {
             IntelType::MapReveal => {
                 // Reveal all positions in the fog of war
-                let revealed_count = intel_data.revealed_positions
-                    .iter()
-                    .filter(|pos| fog_of_war.explore(**pos))
-                    .count();
+                let revealed_count = fog_of_war.reveal_tiles(intel_data.revealed_positions.iter().copied());
                 
                 info!(
                     "MapReveal intel acquired: {} new tiles revealed",
@@ -42,9 +40,7 @@ pub fn intel_acquisition_system(
             }
             IntelType::TreasureLocation => {
                 // Reveal treasure location on map
-                for pos in &intel_data.revealed_positions {
-                    fog_of_war.explore(*pos);
-                }
+                fog_of_war.reveal_tiles(intel_data.revealed_positions.iter().copied());
                 info!(
                     "TreasureLocation intel acquired at {:?}",
                     intel_data.revealed_positions.first()
@@ -52,9 +48,7 @@ pub fn intel_acquisition_system(
             }
             IntelType::FleetPosition => {
                 // Reveal fleet position on map
-                for pos in &intel_data.revealed_positions {
-                    fog_of_war.explore(*pos);
-                }
+                fog_of_war.reveal_tiles(intel_data.revealed_positions.iter().copied());
                 info!(
                     "FleetPosition intel acquired: target {:?}",
                     intel_data.target_entity
@@ -63,9 +57,7 @@ pub fn intel_acquisition_system(
             IntelType::ShipRoute => {
                 // Route waypoints are stored for UI visualization
                 // Optionally reveal waypoint tiles
-                for pos in &intel_data.route_waypoints {
-                    fog_of_war.explore(*pos);
-                }
+                fog_of_war.reveal_tiles(intel_data.route_waypoints.iter().copied());
                 info!(
                     "ShipRoute intel acquired: {} waypoints",
                     intel_data.route_waypoints.len()
@@ -108,29 +100,22 @@ pub fn intel_expiry_system(
 /// System that visualizes acquired intel on the High Seas map using Gizmos.
 /// 
 /// Draws:
-/// - Diamond markers for TreasureLocation and FleetPosition intel
+/// - Diamond markers for FleetPosition intel
 /// - Dotted lines for ShipRoute intel waypoints
+///
+/// TreasureLocation intel is charted as a dig site and marked by the
+/// `DigSitesPlugin` instead.
 pub fn intel_visualization_system(
     intel_query: Query<&IntelData, (With<Intel>, With<AcquiredIntel>)>,
     map_data: Res<crate::resources::MapData>,
     mut gizmos: Gizmos,
 ) {
     // Colors for different intel types
-    let treasure_color = Color::srgba(1.0, 0.85, 0.0, 0.9);  // Gold
     let fleet_color = Color::srgba(0.9, 0.2, 0.2, 0.9);       // Red
     let route_color = Color::srgba(0.2, 0.6, 0.9, 0.7);       // Blue
     
     for intel_data in intel_query.iter() {
         match intel_data.intel_type {
-            IntelType::TreasureLocation => {
-                // Draw gold diamond markers for treasure locations
-                for tile_pos in &intel_data.revealed_positions {
-                    let world_pos = tile_to_world(*tile_pos, map_data.width, map_data.height);
-                    draw_diamond_marker(&mut gizmos, world_pos, 30.0, treasure_color);
-                    // Draw a small X inside
-                    draw_x_marker(&mut gizmos, world_pos, 10.0, treasure_color);
-                }
-            }
             IntelType::FleetPosition => {
                 // Draw red diamond markers for fleet positions
                 for tile_pos in &intel_data.revealed_positions {
@@ -170,7 +155,7 @@ pub fn intel_visualization_system(
                 }
             }
             // MapReveal is handled by fog of war, no additional visualization needed
-            // Rumor and PortInventory have no map visualization
+            // Rumor and PortInventory have no map visualization; TreasureLocation is a dig site
             _ => {}
         }
     }
@@ -190,21 +175,6 @@ fn draw_diamond_marker(gizmos: &mut Gizmos, center: Vec2, size: f32, color: Colo
     gizmos.line_2d(left, top, color);
 }
 
-/// Draws an X marker at the given position.
-fn draw_x_marker(gizmos: &mut Gizmos, center: Vec2, size: f32, color: Color) {
-    let half = size / 2.0;
-    gizmos.line_2d(
-        center + Vec2::new(-half, -half),
-        center + Vec2::new(half, half),
-        color,
-    );
-    gizmos.line_2d(
-        center + Vec2::new(-half, half),
-        center + Vec2::new(half, -half),
-        color,
-    );
-}
-
 /// Draws a dotted line between two points.
 fn draw_dotted_line(gizmos: &mut Gizmos, start: Vec2, end: Vec2, dash_length: f32, color: Color) {
     let direction = end - start;
@@ -232,12 +202,3 @@ fn draw_dotted_line(gizmos: &mut Gizmos, start: Vec2, end: Vec2, dash_length: f3
         draw = !draw;
     }
 }
-
-/// Converts tile coordinates to world coordinates.
-/// Duplicated from pathfinding utility to avoid circular imports.
-fn tile_to_world(tile_pos: IVec2, map_width: u32, map_height: u32) -> Vec2 {
-    const TILE_SIZE: f32 = 64.0;
-    let x = (tile_pos.x as f32 - map_width as f32 / 2.0) * TILE_SIZE + TILE_SIZE / 2.0;
-    let y = (tile_pos.y as f32 - map_height as f32 / 2.0) * TILE_SIZE + TILE_SIZE / 2.0;
-    Vec2::new(x, y)
-}
@@ -8,11 +8,13 @@
 use bevy::prelude::*;
 use bevy_landmass::prelude::*;
 
-use crate::components::{Player, Ship, Destination};
-use crate::components::ship::ShipType;
+use crate::components::{Player, Port, Ship, Destination, Health, JuryRig};
+use crate::components::ship::{ShipClass, ShipType};
 use crate::components::companion::CompanionRole;
+use crate::components::sails::SailTrim;
+use crate::components::upgrade::Fittings;
 use crate::plugins::worldmap::HighSeasAI;
-use crate::resources::Wind;
+use crate::resources::{BalanceConfig, ShipDefinitions, Wind, WindShadow};
 
 /// Extracts the facing direction (forward vector) from a 2D rotation.
 /// Ships face "up" in local space, so we extract the Y axis of the rotation.
@@ -28,18 +30,93 @@ fn signed_angle(from: Vec2, to: Vec2) -> f32 {
     cross.atan2(dot)
 }
 
+/// Turn-rate multiplier from rudder damage. Ships without `Health` steer freely.
+fn steering(health: Option<&Health>, jury_rig: Option<&JuryRig>) -> f32 {
+    match (health, jury_rig) {
+        (_, Some(_)) => 0.0,
+        (Some(health), None) => health.steering(),
+        (None, None) => 1.0,
+    }
+}
+
+/// Speed multiplier from sail damage, trim and any sails fitted at the shipwright.
+fn sail_drive(health: Option<&Health>, trim: Option<&SailTrim>, fittings: Option<&Fittings>) -> f32 {
+    health.map_or(1.0, Health::sail_power)
+        * trim.map_or(1.0, SailTrim::speed_factor)
+        * fittings.map_or(1.0, |fittings| fittings.speed_multiplier)
+}
+
+/// Arrival envelope of a point-sized ship (world units).
+const ARRIVAL_THRESHOLD: f32 = 32.0;
+/// Hull lengths outside her arrival envelope at which a ship starts to shorten sail.
+const APPROACH_LENGTHS: f32 = 4.0;
+/// Share of her speed a ship still carries as she enters her arrival envelope.
+const MIN_APPROACH_SPEED: f32 = 0.3;
+/// Ports this close to where a ship arrives are berthed at bow-on (world units).
+const BERTH_DISTANCE: f32 = 160.0;
+/// Heading error at which a berthing ship is settled (radians).
+const BERTH_TOLERANCE: f32 = 0.02;
+
+/// How close a ship must come to her destination to have arrived. Longer
+/// hulls have arrived once their bow is there, so their envelope is larger.
+pub fn arrival_radius(ship_type: ShipType) -> f32 {
+    ARRIVAL_THRESHOLD + ship_type.hull_length() * 0.5
+}
+
+/// Share of speed kept approaching a destination `remaining` away: full
+/// speed until `APPROACH_LENGTHS` hull lengths out from the arrival
+/// envelope, then easing off to `MIN_APPROACH_SPEED`, so ships glide in
+/// rather than stopping dead.
+fn approach_speed(remaining: f32, ship_type: &ShipType) -> f32 {
+    let braking = APPROACH_LENGTHS * ship_type.hull_length();
+    let way = ((remaining - arrival_radius(*ship_type)) / braking).clamp(0.0, 1.0);
+    MIN_APPROACH_SPEED + (1.0 - MIN_APPROACH_SPEED) * way
+}
+
+/// A ship that has arrived by a port, coming round to lie bow toward it.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Berthing {
+    pub heading: Vec2,
+}
+
+/// Share of speed kept while turning toward the desired heading.
+///
+/// Speed falls off with the square of the misalignment, but a ship carries
+/// at least her `turning_way`, so she eases round corners on her turning
+/// circle rather than stopping to pivot. Within two turning circles of her
+/// destination she may slow right down, so she cannot end up orbiting it.
+fn corner_speed(alignment: f32, ship_type: &ShipType, remaining: f32) -> f32 {
+    let eased = alignment.powi(2);
+    if remaining < 2.0 * ship_type.turning_radius() {
+        return eased;
+    }
+    eased.max(ship_type.turning_way())
+}
+
 /// System that moves ships using landmass velocity steering.
 ///
 /// Ships rotate toward the desired velocity direction at a rate limited by
 /// their ship type, then move forward in their facing direction.
 pub fn landmass_player_movement_system(
     mut query: Query<
-        (&mut Transform, &AgentDesiredVelocity2d, Option<&Destination>, &ShipType),
+        (
+            &mut Transform,
+            &AgentDesiredVelocity2d,
+            Option<&Destination>,
+            &ShipType,
+            Option<&ShipClass>,
+            Option<&Health>,
+            Option<&JuryRig>,
+            Option<&SailTrim>,
+            Option<&Fittings>,
+        ),
         (With<Player>, With<Ship>),
     >,
     companion_query: Query<&CompanionRole>,
     meta_profile: Option<Res<crate::resources::MetaProfile>>,
     wind: Res<Wind>,
+    wind_shadow: Res<WindShadow>,
+    ships: Res<ShipDefinitions>,
     time: Res<Time>,
 ) {
     // Check if player has a Navigator companion (provides +25% speed bonus)
@@ -52,7 +129,7 @@ pub fn landmass_player_movement_system(
         .map(|p| p.stats.sailing_speed_multiplier())
         .unwrap_or(1.0);
 
-    for (mut transform, desired_velocity, destination, ship_type) in &mut query {
+    for (mut transform, desired_velocity, destination, ship_type, class, health, jury_rig, trim, fittings) in &mut query {
         let pos = transform.translation.truncate();
         let velocity = desired_velocity.velocity();
         
@@ -80,8 +157,8 @@ pub fn landmass_player_movement_system(
         // Calculate how much we need to turn
         let angle_diff = signed_angle(current_facing, desired_direction);
 
-        // Limit turn rate based on ship type
-        let max_turn = ship_type.turn_rate() * time.delta_secs();
+        // Limit turn rate based on ship type and rudder damage
+        let max_turn = ship_type.turn_rate() * steering(health, jury_rig) * time.delta_secs();
         let actual_turn = angle_diff.clamp(-max_turn, max_turn);
 
         // Apply rotation
@@ -90,16 +167,16 @@ pub fn landmass_player_movement_system(
         // Get the new facing direction after rotation
         let new_facing = facing_direction(transform.rotation);
 
-        // Calculate speed - reduce when facing differs from desired direction
-        // Quadratic falloff: facing 90° off = 0% speed, 45° off ≈ 50%
+        // Calculate speed - reduce when facing differs from desired direction,
+        // but carry way round corners on the turning circle
         let alignment = new_facing.dot(desired_direction).max(0.0);
-        let turn_penalty = alignment.powi(2);
-        let base_speed = ship_type.base_speed() * navigator_bonus * stat_bonus * turn_penalty;
+        let remaining = pos.distance(dest.target);
+        let turn_penalty = corner_speed(alignment, ship_type, remaining) * approach_speed(remaining, ship_type);
+        let base_speed = ships.speed(class, *ship_type) * navigator_bonus * stat_bonus * turn_penalty * sail_drive(health, trim, fittings);
 
-        // Wind effect (±50% based on alignment with facing direction)
-        let wind_alignment = new_facing.dot(wind.direction_vec());
-        let wind_effect = wind_alignment * wind.strength * 0.5;
-        let speed = base_speed * (1.0 + wind_effect);
+        // Point of sail: fastest on a broad reach, crawling in irons, and
+        // slower in the lee of an island
+        let speed = base_speed * wind.sailing_factor(new_facing) * wind_shadow.lee_factor(pos, wind.strength);
 
         // Move forward in facing direction
         let movement = new_facing * speed * time.delta_secs();
@@ -111,19 +188,36 @@ pub fn landmass_player_movement_system(
 /// System that moves AI ships using landmass velocity steering.
 ///
 /// AI ships also use ship-type-based turning, moving forward in their
-/// facing direction with rotation limited by ship type.
+/// facing direction with rotation limited by ship type, and feel the wind
+/// on each point of sail and in the lee of islands just as the player does.
 pub fn landmass_ai_movement_system(
     mut query: Query<
-        (&mut Transform, &AgentDesiredVelocity2d, Option<&Destination>, &ShipType),
+        (
+            &mut Transform,
+            &AgentDesiredVelocity2d,
+            Option<&Destination>,
+            &ShipType,
+            Option<&ShipClass>,
+            Option<&Health>,
+            Option<&JuryRig>,
+            Option<&SailTrim>,
+            Option<&Fittings>,
+        ),
         (With<HighSeasAI>, With<Ship>),
     >,
+    wind: Res<Wind>,
+    wind_shadow: Res<WindShadow>,
+    balance: Res<BalanceConfig>,
+    ships: Res<ShipDefinitions>,
     time: Res<Time>,
 ) {
-    for (mut transform, desired_velocity, destination, ship_type) in &mut query {
+    for (mut transform, desired_velocity, destination, ship_type, class, health, jury_rig, trim, fittings) in &mut query {
         // Skip if no destination set
-        if destination.is_none() {
+        let Some(destination) = destination else {
             continue;
-        }
+        };
+        let pos = transform.translation.truncate();
+        let remaining = pos.distance(destination.target);
 
         let velocity = desired_velocity.velocity();
 
@@ -138,8 +232,8 @@ pub fn landmass_ai_movement_system(
         // Calculate how much we need to turn
         let angle_diff = signed_angle(current_facing, desired_direction);
 
-        // Limit turn rate based on ship type
-        let max_turn = ship_type.turn_rate() * time.delta_secs();
+        // Limit turn rate based on ship type and rudder damage
+        let max_turn = ship_type.turn_rate() * steering(health, jury_rig) * time.delta_secs();
         let actual_turn = angle_diff.clamp(-max_turn, max_turn);
 
         // Apply rotation
@@ -150,9 +244,13 @@ pub fn landmass_ai_movement_system(
 
         // Speed reduction when facing differs from desired (same as player)
         let alignment = new_facing.dot(desired_direction).max(0.0);
-        let turn_penalty = alignment.powi(2);
+        let turn_penalty = corner_speed(alignment, ship_type, remaining) * approach_speed(remaining, ship_type);
         // AI ships move at reduced speed (set in agent settings)
-        let speed = ship_type.base_speed() * 0.5 * turn_penalty;
+        let speed = balance.ai_speed.cruise_speed(ships.speed(class, *ship_type))
+            * turn_penalty
+            * sail_drive(health, trim, fittings)
+            * wind.sailing_factor(new_facing)
+            * wind_shadow.lee_factor(pos, wind.strength);
 
         // Move forward in facing direction
         let movement = new_facing * speed * time.delta_secs();
@@ -163,25 +261,56 @@ pub fn landmass_ai_movement_system(
 
 /// System that detects arrival at destination and cleans up navigation components.
 ///
-/// Uses proximity to destination rather than waypoint completion.
+/// Uses proximity to destination rather than waypoint completion, within an
+/// envelope sized to the ship (`arrival_radius`). A ship arriving by a port
+/// is left `Berthing` to come round bow toward it.
 pub fn arrival_detection_system(
     mut commands: Commands,
-    query: Query<(Entity, &Transform, &Destination)>,
+    query: Query<(Entity, &Transform, &Destination, Option<&ShipType>)>,
+    port_query: Query<&Transform, With<Port>>,
 ) {
-    const ARRIVAL_THRESHOLD: f32 = 32.0;
-
-    for (entity, transform, destination) in &query {
-        let distance = transform.translation.truncate().distance(destination.target);
+    for (entity, transform, destination, ship_type) in &query {
+        let position = transform.translation.truncate();
+        let distance = position.distance(destination.target);
 
-        if distance < ARRIVAL_THRESHOLD {
+        if distance < arrival_radius(ship_type.copied().unwrap_or_default()) {
             // Arrived at destination - remove navigation components
             commands.entity(entity).remove::<Destination>();
             // Also remove AgentTarget2d to stop landmass from steering
             commands.entity(entity).remove::<AgentTarget2d>();
+
+            let port = port_query
+                .iter()
+                .map(|port| port.translation.truncate())
+                .filter(|port| port.distance(destination.target) < BERTH_DISTANCE)
+                .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)));
+            if let Some(port) = port {
+                commands.entity(entity).insert(Berthing {
+                    heading: (port - position).normalize_or_zero(),
+                });
+            }
         }
     }
 }
 
+/// Brings berthing ships round to their heading at their turn rate, then
+/// settles them. A new destination cancels the manoeuvre.
+pub fn berthing_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &Berthing, &ShipType, Has<Destination>)>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, berthing, ship_type, sailing) in &mut query {
+        let angle_diff = signed_angle(facing_direction(transform.rotation), berthing.heading);
+        if sailing || berthing.heading == Vec2::ZERO || angle_diff.abs() < BERTH_TOLERANCE {
+            commands.entity(entity).remove::<Berthing>();
+            continue;
+        }
+        let max_turn = ship_type.turn_rate() * time.delta_secs();
+        transform.rotation *= Quat::from_rotation_z(angle_diff.clamp(-max_turn, max_turn));
+    }
+}
+
 /// System that syncs Destination component to AgentTarget2d.
 ///
 /// When Destination changes, updates the landmass target.
@@ -200,7 +329,7 @@ pub fn sync_destination_to_agent_target(
 /// movement velocity that would take the ship closer to the coastline.
 /// Uses actual coastline polygon geometry for accurate normal calculation.
 pub fn coastline_avoidance_system(
-    mut query: Query<(&mut Transform, Option<&crate::components::Player>), With<Ship>>,
+    mut query: Query<(&mut Transform, Option<&crate::components::Player>), (With<Ship>, Without<crate::plugins::ai_activation::Dormant>)>,
     coastline_data: Res<crate::plugins::worldmap::CoastlineData>,
 ) {
     const CRITICAL_DISTANCE: f32 = 64.0;
@@ -243,7 +372,7 @@ pub fn coastline_avoidance_system(
 /// and returns the outward-facing normal of that edge.
 ///
 /// Returns `None` if no coastline polygons exist.
-fn find_nearest_coastline_edge(
+pub fn find_nearest_coastline_edge(
     pos: Vec2,
     polygons: &[crate::utils::geometry::CoastlinePolygon],
 ) -> Option<(Vec2, Vec2)> {
@@ -309,3 +438,27 @@ fn closest_point_on_segment_with_normal(p: Vec2, a: Vec2, b: Vec2) -> (Vec2, Vec
     (closest, normal)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ships_ease_into_a_hull_sized_envelope() {
+        let frigate = ShipType::Frigate;
+        assert!(arrival_radius(frigate) > arrival_radius(ShipType::Sloop));
+
+        let envelope = arrival_radius(frigate);
+        assert!((approach_speed(envelope + 10_000.0, &frigate) - 1.0).abs() < 1e-6);
+        assert_eq!(approach_speed(envelope, &frigate), MIN_APPROACH_SPEED);
+        let halfway = approach_speed(envelope + APPROACH_LENGTHS * frigate.hull_length() / 2.0, &frigate);
+        assert!(halfway > MIN_APPROACH_SPEED && halfway < 1.0);
+    }
+
+    #[test]
+    fn test_arrived_scavengers_can_reach_their_wreck() {
+        for ship_type in [ShipType::Raft, ShipType::Sloop, ShipType::Schooner, ShipType::Frigate] {
+            assert!(arrival_radius(ship_type) <= crate::resources::WRECK_DIVE_RADIUS, "{:?}", ship_type);
+        }
+    }
+}
@@ -2,6 +2,7 @@ pub mod ship;
 pub mod movement;
 pub mod combat;
 pub mod ai;
+pub mod weapons;
 pub mod worldmap;
 pub mod wind;
 pub mod navigation;
@@ -17,11 +18,15 @@ pub mod ink_reveal;
 pub mod camera;
 pub mod hit_flash;
 pub mod landmass_movement;
+pub mod separation;
+pub mod stuck_recovery;
+pub mod formation;
 
 pub use ship::*;
 pub use movement::*;
 pub use combat::*;
 pub use ai::*;
+pub use weapons::*;
 pub use worldmap::*;
 pub use wind::*;
 pub use navigation::*;
@@ -35,3 +40,6 @@ pub use intel::*;
 pub use damage_effects::*;
 pub use ink_reveal::*;
 pub use landmass_movement::*;
+pub use separation::*;
+pub use stuck_recovery::*;
+pub use formation::*;
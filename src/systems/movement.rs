@@ -2,7 +2,10 @@ use bevy::prelude::*;
 use avian2d::prelude::*;
 use leafwing_input_manager::prelude::*;
 
-use crate::components::{Ship, Player, Health};
+use crate::components::{Ship, ShipType, Player, Health, JuryRig, Crew};
+use crate::components::sails::SailTrim;
+use crate::components::stations::CrewStations;
+use crate::components::upgrade::Fittings;
 use crate::plugins::input::PlayerAction;
 use crate::resources::Wind;
 use crate::plugins::core::MainCamera;
@@ -54,6 +57,10 @@ pub struct ShipInputBuffer {
     pub anchor: bool,
     pub fire_port: bool,
     pub fire_starboard: bool,
+    /// Fire whichever chaser bears on the mouse cursor.
+    pub fire_chaser: bool,
+    /// Throw a mortar shell at the mouse cursor.
+    pub fire_mortar: bool,
     pub mouse_world_pos: Vec2,
 }
 
@@ -79,6 +86,12 @@ pub fn buffer_ship_input(
         if action_state.just_pressed(&PlayerAction::FireStarboard) {
             input_buffer.fire_starboard = true;
         }
+        if action_state.just_pressed(&PlayerAction::FireChaser) {
+            input_buffer.fire_chaser = true;
+        }
+        if action_state.just_pressed(&PlayerAction::FireMortar) {
+            input_buffer.fire_mortar = true;
+        }
     }
 
     // Capture mouse world position
@@ -91,6 +104,36 @@ pub fn buffer_ship_input(
     }
 }
 
+/// Drag of the water on a hull making `velocity` with her bow along
+/// `forward`, as an acceleration (multiply by mass for the force).
+///
+/// Headway meets `longitudinal_drag`, more with the helm over: `rudder` runs
+/// from -1.0 to 1.0 for hard over either way, and costs her
+/// `ShipType::rudder_drag` of her way at full. Sideways the keel grips with
+/// `lateral_drag` scaled by `ShipType::keel_grip`, and turns her momentum
+/// toward her new heading rather than losing all of it: she keeps
+/// `ShipType::turning_way` of the speed the keel would otherwise take.
+pub fn hull_drag(
+    velocity: Vec2,
+    forward: Vec2,
+    rudder: f32,
+    ship_type: ShipType,
+    longitudinal_drag: f32,
+    lateral_drag: f32,
+) -> Vec2 {
+    // Vec2::perp() is 90deg CCW (port), so starboard is -perp
+    let right = -forward.perp();
+    let v_forward = velocity.dot(forward);
+    let v_lateral = velocity.dot(right);
+
+    let headway = longitudinal_drag + ship_type.rudder_drag() * rudder.abs().min(1.0);
+    let keel = -right * v_lateral * lateral_drag * ship_type.keel_grip();
+    let along = velocity.normalize_or_zero();
+    let keel = keel - along * keel.dot(along) * ship_type.turning_way();
+
+    -forward * v_forward * headway + keel
+}
+
 /// Physics-based ship movement system.
 /// 
 /// Runs in FixedUpdate for deterministic physics.
@@ -98,17 +141,17 @@ pub fn buffer_ship_input(
 /// 
 /// **Force Model:**
 /// ```text
-/// F_total = F_thrust + F_drag + F_wind (future)
+/// F_total = F_thrust + F_drag + F_wind
 /// 
-/// F_thrust = thrust_force * forward_direction * sail_health_ratio
-/// F_drag   = -linear_damping_coefficient * velocity (handled by Avian)
+/// F_thrust = thrust_force * forward_direction * sail_power * trim_speed_factor
+/// F_drag   = hull_drag(velocity, rudder, ship_type) * mass
 /// ```
 /// 
 /// **Torque Model:**
 /// ```text
 /// τ_total = τ_turn + τ_angular_drag (handled by Avian)
 /// 
-/// τ_turn = turn_torque * rudder_health_ratio
+/// τ_turn = turn_torque * steering (rudder ratio, floored; zero while jury-rigging)
 /// ```
 pub fn ship_physics_system(
     input_buffer: Res<ShipInputBuffer>,
@@ -123,15 +166,28 @@ pub fn ship_physics_system(
             &mut LinearVelocity,
             &mut AngularVelocity,
             &Mass,
+            Option<&JuryRig>,
+            Option<&SailTrim>,
+            Option<&CrewStations>,
+            Option<&Crew>,
+            Option<&Fittings>,
+            Option<&ShipType>,
         ),
         (With<Ship>, With<Player>),
     >,
 ) {
-    for (health, transform, mut force, mut torque, mut lin_vel, mut ang_vel, mass) in &mut ship_query {
+    for (health, transform, mut force, mut torque, mut lin_vel, mut ang_vel, mass, jury_rig, trim, stations, crew, fittings, ship_type) in &mut ship_query {
         let ship_mass = mass.0;
         // Calculate effectiveness based on component damage
-        let sail_effectiveness = health.sails_ratio();
-        let rudder_effectiveness = health.rudder_ratio();
+        // Hands on the sheets and braces work the ship harder
+        let maneuver = stations.map_or(1.0, CrewStations::maneuver_multiplier);
+        let sail_effectiveness = health.sail_power()
+            * trim.map_or(1.0, SailTrim::speed_factor)
+            * maneuver
+            * fittings.map_or(1.0, |fittings| fittings.speed_multiplier);
+        // A short-handed crew cannot brace the yards round as smartly
+        let handling = crew.map_or(1.0, Crew::handling_multiplier);
+        let rudder_effectiveness = if jury_rig.is_some() { 0.0 } else { health.steering() * handling };
         
         // Get ship's forward direction (Y-up in local space)
         let forward = transform.rotation * Vec3::Y;
@@ -173,27 +229,22 @@ pub fn ship_physics_system(
         // Apply thrust force in forward direction
         let mut total_force = forward_2d * thrust_magnitude;
         
-        // === Apply Anisotropic Drag (Keel Effect) ===
-        // 1. Get current velocity
-        let world_vel = lin_vel.0;
-        
-        // 2. Project velocity onto local axes
-        // In Bevy 2D (X-right, Y-up), if forward is (0, 1), right is (1, 0)
-        // Vec2::perp() is (-y, x), which is 90deg CCW (Left)
-        // So Right is -forward.perp()
-        let right_2d = -forward_2d.perp(); 
-        
-        let v_forward = world_vel.dot(forward_2d);
-        let v_lateral = world_vel.dot(right_2d);
+        // === Helm ===
+        let mut turn_direction = 0.0;
         
-        // 3. Calculate drag forces
-        // F_drag = -velocity * coefficient * mass
-        // Using mass ensures acceleration remains consistent with thrust
-        let drag_longitudinal = -forward_2d * v_forward * config.longitudinal_drag * ship_mass;
-        let drag_lateral = -right_2d * v_lateral * config.lateral_drag * ship_mass;
+        if input_buffer.turn_left {
+            turn_direction = 1.0;
+        } else if input_buffer.turn_right {
+            turn_direction = -1.0;
+        }
         
-        total_force += drag_longitudinal;
-        total_force += drag_lateral;
+        // === Apply Anisotropic Drag (Keel Effect) ===
+        // The keel grips sideways way and carries it round the turn; the
+        // helm over costs her headway. The player's combat ship carries no
+        // ShipType yet and handles as a sloop
+        let ship_type = ship_type.copied().unwrap_or_default();
+        let rudder = turn_direction * rudder_effectiveness;
+        total_force += hull_drag(lin_vel.0, forward_2d, rudder, ship_type, config.longitudinal_drag, config.lateral_drag) * ship_mass;
         
         // === Apply Wind Force ===
         // Wind pushes the ship in its direction, scaled by sail effectiveness
@@ -204,15 +255,7 @@ pub fn ship_physics_system(
         force.set_force(total_force);
         
         // === Calculate turning torque ===
-        let mut turn_direction = 0.0;
-        
-        if input_buffer.turn_left {
-            turn_direction = 1.0;
-        } else if input_buffer.turn_right {
-            turn_direction = -1.0;
-        }
-        
-        let turn_torque_value = config.turn_torque * turn_direction * rudder_effectiveness;
+        let turn_torque_value = config.turn_torque * turn_direction * rudder_effectiveness * maneuver;
         *torque = ExternalTorque::new(turn_torque_value);
         
         // === Angular speed limit ===
@@ -252,5 +295,34 @@ pub fn debug_ship_physics(
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helm_over_costs_headway() {
+        let ahead = Vec2::new(0.0, 100.0);
+        let steady = hull_drag(ahead, Vec2::Y, 0.0, ShipType::Sloop, 0.6, 3.0);
+        assert!((steady - Vec2::new(0.0, -60.0)).length() < 1e-3);
+        let hard_over = hull_drag(ahead, Vec2::Y, -1.0, ShipType::Sloop, 0.6, 3.0);
+        assert!(hard_over.y < steady.y);
+        assert!(hull_drag(ahead, Vec2::Y, 1.0, ShipType::Frigate, 0.6, 3.0).y < hard_over.y);
+    }
 
+    #[test]
+    fn test_keel_carries_way_round_a_turn() {
+        // Bow swung 45 degrees to port of the way she is making
+        let velocity = Vec2::new(0.0, 100.0);
+        let forward = Vec2::new(-1.0, 1.0).normalize();
+        let drag = hull_drag(velocity, forward, 0.0, ShipType::Frigate, 0.0, 3.0);
+
+        // The keel turns her toward her heading...
+        assert!(drag.x < 0.0);
+        // ...losing less way than a keel that simply stopped the slide
+        let right = -forward.perp();
+        let stopped = -right * velocity.dot(right) * 3.0 * ShipType::Frigate.keel_grip();
+        assert!(drag.dot(velocity) > stopped.dot(velocity));
+        assert!(drag.dot(velocity) < 0.0);
+    }
+}
 
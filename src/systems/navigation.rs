@@ -1,48 +1,71 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use bevy_landmass::debug::EnableLandmassDebug;
 
-use crate::components::{Player, Ship, Destination, NavigationPath};
+use crate::components::{Player, Ship, Destination, NavigationPath, Faction, FactionId};
+use crate::components::port::{Port, PortName};
 use crate::components::companion::CompanionRole;
-use crate::resources::{MapData, Wind};
-use crate::plugins::core::{GameState, MainCamera};
+use crate::events::PickingResult;
+use crate::resources::{FactionRegistry, Journal, MapData, PickTarget, Wind, WorldClock};
+use crate::plugins::core::GameState;
+use crate::plugins::port_ui::CurrentPort;
 use crate::utils::pathfinding::{find_path, tile_to_world, world_to_tile};
 
-/// System that handles mouse clicks to set navigation destination.
+/// How far from a clicked port to look for water to sail to (tiles).
+const PORT_APPROACH_SEARCH: i32 = 3;
+
+/// System that sets the navigation destination from left clicks on the map.
+///
+/// Clicking open water sails there; clicking a port sails to the water beside
+/// it; clicking a ship or wreck sails to where it lies. Land is ignored.
+/// With the F3 navmesh debug on, clicking a ship selects her for the agent
+/// overlay instead.
 pub fn click_to_navigate_system(
     mut commands: Commands,
-    mouse_button: Res<ButtonInput<MouseButton>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut picks: EventReader<PickingResult>,
     player_query: Query<Entity, (With<Player>, With<Ship>)>,
+    target_query: Query<&GlobalTransform>,
     map_data: Res<MapData>,
+    landmass_debug: Option<Res<EnableLandmassDebug>>,
 ) {
-    if !mouse_button.just_pressed(MouseButton::Left) {
+    let Some(pick) = picks.read().filter(|pick| pick.button == MouseButton::Left).last() else {
         return;
-    }
-    
-    let Ok(window) = window_query.get_single() else { return };
-    let Ok((camera, camera_transform)) = camera_query.get_single() else { return };
+    };
     let Ok(player_entity) = player_query.get_single() else { return };
-    
-    // Get cursor position in world coordinates
-    let Some(cursor_pos) = window.cursor_position() else { return };
-    let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) else { return };
-    
-    // Convert to tile coordinates
-    let tile_pos = world_to_tile(world_pos, map_data.width, map_data.height);
-    
-    // Check if destination is navigable
-    if !map_data.is_navigable(tile_pos.x as u32, tile_pos.y as u32) {
-        info!("Clicked on non-navigable tile at ({}, {})", tile_pos.x, tile_pos.y);
+    if matches!(pick.target, PickTarget::Ship(_)) && landmass_debug.is_some_and(|debug| debug.0) {
         return;
     }
-    
+
+    let world_target = match pick.target {
+        PickTarget::Water => {
+            let tile_pos = world_to_tile(pick.world_pos, map_data.width, map_data.height);
+            tile_to_world(tile_pos, map_data.width, map_data.height)
+        }
+        PickTarget::Port(entity) => {
+            let Ok(transform) = target_query.get(entity) else { return };
+            let port_tile = world_to_tile(transform.translation().truncate(), map_data.width, map_data.height);
+            let Some(water) = map_data.nearest_navigable(port_tile, PORT_APPROACH_SEARCH) else {
+                info!("No water beside the clicked port at ({}, {})", port_tile.x, port_tile.y);
+                return;
+            };
+            tile_to_world(water, map_data.width, map_data.height)
+        }
+        PickTarget::Ship(entity) | PickTarget::Wreck(entity) => {
+            let Ok(transform) = target_query.get(entity) else { return };
+            transform.translation().truncate()
+        }
+        PickTarget::Land => {
+            let tile_pos = world_to_tile(pick.world_pos, map_data.width, map_data.height);
+            info!("Clicked on non-navigable tile at ({}, {})", tile_pos.x, tile_pos.y);
+            return;
+        }
+    };
+
     // Set destination on player
-    let world_target = tile_to_world(tile_pos, map_data.width, map_data.height);
     commands.entity(player_entity).insert(Destination { target: world_target });
-    
-    info!("Navigation destination set to tile ({}, {}) = world ({:.0}, {:.0})", 
-          tile_pos.x, tile_pos.y, world_target.x, world_target.y);
+
+    info!("Navigation destination set to {:?} = world ({:.0}, {:.0})",
+          pick.target, world_target.x, world_target.y);
 }
 
 /// System that calculates paths when destination changes.
@@ -234,9 +257,7 @@ pub fn navigation_movement_system(
         
         // Base speed with wind effect, Navigator bonus, and Navigation stat
         let base_speed = 300.0 * navigator_bonus * stat_bonus;
-        let wind_alignment = direction_normalized.dot(wind.direction_vec());
-        let wind_effect = wind_alignment * wind.strength * 0.5;
-        let speed = base_speed * (1.0 + wind_effect);
+        let speed = base_speed * wind.sailing_factor(direction_normalized);
         
         // Move toward waypoint
         let movement = direction_normalized * speed * time.delta_secs();
@@ -325,11 +346,44 @@ pub fn path_visualization_system(
     }
 }
 
+/// Whether a port lets the player in, and the journal note when it does not.
+#[derive(SystemParam)]
+pub struct PortEntry<'w, 's> {
+    factions: Res<'w, FactionRegistry>,
+    clock: Res<'w, WorldClock>,
+    journal: ResMut<'w, Journal>,
+    /// The port that last turned the player away, so it is noted once rather than every frame.
+    turned_away: Local<'s, Option<Vec2>>,
+}
+
+impl PortEntry<'_, '_> {
+    /// Whether the port flying `faction` at `position` lets the player in.
+    fn admits(&mut self, faction: FactionId, name: &str, position: Vec2) -> bool {
+        if self.factions.standing(faction).allows_port_entry() {
+            return true;
+        }
+        if *self.turned_away != Some(position) {
+            *self.turned_away = Some(position);
+            self.journal.record(
+                &self.clock,
+                format!("{} closed its harbour to us: {} will not suffer us ashore.", name, faction.display_name()),
+            );
+        }
+        false
+    }
+}
+
 /// System that detects arrival at port tiles and triggers state transition.
+/// Remembers the flag, name and position of the nearest port, which outlive the High Seas entities.
+/// Ports of a faction hostile to the player keep their harbours closed. The
+/// ship stands in through the harbour approach (see `plugins::harbor_approach`).
 pub fn port_arrival_system(
     query: Query<&Transform, (With<Player>, With<Ship>)>,
+    port_query: Query<(&Transform, &Faction, &PortName), With<Port>>,
     map_data: Res<MapData>,
+    mut current_port: ResMut<CurrentPort>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut entry: PortEntry,
 ) {
     for transform in &query {
         let pos = transform.translation.truncate();
@@ -338,8 +392,22 @@ pub fn port_arrival_system(
         if tile.x >= 0 && tile.y >= 0 {
             if let Some(t) = map_data.tile(tile.x as u32, tile.y as u32) {
                 if t.tile_type.is_port() {
+                    let nearest = port_query
+                        .iter()
+                        .min_by(|(a, _, _), (b, _, _)| {
+                            a.translation.truncate().distance_squared(pos)
+                                .total_cmp(&b.translation.truncate().distance_squared(pos))
+                        });
+                    if let Some((transform, faction, name)) = nearest {
+                        if !entry.admits(faction.0, &name.0, transform.translation.truncate()) {
+                            continue;
+                        }
+                    }
                     info!("Arrived at port at tile ({}, {})", tile.x, tile.y);
-                    next_state.set(GameState::Port);
+                    current_port.faction = nearest.map(|(_, faction, _)| faction.0);
+                    current_port.name = nearest.map(|(_, _, name)| name.0.clone());
+                    current_port.position = nearest.map(|(transform, _, _)| transform.translation.truncate());
+                    next_state.set(GameState::HarborApproach);
                 }
             }
         }
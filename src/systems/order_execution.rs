@@ -5,7 +5,9 @@
 use bevy::prelude::*;
 
 use crate::components::{AI, Ship, Order, OrderQueue, Destination, NavigationPath, Port, Player};
+use crate::plugins::ai_activation::Dormant;
 use crate::plugins::worldmap::HighSeasAI;
+use crate::systems::formation::FormationSlot;
 
 /// System that reads orders from AI ships and sets navigation destinations.
 ///
@@ -17,7 +19,7 @@ use crate::plugins::worldmap::HighSeasAI;
 pub fn order_execution_system(
     mut commands: Commands,
     mut ai_query: Query<
-        (Entity, &Transform, &mut OrderQueue, Option<&NavigationPath>),
+        (Entity, &Transform, &mut OrderQueue, Option<&NavigationPath>, Has<FormationSlot>, Has<Dormant>),
         (With<AI>, With<Ship>, With<HighSeasAI>),
     >,
     port_query: Query<&Transform, With<Port>>,
@@ -35,7 +37,7 @@ pub fn order_execution_system(
     player_query: Query<&Transform, With<Player>>,
     map_data: Res<MapData>,
 ) {
-    for (entity, transform, mut order_queue, nav_path) in &mut ai_query {
+    for (entity, transform, mut order_queue, nav_path, in_formation, dormant) in &mut ai_query {
         // Skip if ship is currently navigating (has remaining waypoints)
         if let Some(path) = nav_path {
             if !path.is_empty() {
@@ -78,13 +80,19 @@ pub fn order_execution_system(
             Order::Escort { target, follow_distance } => {
                 // Try to find target in player query first
                 if let Ok(target_transform) = player_query.get(*target) {
-                     execute_escort(
-                        &mut commands,
-                        entity,
-                        transform,
-                        target_transform, // Pass transform directly
-                        *follow_distance,
-                    );
+                    // Fleet ships escorting the flagship are held on their
+                    // formation slot by `formation_station_system`, which
+                    // passes over dormant ships; those, and ships without a
+                    // slot, follow at their distance
+                    if !in_formation || dormant {
+                        execute_escort(
+                            &mut commands,
+                            entity,
+                            transform,
+                            target_transform, // Pass transform directly
+                            *follow_distance,
+                        );
+                    }
                 } else {
                     // Could also be another AI ship... 
                     // For now, only supporting Player or Port as robust targets in this simple system
@@ -104,6 +112,12 @@ pub fn order_execution_system(
                     &map_data,
                 );
             }
+            Order::Scavenge { position, .. } => {
+                // Stripping the wreck on arrival is `scavenge_wrecks`
+                if transform.translation.truncate().distance(*position) > WRECK_DIVE_RADIUS {
+                    commands.entity(entity).insert(Destination { target: *position });
+                }
+            }
             Order::Idle => {
                 // No action needed for idle
             }
@@ -300,9 +314,9 @@ fn execute_scout(
     }
 }
 
-use crate::resources::{RouteCache, MapData, NavMeshResource};
+use crate::resources::{RouteCache, MapData, NavMeshResource, Wind, WRECK_DIVE_RADIUS};
 use crate::resources::navmesh::ShoreBufferTier;
-use crate::utils::pathfinding::{find_path, tile_to_world, world_to_tile};
+use crate::utils::pathfinding::{find_path_in_wind, tile_to_world, world_to_tile};
 
 /// System that calculates paths for AI ships.
 /// 
@@ -316,6 +330,7 @@ pub fn ai_pathfinding_system(
     mut route_cache: ResMut<RouteCache>,
     map_data: Res<MapData>,
     navmesh: Option<Res<NavMeshResource>>,
+    wind: Res<Wind>,
 ) {
     for (entity, transform, destination) in &mut query {
         let start_pos = transform.translation.truncate();
@@ -342,44 +357,52 @@ pub fn ai_pathfinding_system(
             }
         } else {
             // Fallback to grid-based Theta* with caching
-            let start_tile = world_to_tile(start_pos, map_data.width, map_data.height);
-            let goal_tile = world_to_tile(target_pos, map_data.width, map_data.height);
-            
-            // Check cache first
-            let tile_path = if let Some(cached) = route_cache.get(start_tile, goal_tile) {
-                Some(cached.clone())
-            } else {
-                // Cache miss - compute path
-                if let Some(path) = find_path(start_tile, goal_tile, &map_data) {
-                    route_cache.insert(start_tile, goal_tile, path.clone());
-                    Some(path)
-                } else {
-                    None
-                }
-            };
-            
-            if let Some(path) = tile_path {
-                // Convert tile path to world waypoints, skip first point
-                let result: Vec<Vec2> = path.iter()
-                    .skip(1) 
-                    .map(|&p| tile_to_world(p, map_data.width, map_data.height))
-                    .collect();
-                
-                if result.is_empty() {
-                    vec![target_pos]
-                } else {
-                    result
-                }
-            } else {
-                // Direct line as last resort
-                vec![target_pos]
-            }
+            cached_route(start_pos, target_pos, &map_data, &wind, &mut route_cache)
         };
         
         commands.entity(entity).insert(NavigationPath { waypoints });
     }
 }
 
+/// World waypoints from `start` to `target` along a cached Theta* tile path,
+/// excluding the start, tacking around the current wind. Falls back to a
+/// straight line if no path is found.
+pub fn cached_route(start: Vec2, target: Vec2, map_data: &MapData, wind: &Wind, route_cache: &mut RouteCache) -> Vec<Vec2> {
+    route_cache.plan_for_wind(wind);
+    let start_tile = world_to_tile(start, map_data.width, map_data.height);
+    let goal_tile = world_to_tile(target, map_data.width, map_data.height);
+    
+    // Check cache first
+    let tile_path = if let Some(cached) = route_cache.get(start_tile, goal_tile) {
+        Some(cached.clone())
+    } else {
+        // Cache miss - compute path
+        if let Some(path) = find_path_in_wind(start_tile, goal_tile, map_data, Some(wind)) {
+            route_cache.insert(start_tile, goal_tile, path.clone());
+            Some(path)
+        } else {
+            None
+        }
+    };
+    
+    if let Some(path) = tile_path {
+        // Convert tile path to world waypoints, skip first point
+        let result: Vec<Vec2> = path.iter()
+            .skip(1) 
+            .map(|&p| tile_to_world(p, map_data.width, map_data.height))
+            .collect();
+        
+        if result.is_empty() {
+            vec![target]
+        } else {
+            result
+        }
+    } else {
+        // Direct line as last resort
+        vec![target]
+    }
+}
+
 /// System that moves AI ships along their navigation paths.
 /// 
 /// AI ships move at a fixed speed toward their waypoints.
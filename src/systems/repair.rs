@@ -6,8 +6,10 @@ use crate::components::{
     ship::{Player, Ship},
 };
 use crate::events::{RepairRequestEvent, RepairType};
+use crate::plugins::port_ui::CurrentPort;
+use crate::resources::{BalanceConfig, DockQueue, DockService, PortEvents, RepairBalance, WorldClock, EMERGENCY_REPAIR_MULTIPLIER};
 
-/// Repair cost configuration.
+/// Repair cost configuration. The costs are the defaults for `RepairBalance`.
 pub mod repair_config {
     /// Base cost per HP to repair sails.
     pub const SAILS_COST_PER_HP: f32 = 1.0;
@@ -15,80 +17,241 @@ pub mod repair_config {
     pub const RUDDER_COST_PER_HP: f32 = 1.5;
     /// Base cost per HP to repair hull.
     pub const HULL_COST_PER_HP: f32 = 2.0;
+    /// Sails HP the shipwrights make good per in-game hour.
+    pub const SAILS_HP_PER_HOUR: f32 = 25.0;
+    /// Rudder HP the shipwrights make good per in-game hour.
+    pub const RUDDER_HP_PER_HOUR: f32 = 20.0;
+    /// Hull HP the shipwrights make good per in-game hour.
+    pub const HULL_HP_PER_HOUR: f32 = 10.0;
 }
 
-/// Calculates repair cost for the given component and damage amount.
-pub fn calculate_repair_cost(repair_type: RepairType, damage: f32) -> u32 {
-    let cost_per_hp = match repair_type {
-        RepairType::Sails => repair_config::SAILS_COST_PER_HP,
-        RepairType::Rudder => repair_config::RUDDER_COST_PER_HP,
-        RepairType::Hull => repair_config::HULL_COST_PER_HP,
+/// Calculates repair cost for the given component and damage amount at `prices`.
+pub fn calculate_repair_cost(repair_type: RepairType, damage: f32, prices: &RepairBalance) -> u32 {
+    (damage * prices.cost_per_hp(repair_type)).ceil() as u32
+}
+
+/// Hours the shipwrights need to make good the given damage (at least one).
+pub fn calculate_repair_hours(repair_type: RepairType, damage: f32) -> u32 {
+    let hp_per_hour = match repair_type {
+        RepairType::Sails => repair_config::SAILS_HP_PER_HOUR,
+        RepairType::Rudder => repair_config::RUDDER_HP_PER_HOUR,
+        RepairType::Hull => repair_config::HULL_HP_PER_HOUR,
     };
-    (damage * cost_per_hp).ceil() as u32
+    ((damage / hp_per_hour).ceil() as u32).max(1)
+}
+
+/// Damage the given component has taken.
+pub fn repair_damage(health: &Health, repair_type: RepairType) -> f32 {
+    match repair_type {
+        RepairType::Sails => health.sails_max - health.sails,
+        RepairType::Rudder => health.rudder_max - health.rudder,
+        RepairType::Hull => health.hull_max - health.hull,
+    }
+}
+
+/// Points of damage to `repair_type` the shipwrights make good for at most
+/// `budget` gold (all of `damage` without one), in whole points when the
+/// budget runs out first. The rounded-up cost of the result never exceeds
+/// the budget.
+pub fn repair_hp(repair_type: RepairType, damage: f32, budget: Option<u32>, prices: &RepairBalance) -> f32 {
+    let Some(budget) = budget else {
+        return damage;
+    };
+    let hp = damage.min((budget as f32 / prices.cost_per_hp(repair_type)).floor());
+    // Float error can leave the last whole point a shade over the budget
+    if calculate_repair_cost(repair_type, hp, prices) > budget {
+        (hp - 1.0).max(0.0)
+    } else {
+        hp
+    }
+}
+
+/// Which component the "repair all" order sees to first when gold is short.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepairPriority {
+    /// Keep her afloat: hull, then rudder, then sails.
+    #[default]
+    HullFirst,
+    /// Keep her sailing: sails, then rudder, then hull.
+    SailsFirst,
+    /// Keep her answering the helm: rudder, then sails, then hull.
+    RudderFirst,
+}
+
+impl RepairPriority {
+    pub const ALL: [RepairPriority; 3] = [RepairPriority::HullFirst, RepairPriority::SailsFirst, RepairPriority::RudderFirst];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RepairPriority::HullFirst => "Hull first",
+            RepairPriority::SailsFirst => "Sails first",
+            RepairPriority::RudderFirst => "Rudder first",
+        }
+    }
+
+    /// Components in the order they are seen to.
+    pub fn order(&self) -> [RepairType; 3] {
+        match self {
+            RepairPriority::HullFirst => [RepairType::Hull, RepairType::Rudder, RepairType::Sails],
+            RepairPriority::SailsFirst => [RepairType::Sails, RepairType::Rudder, RepairType::Hull],
+            RepairPriority::RudderFirst => [RepairType::Rudder, RepairType::Sails, RepairType::Hull],
+        }
+    }
+}
+
+/// The shipwrights' work to book for a "repair all" order.
+#[derive(Debug, Default)]
+pub struct RepairAllPlan {
+    /// Requests to send, in priority order.
+    pub requests: Vec<RepairRequestEvent>,
+    /// Gold to repair every damaged component not already in hand.
+    pub full_cost: u32,
+    /// Gold the requests spend.
+    pub cost: u32,
 }
 
-/// System that handles repair requests by restoring component HP and deducting gold.
-/// 
-/// For hull repairs, also removes the WaterIntake component if present.
+impl RepairAllPlan {
+    /// Whether the plan makes good all the damage not already in hand.
+    pub fn is_complete(&self) -> bool {
+        self.cost >= self.full_cost
+    }
+}
+
+/// Plans a "repair all" order for `gold`: each damaged component not already
+/// in hand is repaired fully in `priority` order while the gold lasts, and
+/// the first one it falls short of is repaired as far as the rest will go.
+pub fn plan_repair_all(
+    health: &Health,
+    dock_queue: &DockQueue,
+    gold: u32,
+    prices: &RepairBalance,
+    priority: RepairPriority,
+) -> RepairAllPlan {
+    let mut plan = RepairAllPlan::default();
+    let mut gold_left = gold;
+    for repair_type in priority.order() {
+        let damage = repair_damage(health, repair_type);
+        if damage <= 0.0 || dock_queue.has_repair(repair_type) {
+            continue;
+        }
+        let cost = calculate_repair_cost(repair_type, damage, prices);
+        plan.full_cost += cost;
+        if cost <= gold_left {
+            gold_left -= cost;
+            plan.cost += cost;
+            plan.requests.push(RepairRequestEvent { repair_type, emergency: false, budget: None });
+            continue;
+        }
+        let hp = repair_hp(repair_type, damage, Some(gold_left), prices);
+        if hp >= 1.0 {
+            let spent = calculate_repair_cost(repair_type, hp, prices);
+            plan.cost += spent;
+            plan.requests.push(RepairRequestEvent { repair_type, emergency: false, budget: Some(spent) });
+        }
+        // Nothing is spent on lower priorities while this one is left short
+        gold_left = 0;
+    }
+    plan
+}
+
+/// Makes good `hp` points of damage to a component.
+///
+/// For hull repairs, also removes the WaterIntake component once the hull is sound.
+fn repair_component(commands: &mut Commands, entity: Entity, health: &mut Health, repair_type: RepairType, hp: f32) {
+    match repair_type {
+        RepairType::Sails => health.sails = (health.sails + hp).min(health.sails_max),
+        RepairType::Rudder => health.rudder = (health.rudder + hp).min(health.rudder_max),
+        RepairType::Hull => {
+            health.hull = (health.hull + hp).min(health.hull_max);
+            // Remove WaterIntake when hull is fully repaired
+            if health.hull >= health.hull_max {
+                commands.entity(entity).remove::<WaterIntake>();
+            }
+        }
+    }
+}
+
+/// System that handles repair requests by deducting gold and booking the shipwrights.
+///
+/// A request with a budget repairs only as much as the budget pays for. An
+/// emergency repair costs `EMERGENCY_REPAIR_MULTIPLIER` times as much and is
+/// done at once; otherwise the repair joins the `DockQueue` and the component
+/// is restored by `dock_jobs_system` when the job is finished. Nothing is
+/// booked while the port's dockworkers are on strike.
 pub fn repair_execution_system(
     mut commands: Commands,
     mut repair_events: EventReader<RepairRequestEvent>,
     mut player_query: Query<(Entity, &mut Health, &mut Gold), (With<Player>, With<Ship>)>,
+    mut dock_queue: ResMut<DockQueue>,
+    world_clock: Res<WorldClock>,
+    balance: Res<BalanceConfig>,
+    current_port: Res<CurrentPort>,
+    port_events: Res<PortEvents>,
 ) {
+    let on_strike = port_events.docks_closed(current_port.position);
     for event in repair_events.read() {
+        if on_strike {
+            info!("Repair refused: the dockworkers are on strike");
+            continue;
+        }
         let Ok((entity, mut health, mut gold)) = player_query.get_single_mut() else {
             warn!("Repair failed: Player ship not found");
             continue;
         };
 
-        match event.repair_type {
-            RepairType::Sails => {
-                let damage = health.sails_max - health.sails;
-                if damage <= 0.0 {
-                    info!("Sails already at full health");
-                    continue;
-                }
-                let cost = calculate_repair_cost(RepairType::Sails, damage);
-                if !gold.spend(cost) {
-                    info!("Cannot afford sails repair ({} gold needed)", cost);
-                    continue;
-                }
-                health.sails = health.sails_max;
-                info!("Repaired sails for {} gold", cost);
-            }
-            RepairType::Rudder => {
-                let damage = health.rudder_max - health.rudder;
-                if damage <= 0.0 {
-                    info!("Rudder already at full health");
-                    continue;
-                }
-                let cost = calculate_repair_cost(RepairType::Rudder, damage);
-                if !gold.spend(cost) {
-                    info!("Cannot afford rudder repair ({} gold needed)", cost);
-                    continue;
-                }
-                health.rudder = health.rudder_max;
-                info!("Repaired rudder for {} gold", cost);
-            }
-            RepairType::Hull => {
-                let damage = health.hull_max - health.hull;
-                if damage <= 0.0 {
-                    info!("Hull already at full health");
-                    continue;
-                }
-                let cost = calculate_repair_cost(RepairType::Hull, damage);
-                if !gold.spend(cost) {
-                    info!("Cannot afford hull repair ({} gold needed)", cost);
-                    continue;
-                }
-                health.hull = health.hull_max;
-                
-                // Remove WaterIntake when hull is fully repaired
-                commands.entity(entity).remove::<WaterIntake>();
-                
-                info!("Repaired hull for {} gold (WaterIntake removed)", cost);
-            }
+        let repair_type = event.repair_type;
+        let damage = repair_damage(&health, repair_type);
+        if damage <= 0.0 {
+            info!("{:?} already at full health", repair_type);
+            continue;
         }
+        if dock_queue.has_repair(repair_type) {
+            info!("{:?} repair already underway", repair_type);
+            continue;
+        }
+
+        let multiplier = if event.emergency { EMERGENCY_REPAIR_MULTIPLIER } else { 1 };
+        let budget = event.budget.map(|budget| budget / multiplier);
+        let hp = repair_hp(repair_type, damage, budget, &balance.repair);
+        if hp <= 0.0 {
+            info!("{:?} repair budget too small to buy any work", repair_type);
+            continue;
+        }
+        let cost = calculate_repair_cost(repair_type, hp, &balance.repair) * multiplier;
+        if !gold.spend(cost) {
+            info!("Cannot afford {:?} repair ({} gold needed)", repair_type, cost);
+            continue;
+        }
+
+        if event.emergency {
+            repair_component(&mut commands, entity, &mut health, repair_type, hp);
+            info!("Emergency repair of {:?} for {} gold", repair_type, cost);
+        } else {
+            let hours = calculate_repair_hours(repair_type, hp);
+            dock_queue.schedule(DockService::Repair(repair_type, hp.ceil() as u32), hours, world_clock.total_ticks());
+            info!("{:?} repair of {:.0} points booked for {} gold, ready in {} hours", repair_type, hp, cost, hours);
+        }
+    }
+}
+
+/// System that finishes dock jobs as their time comes.
+pub fn dock_jobs_system(
+    mut commands: Commands,
+    mut dock_queue: ResMut<DockQueue>,
+    world_clock: Res<WorldClock>,
+    mut player_query: Query<(Entity, &mut Health), (With<Player>, With<Ship>)>,
+) {
+    if !dock_queue.is_laid_up() {
+        return;
+    }
+    for job in dock_queue.take_finished(world_clock.total_ticks()) {
+        if let DockService::Repair(repair_type, hp) = job.service {
+            let Ok((entity, mut health)) = player_query.get_single_mut() else {
+                continue;
+            };
+            repair_component(&mut commands, entity, &mut health, repair_type, hp as f32);
+        }
+        info!("Dock job done: {}", job.service.label());
     }
 }
 
@@ -98,25 +261,113 @@ mod tests {
 
     #[test]
     fn test_calculate_repair_cost_sails() {
-        let cost = calculate_repair_cost(RepairType::Sails, 50.0);
+        let cost = calculate_repair_cost(RepairType::Sails, 50.0, &RepairBalance::default());
         assert_eq!(cost, 50); // 50 * 1.0 = 50
     }
 
     #[test]
     fn test_calculate_repair_cost_rudder() {
-        let cost = calculate_repair_cost(RepairType::Rudder, 50.0);
+        let cost = calculate_repair_cost(RepairType::Rudder, 50.0, &RepairBalance::default());
         assert_eq!(cost, 75); // 50 * 1.5 = 75
     }
 
     #[test]
     fn test_calculate_repair_cost_hull() {
-        let cost = calculate_repair_cost(RepairType::Hull, 50.0);
+        let cost = calculate_repair_cost(RepairType::Hull, 50.0, &RepairBalance::default());
         assert_eq!(cost, 100); // 50 * 2.0 = 100
     }
 
     #[test]
     fn test_cost_rounds_up() {
-        let cost = calculate_repair_cost(RepairType::Sails, 1.1);
+        let cost = calculate_repair_cost(RepairType::Sails, 1.1, &RepairBalance::default());
         assert_eq!(cost, 2); // 1.1 * 1.0 = 1.1, ceil = 2
     }
+
+    fn planned(plan: &RepairAllPlan) -> Vec<(RepairType, Option<u32>)> {
+        plan.requests.iter().map(|request| (request.repair_type, request.budget)).collect()
+    }
+
+    #[test]
+    fn test_repair_all_follows_priority_and_stops_short_when_gold_runs_out() {
+        let mut health = Health::default();
+        health.hull -= 50.0; // 100g
+        health.sails -= 30.0; // 30g
+        let mut queue = DockQueue::default();
+        let prices = RepairBalance::default();
+
+        let plan = plan_repair_all(&health, &queue, 500, &prices, RepairPriority::HullFirst);
+        assert_eq!(planned(&plan), vec![(RepairType::Hull, None), (RepairType::Sails, None)]);
+        assert_eq!((plan.cost, plan.full_cost), (130, 130));
+        assert!(plan.is_complete());
+
+        // Short of gold, the hull gets what there is and the sails wait
+        let plan = plan_repair_all(&health, &queue, 41, &prices, RepairPriority::HullFirst);
+        assert_eq!(planned(&plan), vec![(RepairType::Hull, Some(40))]);
+        assert!(!plan.is_complete());
+        assert_eq!(repair_hp(RepairType::Hull, 50.0, Some(40), &prices), 20.0);
+
+        let plan = plan_repair_all(&health, &queue, 41, &prices, RepairPriority::SailsFirst);
+        assert_eq!(planned(&plan), vec![(RepairType::Sails, None), (RepairType::Hull, Some(10))]);
+
+        // The rudder comes first, then the sails ahead of the hull
+        health.rudder -= 10.0; // 15g
+        let plan = plan_repair_all(&health, &queue, 60, &prices, RepairPriority::RudderFirst);
+        assert_eq!(planned(&plan), vec![(RepairType::Rudder, None), (RepairType::Sails, None), (RepairType::Hull, Some(14))]);
+        assert_eq!((plan.cost, plan.full_cost), (59, 145));
+        health.rudder = health.rudder_max;
+
+        // Work already in hand is left out
+        queue.schedule(DockService::Repair(RepairType::Hull, 50), 5, 0);
+        let plan = plan_repair_all(&health, &queue, 500, &prices, RepairPriority::HullFirst);
+        assert_eq!(planned(&plan), vec![(RepairType::Sails, None)]);
+        assert!(plan_repair_all(&Health::default(), &queue, 500, &prices, RepairPriority::HullFirst).requests.is_empty());
+    }
+
+    #[test]
+    fn test_partial_repair_stays_within_budget() {
+        let prices = RepairBalance { rudder_cost_per_hp: 1.3, ..default() };
+        for budget in 1..200 {
+            let hp = repair_hp(RepairType::Rudder, 500.0, Some(budget), &prices);
+            assert!(calculate_repair_cost(RepairType::Rudder, hp, &prices) <= budget, "budget {budget} bought {hp} points");
+        }
+    }
+
+    #[test]
+    fn test_budgeted_request_books_only_what_it_pays_for() {
+        let mut app = App::new();
+        app.add_event::<RepairRequestEvent>()
+            .init_resource::<DockQueue>()
+            .init_resource::<WorldClock>()
+            .init_resource::<BalanceConfig>()
+            .init_resource::<CurrentPort>()
+            .init_resource::<PortEvents>()
+            .add_systems(Update, repair_execution_system);
+        let mut health = Health::default();
+        health.hull -= 50.0;
+        let ship = app.world_mut().spawn((Player, Ship, health, Gold(100))).id();
+
+        app.world_mut().send_event(RepairRequestEvent { repair_type: RepairType::Hull, emergency: false, budget: Some(41) });
+        app.update();
+
+        // 41g buys 20 points of hull at 2g a point
+        assert_eq!(app.world().get::<Gold>(ship).unwrap().0, 60);
+        let queue = app.world().resource::<DockQueue>();
+        assert!(matches!(queue.jobs.as_slice(), [job] if job.service == DockService::Repair(RepairType::Hull, 20)));
+
+        // A rushed budget pays the emergency rate and mends at once
+        app.world_mut().resource_mut::<DockQueue>().jobs.clear();
+        app.world_mut().get_mut::<Health>(ship).unwrap().sails -= 50.0;
+        app.world_mut().send_event(RepairRequestEvent { repair_type: RepairType::Sails, emergency: true, budget: Some(30) });
+        app.update();
+        let health = app.world().get::<Health>(ship).unwrap();
+        assert_eq!(health.sails_max - health.sails, 35.0);
+        assert_eq!(app.world().get::<Gold>(ship).unwrap().0, 30);
+    }
+
+    #[test]
+    fn test_repair_hours() {
+        assert_eq!(calculate_repair_hours(RepairType::Hull, 100.0), 10);
+        assert_eq!(calculate_repair_hours(RepairType::Sails, 30.0), 2); // 1.2h rounds up
+        assert_eq!(calculate_repair_hours(RepairType::Rudder, 1.0), 1);
+    }
 }
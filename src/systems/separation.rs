@@ -0,0 +1,176 @@
+//! Keeping friendly ships from stacking on the same point.
+//!
+//! bevy_landmass steers each agent along its own path, and escorts and
+//! convoys sharing a target all converge on it. On the High Seas friendly
+//! ships are eased apart directly, and escorts are kept out to their station
+//! in the formation; the player's ship is never moved, the others make room
+//! around her. In battle, allied AI ships blend a separation heading into
+//! their steering (see `combat_ai_system`) so they do not pile into one hull.
+
+use bevy::prelude::*;
+
+use crate::components::{Faction, FactionId, Order, OrderQueue, Player, PlayerOwned, Ship};
+use crate::plugins::ai_activation::Dormant;
+use crate::utils::spatial_hash::SpatialHash;
+
+/// Closest two friendly ships come on the High Seas (world units; the
+/// sprites are 48 across).
+pub const SHIP_SEPARATION: f32 = 56.0;
+/// How fast overlapping friendly ships are eased apart (world units per second).
+const SEPARATION_SPEED: f32 = 60.0;
+/// Share of its follow distance an escort keeps from the ship it escorts.
+pub const FORMATION_MIN_SHARE: f32 = 0.75;
+/// Closest allied ships come in battle before steering apart (world units).
+pub const COMBAT_SEPARATION: f32 = 110.0;
+
+/// Which side a ship sails for: the player's, or a faction's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Player,
+    Faction(FactionId),
+}
+
+impl Side {
+    /// A ship's side; `None` for a ship that flies no flag.
+    pub fn of(player_side: bool, faction: Option<&Faction>) -> Option<Self> {
+        if player_side {
+            Some(Side::Player)
+        } else {
+            faction.map(|faction| Side::Faction(faction.0))
+        }
+    }
+}
+
+/// Direction and strength (0 to 1 per neighbour) pushing a ship at `position`
+/// away from `neighbours` closer than `radius`. Ships exactly on top of each
+/// other are split by entity, so they part in opposite directions.
+pub fn separation(me: Entity, position: Vec2, neighbours: impl IntoIterator<Item = (Entity, Vec2)>, radius: f32) -> Vec2 {
+    let mut push = Vec2::ZERO;
+    for (other, other_position) in neighbours {
+        if other == me {
+            continue;
+        }
+        let offset = position - other_position;
+        let distance = offset.length();
+        if distance >= radius {
+            continue;
+        }
+        let away = if distance > 0.01 {
+            offset / distance
+        } else if me.index() < other.index() {
+            Vec2::X
+        } else {
+            -Vec2::X
+        };
+        push += away * (1.0 - distance / radius);
+    }
+    push
+}
+
+/// How far an escort at `position` must move out to keep its station at
+/// `follow_distance` from the ship at `target`; zero if it already does.
+pub fn formation_correction(position: Vec2, target: Vec2, follow_distance: f32) -> Vec2 {
+    let min_distance = follow_distance * FORMATION_MIN_SHARE;
+    let offset = position - target;
+    let distance = offset.length();
+    if distance >= min_distance || distance < 0.01 {
+        return Vec2::ZERO;
+    }
+    offset / distance * (min_distance - distance)
+}
+
+/// High Seas ships considered for separation.
+type SeparatingShips<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static mut Transform, Has<Player>, Has<PlayerOwned>, Option<&'static Faction>, Option<&'static OrderQueue>),
+    (With<Ship>, Without<Dormant>),
+>;
+
+/// Eases friendly High Seas ships apart and keeps escorts out at their
+/// station. Runs after movement, before coastline avoidance has the last word.
+pub fn friendly_separation_system(mut ships: SeparatingShips, time: Res<Time>) {
+    let snapshot: Vec<(Entity, Vec2, Option<Side>)> = ships
+        .iter()
+        .map(|(entity, transform, is_player, owned, faction, _)| {
+            (entity, transform.translation.truncate(), Side::of(is_player || owned, faction))
+        })
+        .collect();
+
+    let mut hash = SpatialHash::new(SHIP_SEPARATION);
+    for (index, (_, position, side)) in snapshot.iter().enumerate() {
+        if side.is_some() {
+            hash.insert(*position, index);
+        }
+    }
+    let position_of = |entity: Entity| {
+        snapshot.iter().find(|(other, _, _)| *other == entity).map(|(_, position, side)| (*position, *side))
+    };
+
+    let max_step = SEPARATION_SPEED * time.delta_secs();
+    for (entity, mut transform, is_player, owned, faction, orders) in &mut ships {
+        // The player's ship answers only to the helm
+        if is_player {
+            continue;
+        }
+        let Some(side) = Side::of(owned, faction) else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+
+        let friends = hash
+            .query(position, SHIP_SEPARATION)
+            .into_iter()
+            .map(|&index| snapshot[index])
+            .filter(|(_, _, other_side)| *other_side == Some(side))
+            .map(|(other, other_position, _)| (other, other_position));
+        let mut step = separation(entity, position, friends, SHIP_SEPARATION).clamp_length_max(1.0) * max_step;
+
+        if let Some(Order::Escort { target, follow_distance }) = orders.and_then(OrderQueue::current) {
+            if let Some((target_position, Some(target_side))) = position_of(*target) {
+                if target_side == side {
+                    step += formation_correction(position, target_position, *follow_distance).clamp_length_max(max_step);
+                }
+            }
+        }
+
+        if step != Vec2::ZERO {
+            transform.translation.x += step.x;
+            transform.translation.y += step.y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_separation_pushes_apart_only_when_close() {
+        let me = Entity::from_raw(1);
+        let push = separation(me, Vec2::ZERO, [(Entity::from_raw(2), Vec2::new(28.0, 0.0))], SHIP_SEPARATION);
+        assert!(push.x < 0.0 && push.y.abs() < 1e-6);
+        assert!((push.length() - 0.5).abs() < 1e-4);
+
+        let far = separation(me, Vec2::ZERO, [(Entity::from_raw(2), Vec2::new(80.0, 0.0))], SHIP_SEPARATION);
+        assert_eq!(far, Vec2::ZERO);
+        // A ship is not its own neighbour
+        assert_eq!(separation(me, Vec2::ZERO, [(me, Vec2::ZERO)], SHIP_SEPARATION), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_stacked_ships_part_in_opposite_directions() {
+        let (a, b) = (Entity::from_raw(1), Entity::from_raw(2));
+        let push_a = separation(a, Vec2::ZERO, [(b, Vec2::ZERO)], SHIP_SEPARATION);
+        let push_b = separation(b, Vec2::ZERO, [(a, Vec2::ZERO)], SHIP_SEPARATION);
+        assert!(push_a.length() > 0.9);
+        assert_eq!(push_a, -push_b);
+    }
+
+    #[test]
+    fn test_formation_correction_restores_station() {
+        let correction = formation_correction(Vec2::new(0.0, -30.0), Vec2::ZERO, 80.0);
+        assert!((correction - Vec2::new(0.0, -30.0)).length() < 1e-4);
+        assert_eq!(formation_correction(Vec2::new(0.0, -70.0), Vec2::ZERO, 80.0), Vec2::ZERO);
+    }
+}
@@ -1,17 +1,31 @@
 use bevy::prelude::*;
 use avian2d::prelude::*;
 
-use crate::components::{Ship, Player, Health, Cargo, Gold, AI, Faction, FactionId, CombatEntity};
+use crate::components::{Ship, Player, PlayerOwned, Health, Cargo, Gold, AI, Faction, FactionId, CombatEntity, ShipClass, ShipType};
+use crate::components::upgrade::{upgrades_hull_bonus, Fittings};
+use crate::resources::{EncounterShip, RunSnapshot, ShipData, ShipDefinitions};
+use crate::systems::ai::CaptainSkill;
 
 /// Spawns the player's ship with all required components.
 /// This function is designed to be called from an `OnEnter(GameState::Combat)` system.
+/// She is of the hull and class the flagship had on the High Seas, and
+/// carries her damage, hold (shot included) and purse, as recorded in
+/// `RunSnapshot` on leaving them.
 pub fn spawn_player_ship(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    snapshot: Res<RunSnapshot>,
+    ships: Res<ShipDefinitions>,
 ) {
     println!("Spawning player ship at (0, 0)...");
     
-    let texture_handle: Handle<Image> = asset_server.load("sprites/ships/player.png");
+    let flagship = snapshot.flagship.as_ref();
+    let ship_type = flagship.map_or_else(ShipType::default, |flagship| flagship.ship_type);
+    let class = flagship
+        .map(|flagship| flagship.class.clone())
+        .filter(|class| !class.is_empty())
+        .unwrap_or_else(|| ship_type.class_id().to_string());
+    let texture_handle: Handle<Image> = asset_server.load(&ships.definition(&class, ship_type).sprite);
     
     // Spawn in groups to avoid Bevy's tuple size limit (15 elements max)
     commands.spawn((
@@ -19,10 +33,12 @@ pub fn spawn_player_ship(
         // Marker components
         Ship,
         Player,
+        ship_type,
+        ShipClass(class),
         // Data components
-        Health::default(),
-        Cargo::new(100),
-        Gold(100),
+        flagship.map_or_else(Health::default, |flagship| flagship.health.clone()),
+        flagship.map_or_else(|| Cargo::armed(100), |flagship| flagship.cargo.clone()),
+        Gold(flagship.map_or(100, |flagship| flagship.gold)),
         // Visual components
         // Kenney sprites face DOWN (Y-), so we flip vertically to align with physics forward (Y+)
         Sprite {
@@ -35,46 +51,48 @@ pub fn spawn_player_ship(
         Transform::from_xyz(0.0, 0.0, 1.0),
         CombatEntity,
     ))
-    .insert((
+    .insert(ship_physics());
+    
+    println!("Player ship spawned!");
+}
+
+/// The physics every ship in battle sails with, player or AI: a dynamic
+/// body of one metric ton, moved by the forces her movement or AI system
+/// applies.
+pub fn ship_physics() -> impl Bundle {
+    (
         // Physics rigid body
         RigidBody::Dynamic,
         Collider::rectangle(48.0, 64.0),
         // Explicit mass properties
         Mass(1000.0), // 1 metric ton
         AngularInertia(20000.0), // Higher inertia makes it harder to spin/stop spinning
-    ))
-    .insert((
         // Physics velocities
-        LinearVelocity(Vec2::ZERO),
-        AngularVelocity(0.0),
-    ))
-    .insert((
-        // External forces (controlled by movement system)
-        ExternalForce::default(),
-        ExternalTorque::default(),
-    ))
-    .insert((
+        (LinearVelocity(Vec2::ZERO), AngularVelocity(0.0)),
+        // External forces (controlled by the movement and AI systems)
+        (ExternalForce::default(), ExternalTorque::default()),
         // Water resistance (damping)
-        // LinearDamping is set to 0.0 because we handle directional drag 
+        // LinearDamping is set to 0.0 because we handle directional drag
         // manually in ship_physics_system to simulate the keel effect.
-        LinearDamping(0.0),
-        AngularDamping(2.5),
-    ));
-    
-    println!("Player ship spawned!");
+        (LinearDamping(0.0), AngularDamping(2.5)),
+    )
 }
 
-/// Spawns an AI-controlled enemy ship at the given position.
-/// Returns the Entity ID of the spawned ship.
+/// Spawns an AI-controlled enemy ship at the given position, built and
+/// captained as `spec` (an `EncounterTemplates` entry) describes, of her
+/// class in `ships`. Returns the Entity ID of the spawned ship.
 pub fn spawn_enemy_ship(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     position: Vec2,
     faction: FactionId,
+    spec: &EncounterShip,
+    ships: &ShipDefinitions,
 ) -> Entity {
-    info!("Spawning enemy ship at ({}, {})...", position.x, position.y);
+    let class = ships.definition(spec.class_id(), spec.ship_type);
+    info!("Spawning enemy {} at ({}, {})...", class.name, position.x, position.y);
     
-    let texture_handle: Handle<Image> = asset_server.load("sprites/ships/enemy.png");
+    let texture_handle: Handle<Image> = asset_server.load(class.enemy_sprite());
     
     commands.spawn((
         Name::new("Enemy Ship"),
@@ -82,8 +100,11 @@ pub fn spawn_enemy_ship(
         Ship,
         AI,
         Faction(faction),
+        class.hull,
+        ShipClass(class.id.clone()),
         // Data components
-        Health::default(),
+        Health::new(100.0, 100.0, class.hull_strength).scaled(spec.health_multiplier),
+        CaptainSkill(spec.captain_skill),
         // Visual components
         Sprite {
             image: texture_handle,
@@ -94,28 +115,51 @@ pub fn spawn_enemy_ship(
         Transform::from_xyz(position.x, position.y, 1.0),
         CombatEntity,
     ))
-    .insert((
-        // Physics rigid body
-        RigidBody::Dynamic,
-        Collider::rectangle(48.0, 64.0),
-        // Explicit mass properties - same as player
-        Mass(1000.0),
-        AngularInertia(20000.0),
-    ))
-    .insert((
-        // Physics velocities
-        LinearVelocity(Vec2::ZERO),
-        AngularVelocity(0.0),
-    ))
-    .insert((
-        // External forces (controlled by AI system)
-        ExternalForce::default(),
-        ExternalTorque::default(),
-    ))
-    .insert((
-        // Water resistance (damping)
-        LinearDamping(0.0),
-        AngularDamping(2.5),
-    ))
+    .insert(ship_physics())
     .id()
 }
+
+/// Spawns one of the player's fleet ships into battle as an AI consort,
+/// from her `ShipData`. Returns the Entity ID of the spawned ship.
+pub fn spawn_fleet_ship(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    position: Vec2,
+    ship_data: &ShipData,
+) -> Entity {
+    info!("Spawning fleet ship {} at ({}, {})...", ship_data.name, position.x, position.y);
+
+    let texture_handle: Handle<Image> = asset_server.load(&ship_data.sprite_path);
+    let hull_bonus = upgrades_hull_bonus(&ship_data.upgrades);
+
+    let mut ship = commands.spawn((
+        Name::new(ship_data.name.clone()),
+        // Marker components
+        Ship,
+        AI,
+        PlayerOwned,
+        ship_data.ship_type,
+        ShipClass(ship_data.class.clone()),
+        // Data components
+        Health {
+            hull: ship_data.hull_health + hull_bonus,
+            hull_max: ship_data.max_hull_health + hull_bonus,
+            ..default()
+        },
+        Fittings::of(&ship_data.upgrades),
+        // Visual components
+        Sprite {
+            image: texture_handle,
+            custom_size: Some(Vec2::splat(64.0)),
+            flip_y: true,  // Kenney sprites face DOWN (Y-)
+            ..default()
+        },
+        Transform::from_xyz(position.x, position.y, 1.0),
+        CombatEntity,
+    ));
+    ship.insert(ship_physics());
+    if let Some(cargo) = &ship_data.cargo {
+        ship.insert(cargo.clone());
+    }
+    ship.id()
+}
@@ -0,0 +1,222 @@
+//! Detecting ships wedged against the coast and working them free.
+//!
+//! Coastline avoidance keeps most ships off the land, but now and then one
+//! still pins itself in a bay corner or against a spit and sits there with
+//! its target unreached. Every ship sailing for a `Destination` is watched:
+//! if it has made less than `STUCK_DISPLACEMENT` of way over `STUCK_WINDOW`
+//! seconds it is stuck, and recovery escalates with each window it stays
+//! that way: first a nudge out along the nearest coastline's normal, then a
+//! fresh path request, and as a last resort a teleport to the nearest open
+//! water, which is logged as a warning. `StuckStats` counts each for the
+//! debug panel.
+
+use bevy::prelude::*;
+use bevy_landmass::prelude::*;
+
+use crate::components::{Destination, Ship};
+use crate::plugins::ai_activation::Dormant;
+use crate::plugins::worldmap::CoastlineData;
+use crate::resources::MapData;
+use crate::systems::find_nearest_coastline_edge;
+use crate::utils::pathfinding::{tile_to_world, world_to_tile, TILE_SIZE};
+
+/// Seconds over which a ship's progress is measured.
+pub const STUCK_WINDOW: f32 = 3.0;
+/// Least way a ship must make over a window not to count as stuck (world units).
+pub const STUCK_DISPLACEMENT: f32 = 24.0;
+/// How far a stuck ship is nudged off the coast (world units).
+const NUDGE_DISTANCE: f32 = 40.0;
+/// Tiles searched for open water when teleporting a ship.
+const TELEPORT_SEARCH_RADIUS: i32 = 8;
+
+/// What is tried on a stuck ship, in order of escalation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recovery {
+    /// Push it out along the nearest coastline's normal.
+    Nudge,
+    /// Ask for a new path to its destination.
+    Repath,
+    /// Move it bodily to the nearest open water.
+    Teleport,
+}
+
+impl Recovery {
+    /// The recovery tried after `attempts` earlier ones have failed.
+    pub fn for_attempt(attempts: u32) -> Self {
+        match attempts {
+            0 => Recovery::Nudge,
+            1 => Recovery::Repath,
+            _ => Recovery::Teleport,
+        }
+    }
+}
+
+/// How much way a ship has made over the current window, and how many
+/// recoveries have been tried on it since it last moved freely.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct StuckWatch {
+    anchor: Vec2,
+    elapsed: f32,
+    pub attempts: u32,
+}
+
+impl StuckWatch {
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            anchor: position,
+            elapsed: 0.0,
+            attempts: 0,
+        }
+    }
+
+    /// Starts watching afresh from `position`, forgetting any failed recoveries.
+    pub fn reset(&mut self, position: Vec2) {
+        *self = Self::new(position);
+    }
+
+    /// Records `delta_secs` more of sailing. At the end of each window, returns
+    /// the recovery to try if the ship has made too little way; a teleport
+    /// starts the escalation over.
+    pub fn observe(&mut self, position: Vec2, delta_secs: f32) -> Option<Recovery> {
+        self.elapsed += delta_secs;
+        if self.elapsed < STUCK_WINDOW {
+            return None;
+        }
+        let moved = position.distance(self.anchor);
+        self.anchor = position;
+        self.elapsed = 0.0;
+        if moved >= STUCK_DISPLACEMENT {
+            self.attempts = 0;
+            return None;
+        }
+
+        let recovery = Recovery::for_attempt(self.attempts);
+        self.attempts = if recovery == Recovery::Teleport { 0 } else { self.attempts + 1 };
+        Some(recovery)
+    }
+}
+
+/// Recoveries made this session, for debugging.
+#[derive(Resource, Debug, Default)]
+pub struct StuckStats {
+    pub nudges: u32,
+    pub repaths: u32,
+    pub teleports: u32,
+}
+
+type WatchedShips<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Transform,
+        Option<&'static mut StuckWatch>,
+        Option<&'static mut Destination>,
+        Option<&'static Name>,
+    ),
+    (With<Ship>, Without<Dormant>),
+>;
+
+/// Watches every ship sailing for a destination and recovers the stuck ones.
+pub fn stuck_recovery_system(
+    mut commands: Commands,
+    mut ships: WatchedShips,
+    coastline_data: Res<CoastlineData>,
+    map_data: Res<MapData>,
+    mut stats: ResMut<StuckStats>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_secs();
+
+    for (entity, mut transform, watch, destination, name) in &mut ships {
+        let position = transform.translation.truncate();
+        let Some(mut watch) = watch else {
+            commands.entity(entity).insert(StuckWatch::new(position));
+            continue;
+        };
+        let Some(mut destination) = destination else {
+            if watch.attempts > 0 || watch.anchor != position {
+                watch.reset(position);
+            }
+            continue;
+        };
+        let Some(recovery) = watch.observe(position, delta_secs) else {
+            continue;
+        };
+
+        let coast = find_nearest_coastline_edge(position, &coastline_data.polygons);
+        match recovery {
+            Recovery::Nudge => {
+                let Some((_, normal)) = coast else {
+                    continue;
+                };
+                let nudge = normal * NUDGE_DISTANCE;
+                transform.translation.x += nudge.x;
+                transform.translation.y += nudge.y;
+                stats.nudges += 1;
+                debug!("Nudged stuck ship {:?} off the coast by {:?}", entity, nudge);
+            }
+            Recovery::Repath => {
+                // The target is re-inserted from the Destination next frame
+                // (`sync_destination_to_agent_target`), and landmass plans afresh
+                commands.entity(entity).remove::<AgentTarget2d>();
+                destination.set_changed();
+                stats.repaths += 1;
+                debug!("Re-requested a path for stuck ship {:?}", entity);
+            }
+            Recovery::Teleport => {
+                let away = coast.map_or(Vec2::ZERO, |(_, normal)| normal * TILE_SIZE);
+                let tile = world_to_tile(position + away, map_data.width, map_data.height);
+                let Some(water) = map_data.nearest_navigable(tile, TELEPORT_SEARCH_RADIUS) else {
+                    warn!("Ship {:?} is stuck with no open water within reach", entity);
+                    continue;
+                };
+                let target = tile_to_world(water, map_data.width, map_data.height);
+                transform.translation.x = target.x;
+                transform.translation.y = target.y;
+                watch.reset(target);
+                destination.set_changed();
+                stats.teleports += 1;
+                warn!(
+                    "Teleported stuck ship {} from ({:.0}, {:.0}) to ({:.0}, {:.0})",
+                    name.map_or_else(|| format!("{:?}", entity), |name| name.to_string()),
+                    position.x,
+                    position.y,
+                    target.x,
+                    target.y
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_escalates_while_stuck() {
+        let mut watch = StuckWatch::new(Vec2::ZERO);
+        let mut recoveries = Vec::new();
+        for _ in 0..40 {
+            recoveries.extend(watch.observe(Vec2::new(1.0, 0.0), 0.25));
+        }
+        assert_eq!(
+            recoveries,
+            vec![Recovery::Nudge, Recovery::Repath, Recovery::Teleport]
+        );
+        assert_eq!(watch.attempts, 0);
+    }
+
+    #[test]
+    fn test_making_way_clears_attempts() {
+        let mut watch = StuckWatch::new(Vec2::ZERO);
+        assert_eq!(watch.observe(Vec2::ZERO, STUCK_WINDOW), Some(Recovery::Nudge));
+        assert_eq!(watch.attempts, 1);
+
+        // Under way again: a full window of progress is not stuck, and forgives the nudge
+        assert_eq!(watch.observe(Vec2::new(STUCK_DISPLACEMENT * 2.0, 0.0), STUCK_WINDOW), None);
+        assert_eq!(watch.attempts, 0);
+        assert_eq!(watch.observe(Vec2::new(STUCK_DISPLACEMENT * 2.0, 0.0), STUCK_WINDOW), Some(Recovery::Nudge));
+    }
+}
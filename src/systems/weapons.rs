@@ -0,0 +1,287 @@
+//! Secondary armament: bow and stern chasers, and the flagship's mortar.
+//!
+//! Chasers fire a single long-range round fore or aft, where the broadside
+//! cannot bear, on a slow reload. The player fires whichever chaser bears on
+//! the mouse cursor; enemy ships use them on the chase and, above all, when
+//! running away. A mortar (a shipyard upgrade) throws a shell in a high arc to
+//! the cursor: it flies over anything in between and bursts where it falls.
+
+use bevy::prelude::*;
+use avian2d::prelude::*;
+
+use crate::components::fort::{Fort, Silenced, FORT_RADIUS};
+use crate::components::upgrade::has_mortar;
+use crate::components::*;
+use crate::resources::UpgradeInventory;
+use crate::systems::ai::{battle_sides, foe_in_sight, side_of, AIPhysicsConfig, AIState, Combatants};
+use crate::systems::separation::Side;
+use crate::systems::combat::ProjectileTimer;
+use crate::systems::movement::ShipInputBuffer;
+
+/// Muzzle speed of a chaser's long gun; faster than a broadside's shot.
+const CHASER_SHOT_SPEED: f32 = 520.0;
+/// Distance from a ship's centre to the muzzle of a chaser (world units).
+const CHASER_MUZZLE_OFFSET: f32 = 44.0;
+/// Mortar shells are dark iron.
+const MORTAR_SHELL_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
+
+/// Gives every ship in a battle her chasers, her freeboard and an even keel,
+/// and the flagship her mortar if one is fitted.
+pub fn arm_combat_ships(
+    mut commands: Commands,
+    upgrades: Res<UpgradeInventory>,
+    ships: Query<(Entity, Has<Player>, Option<&ShipType>), (Added<Ship>, With<CombatEntity>)>,
+) {
+    for (entity, is_player, ship_type) in &ships {
+        let freeboard = ship_type.copied().map_or_else(Freeboard::default, Freeboard::for_ship_type);
+        commands.entity(entity).insert((Chasers::default(), freeboard, Heel::default()));
+        if is_player && has_mortar(&upgrades.flagship) {
+            commands.entity(entity).insert(Mortar::default());
+        }
+    }
+}
+
+/// Spawns a chaser's round shot.
+fn spawn_chaser_shot(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    source: Entity,
+    transform: &Transform,
+    ship_velocity: Vec2,
+    mount: ChaserMount,
+    direction: Vec2,
+) {
+    let forward = (transform.rotation * Vec3::Y).truncate();
+    let end = if mount == ChaserMount::Bow { forward } else { -forward };
+    let spawn_pos = transform.translation + (end * CHASER_MUZZLE_OFFSET).extend(5.0);
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("sprites/projectile.png"),
+            custom_size: Some(Vec2::new(16.0, 16.0)),
+            ..default()
+        },
+        Transform::from_translation(spawn_pos),
+        RigidBody::Dynamic,
+        Collider::circle(8.0),
+        Sensor,
+        LinearVelocity(ship_velocity + direction * CHASER_SHOT_SPEED),
+        Projectile::new(AmmoType::RoundShot, source),
+        ShotHeight::default(),
+        ProjectileTimer::default(),
+        CombatEntity,
+    ));
+}
+
+/// Fires the player's chaser that bears on the mouse cursor.
+pub fn chaser_firing_system(
+    mut commands: Commands,
+    input_buffer: Res<ShipInputBuffer>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(Entity, &Transform, &LinearVelocity, &mut Chasers), (With<Ship>, With<Player>)>,
+    mut cannon_fired_events: EventWriter<crate::events::CannonFiredEvent>,
+) {
+    let Ok((entity, transform, velocity, mut chasers)) = query.get_single_mut() else {
+        return;
+    };
+    chasers.reload.tick(time.delta());
+    if !input_buffer.fire_chaser {
+        return;
+    }
+    if !chasers.reload.finished() {
+        info!("Chasers still reloading");
+        return;
+    }
+
+    let position = transform.translation.truncate();
+    let forward = (transform.rotation * Vec3::Y).truncate();
+    let to_target = input_buffer.mouse_world_pos - position;
+    let Some(mount) = ChaserMount::bearing(forward, to_target) else {
+        info!("No chaser bears on that mark - bring her bow or stern round");
+        return;
+    };
+
+    spawn_chaser_shot(&mut commands, &asset_server, entity, transform, velocity.0, mount, to_target.normalize_or_zero());
+    chasers.reload.reset();
+    cannon_fired_events.send(crate::events::CannonFiredEvent { position, side: 0.0 });
+    info!("{} fired!", mount.name());
+}
+
+/// AI ships fire their chasers whenever the foe they see comes into a chaser's arc:
+/// the bow chaser while closing, and the stern chaser to keep a pursuer off.
+pub fn ai_chaser_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<AIPhysicsConfig>,
+    asset_server: Res<AssetServer>,
+    combatants: Combatants,
+    mut ai_query: Query<(Entity, &Transform, &LinearVelocity, &AIState, &mut Chasers, Option<&Lookout>), (With<Ship>, With<AI>, Without<Surrendered>)>,
+) {
+    let sides = battle_sides(&combatants);
+
+    for (entity, transform, velocity, ai_state, mut chasers, lookout) in &mut ai_query {
+        chasers.reload.tick(time.delta());
+        if !chasers.reload.finished() {
+            continue;
+        }
+
+        let side = side_of(entity, &sides);
+        let Some(foe_pos) = foe_in_sight(lookout, entity, transform.translation.truncate(), &sides) else {
+            continue;
+        };
+        let to_foe = foe_pos - transform.translation.truncate();
+        let distance = to_foe.length();
+        // Within broadside range a circling ship would rather use her broadside
+        let in_range = match ai_state {
+            AIState::Fleeing => distance <= CHASER_RANGE,
+            AIState::Circling => distance > config.firing_range && distance <= CHASER_RANGE,
+        };
+        if !in_range {
+            continue;
+        }
+
+        let forward = (transform.rotation * Vec3::Y).truncate();
+        let Some(mount) = ChaserMount::bearing(forward, to_foe) else {
+            continue;
+        };
+        spawn_chaser_shot(&mut commands, &asset_server, entity, transform, velocity.0, mount, to_foe / distance);
+        chasers.reload.reset();
+        info!(
+            "{} fired her {}!",
+            if side == Some(Side::Player) { "Consort" } else { "Enemy" },
+            mount.name().to_lowercase()
+        );
+    }
+}
+
+/// Throws a mortar shell at the mouse cursor, within the mortar's range.
+pub fn mortar_firing_system(
+    mut commands: Commands,
+    input_buffer: Res<ShipInputBuffer>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(Entity, &Transform, &mut Mortar), (With<Ship>, With<Player>)>,
+    mut cannon_fired_events: EventWriter<crate::events::CannonFiredEvent>,
+) {
+    let Ok((entity, transform, mut mortar)) = query.get_single_mut() else {
+        return;
+    };
+    mortar.reload.tick(time.delta());
+    if !input_buffer.fire_mortar {
+        return;
+    }
+    if !mortar.reload.finished() {
+        info!("Mortar still reloading");
+        return;
+    }
+
+    let position = transform.translation.truncate();
+    let to_target = input_buffer.mouse_world_pos - position;
+    if to_target.length() < MORTAR_MIN_RANGE {
+        info!("Too close for the mortar");
+        return;
+    }
+    let target = position + to_target.clamp_length_max(MORTAR_MAX_RANGE);
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("sprites/projectile.png"),
+            custom_size: Some(Vec2::new(20.0, 20.0)),
+            color: MORTAR_SHELL_COLOR,
+            ..default()
+        },
+        Transform::from_translation(position.extend(6.0)),
+        MortarShell::new(entity, position, target),
+        ShotHeight { height: 0.0, climb: 0.0 },
+        CombatEntity,
+    ));
+    mortar.reload.reset();
+    cannon_fired_events.send(crate::events::CannonFiredEvent { position, side: 0.0 });
+    info!("Mortar fired at ({:.0}, {:.0})", target.x, target.y);
+}
+
+/// Carries mortar shells along their arc, and bursts them where they fall,
+/// holing every ship not on the firer's side and battering every fort within
+/// the splash.
+pub fn mortar_shell_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut shells: Query<(Entity, &mut MortarShell, &mut ShotHeight, &mut Transform), (Without<Ship>, Without<Fort>)>,
+    mut ships: Query<(Entity, &Transform, &mut Health, Has<Fire>, Has<Player>, Has<PlayerOwned>, Option<&Faction>), With<Ship>>,
+    mut forts: Query<(&Fort, &Transform, &mut Health), (Without<Silenced>, Without<Ship>)>,
+    mut ship_hit_events: EventWriter<crate::events::ShipHitEvent>,
+) {
+    for (shell_entity, mut shell, mut shot, mut transform) in &mut shells {
+        shell.elapsed += time.delta_secs();
+        shot.height = shell.height();
+        transform.translation = shell.ground_position().extend(transform.translation.z);
+
+        if !shell.has_fallen() {
+            continue;
+        }
+        let firer = ships
+            .get(shell.source)
+            .ok()
+            .and_then(|(.., player, owned, faction)| Side::of(player || owned, faction));
+        for (ship_entity, ship_transform, mut health, burning, player, owned, faction) in &mut ships {
+            if ship_transform.translation.truncate().distance(shell.to) > MORTAR_SPLASH_RADIUS {
+                continue;
+            }
+            // The burst spares her own side
+            if firer.is_some() && Side::of(player || owned, faction) == firer {
+                continue;
+            }
+            health.hull -= MORTAR_DAMAGE;
+            ship_hit_events.send(crate::events::ShipHitEvent {
+                ship_entity,
+                hit_position: shell.to,
+                damage: MORTAR_DAMAGE,
+                attacker: shell.source,
+                component: TargetComponent::Hull,
+            });
+            info!("Mortar shell bursts on deck!");
+            if !burning && rand::random::<f32>() < MORTAR_FIRE_CHANCE {
+                commands.entity(ship_entity).try_insert(Fire::default());
+                info!("The burst sets her deck afire!");
+            }
+        }
+        for (fort, fort_transform, mut health) in &mut forts {
+            // Forts are broad; a burst anywhere on the walls tells
+            if fort_transform.translation.truncate().distance(shell.to) > MORTAR_SPLASH_RADIUS + FORT_RADIUS {
+                continue;
+            }
+            health.hull -= MORTAR_DAMAGE;
+            info!("Mortar shell bursts on {}! Walls at {:.1}", fort.name, health.hull);
+        }
+        commands.entity(shell_entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mortar_bursts_spare_the_firers_side() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_event::<crate::events::ShipHitEvent>()
+            .add_systems(Update, mortar_shell_system);
+        let ship = |app: &mut App, x: f32| app.world_mut().spawn((Ship, Health::default(), Transform::from_xyz(x, 0.0, 1.0))).id();
+        let flagship = ship(&mut app, -400.0);
+        app.world_mut().entity_mut(flagship).insert(Player);
+        let consort = ship(&mut app, 0.0);
+        app.world_mut().entity_mut(consort).insert(PlayerOwned);
+        let enemy = ship(&mut app, 10.0);
+        app.world_mut().entity_mut(enemy).insert(Faction(FactionId::NationA));
+
+        let mut shell = MortarShell::new(flagship, Vec2::new(-400.0, 0.0), Vec2::ZERO);
+        shell.elapsed = shell.flight_time;
+        app.world_mut().spawn((shell, ShotHeight::default(), Transform::default()));
+        app.update();
+
+        let hull = |app: &App, entity| app.world().get::<Health>(entity).unwrap().hull;
+        assert_eq!(hull(&app, consort), Health::default().hull);
+        assert_eq!(hull(&app, enemy), Health::default().hull - MORTAR_DAMAGE);
+    }
+}
@@ -1,12 +1,14 @@
 use bevy::prelude::*;
-use crate::resources::Wind;
+use crate::resources::{MapData, Wind, WindShadow, WorldClock};
 
 /// System that simulates gradual wind changes over time.
 /// 
 /// Wind direction and strength slowly oscillate to create a dynamic weather feel.
+/// The season freshens or slackens the strength.
 pub fn wind_system(
     mut wind: ResMut<Wind>,
     time: Res<Time>,
+    world_clock: Res<WorldClock>,
 ) {
     let elapsed = time.elapsed_secs();
     
@@ -18,5 +20,15 @@ pub fn wind_system(
     // Oscillate wind strength between 0.3 and 0.8
     let strength_base = 0.55;
     let strength_variation = (elapsed * 0.05).sin() * 0.25;
-    wind.strength = (strength_base + strength_variation).clamp(0.2, 0.9);
+    let seasonal = world_clock.season().wind_factor();
+    wind.strength = ((strength_base + strength_variation) * seasonal).clamp(0.2, 1.0);
+}
+
+/// Casts the wind shadows of the islands afresh for a new map, or once the
+/// wind has shifted far enough to move them.
+pub fn wind_shadow_system(mut shadow: ResMut<WindShadow>, wind: Res<Wind>, map_data: Res<MapData>) {
+    let direction = wind.direction_vec();
+    if map_data.is_changed() || shadow.is_stale(direction) {
+        *shadow = WindShadow::cast(&map_data, direction);
+    }
 }
@@ -1,85 +1,173 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
-use crate::resources::{FogOfWar, MapData};
+use crate::resources::{clock_darkness, night_vision_factor, FogOfWar, MapData, WorldClock};
 use crate::components::{Player, Vision};
+use crate::events::FogRevealedEvent;
+use crate::plugins::worldmap::FogMap;
+use crate::utils::frame_budget::{FrameBudget, SlicedJob};
+use crate::utils::pathfinding::world_to_tile;
 
 /// Marker component for tiles in the fog layer.
 #[derive(Component)]
 pub struct FogTile;
 
+/// Work the fog reveal may do per frame, in rows of a vision disc.
+pub const FOG_REVEAL_BUDGET: FrameBudget = FrameBudget::new(Duration::from_micros(300), 64);
+
+/// Resumable reveal of the vision discs around each viewer.
+///
+/// A disc is only rescanned when a viewer changes tile or vision radius, and
+/// the scan is spread across frames row by row within `FOG_REVEAL_BUDGET`.
+#[derive(Default)]
+pub struct FogReveal {
+    job: SlicedJob,
+    /// Tile and radius of each viewer the current scan covers.
+    viewers: Vec<(IVec2, i32)>,
+    /// Whether the current scan still has rows left.
+    pending: bool,
+}
+
+impl FogReveal {
+    /// Restarts the scan if the viewers moved; otherwise keeps working through it.
+    pub fn watch(&mut self, viewers: Vec<(IVec2, i32)>) {
+        if viewers != self.viewers {
+            self.viewers = viewers;
+            self.job.restart();
+            self.pending = true;
+        }
+    }
+
+    /// Reveals the next budgeted slice of rows.
+    pub fn reveal(&mut self, fog_of_war: &mut FogOfWar, map_size: IVec2, budget: FrameBudget) {
+        if !self.pending {
+            return;
+        }
+        let Self { job, viewers, pending } = self;
+        let rows = viewers.iter().map(|(_, radius)| disc_height(*radius)).sum();
+        let finished = job.run(rows, budget, |row| {
+            let Some((center, radius, dy)) = scan_row(viewers, row) else {
+                return;
+            };
+            let row = (-radius..=radius)
+                // Circular radius check
+                .filter(|dx| dx * dx + dy * dy <= radius * radius)
+                .map(|dx| center + IVec2::new(dx, dy))
+                .filter(|tile| tile.x >= 0 && tile.x < map_size.x && tile.y >= 0 && tile.y < map_size.y);
+            fog_of_war.reveal_tiles(row);
+        });
+        *pending = !finished;
+    }
+}
+
+/// Rows in the vision disc of `radius`.
+fn disc_height(radius: i32) -> usize {
+    (2 * radius + 1).max(0) as usize
+}
+
+/// Viewer tile, radius and row offset of scan row `row` across all viewers' discs.
+fn scan_row(viewers: &[(IVec2, i32)], mut row: usize) -> Option<(IVec2, i32, i32)> {
+    for &(tile, radius) in viewers {
+        let height = disc_height(radius);
+        if row < height {
+            return Some((tile, radius, row as i32 - radius));
+        }
+        row -= height;
+    }
+    None
+}
+
 /// System that updates the `FogOfWar` resource based on entities with `Vision`.
 /// Lookout companion provides +50% vision radius bonus.
 pub fn fog_of_war_update_system(
+    mut reveal: Local<FogReveal>,
     mut fog_of_war: ResMut<FogOfWar>,
     query: Query<(&Transform, &Vision), With<Player>>,
     companion_query: Query<&crate::components::companion::CompanionRole>,
     map_data: Res<MapData>,
+    world_clock: Res<WorldClock>,
 ) {
     // Check if player has a Lookout companion (provides +50% vision radius bonus)
     let has_lookout = companion_query.iter().any(|role| *role == crate::components::companion::CompanionRole::Lookout);
     let lookout_bonus = if has_lookout { 1.5 } else { 1.0 };
+    // The lookout sees less far in the dark
+    let night_factor = night_vision_factor(clock_darkness(&world_clock));
 
-    let tile_size = 64.0;
-    let map_width = map_data.width as f32;
-    let map_height = map_data.height as f32;
-
-    for (transform, vision) in &query {
-        let pos = transform.translation.truncate();
-        
-        // Convert world position to tile coordinates
-        // World (0,0) is map center.
-        let tile_x = (pos.x / tile_size + map_width / 2.0).floor() as i32;
-        let tile_y = (pos.y / tile_size + map_height / 2.0).floor() as i32;
-        
-        // Apply Lookout bonus to vision radius
-        let radius = (vision.radius * lookout_bonus) as i32;
-        
-        // Reveal tiles within radius
-        for dy in -radius..=radius {
-            for dx in -radius..=radius {
-                // Circular radius check
-                if dx * dx + dy * dy <= radius * radius {
-                    let tx = tile_x + dx;
-                    let ty = tile_y + dy;
-                    
-                    if tx >= 0 && tx < map_data.width as i32 && ty >= 0 && ty < map_data.height as i32 {
-                        fog_of_war.explore(IVec2::new(tx, ty));
-                    }
-                }
-            }
-        }
+    let viewers = query
+        .iter()
+        .map(|(transform, vision)| {
+            let tile = world_to_tile(transform.translation.truncate(), map_data.width, map_data.height);
+            // Apply Lookout bonus and the night to vision radius
+            (tile, (vision.radius * lookout_bonus * night_factor) as i32)
+        })
+        .collect();
+
+    // A wiped fog (new game) needs the discs rescanned even if nobody moved
+    if fog_of_war.explored_count() == 0 {
+        reveal.viewers.clear();
+    }
+    reveal.watch(viewers);
+    // Only touch the resource when there is work, so change detection stays quiet
+    if reveal.pending {
+        let map_size = IVec2::new(map_data.width as i32, map_data.height as i32);
+        reveal.reveal(&mut fog_of_war, map_size, FOG_REVEAL_BUDGET);
     }
 }
 
-/// System that updates the visual representation of fog tiles.
-/// Only updates tiles that were newly explored (not all 262k tiles).
-pub fn update_fog_tilemap_system(
-    mut fog_of_war: ResMut<FogOfWar>,
-    mut tile_query: Query<(&TilePos, &mut TileColor), With<FogTile>>,
-    _player_query: Query<&Transform, With<Player>>,
-    _map_data: Res<MapData>,
-) {
-    // Only process if there are newly explored tiles
+/// Publishes the tiles charted since the last frame as one `FogRevealedEvent`.
+pub fn publish_fog_reveals(mut fog_of_war: ResMut<FogOfWar>, mut revealed: EventWriter<FogRevealedEvent>) {
+    // Only touch the resource when there is news, so change detection stays quiet
     if !fog_of_war.has_newly_explored() {
         return;
     }
+    revealed.send(FogRevealedEvent { tiles: fog_of_war.take_newly_explored() });
+}
 
-    // Take the list of newly explored tiles (clears the list)
-    let newly_explored = fog_of_war.take_newly_explored();
-    
-    // Build a set of newly explored positions for O(1) lookup
-    let newly_explored_set: bevy::utils::HashSet<IVec2> = newly_explored.into_iter().collect();
-    
-    // Only iterate tiles and update those that were newly explored
-    // This is still O(n) for tiles but the early continue makes it much faster
-    // A better approach would be to store Entity references, but this is simpler
-    for (pos, mut color) in &mut tile_query {
-        let tile_pos = IVec2::new(pos.x as i32, pos.y as i32);
-        
-        if newly_explored_set.contains(&tile_pos) {
-            // Set alpha to 0 for explored tiles (make transparent)
+/// System that updates the visual representation of fog tiles.
+/// Only touches the tiles newly explored, looked up in the fog layer's storage.
+pub fn update_fog_tilemap_system(
+    mut revealed: EventReader<FogRevealedEvent>,
+    fog_map_query: Query<&TileStorage, With<FogMap>>,
+    mut tile_query: Query<&mut TileColor, With<FogTile>>,
+) {
+    let Ok(storage) = fog_map_query.get_single() else {
+        revealed.clear();
+        return;
+    };
+    for tile in revealed.read().flat_map(|event| event.tiles.iter()) {
+        let Ok(tile_pos) = UVec2::try_from(*tile).map(TilePos::from) else {
+            continue;
+        };
+        if let Some(mut color) = storage.checked_get(&tile_pos).and_then(|entity| tile_query.get_mut(entity).ok()) {
+            // Explored tiles are fully transparent
             color.0.set_alpha(0.0);
         }
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fog_reveal_spreads_across_frames() {
+        let mut fog = FogOfWar::default();
+        let mut reveal = FogReveal::default();
+        let map_size = IVec2::new(100, 100);
+
+        reveal.watch(vec![(IVec2::new(50, 50), 2)]);
+        reveal.reveal(&mut fog, map_size, FrameBudget::items(3));
+        assert!(fog.is_explored(IVec2::new(50, 49)));
+        assert!(!fog.is_explored(IVec2::new(50, 52)));
+
+        reveal.reveal(&mut fog, map_size, FrameBudget::items(3));
+        // Disc of radius 2 holds 13 tiles
+        assert_eq!(fog.explored_count(), 13);
+
+        // Standing still does not rescan
+        reveal.watch(vec![(IVec2::new(50, 50), 2)]);
+        assert!(!reveal.pending);
+    }
+}
@@ -0,0 +1,179 @@
+//! Per-glyph text placement along a polyline.
+//!
+//! Bevy lays out `Text2d` on a straight baseline, so curved chart lettering is
+//! built from one text entity per character, each positioned and rotated to the
+//! path tangent at its arc length.
+
+use bevy::prelude::*;
+
+/// Where to draw one character of a curved label.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphPlacement {
+    pub ch: char,
+    /// Centre of the glyph in the path's coordinate space.
+    pub position: Vec2,
+    /// Rotation in radians of the glyph baseline.
+    pub rotation: f32,
+}
+
+/// Approximate advance width of a character, as a fraction of font size.
+/// Close enough for serif chart fonts; exact metrics are not exposed by `Text2d`.
+pub fn glyph_advance(ch: char, font_size: f32) -> f32 {
+    let em = match ch {
+        ' ' => 0.3,
+        'i' | 'l' | 'j' | 't' | 'f' | 'I' | '\'' | '.' | ',' => 0.3,
+        'm' | 'w' | 'M' | 'W' => 0.85,
+        c if c.is_uppercase() => 0.68,
+        _ => 0.52,
+    };
+    em * font_size
+}
+
+/// Total length of an open polyline.
+pub fn polyline_length(points: &[Vec2]) -> f32 {
+    points.windows(2).map(|w| w[0].distance(w[1])).sum()
+}
+
+/// Point and unit tangent at `distance` along an open polyline.
+/// Distances past either end are clamped to the end points.
+pub fn sample_polyline(points: &[Vec2], distance: f32) -> Option<(Vec2, Vec2)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let mut remaining = distance.max(0.0);
+    let mut last = None;
+    for w in points.windows(2) {
+        let segment = w[1] - w[0];
+        let length = segment.length();
+        if length <= f32::EPSILON {
+            continue;
+        }
+        let tangent = segment / length;
+        if remaining <= length {
+            return Some((w[0] + tangent * remaining, tangent));
+        }
+        remaining -= length;
+        last = Some((w[1], tangent));
+    }
+    last
+}
+
+/// Smooths an open polyline with repeated neighbour averaging, keeping the end points.
+pub fn smooth_path(points: &[Vec2], passes: usize) -> Vec<Vec2> {
+    let mut current = points.to_vec();
+    for _ in 0..passes {
+        if current.len() < 3 {
+            break;
+        }
+        let mut next = current.clone();
+        for i in 1..current.len() - 1 {
+            next[i] = (current[i - 1] + current[i] * 2.0 + current[i + 1]) / 4.0;
+        }
+        current = next;
+    }
+    current
+}
+
+/// Places each character of `text` along `path`, centred on the path.
+///
+/// Paths running right to left are reversed so the lettering never reads upside
+/// down. Returns an empty list if the text does not fit.
+pub fn layout_along_path(
+    path: &[Vec2],
+    text: &str,
+    font_size: f32,
+    letter_spacing: f32,
+) -> Vec<GlyphPlacement> {
+    if path.len() < 2 {
+        return Vec::new();
+    }
+    let mut path = path.to_vec();
+    if path[path.len() - 1].x < path[0].x {
+        path.reverse();
+    }
+
+    let advances: Vec<f32> = text
+        .chars()
+        .map(|ch| glyph_advance(ch, font_size) + letter_spacing)
+        .collect();
+    let text_length: f32 = advances.iter().sum::<f32>() - letter_spacing;
+    let path_length = polyline_length(&path);
+    if text_length > path_length {
+        return Vec::new();
+    }
+
+    let mut cursor = (path_length - text_length) / 2.0;
+    let mut glyphs = Vec::with_capacity(advances.len());
+    for (ch, advance) in text.chars().zip(advances) {
+        let glyph_width = advance - letter_spacing;
+        if !ch.is_whitespace() {
+            if let Some((position, tangent)) = sample_polyline(&path, cursor + glyph_width / 2.0) {
+                glyphs.push(GlyphPlacement {
+                    ch,
+                    position,
+                    rotation: tangent.to_angle(),
+                });
+            }
+        }
+        cursor += advance;
+    }
+    glyphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_polyline() {
+        let path = [Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)];
+        assert_eq!(polyline_length(&path), 20.0);
+        let (p, t) = sample_polyline(&path, 15.0).unwrap();
+        assert!((p - Vec2::new(10.0, 5.0)).length() < 1e-5);
+        assert!((t - Vec2::Y).length() < 1e-5);
+        // Clamped past the end
+        let (end, _) = sample_polyline(&path, 50.0).unwrap();
+        assert!((end - Vec2::new(10.0, 10.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_straight_layout_is_centred_and_flat() {
+        let path = [Vec2::new(-100.0, 0.0), Vec2::new(100.0, 0.0)];
+        let glyphs = layout_along_path(&path, "AA", 20.0, 0.0);
+        assert_eq!(glyphs.len(), 2);
+        assert!(glyphs.iter().all(|g| g.rotation.abs() < 1e-5 && g.position.y.abs() < 1e-5));
+        assert!((glyphs[0].position.x + glyphs[1].position.x).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_reversed_path_reads_left_to_right() {
+        let path = [Vec2::new(100.0, 0.0), Vec2::new(-100.0, 0.0)];
+        let glyphs = layout_along_path(&path, "AB", 20.0, 0.0);
+        assert!(glyphs[0].position.x < glyphs[1].position.x);
+        assert!(glyphs[0].rotation.abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_glyphs_follow_curve() {
+        // Quarter circle arc
+        let path: Vec<Vec2> = (0..=16)
+            .map(|i| Vec2::from_angle(std::f32::consts::PI * (1.0 - i as f32 / 32.0)) * 200.0)
+            .collect();
+        let glyphs = layout_along_path(&path, "Bay", 20.0, 2.0);
+        assert_eq!(glyphs.len(), 3);
+        // Rotation increases clockwise along an arc bending right
+        assert!(glyphs[0].rotation > glyphs[2].rotation);
+    }
+
+    #[test]
+    fn test_text_too_long_for_path() {
+        let path = [Vec2::ZERO, Vec2::new(10.0, 0.0)];
+        assert!(layout_along_path(&path, "Windward Coast", 20.0, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_spaces_produce_no_glyphs() {
+        let path = [Vec2::ZERO, Vec2::new(500.0, 0.0)];
+        assert_eq!(layout_along_path(&path, "A B", 20.0, 0.0).len(), 2);
+    }
+}
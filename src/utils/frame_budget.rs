@@ -0,0 +1,126 @@
+//! Frame-budgeted slicing for bursty periodic work.
+//!
+//! A `SlicedJob` walks a list of work items a slice at a time and remembers
+//! where it stopped, so the next frame resumes from there. Each slice ends
+//! once its `FrameBudget` (a wall-clock limit and an item cap) is spent, so a
+//! job that grows with the world costs a bounded amount per frame instead of
+//! spiking whenever it comes due.
+
+use std::time::Duration;
+
+use bevy::utils::Instant;
+
+/// How much work one slice of a job may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameBudget {
+    /// Wall-clock time a slice may take.
+    pub time: Duration,
+    /// Most items a slice may process, however fast they are.
+    pub max_items: usize,
+}
+
+impl FrameBudget {
+    pub const fn new(time: Duration, max_items: usize) -> Self {
+        Self { time, max_items }
+    }
+
+    /// A budget limited only by item count (deterministic, e.g. for tests).
+    pub const fn items(max_items: usize) -> Self {
+        Self::new(Duration::MAX, max_items)
+    }
+}
+
+/// Resumable cursor over a job's work items.
+///
+/// Items are addressed by index; the caller owns the list and passes its
+/// length each slice. If the list shrinks below the cursor the pass ends.
+#[derive(Debug, Default, Clone)]
+pub struct SlicedJob {
+    cursor: usize,
+    passes: u32,
+}
+
+impl SlicedJob {
+    /// Runs `step` on items from the cursor onwards until `budget` is spent
+    /// or the list ends. At least one item is processed, so a job always
+    /// makes progress. Returns true if this slice finished a pass; the
+    /// cursor then wraps back to the first item for the next pass.
+    pub fn run(&mut self, len: usize, budget: FrameBudget, mut step: impl FnMut(usize)) -> bool {
+        let started = Instant::now();
+        let mut done = 0;
+        while self.cursor < len {
+            if done > 0 && (done >= budget.max_items || started.elapsed() >= budget.time) {
+                return false;
+            }
+            step(self.cursor);
+            self.cursor += 1;
+            done += 1;
+        }
+        self.cursor = 0;
+        self.passes += 1;
+        true
+    }
+
+    /// Whether a pass has started but not yet finished.
+    pub fn in_progress(&self) -> bool {
+        self.cursor > 0
+    }
+
+    /// Index of the next item to process.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Number of passes completed.
+    pub fn passes(&self) -> u32 {
+        self.passes
+    }
+
+    /// Abandons the current pass; the next slice starts from the first item.
+    pub fn restart(&mut self) {
+        self.cursor = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_resumes_where_it_stopped() {
+        let mut job = SlicedJob::default();
+        let mut seen = Vec::new();
+
+        assert!(!job.run(5, FrameBudget::items(2), |i| seen.push(i)));
+        assert!(job.in_progress());
+        assert!(!job.run(5, FrameBudget::items(2), |i| seen.push(i)));
+        assert!(job.run(5, FrameBudget::items(2), |i| seen.push(i)));
+
+        assert_eq!(seen, vec![0, 1, 2, 3, 4]);
+        assert_eq!(job.passes(), 1);
+        assert_eq!(job.cursor(), 0);
+    }
+
+    #[test]
+    fn test_job_always_makes_progress() {
+        let mut job = SlicedJob::default();
+        let mut count = 0;
+        let budget = FrameBudget::new(Duration::ZERO, 0);
+        job.run(3, budget, |_| count += 1);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_shrunk_list_ends_pass() {
+        let mut job = SlicedJob::default();
+        job.run(10, FrameBudget::items(6), |_| {});
+        assert!(job.run(4, FrameBudget::items(6), |_| panic!("past the end")));
+        assert!(!job.in_progress());
+    }
+
+    #[test]
+    fn test_empty_list_finishes_immediately() {
+        let mut job = SlicedJob::default();
+        assert!(job.run(0, FrameBudget::items(4), |_| unreachable!()));
+    }
+}
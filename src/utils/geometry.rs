@@ -24,8 +24,9 @@ pub fn extract_contours(map_data: &MapData, tile_size: f32) -> Vec<CoastlinePoly
     let height = map_data.height as i32;
     
     // Track which edge segments we've already visited
-    // Key: (tile_x, tile_y, edge_direction) where edge_direction is 0=N, 1=E, 2=S, 3=W
-    let mut visited_edges: std::collections::HashSet<(i32, i32, u8)> = std::collections::HashSet::new();
+    // Flat grid indexed by (tile_x, tile_y, edge_direction) where edge_direction is 0=N, 1=E, 2=S, 3=W.
+    // A flat Vec stays cheap on 1024x1024 maps where a HashSet of edges would not.
+    let mut visited_edges = vec![false; (width * height * 4) as usize];
     
     let mut polygons = Vec::new();
     
@@ -51,7 +52,7 @@ pub fn extract_contours(map_data: &MapData, tile_size: f32) -> Vec<CoastlinePoly
                     is_land(map_data.tile(nx as u32, ny as u32).map(|t| t.tile_type).unwrap_or(TileType::DeepWater))
                 };
                 
-                if neighbor_is_land && !visited_edges.contains(&(x, y, *dir)) {
+                if neighbor_is_land && !visited_edges[edge_index(x, y, *dir, width)] {
                     // Start tracing a new contour from this edge
                     if let Some(polygon) = trace_contour(map_data, x, y, *dir, tile_size, &mut visited_edges) {
                         if polygon.points.len() >= 3 {
@@ -75,7 +76,7 @@ fn trace_contour(
     start_y: i32,
     start_dir: u8,
     tile_size: f32,
-    visited: &mut std::collections::HashSet<(i32, i32, u8)>,
+    visited: &mut [bool],
 ) -> Option<CoastlinePolygon> {
     let width = map_data.width as i32;
     let height = map_data.height as i32;
@@ -109,7 +110,7 @@ fn trace_contour(
 
     for _ in 0..max_iterations {
         // Mark current edge as visited
-        visited.insert((x, y, l_dir));
+        visited[edge_index(x, y, l_dir, width)] = true;
         
         // Add point
         let (mx, my) = edge_midpoints[l_dir as usize];
@@ -178,6 +179,11 @@ fn trace_contour(
     }
 }
 
+/// Index of a tile edge in the flat visited-edge grid used by contour tracing.
+fn edge_index(x: i32, y: i32, dir: u8, width: i32) -> usize {
+    ((y * width + x) * 4 + dir as i32) as usize
+}
+
 /// Returns true if the tile is considered "water" for coastline purposes.
 fn is_water(tile: TileType) -> bool {
     matches!(tile, TileType::DeepWater | TileType::ShallowWater)
@@ -599,6 +605,11 @@ fn build_raw_navmesh_data(
         .map(|(i, v)| (v.fix(), i))
         .collect();
     
+    // Bounding boxes let the centroid tests skip distant polygons cheaply;
+    // without them the filter is O(triangles x coastline points).
+    let obstacle_bounds: Vec<Rect> = obstacle_polygons.iter().map(|p| polygon_bounds(p)).collect();
+    let land_bounds: Vec<Rect> = land_polygons.iter().map(|p| polygon_bounds(&p.points)).collect();
+
     // Extract triangles, filtering out those inside obstacles
     let mut triangles: Vec<[usize; 3]> = Vec::new();
 
@@ -616,13 +627,15 @@ fn build_raw_navmesh_data(
         // Check if centroid is inside any obstacle polygon (offset land)
         let inside_obstacle = obstacle_polygons
             .iter()
-            .any(|obs| point_in_polygon(centroid, obs));
+            .zip(&obstacle_bounds)
+            .any(|(obs, bounds)| bounds.contains(centroid) && point_in_polygon(centroid, obs));
 
         // Also check if centroid is inside any ORIGINAL polygon (unoffset land)
         // Use filtered land_polygons to exclude border polygon
         let inside_original_land = land_polygons
             .iter()
-            .any(|poly| point_in_polygon(centroid, &poly.points));
+            .zip(&land_bounds)
+            .any(|(poly, bounds)| bounds.contains(centroid) && point_in_polygon(centroid, &poly.points));
 
         if !inside_obstacle && !inside_original_land {
             triangles.push([i0, i1, i2]);
@@ -636,6 +649,14 @@ fn build_raw_navmesh_data(
     Some(RawNavMeshData { vertices, triangles })
 }
 
+/// Axis-aligned bounding box of a polygon.
+fn polygon_bounds(points: &[Vec2]) -> Rect {
+    points.iter().fold(
+        Rect { min: Vec2::splat(f32::MAX), max: Vec2::splat(f32::MIN) },
+        |rect, p| Rect { min: rect.min.min(*p), max: rect.max.max(*p) },
+    )
+}
+
 /// Tests if a point is inside a polygon using ray casting.
 fn point_in_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
     if polygon.len() < 3 {
@@ -665,6 +686,16 @@ fn point_in_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_polygon_bounds() {
+        let points = vec![Vec2::new(-2.0, 1.0), Vec2::new(3.0, -4.0), Vec2::new(0.5, 6.0)];
+        let bounds = polygon_bounds(&points);
+        assert_eq!(bounds.min, Vec2::new(-2.0, -4.0));
+        assert_eq!(bounds.max, Vec2::new(3.0, 6.0));
+        assert!(bounds.contains(Vec2::ZERO));
+        assert!(!bounds.contains(Vec2::new(4.0, 0.0)));
+    }
+
     #[test]
     fn test_catmull_rom_smoothing() {
         // Square
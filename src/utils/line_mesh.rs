@@ -0,0 +1,203 @@
+//! Batched line meshes for cartographic ink strokes.
+//!
+//! Builds thick polylines as triangle strips with per-vertex colors so that
+//! thousands of coastline, waterline and elevation strokes render as a single
+//! `Mesh2d` (drawn with a white `ColorMaterial`, which multiplies vertex colors).
+
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+
+/// Longest miter allowed at sharp corners, as a multiple of half the stroke width.
+const MITER_LIMIT: f32 = 2.0;
+
+/// Accumulates stroked polylines into one triangle mesh.
+#[derive(Default)]
+pub struct LineMeshBuilder {
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl LineMeshBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stroked polyline with mitered joins.
+    /// Closed polylines also join the last point back to the first.
+    pub fn add_polyline(&mut self, points: &[Vec2], closed: bool, width: f32, color: Color) {
+        let styled: Vec<(Vec2, f32, Color)> = points.iter().map(|&p| (p, width, color)).collect();
+        self.add_stroke(&styled, closed);
+    }
+
+    /// Adds an open polyline whose width and color vary per point, e.g. a
+    /// tapering trail. `widths` and `colors` must match `points` in length.
+    pub fn add_ribbon(&mut self, points: &[Vec2], widths: &[f32], colors: &[Color]) {
+        let styled: Vec<(Vec2, f32, Color)> = points
+            .iter()
+            .zip(widths)
+            .zip(colors)
+            .map(|((&p, &w), &c)| (p, w, c))
+            .collect();
+        self.add_stroke(&styled, false);
+    }
+
+    fn add_stroke(&mut self, points: &[(Vec2, f32, Color)], closed: bool) {
+        // Drop repeated points, which have no direction to stroke along
+        let mut pts: Vec<(Vec2, f32, Color)> = Vec::with_capacity(points.len());
+        for &point in points {
+            if pts.last().is_none_or(|last| last.0.distance_squared(point.0) > 1e-6) {
+                pts.push(point);
+            }
+        }
+        if closed && pts.len() > 2 && pts[0].0.distance_squared(pts[pts.len() - 1].0) <= 1e-6 {
+            pts.pop();
+        }
+        if pts.len() < 2 {
+            return;
+        }
+
+        let n = pts.len();
+        let base = self.positions.len() as u32;
+
+        for i in 0..n {
+            let (p, width, color) = pts[i];
+            let half_width = width / 2.0;
+            let dir_in = if i > 0 {
+                Some((p - pts[i - 1].0).normalize())
+            } else if closed {
+                Some((p - pts[n - 1].0).normalize())
+            } else {
+                None
+            };
+            let dir_out = if i + 1 < n {
+                Some((pts[i + 1].0 - p).normalize())
+            } else if closed {
+                Some((pts[0].0 - p).normalize())
+            } else {
+                None
+            };
+
+            let offset = match (dir_in, dir_out) {
+                (Some(a), Some(b)) => {
+                    let (na, nb) = (a.perp(), b.perp());
+                    let miter = (na + nb).normalize_or(nb);
+                    let scale = (1.0 / miter.dot(nb).max(1e-3)).min(MITER_LIMIT);
+                    miter * half_width * scale
+                }
+                (Some(d), None) | (None, Some(d)) => d.perp() * half_width,
+                (None, None) => Vec2::ZERO,
+            };
+
+            let rgba = color.to_linear().to_f32_array();
+            self.positions.push([p.x + offset.x, p.y + offset.y, 0.0]);
+            self.positions.push([p.x - offset.x, p.y - offset.y, 0.0]);
+            self.colors.push(rgba);
+            self.colors.push(rgba);
+        }
+
+        let segments = if closed { n } else { n - 1 };
+        for i in 0..segments {
+            let a = base + 2 * i as u32;
+            let b = base + 2 * ((i + 1) % n) as u32;
+            self.indices.extend_from_slice(&[a, a + 1, b, a + 1, b + 1, b]);
+        }
+    }
+
+    /// Number of vertices added so far.
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns true if no strokes have been added.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Builds the render-world mesh.
+    pub fn build(self) -> Mesh {
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::RENDER_WORLD)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, self.colors)
+            .with_inserted_indices(Indices::U32(self.indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_polyline_counts() {
+        let mut builder = LineMeshBuilder::new();
+        builder.add_polyline(
+            &[Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)],
+            false,
+            2.0,
+            Color::BLACK,
+        );
+        assert_eq!(builder.vertex_count(), 6);
+        assert_eq!(builder.indices.len(), 2 * 6);
+    }
+
+    #[test]
+    fn test_closed_polyline_wraps() {
+        let mut builder = LineMeshBuilder::new();
+        let square = [
+            Vec2::ZERO,
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(0.0, 10.0),
+            Vec2::ZERO, // Explicit closing point is dropped
+        ];
+        builder.add_polyline(&square, true, 2.0, Color::BLACK);
+        assert_eq!(builder.vertex_count(), 8);
+        assert_eq!(builder.indices.len(), 4 * 6);
+        assert!(builder.indices.iter().all(|&i| (i as usize) < builder.vertex_count()));
+    }
+
+    #[test]
+    fn test_degenerate_polyline_is_skipped() {
+        let mut builder = LineMeshBuilder::new();
+        builder.add_polyline(&[Vec2::ONE, Vec2::ONE], false, 2.0, Color::BLACK);
+        assert!(builder.is_empty());
+    }
+
+    #[test]
+    fn test_right_angle_miter_width() {
+        let mut builder = LineMeshBuilder::new();
+        builder.add_polyline(
+            &[Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)],
+            false,
+            2.0,
+            Color::BLACK,
+        );
+        // Corner vertex offset is half_width * sqrt(2) along the bisector
+        let corner = Vec2::new(builder.positions[2][0], builder.positions[2][1]);
+        assert!((corner.distance(Vec2::new(10.0, 0.0)) - std::f32::consts::SQRT_2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_build_has_colors() {
+        let mut builder = LineMeshBuilder::new();
+        builder.add_polyline(&[Vec2::ZERO, Vec2::X], false, 1.0, Color::srgba(0.2, 0.15, 0.1, 0.5));
+        let mesh = builder.build();
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_COLOR).is_some());
+        assert_eq!(mesh.count_vertices(), 4);
+    }
+
+    #[test]
+    fn test_ribbon_varies_width() {
+        let mut builder = LineMeshBuilder::new();
+        builder.add_ribbon(
+            &[Vec2::ZERO, Vec2::new(10.0, 0.0)],
+            &[2.0, 6.0],
+            &[Color::BLACK, Color::NONE],
+        );
+        assert_eq!(builder.vertex_count(), 4);
+        assert!((builder.positions[0][1] - 1.0).abs() < 1e-5);
+        assert!((builder.positions[2][1] - 3.0).abs() < 1e-5);
+        assert_eq!(builder.colors[3][3], 0.0);
+    }
+}
@@ -0,0 +1,61 @@
+//! Line of sight across a battle arena.
+//!
+//! Anything that blocks sight (a rock of the coast, a cloud of gun smoke) is
+//! taken as a circle. A sightline is blocked by a circle it passes through,
+//! unless the one looking is inside that circle: a gun crew can still see
+//! out through the smoke of its own broadside, but nobody can see in.
+
+use bevy::prelude::*;
+
+/// Whether the straight line from `from` to `to` passes within `radius` of `centre`.
+pub fn segment_meets_circle(from: Vec2, to: Vec2, centre: Vec2, radius: f32) -> bool {
+    let line = to - from;
+    let along = if line.length_squared() > f32::EPSILON {
+        ((centre - from).dot(line) / line.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (from + line * along).distance_squared(centre) < radius * radius
+}
+
+/// The first of `blocks` (centre, radius) standing between `from` and `to`,
+/// as its index; `None` if the way is clear.
+pub fn first_obstruction(from: Vec2, to: Vec2, blocks: impl IntoIterator<Item = (Vec2, f32)>) -> Option<usize> {
+    blocks.into_iter().position(|(centre, radius)| {
+        from.distance_squared(centre) >= radius * radius && segment_meets_circle(from, to, centre, radius)
+    })
+}
+
+/// Whether a ship at `from` can see `to` past `blocks` (centre, radius).
+pub fn line_of_sight(from: Vec2, to: Vec2, blocks: impl IntoIterator<Item = (Vec2, f32)>) -> bool {
+    first_obstruction(from, to, blocks).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cloud_between_blocks_sight() {
+        let cloud = [(Vec2::new(100.0, 0.0), 30.0)];
+        assert!(!line_of_sight(Vec2::ZERO, Vec2::new(200.0, 0.0), cloud));
+        // Passing wide of it, or stopping short of it
+        assert!(line_of_sight(Vec2::ZERO, Vec2::new(200.0, 100.0), cloud));
+        assert!(line_of_sight(Vec2::ZERO, Vec2::new(50.0, 0.0), cloud));
+    }
+
+    #[test]
+    fn test_ship_inside_cloud_sees_out_but_is_not_seen() {
+        let cloud = [(Vec2::ZERO, 50.0)];
+        let outside = Vec2::new(200.0, 0.0);
+        assert!(line_of_sight(Vec2::new(10.0, 0.0), outside, cloud));
+        assert!(!line_of_sight(outside, Vec2::new(10.0, 0.0), cloud));
+    }
+
+    #[test]
+    fn test_first_obstruction_names_the_block() {
+        let blocks = [(Vec2::new(0.0, 500.0), 40.0), (Vec2::new(100.0, 0.0), 40.0)];
+        assert_eq!(first_obstruction(Vec2::ZERO, Vec2::new(200.0, 0.0), blocks), Some(1));
+        assert_eq!(first_obstruction(Vec2::ZERO, Vec2::new(0.0, -200.0), blocks), None);
+    }
+}
@@ -4,3 +4,8 @@ pub mod procgen;
 pub mod pathfinding;
 pub mod spatial_hash;
 pub mod geometry;
+pub mod line_mesh;
+pub mod curved_text;
+pub mod frame_budget;
+pub mod path_smoothing;
+pub mod line_of_sight;
@@ -0,0 +1,170 @@
+//! Post-processing for waypoint paths, so ships sail believable courses.
+//!
+//! Tile paths come out as runs of tile centres with sharp corners between
+//! them. `smooth_course` first string-pulls the path, dropping every
+//! waypoint the ship can see past, then blends each remaining corner into
+//! an arc of the ship's turning radius (see `ShipType::turning_radius`),
+//! cut tighter where the legs are too short for the full circle. Arcs that
+//! would cross land keep their sharp corner.
+
+use bevy::prelude::*;
+
+use crate::resources::MapData;
+use crate::utils::pathfinding::world_to_tile;
+
+/// Step at which a straight leg is sampled for land (world units; a quarter tile).
+const SIGHT_STEP: f32 = 16.0;
+/// Points each corner arc is drawn with.
+pub const ARC_SEGMENTS: usize = 6;
+/// Corners turning less than this are left alone (radians).
+const MIN_CORNER_TURN: f32 = 0.05;
+
+/// Whether the straight leg from `from` to `to` stays on navigable water.
+pub fn leg_is_clear(from: Vec2, to: Vec2, map_data: &MapData) -> bool {
+    let steps = (from.distance(to) / SIGHT_STEP).ceil().max(1.0) as usize;
+    (0..=steps).all(|i| {
+        let tile = world_to_tile(from.lerp(to, i as f32 / steps as f32), map_data.width, map_data.height);
+        tile.x >= 0 && tile.y >= 0 && map_data.is_navigable(tile.x as u32, tile.y as u32)
+    })
+}
+
+/// Drops every waypoint that can be sailed past in a straight line from the
+/// last one kept. `waypoints` excludes `start`; so does the result, which
+/// always ends at the final waypoint.
+pub fn string_pull(start: Vec2, waypoints: &[Vec2], clear: impl Fn(Vec2, Vec2) -> bool) -> Vec<Vec2> {
+    let mut pulled = Vec::new();
+    let mut anchor = start;
+    let mut index = 0;
+    while index < waypoints.len() {
+        // Furthest waypoint in sight of the anchor; the next one is always taken
+        let mut furthest = index;
+        for (ahead, &waypoint) in waypoints.iter().enumerate().skip(index + 1) {
+            if clear(anchor, waypoint) {
+                furthest = ahead;
+            }
+        }
+        anchor = waypoints[furthest];
+        pulled.push(anchor);
+        index = furthest + 1;
+    }
+    pulled
+}
+
+/// Replaces the corner at `corner`, between legs from `before` and to `after`,
+/// with an arc of up to `radius`. Returns the arc's points, or `None` if the
+/// corner is too shallow to need one.
+pub fn corner_arc(before: Vec2, corner: Vec2, after: Vec2, radius: f32, segments: usize) -> Option<Vec<Vec2>> {
+    let back = (before - corner).normalize_or_zero();
+    let ahead = (after - corner).normalize_or_zero();
+    if back == Vec2::ZERO || ahead == Vec2::ZERO || radius <= 0.0 {
+        return None;
+    }
+    // Angle between the two legs at the corner; a straight run is PI
+    let opening = back.dot(ahead).clamp(-1.0, 1.0).acos();
+    if !(1e-3..=std::f32::consts::PI - MIN_CORNER_TURN).contains(&opening) {
+        return None;
+    }
+
+    let half = opening / 2.0;
+    // Tangent points may use no more than half of either leg
+    let reach = (radius / half.tan())
+        .min(before.distance(corner) / 2.0)
+        .min(after.distance(corner) / 2.0);
+    let radius = reach * half.tan();
+    let entry = corner + back * reach;
+    let exit = corner + ahead * reach;
+    let centre = corner + (back + ahead).normalize() * (radius / half.sin());
+
+    let from = entry - centre;
+    let to = exit - centre;
+    let sweep = from.perp_dot(to).atan2(from.dot(to));
+    let start_angle = from.to_angle();
+    let segments = segments.max(1);
+    Some(
+        (0..=segments)
+            .map(|i| centre + Vec2::from_angle(start_angle + sweep * i as f32 / segments as f32) * radius)
+            .collect(),
+    )
+}
+
+/// Blends each corner of the path into an arc of up to `radius`, unless the
+/// arc would leave clear water. `waypoints` excludes `start`.
+pub fn blend_corners(start: Vec2, waypoints: &[Vec2], radius: f32, clear: impl Fn(Vec2, Vec2) -> bool) -> Vec<Vec2> {
+    let mut course = Vec::with_capacity(waypoints.len() * (ARC_SEGMENTS + 1));
+    for (index, &corner) in waypoints.iter().enumerate() {
+        let Some(&after) = waypoints.get(index + 1) else {
+            course.push(corner);
+            break;
+        };
+        let before = if index == 0 { start } else { waypoints[index - 1] };
+        let arc = corner_arc(before, corner, after, radius, ARC_SEGMENTS)
+            .filter(|arc| arc.windows(2).all(|leg| clear(leg[0], leg[1])));
+        match arc {
+            Some(arc) => course.extend(arc),
+            None => course.push(corner),
+        }
+    }
+    course
+}
+
+/// String-pulls a path over the map and rounds its corners to `turning_radius`.
+pub fn smooth_course(start: Vec2, waypoints: &[Vec2], turning_radius: f32, map_data: &MapData) -> Vec<Vec2> {
+    let clear = |from: Vec2, to: Vec2| leg_is_clear(from, to, map_data);
+    let pulled = string_pull(start, waypoints, clear);
+    blend_corners(start, &pulled, turning_radius, clear)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_pull_drops_visible_waypoints() {
+        let waypoints = [Vec2::new(10.0, 0.0), Vec2::new(20.0, 0.0), Vec2::new(20.0, 10.0), Vec2::new(30.0, 10.0)];
+        // Open water: straight to the end
+        assert_eq!(string_pull(Vec2::ZERO, &waypoints, |_, _| true), vec![Vec2::new(30.0, 10.0)]);
+        // Nothing can be seen past: every waypoint kept
+        assert_eq!(string_pull(Vec2::ZERO, &waypoints, |_, _| false), waypoints.to_vec());
+    }
+
+    #[test]
+    fn test_corner_arc_is_tangent_to_both_legs() {
+        let corner = Vec2::new(100.0, 0.0);
+        let arc = corner_arc(Vec2::ZERO, corner, Vec2::new(100.0, 100.0), 20.0, 4).unwrap();
+        // A right angle of radius 20 starts and ends 20 short of the corner
+        assert!(arc.first().unwrap().distance(Vec2::new(80.0, 0.0)) < 1e-3);
+        assert!(arc.last().unwrap().distance(Vec2::new(100.0, 20.0)) < 1e-3);
+        let centre = Vec2::new(80.0, 20.0);
+        assert!(arc.iter().all(|point| (point.distance(centre) - 20.0).abs() < 1e-3));
+
+        // Straight on: no arc
+        assert!(corner_arc(Vec2::ZERO, corner, Vec2::new(200.0, 0.0), 20.0, 4).is_none());
+    }
+
+    #[test]
+    fn test_tight_corners_are_cut_to_fit_the_legs() {
+        let arc = corner_arc(Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0), 100.0, 4).unwrap();
+        assert!(arc.first().unwrap().distance(Vec2::new(5.0, 0.0)) < 1e-3);
+        assert!(arc.last().unwrap().distance(Vec2::new(10.0, 5.0)) < 1e-3);
+    }
+
+    #[test]
+    fn test_course_keeps_corner_round_land() {
+        use crate::resources::TileType;
+        use crate::utils::pathfinding::tile_to_world;
+
+        let mut map = MapData::new(10, 10);
+        map.set_type(5, 5, TileType::Land);
+        let start = tile_to_world(IVec2::new(4, 5), 10, 10);
+        let corner = tile_to_world(IVec2::new(4, 6), 10, 10);
+        let end = tile_to_world(IVec2::new(6, 6), 10, 10);
+
+        let course = smooth_course(start, &[corner, end], 48.0, &map);
+        assert_eq!(*course.last().unwrap(), end);
+        let mut from = start;
+        for &point in &course {
+            assert!(leg_is_clear(from, point, &map));
+            from = point;
+        }
+    }
+}
@@ -7,7 +7,7 @@ use bevy::prelude::*;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
 
-use crate::resources::MapData;
+use crate::resources::{MapData, Wind};
 
 /// Wrapper for f32 that implements Ord for use in BinaryHeap.
 /// Uses total ordering where NaN is treated as greater than all other values.
@@ -78,6 +78,13 @@ impl PartialOrd for Node {
 /// # Returns
 /// `Some(Vec<IVec2>)` with path from start to goal (inclusive), or `None` if no path exists.
 pub fn find_path(start: IVec2, goal: IVec2, map_data: &MapData) -> Option<Vec<IVec2>> {
+    find_path_in_wind(start, goal, map_data, None)
+}
+
+/// Finds a path like `find_path`, but with each leg's cost scaled by
+/// `Wind::route_cost_multiplier`, so the route tacks around a headwind
+/// instead of beating straight into it.
+pub fn find_path_in_wind(start: IVec2, goal: IVec2, map_data: &MapData, wind: Option<&Wind>) -> Option<Vec<IVec2>> {
     // Early exit if goal is not navigable
     if !map_data.is_navigable(goal.x as u32, goal.y as u32) {
         return None;
@@ -128,6 +135,7 @@ pub fn find_path(start: IVec2, goal: IVec2, map_data: &MapData) -> Option<Vec<IV
                 parent,
                 &g_score,
                 map_data,
+                wind,
             );
 
             let current_g = g_score.get(&neighbor).copied().unwrap_or(OrderedF32::new(f32::INFINITY));
@@ -152,32 +160,43 @@ pub fn find_path(start: IVec2, goal: IVec2, map_data: &MapData) -> Option<Vec<IV
 /// Computes the cost to reach a neighbor, implementing Theta* path selection.
 ///
 /// Path 2 (Theta* shortcut): If parent has line-of-sight to neighbor, connect directly.
-/// Path 1 (Standard A*): Otherwise, go through current node.
+/// Path 1 (Standard A*): Go through current node.
+/// The cheaper of the two is taken: a shortcut is longer than the turn it
+/// cuts when it runs straight into the wind.
 fn compute_cost(
     current: IVec2,
     neighbor: IVec2,
     parent: Option<IVec2>,
     g_score: &HashMap<IVec2, OrderedF32>,
     map_data: &MapData,
+    wind: Option<&Wind>,
 ) -> (OrderedF32, IVec2) {
     // Apply coastal penalty: 5x cost for water tiles adjacent to land
     let coastal_multiplier = if is_coastal(neighbor, map_data) { 5.0 } else { 1.0 };
 
-    // Try Path 2: direct connection from parent to neighbor
+    // Path 1: standard A* through current node
+    let current_g = g_score.get(&current).copied().unwrap_or(OrderedF32::new(0.0));
+    let base_cost = euclidean_distance(current, neighbor) * wind_multiplier(current, neighbor, wind);
+    let through_current = (current_g + OrderedF32::new(base_cost * coastal_multiplier), current);
+
+    // Path 2: direct connection from parent to neighbor
     if let Some(parent_pos) = parent {
         if line_of_sight(parent_pos, neighbor, map_data) {
             let parent_g = g_score.get(&parent_pos).copied().unwrap_or(OrderedF32::new(0.0));
-            let base_cost = euclidean_distance(parent_pos, neighbor);
+            let base_cost = euclidean_distance(parent_pos, neighbor) * wind_multiplier(parent_pos, neighbor, wind);
             let cost = parent_g + OrderedF32::new(base_cost * coastal_multiplier);
-            return (cost, parent_pos);
+            if cost <= through_current.0 {
+                return (cost, parent_pos);
+            }
         }
     }
 
-    // Path 1: standard A* through current node
-    let current_g = g_score.get(&current).copied().unwrap_or(OrderedF32::new(0.0));
-    let base_cost = euclidean_distance(current, neighbor);
-    let cost = current_g + OrderedF32::new(base_cost * coastal_multiplier);
-    (cost, current)
+    through_current
+}
+
+/// Wind penalty for sailing the leg from `from` to `to` (1.0 without wind).
+fn wind_multiplier(from: IVec2, to: IVec2, wind: Option<&Wind>) -> f32 {
+    wind.map_or(1.0, |wind| wind.route_cost_multiplier((to - from).as_vec2()))
 }
 
 /// Checks if a water tile is "coastal" (adjacent to any non-navigable tile).
@@ -369,29 +388,24 @@ fn reconstruct_path(came_from: &HashMap<IVec2, IVec2>, mut current: IVec2) -> Ve
     path
 }
 
+/// World units per map tile.
+pub const TILE_SIZE: f32 = 64.0;
+
+/// Half the size in world units of a map `map_width` by `map_height` tiles,
+/// which is centered at the origin.
+pub fn map_half_extents(map_width: u32, map_height: u32) -> Vec2 {
+    Vec2::new(map_width as f32, map_height as f32) * TILE_SIZE / 2.0
+}
+
 /// Converts a tile position to world coordinates.
-/// Assumes 64x64 tile size and map centered at origin.
+/// Assumes `TILE_SIZE` tiles and map centered at origin.
 pub fn tile_to_world(tile_pos: IVec2, map_width: u32, map_height: u32) -> Vec2 {
-    let tile_size = 64.0;
-    let offset_x = (map_width as f32 * tile_size) / 2.0;
-    let offset_y = (map_height as f32 * tile_size) / 2.0;
-
-    Vec2::new(
-        tile_pos.x as f32 * tile_size - offset_x + tile_size / 2.0,
-        tile_pos.y as f32 * tile_size - offset_y + tile_size / 2.0,
-    )
+    tile_pos.as_vec2() * TILE_SIZE - map_half_extents(map_width, map_height) + Vec2::splat(TILE_SIZE / 2.0)
 }
 
 /// Converts world coordinates to a tile position.
 pub fn world_to_tile(world_pos: Vec2, map_width: u32, map_height: u32) -> IVec2 {
-    let tile_size = 64.0;
-    let offset_x = (map_width as f32 * tile_size) / 2.0;
-    let offset_y = (map_height as f32 * tile_size) / 2.0;
-
-    IVec2::new(
-        ((world_pos.x + offset_x) / tile_size).floor() as i32,
-        ((world_pos.y + offset_y) / tile_size).floor() as i32,
-    )
+    ((world_pos + map_half_extents(map_width, map_height)) / TILE_SIZE).floor().as_ivec2()
 }
 
 #[cfg(test)]
@@ -464,6 +478,19 @@ mod tests {
         assert!(path.len() <= 3, "Expected short path, got {} waypoints", path.len());
     }
 
+    #[test]
+    fn test_path_tacks_into_headwind() {
+        let map = MapData::new(30, 30); // All water
+        // A stiff breeze blowing East, straight against a westward passage
+        let wind = Wind { direction: 0.0, strength: 1.0 };
+        let path = find_path_in_wind(IVec2::new(25, 15), IVec2::new(5, 15), &map, Some(&wind)).unwrap();
+        assert!(path.len() > 2, "Expected the route to tack, got {:?}", path);
+        assert!(path.iter().any(|pos| pos.y != 15));
+        // With the wind abeam she holds her course, near enough
+        let path = find_path_in_wind(IVec2::new(15, 5), IVec2::new(15, 25), &map, Some(&wind)).unwrap();
+        assert!(path.iter().all(|pos| (pos.x - 15).abs() <= 2), "Expected a straight reach, got {:?}", path);
+    }
+
     #[test]
     fn test_corner_cutting_prevention() {
         let mut map = MapData::new(10, 10);
@@ -274,8 +274,7 @@ fn add_coastal_transitions(map_data: &mut MapData) {
                 if tile.tile_type == TileType::DeepWater {
                     // Check if adjacent to land, hills, mountains, or sand
                     let has_land_neighbor = neighbors_4(x, y, width, height)
-                        .iter()
-                        .any(|&(nx, ny)| {
+                        .any(|(nx, ny)| {
                             map_data.tile(nx, ny).map(|t| t.tile_type).map_or(false, |t| matches!(
                                 t,
                                 TileType::Land | TileType::Sand | TileType::Hills | TileType::Mountains
@@ -313,16 +312,14 @@ fn place_ports(map_data: &mut MapData, min_ports: usize, max_ports: usize, seed:
         for x in 0..width {
             if let Some(tile) = map_data.tile(x, y) {
                 if tile.tile_type == TileType::Sand {
-                    let neighbors = neighbors_4(x, y, width, height);
-                    
-                    let has_land = neighbors.iter().any(|&(nx, ny)| {
+                    let has_land = neighbors_4(x, y, width, height).any(|(nx, ny)| {
                         map_data.tile(nx, ny).map(|t| t.tile_type).map_or(false, |t| matches!(
                             t,
                             TileType::Land | TileType::Hills | TileType::Mountains
                         ))
                     });
                     
-                    let has_water = neighbors.iter().any(|&(nx, ny)| {
+                    let has_water = neighbors_4(x, y, width, height).any(|(nx, ny)| {
                         map_data.tile(nx, ny).map(|t| t.tile_type).map_or(false, |t| matches!(
                             t,
                             TileType::DeepWater | TileType::ShallowWater
@@ -374,24 +371,17 @@ fn place_ports(map_data: &mut MapData, min_ports: usize, max_ports: usize, seed:
     bevy::log::info!("Placed {} ports on the map", placed_ports.len());
 }
 
-/// Returns the 4-directional neighbors of a tile (N, S, E, W).
-fn neighbors_4(x: u32, y: u32, width: u32, height: u32) -> Vec<(u32, u32)> {
-    let mut result = Vec::with_capacity(4);
-
-    if x > 0 {
-        result.push((x - 1, y));
-    }
-    if x < width - 1 {
-        result.push((x + 1, y));
-    }
-    if y > 0 {
-        result.push((x, y - 1));
-    }
-    if y < height - 1 {
-        result.push((x, y + 1));
-    }
-
-    result
+/// Returns the in-bounds 4-directional neighbors of a tile (W, E, S, N).
+/// Allocation-free, since generation calls this several times per tile.
+fn neighbors_4(x: u32, y: u32, width: u32, height: u32) -> impl Iterator<Item = (u32, u32)> {
+    [
+        (x > 0).then(|| (x - 1, y)),
+        (x + 1 < width).then_some((x + 1, y)),
+        (y > 0).then(|| (x, y - 1)),
+        (y + 1 < height).then_some((x, y + 1)),
+    ]
+    .into_iter()
+    .flatten()
 }
 
 #[cfg(test)]
@@ -414,6 +404,14 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_neighbors_4_respects_bounds() {
+        assert_eq!(neighbors_4(0, 0, 4, 4).count(), 2);
+        assert_eq!(neighbors_4(3, 1, 4, 4).count(), 3);
+        assert_eq!(neighbors_4(1, 1, 4, 4).count(), 4);
+        assert!(neighbors_4(3, 3, 4, 4).all(|(x, y)| x < 4 && y < 4));
+    }
+
     #[test]
     fn test_same_seed_produces_same_map() {
         let config1 = MapGenConfig {